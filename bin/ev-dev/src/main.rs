@@ -5,18 +5,32 @@
 
 #![allow(missing_docs, rustdoc::missing_crate_level_docs)]
 
-use alloy_signer_local::{coins_bip39::English, MnemonicBuilder};
+use alloy_primitives::{Address, B256, U256};
+use alloy_signer_local::{coins_bip39::English, MnemonicBuilder, PrivateKeySigner};
 use clap::Parser;
 use ev_deployer::{config::DeployConfig, genesis::merge_alloc, output::build_manifest};
+use ev_precompiles::mint::MINT_PRECOMPILE_ADDR;
 use evolve_ev_reth::{
     config::EvolveConfig,
-    rpc::txpool::{EvolveTxpoolApiImpl, EvolveTxpoolApiServer},
+    rpc::{
+        fees::{EvolveFeeApiImpl, EvolveFeeApiServer, EvolveFeeDefaultsApiServer},
+        txpool::{EvolveTxpoolApiImpl, EvolveTxpoolApiServer},
+        wallet::{WalletCallsApiImpl, WalletCallsApiServer},
+    },
 };
 use reth_ethereum_cli::Cli;
-use std::{io::Write, path::PathBuf};
+use std::{io::Write, path::PathBuf, sync::Arc, time::Duration};
 use tracing::info;
 
-use ev_node::{EvolveArgs, EvolveChainSpecParser, EvolveNode};
+use ev_node::{
+    faucet::{EvolveFaucetApiImpl, EvolveFaucetApiServer, Faucet, FaucetConfig, FaucetFunding},
+    fee_estimator::run_fee_estimator,
+    send_transaction::{
+        EvNodeSendTransactionApiImpl, EvNodeSendTransactionApiServer, ExecutorSigner,
+    },
+    txpool::txpool_limits,
+    EvolveArgs, EvolveChainSpecParser, EvolveNode, ResolvedEvolveConfig,
+};
 
 #[global_allocator]
 static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::new_allocator();
@@ -24,6 +38,16 @@ static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::ne
 const DEVNET_GENESIS: &str = include_str!("../assets/devnet-genesis.json");
 const HARDHAT_MNEMONIC: &str = "test test test test test test test test test test test junk";
 
+/// Mnemonic index of the account `evolve_requestFunds` mints from. It is the last pre-funded
+/// account, so it can pay for gas, and it is allowlisted in the mint precompile at genesis.
+const FAUCET_ACCOUNT_INDEX: u32 = 19;
+/// Mnemonic index of the account `evnode_sendTransaction` signs batches with.
+const EXECUTOR_ACCOUNT_INDEX: u32 = 18;
+/// Wei sent by a faucet request that does not ask for an amount (100 ETH).
+const FAUCET_AMOUNT: u128 = 100_000_000_000_000_000_000;
+/// Most wei a single faucet request may ask for (1,000,000 ETH).
+const FAUCET_MAX_AMOUNT: u128 = 1_000_000_000_000_000_000_000_000;
+
 fn parse_accounts(s: &str) -> Result<usize, String> {
     let n: usize = s.parse().map_err(|e| format!("{e}"))?;
     if (1..=20).contains(&n) {
@@ -62,15 +86,19 @@ struct EvDevArgs {
     deploy_config: Option<PathBuf>,
 }
 
+fn derive_signer(index: u32) -> PrivateKeySigner {
+    MnemonicBuilder::<English>::default()
+        .phrase(HARDHAT_MNEMONIC)
+        .index(index)
+        .expect("valid derivation index")
+        .build()
+        .expect("valid key derivation")
+}
+
 fn derive_keys(count: usize) -> Vec<(String, String)> {
     (0..count)
         .map(|i| {
-            let signer = MnemonicBuilder::<English>::default()
-                .phrase(HARDHAT_MNEMONIC)
-                .index(i as u32)
-                .expect("valid derivation index")
-                .build()
-                .expect("valid key derivation");
+            let signer = derive_signer(i as u32);
             let address = signer.address();
             let key_bytes = signer.credential().to_bytes();
             (
@@ -89,6 +117,18 @@ fn chain_id_from_genesis() -> u64 {
         .expect("genesis must have config.chainId")
 }
 
+/// Adds `faucet` to the mint precompile allowlist in `genesis`.
+///
+/// The precompile account gets the code and nonce it would give itself on first use, so the
+/// allowlist entry is not pruned as an empty account.
+fn allowlist_faucet(genesis: &mut serde_json::Value, faucet: Address) {
+    let account = &mut genesis["alloc"][MINT_PRECOMPILE_ADDR.to_string()];
+    if account.is_null() {
+        *account = serde_json::json!({ "balance": "0x0", "nonce": "0x1", "code": "0xfe" });
+    }
+    account["storage"][faucet.into_word().to_string()] = serde_json::json!(B256::with_last_byte(1));
+}
+
 fn print_banner(args: &EvDevArgs, deploy_cfg: Option<&DeployConfig>) {
     let accounts = derive_keys(args.accounts);
 
@@ -126,6 +166,15 @@ fn print_banner(args: &EvDevArgs, deploy_cfg: Option<&DeployConfig>) {
         println!("({i}) {key}");
     }
     println!();
+    println!(
+        "Faucet:        evolve_requestFunds mints from ({FAUCET_ACCOUNT_INDEX}) {}",
+        derive_signer(FAUCET_ACCOUNT_INDEX).address()
+    );
+    println!(
+        "Executor:      evnode_sendTransaction and wallet_sendCalls sign with ({EXECUTOR_ACCOUNT_INDEX}) {}",
+        derive_signer(EXECUTOR_ACCOUNT_INDEX).address()
+    );
+    println!();
     println!("Mnemonic: {HARDHAT_MNEMONIC}");
     println!("Derivation path: m/44'/60'/0'/0/{{index}}");
     println!();
@@ -176,14 +225,14 @@ fn main() {
         print_banner(&dev_args, deploy_cfg.as_ref());
     }
 
-    let genesis_json = if let Some(ref cfg) = deploy_cfg {
-        let mut genesis: serde_json::Value =
-            serde_json::from_str(DEVNET_GENESIS).expect("valid genesis JSON");
+    let faucet_signer = derive_signer(FAUCET_ACCOUNT_INDEX);
+    let mut genesis: serde_json::Value =
+        serde_json::from_str(DEVNET_GENESIS).expect("valid genesis JSON");
+    if let Some(ref cfg) = deploy_cfg {
         merge_alloc(cfg, &mut genesis, true).expect("failed to merge deploy config into genesis");
-        serde_json::to_string(&genesis).expect("failed to serialize merged genesis")
-    } else {
-        DEVNET_GENESIS.to_string()
-    };
+    }
+    allowlist_faucet(&mut genesis, faucet_signer.address());
+    let genesis_json = serde_json::to_string(&genesis).expect("failed to serialize genesis");
 
     // Write genesis to a temp file that lives for the process duration
     let mut genesis_file =
@@ -246,18 +295,73 @@ fn main() {
 
     if let Err(err) = cli.run(|builder, _evolve_args| async move {
         info!("=== EV-DEV: Starting local development chain ===");
+        let evolve_config = Arc::new(ResolvedEvolveConfig::from_chain_spec(
+            &builder.config().chain,
+        )?);
+        let rpc_evolve_config = evolve_config.clone();
         let handle = builder
-            .node(EvolveNode::new())
+            .node(EvolveNode::with_config(evolve_config.clone()))
             .extend_rpc_modules(move |ctx| {
                 let evolve_cfg = EvolveConfig::default();
                 let evolve_txpool =
-                    EvolveTxpoolApiImpl::new(ctx.pool().clone(), evolve_cfg.max_txpool_bytes);
+                    EvolveTxpoolApiImpl::new(ctx.pool().clone(), evolve_cfg.max_txpool_bytes)
+                        .with_bundles(rpc_evolve_config.handles.bundles.clone())
+                        .with_maintenance_lane(rpc_evolve_config.handles.maintenance.clone())
+                        .with_limits(txpool_limits(&ctx.config().txpool));
                 ctx.modules.merge_configured(evolve_txpool.into_rpc())?;
+
+                // Default fee filling in wallets goes through `eth_maxPriorityFeePerGas`, so
+                // answer it from the same estimator as `evolve_estimateFeePerGas`.
+                let fee_api = EvolveFeeApiImpl::new(
+                    rpc_evolve_config.handles.fee_estimator.clone(),
+                    ctx.config().txpool.minimum_priority_fee,
+                );
+                ctx.modules
+                    .merge_configured(EvolveFeeApiServer::into_rpc(fee_api.clone()))?;
+                ctx.modules
+                    .replace_configured(EvolveFeeDefaultsApiServer::into_rpc(fee_api))?;
+
+                // Dev accounts are unlimited: every request mints, without cooldowns.
+                let faucet = Faucet::new(
+                    ctx.provider().clone(),
+                    ctx.pool().clone(),
+                    faucet_signer,
+                    chain_id_from_genesis(),
+                    FaucetConfig {
+                        funding: FaucetFunding::Mint,
+                        amount: U256::from(FAUCET_AMOUNT),
+                        max_amount: U256::from(FAUCET_MAX_AMOUNT),
+                        address_cooldown: Duration::ZERO,
+                        ip_cooldown: Duration::ZERO,
+                    },
+                );
+                ctx.modules
+                    .merge_configured(EvolveFaucetApiImpl::new(Arc::new(faucet)).into_rpc())?;
+
+                let executor = Arc::new(ExecutorSigner::new(
+                    ctx.provider().clone(),
+                    ctx.pool().clone(),
+                    ctx.node().evm_config().clone(),
+                    derive_signer(EXECUTOR_ACCOUNT_INDEX),
+                    chain_id_from_genesis(),
+                ));
+                ctx.modules.merge_configured(
+                    EvNodeSendTransactionApiImpl::new(executor.clone()).into_rpc(),
+                )?;
+                ctx.modules
+                    .merge_configured(WalletCallsApiImpl::new(executor).into_rpc())?;
                 Ok(())
             })
             .launch_with_debug_capabilities()
             .await?;
 
+        let fee_config = evolve_config.config().clone();
+        handle.node.task_executor.spawn(run_fee_estimator(
+            handle.node.provider.clone(),
+            handle.node.chain_spec(),
+            fee_config,
+        ));
+
         info!("=== EV-DEV: Local chain running - RPC ready ===");
         handle.node_exit_future.await
     }) {
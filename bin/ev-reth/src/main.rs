@@ -5,18 +5,83 @@
 
 #![allow(missing_docs, rustdoc::missing_crate_level_docs)]
 
+use alloy_primitives::U256;
 use clap::Parser;
 use evolve_ev_reth::{
     config::EvolveConfig,
-    rpc::txpool::{EvolveTxpoolApiImpl, EvolveTxpoolApiServer},
+    rpc::{
+        attestation::{EvolveAttestationApiImpl, EvolveAttestationApiServer},
+        build_report::{EvolveBuildReportApiImpl, EvolveBuildReportApiServer},
+        bundle::{EvolveBundleApiImpl, EvolveBundleApiServer},
+        chain_stats::{EvolveChainStatsApiImpl, EvolveChainStatsApiServer},
+        config::{EvolveChainConfig, EvolveConfigApiImpl, EvolveConfigApiServer},
+        da::{EvolveDaApiImpl, EvolveDaApiServer},
+        encrypted::{EvolveEncryptedApiImpl, EvolveEncryptedApiServer},
+        fees::{EvolveFeeApiImpl, EvolveFeeApiServer},
+        maintenance::{EvolveMaintenanceApiImpl, EvolveMaintenanceApiServer},
+        ordering::{EvolveOrderingApiImpl, EvolveOrderingApiServer},
+        status::{EvolveStatusApiImpl, EvolveStatusApiServer},
+        txpool::{EvolveTxpoolApiImpl, EvolveTxpoolApiServer},
+        wallet::{WalletCallsApiImpl, WalletCallsApiServer},
+    },
 };
+use reth_chainspec::EthChainSpec;
 use reth_ethereum_cli::Cli;
 use reth_tracing_otlp::{OtlpConfig, OtlpProtocol};
-use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+use std::{sync::Arc, time::Duration};
+use tracing::{info, warn};
+use tracing_subscriber::{
+    layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Layer, Registry,
+};
 use url::Url;
 
-use ev_node::{log_startup, EvolveArgs, EvolveChainSpecParser, EvolveNode};
+use ev_node::{
+    abi_registry::{AbiRegistry, EvolveAbiRegistryApiImpl, EvolveAbiRegistryApiServer},
+    activity::{EvolveActivityApiImpl, EvolveActivityApiServer},
+    batch_calls::{EvolveBatchCallsApiImpl, EvolveBatchCallsApiServer},
+    batch_gas::{EvolveBatchGasApiImpl, EvolveBatchGasApiServer},
+    call_cache::{run_call_cache_invalidation, CallCache, EvolveCallApiImpl, EvolveCallApiServer},
+    chain_stats::{genesis_supply, run_chain_stats},
+    chaos_engine::ChaosEngineCommand,
+    commitment::{EvolveCommitmentApiImpl, EvolveCommitmentApiServer},
+    da_finality::run_da_finality,
+    deploy_address::{EvNodeDeployAddressApiImpl, EvNodeDeployAddressApiServer},
+    doctor::DoctorCommand,
+    engine_compression::serve_compressed_engine,
+    error::with_engine_error_data,
+    faucet::{serve_faucet, Faucet, FaucetConfig, FaucetFunding},
+    fee_estimator::run_fee_estimator,
+    graphql::{graphql_schema, serve_graphql},
+    log_filter::{EvolveLogFilterApiImpl, EvolveLogFilterApiServer, LogFilter},
+    log_startup,
+    migrations::{DbCommand, DbSubcommand, Migrator, EVOLVE_DATA_DIR},
+    nonce::{EvolveNonceApiImpl, EvolveNonceApiServer},
+    payload_debug::{EvolveDebugApiImpl, EvolveDebugApiServer},
+    pending_block::{EvolvePendingBlockApiImpl, EvolvePendingBlockApiServer},
+    raw_transaction::{EthRawTransactionApiImpl, EthRawTransactionApiServer},
+    report_command::{CommandReport, ReportCommand},
+    send_transaction::{
+        EvNodeSendTransactionApiImpl, EvNodeSendTransactionApiServer, ExecutorSigner,
+    },
+    signer::load_signer_key,
+    simulate_batch::{EvNodeSimulateBatchApiImpl, EvNodeSimulateBatchApiServer},
+    sponsorship::{
+        EvNodeSponsorshipApiImpl, EvNodeSponsorshipApiServer, Paymaster, SponsorshipPolicy,
+        SponsorshipRules,
+    },
+    sponsorship_audit::AuditSponsorshipsCommand,
+    startup_config::{record_startup_config, resolved_config},
+    state_patch::{EvolveStatePatchApiImpl, EvolveStatePatchApiServer},
+    supply::{
+        run_supply_index, EvolveSupplyApiImpl, EvolveSupplyApiServer, SupplyIndex,
+        SUPPLY_INDEX_FILE,
+    },
+    transfer_intent::{
+        EvolveTransferIntentApiImpl, EvolveTransferIntentApiServer, TransferRelayer,
+    },
+    txpool::txpool_limits,
+    EvolveArgs, EvolveChainSpecParser, EvolveEngineValidator, EvolveNode, ResolvedEvolveConfig,
+};
 
 #[global_allocator]
 static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::new_allocator();
@@ -45,15 +110,32 @@ fn otlp_config_from_env() -> Option<OtlpConfig> {
 
 const EV_TRACE_LEVEL_ENV: &str = "EV_TRACE_LEVEL";
 
+/// Builds the stdout log filter from `RUST_LOG` (falling back to `"info"`), along with the
+/// handle `evolve_setLogFilter` changes it through.
+fn reloadable_log_filter() -> (reload::Layer<EnvFilter, Registry>, LogFilter) {
+    let base = std::env::var(EnvFilter::DEFAULT_ENV)
+        .ok()
+        .filter(|directives| EnvFilter::try_new(directives).is_ok())
+        .unwrap_or_else(|| "info".to_string());
+    let (filter, handle) = reload::Layer::new(EnvFilter::new(&base));
+    let log_filter = LogFilter::new(base, move |directives| {
+        let filter = EnvFilter::try_new(directives).map_err(|err| err.to_string())?;
+        handle.reload(filter).map_err(|err| err.to_string())
+    });
+    (filter, log_filter)
+}
+
 /// Initialize tracing with optional OTLP support.
 ///
 /// When OTLP is enabled, per-layer filtering is applied so that stdout logs
 /// are controlled by `RUST_LOG` while the OTLP span exporter is controlled
 /// by `EV_TRACE_LEVEL` (falling back to `RUST_LOG`, then `"info"`).
-fn init_tracing() {
+///
+/// Returns the stdout log filter, which can be changed at runtime.
+fn init_tracing() -> LogFilter {
     if let Some(config) = otlp_config_from_env() {
         if let Ok(otlp_layer) = reth_tracing_otlp::span_layer(config) {
-            let log_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into());
+            let (log_filter, reloadable) = reloadable_log_filter();
 
             let trace_filter = std::env::var(EV_TRACE_LEVEL_ENV)
                 .ok()
@@ -72,14 +154,37 @@ fn init_tracing() {
                 .init();
 
             info!("OTLP tracing initialized for service: ev-reth");
-            return;
+            return reloadable;
         }
     }
 
+    let (log_filter, reloadable) = reloadable_log_filter();
     tracing_subscriber::registry()
-        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .with(log_filter)
         .with(tracing_subscriber::fmt::layer().with_target(false))
         .init();
+    reloadable
+}
+
+/// Runs `C` if `args` invoke it, printing its report and exiting with an error if the command
+/// fails or the report is not healthy. Returns false if `args` invoke another command.
+fn run_report_command<C: ReportCommand>(args: &[String]) -> bool {
+    if !args.get(1).is_some_and(|arg| arg == C::NAME) {
+        return false;
+    }
+    match C::parse_from(&args[1..]).run() {
+        Ok(report) => {
+            println!("{report}");
+            if !report.is_healthy() {
+                std::process::exit(1);
+            }
+        }
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            std::process::exit(1);
+        }
+    }
+    true
 }
 
 fn main() {
@@ -93,26 +198,433 @@ fn main() {
     }
 
     // initialize tracing (with optional OTLP support based on env vars)
-    init_tracing();
+    let log_filter = init_tracing();
+
+    // `db migrate` and `db status` manage the Evolve schema; every other `db` subcommand is
+    // reth's.
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).is_some_and(|arg| arg == "db")
+        && args
+            .get(2)
+            .is_some_and(|arg| DbSubcommand::NAMES.contains(&arg.as_str()))
+    {
+        if let Err(err) = DbCommand::parse_from(&args[1..]).run() {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if run_report_command::<DoctorCommand>(&args)
+        || run_report_command::<ChaosEngineCommand>(&args)
+        || run_report_command::<AuditSponsorshipsCommand>(&args)
+    {
+        return;
+    }
 
     if let Err(err) =
-        Cli::<EvolveChainSpecParser, EvolveArgs>::parse().run(|builder, _evolve_args| async move {
+        Cli::<EvolveChainSpecParser, EvolveArgs>::parse().run(|builder, evolve_args| async move {
             log_startup();
+            let data_dir = builder.config().datadir().data_dir().to_path_buf();
+            let applied = Migrator::new(&data_dir).migrate()?;
+            if !applied.is_empty() {
+                info!(versions = ?applied, "migrated Evolve schema");
+            }
+            // Resolved once here and shared with every component; record what the node will
+            // run with.
+            let evolve_config = Arc::new(
+                ResolvedEvolveConfig::from_chain_spec(&builder.config().chain)?
+                    .with_node_settings(evolve_args.node_settings()?),
+            );
+            let startup_config = resolved_config(
+                builder.config().chain.chain().id(),
+                &args[1..],
+                &evolve_args,
+                &evolve_config,
+            )?;
+            match record_startup_config(&data_dir.join(EVOLVE_DATA_DIR), &startup_config) {
+                Ok(None) => info!("recorded startup configuration"),
+                Ok(Some(changes)) if changes.is_empty() => {
+                    info!("startup configuration unchanged since previous run")
+                }
+                Ok(Some(changes)) => {
+                    for change in &changes {
+                        info!(%change, "startup configuration changed since previous run");
+                    }
+                }
+                Err(err) => warn!(%err, "failed to record startup configuration"),
+            }
+            if let Some(url) = &evolve_args.screening_url {
+                info!(
+                    %url,
+                    fail_closed = evolve_args.screening_fail_closed,
+                    "screening pool transactions"
+                );
+            }
+            if let Some(signer) = &evolve_config.node.attestation_signer {
+                info!(operator = %signer.address(), "signing build attestations");
+            }
+            let faucet_signer = evolve_args
+                .faucet_key_file
+                .as_deref()
+                .map(load_signer_key)
+                .transpose()?;
+            let sponsor_signer = evolve_args
+                .sponsor_key_file
+                .as_deref()
+                .map(load_signer_key)
+                .transpose()?;
+            let transfer_intent_tokens = evolve_args.transfer_intent_tokens.clone();
+            let sponsorship_policy = match (
+                &evolve_args.sponsorship_policy_file,
+                &evolve_args.sponsorship_policy_url,
+            ) {
+                (Some(path), _) => Some(SponsorshipPolicy::Local(SponsorshipRules::load(path)?)),
+                (None, Some(url)) => Some(SponsorshipPolicy::remote(
+                    url.clone(),
+                    Duration::from_millis(evolve_args.sponsorship_policy_timeout_ms),
+                )),
+                (None, None) => None,
+            };
+            let executor_signer = evolve_args
+                .executor_key_file
+                .as_deref()
+                .map(load_signer_key)
+                .transpose()?;
+            let abi_registry = match &evolve_args.abi_registry {
+                Some(path) => {
+                    let registry = AbiRegistry::open(path)?;
+                    info!(
+                        path = %path.display(),
+                        contracts = registry.len(),
+                        "loaded ABI registry"
+                    );
+                    Arc::new(registry)
+                }
+                None => Arc::default(),
+            };
+            let supply_index = if evolve_args.supply_index {
+                let path = data_dir.join(EVOLVE_DATA_DIR).join(SUPPLY_INDEX_FILE);
+                Some(Arc::new(SupplyIndex::open(path)?))
+            } else {
+                None
+            };
+            let rpc_supply_index = supply_index.clone();
+            let drain_timeout = Duration::from_secs(evolve_args.shutdown_drain_timeout);
+            let call_cache = evolve_args.call_cache.then(|| {
+                Arc::new(CallCache::new(Duration::from_millis(
+                    evolve_args.call_cache_ttl_ms,
+                )))
+            });
+            let rpc_call_cache = call_cache.clone();
+            let engine_compression_addr = evolve_args
+                .engine_compression
+                .then_some(evolve_args.engine_compression_addr);
+            let rpc_evolve_config = evolve_config.clone();
             let handle = builder
-                .node(EvolveNode::new())
+                .node(EvolveNode::with_config(evolve_config.clone()))
                 .extend_rpc_modules(move |ctx| {
                     // Build custom txpool RPC with config + optional CLI/env override
                     let evolve_cfg = EvolveConfig::default();
+                    let handles = &rpc_evolve_config.handles;
                     let evolve_txpool =
-                        EvolveTxpoolApiImpl::new(ctx.pool().clone(), evolve_cfg.max_txpool_bytes);
+                        EvolveTxpoolApiImpl::new(ctx.pool().clone(), evolve_cfg.max_txpool_bytes)
+                            .with_bundles(handles.bundles.clone())
+                            .with_maintenance_lane(handles.maintenance.clone())
+                            .with_limits(txpool_limits(&ctx.config().txpool));
 
                     // Merge into all enabled transports (HTTP / WS)
                     ctx.modules.merge_configured(evolve_txpool.into_rpc())?;
+                    let build_reports = EvolveBuildReportApiImpl::new(handles.build_reports.clone());
+                    ctx.modules.merge_configured(build_reports.into_rpc())?;
+                    let attestations = EvolveAttestationApiImpl::new(handles.attestations.clone());
+                    ctx.modules.merge_configured(attestations.into_rpc())?;
+                    let ordering = EvolveOrderingApiImpl::new(handles.ordering_reports.clone());
+                    ctx.modules.merge_configured(ordering.into_rpc())?;
+                    let state_patches =
+                        EvolveStatePatchApiImpl::new(handles.state_patch_receipts.clone());
+                    ctx.modules.merge_configured(state_patches.into_rpc())?;
+                    let bundles = EvolveBundleApiImpl::new(handles.bundles.clone());
+                    ctx.modules.merge_configured(bundles.into_rpc())?;
+                    let encrypted = EvolveEncryptedApiImpl::new(handles.encrypted.clone());
+                    ctx.modules.merge_configured(encrypted.into_rpc())?;
+                    let status = EvolveStatusApiImpl::new(handles.shutdown.clone());
+                    ctx.modules.merge_configured(status.into_rpc())?;
+                    let da = EvolveDaApiImpl::new(handles.da_finality.clone());
+                    ctx.modules.merge_configured(da.into_rpc())?;
+                    let chain_stats = EvolveChainStatsApiImpl::new(handles.chain_stats.clone());
+                    ctx.modules.merge_configured(chain_stats.into_rpc())?;
+                    ctx.modules
+                        .merge_configured(EvNodeDeployAddressApiImpl::new().into_rpc())?;
+                    let batch_calls =
+                        EvolveBatchCallsApiImpl::new(ctx.provider().clone(), abi_registry.clone());
+                    ctx.modules.merge_configured(batch_calls.into_rpc())?;
+                    let batch_gas = EvolveBatchGasApiImpl::new(
+                        ctx.provider().clone(),
+                        ctx.node().evm_config().clone(),
+                    );
+                    ctx.modules.merge_configured(batch_gas.into_rpc())?;
+                    let simulate_batch = EvNodeSimulateBatchApiImpl::new(
+                        ctx.provider().clone(),
+                        ctx.node().evm_config().clone(),
+                    );
+                    ctx.modules.merge_configured(simulate_batch.into_rpc())?;
+                    let nonces =
+                        EvolveNonceApiImpl::new(ctx.provider().clone(), ctx.pool().clone());
+                    ctx.modules.merge_configured(nonces.into_rpc())?;
+                    let activity = EvolveActivityApiImpl::new(ctx.provider().clone());
+                    ctx.modules.merge_configured(activity.into_rpc())?;
+                    if let Some(index) = rpc_supply_index {
+                        let supply = EvolveSupplyApiImpl::new(ctx.provider().clone(), index);
+                        ctx.modules.merge_configured(supply.into_rpc())?;
+                    }
+                    if let (Some(signer), Some(policy)) = (&sponsor_signer, sponsorship_policy) {
+                        info!(
+                            sponsor = %signer.address(),
+                            policy = %policy.describe(),
+                            "serving evnode_requestSponsorship"
+                        );
+                        let paymaster = Paymaster::new(
+                            ctx.provider().clone(),
+                            policy,
+                            signer.clone(),
+                            ctx.config().chain.chain().id(),
+                        );
+                        let sponsorship = EvNodeSponsorshipApiImpl::new(Arc::new(paymaster));
+                        ctx.modules.merge_configured(sponsorship.into_rpc())?;
+                    }
+                    if let Some(signer) = sponsor_signer {
+                        if transfer_intent_tokens.is_empty() {
+                            warn!("no transfer intent tokens configured, every intent will be refused");
+                        }
+                        info!(
+                            sponsor = %signer.address(),
+                            tokens = transfer_intent_tokens.len(),
+                            "relaying transfer intents"
+                        );
+                        let relayer = TransferRelayer::new(
+                            ctx.provider().clone(),
+                            ctx.pool().clone(),
+                            signer,
+                            ctx.config().chain.chain().id(),
+                            transfer_intent_tokens,
+                        );
+                        let transfer_intents = EvolveTransferIntentApiImpl::new(Arc::new(relayer));
+                        ctx.modules.merge_configured(transfer_intents.into_rpc())?;
+                    }
+                    if let Some(signer) = executor_signer {
+                        info!(
+                            executor = %signer.address(),
+                            "serving evnode_sendTransaction and wallet_sendCalls"
+                        );
+                        let executor = Arc::new(ExecutorSigner::new(
+                            ctx.provider().clone(),
+                            ctx.pool().clone(),
+                            ctx.node().evm_config().clone(),
+                            signer,
+                            ctx.config().chain.chain().id(),
+                        ));
+                        let send_transaction = EvNodeSendTransactionApiImpl::new(executor.clone());
+                        ctx.modules.merge_configured(send_transaction.into_rpc())?;
+                        let wallet = WalletCallsApiImpl::new(executor);
+                        ctx.modules.merge_configured(wallet.into_rpc())?;
+                    }
+                    let fee_api = EvolveFeeApiImpl::new(
+                        handles.fee_estimator.clone(),
+                        ctx.config().txpool.minimum_priority_fee,
+                    );
+                    ctx.modules.merge_configured(fee_api.into_rpc())?;
+
+                    let chain_config = EvolveChainConfig {
+                        native_currency: rpc_evolve_config.native_currency(),
+                    };
+                    ctx.modules
+                        .merge_configured(EvolveConfigApiImpl::new(chain_config).into_rpc())?;
+                    let commitments = EvolveCommitmentApiImpl::new(
+                        ctx.provider().clone(),
+                        rpc_evolve_config.block_commitment_window(),
+                    );
+                    ctx.modules.merge_configured(commitments.into_rpc())?;
+                    let pending_block = EvolvePendingBlockApiImpl::new(
+                        ctx.provider().clone(),
+                        handles.pending_block.clone(),
+                    );
+                    ctx.modules.merge_configured(pending_block.into_rpc())?;
+
+                    // Raw transactions may use the chain's EvNode type alias.
+                    if let Some(alias) = rpc_evolve_config.evnode_tx_type_alias() {
+                        let raw_transactions = EthRawTransactionApiImpl::new(
+                            ctx.provider().clone(),
+                            ctx.pool().clone(),
+                            alias,
+                        );
+                        ctx.modules.replace_configured(raw_transactions.into_rpc())?;
+                    }
+
+                    // Replaces the default `eth_call` with the cached one.
+                    if let Some(cache) = rpc_call_cache {
+                        let cached_call = EvolveCallApiImpl::new(
+                            ctx.provider().clone(),
+                            ctx.registry.eth_api().clone(),
+                            cache,
+                        );
+                        ctx.modules.replace_configured(cached_call.into_rpc())?;
+                    }
+
+                    // Payload re-execution is only exposed on the JWT-authenticated endpoint.
+                    let debug_payload = EvolveDebugApiImpl::new(
+                        ctx.provider().clone(),
+                        ctx.node().evm_config().clone(),
+                        EvolveEngineValidator::from_config(
+                            ctx.config().chain.clone(),
+                            &rpc_evolve_config,
+                            Arc::new(ctx.provider().clone()),
+                        ),
+                        ctx.registry.debug_api(),
+                        abi_registry.clone(),
+                    );
+                    ctx.auth_module
+                        .merge_auth_methods(debug_payload.into_rpc())?;
+                    ctx.auth_module.merge_auth_methods(
+                        EvolveAbiRegistryApiImpl::new(abi_registry).into_rpc(),
+                    )?;
+                    // Maintenance transactions skip the fee floor, so only the operator may
+                    // submit them.
+                    let maintenance = EvolveMaintenanceApiImpl::new(
+                        ctx.pool().clone(),
+                        handles.maintenance.clone(),
+                    );
+                    ctx.auth_module
+                        .merge_auth_methods(maintenance.into_rpc())?;
+                    ctx.auth_module
+                        .merge_auth_methods(EvolveLogFilterApiImpl::new(log_filter).into_rpc())?;
+
+                    // reth reports every failed payload job as an unknown payload; attach the
+                    // recorded cause so ev-node can tell whether to retry.
+                    let get_payload = with_engine_error_data(
+                        ctx.auth_module.module_mut().clone(),
+                        handles.engine_errors.clone(),
+                    );
+                    for method in get_payload.method_names().collect::<Vec<_>>() {
+                        ctx.auth_module.module_mut().remove_method(method);
+                    }
+                    ctx.auth_module.merge_auth_methods(get_payload)?;
+
+                    if let Some(addr) = engine_compression_addr {
+                        let secret = ctx
+                            .config()
+                            .rpc
+                            .auth_jwt_secret(ctx.config().datadir().jwt())?;
+                        let engine = ctx.auth_module.module_mut().clone();
+                        ctx.node().task_executor().spawn(async move {
+                            if let Err(err) = serve_compressed_engine(addr, secret, engine).await {
+                                warn!(%addr, %err, "Compressed Engine API endpoint stopped");
+                            }
+                        });
+                    }
                     Ok(())
                 })
                 .launch()
                 .await?;
 
+            let fee_config = evolve_config.config().clone();
+            let genesis_supply = genesis_supply(handle.node.chain_spec().genesis());
+            handle.node.task_executor.spawn(run_chain_stats(
+                handle.node.provider.clone(),
+                genesis_supply,
+                fee_config.clone(),
+            ));
+            if let Some(index) = supply_index {
+                handle.node.task_executor.spawn(run_supply_index(
+                    handle.node.provider.clone(),
+                    index,
+                    genesis_supply,
+                ));
+            }
+
+            handle.node.task_executor.spawn(run_fee_estimator(
+                handle.node.provider.clone(),
+                handle.node.chain_spec(),
+                fee_config,
+            ));
+
+            if evolve_args.graphql {
+                let schema = graphql_schema(handle.node.provider.clone());
+                let addr = evolve_args.graphql_addr;
+                handle.node.task_executor.spawn(async move {
+                    if let Err(err) = serve_graphql(addr, schema).await {
+                        warn!(%addr, %err, "GraphQL endpoint stopped");
+                    }
+                });
+            }
+
+            if let Some(cache) = call_cache {
+                handle.node.task_executor.spawn(run_call_cache_invalidation(
+                    handle.node.provider.clone(),
+                    cache,
+                ));
+            }
+
+            if let Some(signer) = faucet_signer {
+                let config = FaucetConfig {
+                    funding: if evolve_args.faucet_mint {
+                        FaucetFunding::Mint
+                    } else {
+                        FaucetFunding::Transfer
+                    },
+                    amount: U256::from(evolve_args.faucet_amount),
+                    max_amount: U256::from(evolve_args.faucet_max_amount),
+                    address_cooldown: Duration::from_secs(evolve_args.faucet_address_cooldown_secs),
+                    ip_cooldown: Duration::from_secs(evolve_args.faucet_ip_cooldown_secs),
+                };
+                info!(faucet = %signer.address(), funding = ?config.funding, "serving faucet");
+                let faucet = Arc::new(Faucet::new(
+                    handle.node.provider.clone(),
+                    handle.node.pool.clone(),
+                    signer,
+                    handle.node.chain_spec().chain().id(),
+                    config,
+                ));
+                let addr = evolve_args.faucet_addr;
+                handle.node.task_executor.spawn(async move {
+                    if let Err(err) = serve_faucet(addr, faucet).await {
+                        warn!(%addr, %err, "faucet endpoint stopped");
+                    }
+                });
+            }
+
+            if evolve_args.da_finality {
+                info!("safe and finalized block tags follow DA inclusion");
+                handle
+                    .node
+                    .task_executor
+                    .spawn(run_da_finality(
+                        handle.node.provider.clone(),
+                        evolve_config.handles.da_finality.clone(),
+                    ));
+            }
+
+            // Hold reth's graceful shutdown until in-flight payload jobs are done, so the
+            // txpool backup and database flush run after the last payload is built.
+            let shutdown_coordinator = evolve_config.handles.shutdown.clone();
+            handle
+                .node
+                .task_executor
+                .spawn_critical_with_graceful_shutdown_signal(
+                    "evolve shutdown coordinator",
+                    move |shutdown| async move {
+                        let guard = shutdown.await;
+                        info!("shutdown requested; draining in-flight payload jobs");
+                        if !shutdown_coordinator.drain(drain_timeout).await {
+                            warn!(
+                                timeout_secs = drain_timeout.as_secs(),
+                                "payload jobs still in flight after drain timeout"
+                            );
+                        }
+                        drop(guard);
+                    },
+                );
+
             info!("=== EV-RETH: Node launched successfully with ev-reth payload builder ===");
             handle.node_exit_future.await
         })
@@ -0,0 +1,242 @@
+// System channel precompile
+
+use crate::mint::{MintPrecompile, MintPrecompileError, MintPrecompileResult};
+use alloy::{
+    sol,
+    sol_types::{SolInterface, SolValue},
+};
+use alloy_evm::{
+    precompiles::{Precompile, PrecompileInput},
+    revm::precompile::{PrecompileId, PrecompileResult},
+    EvmInternals,
+};
+use alloy_primitives::{address, Address, Bytes, U256};
+use revm::precompile::{PrecompileHalt, PrecompileOutput};
+use std::sync::{Arc, OnceLock};
+
+sol! {
+    interface ISystemChannels {
+        function advance(uint64 channel, uint64 nonce) external;
+        function lastNonce(uint64 channel) external view returns (uint64);
+    }
+}
+
+pub const SYSTEM_CHANNEL_PRECOMPILE_ADDR: Address =
+    address!("0x000000000000000000000000000000000000F103");
+
+/// A precompile tracking a monotone nonce per system transaction channel.
+///
+/// System senders tag each message they deliver (a deposit, a scheduled job run, ...) with the
+/// next nonce of its channel by calling `advance` first. A reused or skipped nonce halts the
+/// call, so a transaction replayed by a restarted sequencer reverts instead of applying twice.
+/// The last nonce of each channel is kept in the precompile's storage, keyed by channel id.
+#[derive(Clone, Debug, Default)]
+pub struct SystemChannelPrecompile {
+    senders: Arc<[Address]>,
+}
+
+impl SystemChannelPrecompile {
+    pub fn id() -> &'static PrecompileId {
+        static ID: OnceLock<PrecompileId> = OnceLock::new();
+        ID.get_or_init(|| PrecompileId::custom("system_channels"))
+    }
+
+    pub fn new(senders: Arc<[Address]>) -> Self {
+        Self { senders }
+    }
+
+    fn is_sender(&self, caller: Address) -> bool {
+        self.senders.contains(&caller)
+    }
+
+    fn ensure_channel_account(internals: &mut EvmInternals<'_>) -> MintPrecompileResult<()> {
+        let account = internals
+            .load_account(SYSTEM_CHANNEL_PRECOMPILE_ADDR)
+            .map_err(MintPrecompileError::fatal)?;
+        if account.is_loaded_as_not_existing() {
+            MintPrecompile::mark_precompile_account(internals, SYSTEM_CHANNEL_PRECOMPILE_ADDR)?;
+            internals
+                .touch_account(SYSTEM_CHANNEL_PRECOMPILE_ADDR)
+                .map_err(MintPrecompileError::fatal)?;
+        }
+        Ok(())
+    }
+
+    fn last_nonce(internals: &mut EvmInternals<'_>, channel: u64) -> MintPrecompileResult<u64> {
+        Self::ensure_channel_account(internals)?;
+        let value = internals
+            .sload(SYSTEM_CHANNEL_PRECOMPILE_ADDR, U256::from(channel))
+            .map_err(MintPrecompileError::fatal)?;
+        Ok(value.saturating_to())
+    }
+
+    fn set_last_nonce(
+        internals: &mut EvmInternals<'_>,
+        channel: u64,
+        nonce: u64,
+    ) -> MintPrecompileResult<()> {
+        internals
+            .sstore(
+                SYSTEM_CHANNEL_PRECOMPILE_ADDR,
+                U256::from(channel),
+                U256::from(nonce),
+            )
+            .map_err(MintPrecompileError::fatal)?;
+        internals
+            .touch_account(SYSTEM_CHANNEL_PRECOMPILE_ADDR)
+            .map_err(MintPrecompileError::fatal)?;
+        Ok(())
+    }
+}
+
+impl Precompile for SystemChannelPrecompile {
+    fn precompile_id(&self) -> &PrecompileId {
+        Self::id()
+    }
+
+    fn call(&self, mut input: PrecompileInput<'_>) -> PrecompileResult {
+        let caller: Address = input.caller;
+        let reservoir = input.reservoir;
+
+        let decoded = match ISystemChannels::ISystemChannelsCalls::abi_decode(input.data) {
+            Ok(v) => v,
+            Err(e) => {
+                return Ok(PrecompileOutput::halt(
+                    PrecompileHalt::other(e.to_string()),
+                    reservoir,
+                ))
+            }
+        };
+        let internals = input.internals_mut();
+
+        let result = (|| -> MintPrecompileResult<Bytes> {
+            match decoded {
+                ISystemChannels::ISystemChannelsCalls::advance(call) => {
+                    if !self.is_sender(caller) {
+                        tracing::warn!(target: "system_channel_precompile", ?caller, "advance rejected: not a system sender");
+                        return Err(MintPrecompileError::halt_static("unauthorized caller"));
+                    }
+
+                    let last = Self::last_nonce(internals, call.channel)?;
+                    if call.nonce <= last {
+                        tracing::warn!(
+                            target: "system_channel_precompile",
+                            channel = call.channel,
+                            nonce = call.nonce,
+                            last,
+                            "system transaction replay rejected"
+                        );
+                        return Err(MintPrecompileError::halt_static("channel nonce reused"));
+                    }
+                    if call.nonce != last + 1 {
+                        return Err(MintPrecompileError::halt_static(
+                            "channel nonce out of order",
+                        ));
+                    }
+
+                    Self::set_last_nonce(internals, call.channel, call.nonce)?;
+                    tracing::debug!(
+                        target: "system_channel_precompile",
+                        channel = call.channel,
+                        nonce = call.nonce,
+                        "channel nonce advanced"
+                    );
+                    Ok(Bytes::new())
+                }
+                ISystemChannels::ISystemChannelsCalls::lastNonce(call) => {
+                    let last = Self::last_nonce(internals, call.channel)?;
+                    Ok(last.abi_encode().into())
+                }
+            }
+        })();
+
+        match result {
+            Ok(bytes) => Ok(PrecompileOutput::new(0, bytes, reservoir)),
+            Err(MintPrecompileError::Halt(reason)) => Ok(PrecompileOutput::halt(reason, reservoir)),
+            Err(MintPrecompileError::Fatal(err)) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolCall;
+    use revm::{
+        context::{
+            journal::{Journal, JournalInner},
+            BlockEnv, CfgEnv, TxEnv,
+        },
+        database::{CacheDB, EmptyDB},
+        primitives::hardfork::SpecId,
+    };
+
+    type TestJournal = Journal<CacheDB<EmptyDB>>;
+
+    const SENDER: Address = address!("0x00000000000000000000000000000000000000c1");
+    const OTHER: Address = address!("0x00000000000000000000000000000000000000d1");
+
+    fn setup_journal() -> TestJournal {
+        let mut journal = Journal::new_with_inner(CacheDB::default(), JournalInner::new());
+        journal.inner.set_spec_id(SpecId::PRAGUE);
+        journal
+    }
+
+    fn run_call(journal: &mut TestJournal, caller: Address, data: &[u8]) -> PrecompileOutput {
+        let block_env = BlockEnv::default();
+        let cfg_env = CfgEnv::default();
+        let tx_env = TxEnv::default();
+        let input = PrecompileInput {
+            data,
+            gas: 1_000_000,
+            reservoir: 0,
+            caller,
+            value: U256::ZERO,
+            target_address: SYSTEM_CHANNEL_PRECOMPILE_ADDR,
+            is_static: false,
+            bytecode_address: SYSTEM_CHANNEL_PRECOMPILE_ADDR,
+            internals: EvmInternals::new(journal, &block_env, &cfg_env, &tx_env),
+        };
+        SystemChannelPrecompile::new(Arc::from([SENDER]))
+            .call(input)
+            .expect("no fatal error")
+    }
+
+    fn advance(channel: u64, nonce: u64) -> Vec<u8> {
+        ISystemChannels::advanceCall { channel, nonce }.abi_encode()
+    }
+
+    fn last_nonce(journal: &mut TestJournal, channel: u64) -> u64 {
+        let data = ISystemChannels::lastNonceCall { channel }.abi_encode();
+        let output = run_call(journal, OTHER, &data);
+        u64::abi_decode(&output.bytes).unwrap()
+    }
+
+    #[test]
+    fn advance_rejects_reused_and_skipped_nonces() {
+        let mut journal = setup_journal();
+
+        assert!(!run_call(&mut journal, SENDER, &advance(1, 1)).is_halt());
+        assert!(
+            run_call(&mut journal, SENDER, &advance(1, 1)).is_halt(),
+            "reused nonce must be rejected"
+        );
+        assert!(
+            run_call(&mut journal, SENDER, &advance(1, 3)).is_halt(),
+            "skipped nonce must be rejected"
+        );
+        assert!(!run_call(&mut journal, SENDER, &advance(1, 2)).is_halt());
+        assert_eq!(last_nonce(&mut journal, 1), 2);
+
+        // Channels are independent.
+        assert_eq!(last_nonce(&mut journal, 2), 0);
+        assert!(!run_call(&mut journal, SENDER, &advance(2, 1)).is_halt());
+    }
+
+    #[test]
+    fn advance_rejects_non_system_sender() {
+        let mut journal = setup_journal();
+        assert!(run_call(&mut journal, OTHER, &advance(1, 1)).is_halt());
+        assert_eq!(last_nonce(&mut journal, 1), 0);
+    }
+}
@@ -0,0 +1,372 @@
+// State expiry registry precompile
+
+use crate::mint::{MintPrecompile, MintPrecompileError, MintPrecompileResult};
+use alloy::{
+    sol,
+    sol_types::{SolInterface, SolValue},
+};
+use alloy_evm::{
+    precompiles::{Precompile, PrecompileInput},
+    revm::precompile::{PrecompileId, PrecompileResult},
+    EvmInternals,
+};
+use alloy_primitives::{address, keccak256, Address, Bytes, B256, U256};
+use revm::{
+    precompile::{PrecompileHalt, PrecompileOutput},
+    primitives::KECCAK_EMPTY,
+};
+use std::sync::OnceLock;
+
+sol! {
+    interface IStateExpiry {
+        function lastActive(address account) external view returns (uint64);
+        function archived(address account) external view returns (bytes32);
+        function archive(address account) external;
+        function revive(address account, uint64 nonce, uint256 balance) external;
+    }
+}
+
+pub const STATE_EXPIRY_PRECOMPILE_ADDR: Address =
+    address!("0x000000000000000000000000000000000000F102");
+
+/// Storage slot holding the last block `account` was active in.
+pub fn last_active_slot(account: Address) -> U256 {
+    registry_slot(account, 0)
+}
+
+/// Storage slot holding the archive commitment of `account`, zero while it is live.
+pub fn archive_slot(account: Address) -> U256 {
+    registry_slot(account, 1)
+}
+
+fn registry_slot(account: Address, index: u8) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(account.into_word().as_slice());
+    preimage[63] = index;
+    keccak256(preimage).into()
+}
+
+/// Commitment to the state of an archived account: `keccak256(abi.encode(account, nonce,
+/// balance))`. The preimage is the revive proof.
+pub fn witness_commitment(account: Address, nonce: u64, balance: U256) -> B256 {
+    keccak256((account, nonce, balance).abi_encode())
+}
+
+/// Registry of account activity for state expiry.
+///
+/// The handler records the last block each transaction's sender, fee payer and target were
+/// active in. Once an account without code has been inactive for `expiry_period` blocks,
+/// anyone may `archive` it: its nonce and balance are replaced by a commitment in this
+/// precompile's storage and the account is removed from the state. `revive` restores it from
+/// the commitment preimage. Accounts with code are never archived.
+#[derive(Clone, Debug, Default)]
+pub struct StateExpiryPrecompile {
+    activation_height: u64,
+    expiry_period: u64,
+}
+
+impl StateExpiryPrecompile {
+    pub fn id() -> &'static PrecompileId {
+        static ID: OnceLock<PrecompileId> = OnceLock::new();
+        ID.get_or_init(|| PrecompileId::custom("state_expiry"))
+    }
+
+    pub fn new(activation_height: u64, expiry_period: u64) -> Self {
+        Self {
+            activation_height,
+            expiry_period,
+        }
+    }
+
+    fn ensure_registry_account(internals: &mut EvmInternals<'_>) -> MintPrecompileResult<()> {
+        let account = internals
+            .load_account(STATE_EXPIRY_PRECOMPILE_ADDR)
+            .map_err(MintPrecompileError::fatal)?;
+        if account.is_loaded_as_not_existing() {
+            MintPrecompile::mark_precompile_account(internals, STATE_EXPIRY_PRECOMPILE_ADDR)?;
+            internals
+                .touch_account(STATE_EXPIRY_PRECOMPILE_ADDR)
+                .map_err(MintPrecompileError::fatal)?;
+        }
+        Ok(())
+    }
+
+    fn read(internals: &mut EvmInternals<'_>, slot: U256) -> MintPrecompileResult<U256> {
+        Self::ensure_registry_account(internals)?;
+        let value = internals
+            .sload(STATE_EXPIRY_PRECOMPILE_ADDR, slot)
+            .map_err(MintPrecompileError::fatal)?;
+        Ok(*value)
+    }
+
+    fn write(
+        internals: &mut EvmInternals<'_>,
+        slot: U256,
+        value: U256,
+    ) -> MintPrecompileResult<()> {
+        Self::ensure_registry_account(internals)?;
+        internals
+            .sstore(STATE_EXPIRY_PRECOMPILE_ADDR, slot, value)
+            .map_err(MintPrecompileError::fatal)?;
+        internals
+            .touch_account(STATE_EXPIRY_PRECOMPILE_ADDR)
+            .map_err(MintPrecompileError::fatal)?;
+        Ok(())
+    }
+
+    /// Last block `account` was active in; accounts never seen since activation count as
+    /// active at the activation height.
+    fn last_active(
+        &self,
+        internals: &mut EvmInternals<'_>,
+        account: Address,
+    ) -> MintPrecompileResult<u64> {
+        let recorded: u64 = Self::read(internals, last_active_slot(account))?.saturating_to();
+        Ok(recorded.max(self.activation_height))
+    }
+
+    fn archive(
+        &self,
+        internals: &mut EvmInternals<'_>,
+        account: Address,
+    ) -> MintPrecompileResult<()> {
+        if !Self::read(internals, archive_slot(account))?.is_zero() {
+            return Err(MintPrecompileError::halt_static("account already archived"));
+        }
+        let block_number: u64 = internals.block_number().saturating_to();
+        let last_active = self.last_active(internals, account)?;
+        if block_number < last_active.saturating_add(self.expiry_period) {
+            return Err(MintPrecompileError::halt_static("account not expired"));
+        }
+
+        let info = internals
+            .load_account(account)
+            .map_err(MintPrecompileError::fatal)?
+            .info
+            .clone();
+        if info.code_hash != KECCAK_EMPTY {
+            return Err(MintPrecompileError::halt_static("account has code"));
+        }
+        if info.nonce == 0 && info.balance.is_zero() {
+            return Err(MintPrecompileError::halt_static("account is empty"));
+        }
+
+        let commitment = witness_commitment(account, info.nonce, info.balance);
+        Self::write(internals, archive_slot(account), commitment.into())?;
+        {
+            let mut account = internals
+                .load_account_mut(account)
+                .map_err(MintPrecompileError::fatal)?;
+            account.set_balance(U256::ZERO);
+            account.set_nonce(0);
+        }
+        internals
+            .touch_account(account)
+            .map_err(MintPrecompileError::fatal)?;
+
+        tracing::debug!(
+            target: "state_expiry_precompile",
+            ?account,
+            nonce = info.nonce,
+            balance = %info.balance,
+            %commitment,
+            "account archived"
+        );
+        Ok(())
+    }
+
+    fn revive(
+        internals: &mut EvmInternals<'_>,
+        account: Address,
+        nonce: u64,
+        balance: U256,
+    ) -> MintPrecompileResult<()> {
+        let commitment = Self::read(internals, archive_slot(account))?;
+        if commitment.is_zero() {
+            return Err(MintPrecompileError::halt_static("account not archived"));
+        }
+        if B256::from(commitment) != witness_commitment(account, nonce, balance) {
+            return Err(MintPrecompileError::halt_static("invalid revive proof"));
+        }
+
+        Self::write(internals, archive_slot(account), U256::ZERO)?;
+        let block_number = internals.block_number();
+        Self::write(internals, last_active_slot(account), block_number)?;
+
+        // Value sent to the address while archived is kept on top of the archived balance.
+        MintPrecompile::credit(internals, account, balance)?;
+        let mut loaded = internals
+            .load_account_mut(account)
+            .map_err(MintPrecompileError::fatal)?;
+        let current = loaded.nonce();
+        loaded.set_nonce(current.max(nonce));
+
+        tracing::debug!(
+            target: "state_expiry_precompile",
+            ?account,
+            nonce,
+            %balance,
+            "account revived"
+        );
+        Ok(())
+    }
+}
+
+impl Precompile for StateExpiryPrecompile {
+    fn precompile_id(&self) -> &PrecompileId {
+        Self::id()
+    }
+
+    fn call(&self, mut input: PrecompileInput<'_>) -> PrecompileResult {
+        let reservoir = input.reservoir;
+
+        let decoded = match IStateExpiry::IStateExpiryCalls::abi_decode(input.data) {
+            Ok(v) => v,
+            Err(e) => {
+                return Ok(PrecompileOutput::halt(
+                    PrecompileHalt::other(e.to_string()),
+                    reservoir,
+                ))
+            }
+        };
+        let internals = input.internals_mut();
+
+        let result = (|| -> MintPrecompileResult<Bytes> {
+            match decoded {
+                IStateExpiry::IStateExpiryCalls::lastActive(call) => {
+                    let last_active = self.last_active(internals, call.account)?;
+                    Ok(last_active.abi_encode().into())
+                }
+                IStateExpiry::IStateExpiryCalls::archived(call) => {
+                    let commitment = Self::read(internals, archive_slot(call.account))?;
+                    Ok(B256::from(commitment).abi_encode().into())
+                }
+                IStateExpiry::IStateExpiryCalls::archive(call) => {
+                    self.archive(internals, call.account)?;
+                    Ok(Bytes::new())
+                }
+                IStateExpiry::IStateExpiryCalls::revive(call) => {
+                    Self::revive(internals, call.account, call.nonce, call.balance)?;
+                    Ok(Bytes::new())
+                }
+            }
+        })();
+
+        match result {
+            Ok(bytes) => Ok(PrecompileOutput::new(0, bytes, reservoir)),
+            Err(MintPrecompileError::Halt(reason)) => Ok(PrecompileOutput::halt(reason, reservoir)),
+            Err(MintPrecompileError::Fatal(err)) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolCall;
+    use revm::{
+        context::{
+            journal::{Journal, JournalInner},
+            BlockEnv, CfgEnv, TxEnv,
+        },
+        database::{CacheDB, EmptyDB},
+        primitives::hardfork::SpecId,
+        state::AccountInfo,
+    };
+
+    type TestJournal = Journal<CacheDB<EmptyDB>>;
+
+    const ACCOUNT: Address = address!("0x00000000000000000000000000000000000000a1");
+    const CALLER: Address = address!("0x00000000000000000000000000000000000000b1");
+    const PERIOD: u64 = 100;
+
+    fn setup_journal() -> TestJournal {
+        let mut db = CacheDB::default();
+        db.insert_account_info(
+            ACCOUNT,
+            AccountInfo {
+                nonce: 3,
+                balance: U256::from(500),
+                ..Default::default()
+            },
+        );
+        let mut journal = Journal::new_with_inner(db, JournalInner::new());
+        journal.inner.set_spec_id(SpecId::PRAGUE);
+        journal
+    }
+
+    fn run_call(journal: &mut TestJournal, block_number: u64, data: &[u8]) -> PrecompileOutput {
+        let block_env = BlockEnv {
+            number: U256::from(block_number),
+            ..Default::default()
+        };
+        let cfg_env = CfgEnv::default();
+        let tx_env = TxEnv::default();
+        let input = PrecompileInput {
+            data,
+            gas: 1_000_000,
+            reservoir: 0,
+            caller: CALLER,
+            value: U256::ZERO,
+            target_address: STATE_EXPIRY_PRECOMPILE_ADDR,
+            is_static: false,
+            bytecode_address: STATE_EXPIRY_PRECOMPILE_ADDR,
+            internals: EvmInternals::new(journal, &block_env, &cfg_env, &tx_env),
+        };
+        StateExpiryPrecompile::new(0, PERIOD)
+            .call(input)
+            .expect("no fatal error")
+    }
+
+    fn account_state(journal: &mut TestJournal) -> (u64, U256) {
+        let info = &journal.load_account(ACCOUNT).unwrap().data.info;
+        (info.nonce, info.balance)
+    }
+
+    #[test]
+    fn archive_requires_expiry() {
+        let mut journal = setup_journal();
+        let data = IStateExpiry::archiveCall { account: ACCOUNT }.abi_encode();
+        let output = run_call(&mut journal, PERIOD - 1, &data);
+        assert!(output.is_halt(), "account is still live");
+        assert_eq!(account_state(&mut journal), (3, U256::from(500)));
+    }
+
+    #[test]
+    fn archive_and_revive_round_trip() {
+        let mut journal = setup_journal();
+        let archive = IStateExpiry::archiveCall { account: ACCOUNT }.abi_encode();
+        let output = run_call(&mut journal, PERIOD, &archive);
+        assert!(!output.is_halt(), "expired account should be archived");
+        assert_eq!(account_state(&mut journal), (0, U256::ZERO));
+
+        let query = IStateExpiry::archivedCall { account: ACCOUNT }.abi_encode();
+        let output = run_call(&mut journal, PERIOD, &query);
+        assert_eq!(
+            B256::abi_decode(&output.bytes).unwrap(),
+            witness_commitment(ACCOUNT, 3, U256::from(500))
+        );
+
+        let bad_proof = IStateExpiry::reviveCall {
+            account: ACCOUNT,
+            nonce: 3,
+            balance: U256::from(501),
+        }
+        .abi_encode();
+        assert!(run_call(&mut journal, PERIOD + 1, &bad_proof).is_halt());
+
+        let revive = IStateExpiry::reviveCall {
+            account: ACCOUNT,
+            nonce: 3,
+            balance: U256::from(500),
+        }
+        .abi_encode();
+        let output = run_call(&mut journal, PERIOD + 1, &revive);
+        assert!(!output.is_halt(), "valid proof should revive the account");
+        assert_eq!(account_state(&mut journal), (3, U256::from(500)));
+
+        let last_active = IStateExpiry::lastActiveCall { account: ACCOUNT }.abi_encode();
+        let output = run_call(&mut journal, PERIOD + 1, &last_active);
+        assert_eq!(u64::abi_decode(&output.bytes).unwrap(), PERIOD + 1);
+    }
+}
@@ -0,0 +1,183 @@
+// Feature bitmap precompile
+
+use alloy::{
+    sol,
+    sol_types::{SolInterface, SolValue},
+};
+use alloy_evm::{
+    precompiles::{Precompile, PrecompileInput},
+    revm::precompile::{PrecompileId, PrecompileResult},
+};
+use alloy_primitives::{address, Address, Bytes, U256};
+use revm::precompile::{PrecompileHalt, PrecompileOutput};
+use std::sync::OnceLock;
+
+sol! {
+    interface IEvFeatures {
+        function features() external view returns (uint256);
+        function hasFeatures(uint256 required) external view returns (bool);
+    }
+}
+
+pub const FEATURES_PRECOMPILE_ADDR: Address =
+    address!("0x000000000000000000000000000000000000F104");
+
+/// The mint precompile is installed.
+pub const FEATURE_MINT_PRECOMPILE: u64 = 1 << 0;
+/// The deposit inbox precompile is installed.
+pub const FEATURE_DEPOSIT_INBOX: u64 = 1 << 1;
+/// The system channel precompile is installed.
+pub const FEATURE_SYSTEM_CHANNELS: u64 = 1 << 2;
+/// Account state expiry and its registry precompile are active.
+pub const FEATURE_STATE_EXPIRY: u64 = 1 << 3;
+/// EvNode transactions may name an `IGasAllowance` contract as fee payer.
+pub const FEATURE_CONTRACT_FEE_PAYERS: u64 = 1 << 4;
+/// EvNode calls may carry their own gas limit.
+pub const FEATURE_CALL_GAS_LIMITS: u64 = 1 << 5;
+/// EvNode batches may be best-effort.
+pub const FEATURE_BEST_EFFORT_BATCHES: u64 = 1 << 6;
+/// EvNode transactions may be signed by session keys.
+pub const FEATURE_SESSION_KEYS: u64 = 1 << 7;
+/// Sponsors may sign EIP-712 typed data.
+pub const FEATURE_EIP712_SPONSORSHIPS: u64 = 1 << 8;
+/// EvNode transactions may carry a validity window.
+pub const FEATURE_VALIDITY_WINDOWS: u64 = 1 << 9;
+/// The base fee is redirected to a sink instead of burned.
+pub const FEATURE_BASE_FEE_REDIRECT: u64 = 1 << 10;
+/// Contract deployments are restricted to an allowlist.
+pub const FEATURE_DEPLOY_ALLOWLIST: u64 = 1 << 11;
+/// EvNode transactions may use nonce lanes, and the nonce manager precompile is installed.
+pub const FEATURE_NONCE_LANES: u64 = 1 << 12;
+/// EvNode executors may sign with a P256 key or a WebAuthn authenticator.
+pub const FEATURE_P256_SIGNATURES: u64 = 1 << 13;
+/// The native staking precompile is installed.
+pub const FEATURE_NATIVE_STAKING: u64 = 1 << 14;
+/// The RIP-7212 P256 verification precompile is installed.
+pub const FEATURE_P256_VERIFY: u64 = 1 << 15;
+
+/// A read-only precompile reporting which ev-reth features are active at the current block.
+///
+/// Bits are derived from the chainspec's activation heights, so every node following the chain
+/// reports the same bitmap for a block. Contracts such as bridges can check for the features
+/// they depend on with `hasFeatures` and refuse to run otherwise; on a node without this
+/// precompile the call returns no data and decoding the result reverts.
+#[derive(Clone, Debug, Default)]
+pub struct FeaturesPrecompile {
+    features: u64,
+}
+
+impl FeaturesPrecompile {
+    pub fn id() -> &'static PrecompileId {
+        static ID: OnceLock<PrecompileId> = OnceLock::new();
+        ID.get_or_init(|| PrecompileId::custom("ev_features"))
+    }
+
+    pub const fn new(features: u64) -> Self {
+        Self { features }
+    }
+
+    pub const fn features(&self) -> u64 {
+        self.features
+    }
+
+    fn has_features(&self, required: U256) -> bool {
+        required & U256::from(self.features) == required
+    }
+}
+
+impl Precompile for FeaturesPrecompile {
+    fn precompile_id(&self) -> &PrecompileId {
+        Self::id()
+    }
+
+    fn call(&self, input: PrecompileInput<'_>) -> PrecompileResult {
+        let reservoir = input.reservoir;
+        let bytes: Bytes = match IEvFeatures::IEvFeaturesCalls::abi_decode(input.data) {
+            Ok(IEvFeatures::IEvFeaturesCalls::features(_)) => {
+                U256::from(self.features).abi_encode().into()
+            }
+            Ok(IEvFeatures::IEvFeaturesCalls::hasFeatures(call)) => {
+                self.has_features(call.required).abi_encode().into()
+            }
+            Err(e) => {
+                return Ok(PrecompileOutput::halt(
+                    PrecompileHalt::other(e.to_string()),
+                    reservoir,
+                ))
+            }
+        };
+        Ok(PrecompileOutput::new(0, bytes, reservoir))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolCall;
+    use alloy_evm::EvmInternals;
+    use revm::{
+        context::{
+            journal::{Journal, JournalInner},
+            BlockEnv, CfgEnv, TxEnv,
+        },
+        database::{CacheDB, EmptyDB},
+        primitives::hardfork::SpecId,
+    };
+
+    fn run_call(precompile: &FeaturesPrecompile, data: &[u8]) -> PrecompileOutput {
+        let mut journal =
+            Journal::new_with_inner(CacheDB::<EmptyDB>::default(), JournalInner::new());
+        journal.inner.set_spec_id(SpecId::PRAGUE);
+        let block_env = BlockEnv::default();
+        let cfg_env = CfgEnv::default();
+        let tx_env = TxEnv::default();
+        let input = PrecompileInput {
+            data,
+            gas: 100_000,
+            reservoir: 0,
+            caller: Address::ZERO,
+            value: U256::ZERO,
+            target_address: FEATURES_PRECOMPILE_ADDR,
+            is_static: true,
+            bytecode_address: FEATURES_PRECOMPILE_ADDR,
+            internals: EvmInternals::new(&mut journal, &block_env, &cfg_env, &tx_env),
+        };
+        precompile.call(input).expect("no fatal error")
+    }
+
+    fn has_features(precompile: &FeaturesPrecompile, required: u64) -> bool {
+        let data = IEvFeatures::hasFeaturesCall {
+            required: U256::from(required),
+        }
+        .abi_encode();
+        bool::abi_decode(&run_call(precompile, &data).bytes).unwrap()
+    }
+
+    #[test]
+    fn reports_active_features() {
+        let precompile = FeaturesPrecompile::new(FEATURE_MINT_PRECOMPILE | FEATURE_SESSION_KEYS);
+
+        let output = run_call(&precompile, &IEvFeatures::featuresCall {}.abi_encode());
+        assert_eq!(
+            U256::abi_decode(&output.bytes).unwrap(),
+            U256::from(FEATURE_MINT_PRECOMPILE | FEATURE_SESSION_KEYS)
+        );
+
+        assert!(has_features(&precompile, 0));
+        assert!(has_features(&precompile, FEATURE_SESSION_KEYS));
+        assert!(has_features(
+            &precompile,
+            FEATURE_MINT_PRECOMPILE | FEATURE_SESSION_KEYS
+        ));
+        assert!(!has_features(
+            &precompile,
+            FEATURE_SESSION_KEYS | FEATURE_VALIDITY_WINDOWS
+        ));
+    }
+
+    #[test]
+    fn unknown_selector_halts() {
+        let precompile = FeaturesPrecompile::new(FEATURE_MINT_PRECOMPILE);
+        assert!(run_call(&precompile, &[0xde, 0xad, 0xbe, 0xef]).is_halt());
+    }
+}
@@ -0,0 +1,271 @@
+// Deposit inbox precompile
+
+use crate::mint::{MintPrecompile, MintPrecompileError, MintPrecompileResult};
+use alloy::{
+    sol,
+    sol_types::{SolInterface, SolValue},
+};
+use alloy_evm::{
+    precompiles::{Precompile, PrecompileInput},
+    revm::precompile::{PrecompileId, PrecompileResult},
+    EvmInternals,
+};
+use alloy_primitives::{address, Address, Bytes, U256};
+use revm::precompile::{PrecompileHalt, PrecompileOutput};
+use std::sync::OnceLock;
+
+sol! {
+    interface IDepositInbox {
+        function deposit(uint64 sourceChainId, uint64 nonce, address to, uint256 amount) external;
+        function lastNonce(uint64 sourceChainId) external view returns (uint64);
+        function isProcessed(uint64 sourceChainId, uint64 nonce) external view returns (bool);
+    }
+}
+
+pub const INBOX_PRECOMPILE_ADDR: Address = address!("0x000000000000000000000000000000000000F101");
+
+/// A precompile that credits bridge deposits delivered by a trusted relayer.
+///
+/// Deposits carry a per-source-chain nonce that must increase by exactly one, so a message can
+/// only be processed once and in order. The last processed nonce of each source chain is kept in
+/// the precompile's storage, keyed by the source chain id.
+#[derive(Clone, Debug, Default)]
+pub struct InboxPrecompile {
+    relayer: Address,
+}
+
+impl InboxPrecompile {
+    pub fn id() -> &'static PrecompileId {
+        static ID: OnceLock<PrecompileId> = OnceLock::new();
+        ID.get_or_init(|| PrecompileId::custom("deposit_inbox"))
+    }
+
+    pub fn new(relayer: Address) -> Self {
+        Self { relayer }
+    }
+
+    fn ensure_inbox_account(internals: &mut EvmInternals<'_>) -> MintPrecompileResult<()> {
+        let account = internals
+            .load_account(INBOX_PRECOMPILE_ADDR)
+            .map_err(MintPrecompileError::fatal)?;
+        if account.is_loaded_as_not_existing() {
+            MintPrecompile::mark_precompile_account(internals, INBOX_PRECOMPILE_ADDR)?;
+            internals
+                .touch_account(INBOX_PRECOMPILE_ADDR)
+                .map_err(MintPrecompileError::fatal)?;
+        }
+        Ok(())
+    }
+
+    fn last_nonce(
+        internals: &mut EvmInternals<'_>,
+        source_chain_id: u64,
+    ) -> MintPrecompileResult<u64> {
+        Self::ensure_inbox_account(internals)?;
+        let value = internals
+            .sload(INBOX_PRECOMPILE_ADDR, U256::from(source_chain_id))
+            .map_err(MintPrecompileError::fatal)?;
+        Ok(value.saturating_to())
+    }
+
+    fn set_last_nonce(
+        internals: &mut EvmInternals<'_>,
+        source_chain_id: u64,
+        nonce: u64,
+    ) -> MintPrecompileResult<()> {
+        internals
+            .sstore(
+                INBOX_PRECOMPILE_ADDR,
+                U256::from(source_chain_id),
+                U256::from(nonce),
+            )
+            .map_err(MintPrecompileError::fatal)?;
+        internals
+            .touch_account(INBOX_PRECOMPILE_ADDR)
+            .map_err(MintPrecompileError::fatal)?;
+        Ok(())
+    }
+}
+
+impl Precompile for InboxPrecompile {
+    fn precompile_id(&self) -> &PrecompileId {
+        Self::id()
+    }
+
+    fn call(&self, mut input: PrecompileInput<'_>) -> PrecompileResult {
+        let caller: Address = input.caller;
+        let reservoir = input.reservoir;
+
+        let decoded = match IDepositInbox::IDepositInboxCalls::abi_decode(input.data) {
+            Ok(v) => v,
+            Err(e) => {
+                return Ok(PrecompileOutput::halt(
+                    PrecompileHalt::other(e.to_string()),
+                    reservoir,
+                ))
+            }
+        };
+        let internals = input.internals_mut();
+
+        let result = (|| -> MintPrecompileResult<Bytes> {
+            match decoded {
+                IDepositInbox::IDepositInboxCalls::deposit(call) => {
+                    if caller != self.relayer {
+                        tracing::warn!(target: "inbox_precompile", ?caller, "deposit rejected: not the relayer");
+                        return Err(MintPrecompileError::halt_static("unauthorized caller"));
+                    }
+
+                    let last = Self::last_nonce(internals, call.sourceChainId)?;
+                    if call.nonce <= last {
+                        return Err(MintPrecompileError::halt_static(
+                            "deposit already processed",
+                        ));
+                    }
+                    if call.nonce != last + 1 {
+                        return Err(MintPrecompileError::halt_static(
+                            "deposit nonce out of order",
+                        ));
+                    }
+
+                    MintPrecompile::credit(internals, call.to, call.amount)?;
+                    Self::set_last_nonce(internals, call.sourceChainId, call.nonce)?;
+
+                    tracing::debug!(
+                        target: "inbox_precompile",
+                        source_chain_id = call.sourceChainId,
+                        nonce = call.nonce,
+                        to = ?call.to,
+                        amount = %call.amount,
+                        "deposit processed"
+                    );
+                    Ok(Bytes::new())
+                }
+                IDepositInbox::IDepositInboxCalls::lastNonce(call) => {
+                    let last = Self::last_nonce(internals, call.sourceChainId)?;
+                    Ok(last.abi_encode().into())
+                }
+                IDepositInbox::IDepositInboxCalls::isProcessed(call) => {
+                    let last = Self::last_nonce(internals, call.sourceChainId)?;
+                    let processed = call.nonce != 0 && call.nonce <= last;
+                    Ok(processed.abi_encode().into())
+                }
+            }
+        })();
+
+        match result {
+            Ok(bytes) => Ok(PrecompileOutput::new(0, bytes, reservoir)),
+            Err(MintPrecompileError::Halt(reason)) => Ok(PrecompileOutput::halt(reason, reservoir)),
+            Err(MintPrecompileError::Fatal(err)) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolCall;
+    use revm::{
+        context::{
+            journal::{Journal, JournalInner},
+            BlockEnv, CfgEnv, TxEnv,
+        },
+        database::{CacheDB, EmptyDB},
+        primitives::hardfork::SpecId,
+    };
+
+    type TestJournal = Journal<CacheDB<EmptyDB>>;
+
+    const RELAYER: Address = address!("0x00000000000000000000000000000000000000c1");
+    const RECIPIENT: Address = address!("0x00000000000000000000000000000000000000d1");
+
+    fn setup_journal() -> TestJournal {
+        let mut journal = Journal::new_with_inner(CacheDB::default(), JournalInner::new());
+        journal.inner.set_spec_id(SpecId::PRAGUE);
+        journal
+    }
+
+    fn run_call(journal: &mut TestJournal, caller: Address, data: &[u8]) -> PrecompileOutput {
+        let block_env = BlockEnv::default();
+        let cfg_env = CfgEnv::default();
+        let tx_env = TxEnv::default();
+        let input = PrecompileInput {
+            data,
+            gas: 1_000_000,
+            reservoir: 0,
+            caller,
+            value: U256::ZERO,
+            target_address: INBOX_PRECOMPILE_ADDR,
+            is_static: false,
+            bytecode_address: INBOX_PRECOMPILE_ADDR,
+            internals: EvmInternals::new(journal, &block_env, &cfg_env, &tx_env),
+        };
+        InboxPrecompile::new(RELAYER)
+            .call(input)
+            .expect("no fatal error")
+    }
+
+    fn deposit(nonce: u64, amount: u64) -> Vec<u8> {
+        IDepositInbox::depositCall {
+            sourceChainId: 1,
+            nonce,
+            to: RECIPIENT,
+            amount: U256::from(amount),
+        }
+        .abi_encode()
+    }
+
+    fn balance(journal: &TestJournal) -> U256 {
+        journal
+            .inner
+            .state
+            .get(&RECIPIENT)
+            .map(|account| account.info.balance)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn deposit_credits_recipient_once() {
+        let mut journal = setup_journal();
+
+        let output = run_call(&mut journal, RELAYER, &deposit(1, 50));
+        assert!(!output.is_halt(), "first deposit should succeed");
+        assert_eq!(balance(&journal), U256::from(50));
+
+        let replay = run_call(&mut journal, RELAYER, &deposit(1, 50));
+        assert!(replay.is_halt(), "replayed deposit must be rejected");
+        assert_eq!(balance(&journal), U256::from(50));
+    }
+
+    #[test]
+    fn deposit_rejects_nonce_gap() {
+        let mut journal = setup_journal();
+        let output = run_call(&mut journal, RELAYER, &deposit(2, 50));
+        assert!(output.is_halt(), "nonce gap must be rejected");
+        assert_eq!(balance(&journal), U256::ZERO);
+    }
+
+    #[test]
+    fn deposit_rejects_non_relayer() {
+        let mut journal = setup_journal();
+        let output = run_call(&mut journal, RECIPIENT, &deposit(1, 50));
+        assert!(output.is_halt(), "only the relayer may deposit");
+    }
+
+    #[test]
+    fn is_processed_reflects_last_nonce() {
+        let mut journal = setup_journal();
+        run_call(&mut journal, RELAYER, &deposit(1, 10));
+
+        let query = |journal: &mut TestJournal, nonce| {
+            let data = IDepositInbox::isProcessedCall {
+                sourceChainId: 1,
+                nonce,
+            }
+            .abi_encode();
+            let output = run_call(journal, RECIPIENT, &data);
+            bool::abi_decode(&output.bytes).unwrap()
+        };
+        assert!(query(&mut journal, 1));
+        assert!(!query(&mut journal, 2));
+    }
+}
@@ -1 +1,8 @@
+pub mod channel;
+pub mod expiry;
+pub mod features;
+pub mod inbox;
 pub mod mint;
+pub mod nonce_manager;
+pub mod p256_verify;
+pub mod staking;
@@ -35,19 +35,19 @@ pub struct MintPrecompile {
 }
 
 #[derive(Debug)]
-enum MintPrecompileError {
+pub(crate) enum MintPrecompileError {
     Fatal(PrecompileError),
     Halt(PrecompileHalt),
 }
 
-type MintPrecompileResult<T> = Result<T, MintPrecompileError>;
+pub(crate) type MintPrecompileResult<T> = Result<T, MintPrecompileError>;
 
 impl MintPrecompileError {
-    fn fatal(err: EvmInternalsError) -> Self {
+    pub(crate) fn fatal(err: EvmInternalsError) -> Self {
         Self::Fatal(PrecompileError::Fatal(err.to_string()))
     }
 
-    const fn halt_static(reason: &'static str) -> Self {
+    pub(crate) const fn halt_static(reason: &'static str) -> Self {
         Self::Halt(PrecompileHalt::other_static(reason))
     }
 }
@@ -83,15 +83,7 @@ impl MintPrecompile {
 
         if account.is_loaded_as_not_existing() {
             if addr == MINT_PRECOMPILE_ADDR {
-                // ensure the mint precompile account is treated as non-empty so state pruning
-                // does not wipe out its storage between blocks.
-                internals
-                    .set_code(addr, Self::bytecode().clone())
-                    .map_err(Self::map_internals_error)?;
-                internals
-                    .load_account_mut(addr)
-                    .map_err(Self::map_internals_error)?
-                    .set_nonce(1);
+                Self::mark_precompile_account(internals, addr)?;
             }
             // touch_account handles marking the account appropriately
             internals
@@ -102,6 +94,47 @@ impl MintPrecompile {
         Ok(())
     }
 
+    /// Gives a stateful precompile account code and a nonce so it is treated as non-empty and
+    /// state pruning does not wipe out its storage between blocks.
+    pub(crate) fn mark_precompile_account(
+        internals: &mut EvmInternals<'_>,
+        addr: Address,
+    ) -> MintPrecompileResult<()> {
+        internals
+            .set_code(addr, Self::bytecode().clone())
+            .map_err(Self::map_internals_error)?;
+        internals
+            .load_account_mut(addr)
+            .map_err(Self::map_internals_error)?
+            .set_nonce(1);
+        Ok(())
+    }
+
+    /// Credits `amount` of the native token to `to`, creating the account if needed.
+    pub(crate) fn credit(
+        internals: &mut EvmInternals<'_>,
+        to: Address,
+        amount: U256,
+    ) -> MintPrecompileResult<()> {
+        Self::ensure_account_created(internals, to)?;
+        Self::add_balance(internals, to, amount)?;
+        internals
+            .touch_account(to)
+            .map_err(Self::map_internals_error)
+    }
+
+    /// Debits `amount` of the native token from `from`.
+    pub(crate) fn debit(
+        internals: &mut EvmInternals<'_>,
+        from: Address,
+        amount: U256,
+    ) -> MintPrecompileResult<()> {
+        Self::sub_balance(internals, from, amount)?;
+        internals
+            .touch_account(from)
+            .map_err(Self::map_internals_error)
+    }
+
     fn add_balance(
         internals: &mut EvmInternals<'_>,
         addr: Address,
@@ -238,14 +271,7 @@ impl Precompile for MintPrecompile {
             match decoded {
                 INativeToken::INativeTokenCalls::mint(call) => {
                     self.ensure_authorized(internals, caller)?;
-                    let to = call.to;
-                    let amount = call.amount;
-
-                    Self::ensure_account_created(internals, to)?;
-                    Self::add_balance(internals, to, amount)?;
-                    internals
-                        .touch_account(to)
-                        .map_err(Self::map_internals_error)?;
+                    Self::credit(internals, call.to, call.amount)?;
 
                     Ok(Bytes::new())
                 }
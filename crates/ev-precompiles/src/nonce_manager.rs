@@ -0,0 +1,183 @@
+// Nonce manager precompile
+
+use crate::mint::{MintPrecompileError, MintPrecompileResult};
+use alloy::{
+    sol,
+    sol_types::{SolInterface, SolValue},
+};
+use alloy_evm::{
+    precompiles::{Precompile, PrecompileInput},
+    revm::precompile::{PrecompileId, PrecompileResult},
+};
+use alloy_primitives::{address, aliases::U192, keccak256, Address, Bytes, U256};
+use revm::precompile::{PrecompileHalt, PrecompileOutput};
+use std::sync::OnceLock;
+
+sol! {
+    interface INonceManager {
+        function getNonce(address account, uint192 key) external view returns (uint64);
+        function laneAddress(address account, uint192 key) external view returns (address);
+    }
+}
+
+pub const NONCE_MANAGER_PRECOMPILE_ADDR: Address =
+    address!("0x000000000000000000000000000000000000F105");
+
+/// Account whose nonce holds the sequence of nonce lane `key` of `account`:
+/// the last 20 bytes of `keccak256(abi.encode(0xF105, account, key))`.
+///
+/// Lane `0` is `account` itself, whose nonce is the regular account nonce.
+pub fn nonce_lane_address(account: Address, key: U192) -> Address {
+    if key.is_zero() {
+        return account;
+    }
+    let preimage = (NONCE_MANAGER_PRECOMPILE_ADDR, account, U256::from(key)).abi_encode();
+    Address::from_word(keccak256(preimage))
+}
+
+/// A read-only precompile exposing the sequences of EvNode nonce lanes.
+///
+/// Each lane keeps its sequence as the nonce of its [`nonce_lane_address`], an account nobody
+/// holds a key for; the handler checks and bumps it in place of the executor's nonce. Keeping
+/// it in an account lets the transaction pool order each lane like a sender of its own.
+#[derive(Clone, Debug, Default)]
+pub struct NonceManagerPrecompile;
+
+impl NonceManagerPrecompile {
+    pub fn id() -> &'static PrecompileId {
+        static ID: OnceLock<PrecompileId> = OnceLock::new();
+        ID.get_or_init(|| PrecompileId::custom("ev_nonce_manager"))
+    }
+
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Precompile for NonceManagerPrecompile {
+    fn precompile_id(&self) -> &PrecompileId {
+        Self::id()
+    }
+
+    fn call(&self, mut input: PrecompileInput<'_>) -> PrecompileResult {
+        let reservoir = input.reservoir;
+
+        let decoded = match INonceManager::INonceManagerCalls::abi_decode(input.data) {
+            Ok(v) => v,
+            Err(e) => {
+                return Ok(PrecompileOutput::halt(
+                    PrecompileHalt::other(e.to_string()),
+                    reservoir,
+                ))
+            }
+        };
+        let internals = input.internals_mut();
+
+        let result = (|| -> MintPrecompileResult<Bytes> {
+            match decoded {
+                INonceManager::INonceManagerCalls::getNonce(call) => {
+                    let nonce = internals
+                        .load_account(nonce_lane_address(call.account, call.key))
+                        .map_err(MintPrecompileError::fatal)?
+                        .info
+                        .nonce;
+                    Ok(nonce.abi_encode().into())
+                }
+                INonceManager::INonceManagerCalls::laneAddress(call) => {
+                    Ok(nonce_lane_address(call.account, call.key)
+                        .abi_encode()
+                        .into())
+                }
+            }
+        })();
+
+        match result {
+            Ok(bytes) => Ok(PrecompileOutput::new(0, bytes, reservoir)),
+            Err(MintPrecompileError::Halt(reason)) => Ok(PrecompileOutput::halt(reason, reservoir)),
+            Err(MintPrecompileError::Fatal(err)) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolCall;
+    use alloy_evm::EvmInternals;
+    use revm::{
+        context::{
+            journal::{Journal, JournalInner},
+            BlockEnv, CfgEnv, TxEnv,
+        },
+        database::{CacheDB, EmptyDB},
+        primitives::hardfork::SpecId,
+        state::AccountInfo,
+    };
+
+    const ACCOUNT: Address = address!("0x00000000000000000000000000000000000000aa");
+
+    fn run_call(db: CacheDB<EmptyDB>, data: &[u8]) -> PrecompileOutput {
+        let mut journal = Journal::new_with_inner(db, JournalInner::new());
+        journal.inner.set_spec_id(SpecId::PRAGUE);
+        let block_env = BlockEnv::default();
+        let cfg_env = CfgEnv::default();
+        let tx_env = TxEnv::default();
+        let input = PrecompileInput {
+            data,
+            gas: 100_000,
+            reservoir: 0,
+            caller: Address::ZERO,
+            value: U256::ZERO,
+            target_address: NONCE_MANAGER_PRECOMPILE_ADDR,
+            is_static: true,
+            bytecode_address: NONCE_MANAGER_PRECOMPILE_ADDR,
+            internals: EvmInternals::new(&mut journal, &block_env, &cfg_env, &tx_env),
+        };
+        NonceManagerPrecompile::new()
+            .call(input)
+            .expect("no fatal error")
+    }
+
+    #[test]
+    fn lane_zero_is_the_account() {
+        assert_eq!(nonce_lane_address(ACCOUNT, U192::ZERO), ACCOUNT);
+        let lane = nonce_lane_address(ACCOUNT, U192::from(1));
+        assert_ne!(lane, ACCOUNT);
+        assert_ne!(lane, nonce_lane_address(ACCOUNT, U192::from(2)));
+        assert_ne!(lane, nonce_lane_address(Address::ZERO, U192::from(1)));
+    }
+
+    #[test]
+    fn reports_lane_nonces() {
+        let key = U192::from(7);
+        let mut db = CacheDB::<EmptyDB>::default();
+        db.insert_account_info(
+            nonce_lane_address(ACCOUNT, key),
+            AccountInfo {
+                nonce: 3,
+                ..Default::default()
+            },
+        );
+
+        let nonce_of = |db: &CacheDB<EmptyDB>, key: U192| {
+            let data = INonceManager::getNonceCall {
+                account: ACCOUNT,
+                key,
+            }
+            .abi_encode();
+            u64::abi_decode(&run_call(db.clone(), &data).bytes).unwrap()
+        };
+        assert_eq!(nonce_of(&db, key), 3);
+        assert_eq!(nonce_of(&db, U192::from(8)), 0);
+
+        let data = INonceManager::laneAddressCall {
+            account: ACCOUNT,
+            key,
+        }
+        .abi_encode();
+        assert_eq!(
+            Address::abi_decode(&run_call(db, &data).bytes).unwrap(),
+            nonce_lane_address(ACCOUNT, key)
+        );
+    }
+}
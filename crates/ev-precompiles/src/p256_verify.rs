@@ -0,0 +1,168 @@
+// P256 (secp256r1) signature verification precompile
+
+use alloy_evm::{
+    precompiles::{Precompile, PrecompileInput},
+    revm::precompile::{PrecompileId, PrecompileResult},
+};
+use alloy_primitives::{address, Address, Bytes, B256};
+use p256::{
+    ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey},
+    EncodedPoint,
+};
+use revm::precompile::{PrecompileHalt, PrecompileOutput};
+use std::sync::OnceLock;
+
+/// RIP-7212 address of the P256 verification precompile.
+pub const P256_VERIFY_PRECOMPILE_ADDR: Address =
+    address!("0x0000000000000000000000000000000000000100");
+
+/// Gas charged per call, as priced by RIP-7212.
+pub const P256_VERIFY_GAS: u64 = 3_450;
+
+/// Length of the input: the message hash, `r`, `s` and the public key coordinates `x` and `y`.
+const P256_VERIFY_INPUT_LEN: usize = 160;
+
+/// A precompile verifying a P256 (secp256r1) signature over a message hash, as specified by
+/// RIP-7212.
+///
+/// The input is `hash || r || s || x || y`, each 32 bytes. A valid signature returns the 32-byte
+/// word `1`; anything else, including an input of the wrong length, a public key off the curve
+/// or a zero scalar, returns no data. High `s` values are accepted, so that signatures produced
+/// by WebAuthn authenticators verify as they are.
+#[derive(Clone, Debug, Default)]
+pub struct P256VerifyPrecompile;
+
+impl P256VerifyPrecompile {
+    pub fn id() -> &'static PrecompileId {
+        static ID: OnceLock<PrecompileId> = OnceLock::new();
+        ID.get_or_init(|| PrecompileId::custom("ev_p256_verify"))
+    }
+
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Returns whether `input` holds a valid signature.
+    pub fn verify(input: &[u8]) -> bool {
+        if input.len() != P256_VERIFY_INPUT_LEN {
+            return false;
+        }
+        let word = |index: usize| B256::from_slice(&input[index * 32..(index + 1) * 32]);
+        let (hash, r, s, x, y) = (word(0), word(1), word(2), word(3), word(4));
+
+        let point = EncodedPoint::from_affine_coordinates(&x.0.into(), &y.0.into(), false);
+        let Ok(key) = VerifyingKey::from_encoded_point(&point) else {
+            return false;
+        };
+        let Ok(signature) = Signature::from_scalars(r.0, s.0) else {
+            return false;
+        };
+        key.verify_prehash(hash.as_slice(), &signature).is_ok()
+    }
+}
+
+impl Precompile for P256VerifyPrecompile {
+    fn precompile_id(&self) -> &PrecompileId {
+        Self::id()
+    }
+
+    fn call(&self, input: PrecompileInput<'_>) -> PrecompileResult {
+        let reservoir = input.reservoir;
+        if input.gas < P256_VERIFY_GAS {
+            return Ok(PrecompileOutput::halt(PrecompileHalt::OutOfGas, reservoir));
+        }
+        let bytes = if Self::verify(input.data) {
+            Bytes::copy_from_slice(B256::with_last_byte(1).as_slice())
+        } else {
+            Bytes::new()
+        };
+        Ok(PrecompileOutput::new(P256_VERIFY_GAS, bytes, reservoir))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_evm::EvmInternals;
+    use alloy_primitives::U256;
+    use p256::ecdsa::{signature::hazmat::PrehashSigner, SigningKey};
+    use revm::{
+        context::{
+            journal::{Journal, JournalInner},
+            BlockEnv, CfgEnv, TxEnv,
+        },
+        database::{CacheDB, EmptyDB},
+        primitives::hardfork::SpecId,
+    };
+
+    const HASH: [u8; 32] = [0x11; 32];
+
+    fn run_call(data: &[u8], gas: u64) -> PrecompileOutput {
+        let mut journal =
+            Journal::new_with_inner(CacheDB::<EmptyDB>::default(), JournalInner::new());
+        journal.inner.set_spec_id(SpecId::PRAGUE);
+        let block_env = BlockEnv::default();
+        let cfg_env = CfgEnv::default();
+        let tx_env = TxEnv::default();
+        let input = PrecompileInput {
+            data,
+            gas,
+            reservoir: 0,
+            caller: Address::ZERO,
+            value: U256::ZERO,
+            target_address: P256_VERIFY_PRECOMPILE_ADDR,
+            is_static: true,
+            bytecode_address: P256_VERIFY_PRECOMPILE_ADDR,
+            internals: EvmInternals::new(&mut journal, &block_env, &cfg_env, &tx_env),
+        };
+        P256VerifyPrecompile::new()
+            .call(input)
+            .expect("no fatal error")
+    }
+
+    fn signed_input() -> Vec<u8> {
+        let key = SigningKey::from_slice(&[7u8; 32]).expect("valid key");
+        let signature: Signature = key.sign_prehash(&HASH).expect("signs");
+        let point = key.verifying_key().to_encoded_point(false);
+        let (r, s) = signature.split_bytes();
+        [
+            HASH.as_slice(),
+            r.as_slice(),
+            s.as_slice(),
+            point.x().expect("uncompressed point"),
+            point.y().expect("uncompressed point"),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn verifies_valid_signature() {
+        let output = run_call(&signed_input(), 100_000);
+        assert!(!output.is_halt());
+        assert_eq!(output.gas_used, P256_VERIFY_GAS);
+        assert_eq!(output.bytes.as_ref(), B256::with_last_byte(1).as_slice());
+    }
+
+    #[test]
+    fn returns_no_data_for_invalid_input() {
+        let mut tampered = signed_input();
+        tampered[0] ^= 1;
+        let output = run_call(&tampered, 100_000);
+        assert!(!output.is_halt());
+        assert_eq!(output.gas_used, P256_VERIFY_GAS);
+        assert!(output.bytes.is_empty());
+
+        let mut truncated = signed_input();
+        truncated.pop();
+        assert!(run_call(&truncated, 100_000).bytes.is_empty());
+
+        let mut off_curve = signed_input();
+        off_curve[159] ^= 1;
+        assert!(run_call(&off_curve, 100_000).bytes.is_empty());
+    }
+
+    #[test]
+    fn halts_without_enough_gas() {
+        assert!(run_call(&signed_input(), P256_VERIFY_GAS - 1).is_halt());
+    }
+}
@@ -0,0 +1,524 @@
+// Native staking precompile
+
+use crate::mint::{MintPrecompile, MintPrecompileError, MintPrecompileResult};
+use alloy::{
+    sol,
+    sol_types::{SolInterface, SolValue},
+};
+use alloy_evm::{
+    precompiles::{Precompile, PrecompileInput},
+    revm::precompile::{PrecompileId, PrecompileResult},
+    EvmInternals,
+};
+use alloy_primitives::{address, keccak256, Address, Bytes, U256};
+use revm::precompile::{PrecompileHalt, PrecompileOutput};
+use std::sync::OnceLock;
+
+sol! {
+    interface INativeStaking {
+        function deposit() external payable;
+        function requestWithdrawal(uint256 amount) external;
+        function claim() external returns (uint256);
+        function stakeOf(address account) external view returns (uint256);
+        function pendingWithdrawal(address account) external view returns (uint256 amount, uint64 releaseBlock);
+        function validators() external view returns (address[] memory);
+        function unbondingPeriod() external view returns (uint64);
+        function minimumStake() external view returns (uint256);
+    }
+}
+
+pub const STAKING_PRECOMPILE_ADDR: Address = address!("0x000000000000000000000000000000000000F106");
+
+/// Storage slot holding the number of validators in the set.
+pub const VALIDATOR_COUNT_SLOT: U256 = U256::ZERO;
+
+/// Storage slot holding the bonded stake of `account`.
+pub fn stake_slot(account: Address) -> U256 {
+    account_slot(account, 1)
+}
+
+/// Storage slot holding the position of `account` in the validator set plus one, zero when
+/// the account is not a validator.
+pub fn validator_index_slot(account: Address) -> U256 {
+    account_slot(account, 2)
+}
+
+/// Storage slot holding the amount `account` is unbonding.
+pub fn unbonding_amount_slot(account: Address) -> U256 {
+    account_slot(account, 3)
+}
+
+/// Storage slot holding the block from which the unbonding amount of `account` can be claimed.
+pub fn release_block_slot(account: Address) -> U256 {
+    account_slot(account, 4)
+}
+
+/// Storage slot holding the validator at `index` of the set.
+pub fn validator_slot(index: u64) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[24..32].copy_from_slice(&index.to_be_bytes());
+    preimage[63] = 5;
+    keccak256(preimage).into()
+}
+
+fn account_slot(account: Address, index: u8) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(account.into_word().as_slice());
+    preimage[63] = index;
+    keccak256(preimage).into()
+}
+
+/// Protocol-level staking for chains that elect sequencers or validators on-chain.
+///
+/// `deposit` bonds the native token sent with the call; an account whose stake reaches
+/// `minimum_stake` joins the validator set. `requestWithdrawal` unbonds part of the stake,
+/// removing the account from the set once it falls below the minimum, and `claim` pays the
+/// unbonded amount out after `unbonding_period` blocks. Bonded funds are held in the balance
+/// of the precompile account.
+#[derive(Clone, Debug, Default)]
+pub struct StakingPrecompile {
+    minimum_stake: U256,
+    unbonding_period: u64,
+}
+
+impl StakingPrecompile {
+    pub fn id() -> &'static PrecompileId {
+        static ID: OnceLock<PrecompileId> = OnceLock::new();
+        ID.get_or_init(|| PrecompileId::custom("native_staking"))
+    }
+
+    pub fn new(minimum_stake: U256, unbonding_period: u64) -> Self {
+        Self {
+            minimum_stake,
+            unbonding_period,
+        }
+    }
+
+    fn ensure_staking_account(internals: &mut EvmInternals<'_>) -> MintPrecompileResult<()> {
+        let account = internals
+            .load_account(STAKING_PRECOMPILE_ADDR)
+            .map_err(MintPrecompileError::fatal)?;
+        // Deposits fund the account before the first call, so it may exist without code.
+        if account.is_loaded_as_not_existing() || account.info.nonce == 0 {
+            MintPrecompile::mark_precompile_account(internals, STAKING_PRECOMPILE_ADDR)?;
+            internals
+                .touch_account(STAKING_PRECOMPILE_ADDR)
+                .map_err(MintPrecompileError::fatal)?;
+        }
+        Ok(())
+    }
+
+    fn read(internals: &mut EvmInternals<'_>, slot: U256) -> MintPrecompileResult<U256> {
+        Self::ensure_staking_account(internals)?;
+        let value = internals
+            .sload(STAKING_PRECOMPILE_ADDR, slot)
+            .map_err(MintPrecompileError::fatal)?;
+        Ok(*value)
+    }
+
+    fn write(
+        internals: &mut EvmInternals<'_>,
+        slot: U256,
+        value: U256,
+    ) -> MintPrecompileResult<()> {
+        Self::ensure_staking_account(internals)?;
+        internals
+            .sstore(STAKING_PRECOMPILE_ADDR, slot, value)
+            .map_err(MintPrecompileError::fatal)?;
+        internals
+            .touch_account(STAKING_PRECOMPILE_ADDR)
+            .map_err(MintPrecompileError::fatal)?;
+        Ok(())
+    }
+
+    fn validators(internals: &mut EvmInternals<'_>) -> MintPrecompileResult<Vec<Address>> {
+        let count: u64 = Self::read(internals, VALIDATOR_COUNT_SLOT)?.saturating_to();
+        (0..count)
+            .map(|index| {
+                let word = Self::read(internals, validator_slot(index))?;
+                Ok(Address::from_word(word.into()))
+            })
+            .collect()
+    }
+
+    fn add_validator(
+        internals: &mut EvmInternals<'_>,
+        account: Address,
+    ) -> MintPrecompileResult<()> {
+        if !Self::read(internals, validator_index_slot(account))?.is_zero() {
+            return Ok(());
+        }
+        let count: u64 = Self::read(internals, VALIDATOR_COUNT_SLOT)?.saturating_to();
+        Self::write(
+            internals,
+            validator_slot(count),
+            U256::from_be_bytes(account.into_word().0),
+        )?;
+        Self::write(
+            internals,
+            validator_index_slot(account),
+            U256::from(count + 1),
+        )?;
+        Self::write(internals, VALIDATOR_COUNT_SLOT, U256::from(count + 1))
+    }
+
+    /// Removes `account` from the set by moving the last validator into its position.
+    fn remove_validator(
+        internals: &mut EvmInternals<'_>,
+        account: Address,
+    ) -> MintPrecompileResult<()> {
+        let position: u64 = Self::read(internals, validator_index_slot(account))?.saturating_to();
+        if position == 0 {
+            return Ok(());
+        }
+        let count: u64 = Self::read(internals, VALIDATOR_COUNT_SLOT)?.saturating_to();
+        let last = count - 1;
+        if position - 1 != last {
+            let moved = Self::read(internals, validator_slot(last))?;
+            Self::write(internals, validator_slot(position - 1), moved)?;
+            Self::write(
+                internals,
+                validator_index_slot(Address::from_word(moved.into())),
+                U256::from(position),
+            )?;
+        }
+        Self::write(internals, validator_slot(last), U256::ZERO)?;
+        Self::write(internals, validator_index_slot(account), U256::ZERO)?;
+        Self::write(internals, VALIDATOR_COUNT_SLOT, U256::from(last))
+    }
+
+    fn deposit(
+        &self,
+        internals: &mut EvmInternals<'_>,
+        account: Address,
+        amount: U256,
+    ) -> MintPrecompileResult<()> {
+        if amount.is_zero() {
+            return Err(MintPrecompileError::halt_static("zero deposit"));
+        }
+        let stake = Self::read(internals, stake_slot(account))?
+            .checked_add(amount)
+            .ok_or_else(|| MintPrecompileError::halt_static("stake overflow"))?;
+        Self::write(internals, stake_slot(account), stake)?;
+        if stake >= self.minimum_stake {
+            Self::add_validator(internals, account)?;
+        }
+
+        tracing::debug!(
+            target: "staking_precompile",
+            ?account,
+            %amount,
+            %stake,
+            "stake deposited"
+        );
+        Ok(())
+    }
+
+    fn request_withdrawal(
+        &self,
+        internals: &mut EvmInternals<'_>,
+        account: Address,
+        amount: U256,
+    ) -> MintPrecompileResult<()> {
+        if amount.is_zero() {
+            return Err(MintPrecompileError::halt_static("zero withdrawal"));
+        }
+        let stake = Self::read(internals, stake_slot(account))?;
+        let remaining = stake
+            .checked_sub(amount)
+            .ok_or_else(|| MintPrecompileError::halt_static("insufficient stake"))?;
+        Self::write(internals, stake_slot(account), remaining)?;
+        if remaining < self.minimum_stake {
+            Self::remove_validator(internals, account)?;
+        }
+
+        // A new request adds to the pending amount and restarts its unbonding period.
+        let unbonding = Self::read(internals, unbonding_amount_slot(account))? + amount;
+        let block_number: u64 = internals.block_number().saturating_to();
+        let release_block = block_number.saturating_add(self.unbonding_period);
+        Self::write(internals, unbonding_amount_slot(account), unbonding)?;
+        Self::write(
+            internals,
+            release_block_slot(account),
+            U256::from(release_block),
+        )?;
+
+        tracing::debug!(
+            target: "staking_precompile",
+            ?account,
+            %amount,
+            %unbonding,
+            release_block,
+            "withdrawal requested"
+        );
+        Ok(())
+    }
+
+    fn claim(internals: &mut EvmInternals<'_>, account: Address) -> MintPrecompileResult<U256> {
+        let amount = Self::read(internals, unbonding_amount_slot(account))?;
+        if amount.is_zero() {
+            return Err(MintPrecompileError::halt_static("nothing to claim"));
+        }
+        let release_block: u64 =
+            Self::read(internals, release_block_slot(account))?.saturating_to();
+        let block_number: u64 = internals.block_number().saturating_to();
+        if block_number < release_block {
+            return Err(MintPrecompileError::halt_static(
+                "withdrawal still unbonding",
+            ));
+        }
+
+        Self::write(internals, unbonding_amount_slot(account), U256::ZERO)?;
+        Self::write(internals, release_block_slot(account), U256::ZERO)?;
+        MintPrecompile::debit(internals, STAKING_PRECOMPILE_ADDR, amount)?;
+        MintPrecompile::credit(internals, account, amount)?;
+
+        tracing::debug!(
+            target: "staking_precompile",
+            ?account,
+            %amount,
+            "withdrawal claimed"
+        );
+        Ok(amount)
+    }
+}
+
+impl Precompile for StakingPrecompile {
+    fn precompile_id(&self) -> &PrecompileId {
+        Self::id()
+    }
+
+    fn call(&self, mut input: PrecompileInput<'_>) -> PrecompileResult {
+        let caller = input.caller;
+        let value = input.value;
+        let is_static = input.is_static;
+        let reservoir = input.reservoir;
+
+        let decoded = match INativeStaking::INativeStakingCalls::abi_decode(input.data) {
+            Ok(v) => v,
+            Err(e) => {
+                return Ok(PrecompileOutput::halt(
+                    PrecompileHalt::other(e.to_string()),
+                    reservoir,
+                ))
+            }
+        };
+        let mutates = matches!(
+            decoded,
+            INativeStaking::INativeStakingCalls::deposit(_)
+                | INativeStaking::INativeStakingCalls::requestWithdrawal(_)
+                | INativeStaking::INativeStakingCalls::claim(_)
+        );
+        let internals = input.internals_mut();
+
+        let result = (|| -> MintPrecompileResult<Bytes> {
+            if mutates && is_static {
+                return Err(MintPrecompileError::halt_static(
+                    "state change in static call",
+                ));
+            }
+            if !value.is_zero()
+                && !matches!(decoded, INativeStaking::INativeStakingCalls::deposit(_))
+            {
+                return Err(MintPrecompileError::halt_static("function is not payable"));
+            }
+            match decoded {
+                INativeStaking::INativeStakingCalls::deposit(_) => {
+                    self.deposit(internals, caller, value)?;
+                    Ok(Bytes::new())
+                }
+                INativeStaking::INativeStakingCalls::requestWithdrawal(call) => {
+                    self.request_withdrawal(internals, caller, call.amount)?;
+                    Ok(Bytes::new())
+                }
+                INativeStaking::INativeStakingCalls::claim(_) => {
+                    let amount = Self::claim(internals, caller)?;
+                    Ok(amount.abi_encode().into())
+                }
+                INativeStaking::INativeStakingCalls::stakeOf(call) => {
+                    let stake = Self::read(internals, stake_slot(call.account))?;
+                    Ok(stake.abi_encode().into())
+                }
+                INativeStaking::INativeStakingCalls::pendingWithdrawal(call) => {
+                    let amount = Self::read(internals, unbonding_amount_slot(call.account))?;
+                    let release_block: u64 =
+                        Self::read(internals, release_block_slot(call.account))?.saturating_to();
+                    Ok((amount, release_block).abi_encode_params().into())
+                }
+                INativeStaking::INativeStakingCalls::validators(_) => {
+                    let validators = Self::validators(internals)?;
+                    Ok(validators.abi_encode().into())
+                }
+                INativeStaking::INativeStakingCalls::unbondingPeriod(_) => {
+                    Ok(self.unbonding_period.abi_encode().into())
+                }
+                INativeStaking::INativeStakingCalls::minimumStake(_) => {
+                    Ok(self.minimum_stake.abi_encode().into())
+                }
+            }
+        })();
+
+        match result {
+            Ok(bytes) => Ok(PrecompileOutput::new(0, bytes, reservoir)),
+            Err(MintPrecompileError::Halt(reason)) => Ok(PrecompileOutput::halt(reason, reservoir)),
+            Err(MintPrecompileError::Fatal(err)) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolCall;
+    use revm::{
+        context::{
+            journal::{Journal, JournalInner},
+            BlockEnv, CfgEnv, TxEnv,
+        },
+        database::{CacheDB, EmptyDB},
+        primitives::hardfork::SpecId,
+        state::AccountInfo,
+    };
+
+    type TestJournal = Journal<CacheDB<EmptyDB>>;
+
+    const ALICE: Address = address!("0x00000000000000000000000000000000000000a1");
+    const BOB: Address = address!("0x00000000000000000000000000000000000000b1");
+    const MINIMUM: u64 = 100;
+    const PERIOD: u64 = 10;
+
+    /// Funds the precompile account as the value transfers of the test deposits would.
+    fn setup_journal() -> TestJournal {
+        let mut db = CacheDB::default();
+        db.insert_account_info(
+            STAKING_PRECOMPILE_ADDR,
+            AccountInfo {
+                balance: U256::from(1_000),
+                ..Default::default()
+            },
+        );
+        let mut journal = Journal::new_with_inner(db, JournalInner::new());
+        journal.inner.set_spec_id(SpecId::PRAGUE);
+        journal
+    }
+
+    fn run_call(
+        journal: &mut TestJournal,
+        block_number: u64,
+        caller: Address,
+        value: u64,
+        data: &[u8],
+    ) -> PrecompileOutput {
+        let block_env = BlockEnv {
+            number: U256::from(block_number),
+            ..Default::default()
+        };
+        let cfg_env = CfgEnv::default();
+        let tx_env = TxEnv::default();
+        let input = PrecompileInput {
+            data,
+            gas: 1_000_000,
+            reservoir: 0,
+            caller,
+            value: U256::from(value),
+            target_address: STAKING_PRECOMPILE_ADDR,
+            is_static: false,
+            bytecode_address: STAKING_PRECOMPILE_ADDR,
+            internals: EvmInternals::new(journal, &block_env, &cfg_env, &tx_env),
+        };
+        StakingPrecompile::new(U256::from(MINIMUM), PERIOD)
+            .call(input)
+            .expect("no fatal error")
+    }
+
+    fn validators(journal: &mut TestJournal) -> Vec<Address> {
+        let output = run_call(
+            journal,
+            0,
+            ALICE,
+            0,
+            &INativeStaking::validatorsCall {}.abi_encode(),
+        );
+        Vec::<Address>::abi_decode(&output.bytes).unwrap()
+    }
+
+    fn balance(journal: &mut TestJournal, account: Address) -> U256 {
+        journal.load_account(account).unwrap().data.info.balance
+    }
+
+    #[test]
+    fn deposits_reaching_minimum_join_validator_set() {
+        let mut journal = setup_journal();
+        let deposit = INativeStaking::depositCall {}.abi_encode();
+
+        assert!(!run_call(&mut journal, 1, ALICE, MINIMUM - 1, &deposit).is_halt());
+        assert!(validators(&mut journal).is_empty());
+
+        assert!(!run_call(&mut journal, 1, ALICE, 1, &deposit).is_halt());
+        assert!(!run_call(&mut journal, 1, BOB, MINIMUM, &deposit).is_halt());
+        assert_eq!(validators(&mut journal), vec![ALICE, BOB]);
+
+        let stake = run_call(
+            &mut journal,
+            1,
+            BOB,
+            0,
+            &INativeStaking::stakeOfCall { account: ALICE }.abi_encode(),
+        );
+        assert_eq!(U256::abi_decode(&stake.bytes).unwrap(), U256::from(MINIMUM));
+    }
+
+    #[test]
+    fn withdrawals_unbond_before_claim() {
+        let mut journal = setup_journal();
+        let deposit = INativeStaking::depositCall {}.abi_encode();
+        run_call(&mut journal, 1, ALICE, MINIMUM, &deposit);
+        run_call(&mut journal, 1, BOB, MINIMUM, &deposit);
+
+        let withdraw = INativeStaking::requestWithdrawalCall {
+            amount: U256::from(40),
+        }
+        .abi_encode();
+        assert!(!run_call(&mut journal, 5, ALICE, 0, &withdraw).is_halt());
+        // Alice fell below the minimum and Bob took her place in the set.
+        assert_eq!(validators(&mut journal), vec![BOB]);
+
+        let claim = INativeStaking::claimCall {}.abi_encode();
+        assert!(run_call(&mut journal, 5 + PERIOD - 1, ALICE, 0, &claim).is_halt());
+
+        let output = run_call(&mut journal, 5 + PERIOD, ALICE, 0, &claim);
+        assert!(!output.is_halt());
+        assert_eq!(U256::abi_decode(&output.bytes).unwrap(), U256::from(40));
+        assert_eq!(balance(&mut journal, ALICE), U256::from(40));
+        assert_eq!(
+            balance(&mut journal, STAKING_PRECOMPILE_ADDR),
+            U256::from(960)
+        );
+        assert!(run_call(&mut journal, 5 + PERIOD, ALICE, 0, &claim).is_halt());
+    }
+
+    #[test]
+    fn rejects_overdrawn_and_non_payable_calls() {
+        let mut journal = setup_journal();
+        run_call(
+            &mut journal,
+            1,
+            ALICE,
+            MINIMUM,
+            &INativeStaking::depositCall {}.abi_encode(),
+        );
+
+        let overdraw = INativeStaking::requestWithdrawalCall {
+            amount: U256::from(MINIMUM + 1),
+        }
+        .abi_encode();
+        assert!(run_call(&mut journal, 2, ALICE, 0, &overdraw).is_halt());
+
+        let with_value = INativeStaking::requestWithdrawalCall {
+            amount: U256::from(1),
+        }
+        .abi_encode();
+        assert!(run_call(&mut journal, 2, ALICE, 1, &with_value).is_halt());
+        assert_eq!(validators(&mut journal), vec![ALICE]);
+    }
+}
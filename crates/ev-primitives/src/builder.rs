@@ -0,0 +1,468 @@
+//! Builder and signing helpers for EvNode transactions.
+//!
+//! The executor signs the transaction with domain `0x76` over everything except the fee payer
+//! slot; a sponsor signs with domain `0x78` over the same fields plus the executor address and
+//! its `max_sponsor_fee`. Neither signature covers the other, so the executor and the sponsor
+//! can sign in either order.
+//!
+//! # Examples
+//!
+//! A self-paid batch of two calls:
+//!
+//! ```
+//! use alloy_primitives::{address, Bytes, TxKind, U256};
+//! use alloy_signer_local::PrivateKeySigner;
+//! use ev_primitives::EvNodeTransactionBuilder;
+//!
+//! let executor = PrivateKeySigner::random();
+//! let envelope = EvNodeTransactionBuilder::new(1234)
+//!     .nonce(0)
+//!     .gas_limit(100_000)
+//!     .max_fee_per_gas(2_000_000_000)
+//!     .max_priority_fee_per_gas(1_000_000_000)
+//!     .add_call(
+//!         TxKind::Call(address!("0x000000000000000000000000000000000000beef")),
+//!         U256::from(1),
+//!         Bytes::new(),
+//!     )
+//!     .add_call(
+//!         TxKind::Call(address!("0x000000000000000000000000000000000000cafe")),
+//!         U256::ZERO,
+//!         Bytes::from_static(&[0xde, 0xad]),
+//!     )
+//!     .sign_executor(&executor)
+//!     .unwrap();
+//! // `envelope.encoded_2718()` is ready for `eth_sendRawTransaction`.
+//! # use alloy_eips::eip2718::Encodable2718;
+//! # assert_eq!(envelope.encoded_2718()[0], ev_primitives::EVNODE_TX_TYPE_ID);
+//! ```
+//!
+//! A sponsored transaction where the sponsor signs first and pays at most `max_sponsor_fee`;
+//! the executor covers any gas fees above the cap:
+//!
+//! ```
+//! use alloy_primitives::{Address, Bytes, TxKind, U256};
+//! use alloy_signer_local::PrivateKeySigner;
+//! use ev_primitives::{EvNodeTransactionBuilder, EvTxEnvelope};
+//!
+//! let executor = PrivateKeySigner::random();
+//! let sponsor = PrivateKeySigner::random();
+//! let envelope = EvNodeTransactionBuilder::new(1234)
+//!     .gas_limit(100_000)
+//!     .max_fee_per_gas(2_000_000_000)
+//!     .add_call(
+//!         TxKind::Call(Address::repeat_byte(0x42)),
+//!         U256::ZERO,
+//!         Bytes::new(),
+//!     )
+//!     .max_sponsor_fee(100_000_000_000_000)
+//!     .sign_sponsor(executor.address(), &sponsor)
+//!     .unwrap()
+//!     .sign_executor(&executor)
+//!     .unwrap();
+//!
+//! let EvTxEnvelope::EvNode(signed) = &envelope else {
+//!     unreachable!()
+//! };
+//! assert_eq!(
+//!     signed.tx().fee_payer(executor.address()),
+//!     Some(sponsor.address())
+//! );
+//! ```
+//!
+//! The executor can also sign first and hand the envelope to a sponsoring service, which
+//! adds its signature with [`sponsor_envelope`]:
+//!
+//! ```
+//! use alloy_primitives::{Address, Bytes, TxKind, U256};
+//! use alloy_signer_local::PrivateKeySigner;
+//! use ev_primitives::{sponsor_envelope, EvNodeTransactionBuilder};
+//!
+//! let executor = PrivateKeySigner::random();
+//! let sponsor = PrivateKeySigner::random();
+//! let unsponsored = EvNodeTransactionBuilder::new(1234)
+//!     .gas_limit(100_000)
+//!     .max_fee_per_gas(2_000_000_000)
+//!     .add_call(
+//!         TxKind::Call(Address::repeat_byte(0x42)),
+//!         U256::ZERO,
+//!         Bytes::new(),
+//!     )
+//!     .sign_executor(&executor)
+//!     .unwrap();
+//! let sponsored = sponsor_envelope(unsponsored, &sponsor).unwrap();
+//! # let ev_primitives::EvTxEnvelope::EvNode(signed) = &sponsored else { unreachable!() };
+//! # assert_eq!(signed.tx().fee_payer(executor.address()), Some(sponsor.address()));
+//! ```
+//!
+//! A contract implementing `IGasAllowance` pays instead of an ECDSA sponsor:
+//!
+//! ```
+//! use alloy_primitives::{Address, Bytes, TxKind, U256};
+//! use alloy_signer_local::PrivateKeySigner;
+//! use ev_primitives::{EvNodeTransactionBuilder, EvTxEnvelope};
+//!
+//! let paymaster = Address::repeat_byte(0x77);
+//! let envelope = EvNodeTransactionBuilder::new(1234)
+//!     .gas_limit(100_000)
+//!     .max_fee_per_gas(2_000_000_000)
+//!     .add_call(
+//!         TxKind::Call(Address::repeat_byte(0x42)),
+//!         U256::ZERO,
+//!         Bytes::new(),
+//!     )
+//!     .contract_fee_payer(paymaster)
+//!     .sign_executor(&PrivateKeySigner::random())
+//!     .unwrap();
+//!
+//! let EvTxEnvelope::EvNode(signed) = &envelope else {
+//!     unreachable!()
+//! };
+//! assert_eq!(signed.tx().contract_fee_payer(), Some(paymaster));
+//! ```
+//!
+//! A session key acting for a primary account within a signed scope; the primary account is
+//! the transaction's sender:
+//!
+//! ```
+//! use alloy_consensus::transaction::SignerRecoverable;
+//! use alloy_primitives::{Address, Bytes, TxKind, U256};
+//! use alloy_signer_local::PrivateKeySigner;
+//! use ev_primitives::{EvNodeTransactionBuilder, SessionAuthorization};
+//!
+//! let primary = PrivateKeySigner::random();
+//! let session_key = PrivateKeySigner::random();
+//! let game = Address::repeat_byte(0x42);
+//! let session = SessionAuthorization {
+//!     chain_id: 1234,
+//!     session_key: session_key.address(),
+//!     targets: vec![game],
+//!     max_value: U256::ZERO,
+//!     expiry: 1_900_000_000,
+//! }
+//! .sign(&primary)
+//! .unwrap();
+//! let envelope = EvNodeTransactionBuilder::new(1234)
+//!     .gas_limit(100_000)
+//!     .max_fee_per_gas(2_000_000_000)
+//!     .add_call(TxKind::Call(game), U256::ZERO, Bytes::new())
+//!     .session(session)
+//!     .sign_executor(&session_key)
+//!     .unwrap();
+//!
+//! assert_eq!(envelope.recover_signer().unwrap(), primary.address());
+//! ```
+
+use crate::{
+    contract_fee_payer_signature, Call, EvNodeTransaction, EvTxEnvelope, RevertMode,
+    SignedSessionAuthorization,
+};
+use alloy_consensus::Signed;
+use alloy_eips::eip2930::AccessList;
+use alloy_primitives::{aliases::U192, Address, Bytes, TxKind, U256};
+use alloy_signer::SignerSync;
+
+/// Builds an [`EvNodeTransaction`] and signs it for broadcasting.
+///
+/// Fee fields default to zero and the access list to empty. See the
+/// [module documentation](self) for the signing flows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvNodeTransactionBuilder {
+    tx: EvNodeTransaction,
+}
+
+impl EvNodeTransactionBuilder {
+    /// Starts a transaction for the given chain.
+    pub const fn new(chain_id: u64) -> Self {
+        Self {
+            tx: EvNodeTransaction {
+                chain_id,
+                nonce: 0,
+                nonce_key: U192::ZERO,
+                max_priority_fee_per_gas: 0,
+                max_fee_per_gas: 0,
+                gas_limit: 0,
+                calls: Vec::new(),
+                access_list: AccessList(Vec::new()),
+                revert_mode: RevertMode::Atomic,
+                valid_after: None,
+                valid_until: None,
+                session: None,
+                fee_payer_signature: None,
+                max_sponsor_fee: None,
+                p256_signature: None,
+            },
+        }
+    }
+
+    /// Sets the executor nonce.
+    pub const fn nonce(mut self, nonce: u64) -> Self {
+        self.tx.nonce = nonce;
+        self
+    }
+
+    /// Sends the transaction in the given nonce lane; [`Self::nonce`] then sets the sequence
+    /// within that lane.
+    pub const fn nonce_key(mut self, nonce_key: U192) -> Self {
+        self.tx.nonce_key = nonce_key;
+        self
+    }
+
+    /// Sets the gas limit shared by all calls.
+    pub const fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.tx.gas_limit = gas_limit;
+        self
+    }
+
+    /// Sets the EIP-1559 total fee cap.
+    pub const fn max_fee_per_gas(mut self, max_fee_per_gas: u128) -> Self {
+        self.tx.max_fee_per_gas = max_fee_per_gas;
+        self
+    }
+
+    /// Sets the EIP-1559 priority fee cap.
+    pub const fn max_priority_fee_per_gas(mut self, max_priority_fee_per_gas: u128) -> Self {
+        self.tx.max_priority_fee_per_gas = max_priority_fee_per_gas;
+        self
+    }
+
+    /// Appends a call to the batch. Only the first call may be a contract creation.
+    pub fn add_call(self, to: TxKind, value: U256, input: Bytes) -> Self {
+        self.call(Call {
+            to,
+            value,
+            input,
+            gas_limit: None,
+        })
+    }
+
+    /// Appends a prepared [`Call`] to the batch.
+    pub fn call(mut self, call: Call) -> Self {
+        self.tx.calls.push(call);
+        self
+    }
+
+    /// Deploys `initcode` as the first call of the batch, endowing the new contract with
+    /// `value`.
+    pub fn create(self, initcode: impl Into<Bytes>, value: U256) -> Self {
+        self.call(Call::deploy(initcode, value))
+    }
+
+    /// Sets the EIP-2930 access list.
+    pub fn access_list(mut self, access_list: AccessList) -> Self {
+        self.tx.access_list = access_list;
+        self
+    }
+
+    /// Sets how the batch handles a failing call; batches are atomic by default.
+    pub const fn revert_mode(mut self, revert_mode: RevertMode) -> Self {
+        self.tx.revert_mode = revert_mode;
+        self
+    }
+
+    /// Makes the transaction invalid in blocks before `timestamp`.
+    pub const fn valid_after(mut self, timestamp: u64) -> Self {
+        self.tx.valid_after = Some(timestamp);
+        self
+    }
+
+    /// Makes the transaction invalid in blocks after `timestamp`, bounding how long its
+    /// sponsor signature can be used.
+    pub const fn valid_until(mut self, timestamp: u64) -> Self {
+        self.tx.valid_until = Some(timestamp);
+        self
+    }
+
+    /// Attaches a session authorization, making its primary account the executor.
+    ///
+    /// The transaction must then be signed with [`Self::sign_executor`] by the session key,
+    /// and sponsors sign for the primary account.
+    pub fn session(mut self, session: SignedSessionAuthorization) -> Self {
+        self.tx.session = Some(session);
+        self
+    }
+
+    /// Caps the gas fees paid by the sponsor; the executor pays any excess.
+    ///
+    /// Must be set before [`Self::sign_sponsor`], since the sponsor signature covers it.
+    pub const fn max_sponsor_fee(mut self, max_sponsor_fee: u128) -> Self {
+        self.tx.max_sponsor_fee = Some(max_sponsor_fee);
+        self
+    }
+
+    /// Designates a contract implementing `IGasAllowance` as the fee payer.
+    pub fn contract_fee_payer(mut self, fee_payer: Address) -> Self {
+        self.tx.fee_payer_signature = Some(contract_fee_payer_signature(fee_payer));
+        self
+    }
+
+    /// Signs the sponsorship of the transaction for `executor`.
+    ///
+    /// Any later change to the transaction fields invalidates the sponsor signature.
+    pub fn sign_sponsor<S>(mut self, executor: Address, signer: &S) -> alloy_signer::Result<Self>
+    where
+        S: SignerSync + ?Sized,
+    {
+        let signature = signer.sign_hash_sync(&self.tx.sponsor_signing_hash(executor))?;
+        self.tx.fee_payer_signature = Some(signature);
+        Ok(self)
+    }
+
+    /// Has `sponsor` pay the gas fees of `executor`'s transaction up to `max_sponsor_fee`.
+    ///
+    /// Shorthand for [`Self::max_sponsor_fee`] followed by [`Self::sign_sponsor`], so it must
+    /// come after every other field is set.
+    pub fn with_sponsor<S>(
+        self,
+        executor: Address,
+        sponsor: &S,
+        max_sponsor_fee: u128,
+    ) -> alloy_signer::Result<Self>
+    where
+        S: SignerSync + ?Sized,
+    {
+        self.max_sponsor_fee(max_sponsor_fee)
+            .sign_sponsor(executor, sponsor)
+    }
+
+    /// Signs the transaction as the executor, producing a ready-to-broadcast envelope.
+    pub fn sign_executor<S>(self, signer: &S) -> alloy_signer::Result<EvTxEnvelope>
+    where
+        S: SignerSync + ?Sized,
+    {
+        let signature = signer.sign_hash_sync(&self.tx.executor_signing_hash())?;
+        Ok(EvTxEnvelope::EvNode(Signed::new_unhashed(
+            self.tx, signature,
+        )))
+    }
+
+    /// Returns the unsigned transaction.
+    pub fn build(self) -> EvNodeTransaction {
+        self.tx
+    }
+}
+
+impl From<EvNodeTransaction> for EvNodeTransactionBuilder {
+    fn from(tx: EvNodeTransaction) -> Self {
+        Self { tx }
+    }
+}
+
+/// Adds `signer`'s sponsorship to an EvNode envelope already signed by the executor.
+///
+/// The executor signature stays valid since it does not cover the fee payer slot; the
+/// transaction hash changes. Envelopes of other transaction types are returned unchanged.
+pub fn sponsor_envelope<S>(envelope: EvTxEnvelope, signer: &S) -> alloy_signer::Result<EvTxEnvelope>
+where
+    S: SignerSync + ?Sized,
+{
+    let EvTxEnvelope::EvNode(signed) = envelope else {
+        return Ok(envelope);
+    };
+    let (mut tx, executor_signature, _) = signed.into_parts();
+    let executor = tx
+        .recover_executor(&executor_signature)
+        .map_err(alloy_signer::Error::other)?;
+    tx.fee_payer_signature = Some(signer.sign_hash_sync(&tx.sponsor_signing_hash(executor))?);
+    Ok(EvTxEnvelope::EvNode(Signed::new_unhashed(
+        tx,
+        executor_signature,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::transaction::{SignerRecoverable, TxHashRef};
+    use alloy_signer_local::PrivateKeySigner;
+
+    fn builder() -> EvNodeTransactionBuilder {
+        EvNodeTransactionBuilder::new(1234)
+            .nonce(3)
+            .gas_limit(100_000)
+            .max_fee_per_gas(2_000_000_000)
+            .max_priority_fee_per_gas(1_000_000_000)
+            .add_call(
+                TxKind::Call(Address::repeat_byte(0x42)),
+                U256::from(7),
+                Bytes::new(),
+            )
+    }
+
+    #[test]
+    fn signing_order_does_not_matter() {
+        let executor = PrivateKeySigner::random();
+        let sponsor = PrivateKeySigner::random();
+
+        let sponsor_first = builder()
+            .max_sponsor_fee(1_000)
+            .sign_sponsor(executor.address(), &sponsor)
+            .unwrap()
+            .sign_executor(&executor)
+            .unwrap();
+        let executor_first = sponsor_envelope(
+            builder()
+                .max_sponsor_fee(1_000)
+                .sign_executor(&executor)
+                .unwrap(),
+            &sponsor,
+        )
+        .unwrap();
+
+        for envelope in [&sponsor_first, &executor_first] {
+            assert_eq!(envelope.recover_signer().unwrap(), executor.address());
+            let EvTxEnvelope::EvNode(signed) = envelope else {
+                panic!("expected an EvNode envelope");
+            };
+            assert_eq!(
+                signed.tx().fee_payer(executor.address()),
+                Some(sponsor.address())
+            );
+        }
+        // ECDSA signing is deterministic (RFC 6979), so both flows yield the same bytes.
+        assert_eq!(sponsor_first.tx_hash(), executor_first.tx_hash());
+    }
+
+    #[test]
+    fn sponsored_create_round_trips_through_2718() {
+        use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+
+        let executor = PrivateKeySigner::random();
+        let sponsor = PrivateKeySigner::random();
+        let envelope = EvNodeTransactionBuilder::new(1234)
+            .gas_limit(500_000)
+            .max_fee_per_gas(2_000_000_000)
+            .create(
+                Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]),
+                U256::ZERO,
+            )
+            .with_sponsor(executor.address(), &sponsor, 10_000)
+            .unwrap()
+            .sign_executor(&executor)
+            .unwrap();
+
+        let decoded = EvTxEnvelope::decode_2718(&mut envelope.encoded_2718().as_slice()).unwrap();
+        assert_eq!(decoded.tx_hash(), envelope.tx_hash());
+        let EvTxEnvelope::EvNode(signed) = decoded else {
+            panic!("expected an EvNode envelope");
+        };
+        assert_eq!(signed.tx().calls[0].to, TxKind::Create);
+        assert_eq!(signed.tx().max_sponsor_fee, Some(10_000));
+        assert_eq!(
+            signed.tx().fee_payer(executor.address()),
+            Some(sponsor.address())
+        );
+    }
+
+    #[test]
+    fn changing_fields_after_sponsoring_voids_the_sponsorship() {
+        let executor = PrivateKeySigner::random();
+        let sponsor = PrivateKeySigner::random();
+        let tx = builder()
+            .sign_sponsor(executor.address(), &sponsor)
+            .unwrap()
+            .gas_limit(200_000)
+            .build();
+        assert_ne!(tx.fee_payer(executor.address()), Some(sponsor.address()));
+    }
+}
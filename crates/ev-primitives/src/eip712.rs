@@ -0,0 +1,55 @@
+//! EIP-712 typed data for sponsor authorizations.
+//!
+//! Wallets that sign typed data show the sponsor a [`Sponsorship`] field by field instead of
+//! an opaque hash. What they have no natural way to display, such as the access list, the
+//! revert mode, call gas limits or a session authorization, is bound through `payloadHash`:
+//! the keccak256 of the RLP payload with an unsigned fee payer slot, the same payload signed
+//! under [`SponsorHashScheme::FeePayer`](crate::SponsorHashScheme::FeePayer).
+
+use alloy_primitives::U256;
+use alloy_sol_types::Eip712Domain;
+use std::borrow::Cow;
+
+/// EIP-712 domain name of sponsor authorizations.
+pub const EVNODE_SPONSOR_EIP712_NAME: &str = "EvNode Sponsorship";
+
+/// EIP-712 domain version of sponsor authorizations.
+pub const EVNODE_SPONSOR_EIP712_VERSION: &str = "1";
+
+alloy_sol_types::sol! {
+    /// Call of a sponsored batch. Contract creations target the zero address.
+    #[derive(Debug, PartialEq, Eq)]
+    struct SponsorCall {
+        address to;
+        uint256 value;
+        bytes data;
+    }
+
+    /// Sponsorship of an EvNode batch sent by `executor`. `maxSponsorFee` is the maximum
+    /// uint256 when the sponsor pays the whole fee, and `validUntil` the maximum uint64 when
+    /// the sponsorship does not expire.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Sponsorship {
+        address executor;
+        uint256 nonce;
+        uint256 gasLimit;
+        uint256 maxFeePerGas;
+        uint256 maxPriorityFeePerGas;
+        uint256 maxSponsorFee;
+        SponsorCall[] calls;
+        uint256 validAfter;
+        uint256 validUntil;
+        bytes32 payloadHash;
+    }
+}
+
+/// Returns the EIP-712 domain of sponsor authorizations on `chain_id`.
+pub const fn sponsor_eip712_domain(chain_id: u64) -> Eip712Domain {
+    Eip712Domain::new(
+        Some(Cow::Borrowed(EVNODE_SPONSOR_EIP712_NAME)),
+        Some(Cow::Borrowed(EVNODE_SPONSOR_EIP712_VERSION)),
+        Some(U256::from_limbs([chain_id, 0, 0, 0])),
+        None,
+        None,
+    )
+}
@@ -1,13 +1,33 @@
 //! EV-specific primitive types, including the EvNode 0x76 transaction.
 
+mod builder;
+mod eip712;
+mod passkey;
 mod pool;
+mod session;
 mod tx;
+mod tx_type;
 
+pub use builder::{sponsor_envelope, EvNodeTransactionBuilder};
+pub use eip712::{
+    sponsor_eip712_domain, SponsorCall, Sponsorship, EVNODE_SPONSOR_EIP712_NAME,
+    EVNODE_SPONSOR_EIP712_VERSION,
+};
+pub use passkey::{
+    p256_executor_marker, P256Signature, WebAuthnAssertion, P256_SIGNATURE_TYPE,
+    WEBAUTHN_SIGNATURE_TYPE,
+};
 pub use pool::{EvPooledTxEnvelope, EvPooledTxType};
+pub use session::{
+    SessionAuthorization, SessionScopeError, SignedSessionAuthorization, EVNODE_SESSION_DOMAIN,
+};
 pub use tx::{
-    Call, EvNodeSignedTx, EvNodeTransaction, EvTxEnvelope, EvTxType, TransactionSigned,
+    contract_fee_payer_signature, Call, CallsSummary, CrossDomainSignature, EvNodeSignedTx,
+    EvNodeTransaction, EvNodeTxDisabled, EvTxEnvelope, EvTxType, MaxBatchCalls, RevertMode,
+    SponsorHashScheme, TooManyBatchCalls, TransactionSigned, ValidityWindow, ValidityWindowError,
     EVNODE_SPONSOR_DOMAIN, EVNODE_TX_TYPE_ID,
 };
+pub use tx_type::{EvNodeTxTypeAlias, EvNodeTxTypeError};
 
 use reth_primitives_traits::NodePrimitives;
 
@@ -0,0 +1,310 @@
+//! P256 executor signatures for EvNode transactions.
+//!
+//! Instead of a secp256k1 signature, the executor may sign [`EvNodeTransaction::executor_signing_hash`]
+//! with a P256 (secp256r1) key, either directly or through a WebAuthn authenticator such as a
+//! passkey. The signature travels in the transaction as a [`P256Signature`] whose first element
+//! is the signature type byte, and the executor is the address of its public key, derived like
+//! a secp256k1 address: the last 20 bytes of `keccak256(x || y)`.
+//!
+//! A WebAuthn authenticator signs `sha256(authenticatorData || sha256(clientDataJSON))`; the
+//! assertion is accepted when the client data is a `webauthn.get` whose challenge is the
+//! unpadded base64url encoding of the executor signing hash and the authenticator reports the
+//! user as present.
+//!
+//! [`EvNodeTransaction::executor_signing_hash`]: crate::EvNodeTransaction::executor_signing_hash
+
+use alloy_primitives::{Address, Bytes, Signature, SignatureError, B256, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use p256::{
+    ecdsa::{
+        signature::hazmat::{PrehashSigner, PrehashVerifier},
+        SigningKey, VerifyingKey,
+    },
+    EncodedPoint,
+};
+use sha2::{Digest, Sha256};
+
+/// Signature type byte of a P256 signature over the executor signing hash itself.
+pub const P256_SIGNATURE_TYPE: u8 = 0x01;
+/// Signature type byte of a WebAuthn assertion whose challenge is the executor signing hash.
+pub const WEBAUTHN_SIGNATURE_TYPE: u8 = 0x02;
+
+/// Shortest authenticator data: the RP ID hash, the flags byte and the signature counter.
+const MIN_AUTHENTICATOR_DATA_LEN: usize = 37;
+/// Position of the flags byte in the authenticator data.
+const AUTHENTICATOR_FLAGS_INDEX: usize = 32;
+/// User-present flag of the authenticator data.
+const USER_PRESENT_FLAG: u8 = 0x01;
+
+/// Builds the executor signature a P256-signed EvNode transaction carries in place of a
+/// secp256k1 signature.
+///
+/// `r` and `s` are both zero, which is never a valid ECDSA signature, so the marker cannot be
+/// mistaken for a real executor signature.
+pub fn p256_executor_marker() -> Signature {
+    Signature::new(U256::ZERO, U256::ZERO, false)
+}
+
+/// Authenticator output of a WebAuthn assertion.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebAuthnAssertion {
+    /// Authenticator data, starting with the RP ID hash and the flags byte.
+    pub authenticator_data: Bytes,
+    /// Client data JSON, holding the challenge the authenticator signed.
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: Bytes,
+}
+
+impl WebAuthnAssertion {
+    /// Returns the digest the authenticator signs if the assertion is for `challenge`.
+    fn signed_digest(&self, challenge: B256) -> Result<[u8; 32], SignatureError> {
+        let flags = self
+            .authenticator_data
+            .get(AUTHENTICATOR_FLAGS_INDEX)
+            .filter(|_| self.authenticator_data.len() >= MIN_AUTHENTICATOR_DATA_LEN)
+            .ok_or(SignatureError::FromBytes("authenticator data too short"))?;
+        if flags & USER_PRESENT_FLAG == 0 {
+            return Err(SignatureError::FromBytes("authenticator user not present"));
+        }
+        let client_data = core::str::from_utf8(&self.client_data_json)
+            .map_err(|_| SignatureError::FromBytes("client data is not UTF-8"))?;
+        if !client_data.contains(r#""type":"webauthn.get""#) {
+            return Err(SignatureError::FromBytes(
+                "client data is not a webauthn.get",
+            ));
+        }
+        let challenge = format!(r#""challenge":"{}""#, URL_SAFE_NO_PAD.encode(challenge));
+        if !client_data.contains(&challenge) {
+            return Err(SignatureError::FromBytes(
+                "client data challenge is not the signing hash",
+            ));
+        }
+        Ok(Sha256::new()
+            .chain_update(&self.authenticator_data)
+            .chain_update(Sha256::digest(client_data))
+            .finalize()
+            .into())
+    }
+}
+
+/// P256 signature of the executor, raw or through a WebAuthn authenticator.
+///
+/// RLP-encoded as `[type, x, y, r, s]` for [`P256_SIGNATURE_TYPE`] and
+/// `[type, x, y, r, s, authenticatorData, clientDataJSON]` for [`WEBAUTHN_SIGNATURE_TYPE`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct P256Signature {
+    /// X coordinate of the public key.
+    pub x: B256,
+    /// Y coordinate of the public key.
+    pub y: B256,
+    /// Signature `r`.
+    pub r: B256,
+    /// Signature `s`, in the lower half of the curve order.
+    pub s: B256,
+    /// WebAuthn assertion the signature is over, or `None` for a signature over the signing
+    /// hash itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webauthn: Option<WebAuthnAssertion>,
+}
+
+impl P256Signature {
+    /// Signs `hash` with `key`.
+    pub fn sign(hash: B256, key: &SigningKey) -> Result<Self, p256::ecdsa::Error> {
+        let signature: p256::ecdsa::Signature = key.sign_prehash(hash.as_slice())?;
+        let signature = signature.normalize_s().unwrap_or(signature);
+        let point = key.verifying_key().to_encoded_point(false);
+        let (r, s) = signature.split_bytes();
+        Ok(Self {
+            x: B256::from_slice(point.x().expect("uncompressed point")),
+            y: B256::from_slice(point.y().expect("uncompressed point")),
+            r: B256::from_slice(&r),
+            s: B256::from_slice(&s),
+            webauthn: None,
+        })
+    }
+
+    /// Returns the signature type byte.
+    pub const fn signature_type(&self) -> u8 {
+        if self.webauthn.is_some() {
+            WEBAUTHN_SIGNATURE_TYPE
+        } else {
+            P256_SIGNATURE_TYPE
+        }
+    }
+
+    /// Returns the address of the public key.
+    pub fn address(&self) -> Address {
+        let mut public_key = [0u8; 64];
+        public_key[..32].copy_from_slice(self.x.as_slice());
+        public_key[32..].copy_from_slice(self.y.as_slice());
+        Address::from_raw_public_key(&public_key)
+    }
+
+    /// Verifies the signature over `hash` and returns the signer address.
+    ///
+    /// Signatures with a high `s` are rejected, so that a signature has a single encoding.
+    pub fn recover(&self, hash: B256) -> Result<Address, SignatureError> {
+        let point =
+            EncodedPoint::from_affine_coordinates(&self.x.0.into(), &self.y.0.into(), false);
+        let key = VerifyingKey::from_encoded_point(&point)
+            .map_err(|_| SignatureError::FromBytes("invalid P256 public key"))?;
+        let signature = p256::ecdsa::Signature::from_scalars(self.r.0, self.s.0)
+            .map_err(|_| SignatureError::FromBytes("invalid P256 signature"))?;
+        if signature.normalize_s().is_some() {
+            return Err(SignatureError::FromBytes("P256 signature s is too high"));
+        }
+        let digest = match &self.webauthn {
+            Some(assertion) => assertion.signed_digest(hash)?,
+            None => hash.0,
+        };
+        key.verify_prehash(&digest, &signature)
+            .map_err(|_| SignatureError::FromBytes("P256 signature does not match"))?;
+        Ok(self.address())
+    }
+
+    fn payload_length(&self) -> usize {
+        self.signature_type().length()
+            + self.x.length()
+            + self.y.length()
+            + self.r.length()
+            + self.s.length()
+            + self.webauthn.as_ref().map_or(0, |assertion| {
+                assertion.authenticator_data.length() + assertion.client_data_json.length()
+            })
+    }
+}
+
+impl Encodable for P256Signature {
+    fn length(&self) -> usize {
+        Header {
+            list: true,
+            payload_length: self.payload_length(),
+        }
+        .length_with_payload()
+    }
+
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header {
+            list: true,
+            payload_length: self.payload_length(),
+        }
+        .encode(out);
+        self.signature_type().encode(out);
+        self.x.encode(out);
+        self.y.encode(out);
+        self.r.encode(out);
+        self.s.encode(out);
+        if let Some(assertion) = &self.webauthn {
+            assertion.authenticator_data.encode(out);
+            assertion.client_data_json.encode(out);
+        }
+    }
+}
+
+impl Decodable for P256Signature {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut payload = Header::decode_bytes(buf, true)?;
+        let signature_type = u8::decode(&mut payload)?;
+        let x = Decodable::decode(&mut payload)?;
+        let y = Decodable::decode(&mut payload)?;
+        let r = Decodable::decode(&mut payload)?;
+        let s = Decodable::decode(&mut payload)?;
+        let webauthn = match signature_type {
+            P256_SIGNATURE_TYPE => None,
+            WEBAUTHN_SIGNATURE_TYPE => Some(WebAuthnAssertion {
+                authenticator_data: Decodable::decode(&mut payload)?,
+                client_data_json: Decodable::decode(&mut payload)?,
+            }),
+            _ => return Err(alloy_rlp::Error::Custom("unknown P256 signature type")),
+        };
+        if !payload.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+        Ok(Self {
+            x,
+            y,
+            r,
+            s,
+            webauthn,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    const HASH: B256 = b256!("0x1111111111111111111111111111111111111111111111111111111111111111");
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_slice(&[7u8; 32]).expect("valid key")
+    }
+
+    fn webauthn_signature(client_data: &str, flags: u8) -> P256Signature {
+        let mut authenticator_data = vec![0u8; MIN_AUTHENTICATOR_DATA_LEN];
+        authenticator_data[AUTHENTICATOR_FLAGS_INDEX] = flags;
+        let digest: [u8; 32] = Sha256::new()
+            .chain_update(&authenticator_data)
+            .chain_update(Sha256::digest(client_data))
+            .finalize()
+            .into();
+        let mut signature = P256Signature::sign(B256::from(digest), &signing_key()).unwrap();
+        signature.webauthn = Some(WebAuthnAssertion {
+            authenticator_data: authenticator_data.into(),
+            client_data_json: Bytes::copy_from_slice(client_data.as_bytes()),
+        });
+        signature
+    }
+
+    #[test]
+    fn recovers_p256_signer() {
+        let signature = P256Signature::sign(HASH, &signing_key()).unwrap();
+        assert_eq!(signature.recover(HASH), Ok(signature.address()));
+        assert!(signature.recover(B256::ZERO).is_err());
+
+        let encoded = alloy_rlp::encode(&signature);
+        assert_eq!(encoded[1], P256_SIGNATURE_TYPE);
+        assert_eq!(
+            P256Signature::decode(&mut encoded.as_slice()).unwrap(),
+            signature
+        );
+    }
+
+    #[test]
+    fn rejects_high_s() {
+        let mut signature = P256Signature::sign(HASH, &signing_key()).unwrap();
+        let order = U256::from_be_bytes(
+            b256!("0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551").0,
+        );
+        let high_s = order - U256::from_be_bytes(signature.s.0);
+        signature.s = B256::from(high_s.to_be_bytes());
+        assert!(signature.recover(HASH).is_err());
+    }
+
+    #[test]
+    fn recovers_webauthn_signer() {
+        let challenge = URL_SAFE_NO_PAD.encode(HASH);
+        let client_data = format!(
+            r#"{{"type":"webauthn.get","challenge":"{challenge}","origin":"https://wallet.example"}}"#
+        );
+        let signature = webauthn_signature(&client_data, USER_PRESENT_FLAG);
+        assert_eq!(signature.signature_type(), WEBAUTHN_SIGNATURE_TYPE);
+        assert_eq!(signature.recover(HASH), Ok(signature.address()));
+        assert!(signature.recover(B256::ZERO).is_err());
+
+        let encoded = alloy_rlp::encode(&signature);
+        assert_eq!(
+            P256Signature::decode(&mut encoded.as_slice()).unwrap(),
+            signature
+        );
+
+        let absent = webauthn_signature(&client_data, 0);
+        assert!(absent.recover(HASH).is_err());
+        let create = webauthn_signature(&client_data.replace("get", "create"), USER_PRESENT_FLAG);
+        assert!(create.recover(HASH).is_err());
+    }
+}
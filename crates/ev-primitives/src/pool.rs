@@ -27,6 +27,7 @@ pub enum EvPooledTxEnvelope {
     #[envelope(flatten)]
     Ethereum(reth_ethereum_primitives::PooledTransactionVariant),
     /// EvNode typed transaction (no sidecar).
+    /// The derive takes a literal, which must equal [`crate::EVNODE_TX_TYPE_ID`].
     #[envelope(ty = 0x76)]
     EvNode(EvNodeSignedTx),
 }
@@ -45,8 +46,8 @@ impl SignerRecoverable for EvPooledTxEnvelope {
         match self {
             EvPooledTxEnvelope::Ethereum(tx) => tx.recover_signer(),
             EvPooledTxEnvelope::EvNode(tx) => tx
-                .signature()
-                .recover_address_from_prehash(&tx.tx().executor_signing_hash())
+                .tx()
+                .recover_executor(tx.signature())
                 .map_err(|_| alloy_consensus::crypto::RecoveryError::new()),
         }
     }
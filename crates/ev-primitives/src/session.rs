@@ -0,0 +1,284 @@
+//! Scoped session key authorizations for EvNode transactions.
+//!
+//! A primary account signs a [`SessionAuthorization`] with domain `0x79`, delegating to a
+//! session key the right to send EvNode transactions on its behalf within a scope: the calls
+//! may only target the listed contracts, transfer at most `max_value` in total and be included
+//! up to the `expiry` timestamp. A transaction carrying the signed authorization is signed by
+//! the session key, and the primary account becomes its executor, paying for it and using its
+//! nonce. A session ends at its expiry; the primary account cannot revoke it earlier.
+
+use crate::Call;
+use alloy_primitives::{keccak256, Address, Signature, SignatureError, B256, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header, RlpDecodable, RlpEncodable};
+use alloy_signer::SignerSync;
+
+/// Signature domain for session authorizations.
+pub const EVNODE_SESSION_DOMAIN: u8 = 0x79;
+
+/// Length of the primary account signature in an encoded [`SignedSessionAuthorization`].
+const SESSION_SIGNATURE_LEN: usize = 65;
+
+/// Scope within which a session key may act for the primary account that signs it.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    RlpEncodable,
+    RlpDecodable,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAuthorization {
+    /// Chain ID the authorization is bound to.
+    #[serde(with = "alloy_serde::quantity")]
+    pub chain_id: u64,
+    /// Key that signs the transactions.
+    pub session_key: Address,
+    /// Contracts the calls may target. Session keys can never deploy contracts.
+    pub targets: Vec<Address>,
+    /// Most a single transaction may transfer across all of its calls.
+    pub max_value: U256,
+    /// Last block timestamp at which transactions are accepted.
+    #[serde(with = "alloy_serde::quantity")]
+    pub expiry: u64,
+}
+
+impl SessionAuthorization {
+    /// Returns the hash the primary account signs: `keccak256(0x79 || rlp(authorization))`.
+    pub fn signing_hash(&self) -> B256 {
+        let mut preimage = Vec::with_capacity(1 + self.length());
+        preimage.push(EVNODE_SESSION_DOMAIN);
+        self.encode(&mut preimage);
+        keccak256(preimage)
+    }
+
+    /// Signs the authorization as the primary account.
+    pub fn sign<S>(self, signer: &S) -> alloy_signer::Result<SignedSessionAuthorization>
+    where
+        S: SignerSync + ?Sized,
+    {
+        let signature = signer.sign_hash_sync(&self.signing_hash())?;
+        Ok(SignedSessionAuthorization {
+            authorization: self,
+            signature,
+        })
+    }
+
+    /// Checks that `calls`, sent on `chain_id` in a block at `timestamp`, stay within scope.
+    pub fn check_scope(
+        &self,
+        chain_id: u64,
+        calls: &[Call],
+        timestamp: u64,
+    ) -> Result<(), SessionScopeError> {
+        if self.chain_id != chain_id {
+            return Err(SessionScopeError::ChainId {
+                authorized: self.chain_id,
+                chain_id,
+            });
+        }
+        if timestamp > self.expiry {
+            return Err(SessionScopeError::Expired {
+                expiry: self.expiry,
+            });
+        }
+        let mut value = U256::ZERO;
+        for call in calls {
+            let Some(to) = call.to.to() else {
+                return Err(SessionScopeError::Create);
+            };
+            if !self.targets.contains(to) {
+                return Err(SessionScopeError::Target(*to));
+            }
+            value = value.saturating_add(call.value);
+        }
+        if value > self.max_value {
+            return Err(SessionScopeError::Value {
+                value,
+                max_value: self.max_value,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Session authorization signed by the primary account.
+///
+/// RLP-encoded as `[authorization, signature]`, the signature being the 65-byte `r || s || v`
+/// string also used for sponsor signatures.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedSessionAuthorization {
+    /// Scope delegated to the session key.
+    #[serde(flatten)]
+    pub authorization: SessionAuthorization,
+    /// Primary account signature over [`SessionAuthorization::signing_hash`].
+    pub signature: Signature,
+}
+
+impl SignedSessionAuthorization {
+    /// Recovers the primary account that signed the authorization.
+    pub fn recover_account(&self) -> Result<Address, SignatureError> {
+        self.signature
+            .recover_address_from_prehash(&self.authorization.signing_hash())
+    }
+
+    fn payload_length(&self) -> usize {
+        self.authorization.length() + self.signature.as_bytes().as_slice().length()
+    }
+}
+
+impl Encodable for SignedSessionAuthorization {
+    fn length(&self) -> usize {
+        Header {
+            list: true,
+            payload_length: self.payload_length(),
+        }
+        .length_with_payload()
+    }
+
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header {
+            list: true,
+            payload_length: self.payload_length(),
+        }
+        .encode(out);
+        self.authorization.encode(out);
+        self.signature.as_bytes().as_slice().encode(out);
+    }
+}
+
+impl Decodable for SignedSessionAuthorization {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut payload = Header::decode_bytes(buf, true)?;
+        let authorization = SessionAuthorization::decode(&mut payload)?;
+        let signature = Header::decode_bytes(&mut payload, false)?;
+        if !payload.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+        let raw: [u8; SESSION_SIGNATURE_LEN] = signature
+            .try_into()
+            .map_err(|_| alloy_rlp::Error::UnexpectedLength)?;
+        let signature = Signature::from_raw_array(&raw)
+            .map_err(|_| alloy_rlp::Error::Custom("invalid signature bytes"))?;
+        Ok(Self {
+            authorization,
+            signature,
+        })
+    }
+}
+
+/// Why calls fall outside the scope of a session authorization.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SessionScopeError {
+    /// The authorization is for another chain.
+    #[error("session authorization is for chain {authorized}, not {chain_id}")]
+    ChainId {
+        /// Chain ID in the authorization.
+        authorized: u64,
+        /// Chain ID of the transaction.
+        chain_id: u64,
+    },
+    /// The authorization expired before the block.
+    #[error("session authorization expired at {expiry}")]
+    Expired {
+        /// Expiry timestamp of the authorization.
+        expiry: u64,
+    },
+    /// A call deploys a contract.
+    #[error("session keys may not deploy contracts")]
+    Create,
+    /// A call targets a contract outside the authorization.
+    #[error("session key may not call {0}")]
+    Target(Address),
+    /// The calls transfer more than the authorization allows.
+    #[error("session transfers {value}, above its limit of {max_value}")]
+    Value {
+        /// Value transferred by the calls.
+        value: U256,
+        /// Most the authorization allows.
+        max_value: U256,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, TxKind};
+    use alloy_signer_local::PrivateKeySigner;
+
+    fn authorization() -> SessionAuthorization {
+        SessionAuthorization {
+            chain_id: 1234,
+            session_key: Address::repeat_byte(0x5e),
+            targets: vec![Address::repeat_byte(0xaa)],
+            max_value: U256::from(10),
+            expiry: 1_000,
+        }
+    }
+
+    #[test]
+    fn signed_authorization_roundtrip() {
+        let primary = PrivateKeySigner::random();
+        let signed = authorization().sign(&primary).unwrap();
+        assert_eq!(signed.recover_account().unwrap(), primary.address());
+
+        let encoded = alloy_rlp::encode(&signed);
+        assert_eq!(encoded.len(), signed.length());
+        let decoded = SignedSessionAuthorization::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, signed);
+
+        let json = serde_json::to_value(&signed).unwrap();
+        assert_eq!(
+            json["sessionKey"],
+            serde_json::json!(Address::repeat_byte(0x5e))
+        );
+        assert_eq!(json["expiry"], "0x3e8");
+    }
+
+    #[test]
+    fn scope_checks() {
+        let auth = authorization();
+        let call = |to: TxKind, value: u64| Call {
+            to,
+            value: U256::from(value),
+            input: Bytes::new(),
+            gas_limit: None,
+        };
+        let target = TxKind::Call(Address::repeat_byte(0xaa));
+
+        assert_eq!(
+            auth.check_scope(1234, &[call(target, 4), call(target, 6)], 1_000),
+            Ok(())
+        );
+        assert_eq!(
+            auth.check_scope(1, &[call(target, 0)], 0),
+            Err(SessionScopeError::ChainId {
+                authorized: 1234,
+                chain_id: 1
+            })
+        );
+        assert_eq!(
+            auth.check_scope(1234, &[call(target, 0)], 1_001),
+            Err(SessionScopeError::Expired { expiry: 1_000 })
+        );
+        assert_eq!(
+            auth.check_scope(1234, &[call(TxKind::Create, 0)], 0),
+            Err(SessionScopeError::Create)
+        );
+        assert_eq!(
+            auth.check_scope(1234, &[call(TxKind::Call(Address::ZERO), 0)], 0),
+            Err(SessionScopeError::Target(Address::ZERO))
+        );
+        assert_eq!(
+            auth.check_scope(1234, &[call(target, 6), call(target, 6)], 0),
+            Err(SessionScopeError::Value {
+                value: U256::from(12),
+                max_value: U256::from(10)
+            })
+        );
+    }
+}
@@ -5,8 +5,9 @@ use alloy_consensus::{
     SignableTransaction, Transaction, TransactionEnvelope,
 };
 use alloy_eips::eip2930::AccessList;
-use alloy_primitives::{keccak256, Address, Bytes, Signature, TxKind, B256, U256};
+use alloy_primitives::{aliases::U192, keccak256, Address, Bytes, Signature, TxKind, B256, U256};
 use alloy_rlp::{bytes::Buf, BufMut, Decodable, Encodable, Header, RlpDecodable, RlpEncodable};
+use alloy_sol_types::{SolCall, SolStruct, SolValue};
 use reth_codecs::{
     alloy::transaction::{CompactEnvelope, Envelope, FromTxCompact, ToTxCompact},
     txtype::COMPACT_EXTENDED_IDENTIFIER_FLAG,
@@ -16,11 +17,202 @@ use reth_db_api::table::{Compress, Decompress};
 use reth_primitives_traits::InMemorySize;
 use std::vec::Vec;
 
+use crate::{
+    eip712::{sponsor_eip712_domain, SponsorCall, Sponsorship},
+    passkey::P256Signature,
+    session::SignedSessionAuthorization,
+};
+
 /// EIP-2718 transaction type for EvNode batch + sponsorship.
 pub const EVNODE_TX_TYPE_ID: u8 = 0x76;
 /// Signature domain for sponsor authorization.
 pub const EVNODE_SPONSOR_DOMAIN: u8 = 0x78;
 
+/// An EvNode transaction on a chain that disabled the EvNode transaction type via
+/// `evolve.enableEvNodeTx`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("evnode transaction type (0x76) is disabled on this chain")]
+pub struct EvNodeTxDisabled;
+
+/// Limit on the number of calls in one EvNode transaction, set via `evolve.maxBatchCalls`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxBatchCalls {
+    max_calls: u64,
+    activation_height: u64,
+}
+
+impl MaxBatchCalls {
+    /// Creates a limit of `max_calls` calls per transaction from `activation_height` onwards.
+    pub const fn new(max_calls: u64, activation_height: u64) -> Self {
+        Self {
+            max_calls,
+            activation_height,
+        }
+    }
+
+    /// Returns the maximum number of calls per transaction.
+    pub const fn max_calls(&self) -> u64 {
+        self.max_calls
+    }
+
+    /// Returns the first block the limit applies to.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns whether the limit applies at `block_number`.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+
+    /// Checks the number of calls of `tx` against the limit at `block_number`.
+    pub fn check(
+        &self,
+        tx: &EvNodeTransaction,
+        block_number: u64,
+    ) -> Result<(), TooManyBatchCalls> {
+        let calls = tx.calls.len() as u64;
+        if self.is_active(block_number) && calls > self.max_calls {
+            return Err(TooManyBatchCalls {
+                calls,
+                max_calls: self.max_calls,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// An EvNode transaction carrying more calls than the chain's `evolve.maxBatchCalls`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("evnode transaction has {calls} calls, above the maximum of {max_calls}")]
+pub struct TooManyBatchCalls {
+    /// Number of calls in the transaction.
+    pub calls: u64,
+    /// Maximum number of calls per transaction.
+    pub max_calls: u64,
+}
+
+/// An EvNode signature that also verifies in the other signing domain.
+///
+/// Reported by [`EvNodeTransaction::check_signing_domains`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum CrossDomainSignature {
+    /// The fee payer slot carries the executor signature.
+    #[error("fee payer signature reuses the executor signature")]
+    ReusedSignature,
+    /// The executor signature also recovers to its signer under a sponsor signing hash.
+    #[error("executor signature also verifies in the {} sponsor domain", .0.as_str())]
+    ExecutorInSponsorDomain(SponsorHashScheme),
+    /// The fee payer signature also recovers to its signer under the executor signing hash.
+    #[error("fee payer signature also verifies in the executor domain")]
+    SponsorInExecutorDomain,
+}
+
+/// Preimage layout of the sponsor signing hash.
+///
+/// Both raw layouts use the [`EVNODE_SPONSOR_DOMAIN`] prefix; chains migrating from one to
+/// the other accept both during a transition window. Chains that activate EIP-712
+/// sponsorships accept them alongside the raw layout in force.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum SponsorHashScheme {
+    /// `keccak256(0x78 || executor || fields)`: the sponsorship is bound to the executor.
+    #[default]
+    Executor,
+    /// `keccak256(0x78 || rlp([fields, fee_payer_slot]))` with the signature left out of the
+    /// fee payer slot: the sponsorship binds the payload but not the executor.
+    FeePayer,
+    /// EIP-712 typed data over a [`Sponsorship`](crate::Sponsorship), bound to the executor
+    /// and readable in wallets.
+    Eip712,
+}
+
+impl SponsorHashScheme {
+    /// Returns the scheme name as used in chainspec extras.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Executor => "executor",
+            Self::FeePayer => "feePayer",
+            Self::Eip712 => "eip712",
+        }
+    }
+}
+
+/// How an EvNode batch handles a failing call.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub enum RevertMode {
+    /// A failing call reverts the whole batch.
+    #[default]
+    Atomic,
+    /// A failing call only reverts its own changes and the batch goes on with the next call.
+    /// The batch succeeds and every failed call is recorded in the receipt's logs.
+    BestEffort,
+}
+
+impl RevertMode {
+    /// Returns whether a failing call reverts the whole batch.
+    pub const fn is_atomic(&self) -> bool {
+        matches!(self, Self::Atomic)
+    }
+
+    /// Returns the mode name as used in JSON.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Atomic => "atomic",
+            Self::BestEffort => "bestEffort",
+        }
+    }
+}
+
+/// Block timestamps between which an EvNode transaction may be included, both inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ValidityWindow {
+    /// Earliest block timestamp at which the transaction is valid.
+    pub valid_after: u64,
+    /// Latest block timestamp at which the transaction is valid.
+    pub valid_until: u64,
+}
+
+impl ValidityWindow {
+    /// Checks that a block at `timestamp` falls within the window.
+    pub const fn check(&self, timestamp: u64) -> Result<(), ValidityWindowError> {
+        if timestamp < self.valid_after {
+            return Err(ValidityWindowError::NotYetValid {
+                valid_after: self.valid_after,
+            });
+        }
+        if timestamp > self.valid_until {
+            return Err(ValidityWindowError::Expired {
+                valid_until: self.valid_until,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Why a block falls outside the validity window of an EvNode transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ValidityWindowError {
+    /// The block is before the window opens.
+    #[error("transaction is not valid before timestamp {valid_after}")]
+    NotYetValid {
+        /// Earliest block timestamp at which the transaction is valid.
+        valid_after: u64,
+    },
+    /// The block is after the window closed.
+    #[error("transaction expired at timestamp {valid_until}")]
+    Expired {
+        /// Latest block timestamp at which the transaction was valid.
+        valid_until: u64,
+    },
+}
+
 /// Single call entry in an EvNode transaction.
 #[derive(
     Clone,
@@ -33,6 +225,8 @@ pub const EVNODE_SPONSOR_DOMAIN: u8 = 0x78;
     serde::Serialize,
     serde::Deserialize,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[rlp(trailing)]
 pub struct Call {
     /// Destination (CALL or CREATE).
     pub to: TxKind,
@@ -40,25 +234,190 @@ pub struct Call {
     pub value: U256,
     /// Calldata.
     pub input: Bytes,
+    /// Most execution gas the call may use, on top of its intrinsic gas. Uncapped calls may
+    /// use all gas left in the batch.
+    ///
+    /// Encoded as a fourth list element only when set, so uncapped calls keep their encoding
+    /// and signing hashes. Only valid once the chain activates per-call gas limits.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub gas_limit: Option<u64>,
+}
+
+impl Call {
+    /// Calls `to` with the ABI-encoded `call` and no value.
+    pub fn contract_call<C: SolCall>(to: Address, call: &C) -> Self {
+        Self::contract_call_with_value(to, call, U256::ZERO)
+    }
+
+    /// Calls `to` with the ABI-encoded `call`, transferring `value`.
+    pub fn contract_call_with_value<C: SolCall>(to: Address, call: &C, value: U256) -> Self {
+        Self {
+            to: TxKind::Call(to),
+            value,
+            input: call.abi_encode().into(),
+            gas_limit: None,
+        }
+    }
+
+    /// Transfers `value` to `to` without calldata.
+    pub const fn transfer(to: Address, value: U256) -> Self {
+        Self {
+            to: TxKind::Call(to),
+            value,
+            input: Bytes::new(),
+            gas_limit: None,
+        }
+    }
+
+    /// Deploys a contract from `initcode`, endowing it with `value`.
+    ///
+    /// Only the first call of a batch may be a deployment.
+    pub fn deploy(initcode: impl Into<Bytes>, value: U256) -> Self {
+        Self {
+            to: TxKind::Create,
+            value,
+            input: initcode.into(),
+            gas_limit: None,
+        }
+    }
+
+    /// Deploys a contract from `bytecode` followed by the ABI-encoded constructor arguments.
+    pub fn deploy_with_args<A: SolValue>(bytecode: &[u8], args: &A, value: U256) -> Self {
+        let mut initcode = bytecode.to_vec();
+        initcode.extend_from_slice(&args.abi_encode_params());
+        Self::deploy(initcode, value)
+    }
+
+    /// Caps the execution gas of the call at `gas_limit`.
+    pub const fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Returns whether this call deploys a contract.
+    pub const fn is_create(&self) -> bool {
+        matches!(self.to, TxKind::Create)
+    }
+}
+
+/// Aggregate view of every call of an EvNode batch.
+///
+/// [`Transaction::kind`] and [`Transaction::input`] only describe the first call of a batch,
+/// while [`Transaction::value`] sums all of them; tooling that renders a transaction as a
+/// single call should show this summary instead.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallsSummary {
+    /// Number of calls in the batch.
+    #[serde(with = "alloy_serde::quantity")]
+    pub call_count: u64,
+    /// Number of calls deploying a contract.
+    #[serde(with = "alloy_serde::quantity")]
+    pub create_count: u64,
+    /// Value transferred by all calls together.
+    pub total_value: U256,
+    /// Distinct call targets, in the order they are first called.
+    pub targets: Vec<Address>,
 }
 
 /// EvNode batch + sponsorship transaction payload.
+///
+/// This is the only definition of the 0x76 payload; the field order below is the RLP order
+/// (`nonce_key` shares the nonce field, `max_sponsor_fee` shares the fee payer slot,
+/// `revert_mode` is only encoded for best-effort batches, `session` only when set and
+/// `p256_signature` only when set, after the fee payer slot) and, together with the serde
+/// names, is
+/// consensus-critical. The `encoding_is_stable` test and the versioned golden vectors in
+/// `testdata/` pin both, so any change must come with a new transaction type or an explicit
+/// migration.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EvNodeTransaction {
+    /// Chain ID the transaction is bound to.
     #[serde(with = "alloy_serde::quantity")]
     pub chain_id: u64,
+    /// Executor nonce, or the sequence in the nonce lane named by [`Self::nonce_key`].
     #[serde(with = "alloy_serde::quantity")]
     pub nonce: u64,
+    /// Nonce lane of the transaction. Lane `0` is the executor's account nonce; any other
+    /// lane keeps its own sequence, so transactions in different lanes do not wait on each
+    /// other. Encoded in the upper 192 bits of the nonce field, like ERC-4337 2D nonces, so
+    /// lane-`0` transactions encode exactly as before; see [`Self::full_nonce`].
+    #[serde(default, skip_serializing_if = "U192::is_zero")]
+    pub nonce_key: U192,
+    /// EIP-1559 priority fee cap.
     #[serde(with = "alloy_serde::quantity")]
     pub max_priority_fee_per_gas: u128,
+    /// EIP-1559 total fee cap.
     #[serde(with = "alloy_serde::quantity")]
     pub max_fee_per_gas: u128,
+    /// Gas limit shared by all calls.
     #[serde(with = "alloy_serde::quantity")]
     pub gas_limit: u64,
+    /// Calls executed in order; unless [`Self::revert_mode`] is best-effort, the batch reverts
+    /// as a whole if any call fails.
     pub calls: Vec<Call>,
+    /// EIP-2930 access list.
     pub access_list: AccessList,
+    /// How the batch handles a failing call. Best-effort batches carry a one-element `[1]`
+    /// list before the fee payer slot, which is always a string; atomic batches encode
+    /// nothing, so their encoding and signing hashes are unchanged.
+    #[serde(default, skip_serializing_if = "RevertMode::is_atomic")]
+    pub revert_mode: RevertMode,
+    /// Earliest block timestamp at which the transaction may be included. Bound by the
+    /// executor and sponsor signing hashes; see [`Self::validity_window`].
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub valid_after: Option<u64>,
+    /// Latest block timestamp at which the transaction may be included, bounding how long a
+    /// sponsor signature can be used. Bound like [`Self::valid_after`].
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub valid_until: Option<u64>,
+    /// Session authorization of the primary account, when the transaction is signed by a
+    /// session key. Encoded as a list after the revert mode and validity window, neither of
+    /// which starts with a list; transactions without a session encode nothing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<SignedSessionAuthorization>,
+    /// Sponsor signature over [`Self::sponsor_signing_hash`], or a contract fee payer marker.
+    /// Excluded from the executor signing hash.
+    #[serde(default)]
     pub fee_payer_signature: Option<Signature>,
+    /// Most the sponsor pays in gas fees; the executor pays any excess. Encoded after the
+    /// sponsor signature in the fee payer slot and bound by the sponsor signing hash.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub max_sponsor_fee: Option<u128>,
+    /// P256 or WebAuthn signature of the executor over [`Self::executor_signing_hash`], for
+    /// executors without a secp256k1 key; the envelope then carries
+    /// [`p256_executor_marker`](crate::p256_executor_marker) as its signature. Encoded as a
+    /// list after the fee payer slot and covered by neither signing hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p256_signature: Option<P256Signature>,
+}
+
+/// Builds the fee-payer marker signature designating `fee_payer` as a contract sponsor.
+///
+/// See [`EvNodeTransaction::contract_fee_payer`].
+pub fn contract_fee_payer_signature(fee_payer: Address) -> Signature {
+    Signature::new(
+        U256::from_be_bytes(fee_payer.into_word().0),
+        U256::ZERO,
+        false,
+    )
 }
 
 /// Signed EvNode transaction (executor signature).
@@ -72,6 +431,7 @@ pub enum EvTxEnvelope {
     #[envelope(flatten)]
     Ethereum(reth_ethereum_primitives::TransactionSigned),
     /// EvNode typed transaction.
+    /// The derive takes a literal, which must equal [`EVNODE_TX_TYPE_ID`].
     #[envelope(ty = 0x76)]
     EvNode(EvNodeSignedTx),
 }
@@ -82,7 +442,7 @@ pub type TransactionSigned = EvTxEnvelope;
 impl EvNodeTransaction {
     /// Returns the executor signing hash (domain 0x76, empty sponsor fields).
     pub fn executor_signing_hash(&self) -> B256 {
-        let payload = self.encoded_payload(None);
+        let payload = self.encoded_payload(SponsorSlot::EMPTY);
         let mut preimage = Vec::with_capacity(1 + payload.len());
         preimage.push(EVNODE_TX_TYPE_ID);
         preimage.extend_from_slice(&payload);
@@ -91,19 +451,139 @@ impl EvNodeTransaction {
 
     /// Returns the sponsor signing hash (domain 0x78, executor address bound).
     pub fn sponsor_signing_hash(&self, executor: Address) -> B256 {
-        let payload = self.encoded_payload_with_executor(executor);
+        self.sponsor_signing_hash_with(SponsorHashScheme::Executor, executor)
+    }
+
+    /// Returns the sponsor signing hash under the given scheme. `executor` is ignored by
+    /// [`SponsorHashScheme::FeePayer`].
+    pub fn sponsor_signing_hash_with(&self, scheme: SponsorHashScheme, executor: Address) -> B256 {
+        let payload = match scheme {
+            SponsorHashScheme::Executor => self.encoded_payload_with_executor(executor),
+            SponsorHashScheme::FeePayer => self.encoded_payload(self.unsigned_sponsor_slot()),
+            SponsorHashScheme::Eip712 => return self.sponsor_signing_hash_eip712(executor),
+        };
         let mut preimage = Vec::with_capacity(1 + payload.len());
         preimage.push(EVNODE_SPONSOR_DOMAIN);
         preimage.extend_from_slice(&payload);
         keccak256(preimage)
     }
 
+    /// Returns the typed data a sponsor signs under [`SponsorHashScheme::Eip712`].
+    pub fn sponsorship_eip712(&self, executor: Address) -> Sponsorship {
+        Sponsorship {
+            executor,
+            nonce: self.full_nonce(),
+            gasLimit: U256::from(self.gas_limit),
+            maxFeePerGas: U256::from(self.max_fee_per_gas),
+            maxPriorityFeePerGas: U256::from(self.max_priority_fee_per_gas),
+            maxSponsorFee: self.max_sponsor_fee.map_or(U256::MAX, U256::from),
+            calls: self
+                .calls
+                .iter()
+                .map(|call| SponsorCall {
+                    to: call.to.to().copied().unwrap_or_default(),
+                    value: call.value,
+                    data: call.input.clone(),
+                })
+                .collect(),
+            validAfter: U256::from(self.valid_after.unwrap_or(0)),
+            validUntil: U256::from(self.valid_until.unwrap_or(u64::MAX)),
+            payloadHash: keccak256(self.encoded_payload(self.unsigned_sponsor_slot())),
+        }
+    }
+
+    /// Returns the EIP-712 sponsor signing hash of [`Self::sponsorship_eip712`] in the
+    /// sponsorship domain of the transaction's chain.
+    pub fn sponsor_signing_hash_eip712(&self, executor: Address) -> B256 {
+        self.sponsorship_eip712(executor)
+            .eip712_signing_hash(&sponsor_eip712_domain(self.chain_id))
+    }
+
+    /// Returns the address of the contract deployed by the leading CREATE call of the batch,
+    /// or `None` if the batch does not start with one.
+    ///
+    /// The executor is the deployer whether or not the transaction is sponsored, so the
+    /// address only depends on `executor` and the transaction's nonce. Nonce lane transactions
+    /// may not deploy contracts and return `None`.
+    pub fn deploy_address(&self, executor: Address) -> Option<Address> {
+        self.first_call()
+            .filter(|call| call.is_create() && self.nonce_lane().is_none())
+            .map(|_| executor.create(self.nonce))
+    }
+
+    /// Returns the number of calls, deployments, total value and distinct targets of the batch.
+    pub fn calls_summary(&self) -> CallsSummary {
+        let mut summary = CallsSummary::default();
+        for call in &self.calls {
+            summary.call_count += 1;
+            summary.total_value = summary.total_value.saturating_add(call.value);
+            match call.to {
+                TxKind::Call(to) if !summary.targets.contains(&to) => summary.targets.push(to),
+                TxKind::Call(_) => {}
+                TxKind::Create => summary.create_count += 1,
+            }
+        }
+        summary
+    }
+
+    /// Returns the nonce field as encoded: the [`Self::nonce_key`] in the upper 192 bits and
+    /// the [`Self::nonce`] in the lower 64.
+    pub fn full_nonce(&self) -> U256 {
+        let key = self.nonce_key.into_limbs();
+        U256::from_limbs([self.nonce, key[0], key[1], key[2]])
+    }
+
+    /// Returns the nonce lane of the transaction, or `None` for the executor's account nonce.
+    pub fn nonce_lane(&self) -> Option<U192> {
+        (!self.nonce_key.is_zero()).then_some(self.nonce_key)
+    }
+
+    /// Returns the block timestamps the transaction may be included between, or `None` when
+    /// it is valid at any time.
+    ///
+    /// Bounded windows are encoded as a `[valid_after, valid_until]` list after the revert
+    /// mode, with `0` and `u64::MAX` standing for a missing bound; unbounded transactions
+    /// encode nothing, so their encoding and signing hashes are unchanged.
+    pub fn validity_window(&self) -> Option<ValidityWindow> {
+        let window = ValidityWindow {
+            valid_after: self.valid_after.unwrap_or(0),
+            valid_until: self.valid_until.unwrap_or(u64::MAX),
+        };
+        (window != UNBOUNDED_WINDOW).then_some(window)
+    }
+
     /// Recovers the executor address from the provided signature.
+    ///
+    /// For a transaction carrying a [`Self::p256_signature`], `signature` must be the
+    /// [`p256_executor_marker`](crate::p256_executor_marker) and the signer is the address of
+    /// the P256 key. For a transaction carrying a session authorization, the signature must be
+    /// from the authorized session key and the executor is the primary account that signed the
+    /// authorization. Its scope is checked separately, against the including block.
     pub fn recover_executor(
         &self,
         signature: &Signature,
     ) -> Result<Address, alloy_primitives::SignatureError> {
-        signature.recover_address_from_prehash(&self.executor_signing_hash())
+        let hash = self.executor_signing_hash();
+        let signer = match &self.p256_signature {
+            None => signature.recover_address_from_prehash(&hash)?,
+            Some(p256) if signature.r().is_zero() && signature.s().is_zero() => {
+                p256.recover(hash)?
+            }
+            Some(_) => {
+                return Err(alloy_primitives::SignatureError::FromBytes(
+                    "P256-signed transaction carries a secp256k1 signature",
+                ))
+            }
+        };
+        match &self.session {
+            None => Ok(signer),
+            Some(session) if session.authorization.session_key == signer => {
+                session.recover_account()
+            }
+            Some(_) => Err(alloy_primitives::SignatureError::FromBytes(
+                "transaction is not signed by the session key",
+            )),
+        }
     }
 
     /// Recovers the sponsor address from the provided signature and executor address.
@@ -115,12 +595,118 @@ impl EvNodeTransaction {
         signature.recover_address_from_prehash(&self.sponsor_signing_hash(executor))
     }
 
+    /// Recovers the sponsor address under the given sponsor hash scheme.
+    pub fn recover_sponsor_with(
+        &self,
+        scheme: SponsorHashScheme,
+        executor: Address,
+        signature: &Signature,
+    ) -> Result<Address, alloy_primitives::SignatureError> {
+        signature.recover_address_from_prehash(&self.sponsor_signing_hash_with(scheme, executor))
+    }
+
+    /// Returns the contract fee payer designated by the fee-payer signature, if any.
+    ///
+    /// A contract cannot produce an ECDSA signature, so it is designated with a marker
+    /// signature whose `s` is zero and whose `r` is the left-padded, non-zero contract address.
+    /// A zero `s` is never a valid ECDSA signature, so the marker cannot collide with a real
+    /// sponsor.
+    pub fn contract_fee_payer(&self) -> Option<Address> {
+        let signature = self.fee_payer_signature.as_ref()?;
+        if !signature.s().is_zero() {
+            return None;
+        }
+        let word = B256::from(signature.r());
+        if word[..12].iter().any(|byte| *byte != 0) {
+            return None;
+        }
+        let fee_payer = Address::from_word(word);
+        (!fee_payer.is_zero()).then_some(fee_payer)
+    }
+
+    /// Returns the fee payer of the transaction: the designated contract fee payer or the
+    /// sponsor recovered from the fee-payer signature.
+    pub fn fee_payer(&self, executor: Address) -> Option<Address> {
+        self.contract_fee_payer().or_else(|| {
+            self.fee_payer_signature
+                .as_ref()
+                .and_then(|sig| self.recover_sponsor(executor, sig).ok())
+        })
+    }
+
+    /// Checks that neither signature of the transaction also verifies in the other signing
+    /// domain for the same key.
+    ///
+    /// Executor (`0x76`) and sponsor (`0x78`, EIP-712) signing hashes are built from distinct
+    /// preimages, so a signature can only recover to the same signer in both domains on a
+    /// hash collision; this asserts that they stay mutually exclusive under every
+    /// [`SponsorHashScheme`], and catches fee payer slots copying the executor signature.
+    /// P256 executor signatures and contract fee payers are not recovered and are skipped.
+    pub fn check_signing_domains(
+        &self,
+        executor: Address,
+        signature: &Signature,
+    ) -> Result<(), CrossDomainSignature> {
+        const SCHEMES: [SponsorHashScheme; 3] = [
+            SponsorHashScheme::Executor,
+            SponsorHashScheme::FeePayer,
+            SponsorHashScheme::Eip712,
+        ];
+        let executor_hash = self.executor_signing_hash();
+
+        if self.p256_signature.is_none() {
+            if let Ok(signer) = signature.recover_address_from_prehash(&executor_hash) {
+                for scheme in SCHEMES {
+                    let hash = self.sponsor_signing_hash_with(scheme, executor);
+                    if signature.recover_address_from_prehash(&hash) == Ok(signer) {
+                        return Err(CrossDomainSignature::ExecutorInSponsorDomain(scheme));
+                    }
+                }
+            }
+        }
+
+        let Some(fee_payer_signature) = self.fee_payer_signature.as_ref() else {
+            return Ok(());
+        };
+        if self.contract_fee_payer().is_some() {
+            return Ok(());
+        }
+        if fee_payer_signature == signature {
+            return Err(CrossDomainSignature::ReusedSignature);
+        }
+        let Ok(signer) = fee_payer_signature.recover_address_from_prehash(&executor_hash) else {
+            return Ok(());
+        };
+        for scheme in SCHEMES {
+            let hash = self.sponsor_signing_hash_with(scheme, executor);
+            if fee_payer_signature.recover_address_from_prehash(&hash) == Ok(signer) {
+                return Err(CrossDomainSignature::SponsorInExecutorDomain);
+            }
+        }
+        Ok(())
+    }
+
     fn first_call(&self) -> Option<&Call> {
         self.calls.first()
     }
 
-    fn encoded_payload(&self, fee_payer_signature: Option<&Signature>) -> Vec<u8> {
-        let payload_len = self.payload_fields_length(fee_payer_signature);
+    /// Returns the fee payer slot as covered by the sponsor signing hash.
+    const fn unsigned_sponsor_slot(&self) -> SponsorSlot<'static> {
+        SponsorSlot {
+            signature: None,
+            max_sponsor_fee: self.max_sponsor_fee,
+        }
+    }
+
+    const fn sponsor_slot(&self) -> SponsorSlot<'_> {
+        SponsorSlot {
+            signature: self.fee_payer_signature.as_ref(),
+            max_sponsor_fee: self.max_sponsor_fee,
+        }
+    }
+
+    fn encoded_payload(&self, sponsor_slot: SponsorSlot<'_>) -> Vec<u8> {
+        let payload_len = self.payload_fields_length(sponsor_slot);
         let mut out = Vec::with_capacity(
             Header {
                 list: true,
@@ -133,39 +719,52 @@ impl EvNodeTransaction {
             payload_length: payload_len,
         }
         .encode(&mut out);
-        self.encode_payload_fields(&mut out, fee_payer_signature);
+        self.encode_payload_fields(&mut out, sponsor_slot);
         out
     }
 
     fn encoded_payload_with_executor(&self, executor: Address) -> Vec<u8> {
         // Sponsor signatures must be computed over the unsigned sponsor field to avoid
         // self-referential hashing.
-        let mut out = Vec::with_capacity(self.payload_fields_length(None) + 32);
+        let sponsor_slot = self.unsigned_sponsor_slot();
+        let mut out = Vec::with_capacity(self.payload_fields_length(sponsor_slot) + 32);
         out.extend_from_slice(executor.as_slice());
-        self.encode_payload_fields(&mut out, None);
+        self.encode_payload_fields(&mut out, sponsor_slot);
         out
     }
 
-    fn payload_fields_length(&self, fee_payer_signature: Option<&Signature>) -> usize {
+    fn payload_fields_length(&self, sponsor_slot: SponsorSlot<'_>) -> usize {
         self.chain_id.length()
-            + self.nonce.length()
+            + self.full_nonce().length()
             + self.max_priority_fee_per_gas.length()
             + self.max_fee_per_gas.length()
             + self.gas_limit.length()
             + self.calls.length()
             + self.access_list.length()
-            + optional_signature_length(fee_payer_signature)
+            + revert_mode_length(self.revert_mode)
+            + self
+                .validity_window()
+                .map_or(0, |window| validity_window_length(&window))
+            + self.session.as_ref().map_or(0, Encodable::length)
+            + sponsor_slot.length()
     }
 
-    fn encode_payload_fields(&self, out: &mut dyn BufMut, fee_payer_signature: Option<&Signature>) {
+    fn encode_payload_fields(&self, out: &mut dyn BufMut, sponsor_slot: SponsorSlot<'_>) {
         self.chain_id.encode(out);
-        self.nonce.encode(out);
+        self.full_nonce().encode(out);
         self.max_priority_fee_per_gas.encode(out);
         self.max_fee_per_gas.encode(out);
         self.gas_limit.encode(out);
         self.calls.encode(out);
         self.access_list.encode(out);
-        encode_optional_signature(out, fee_payer_signature);
+        encode_revert_mode(self.revert_mode, out);
+        if let Some(window) = self.validity_window() {
+            encode_validity_window(&window, out);
+        }
+        if let Some(session) = &self.session {
+            session.encode(out);
+        }
+        sponsor_slot.encode(out);
     }
 }
 
@@ -222,6 +821,8 @@ impl Transaction for EvNodeTransaction {
         true
     }
 
+    /// Returns the destination of the first call; see [`EvNodeTransaction::calls_summary`] for
+    /// the whole batch.
     fn kind(&self) -> TxKind {
         self.first_call()
             .map(|call| call.to)
@@ -232,12 +833,15 @@ impl Transaction for EvNodeTransaction {
         matches!(self.first_call().map(|call| call.to), Some(TxKind::Create))
     }
 
+    /// Returns the value transferred by all calls together, saturating at `U256::MAX`.
     fn value(&self) -> U256 {
         self.calls
             .iter()
             .fold(U256::ZERO, |acc, call| acc.saturating_add(call.value))
     }
 
+    /// Returns the input of the first call; see [`EvNodeTransaction::calls_summary`] for the
+    /// whole batch.
     fn input(&self) -> &Bytes {
         static EMPTY: Bytes = Bytes::new();
         self.first_call().map(|call| &call.input).unwrap_or(&EMPTY)
@@ -271,19 +875,19 @@ impl SignableTransaction<Signature> for EvNodeTransaction {
 
     fn encode_for_signing(&self, out: &mut dyn BufMut) {
         out.put_u8(EVNODE_TX_TYPE_ID);
-        let payload_len = self.payload_fields_length(None);
+        let payload_len = self.payload_fields_length(SponsorSlot::EMPTY);
         Header {
             list: true,
             payload_length: payload_len,
         }
         .encode(out);
-        self.encode_payload_fields(out, None);
+        self.encode_payload_fields(out, SponsorSlot::EMPTY);
     }
 
     fn payload_len_for_signature(&self) -> usize {
         1 + Header {
             list: true,
-            payload_length: self.payload_fields_length(None),
+            payload_length: self.payload_fields_length(SponsorSlot::EMPTY),
         }
         .length_with_payload()
     }
@@ -291,11 +895,15 @@ impl SignableTransaction<Signature> for EvNodeTransaction {
 
 impl RlpEcdsaEncodableTx for EvNodeTransaction {
     fn rlp_encoded_fields_length(&self) -> usize {
-        self.payload_fields_length(self.fee_payer_signature.as_ref())
+        self.payload_fields_length(self.sponsor_slot())
+            + self.p256_signature.as_ref().map_or(0, Encodable::length)
     }
 
     fn rlp_encode_fields(&self, out: &mut dyn BufMut) {
-        self.encode_payload_fields(out, self.fee_payer_signature.as_ref());
+        self.encode_payload_fields(out, self.sponsor_slot());
+        if let Some(p256) = &self.p256_signature {
+            p256.encode(out);
+        }
     }
 }
 
@@ -303,15 +911,34 @@ impl RlpEcdsaDecodableTx for EvNodeTransaction {
     const DEFAULT_TX_TYPE: u8 = EVNODE_TX_TYPE_ID;
 
     fn rlp_decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let chain_id = Decodable::decode(buf)?;
+        let nonce = U256::decode(buf)?.into_limbs();
+        let max_priority_fee_per_gas = Decodable::decode(buf)?;
+        let max_fee_per_gas = Decodable::decode(buf)?;
+        let gas_limit = Decodable::decode(buf)?;
+        let calls = Decodable::decode(buf)?;
+        let access_list = Decodable::decode(buf)?;
+        let revert_mode = decode_revert_mode(buf)?;
+        let (valid_after, valid_until) = decode_validity_window(buf)?;
+        let session = decode_session(buf)?;
+        let (fee_payer_signature, max_sponsor_fee) = decode_sponsor_slot(buf)?;
+        let p256_signature = decode_p256_signature(buf)?;
         Ok(Self {
-            chain_id: Decodable::decode(buf)?,
-            nonce: Decodable::decode(buf)?,
-            max_priority_fee_per_gas: Decodable::decode(buf)?,
-            max_fee_per_gas: Decodable::decode(buf)?,
-            gas_limit: Decodable::decode(buf)?,
-            calls: Decodable::decode(buf)?,
-            access_list: Decodable::decode(buf)?,
-            fee_payer_signature: decode_optional_signature(buf)?,
+            chain_id,
+            nonce: nonce[0],
+            nonce_key: U192::from_limbs([nonce[1], nonce[2], nonce[3]]),
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            calls,
+            access_list,
+            revert_mode,
+            valid_after,
+            valid_until,
+            session,
+            fee_payer_signature,
+            max_sponsor_fee,
+            p256_signature,
         })
     }
 }
@@ -361,6 +988,41 @@ impl Compact for EvNodeTransaction {
     }
 }
 
+/// Generates transactions that survive an RLP round trip, for fuzzing the codecs.
+///
+/// Validity window bounds of `0` and `u64::MAX` are generated as missing, since both encode
+/// the same, and a `max_sponsor_fee` only alongside a fee payer signature, since the fee payer
+/// slot cannot carry it alone. Sessions and P256 signatures are not generated.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for EvNodeTransaction {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let valid_after: Option<u64> = u.arbitrary()?;
+        let valid_until: Option<u64> = u.arbitrary()?;
+        let fee_payer_signature: Option<Signature> = u.arbitrary()?;
+        let max_sponsor_fee = match fee_payer_signature {
+            Some(_) => u.arbitrary()?,
+            None => None,
+        };
+        Ok(Self {
+            chain_id: u.arbitrary()?,
+            nonce: u.arbitrary()?,
+            nonce_key: u.arbitrary()?,
+            max_priority_fee_per_gas: u.arbitrary()?,
+            max_fee_per_gas: u.arbitrary()?,
+            gas_limit: u.arbitrary()?,
+            calls: u.arbitrary()?,
+            access_list: u.arbitrary()?,
+            revert_mode: u.arbitrary()?,
+            valid_after: valid_after.filter(|after| *after != UNBOUNDED_WINDOW.valid_after),
+            valid_until: valid_until.filter(|until| *until != UNBOUNDED_WINDOW.valid_until),
+            session: None,
+            fee_payer_signature,
+            max_sponsor_fee,
+            p256_signature: None,
+        })
+    }
+}
+
 impl InMemorySize for Call {
     fn size(&self) -> usize {
         core::mem::size_of::<Self>() + self.input.len()
@@ -375,7 +1037,22 @@ impl InMemorySize for EvNodeTransaction {
             .fee_payer_signature
             .map(|_| core::mem::size_of::<Signature>())
             .unwrap_or(0);
-        core::mem::size_of::<Self>() + calls_size + access_list_size + sponsor_sig_size
+        let session_targets_size = self.session.as_ref().map_or(0, |session| {
+            session.authorization.targets.len() * core::mem::size_of::<Address>()
+        });
+        let webauthn_size = self
+            .p256_signature
+            .as_ref()
+            .and_then(|p256| p256.webauthn.as_ref())
+            .map_or(0, |assertion| {
+                assertion.authenticator_data.len() + assertion.client_data_json.len()
+            });
+        core::mem::size_of::<Self>()
+            + calls_size
+            + access_list_size
+            + sponsor_sig_size
+            + session_targets_size
+            + webauthn_size
     }
 }
 
@@ -399,8 +1076,8 @@ impl SignerRecoverable for EvTxEnvelope {
         match self {
             EvTxEnvelope::Ethereum(tx) => tx.recover_signer(),
             EvTxEnvelope::EvNode(tx) => tx
-                .signature()
-                .recover_address_from_prehash(&tx.tx().executor_signing_hash())
+                .tx()
+                .recover_executor(tx.signature())
                 .map_err(|_| alloy_consensus::crypto::RecoveryError::new()),
         }
     }
@@ -535,37 +1212,203 @@ impl Decompress for EvTxEnvelope {
     }
 }
 
-fn optional_signature_length(value: Option<&Signature>) -> usize {
-    match value {
-        Some(sig) => sig.as_bytes().as_slice().length(),
-        None => 1,
-    }
+/// Length of a fee payer signature in the fee payer slot.
+const SPONSOR_SIGNATURE_LEN: usize = 65;
+/// Length of the big-endian `max_sponsor_fee` in the fee payer slot.
+const MAX_SPONSOR_FEE_LEN: usize = 16;
+
+/// Fee payer slot: an RLP string holding the sponsor signature, if any, followed by the
+/// `max_sponsor_fee`, if any. Empty for unsponsored transactions.
+#[derive(Clone, Copy)]
+struct SponsorSlot<'a> {
+    signature: Option<&'a Signature>,
+    max_sponsor_fee: Option<u128>,
 }
 
-fn encode_optional_signature(out: &mut dyn BufMut, value: Option<&Signature>) {
-    match value {
-        Some(sig) => sig.as_bytes().as_slice().encode(out),
-        None => out.put_u8(alloy_rlp::EMPTY_STRING_CODE),
+impl SponsorSlot<'_> {
+    const EMPTY: SponsorSlot<'static> = SponsorSlot {
+        signature: None,
+        max_sponsor_fee: None,
+    };
+
+    fn header(&self) -> Header {
+        Header {
+            list: false,
+            payload_length: self.signature.map_or(0, |_| SPONSOR_SIGNATURE_LEN)
+                + self.max_sponsor_fee.map_or(0, |_| MAX_SPONSOR_FEE_LEN),
+        }
+    }
+
+    fn length(&self) -> usize {
+        self.header().length_with_payload()
+    }
+
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.header().encode(out);
+        if let Some(signature) = self.signature {
+            out.put_slice(&signature.as_bytes());
+        }
+        if let Some(max_sponsor_fee) = self.max_sponsor_fee {
+            out.put_slice(&max_sponsor_fee.to_be_bytes());
+        }
     }
 }
 
-fn decode_optional_signature(buf: &mut &[u8]) -> alloy_rlp::Result<Option<Signature>> {
+fn decode_sponsor_slot(buf: &mut &[u8]) -> alloy_rlp::Result<(Option<Signature>, Option<u128>)> {
     let bytes = Header::decode_bytes(buf, false)?;
-    if bytes.is_empty() {
-        return Ok(None);
-    }
-    let raw: [u8; 65] = bytes
-        .try_into()
-        .map_err(|_| alloy_rlp::Error::UnexpectedLength)?;
+    let (signature, max_sponsor_fee) = match bytes.len() {
+        0 => return Ok((None, None)),
+        SPONSOR_SIGNATURE_LEN => (bytes, None),
+        len if len == SPONSOR_SIGNATURE_LEN + MAX_SPONSOR_FEE_LEN => {
+            let (signature, max_sponsor_fee) = bytes.split_at(SPONSOR_SIGNATURE_LEN);
+            let max_sponsor_fee: [u8; MAX_SPONSOR_FEE_LEN] =
+                max_sponsor_fee.try_into().expect("length checked above");
+            (signature, Some(u128::from_be_bytes(max_sponsor_fee)))
+        }
+        _ => return Err(alloy_rlp::Error::UnexpectedLength),
+    };
+    let raw: [u8; SPONSOR_SIGNATURE_LEN] = signature.try_into().expect("length checked above");
     Signature::from_raw_array(&raw)
-        .map(Some)
+        .map(|signature| (Some(signature), max_sponsor_fee))
         .map_err(|_| alloy_rlp::Error::Custom("invalid signature bytes"))
 }
 
+/// Value of [`RevertMode::BestEffort`] in the revert mode list.
+const BEST_EFFORT_MODE: u8 = 1;
+
+const fn revert_mode_length(mode: RevertMode) -> usize {
+    match mode {
+        RevertMode::Atomic => 0,
+        // A list header and the single-byte mode.
+        RevertMode::BestEffort => 2,
+    }
+}
+
+fn encode_revert_mode(mode: RevertMode, out: &mut dyn BufMut) {
+    if mode == RevertMode::BestEffort {
+        Header {
+            list: true,
+            payload_length: 1,
+        }
+        .encode(out);
+        BEST_EFFORT_MODE.encode(out);
+    }
+}
+
+fn decode_revert_mode(buf: &mut &[u8]) -> alloy_rlp::Result<RevertMode> {
+    // The fee payer slot that follows is a string, and a validity window or a session
+    // authorization a longer list, so a list holding a single byte can only be the mode.
+    let mut peek = *buf;
+    if !matches!(
+        Header::decode(&mut peek),
+        Ok(Header {
+            list: true,
+            payload_length: 1
+        })
+    ) {
+        return Ok(RevertMode::Atomic);
+    }
+    let mut payload = Header::decode_bytes(buf, true)?;
+    let mode = u8::decode(&mut payload)?;
+    if !payload.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength);
+    }
+    match mode {
+        BEST_EFFORT_MODE => Ok(RevertMode::BestEffort),
+        _ => Err(alloy_rlp::Error::Custom("unknown revert mode")),
+    }
+}
+
+/// Window of transactions without a validity window.
+const UNBOUNDED_WINDOW: ValidityWindow = ValidityWindow {
+    valid_after: 0,
+    valid_until: u64::MAX,
+};
+
+fn validity_window_payload_length(window: &ValidityWindow) -> usize {
+    window.valid_after.length() + window.valid_until.length()
+}
+
+fn validity_window_length(window: &ValidityWindow) -> usize {
+    Header {
+        list: true,
+        payload_length: validity_window_payload_length(window),
+    }
+    .length_with_payload()
+}
+
+fn encode_validity_window(window: &ValidityWindow, out: &mut dyn BufMut) {
+    Header {
+        list: true,
+        payload_length: validity_window_payload_length(window),
+    }
+    .encode(out);
+    window.valid_after.encode(out);
+    window.valid_until.encode(out);
+}
+
+fn decode_validity_window(buf: &mut &[u8]) -> alloy_rlp::Result<(Option<u64>, Option<u64>)> {
+    // A session authorization is a list starting with a list, so a list starting with a
+    // string can only be the window.
+    let mut peek = *buf;
+    match Header::decode(&mut peek) {
+        Ok(Header { list: true, .. })
+            if peek
+                .first()
+                .is_some_and(|byte| *byte < alloy_rlp::EMPTY_LIST_CODE) => {}
+        _ => return Ok((None, None)),
+    }
+    let mut payload = Header::decode_bytes(buf, true)?;
+    let window = ValidityWindow {
+        valid_after: u64::decode(&mut payload)?,
+        valid_until: u64::decode(&mut payload)?,
+    };
+    if !payload.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength);
+    }
+    if window == UNBOUNDED_WINDOW {
+        return Err(alloy_rlp::Error::Custom("unbounded validity window"));
+    }
+    Ok((
+        (window.valid_after != UNBOUNDED_WINDOW.valid_after).then_some(window.valid_after),
+        (window.valid_until != UNBOUNDED_WINDOW.valid_until).then_some(window.valid_until),
+    ))
+}
+
+fn decode_session(buf: &mut &[u8]) -> alloy_rlp::Result<Option<SignedSessionAuthorization>> {
+    // The fee payer slot that follows is a string, so a list here can only be the session.
+    if buf
+        .first()
+        .is_none_or(|byte| *byte < alloy_rlp::EMPTY_LIST_CODE)
+    {
+        return Ok(None);
+    }
+    SignedSessionAuthorization::decode(buf).map(Some)
+}
+
+fn decode_p256_signature(buf: &mut &[u8]) -> alloy_rlp::Result<Option<P256Signature>> {
+    // The executor signature that follows starts with its parity, a string, so a list here
+    // can only be the P256 signature.
+    if buf
+        .first()
+        .is_none_or(|byte| *byte < alloy_rlp::EMPTY_LIST_CODE)
+    {
+        return Ok(None);
+    }
+    P256Signature::decode(buf).map(Some)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_eips::eip2930::AccessList;
+    use alloy_eips::{
+        eip2718::{Decodable2718, Encodable2718},
+        eip2930::{AccessList, AccessListItem},
+    };
+
+    alloy_sol_types::sol! {
+        function transfer(address to, uint256 amount) returns (bool);
+    }
 
     fn sample_signature() -> Signature {
         let mut bytes = [0u8; 65];
@@ -577,6 +1420,7 @@ mod tests {
         EvNodeTransaction {
             chain_id: 1,
             nonce: 1,
+            nonce_key: U192::ZERO,
             max_priority_fee_per_gas: 1,
             max_fee_per_gas: 2,
             gas_limit: 30_000,
@@ -584,9 +1428,16 @@ mod tests {
                 to: TxKind::Create,
                 value: U256::from(1),
                 input: Bytes::new(),
+                gas_limit: None,
             }],
             access_list: AccessList::default(),
+            revert_mode: RevertMode::Atomic,
+            valid_after: None,
+            valid_until: None,
+            session: None,
             fee_payer_signature: None,
+            max_sponsor_fee: None,
+            p256_signature: None,
         }
     }
 
@@ -608,6 +1459,49 @@ mod tests {
         assert_ne!(tx.sponsor_signing_hash(a), tx.sponsor_signing_hash(b));
     }
 
+    #[test]
+    fn fee_payer_scheme_ignores_executor() {
+        let tx = sample_tx();
+        let a = Address::from_slice(&[1u8; 20]);
+        let b = Address::from_slice(&[2u8; 20]);
+        let fee_payer_hash = tx.sponsor_signing_hash_with(SponsorHashScheme::FeePayer, a);
+        assert_eq!(
+            fee_payer_hash,
+            tx.sponsor_signing_hash_with(SponsorHashScheme::FeePayer, b)
+        );
+        assert_ne!(fee_payer_hash, tx.sponsor_signing_hash(a));
+        assert_ne!(fee_payer_hash, tx.executor_signing_hash());
+    }
+
+    #[test]
+    fn eip712_scheme_binds_executor_chain_and_payload() {
+        let mut tx = sample_tx();
+        let a = Address::from_slice(&[1u8; 20]);
+        let b = Address::from_slice(&[2u8; 20]);
+        let eip712_hash = tx.sponsor_signing_hash_eip712(a);
+        assert_eq!(
+            eip712_hash,
+            tx.sponsor_signing_hash_with(SponsorHashScheme::Eip712, a)
+        );
+        assert_ne!(eip712_hash, tx.sponsor_signing_hash_eip712(b));
+        assert_ne!(eip712_hash, tx.sponsor_signing_hash(a));
+
+        let sponsorship = tx.sponsorship_eip712(a);
+        assert_eq!(sponsorship.executor, a);
+        assert_eq!(sponsorship.maxSponsorFee, U256::MAX);
+        assert_eq!(sponsorship.calls[0].to, Address::ZERO);
+        assert_eq!(
+            eip712_hash,
+            sponsorship.eip712_signing_hash(&crate::sponsor_eip712_domain(1))
+        );
+
+        tx.chain_id = 2;
+        assert_ne!(tx.sponsor_signing_hash_eip712(a), eip712_hash);
+        tx.chain_id = 1;
+        tx.revert_mode = RevertMode::BestEffort;
+        assert_ne!(tx.sponsor_signing_hash_eip712(a), eip712_hash);
+    }
+
     #[test]
     fn rlp_roundtrip_with_optional_signature() {
         let mut tx = sample_tx();
@@ -621,17 +1515,655 @@ mod tests {
     }
 
     #[test]
-    fn decode_optional_signature_none() {
+    fn max_sponsor_fee_roundtrip_and_binding() {
+        let executor = Address::repeat_byte(0x11);
+        let mut tx = sample_tx();
+        let executor_hash = tx.executor_signing_hash();
+        let sponsor_hash = tx.sponsor_signing_hash(executor);
+
+        tx.max_sponsor_fee = Some(1_000);
+        assert_eq!(tx.executor_signing_hash(), executor_hash);
+        assert_ne!(tx.sponsor_signing_hash(executor), sponsor_hash);
+
+        tx.fee_payer_signature = Some(sample_signature());
+        let mut out = Vec::new();
+        tx.encode(&mut out);
+        let decoded = EvNodeTransaction::decode(&mut out.as_slice()).expect("decode tx");
+        assert_eq!(decoded, tx);
+
+        let json = serde_json::to_value(&tx).expect("serialize tx");
+        assert_eq!(json["maxSponsorFee"], "0x3e8");
+    }
+
+    #[test]
+    fn call_gas_limit_roundtrip_and_binding() {
+        let mut tx = sample_tx();
+        let executor_hash = tx.executor_signing_hash();
+
+        tx.calls[0] = tx.calls[0].clone().with_gas_limit(50_000);
+        assert_ne!(tx.executor_signing_hash(), executor_hash);
+
+        let mut out = Vec::new();
+        tx.encode(&mut out);
+        let decoded = EvNodeTransaction::decode(&mut out.as_slice()).expect("decode tx");
+        assert_eq!(decoded.calls[0].gas_limit, Some(50_000));
+
+        let json = serde_json::to_value(&tx).expect("serialize tx");
+        assert_eq!(json["calls"][0]["gasLimit"], "0xc350");
+    }
+
+    #[test]
+    fn best_effort_roundtrip_and_binding() {
+        let mut tx = sample_tx();
+        let executor_hash = tx.executor_signing_hash();
+        let sponsor_hash = tx.sponsor_signing_hash(Address::repeat_byte(0x11));
+
+        tx.revert_mode = RevertMode::BestEffort;
+        assert_ne!(tx.executor_signing_hash(), executor_hash);
+        assert_ne!(
+            tx.sponsor_signing_hash(Address::repeat_byte(0x11)),
+            sponsor_hash
+        );
+
+        let mut rlp = Vec::new();
+        tx.encode(&mut rlp);
+        assert_eq!(
+            alloy_primitives::hex::encode(&rlp),
+            "d001010102827530c4c3800180c0c10180"
+        );
+        assert_eq!(
+            EvNodeTransaction::decode(&mut rlp.as_slice()),
+            Ok(tx.clone())
+        );
+
+        // The mode sits before the fee payer slot, so a sponsored, signed batch still decodes.
+        tx.fee_payer_signature = Some(sample_signature());
+        let signed = tx.clone().into_signed(sample_signature());
+        let mut out = Vec::new();
+        signed.tx().rlp_encode_signed(signed.signature(), &mut out);
+        let decoded = EvNodeTransaction::rlp_decode_signed(&mut out.as_slice()).expect("decode");
+        assert_eq!(decoded.tx(), &tx);
+
+        let json = serde_json::to_value(&tx).expect("serialize tx");
+        assert_eq!(json["revertMode"], "bestEffort");
+
+        // Only the best-effort marker is accepted in the mode list.
+        let unknown = alloy_primitives::hex::decode("d001010102827530c4c3800180c0c10280").unwrap();
+        assert!(EvNodeTransaction::decode(&mut unknown.as_slice()).is_err());
+    }
+
+    #[test]
+    fn max_batch_calls_applies_from_activation() {
+        let mut tx = sample_tx();
+        tx.calls = vec![tx.calls[0].clone(); 3];
+        let limit = MaxBatchCalls::new(2, 10);
+
+        assert_eq!(limit.check(&tx, 9), Ok(()));
+        assert_eq!(
+            limit.check(&tx, 10),
+            Err(TooManyBatchCalls {
+                calls: 3,
+                max_calls: 2
+            })
+        );
+        tx.calls.pop();
+        assert_eq!(limit.check(&tx, 10), Ok(()));
+    }
+
+    #[test]
+    fn validity_window_roundtrip_and_binding() {
+        let mut tx = sample_tx();
+        let executor_hash = tx.executor_signing_hash();
+        let sponsor_hash = tx.sponsor_signing_hash(Address::repeat_byte(0x11));
+        assert_eq!(tx.validity_window(), None);
+
+        tx.valid_until = Some(1_000);
+        assert_eq!(
+            tx.validity_window(),
+            Some(ValidityWindow {
+                valid_after: 0,
+                valid_until: 1_000
+            })
+        );
+        assert_ne!(tx.executor_signing_hash(), executor_hash);
+        assert_ne!(
+            tx.sponsor_signing_hash(Address::repeat_byte(0x11)),
+            sponsor_hash
+        );
+
+        let mut rlp = Vec::new();
+        tx.encode(&mut rlp);
+        assert_eq!(
+            alloy_primitives::hex::encode(&rlp),
+            "d301010102827530c4c3800180c0c4808203e880"
+        );
+        assert_eq!(
+            EvNodeTransaction::decode(&mut rlp.as_slice()),
+            Ok(tx.clone())
+        );
+
+        // The window sits between the revert mode and the session.
+        tx.revert_mode = RevertMode::BestEffort;
+        tx.valid_after = Some(500);
+        tx.session = Some(
+            crate::SessionAuthorization {
+                chain_id: 1,
+                session_key: Address::repeat_byte(0x5e),
+                targets: vec![Address::repeat_byte(0xaa)],
+                max_value: U256::from(1),
+                expiry: 1_000,
+            }
+            .sign(&alloy_signer_local::PrivateKeySigner::random())
+            .unwrap(),
+        );
+        tx.fee_payer_signature = Some(sample_signature());
+        let mut rlp = Vec::new();
+        tx.encode(&mut rlp);
+        assert_eq!(
+            EvNodeTransaction::decode(&mut rlp.as_slice()),
+            Ok(tx.clone())
+        );
+
+        let json = serde_json::to_value(&tx).expect("serialize tx");
+        assert_eq!(json["validAfter"], "0x1f4");
+        assert_eq!(json["validUntil"], "0x3e8");
+
+        let window = tx.validity_window().unwrap();
+        assert_eq!(
+            window.check(499),
+            Err(ValidityWindowError::NotYetValid { valid_after: 500 })
+        );
+        assert_eq!(window.check(500), Ok(()));
+        assert_eq!(window.check(1_000), Ok(()));
+        assert_eq!(
+            window.check(1_001),
+            Err(ValidityWindowError::Expired { valid_until: 1_000 })
+        );
+
+        // An unbounded window is encoded as nothing, never as a list.
+        let unbounded =
+            alloy_primitives::hex::decode("d901010102827530c4c3800180c0ca8088ffffffffffffffff80")
+                .unwrap();
+        assert!(EvNodeTransaction::decode(&mut unbounded.as_slice()).is_err());
+    }
+
+    #[test]
+    fn nonce_key_shares_the_nonce_field() {
+        let mut tx = sample_tx();
+        let executor_hash = tx.executor_signing_hash();
+        let sponsorship = tx.sponsorship_eip712(Address::repeat_byte(0x11));
+        assert_eq!(tx.nonce_lane(), None);
+        assert_eq!(tx.full_nonce(), U256::from(1));
+
+        tx.nonce_key = U192::from(2);
+        assert_eq!(tx.nonce_lane(), Some(U192::from(2)));
+        assert_eq!(tx.full_nonce(), (U256::from(2) << 64) | U256::from(1));
+        assert_ne!(tx.executor_signing_hash(), executor_hash);
+        assert_ne!(
+            tx.sponsorship_eip712(Address::repeat_byte(0x11)).nonce,
+            sponsorship.nonce
+        );
+
+        let mut rlp = Vec::new();
+        tx.encode(&mut rlp);
+        assert_eq!(
+            alloy_primitives::hex::encode(&rlp),
+            "d701890200000000000000010102827530c4c3800180c080"
+        );
+        assert_eq!(
+            EvNodeTransaction::decode(&mut rlp.as_slice()),
+            Ok(tx.clone())
+        );
+
+        let json = serde_json::to_value(&tx).expect("serialize tx");
+        assert_eq!(json["nonce"], "0x1");
+        assert_eq!(json["nonceKey"], "0x2");
+        assert!(serde_json::to_value(sample_tx()).unwrap()["nonceKey"].is_null());
+    }
+
+    #[test]
+    fn session_key_recovers_primary_account() {
+        use alloy_signer::SignerSync;
+        use alloy_signer_local::PrivateKeySigner;
+
+        let primary = PrivateKeySigner::random();
+        let session_key = PrivateKeySigner::random();
+        let mut tx = sample_tx();
+        let executor_hash = tx.executor_signing_hash();
+        tx.revert_mode = RevertMode::BestEffort;
+        tx.session = Some(
+            crate::SessionAuthorization {
+                chain_id: 1,
+                session_key: session_key.address(),
+                targets: vec![Address::repeat_byte(0xaa)],
+                max_value: U256::from(1),
+                expiry: 1_000,
+            }
+            .sign(&primary)
+            .unwrap(),
+        );
+        assert_ne!(tx.executor_signing_hash(), executor_hash);
+
+        let signature = session_key
+            .sign_hash_sync(&tx.executor_signing_hash())
+            .unwrap();
+        assert_eq!(tx.recover_executor(&signature), Ok(primary.address()));
+        let other = PrivateKeySigner::random()
+            .sign_hash_sync(&tx.executor_signing_hash())
+            .unwrap();
+        assert!(tx.recover_executor(&other).is_err());
+
+        // Both optional lists sit before the fee payer slot.
+        tx.fee_payer_signature = Some(sample_signature());
+        let signed = tx.clone().into_signed(signature);
+        let mut out = Vec::new();
+        signed.tx().rlp_encode_signed(signed.signature(), &mut out);
+        let decoded = EvNodeTransaction::rlp_decode_signed(&mut out.as_slice()).expect("decode");
+        assert_eq!(decoded.tx(), &tx);
+        let envelope = EvTxEnvelope::EvNode(signed);
+        assert_eq!(envelope.recover_signer().unwrap(), primary.address());
+    }
+
+    #[test]
+    fn p256_signature_recovers_executor() {
+        use alloy_signer::SignerSync;
+        use alloy_signer_local::PrivateKeySigner;
+
+        let key = p256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let mut tx = sample_tx();
+        let p256 = P256Signature::sign(tx.executor_signing_hash(), &key).unwrap();
+        let executor = p256.address();
+        tx.p256_signature = Some(p256);
+        tx.fee_payer_signature = Some(sample_signature());
+
+        let marker = crate::p256_executor_marker();
+        assert_eq!(tx.recover_executor(&marker), Ok(executor));
+        let secp256k1 = PrivateKeySigner::random()
+            .sign_hash_sync(&tx.executor_signing_hash())
+            .unwrap();
+        assert!(tx.recover_executor(&secp256k1).is_err());
+
+        // The P256 signature sits between the fee payer slot and the executor signature.
+        let signed = tx.clone().into_signed(marker);
+        let mut out = Vec::new();
+        signed.tx().rlp_encode_signed(signed.signature(), &mut out);
+        let decoded = EvNodeTransaction::rlp_decode_signed(&mut out.as_slice()).expect("decode");
+        assert_eq!(decoded.tx(), &tx);
+        let envelope = EvTxEnvelope::EvNode(signed);
+        assert_eq!(envelope.recover_signer().unwrap(), executor);
+
+        let mut tampered = tx;
+        tampered.gas_limit += 1;
+        assert!(tampered.recover_executor(&marker).is_err());
+    }
+
+    #[test]
+    fn contract_fee_payer_marker_roundtrip() {
+        let mut tx = sample_tx();
+        assert_eq!(tx.contract_fee_payer(), None);
+
+        tx.fee_payer_signature = Some(sample_signature());
+        assert_eq!(tx.contract_fee_payer(), None);
+
+        let pool = Address::from_slice(&[0xaa; 20]);
+        tx.fee_payer_signature = Some(contract_fee_payer_signature(pool));
+        assert_eq!(tx.contract_fee_payer(), Some(pool));
+
+        let mut out = Vec::new();
+        tx.encode(&mut out);
+        let decoded = EvNodeTransaction::decode(&mut out.as_slice()).expect("decode tx");
+        assert_eq!(decoded.contract_fee_payer(), Some(pool));
+    }
+
+    #[test]
+    fn encoding_is_stable() {
+        let tx = sample_tx();
+
+        let mut rlp = Vec::new();
+        tx.encode(&mut rlp);
+        assert_eq!(
+            alloy_primitives::hex::encode(&rlp),
+            "ce01010102827530c4c3800180c080"
+        );
+        assert_eq!(
+            tx.executor_signing_hash(),
+            alloy_primitives::b256!(
+                "0xe3a6023b99abbacba7c36fd3ac06b52576daf87303d738fabf00a94c91344d4b"
+            )
+        );
+        assert_eq!(
+            tx.sponsor_signing_hash(Address::repeat_byte(0x11)),
+            alloy_primitives::b256!(
+                "0x1e232472eb698e921c7e06351e7b46d06e09787fe8a8d3b64cc7fa4833b80d44"
+            )
+        );
+
+        let json = serde_json::to_value(&tx).expect("serialize tx");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "chainId": "0x1",
+                "nonce": "0x1",
+                "maxPriorityFeePerGas": "0x1",
+                "maxFeePerGas": "0x2",
+                "gasLimit": "0x7530",
+                "calls": [{ "to": null, "value": "0x1", "input": "0x" }],
+                "accessList": [],
+                "feePayerSignature": null
+            })
+        );
+        let decoded: EvNodeTransaction = serde_json::from_value(json).expect("deserialize tx");
+        assert_eq!(decoded, tx);
+    }
+
+    /// Golden vectors of the 0x76 encoding, signing hashes and transaction hashes.
+    ///
+    /// Vectors of a released version are never edited: a change that alters any of them needs
+    /// a new transaction type or an explicit migration, and its vectors go in a new file.
+    const GOLDEN_VECTORS_V1: &str = include_str!("../testdata/evnode_tx_v1.json");
+
+    const GOLDEN_VECTOR_NAMES: [&str; 3] = ["minimal", "sponsored", "best_effort_lane_window"];
+
+    /// Returns the transaction of the golden vector `name`.
+    fn golden_tx(name: &str) -> EvNodeTransaction {
+        match name {
+            "minimal" => sample_tx(),
+            "sponsored" => EvNodeTransaction {
+                chain_id: 1234,
+                nonce: 7,
+                max_priority_fee_per_gas: 1_000_000_000,
+                max_fee_per_gas: 2_000_000_000,
+                gas_limit: 100_000,
+                calls: vec![
+                    Call {
+                        to: TxKind::Call(Address::repeat_byte(0x22)),
+                        value: U256::from(5),
+                        input: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+                        gas_limit: None,
+                    },
+                    Call::transfer(Address::repeat_byte(0x33), U256::ZERO).with_gas_limit(50_000),
+                ],
+                access_list: AccessList(vec![AccessListItem {
+                    address: Address::repeat_byte(0x44),
+                    storage_keys: vec![B256::with_last_byte(1)],
+                }]),
+                fee_payer_signature: Some(Signature::new(
+                    U256::from(0xf1),
+                    U256::from(0xf2),
+                    false,
+                )),
+                max_sponsor_fee: Some(1_000_000),
+                ..sample_tx()
+            },
+            "best_effort_lane_window" => EvNodeTransaction {
+                chain_id: 1234,
+                nonce: 3,
+                nonce_key: U192::from(2),
+                max_priority_fee_per_gas: 0,
+                max_fee_per_gas: 1_000,
+                gas_limit: 200_000,
+                calls: vec![Call::deploy(Bytes::from_static(&[0x60, 0x00]), U256::ZERO)],
+                revert_mode: RevertMode::BestEffort,
+                valid_after: Some(100),
+                valid_until: Some(200),
+                fee_payer_signature: Some(contract_fee_payer_signature(Address::repeat_byte(0xaa))),
+                ..sample_tx()
+            },
+            _ => panic!("unknown golden vector {name}"),
+        }
+    }
+
+    /// Executor signature of the golden vector envelopes; the codecs do not verify it.
+    fn golden_executor_signature() -> Signature {
+        Signature::new(U256::from(0xe1), U256::from(0xe2), true)
+    }
+
+    #[test]
+    fn golden_vectors_v1() {
+        use alloy_primitives::hex::encode_prefixed;
+
+        let file: serde_json::Value = serde_json::from_str(GOLDEN_VECTORS_V1).unwrap();
+        assert_eq!(file["version"], 1);
+        let executor: Address = serde_json::from_value(file["sponsorExecutor"].clone()).unwrap();
+        let vectors = file["vectors"].as_array().unwrap();
+        assert_eq!(vectors.len(), GOLDEN_VECTOR_NAMES.len());
+
+        for (vector, name) in vectors.iter().zip(GOLDEN_VECTOR_NAMES) {
+            assert_eq!(vector["name"], name);
+            let tx = golden_tx(name);
+
+            let mut rlp = Vec::new();
+            tx.encode(&mut rlp);
+            assert_eq!(encode_prefixed(&rlp), vector["rlp"], "{name}: rlp");
+            assert_eq!(
+                EvNodeTransaction::decode(&mut rlp.as_slice()),
+                Ok(tx.clone()),
+                "{name}"
+            );
+            assert_eq!(
+                encode_prefixed(tx.executor_signing_hash()),
+                vector["executorSigningHash"],
+                "{name}: executor signing hash"
+            );
+            assert_eq!(
+                encode_prefixed(tx.sponsor_signing_hash(executor)),
+                vector["sponsorSigningHash"],
+                "{name}: sponsor signing hash"
+            );
+            assert_eq!(
+                encode_prefixed(
+                    tx.sponsor_signing_hash_with(SponsorHashScheme::FeePayer, executor)
+                ),
+                vector["feePayerSigningHash"],
+                "{name}: fee payer signing hash"
+            );
+
+            let envelope = EvTxEnvelope::EvNode(tx.into_signed(golden_executor_signature()));
+            let encoded = envelope.encoded_2718();
+            assert_eq!(
+                encode_prefixed(&encoded),
+                vector["encoded2718"],
+                "{name}: 2718"
+            );
+            assert_eq!(
+                encode_prefixed(envelope.tx_hash()),
+                vector["txHash"],
+                "{name}: hash"
+            );
+            let decoded = EvTxEnvelope::decode_2718(&mut encoded.as_slice()).expect("decode");
+            assert_eq!(decoded.tx_hash(), envelope.tx_hash(), "{name}");
+        }
+    }
+
+    #[test]
+    fn compact_roundtrip() {
+        for name in GOLDEN_VECTOR_NAMES {
+            let tx = golden_tx(name);
+            let mut buf = Vec::new();
+            let len = tx.to_compact(&mut buf);
+            let (decoded, rest) = EvNodeTransaction::from_compact(&buf, len);
+            assert_eq!(decoded, tx, "{name}");
+            assert!(rest.is_empty(), "{name}");
+        }
+
+        let ethereum = alloy_consensus::TxEip1559 {
+            chain_id: 1,
+            nonce: 1,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2,
+            max_priority_fee_per_gas: 1,
+            to: TxKind::Call(Address::repeat_byte(0x22)),
+            value: U256::from(1),
+            ..Default::default()
+        }
+        .into_signed(golden_executor_signature());
+        let envelopes = GOLDEN_VECTOR_NAMES
+            .iter()
+            .map(|name| {
+                EvTxEnvelope::EvNode(golden_tx(name).into_signed(golden_executor_signature()))
+            })
+            .chain([EvTxEnvelope::Ethereum(
+                reth_ethereum_primitives::TransactionSigned::Eip1559(ethereum),
+            )]);
+        for envelope in envelopes {
+            let mut buf = Vec::new();
+            let len = Compact::to_compact(&envelope, &mut buf);
+            let (decoded, _) = <EvTxEnvelope as Compact>::from_compact(&buf, len);
+            assert_eq!(decoded.encoded_2718(), envelope.encoded_2718());
+            assert_eq!(decoded.signature(), envelope.signature());
+
+            let decompressed = EvTxEnvelope::decompress(&envelope.clone().compress()).unwrap();
+            assert_eq!(decompressed.tx_hash(), envelope.tx_hash());
+        }
+
+        for ty in [
+            EvTxType::EvNode,
+            EvTxType::Ethereum(alloy_consensus::TxType::Legacy),
+            EvTxType::Ethereum(alloy_consensus::TxType::Eip1559),
+        ] {
+            let mut buf = Vec::new();
+            let identifier = ty.to_compact(&mut buf);
+            let (decoded, rest) = EvTxType::from_compact(&buf, identifier);
+            assert_eq!(decoded, ty);
+            assert!(rest.is_empty());
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_transactions_roundtrip() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        for case in 0u64..256 {
+            let mut word = keccak256(case.to_be_bytes());
+            let mut entropy = Vec::new();
+            for _ in 0..32 {
+                entropy.extend_from_slice(word.as_slice());
+                word = keccak256(word);
+            }
+            let tx = EvNodeTransaction::arbitrary(&mut Unstructured::new(&entropy)).unwrap();
+
+            let mut rlp = Vec::new();
+            tx.encode(&mut rlp);
+            assert_eq!(
+                EvNodeTransaction::decode(&mut rlp.as_slice()),
+                Ok(tx.clone()),
+                "case {case}"
+            );
+            let mut buf = Vec::new();
+            let len = tx.to_compact(&mut buf);
+            assert_eq!(
+                EvNodeTransaction::from_compact(&buf, len).0,
+                tx,
+                "case {case}"
+            );
+        }
+    }
+
+    #[test]
+    fn serde_accepts_missing_fee_payer_signature() {
+        let json = serde_json::json!({
+            "chainId": "0x1",
+            "nonce": "0x1",
+            "maxPriorityFeePerGas": "0x1",
+            "maxFeePerGas": "0x2",
+            "gasLimit": "0x7530",
+            "calls": [],
+            "accessList": []
+        });
+        let decoded: EvNodeTransaction = serde_json::from_value(json).expect("deserialize tx");
+        assert_eq!(decoded.fee_payer_signature, None);
+    }
+
+    #[test]
+    fn decode_sponsor_slot_none() {
         let mut buf: &[u8] = &[alloy_rlp::EMPTY_STRING_CODE];
-        let decoded = decode_optional_signature(&mut buf).expect("decode none signature");
-        assert_eq!(decoded, None);
+        let decoded = decode_sponsor_slot(&mut buf).expect("decode none signature");
+        assert_eq!(decoded, (None, None));
         assert!(buf.is_empty());
     }
 
     #[test]
-    fn decode_optional_signature_rejects_invalid_length() {
+    fn decode_sponsor_slot_rejects_invalid_length() {
         let mut buf: &[u8] = &[0x82, 0x01, 0x02];
-        let err = decode_optional_signature(&mut buf).expect_err("invalid length");
+        let err = decode_sponsor_slot(&mut buf).expect_err("invalid length");
         assert_eq!(err, alloy_rlp::Error::UnexpectedLength);
     }
+
+    #[test]
+    fn call_helpers_encode_abi() {
+        let token = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x22);
+        let call = Call::contract_call(
+            token,
+            &transferCall {
+                to: recipient,
+                amount: U256::from(5),
+            },
+        );
+        assert_eq!(call.to, TxKind::Call(token));
+        assert_eq!(call.value, U256::ZERO);
+        assert_eq!(&call.input[..4], &transferCall::SELECTOR);
+        let decoded = transferCall::abi_decode(&call.input).unwrap();
+        assert_eq!(decoded.to, recipient);
+        assert_eq!(decoded.amount, U256::from(5));
+
+        let deploy =
+            Call::deploy_with_args(&[0x60, 0x00], &(recipient, U256::from(7)), U256::from(1));
+        assert!(deploy.is_create());
+        assert_eq!(deploy.value, U256::from(1));
+        assert_eq!(deploy.input.len(), 2 + 64);
+        assert_eq!(
+            <(Address, U256)>::abi_decode_params(&deploy.input[2..]).unwrap(),
+            (recipient, U256::from(7))
+        );
+
+        assert!(Call::transfer(recipient, U256::from(3)).input.is_empty());
+    }
+
+    #[test]
+    fn deploy_address_follows_executor_nonce() {
+        let executor = alloy_primitives::address!("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        let deployed = alloy_primitives::address!("0x343c43a37d37dff08ae8c4a11544c718abb4fcf8");
+        let mut tx = sample_tx();
+        assert_eq!(tx.deploy_address(executor), Some(deployed));
+
+        // Sponsorship does not change the deployer.
+        tx.fee_payer_signature = Some(sample_signature());
+        assert_eq!(tx.deploy_address(executor), Some(deployed));
+
+        tx.nonce_key = U192::from(1);
+        assert_eq!(tx.deploy_address(executor), None);
+        tx.nonce_key = U192::ZERO;
+        tx.calls[0].to = TxKind::Call(executor);
+        assert_eq!(tx.deploy_address(executor), None);
+    }
+
+    #[test]
+    fn calls_summary_covers_every_call() {
+        let a = Address::repeat_byte(0x0a);
+        let b = Address::repeat_byte(0x0b);
+        let mut tx = sample_tx();
+        tx.calls.extend([
+            Call::transfer(b, U256::from(2)),
+            Call::transfer(a, U256::from(3)),
+            Call::transfer(b, U256::MAX),
+        ]);
+
+        let summary = tx.calls_summary();
+        assert_eq!(summary.call_count, 4);
+        assert_eq!(summary.create_count, 1);
+        assert_eq!(summary.total_value, U256::MAX);
+        assert_eq!(summary.targets, vec![b, a]);
+        assert_eq!(summary.total_value, tx.value());
+        assert_eq!(tx.kind(), TxKind::Create);
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["callCount"], "0x4");
+        assert_eq!(json["createCount"], "0x1");
+        assert_eq!(
+            serde_json::from_value::<CallsSummary>(json).unwrap(),
+            summary
+        );
+    }
 }
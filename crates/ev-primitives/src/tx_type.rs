@@ -0,0 +1,181 @@
+//! Chain-specific alias of the EvNode transaction type.
+//!
+//! EvNode transactions are hashed, signed, stored and carried in blocks as type
+//! [`EVNODE_TX_TYPE_ID`]. Chains where `0x76` already means something else can configure
+//! another type byte that raw transactions may use instead: it is mapped back to `0x76`
+//! before decoding, so hashes and signatures do not depend on the wire type.
+
+use std::borrow::Cow;
+
+use crate::tx::EVNODE_TX_TYPE_ID;
+
+/// Highest EIP-2718 type byte; encodings starting above it are legacy transactions.
+const MAX_TYPED_TX_ID: u8 = 0x7f;
+
+/// Highest type byte of an Ethereum transaction (EIP-7702).
+const MAX_ETHEREUM_TX_ID: u8 = 0x04;
+
+/// Errors of an EvNode transaction type alias.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum EvNodeTxTypeError {
+    /// The alias is an Ethereum type, the canonical EvNode type or not an EIP-2718 type.
+    #[error("type {0:#04x} cannot alias evnode transactions")]
+    InvalidAlias(u8),
+    /// A submitted transaction uses the canonical type after the chain moved to its alias.
+    #[error("evnode transactions must use type {alias:#04x} on this chain, not 0x76")]
+    CanonicalRefused {
+        /// Type byte EvNode transactions must be submitted with.
+        alias: u8,
+    },
+}
+
+/// Type byte raw EvNode transactions may use instead of [`EVNODE_TX_TYPE_ID`] from an
+/// activation height.
+///
+/// The alias only applies to raw transactions: once decoded, EvNode transactions report,
+/// hash and encode with `0x76`. Blocks built by the node keep using `0x76`, so payloads are
+/// accepted with either type byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvNodeTxTypeAlias {
+    alias: u8,
+    activation_height: u64,
+    refuse_canonical: bool,
+}
+
+impl EvNodeTxTypeAlias {
+    /// Creates an alias accepted from `activation_height`.
+    pub const fn new(alias: u8, activation_height: u64) -> Result<Self, EvNodeTxTypeError> {
+        if alias <= MAX_ETHEREUM_TX_ID || alias > MAX_TYPED_TX_ID || alias == EVNODE_TX_TYPE_ID {
+            return Err(EvNodeTxTypeError::InvalidAlias(alias));
+        }
+        Ok(Self {
+            alias,
+            activation_height,
+            refuse_canonical: false,
+        })
+    }
+
+    /// Refuses submitted transactions typed `0x76` once the alias is active, for chains where
+    /// `0x76` belongs to another transaction type.
+    pub const fn with_canonical_refused(mut self, refuse_canonical: bool) -> Self {
+        self.refuse_canonical = refuse_canonical;
+        self
+    }
+
+    /// Returns the alias type byte.
+    pub const fn alias(&self) -> u8 {
+        self.alias
+    }
+
+    /// Returns the block height from which the alias is accepted.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns whether submitted transactions typed `0x76` are refused once the alias is
+    /// active.
+    pub const fn refuses_canonical(&self) -> bool {
+        self.refuse_canonical
+    }
+
+    /// Returns whether the alias is accepted at `block_number`.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+
+    /// Returns `raw` with the alias type byte replaced by [`EVNODE_TX_TYPE_ID`] if the alias is
+    /// active at `block_number`. Other encodings are returned unchanged.
+    pub fn canonicalize<'a>(&self, raw: &'a [u8], block_number: u64) -> Cow<'a, [u8]> {
+        match raw.first() {
+            Some(&ty) if ty == self.alias && self.is_active(block_number) => {
+                let mut canonical = raw.to_vec();
+                canonical[0] = EVNODE_TX_TYPE_ID;
+                Cow::Owned(canonical)
+            }
+            _ => Cow::Borrowed(raw),
+        }
+    }
+
+    /// Like [`Self::canonicalize`], for a transaction submitted to the node, which is refused
+    /// if it uses `0x76` while that is no longer accepted.
+    pub fn canonicalize_submission<'a>(
+        &self,
+        raw: &'a [u8],
+        block_number: u64,
+    ) -> Result<Cow<'a, [u8]>, EvNodeTxTypeError> {
+        if self.refuse_canonical
+            && self.is_active(block_number)
+            && raw.first() == Some(&EVNODE_TX_TYPE_ID)
+        {
+            return Err(EvNodeTxTypeError::CanonicalRefused { alias: self.alias });
+        }
+        Ok(self.canonicalize(raw, block_number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EvPooledTxType, EvTxType};
+    use alloy_eips::Typed2718;
+
+    #[test]
+    fn envelopes_use_canonical_type() {
+        assert_eq!(EvTxType::EvNode.ty(), EVNODE_TX_TYPE_ID);
+        assert_eq!(EvPooledTxType::EvNode.ty(), EVNODE_TX_TYPE_ID);
+    }
+
+    #[test]
+    fn alias_must_be_a_free_typed_transaction_id() {
+        for alias in [0x00, 0x02, 0x04, EVNODE_TX_TYPE_ID, 0x80, 0xc0] {
+            assert_eq!(
+                EvNodeTxTypeAlias::new(alias, 0),
+                Err(EvNodeTxTypeError::InvalidAlias(alias))
+            );
+        }
+        assert_eq!(EvNodeTxTypeAlias::new(0x7a, 5).unwrap().alias(), 0x7a);
+    }
+
+    #[test]
+    fn alias_maps_to_canonical_type_from_activation() {
+        let alias = EvNodeTxTypeAlias::new(0x7a, 10).unwrap();
+        let raw = [0x7a, 0xc0];
+        assert_eq!(alias.canonicalize(&raw, 9).as_ref(), &raw);
+        assert_eq!(
+            alias.canonicalize(&raw, 10).as_ref(),
+            &[EVNODE_TX_TYPE_ID, 0xc0]
+        );
+        // Canonical and Ethereum encodings pass through.
+        assert_eq!(
+            alias.canonicalize(&[0x76, 0xc0], 10).as_ref(),
+            &[0x76, 0xc0]
+        );
+        assert_eq!(
+            alias.canonicalize(&[0x02, 0xc0], 10).as_ref(),
+            &[0x02, 0xc0]
+        );
+        assert_eq!(alias.canonicalize(&[], 10).as_ref(), &[] as &[u8]);
+    }
+
+    #[test]
+    fn refused_canonical_submissions() {
+        let alias = EvNodeTxTypeAlias::new(0x7a, 10)
+            .unwrap()
+            .with_canonical_refused(true);
+        let canonical = [EVNODE_TX_TYPE_ID, 0xc0];
+        assert!(alias.canonicalize_submission(&canonical, 9).is_ok());
+        assert_eq!(
+            alias.canonicalize_submission(&canonical, 10),
+            Err(EvNodeTxTypeError::CanonicalRefused { alias: 0x7a })
+        );
+        // Payloads keep accepting the canonical type.
+        assert_eq!(alias.canonicalize(&canonical, 10).as_ref(), &canonical);
+        assert_eq!(
+            alias
+                .canonicalize_submission(&[0x7a, 0xc0], 10)
+                .unwrap()
+                .as_ref(),
+            &canonical
+        );
+    }
+}
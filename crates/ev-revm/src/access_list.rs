@@ -0,0 +1,155 @@
+//! Size limits and pricing for the access list of EvNode transactions.
+//!
+//! An access list is charged per address and storage key on top of the calls, but the charge
+//! does not scale with the work of loading it, so a cheap batch could carry a huge list. Once
+//! active, the handler rejects EvNode transactions whose access list has more entries, or more
+//! storage keys in one entry, than configured, and adds the configured gas per address and
+//! storage key to their intrinsic gas. The txpool applies the same limits on submission.
+
+use thiserror::Error;
+
+/// Error returned when the access list of an EvNode transaction exceeds the configured limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum AccessListTooLarge {
+    /// The access list has too many entries.
+    #[error("access list has {entries} entries, above the maximum of {max_entries}")]
+    Entries {
+        /// Number of entries in the access list.
+        entries: u64,
+        /// Most entries an access list may have.
+        max_entries: u64,
+    },
+    /// An entry of the access list has too many storage keys.
+    #[error(
+        "access list entry {index} has {storage_keys} storage keys, above the maximum of \
+         {max_storage_keys}"
+    )]
+    StorageKeys {
+        /// Position of the entry in the access list.
+        index: usize,
+        /// Number of storage keys of the entry.
+        storage_keys: u64,
+        /// Most storage keys an entry may have.
+        max_storage_keys: u64,
+    },
+}
+
+/// Settings for bounding and pricing the access list of EvNode transactions from a specific
+/// block height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessListSettings {
+    max_entries: u64,
+    max_storage_keys: u64,
+    address_gas: u64,
+    storage_key_gas: u64,
+    activation_height: u64,
+}
+
+impl AccessListSettings {
+    /// Creates a new settings object allowing `max_entries` entries of at most
+    /// `max_storage_keys` storage keys each, charged `address_gas` per entry and
+    /// `storage_key_gas` per storage key on top of the EIP-2930 costs.
+    pub const fn new(
+        max_entries: u64,
+        max_storage_keys: u64,
+        address_gas: u64,
+        storage_key_gas: u64,
+        activation_height: u64,
+    ) -> Self {
+        Self {
+            max_entries,
+            max_storage_keys,
+            address_gas,
+            storage_key_gas,
+            activation_height,
+        }
+    }
+
+    /// Returns the most entries an access list may have.
+    pub const fn max_entries(&self) -> u64 {
+        self.max_entries
+    }
+
+    /// Returns the most storage keys one entry may have.
+    pub const fn max_storage_keys(&self) -> u64 {
+        self.max_storage_keys
+    }
+
+    /// Returns the extra gas charged per entry and per storage key.
+    pub const fn gas_prices(&self) -> (u64, u64) {
+        (self.address_gas, self.storage_key_gas)
+    }
+
+    /// Returns the activation height for the limits and pricing.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if the limits and pricing apply at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+
+    /// Checks an access list given by the number of storage keys of each entry.
+    pub fn check(
+        &self,
+        storage_keys: impl IntoIterator<Item = usize>,
+    ) -> Result<(), AccessListTooLarge> {
+        let mut entries = 0u64;
+        for (index, keys) in storage_keys.into_iter().enumerate() {
+            if keys as u64 > self.max_storage_keys {
+                return Err(AccessListTooLarge::StorageKeys {
+                    index,
+                    storage_keys: keys as u64,
+                    max_storage_keys: self.max_storage_keys,
+                });
+            }
+            entries += 1;
+        }
+        if entries > self.max_entries {
+            return Err(AccessListTooLarge::Entries {
+                entries,
+                max_entries: self.max_entries,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the gas charged on top of the EIP-2930 costs for `entries` entries holding
+    /// `storage_keys` storage keys in total.
+    pub const fn extra_gas(&self, entries: u64, storage_keys: u64) -> u64 {
+        entries
+            .saturating_mul(self.address_gas)
+            .saturating_add(storage_keys.saturating_mul(self.storage_key_gas))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_entries_and_storage_keys() {
+        let settings = AccessListSettings::new(2, 3, 1_000, 100, 10);
+        assert!(settings.is_active(10));
+        assert!(!settings.is_active(9));
+
+        assert!(settings.check([3, 0]).is_ok());
+        assert_eq!(
+            settings.check([1, 4]),
+            Err(AccessListTooLarge::StorageKeys {
+                index: 1,
+                storage_keys: 4,
+                max_storage_keys: 3,
+            })
+        );
+        assert_eq!(
+            settings.check([0, 0, 0]),
+            Err(AccessListTooLarge::Entries {
+                entries: 3,
+                max_entries: 2,
+            })
+        );
+        assert_eq!(settings.extra_gas(2, 3), 2_300);
+    }
+}
@@ -3,6 +3,7 @@
 use crate::{
     base_fee::BaseFeeRedirect,
     evm::{DefaultEvEvm, EvEvm},
+    handler::EvHandlerSettings,
 };
 use reth_revm::revm::handler::MainBuilder;
 
@@ -30,7 +31,11 @@ where
         self,
         redirect: Option<BaseFeeRedirect>,
     ) -> DefaultEvEvm<<Self as MainBuilder>::Context> {
-        EvEvm::from_inner(self.build_mainnet(), redirect, None, false)
+        EvEvm::from_inner(
+            self.build_mainnet(),
+            EvHandlerSettings::new(redirect, None),
+            false,
+        )
     }
 
     fn build_ev_with_inspector<INSP>(
@@ -40,8 +45,7 @@ where
     ) -> EvEvm<<Self as MainBuilder>::Context, INSP> {
         EvEvm::from_inner(
             self.build_mainnet_with_inspector(inspector),
-            redirect,
-            None,
+            EvHandlerSettings::new(redirect, None),
             true,
         )
     }
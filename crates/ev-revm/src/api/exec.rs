@@ -30,8 +30,10 @@ pub type EvExecutionResult = ExecutionResult<HaltReason>;
 
 impl<CTX, INSP, PRECOMP> ExecuteEvm for EvEvm<CTX, INSP, PRECOMP>
 where
-    CTX: ContextTr<Journal: JournalTr<State = EvmState>, Tx: SponsorPayerTx + BatchCallsTx>
-        + ContextSetters,
+    CTX: ContextTr<
+            Journal: JournalTr<State = EvmState>,
+            Tx: SystemCallTx + SponsorPayerTx + BatchCallsTx,
+        > + ContextSetters,
     <CTX as ContextTr>::Tx: Clone,
     <CTX as ContextTr>::Db: Database,
     <CTX as ContextTr>::Journal:
@@ -49,12 +51,10 @@ where
     }
 
     fn transact_one(&mut self, tx: Self::Tx) -> Result<Self::ExecutionResult, Self::Error> {
-        let redirect = self.redirect();
-        let deploy_allowlist = self.deploy_allowlist();
+        let settings = self.handler_settings().clone();
         let inner = self.inner_mut();
         inner.ctx.set_tx(tx);
-        let mut handler =
-            EvHandler::<_, _, EthFrame<EthInterpreter>>::new(redirect, deploy_allowlist);
+        let mut handler = EvHandler::<_, _, EthFrame<EthInterpreter>>::new(settings);
         handler.run(inner)
     }
 
@@ -65,11 +65,9 @@ where
     fn replay(
         &mut self,
     ) -> Result<ExecResultAndState<Self::ExecutionResult, Self::State>, Self::Error> {
-        let redirect = self.redirect();
-        let deploy_allowlist = self.deploy_allowlist();
+        let settings = self.handler_settings().clone();
         let inner = self.inner_mut();
-        let mut handler =
-            EvHandler::<_, _, EthFrame<EthInterpreter>>::new(redirect, deploy_allowlist);
+        let mut handler = EvHandler::<_, _, EthFrame<EthInterpreter>>::new(settings);
         handler.run(inner).map(|result| {
             let state = inner.journal_mut().finalize();
             ExecResultAndState::new(result, state)
@@ -82,7 +80,7 @@ where
     CTX: ContextTr<
             Db: DatabaseCommit,
             Journal: JournalTr<State = EvmState>,
-            Tx: SponsorPayerTx + BatchCallsTx,
+            Tx: SystemCallTx + SponsorPayerTx + BatchCallsTx,
         > + ContextSetters,
     <CTX as ContextTr>::Tx: Clone,
     PRECOMP: PrecompileProvider<CTX, Output = InterpreterResult>,
@@ -96,7 +94,7 @@ impl<CTX, INSP, PRECOMP> InspectEvm for EvEvm<CTX, INSP, PRECOMP>
 where
     CTX: ContextTr<
             Journal: JournalTr<State = EvmState> + JournalExt,
-            Tx: SponsorPayerTx + BatchCallsTx,
+            Tx: SystemCallTx + SponsorPayerTx + BatchCallsTx,
         > + ContextSetters,
     <CTX as ContextTr>::Tx: Clone,
     INSP: Inspector<CTX, EthInterpreter>,
@@ -109,12 +107,10 @@ where
     }
 
     fn inspect_one_tx(&mut self, tx: Self::Tx) -> Result<Self::ExecutionResult, Self::Error> {
-        let redirect = self.redirect();
-        let deploy_allowlist = self.deploy_allowlist();
+        let settings = self.handler_settings().clone();
         let inner = self.inner_mut();
         inner.ctx.set_tx(tx);
-        let mut handler =
-            EvHandler::<_, _, EthFrame<EthInterpreter>>::new(redirect, deploy_allowlist);
+        let mut handler = EvHandler::<_, _, EthFrame<EthInterpreter>>::new(settings);
         handler.inspect_run(inner)
     }
 }
@@ -124,7 +120,7 @@ where
     CTX: ContextTr<
             Journal: JournalTr<State = EvmState> + JournalExt,
             Db: DatabaseCommit,
-            Tx: SponsorPayerTx + BatchCallsTx,
+            Tx: SystemCallTx + SponsorPayerTx + BatchCallsTx,
         > + ContextSetters,
     <CTX as ContextTr>::Tx: Clone,
     INSP: Inspector<CTX, EthInterpreter>,
@@ -147,8 +143,7 @@ where
         system_contract_address: Address,
         data: Bytes,
     ) -> Result<Self::ExecutionResult, Self::Error> {
-        let redirect = self.redirect();
-        let deploy_allowlist = self.deploy_allowlist();
+        let settings = self.handler_settings().clone();
         let inner = self.inner_mut();
         inner
             .ctx
@@ -157,8 +152,7 @@ where
                 system_contract_address,
                 data,
             ));
-        let mut handler =
-            EvHandler::<_, _, EthFrame<EthInterpreter>>::new(redirect, deploy_allowlist);
+        let mut handler = EvHandler::<_, _, EthFrame<EthInterpreter>>::new(settings);
         handler.run_system_call(inner)
     }
 }
@@ -179,8 +173,7 @@ where
         system_contract_address: Address,
         data: Bytes,
     ) -> Result<Self::ExecutionResult, Self::Error> {
-        let redirect = self.redirect();
-        let deploy_allowlist = self.deploy_allowlist();
+        let settings = self.handler_settings().clone();
         let inner = self.inner_mut();
         inner
             .ctx
@@ -189,8 +182,7 @@ where
                 system_contract_address,
                 data,
             ));
-        let mut handler =
-            EvHandler::<_, _, EthFrame<EthInterpreter>>::new(redirect, deploy_allowlist);
+        let mut handler = EvHandler::<_, _, EthFrame<EthInterpreter>>::new(settings);
         handler.inspect_run_system_call(inner)
     }
 }
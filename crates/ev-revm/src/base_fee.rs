@@ -7,16 +7,37 @@ use reth_revm::revm::{
 };
 use thiserror::Error;
 
+/// Basis points representing the whole base fee.
+pub const MAX_BURN_BPS: u16 = 10_000;
+
 /// Encapsulates the policy of crediting EIP-1559 base fees to a specific address.
+///
+/// A share of the base fee, in basis points, can be left burned; only the remainder is
+/// credited to the sink.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct BaseFeeRedirect {
     fee_sink: Address,
+    burn_bps: u16,
 }
 
 impl BaseFeeRedirect {
     /// Creates a new redirect policy for the provided sink address.
     pub const fn new(fee_sink: Address) -> Self {
-        Self { fee_sink }
+        Self {
+            fee_sink,
+            burn_bps: 0,
+        }
+    }
+
+    /// Burns `burn_bps` basis points of the base fee instead of crediting them to the sink.
+    /// Values above [`MAX_BURN_BPS`] burn the whole base fee.
+    pub const fn with_burn_bps(mut self, burn_bps: u16) -> Self {
+        self.burn_bps = if burn_bps > MAX_BURN_BPS {
+            MAX_BURN_BPS
+        } else {
+            burn_bps
+        };
+        self
     }
 
     /// Returns the configured sink address.
@@ -24,7 +45,18 @@ impl BaseFeeRedirect {
         self.fee_sink
     }
 
-    /// Credits the sink address with the base-fee portion of the transaction cost.
+    /// Returns the share of the base fee that is burned, in basis points.
+    pub const fn burn_bps(&self) -> u16 {
+        self.burn_bps
+    }
+
+    /// Returns the part of `base_fee_amount` credited to the sink; the rest stays burned.
+    pub fn sink_share(&self, base_fee_amount: U256) -> U256 {
+        let kept = U256::from(MAX_BURN_BPS - self.burn_bps);
+        base_fee_amount * kept / U256::from(MAX_BURN_BPS)
+    }
+
+    /// Credits the sink address with its share of the base-fee portion of the transaction cost.
     ///
     /// Returns the amount that was credited (in wei).
     pub fn apply<CTX>(
@@ -43,7 +75,7 @@ impl BaseFeeRedirect {
             return Ok(U256::ZERO);
         }
 
-        let amount = U256::from(base_fee) * U256::from(gas_used);
+        let amount = self.sink_share(U256::from(base_fee) * U256::from(gas_used));
         if amount.is_zero() {
             return Ok(amount);
         }
@@ -96,6 +128,22 @@ mod tests {
         assert_eq!(account.info.balance, amount);
     }
 
+    #[test]
+    fn burns_configured_share() {
+        let fee_sink = address!("0x00000000000000000000000000000000000000fd");
+        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
+        ctx.block.basefee = 100;
+
+        let redirect = BaseFeeRedirect::new(fee_sink).with_burn_bps(2_500);
+        let amount = redirect.apply(&mut ctx, 50_000).expect("credit succeeds");
+        assert_eq!(amount, U256::from(3_750_000));
+        assert_eq!(ctx.journal().account(fee_sink).info.balance, amount);
+
+        let burn_all = BaseFeeRedirect::new(fee_sink).with_burn_bps(u16::MAX);
+        assert_eq!(burn_all.burn_bps(), MAX_BURN_BPS);
+        assert!(burn_all.sink_share(U256::from(5_000_000)).is_zero());
+    }
+
     #[test]
     fn skips_when_no_basefee_or_gas() {
         let fee_sink = address!("0x00000000000000000000000000000000000000ef");
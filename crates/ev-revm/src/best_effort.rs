@@ -0,0 +1,91 @@
+//! Best-effort EvNode batches.
+//!
+//! A batch with [`ev_primitives::RevertMode::BestEffort`] does not revert as a whole when a
+//! call fails: the failing call's own changes are undone, the batch goes on with the next
+//! call and the transaction succeeds. Every failed call is recorded in the receipt as a
+//! `CallFailed(uint256 indexed index, bytes output)` log emitted from
+//! [`BATCH_STATUS_ADDRESS`], so calls without such a log succeeded.
+//!
+//! Best-effort batches change the transaction encoding, so they are rejected before the
+//! activation height. They may not deploy contracts.
+
+use alloy_primitives::{address, keccak256, Address, Bytes, Log, B256, U256};
+
+/// Address the `CallFailed` logs are emitted from. No code is ever deployed there.
+pub const BATCH_STATUS_ADDRESS: Address = address!("0x000000000000000000000000000000000000F176");
+
+/// Solidity signature of the failed call event.
+pub const CALL_FAILED_SIGNATURE: &str = "CallFailed(uint256,bytes)";
+
+/// Settings for accepting best-effort batches from a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct BestEffortSettings {
+    activation_height: u64,
+}
+
+impl BestEffortSettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self { activation_height }
+    }
+
+    /// Returns the activation height for best-effort batches.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if best-effort batches are accepted at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+}
+
+/// Builds the `CallFailed` log for the call at `index` of a batch, with the revert data it
+/// returned, if any.
+pub fn call_failed_log(index: usize, output: &[u8]) -> Log {
+    let padded_len = output.len().div_ceil(32) * 32;
+    let mut data = Vec::with_capacity(64 + padded_len);
+    data.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+    data.extend_from_slice(&U256::from(output.len()).to_be_bytes::<32>());
+    data.extend_from_slice(output);
+    data.resize(64 + padded_len, 0);
+    Log::new_unchecked(
+        BATCH_STATUS_ADDRESS,
+        vec![
+            keccak256(CALL_FAILED_SIGNATURE),
+            B256::from(U256::from(index)),
+        ],
+        Bytes::from(data),
+    )
+}
+
+/// Returns the index of the failed call recorded by `log`, if it is a `CallFailed` log.
+pub fn failed_call_index(log: &Log) -> Option<u64> {
+    let topics = log.topics();
+    if log.address != BATCH_STATUS_ADDRESS
+        || topics.len() != 2
+        || topics[0] != keccak256(CALL_FAILED_SIGNATURE)
+    {
+        return None;
+    }
+    u64::try_from(U256::from_be_bytes(topics[1].0)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_failed_log_layout() {
+        let log = call_failed_log(2, &[0xaa; 33]);
+        assert_eq!(log.address, BATCH_STATUS_ADDRESS);
+        assert_eq!(log.topics()[0], keccak256(CALL_FAILED_SIGNATURE));
+        assert_eq!(log.topics()[1], B256::with_last_byte(2));
+        assert_eq!(log.data.data.len(), 128);
+        assert_eq!(log.data.data[63], 33);
+        assert_eq!(&log.data.data[64..97], &[0xaa; 33]);
+
+        assert_eq!(call_failed_log(0, &[]).data.data.len(), 64);
+        assert_eq!(failed_call_index(&log), Some(2));
+    }
+}
@@ -0,0 +1,32 @@
+//! Per-call gas limits for EvNode batches.
+//!
+//! A call carrying a `gas_limit` runs with at most that much execution gas, so an expensive
+//! or failing call cannot drain the gas of the whole batch. If a capped call runs out of gas
+//! or otherwise halts, the batch still reverts as a whole, but only the gas given to that call
+//! is consumed; the rest of the batch's gas is refunded as for a revert.
+//!
+//! Calls carrying a gas limit change the transaction encoding, so they are rejected before
+//! the activation height.
+
+/// Settings for accepting per-call gas limits from a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct CallGasLimitSettings {
+    activation_height: u64,
+}
+
+impl CallGasLimitSettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self { activation_height }
+    }
+
+    /// Returns the activation height for per-call gas limits.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if per-call gas limits are accepted at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+}
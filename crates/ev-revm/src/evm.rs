@@ -1,6 +1,9 @@
 //! EV-specific EVM wrapper that installs the base-fee redirect handler.
 
-use crate::{base_fee::BaseFeeRedirect, deploy::DeployAllowlistSettings, tx_env::EvTxEnv};
+use crate::{
+    base_fee::BaseFeeRedirect, deploy::DeployAllowlistSettings, handler::EvHandlerSettings,
+    tx_env::EvTxEnv,
+};
 use alloy_evm::{Evm as AlloyEvm, EvmEnv};
 use alloy_primitives::{Address, Bytes};
 use reth_revm::{
@@ -12,10 +15,13 @@ use reth_revm::{
         },
         handler::{
             instructions::EthInstructions, EthFrame, EthPrecompiles, EvmTr, FrameInitOrResult,
-            FrameTr, ItemOrResult, PrecompileProvider,
+            FrameResult, FrameTr, ItemOrResult, PrecompileProvider,
         },
         inspector::{InspectEvm, InspectSystemCallEvm, Inspector, InspectorEvmTr, JournalExt},
-        interpreter::{interpreter::EthInterpreter, InterpreterResult},
+        interpreter::{
+            interpreter::EthInterpreter, CallOutcome, CreateOutcome, FrameInput, Gas,
+            InstructionResult, InterpreterResult,
+        },
         primitives::hardfork::SpecId,
         state::EvmState,
         ExecuteEvm, SystemCallEvm,
@@ -31,26 +37,21 @@ pub type DefaultEvEvm<CTX, INSP = ()> = EvEvm<CTX, INSP, EthPrecompiles>;
 #[derive(Debug)]
 pub struct EvEvm<CTX, INSP, PRECOMP = EthPrecompiles> {
     inner: Evm<CTX, INSP, EthInstructions<EthInterpreter, CTX>, PRECOMP, EthFrame<EthInterpreter>>,
-    redirect: Option<BaseFeeRedirect>,
-    deploy_allowlist: Option<DeployAllowlistSettings>,
+    handler: EvHandlerSettings,
+    max_call_depth: Option<usize>,
     inspect: bool,
 }
 
 impl<CTX, INSP, P> EvEvm<CTX, INSP, P> {
-    /// Wraps an existing EVM instance with the redirect policy.
-    pub fn from_inner<T>(
-        inner: T,
-        redirect: Option<BaseFeeRedirect>,
-        deploy_allowlist: Option<DeployAllowlistSettings>,
-        inspect: bool,
-    ) -> Self
+    /// Wraps an existing EVM instance with the given handler policies.
+    pub fn from_inner<T>(inner: T, handler: EvHandlerSettings, inspect: bool) -> Self
     where
         T: IntoRevmEvm<CTX, INSP, P>,
     {
         Self {
             inner: inner.into_revm_evm(),
-            redirect,
-            deploy_allowlist,
+            handler,
+            max_call_depth: None,
             inspect,
         }
     }
@@ -64,20 +65,37 @@ impl<CTX, INSP, P> EvEvm<CTX, INSP, P> {
 
     /// Returns the configured base-fee redirect policy.
     pub const fn redirect(&self) -> Option<BaseFeeRedirect> {
-        self.redirect
+        self.handler.redirect
     }
 
     /// Returns the configured deploy allowlist settings, if any.
     pub fn deploy_allowlist(&self) -> Option<DeployAllowlistSettings> {
-        self.deploy_allowlist.clone()
+        self.handler.deploy_allowlist.clone()
+    }
+
+    /// Returns the policies the EV handler applies to each transaction.
+    pub const fn handler_settings(&self) -> &EvHandlerSettings {
+        &self.handler
+    }
+
+    /// Fails calls nested deeper than `max_call_depth` with `CallTooDeep`, the top-level call
+    /// being at depth 0.
+    pub fn with_max_call_depth(mut self, max_call_depth: Option<u64>) -> Self {
+        self.max_call_depth = max_call_depth.map(|depth| depth as usize);
+        self
+    }
+
+    /// Returns the configured call depth cap, if any.
+    pub const fn max_call_depth(&self) -> Option<usize> {
+        self.max_call_depth
     }
 
     /// Allows adjusting the precompiles map while preserving redirect configuration.
     pub fn with_precompiles<OP>(self, precompiles: OP) -> EvEvm<CTX, INSP, OP> {
         EvEvm {
             inner: self.inner.with_precompiles(precompiles),
-            redirect: self.redirect,
-            deploy_allowlist: self.deploy_allowlist,
+            handler: self.handler,
+            max_call_depth: self.max_call_depth,
             inspect: self.inspect,
         }
     }
@@ -86,8 +104,8 @@ impl<CTX, INSP, P> EvEvm<CTX, INSP, P> {
     pub fn with_inspector<OINSP>(self, inspector: OINSP) -> EvEvm<CTX, OINSP, P> {
         EvEvm {
             inner: self.inner.with_inspector(inspector),
-            redirect: self.redirect,
-            deploy_allowlist: self.deploy_allowlist,
+            handler: self.handler,
+            max_call_depth: self.max_call_depth,
             inspect: self.inspect,
         }
     }
@@ -309,6 +327,14 @@ where
         ItemOrResult<&mut Self::Frame, <Self::Frame as FrameTr>::FrameResult>,
         ContextError<<<Self::Context as ContextTr>::Db as Database>::Error>,
     > {
+        if self
+            .max_call_depth
+            .is_some_and(|max_depth| frame_input.depth > max_depth)
+        {
+            return Ok(ItemOrResult::Result(call_too_deep(
+                &frame_input.frame_input,
+            )));
+        }
         self.inner.frame_init(frame_input)
     }
 
@@ -332,6 +358,28 @@ where
     }
 }
 
+/// Result of a frame refused for exceeding the call depth cap, matching the one revm returns
+/// past its own limit: the frame's gas is returned unspent and nothing is executed.
+fn call_too_deep(input: &FrameInput) -> FrameResult {
+    let result = |gas_limit| {
+        InterpreterResult::new(
+            InstructionResult::CallTooDeep,
+            Bytes::new(),
+            Gas::new(gas_limit),
+        )
+    };
+    match input {
+        FrameInput::Call(inputs) => FrameResult::Call(CallOutcome::new(
+            result(inputs.gas_limit),
+            inputs.return_memory_offset.clone(),
+        )),
+        FrameInput::Create(inputs) => {
+            FrameResult::Create(CreateOutcome::new(result(inputs.gas_limit()), None))
+        }
+        FrameInput::Empty => unreachable!("empty frames are never initialized"),
+    }
+}
+
 impl<DB, INSP, PRECOMP> AlloyEvm
     for EvEvm<Context<BlockEnv, TxEnv, CfgEnv<SpecId>, DB>, INSP, PRECOMP>
 where
@@ -0,0 +1,77 @@
+//! Per-chain EVM version pinning.
+//!
+//! App-chains inherit Ethereum's timestamp-based hardfork schedule from the chainspec. The
+//! settings here override the resulting `SpecId` by block height, so a chain can decide when
+//! its EVM semantics change independently of mainnet timestamps.
+
+use reth_revm::revm::primitives::hardfork::SpecId;
+use std::sync::Arc;
+
+/// Parses an EVM version name (case-insensitive), e.g. `"cancun"`.
+pub fn parse_evm_version(name: &str) -> Option<SpecId> {
+    let spec = match name.to_ascii_lowercase().as_str() {
+        "istanbul" => SpecId::ISTANBUL,
+        "berlin" => SpecId::BERLIN,
+        "london" => SpecId::LONDON,
+        "paris" | "merge" => SpecId::MERGE,
+        "shanghai" => SpecId::SHANGHAI,
+        "cancun" => SpecId::CANCUN,
+        "prague" => SpecId::PRAGUE,
+        "osaka" => SpecId::OSAKA,
+        _ => return None,
+    };
+    Some(spec)
+}
+
+/// Schedule of pinned EVM versions keyed by activation height.
+#[derive(Debug, Clone)]
+pub struct EvmVersionSettings {
+    /// `(activation_height, spec)` pairs sorted by height.
+    schedule: Arc<[(u64, SpecId)]>,
+}
+
+impl EvmVersionSettings {
+    /// Creates a new schedule from `(activation_height, spec)` pairs.
+    pub fn new(schedule: Vec<(u64, SpecId)>) -> Self {
+        let mut schedule = schedule;
+        schedule.sort_by_key(|(height, _)| *height);
+        Self {
+            schedule: Arc::from(schedule),
+        }
+    }
+
+    /// Returns the `(activation_height, spec)` pairs, sorted by height.
+    pub fn schedule(&self) -> &[(u64, SpecId)] {
+        &self.schedule
+    }
+
+    /// Returns the pinned spec for the given block, or `None` before the first activation.
+    pub fn spec_for_block(&self, block_number: u64) -> Option<SpecId> {
+        self.schedule
+            .iter()
+            .rev()
+            .find(|(height, _)| block_number >= *height)
+            .map(|(_, spec)| *spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_versions() {
+        assert_eq!(parse_evm_version("Cancun"), Some(SpecId::CANCUN));
+        assert_eq!(parse_evm_version("paris"), Some(SpecId::MERGE));
+        assert_eq!(parse_evm_version("homestead"), None);
+    }
+
+    #[test]
+    fn spec_follows_schedule() {
+        let settings = EvmVersionSettings::new(vec![(100, SpecId::PRAGUE), (10, SpecId::SHANGHAI)]);
+        assert_eq!(settings.spec_for_block(9), None);
+        assert_eq!(settings.spec_for_block(10), Some(SpecId::SHANGHAI));
+        assert_eq!(settings.spec_for_block(99), Some(SpecId::SHANGHAI));
+        assert_eq!(settings.spec_for_block(100), Some(SpecId::PRAGUE));
+    }
+}
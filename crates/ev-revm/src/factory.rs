@@ -1,7 +1,9 @@
 //! Helpers for wrapping Reth EVM factories with the EV handler.
 
 use crate::{
-    base_fee::BaseFeeRedirect, deploy::DeployAllowlistSettings, evm::EvEvm, tx_env::EvTxEnv,
+    base_fee::BaseFeeRedirect, evm::EvEvm, evm_version::EvmVersionSettings,
+    handler::EvHandlerSettings, limits::EvmLimitsSettings, tx_env::EvTxEnv,
+    warmup::PrecompileWarmupSettings,
 };
 use alloy_evm::{
     eth::{EthBlockExecutorFactory, EthEvmContext, EthEvmFactory},
@@ -9,7 +11,23 @@ use alloy_evm::{
     Database, EvmEnv, EvmFactory,
 };
 use alloy_primitives::{Address, U256};
-use ev_precompiles::mint::{MintPrecompile, MINT_PRECOMPILE_ADDR};
+use ev_precompiles::{
+    channel::{SystemChannelPrecompile, SYSTEM_CHANNEL_PRECOMPILE_ADDR},
+    expiry::{StateExpiryPrecompile, STATE_EXPIRY_PRECOMPILE_ADDR},
+    features::{
+        FeaturesPrecompile, FEATURES_PRECOMPILE_ADDR, FEATURE_BASE_FEE_REDIRECT,
+        FEATURE_BEST_EFFORT_BATCHES, FEATURE_CALL_GAS_LIMITS, FEATURE_CONTRACT_FEE_PAYERS,
+        FEATURE_DEPLOY_ALLOWLIST, FEATURE_DEPOSIT_INBOX, FEATURE_EIP712_SPONSORSHIPS,
+        FEATURE_MINT_PRECOMPILE, FEATURE_NATIVE_STAKING, FEATURE_NONCE_LANES,
+        FEATURE_P256_SIGNATURES, FEATURE_P256_VERIFY, FEATURE_SESSION_KEYS, FEATURE_STATE_EXPIRY,
+        FEATURE_SYSTEM_CHANNELS, FEATURE_VALIDITY_WINDOWS,
+    },
+    inbox::{InboxPrecompile, INBOX_PRECOMPILE_ADDR},
+    mint::{MintPrecompile, MINT_PRECOMPILE_ADDR},
+    nonce_manager::{NonceManagerPrecompile, NONCE_MANAGER_PRECOMPILE_ADDR},
+    p256_verify::{P256VerifyPrecompile, P256_VERIFY_PRECOMPILE_ADDR},
+    staking::{StakingPrecompile, STAKING_PRECOMPILE_ADDR},
+};
 use reth_evm_ethereum::EthEvmConfig;
 use reth_revm::{
     inspector::NoOpInspector,
@@ -78,6 +96,121 @@ impl MintPrecompileSettings {
     }
 }
 
+/// Settings for enabling the deposit inbox precompile at a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct InboxPrecompileSettings {
+    relayer: Address,
+    activation_height: u64,
+}
+
+impl InboxPrecompileSettings {
+    /// Creates a new settings object.
+    pub const fn new(relayer: Address, activation_height: u64) -> Self {
+        Self {
+            relayer,
+            activation_height,
+        }
+    }
+
+    const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    const fn relayer(&self) -> Address {
+        self.relayer
+    }
+}
+
+/// Settings for enabling the native staking precompile at a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct StakingPrecompileSettings {
+    minimum_stake: U256,
+    unbonding_period: u64,
+    activation_height: u64,
+}
+
+impl StakingPrecompileSettings {
+    /// Creates a new settings object.
+    pub const fn new(minimum_stake: U256, unbonding_period: u64, activation_height: u64) -> Self {
+        Self {
+            minimum_stake,
+            unbonding_period,
+            activation_height,
+        }
+    }
+
+    const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    const fn minimum_stake(&self) -> U256 {
+        self.minimum_stake
+    }
+
+    const fn unbonding_period(&self) -> u64 {
+        self.unbonding_period
+    }
+}
+
+/// Settings for enabling the system channel precompile at a specific block height.
+#[derive(Debug, Clone)]
+pub struct SystemChannelSettings {
+    senders: Arc<[Address]>,
+    activation_height: u64,
+}
+
+impl SystemChannelSettings {
+    /// Creates a new settings object allowing `senders` to advance channel nonces.
+    pub fn new(senders: Vec<Address>, activation_height: u64) -> Self {
+        Self {
+            senders: Arc::from(senders),
+            activation_height,
+        }
+    }
+
+    const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    fn senders(&self) -> Arc<[Address]> {
+        Arc::clone(&self.senders)
+    }
+}
+
+/// Settings for enabling the feature bitmap precompile at a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct FeaturesPrecompileSettings {
+    activation_height: u64,
+}
+
+impl FeaturesPrecompileSettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self { activation_height }
+    }
+
+    const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+}
+
+/// Settings for enabling the RIP-7212 P256 verification precompile at a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct P256VerifyPrecompileSettings {
+    activation_height: u64,
+}
+
+impl P256VerifyPrecompileSettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self { activation_height }
+    }
+
+    const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+}
+
 /// Settings for custom contract size limit with activation height.
 #[derive(Debug, Clone, Copy)]
 pub struct ContractSizeLimitSettings {
@@ -101,6 +234,16 @@ impl ContractSizeLimitSettings {
     const fn limit(&self) -> usize {
         self.limit
     }
+
+    /// Returns the largest initcode a transaction may deploy at `block_number`, twice the
+    /// contract size limit as in EIP-3860, or `None` before activation.
+    pub const fn max_initcode_size(&self, block_number: u64) -> Option<usize> {
+        if block_number >= self.activation_height {
+            Some(self.limit.saturating_mul(2))
+        } else {
+            None
+        }
+    }
 }
 
 /// Wrapper around an existing `EvmFactory` that produces [`EvEvm`] instances.
@@ -109,26 +252,196 @@ pub struct EvEvmFactory<F> {
     inner: F,
     redirect: Option<BaseFeeRedirectSettings>,
     mint_precompile: Option<MintPrecompileSettings>,
-    deploy_allowlist: Option<DeployAllowlistSettings>,
+    handler: EvHandlerSettings,
     contract_size_limit: Option<ContractSizeLimitSettings>,
+    inbox_precompile: Option<InboxPrecompileSettings>,
+    staking_precompile: Option<StakingPrecompileSettings>,
+    system_channel: Option<SystemChannelSettings>,
+    features_precompile: Option<FeaturesPrecompileSettings>,
+    p256_verify_precompile: Option<P256VerifyPrecompileSettings>,
+    evm_version: Option<EvmVersionSettings>,
+    precompile_warmup: Option<PrecompileWarmupSettings>,
+    evm_limits: Option<EvmLimitsSettings>,
 }
 
 impl<F> EvEvmFactory<F> {
     /// Creates a new factory wrapper with the given redirect policy.
-    pub const fn new(
+    pub fn new(
         inner: F,
         redirect: Option<BaseFeeRedirectSettings>,
         mint_precompile: Option<MintPrecompileSettings>,
-        deploy_allowlist: Option<DeployAllowlistSettings>,
         contract_size_limit: Option<ContractSizeLimitSettings>,
     ) -> Self {
         Self {
             inner,
             redirect,
             mint_precompile,
-            deploy_allowlist,
+            handler: EvHandlerSettings::default(),
             contract_size_limit,
+            inbox_precompile: None,
+            staking_precompile: None,
+            system_channel: None,
+            features_precompile: None,
+            p256_verify_precompile: None,
+            evm_version: None,
+            precompile_warmup: None,
+            evm_limits: None,
+        }
+    }
+
+    /// Enables the deposit inbox precompile with the given settings.
+    pub const fn with_inbox_precompile(
+        mut self,
+        inbox_precompile: Option<InboxPrecompileSettings>,
+    ) -> Self {
+        self.inbox_precompile = inbox_precompile;
+        self
+    }
+
+    /// Enables the native staking precompile with the given settings.
+    pub const fn with_staking_precompile(
+        mut self,
+        staking_precompile: Option<StakingPrecompileSettings>,
+    ) -> Self {
+        self.staking_precompile = staking_precompile;
+        self
+    }
+
+    /// Enables the system channel precompile with the given settings.
+    pub fn with_system_channel(mut self, system_channel: Option<SystemChannelSettings>) -> Self {
+        self.system_channel = system_channel;
+        self
+    }
+
+    /// Enables the feature bitmap precompile with the given settings.
+    pub const fn with_features_precompile(
+        mut self,
+        features_precompile: Option<FeaturesPrecompileSettings>,
+    ) -> Self {
+        self.features_precompile = features_precompile;
+        self
+    }
+
+    /// Enables the RIP-7212 P256 verification precompile with the given settings.
+    pub const fn with_p256_verify_precompile(
+        mut self,
+        p256_verify_precompile: Option<P256VerifyPrecompileSettings>,
+    ) -> Self {
+        self.p256_verify_precompile = p256_verify_precompile;
+        self
+    }
+
+    /// Applies the given policies to the EV handler of every EVM the factory creates, and
+    /// installs the precompiles of the state expiry and nonce lane policies. The base-fee
+    /// redirect and warm addresses come from the factory's own settings, resolved per block.
+    pub fn with_handler_settings(mut self, handler: EvHandlerSettings) -> Self {
+        self.handler = handler;
+        self
+    }
+
+    /// Pins the EVM version by block height, overriding the chainspec's hardfork schedule.
+    pub fn with_evm_version(mut self, evm_version: Option<EvmVersionSettings>) -> Self {
+        self.evm_version = evm_version;
+        self
+    }
+
+    /// Warms the installed Evolve precompiles, and any extra addresses, in every transaction.
+    pub fn with_precompile_warmup(
+        mut self,
+        precompile_warmup: Option<PrecompileWarmupSettings>,
+    ) -> Self {
+        self.precompile_warmup = precompile_warmup;
+        self
+    }
+
+    /// Caps EVM memory and call depth with the given settings.
+    pub const fn with_evm_limits(mut self, evm_limits: Option<EvmLimitsSettings>) -> Self {
+        self.evm_limits = evm_limits;
+        self
+    }
+
+    /// Returns the call depth and memory caps active at `block_number`.
+    fn evm_limits_for_block(&self, block_number: U256) -> (Option<u64>, Option<u64>) {
+        let Some(settings) = self.evm_limits else {
+            return (None, None);
+        };
+        let number = u64::try_from(block_number).unwrap_or(u64::MAX);
+        (
+            settings.max_call_depth_for_block(number),
+            settings.memory_limit_for_block(number),
+        )
+    }
+
+    fn warm_addresses_for_block(&self, block_number: U256) -> Vec<Address> {
+        let Some(settings) = &self.precompile_warmup else {
+            return Vec::new();
+        };
+        let number = u64::try_from(block_number).unwrap_or(u64::MAX);
+        if !settings.is_active(number) {
+            return Vec::new();
+        }
+
+        let mut addresses = Vec::new();
+        if self
+            .mint_precompile
+            .is_some_and(|mint| number >= mint.activation_height())
+        {
+            addresses.push(MINT_PRECOMPILE_ADDR);
+        }
+        if self
+            .inbox_precompile
+            .is_some_and(|inbox| number >= inbox.activation_height())
+        {
+            addresses.push(INBOX_PRECOMPILE_ADDR);
+        }
+        if self
+            .staking_precompile
+            .is_some_and(|staking| number >= staking.activation_height())
+        {
+            addresses.push(STAKING_PRECOMPILE_ADDR);
+        }
+        if self
+            .system_channel
+            .as_ref()
+            .is_some_and(|channel| number >= channel.activation_height())
+        {
+            addresses.push(SYSTEM_CHANNEL_PRECOMPILE_ADDR);
+        }
+        if self
+            .handler
+            .state_expiry
+            .is_some_and(|expiry| expiry.is_active(number))
+        {
+            addresses.push(STATE_EXPIRY_PRECOMPILE_ADDR);
+        }
+        if self
+            .features_precompile
+            .is_some_and(|features| number >= features.activation_height())
+        {
+            addresses.push(FEATURES_PRECOMPILE_ADDR);
+        }
+        if self
+            .handler
+            .nonce_lanes
+            .is_some_and(|lanes| lanes.is_active(number))
+        {
+            addresses.push(NONCE_MANAGER_PRECOMPILE_ADDR);
+        }
+        if self
+            .p256_verify_precompile
+            .is_some_and(|p256| number >= p256.activation_height())
+        {
+            addresses.push(P256_VERIFY_PRECOMPILE_ADDR);
         }
+        addresses.extend_from_slice(settings.addresses());
+        addresses
+    }
+
+    fn evm_version_for_block(&self, block_number: U256) -> Option<SpecId> {
+        let block_number = u64::try_from(block_number).unwrap_or(u64::MAX);
+        self.evm_version
+            .as_ref()
+            .and_then(|settings| settings.spec_for_block(block_number))
     }
 
     fn contract_size_limit_for_block(&self, block_number: U256) -> Option<usize> {
@@ -161,6 +474,262 @@ impl<F> EvEvmFactory<F> {
         });
     }
 
+    fn install_inbox_precompile(&self, precompiles: &mut PrecompilesMap, block_number: U256) {
+        let Some(settings) = self.inbox_precompile else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let inbox = Arc::new(InboxPrecompile::new(settings.relayer()));
+        let id = InboxPrecompile::id().clone();
+
+        precompiles.apply_precompile(&INBOX_PRECOMPILE_ADDR, move |_| {
+            let inbox_for_call = Arc::clone(&inbox);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                inbox_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_staking_precompile(&self, precompiles: &mut PrecompilesMap, block_number: U256) {
+        let Some(settings) = self.staking_precompile else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let staking = Arc::new(StakingPrecompile::new(
+            settings.minimum_stake(),
+            settings.unbonding_period(),
+        ));
+        let id = StakingPrecompile::id().clone();
+
+        precompiles.apply_precompile(&STAKING_PRECOMPILE_ADDR, move |_| {
+            let staking_for_call = Arc::clone(&staking);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                staking_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_system_channel_precompile(
+        &self,
+        precompiles: &mut PrecompilesMap,
+        block_number: U256,
+    ) {
+        let Some(settings) = &self.system_channel else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let channels = Arc::new(SystemChannelPrecompile::new(settings.senders()));
+        let id = SystemChannelPrecompile::id().clone();
+
+        precompiles.apply_precompile(&SYSTEM_CHANNEL_PRECOMPILE_ADDR, move |_| {
+            let channels_for_call = Arc::clone(&channels);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                channels_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_state_expiry_precompile(
+        &self,
+        precompiles: &mut PrecompilesMap,
+        block_number: U256,
+    ) {
+        let Some(settings) = self.handler.state_expiry else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let registry = Arc::new(StateExpiryPrecompile::new(
+            settings.activation_height(),
+            settings.expiry_period(),
+        ));
+        let id = StateExpiryPrecompile::id().clone();
+
+        precompiles.apply_precompile(&STATE_EXPIRY_PRECOMPILE_ADDR, move |_| {
+            let registry_for_call = Arc::clone(&registry);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                registry_for_call.call(input)
+            }))
+        });
+    }
+
+    /// Returns the bitmap of Evolve features active at `block_number`.
+    fn features_for_block(&self, block_number: U256) -> u64 {
+        let number = u64::try_from(block_number).unwrap_or(u64::MAX);
+        [
+            (
+                self.mint_precompile
+                    .is_some_and(|mint| number >= mint.activation_height()),
+                FEATURE_MINT_PRECOMPILE,
+            ),
+            (
+                self.inbox_precompile
+                    .is_some_and(|inbox| number >= inbox.activation_height()),
+                FEATURE_DEPOSIT_INBOX,
+            ),
+            (
+                self.staking_precompile
+                    .is_some_and(|staking| number >= staking.activation_height()),
+                FEATURE_NATIVE_STAKING,
+            ),
+            (
+                self.system_channel
+                    .as_ref()
+                    .is_some_and(|channel| number >= channel.activation_height()),
+                FEATURE_SYSTEM_CHANNELS,
+            ),
+            (
+                self.handler
+                    .state_expiry
+                    .is_some_and(|expiry| expiry.is_active(number)),
+                FEATURE_STATE_EXPIRY,
+            ),
+            (
+                self.handler
+                    .gas_allowance
+                    .is_some_and(|allowance| allowance.is_active(number)),
+                FEATURE_CONTRACT_FEE_PAYERS,
+            ),
+            (
+                self.handler
+                    .call_gas_limits
+                    .is_some_and(|limits| limits.is_active(number)),
+                FEATURE_CALL_GAS_LIMITS,
+            ),
+            (
+                self.handler
+                    .best_effort
+                    .is_some_and(|best_effort| best_effort.is_active(number)),
+                FEATURE_BEST_EFFORT_BATCHES,
+            ),
+            (
+                self.handler
+                    .session_keys
+                    .is_some_and(|sessions| sessions.is_active(number)),
+                FEATURE_SESSION_KEYS,
+            ),
+            (
+                self.handler
+                    .eip712_sponsor
+                    .is_some_and(|eip712| eip712.is_active(number)),
+                FEATURE_EIP712_SPONSORSHIPS,
+            ),
+            (
+                self.handler
+                    .validity_windows
+                    .is_some_and(|windows| windows.is_active(number)),
+                FEATURE_VALIDITY_WINDOWS,
+            ),
+            (
+                self.redirect_for_block(block_number).is_some(),
+                FEATURE_BASE_FEE_REDIRECT,
+            ),
+            (
+                self.handler
+                    .deploy_allowlist
+                    .as_ref()
+                    .is_some_and(|allowlist| allowlist.is_active(number)),
+                FEATURE_DEPLOY_ALLOWLIST,
+            ),
+            (
+                self.handler
+                    .nonce_lanes
+                    .is_some_and(|lanes| lanes.is_active(number)),
+                FEATURE_NONCE_LANES,
+            ),
+            (
+                self.handler
+                    .p256_signatures
+                    .is_some_and(|p256| p256.is_active(number)),
+                FEATURE_P256_SIGNATURES,
+            ),
+            (
+                self.p256_verify_precompile
+                    .is_some_and(|p256| number >= p256.activation_height()),
+                FEATURE_P256_VERIFY,
+            ),
+        ]
+        .into_iter()
+        .filter(|(active, _)| *active)
+        .fold(0, |features, (_, bit)| features | bit)
+    }
+
+    fn install_features_precompile(&self, precompiles: &mut PrecompilesMap, block_number: U256) {
+        let Some(settings) = self.features_precompile else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let features = Arc::new(FeaturesPrecompile::new(
+            self.features_for_block(block_number),
+        ));
+        let id = FeaturesPrecompile::id().clone();
+
+        precompiles.apply_precompile(&FEATURES_PRECOMPILE_ADDR, move |_| {
+            let features_for_call = Arc::clone(&features);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                features_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_nonce_manager_precompile(
+        &self,
+        precompiles: &mut PrecompilesMap,
+        block_number: U256,
+    ) {
+        let Some(settings) = self.handler.nonce_lanes else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let manager = Arc::new(NonceManagerPrecompile::new());
+        let id = NonceManagerPrecompile::id().clone();
+
+        precompiles.apply_precompile(&NONCE_MANAGER_PRECOMPILE_ADDR, move |_| {
+            let manager_for_call = Arc::clone(&manager);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                manager_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_p256_verify_precompile(&self, precompiles: &mut PrecompilesMap, block_number: U256) {
+        let Some(settings) = self.p256_verify_precompile else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let verifier = Arc::new(P256VerifyPrecompile::new());
+        let id = P256VerifyPrecompile::id().clone();
+
+        // Replaces the Osaka P256VERIFY precompile at the same address, if any, so the
+        // RIP-7212 pricing applies whatever the hardfork.
+        precompiles.apply_precompile(&P256_VERIFY_PRECOMPILE_ADDR, move |_| {
+            let verifier_for_call = Arc::clone(&verifier);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                verifier_for_call.call(input)
+            }))
+        });
+    }
+
     fn redirect_for_block(&self, block_number: U256) -> Option<BaseFeeRedirect> {
         self.redirect.and_then(|settings| {
             if block_number >= U256::from(settings.activation_height()) {
@@ -170,6 +739,16 @@ impl<F> EvEvmFactory<F> {
             }
         })
     }
+
+    /// Returns the handler policies for `block_number`, with the base-fee redirect and warm
+    /// addresses active at that height.
+    fn handler_settings_for_block(&self, block_number: U256) -> EvHandlerSettings {
+        EvHandlerSettings {
+            redirect: self.redirect_for_block(block_number),
+            warm_addresses: self.warm_addresses_for_block(block_number),
+            ..self.handler.clone()
+        }
+    }
 }
 
 impl EvmFactory for EvEvmFactory<EthEvmFactory> {
@@ -194,16 +773,27 @@ impl EvmFactory for EvEvmFactory<EthEvmFactory> {
         if let Some(limit) = self.contract_size_limit_for_block(block_number) {
             evm_env.cfg_env.limit_contract_code_size = Some(limit);
         }
+        if let Some(spec) = self.evm_version_for_block(block_number) {
+            evm_env.cfg_env.spec = spec;
+        }
+        let (max_call_depth, memory_limit) = self.evm_limits_for_block(block_number);
+        if let Some(limit) = memory_limit {
+            evm_env.cfg_env.memory_limit = limit;
+        }
         let inner = self.inner.create_evm(db, evm_env);
-        let mut evm = EvEvm::from_inner(
-            inner,
-            self.redirect_for_block(block_number),
-            self.deploy_allowlist.clone(),
-            false,
-        );
+        let mut evm =
+            EvEvm::from_inner(inner, self.handler_settings_for_block(block_number), false)
+                .with_max_call_depth(max_call_depth);
         {
             let inner = evm.inner_mut();
             self.install_mint_precompile(&mut inner.precompiles, block_number);
+            self.install_inbox_precompile(&mut inner.precompiles, block_number);
+            self.install_staking_precompile(&mut inner.precompiles, block_number);
+            self.install_system_channel_precompile(&mut inner.precompiles, block_number);
+            self.install_state_expiry_precompile(&mut inner.precompiles, block_number);
+            self.install_features_precompile(&mut inner.precompiles, block_number);
+            self.install_nonce_manager_precompile(&mut inner.precompiles, block_number);
+            self.install_p256_verify_precompile(&mut inner.precompiles, block_number);
         }
         evm
     }
@@ -219,16 +809,26 @@ impl EvmFactory for EvEvmFactory<EthEvmFactory> {
         if let Some(limit) = self.contract_size_limit_for_block(block_number) {
             input.cfg_env.limit_contract_code_size = Some(limit);
         }
+        if let Some(spec) = self.evm_version_for_block(block_number) {
+            input.cfg_env.spec = spec;
+        }
+        let (max_call_depth, memory_limit) = self.evm_limits_for_block(block_number);
+        if let Some(limit) = memory_limit {
+            input.cfg_env.memory_limit = limit;
+        }
         let inner = self.inner.create_evm_with_inspector(db, input, inspector);
-        let mut evm = EvEvm::from_inner(
-            inner,
-            self.redirect_for_block(block_number),
-            self.deploy_allowlist.clone(),
-            true,
-        );
+        let mut evm = EvEvm::from_inner(inner, self.handler_settings_for_block(block_number), true)
+            .with_max_call_depth(max_call_depth);
         {
             let inner = evm.inner_mut();
             self.install_mint_precompile(&mut inner.precompiles, block_number);
+            self.install_inbox_precompile(&mut inner.precompiles, block_number);
+            self.install_staking_precompile(&mut inner.precompiles, block_number);
+            self.install_system_channel_precompile(&mut inner.precompiles, block_number);
+            self.install_state_expiry_precompile(&mut inner.precompiles, block_number);
+            self.install_features_precompile(&mut inner.precompiles, block_number);
+            self.install_nonce_manager_precompile(&mut inner.precompiles, block_number);
+            self.install_p256_verify_precompile(&mut inner.precompiles, block_number);
         }
         evm
     }
@@ -239,8 +839,16 @@ impl EvmFactory for EvEvmFactory<EthEvmFactory> {
 pub struct EvTxEvmFactory {
     redirect: Option<BaseFeeRedirectSettings>,
     mint_precompile: Option<MintPrecompileSettings>,
-    deploy_allowlist: Option<DeployAllowlistSettings>,
+    handler: EvHandlerSettings,
     contract_size_limit: Option<ContractSizeLimitSettings>,
+    inbox_precompile: Option<InboxPrecompileSettings>,
+    staking_precompile: Option<StakingPrecompileSettings>,
+    system_channel: Option<SystemChannelSettings>,
+    features_precompile: Option<FeaturesPrecompileSettings>,
+    p256_verify_precompile: Option<P256VerifyPrecompileSettings>,
+    evm_version: Option<EvmVersionSettings>,
+    precompile_warmup: Option<PrecompileWarmupSettings>,
+    evm_limits: Option<EvmLimitsSettings>,
 }
 
 type EvEvmContext<DB> = Context<
@@ -258,21 +866,183 @@ type EvRevmEvm<DB, I> = RevmEvm<
 >;
 
 impl EvTxEvmFactory {
-    /// Creates a new EV EVM factory with optional redirect, mint, allowlist, and size settings.
-    pub const fn new(
+    /// Creates a new EV EVM factory with optional redirect, mint, and size settings.
+    pub fn new(
         redirect: Option<BaseFeeRedirectSettings>,
         mint_precompile: Option<MintPrecompileSettings>,
-        deploy_allowlist: Option<DeployAllowlistSettings>,
         contract_size_limit: Option<ContractSizeLimitSettings>,
     ) -> Self {
         Self {
             redirect,
             mint_precompile,
-            deploy_allowlist,
+            handler: EvHandlerSettings::default(),
             contract_size_limit,
+            inbox_precompile: None,
+            staking_precompile: None,
+            system_channel: None,
+            features_precompile: None,
+            p256_verify_precompile: None,
+            evm_version: None,
+            precompile_warmup: None,
+            evm_limits: None,
         }
     }
 
+    /// Enables the deposit inbox precompile with the given settings.
+    pub const fn with_inbox_precompile(
+        mut self,
+        inbox_precompile: Option<InboxPrecompileSettings>,
+    ) -> Self {
+        self.inbox_precompile = inbox_precompile;
+        self
+    }
+
+    /// Enables the native staking precompile with the given settings.
+    pub const fn with_staking_precompile(
+        mut self,
+        staking_precompile: Option<StakingPrecompileSettings>,
+    ) -> Self {
+        self.staking_precompile = staking_precompile;
+        self
+    }
+
+    /// Enables the system channel precompile with the given settings.
+    pub fn with_system_channel(mut self, system_channel: Option<SystemChannelSettings>) -> Self {
+        self.system_channel = system_channel;
+        self
+    }
+
+    /// Enables the feature bitmap precompile with the given settings.
+    pub const fn with_features_precompile(
+        mut self,
+        features_precompile: Option<FeaturesPrecompileSettings>,
+    ) -> Self {
+        self.features_precompile = features_precompile;
+        self
+    }
+
+    /// Enables the RIP-7212 P256 verification precompile with the given settings.
+    pub const fn with_p256_verify_precompile(
+        mut self,
+        p256_verify_precompile: Option<P256VerifyPrecompileSettings>,
+    ) -> Self {
+        self.p256_verify_precompile = p256_verify_precompile;
+        self
+    }
+
+    /// Applies the given policies to the EV handler of every EVM the factory creates, and
+    /// installs the precompiles of the state expiry and nonce lane policies. The base-fee
+    /// redirect and warm addresses come from the factory's own settings, resolved per block.
+    pub fn with_handler_settings(mut self, handler: EvHandlerSettings) -> Self {
+        self.handler = handler;
+        self
+    }
+
+    /// Pins the EVM version by block height, overriding the chainspec's hardfork schedule.
+    pub fn with_evm_version(mut self, evm_version: Option<EvmVersionSettings>) -> Self {
+        self.evm_version = evm_version;
+        self
+    }
+
+    /// Warms the installed Evolve precompiles, and any extra addresses, in every transaction.
+    pub fn with_precompile_warmup(
+        mut self,
+        precompile_warmup: Option<PrecompileWarmupSettings>,
+    ) -> Self {
+        self.precompile_warmup = precompile_warmup;
+        self
+    }
+
+    /// Caps EVM memory and call depth with the given settings.
+    pub const fn with_evm_limits(mut self, evm_limits: Option<EvmLimitsSettings>) -> Self {
+        self.evm_limits = evm_limits;
+        self
+    }
+
+    /// Returns the call depth and memory caps active at `block_number`.
+    fn evm_limits_for_block(&self, block_number: U256) -> (Option<u64>, Option<u64>) {
+        let Some(settings) = self.evm_limits else {
+            return (None, None);
+        };
+        let number = u64::try_from(block_number).unwrap_or(u64::MAX);
+        (
+            settings.max_call_depth_for_block(number),
+            settings.memory_limit_for_block(number),
+        )
+    }
+
+    fn warm_addresses_for_block(&self, block_number: U256) -> Vec<Address> {
+        let Some(settings) = &self.precompile_warmup else {
+            return Vec::new();
+        };
+        let number = u64::try_from(block_number).unwrap_or(u64::MAX);
+        if !settings.is_active(number) {
+            return Vec::new();
+        }
+
+        let mut addresses = Vec::new();
+        if self
+            .mint_precompile
+            .is_some_and(|mint| number >= mint.activation_height())
+        {
+            addresses.push(MINT_PRECOMPILE_ADDR);
+        }
+        if self
+            .inbox_precompile
+            .is_some_and(|inbox| number >= inbox.activation_height())
+        {
+            addresses.push(INBOX_PRECOMPILE_ADDR);
+        }
+        if self
+            .staking_precompile
+            .is_some_and(|staking| number >= staking.activation_height())
+        {
+            addresses.push(STAKING_PRECOMPILE_ADDR);
+        }
+        if self
+            .system_channel
+            .as_ref()
+            .is_some_and(|channel| number >= channel.activation_height())
+        {
+            addresses.push(SYSTEM_CHANNEL_PRECOMPILE_ADDR);
+        }
+        if self
+            .handler
+            .state_expiry
+            .is_some_and(|expiry| expiry.is_active(number))
+        {
+            addresses.push(STATE_EXPIRY_PRECOMPILE_ADDR);
+        }
+        if self
+            .features_precompile
+            .is_some_and(|features| number >= features.activation_height())
+        {
+            addresses.push(FEATURES_PRECOMPILE_ADDR);
+        }
+        if self
+            .handler
+            .nonce_lanes
+            .is_some_and(|lanes| lanes.is_active(number))
+        {
+            addresses.push(NONCE_MANAGER_PRECOMPILE_ADDR);
+        }
+        if self
+            .p256_verify_precompile
+            .is_some_and(|p256| number >= p256.activation_height())
+        {
+            addresses.push(P256_VERIFY_PRECOMPILE_ADDR);
+        }
+        addresses.extend_from_slice(settings.addresses());
+        addresses
+    }
+
+    fn evm_version_for_block(&self, block_number: U256) -> Option<SpecId> {
+        let block_number = u64::try_from(block_number).unwrap_or(u64::MAX);
+        self.evm_version
+            .as_ref()
+            .and_then(|settings| settings.spec_for_block(block_number))
+    }
+
     fn contract_size_limit_for_block(&self, block_number: U256) -> Option<usize> {
         self.contract_size_limit.and_then(|settings| {
             if block_number >= U256::from(settings.activation_height()) {
@@ -303,6 +1073,262 @@ impl EvTxEvmFactory {
         });
     }
 
+    fn install_inbox_precompile(&self, precompiles: &mut PrecompilesMap, block_number: U256) {
+        let Some(settings) = self.inbox_precompile else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let inbox = Arc::new(InboxPrecompile::new(settings.relayer()));
+        let id = InboxPrecompile::id().clone();
+
+        precompiles.apply_precompile(&INBOX_PRECOMPILE_ADDR, move |_| {
+            let inbox_for_call = Arc::clone(&inbox);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                inbox_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_staking_precompile(&self, precompiles: &mut PrecompilesMap, block_number: U256) {
+        let Some(settings) = self.staking_precompile else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let staking = Arc::new(StakingPrecompile::new(
+            settings.minimum_stake(),
+            settings.unbonding_period(),
+        ));
+        let id = StakingPrecompile::id().clone();
+
+        precompiles.apply_precompile(&STAKING_PRECOMPILE_ADDR, move |_| {
+            let staking_for_call = Arc::clone(&staking);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                staking_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_system_channel_precompile(
+        &self,
+        precompiles: &mut PrecompilesMap,
+        block_number: U256,
+    ) {
+        let Some(settings) = &self.system_channel else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let channels = Arc::new(SystemChannelPrecompile::new(settings.senders()));
+        let id = SystemChannelPrecompile::id().clone();
+
+        precompiles.apply_precompile(&SYSTEM_CHANNEL_PRECOMPILE_ADDR, move |_| {
+            let channels_for_call = Arc::clone(&channels);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                channels_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_state_expiry_precompile(
+        &self,
+        precompiles: &mut PrecompilesMap,
+        block_number: U256,
+    ) {
+        let Some(settings) = self.handler.state_expiry else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let registry = Arc::new(StateExpiryPrecompile::new(
+            settings.activation_height(),
+            settings.expiry_period(),
+        ));
+        let id = StateExpiryPrecompile::id().clone();
+
+        precompiles.apply_precompile(&STATE_EXPIRY_PRECOMPILE_ADDR, move |_| {
+            let registry_for_call = Arc::clone(&registry);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                registry_for_call.call(input)
+            }))
+        });
+    }
+
+    /// Returns the bitmap of Evolve features active at `block_number`.
+    fn features_for_block(&self, block_number: U256) -> u64 {
+        let number = u64::try_from(block_number).unwrap_or(u64::MAX);
+        [
+            (
+                self.mint_precompile
+                    .is_some_and(|mint| number >= mint.activation_height()),
+                FEATURE_MINT_PRECOMPILE,
+            ),
+            (
+                self.inbox_precompile
+                    .is_some_and(|inbox| number >= inbox.activation_height()),
+                FEATURE_DEPOSIT_INBOX,
+            ),
+            (
+                self.staking_precompile
+                    .is_some_and(|staking| number >= staking.activation_height()),
+                FEATURE_NATIVE_STAKING,
+            ),
+            (
+                self.system_channel
+                    .as_ref()
+                    .is_some_and(|channel| number >= channel.activation_height()),
+                FEATURE_SYSTEM_CHANNELS,
+            ),
+            (
+                self.handler
+                    .state_expiry
+                    .is_some_and(|expiry| expiry.is_active(number)),
+                FEATURE_STATE_EXPIRY,
+            ),
+            (
+                self.handler
+                    .gas_allowance
+                    .is_some_and(|allowance| allowance.is_active(number)),
+                FEATURE_CONTRACT_FEE_PAYERS,
+            ),
+            (
+                self.handler
+                    .call_gas_limits
+                    .is_some_and(|limits| limits.is_active(number)),
+                FEATURE_CALL_GAS_LIMITS,
+            ),
+            (
+                self.handler
+                    .best_effort
+                    .is_some_and(|best_effort| best_effort.is_active(number)),
+                FEATURE_BEST_EFFORT_BATCHES,
+            ),
+            (
+                self.handler
+                    .session_keys
+                    .is_some_and(|sessions| sessions.is_active(number)),
+                FEATURE_SESSION_KEYS,
+            ),
+            (
+                self.handler
+                    .eip712_sponsor
+                    .is_some_and(|eip712| eip712.is_active(number)),
+                FEATURE_EIP712_SPONSORSHIPS,
+            ),
+            (
+                self.handler
+                    .validity_windows
+                    .is_some_and(|windows| windows.is_active(number)),
+                FEATURE_VALIDITY_WINDOWS,
+            ),
+            (
+                self.redirect_for_block(block_number).is_some(),
+                FEATURE_BASE_FEE_REDIRECT,
+            ),
+            (
+                self.handler
+                    .deploy_allowlist
+                    .as_ref()
+                    .is_some_and(|allowlist| allowlist.is_active(number)),
+                FEATURE_DEPLOY_ALLOWLIST,
+            ),
+            (
+                self.handler
+                    .nonce_lanes
+                    .is_some_and(|lanes| lanes.is_active(number)),
+                FEATURE_NONCE_LANES,
+            ),
+            (
+                self.handler
+                    .p256_signatures
+                    .is_some_and(|p256| p256.is_active(number)),
+                FEATURE_P256_SIGNATURES,
+            ),
+            (
+                self.p256_verify_precompile
+                    .is_some_and(|p256| number >= p256.activation_height()),
+                FEATURE_P256_VERIFY,
+            ),
+        ]
+        .into_iter()
+        .filter(|(active, _)| *active)
+        .fold(0, |features, (_, bit)| features | bit)
+    }
+
+    fn install_features_precompile(&self, precompiles: &mut PrecompilesMap, block_number: U256) {
+        let Some(settings) = self.features_precompile else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let features = Arc::new(FeaturesPrecompile::new(
+            self.features_for_block(block_number),
+        ));
+        let id = FeaturesPrecompile::id().clone();
+
+        precompiles.apply_precompile(&FEATURES_PRECOMPILE_ADDR, move |_| {
+            let features_for_call = Arc::clone(&features);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                features_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_nonce_manager_precompile(
+        &self,
+        precompiles: &mut PrecompilesMap,
+        block_number: U256,
+    ) {
+        let Some(settings) = self.handler.nonce_lanes else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let manager = Arc::new(NonceManagerPrecompile::new());
+        let id = NonceManagerPrecompile::id().clone();
+
+        precompiles.apply_precompile(&NONCE_MANAGER_PRECOMPILE_ADDR, move |_| {
+            let manager_for_call = Arc::clone(&manager);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                manager_for_call.call(input)
+            }))
+        });
+    }
+
+    fn install_p256_verify_precompile(&self, precompiles: &mut PrecompilesMap, block_number: U256) {
+        let Some(settings) = self.p256_verify_precompile else {
+            return;
+        };
+        if block_number < U256::from(settings.activation_height()) {
+            return;
+        }
+
+        let verifier = Arc::new(P256VerifyPrecompile::new());
+        let id = P256VerifyPrecompile::id().clone();
+
+        // Replaces the Osaka P256VERIFY precompile at the same address, if any, so the
+        // RIP-7212 pricing applies whatever the hardfork.
+        precompiles.apply_precompile(&P256_VERIFY_PRECOMPILE_ADDR, move |_| {
+            let verifier_for_call = Arc::clone(&verifier);
+            Some(DynPrecompile::new_stateful(id, move |input| {
+                verifier_for_call.call(input)
+            }))
+        });
+    }
+
     fn redirect_for_block(&self, block_number: U256) -> Option<BaseFeeRedirect> {
         self.redirect.and_then(|settings| {
             if block_number >= U256::from(settings.activation_height()) {
@@ -313,6 +1339,16 @@ impl EvTxEvmFactory {
         })
     }
 
+    /// Returns the handler policies for `block_number`, with the base-fee redirect and warm
+    /// addresses active at that height.
+    fn handler_settings_for_block(&self, block_number: U256) -> EvHandlerSettings {
+        EvHandlerSettings {
+            redirect: self.redirect_for_block(block_number),
+            warm_addresses: self.warm_addresses_for_block(block_number),
+            ..self.handler.clone()
+        }
+    }
+
     fn build_evm<DB: Database, I: Inspector<EvEvmContext<DB>>>(
         &self,
         db: DB,
@@ -367,16 +1403,27 @@ impl EvmFactory for EvTxEvmFactory {
         if let Some(limit) = self.contract_size_limit_for_block(block_number) {
             env.cfg_env.limit_contract_code_size = Some(limit);
         }
+        if let Some(spec) = self.evm_version_for_block(block_number) {
+            env.cfg_env.spec = spec;
+        }
+        let (max_call_depth, memory_limit) = self.evm_limits_for_block(block_number);
+        if let Some(limit) = memory_limit {
+            env.cfg_env.memory_limit = limit;
+        }
         let inner = self.build_evm(db, env, NoOpInspector {});
-        let mut evm = EvEvm::from_inner(
-            inner,
-            self.redirect_for_block(block_number),
-            self.deploy_allowlist.clone(),
-            false,
-        );
+        let mut evm =
+            EvEvm::from_inner(inner, self.handler_settings_for_block(block_number), false)
+                .with_max_call_depth(max_call_depth);
         {
             let inner = evm.inner_mut();
             self.install_mint_precompile(&mut inner.precompiles, block_number);
+            self.install_inbox_precompile(&mut inner.precompiles, block_number);
+            self.install_staking_precompile(&mut inner.precompiles, block_number);
+            self.install_system_channel_precompile(&mut inner.precompiles, block_number);
+            self.install_state_expiry_precompile(&mut inner.precompiles, block_number);
+            self.install_features_precompile(&mut inner.precompiles, block_number);
+            self.install_nonce_manager_precompile(&mut inner.precompiles, block_number);
+            self.install_p256_verify_precompile(&mut inner.precompiles, block_number);
         }
         evm
     }
@@ -391,16 +1438,26 @@ impl EvmFactory for EvTxEvmFactory {
         if let Some(limit) = self.contract_size_limit_for_block(block_number) {
             env.cfg_env.limit_contract_code_size = Some(limit);
         }
+        if let Some(spec) = self.evm_version_for_block(block_number) {
+            env.cfg_env.spec = spec;
+        }
+        let (max_call_depth, memory_limit) = self.evm_limits_for_block(block_number);
+        if let Some(limit) = memory_limit {
+            env.cfg_env.memory_limit = limit;
+        }
         let inner = self.build_evm(db, env, inspector);
-        let mut evm = EvEvm::from_inner(
-            inner,
-            self.redirect_for_block(block_number),
-            self.deploy_allowlist.clone(),
-            true,
-        );
+        let mut evm = EvEvm::from_inner(inner, self.handler_settings_for_block(block_number), true)
+            .with_max_call_depth(max_call_depth);
         {
             let inner = evm.inner_mut();
             self.install_mint_precompile(&mut inner.precompiles, block_number);
+            self.install_inbox_precompile(&mut inner.precompiles, block_number);
+            self.install_staking_precompile(&mut inner.precompiles, block_number);
+            self.install_system_channel_precompile(&mut inner.precompiles, block_number);
+            self.install_state_expiry_precompile(&mut inner.precompiles, block_number);
+            self.install_features_precompile(&mut inner.precompiles, block_number);
+            self.install_nonce_manager_precompile(&mut inner.precompiles, block_number);
+            self.install_p256_verify_precompile(&mut inner.precompiles, block_number);
         }
         evm
     }
@@ -411,7 +1468,6 @@ pub fn with_ev_handler<ChainSpec>(
     config: EthEvmConfig<ChainSpec, EthEvmFactory>,
     redirect: Option<BaseFeeRedirectSettings>,
     mint_precompile: Option<MintPrecompileSettings>,
-    deploy_allowlist: Option<DeployAllowlistSettings>,
     contract_size_limit: Option<ContractSizeLimitSettings>,
 ) -> EthEvmConfig<ChainSpec, EvEvmFactory<EthEvmFactory>> {
     let EthEvmConfig {
@@ -422,7 +1478,6 @@ pub fn with_ev_handler<ChainSpec>(
         *executor_factory.evm_factory(),
         redirect,
         mint_precompile,
-        deploy_allowlist,
         contract_size_limit,
     );
     let new_executor_factory = EthBlockExecutorFactory::new(
@@ -440,7 +1495,7 @@ pub fn with_ev_handler<ChainSpec>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::factory::SpecId;
+    use crate::{factory::SpecId, SessionKeySettings};
     use alloy_evm::{Evm, EvmEnv};
     use alloy_primitives::{address, keccak256, Address, Bytes, TxKind, U256};
     use alloy_sol_types::{sol, SolCall};
@@ -510,7 +1565,6 @@ mod tests {
             Some(BaseFeeRedirectSettings::new(redirect, 0)),
             None,
             None,
-            None,
         )
         .create_evm(state, evm_env.clone());
 
@@ -605,7 +1659,6 @@ mod tests {
             None,
             Some(MintPrecompileSettings::new(contract, 0)),
             None,
-            None,
         )
         .create_evm(state, evm_env);
 
@@ -647,7 +1700,6 @@ mod tests {
             Some(BaseFeeRedirectSettings::new(BaseFeeRedirect::new(sink), 5)),
             None,
             None,
-            None,
         );
 
         let mut before_env: alloy_evm::EvmEnv<SpecId> = EvmEnv::default();
@@ -672,6 +1724,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn evm_version_overrides_spec_by_height() {
+        let factory = EvEvmFactory::new(alloy_evm::eth::EthEvmFactory::default(), None, None, None)
+            .with_evm_version(Some(crate::EvmVersionSettings::new(vec![(
+                5,
+                SpecId::SHANGHAI,
+            )])));
+
+        let mut before_env: alloy_evm::EvmEnv<SpecId> = EvmEnv::default();
+        before_env.cfg_env.chain_id = 1;
+        before_env.cfg_env.spec = SpecId::PRAGUE;
+        before_env.block_env.number = U256::from(4);
+        before_env.block_env.gas_limit = 30_000_000;
+
+        let mut after_env = before_env.clone();
+        after_env.block_env.number = U256::from(5);
+
+        let evm_before = factory.create_evm(empty_state(), before_env);
+        assert_eq!(evm_before.ctx.cfg.spec, SpecId::PRAGUE);
+
+        let evm_after = factory.create_evm(empty_state(), after_env);
+        assert_eq!(evm_after.ctx.cfg.spec, SpecId::SHANGHAI);
+    }
+
     #[test]
     fn mint_precompile_respects_activation_height() {
         let caller = address!("0x0000000000000000000000000000000000000aaa");
@@ -717,7 +1793,6 @@ mod tests {
             None,
             Some(MintPrecompileSettings::new(contract, 3)),
             None,
-            None,
         );
 
         let tx_env = || crate::factory::TxEnv {
@@ -766,4 +1841,182 @@ mod tests {
             .expect("mint precompile should mint after activation");
         assert_eq!(mintee_account.info.balance, amount);
     }
+
+    #[test]
+    fn features_precompile_reports_features_active_at_block() {
+        use ev_precompiles::features::{
+            IEvFeatures, FEATURE_MINT_PRECOMPILE, FEATURE_SESSION_KEYS,
+        };
+
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let factory = EvEvmFactory::new(
+            alloy_evm::eth::EthEvmFactory::default(),
+            None,
+            Some(MintPrecompileSettings::new(caller, 0)),
+            None,
+        )
+        .with_handler_settings(EvHandlerSettings {
+            session_keys: Some(SessionKeySettings::new(5)),
+            ..Default::default()
+        })
+        .with_features_precompile(Some(FeaturesPrecompileSettings::new(2)));
+
+        let features_at = |number: u64| {
+            let mut state = empty_state();
+            state.insert_account(
+                caller,
+                AccountInfo {
+                    balance: U256::from(10_000_000_000u64),
+                    nonce: 0,
+                    code_hash: KECCAK_EMPTY,
+                    code: None,
+                    account_id: None,
+                },
+            );
+            let mut env: alloy_evm::EvmEnv<SpecId> = EvmEnv::default();
+            env.cfg_env.chain_id = 1;
+            env.cfg_env.spec = SpecId::CANCUN;
+            env.block_env.number = U256::from(number);
+            env.block_env.basefee = 1;
+            env.block_env.gas_limit = 30_000_000;
+
+            let mut evm = factory.create_evm(state, env);
+            let result = evm
+                .transact_raw(crate::factory::TxEnv {
+                    caller,
+                    kind: TxKind::Call(FEATURES_PRECOMPILE_ADDR),
+                    gas_limit: 100_000,
+                    gas_price: 1,
+                    data: IEvFeatures::featuresCall {}.abi_encode().into(),
+                    ..Default::default()
+                })
+                .expect("features call executes");
+            let ExecutionResult::Success { output, .. } = result.result else {
+                panic!("expected successful features call");
+            };
+            U256::from_be_slice(output.data())
+        };
+
+        // Before activation the address is an empty account and returns no data.
+        assert_eq!(features_at(1), U256::ZERO);
+        assert_eq!(features_at(4), U256::from(FEATURE_MINT_PRECOMPILE));
+        assert_eq!(
+            features_at(5),
+            U256::from(FEATURE_MINT_PRECOMPILE | FEATURE_SESSION_KEYS)
+        );
+    }
+
+    #[test]
+    fn p256_verify_precompile_installed_from_activation_height() {
+        use ev_precompiles::p256_verify::{P256_VERIFY_GAS, P256_VERIFY_PRECOMPILE_ADDR};
+
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let factory = EvEvmFactory::new(alloy_evm::eth::EthEvmFactory::default(), None, None, None)
+            .with_p256_verify_precompile(Some(P256VerifyPrecompileSettings::new(2)));
+
+        let gas_used_at = |number: u64| {
+            let mut state = empty_state();
+            state.insert_account(
+                caller,
+                AccountInfo {
+                    balance: U256::from(10_000_000_000u64),
+                    nonce: 0,
+                    code_hash: KECCAK_EMPTY,
+                    code: None,
+                    account_id: None,
+                },
+            );
+            let mut env: alloy_evm::EvmEnv<SpecId> = EvmEnv::default();
+            env.cfg_env.chain_id = 1;
+            env.cfg_env.spec = SpecId::CANCUN;
+            env.block_env.number = U256::from(number);
+            env.block_env.basefee = 1;
+            env.block_env.gas_limit = 30_000_000;
+
+            let mut evm = factory.create_evm(state, env);
+            let result = evm
+                .transact_raw(crate::factory::TxEnv {
+                    caller,
+                    kind: TxKind::Call(P256_VERIFY_PRECOMPILE_ADDR),
+                    gas_limit: 100_000,
+                    gas_price: 1,
+                    ..Default::default()
+                })
+                .expect("verify call executes");
+            let ExecutionResult::Success { gas, output, .. } = result.result else {
+                panic!("expected successful verify call");
+            };
+            // An empty input is not a valid signature, so no data is returned either way.
+            assert!(output.data().is_empty());
+            gas.tx_gas_used()
+        };
+
+        // Before activation the address is an empty account on Cancun.
+        assert_eq!(gas_used_at(1), 21_000);
+        assert_eq!(gas_used_at(2), 21_000 + P256_VERIFY_GAS);
+    }
+
+    #[test]
+    fn evm_limits_apply_from_activation_height() {
+        // Counts its frames in slot 0, then calls itself with all remaining gas.
+        const RECURSIVE_COUNTER_RUNTIME: [u8; 23] =
+            alloy_primitives::hex!("60005460010160005560006000600060006000305af100");
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let contract = address!("0x0000000000000000000000000000000000000bbb");
+
+        let build_state = || {
+            let mut state = empty_state();
+            state.insert_account(
+                contract,
+                AccountInfo {
+                    balance: U256::ZERO,
+                    nonce: 1,
+                    code_hash: keccak256(RECURSIVE_COUNTER_RUNTIME.as_slice()),
+                    code: Some(RevmBytecode::new_raw(Bytes::copy_from_slice(
+                        RECURSIVE_COUNTER_RUNTIME.as_slice(),
+                    ))),
+                    account_id: None,
+                },
+            );
+            state
+        };
+
+        let factory = EvEvmFactory::new(alloy_evm::eth::EthEvmFactory::default(), None, None, None)
+            .with_evm_limits(Some(
+                crate::EvmLimitsSettings::new(5)
+                    .with_max_call_depth(Some(16))
+                    .with_memory_limit(Some(1 << 20)),
+            ));
+
+        let frames = |block_number: u64| {
+            let mut env: alloy_evm::EvmEnv<SpecId> = EvmEnv::default();
+            env.cfg_env.chain_id = 1;
+            env.cfg_env.spec = SpecId::CANCUN;
+            env.block_env.number = U256::from(block_number);
+            env.block_env.gas_limit = 30_000_000;
+
+            let mut evm = factory.create_evm(build_state(), env);
+            let memory_limit = evm.ctx.cfg.memory_limit;
+            let result = evm
+                .transact_raw(crate::factory::TxEnv {
+                    caller,
+                    kind: TxKind::Call(contract),
+                    gas_limit: 1_000_000,
+                    ..Default::default()
+                })
+                .expect("recursive call executes");
+            assert!(result.result.is_success());
+            let frames = result.state[&contract].storage[&U256::ZERO].present_value;
+            (frames, memory_limit)
+        };
+
+        let (frames_before, memory_limit_before) = frames(4);
+        assert!(frames_before > U256::from(17));
+        assert_eq!(memory_limit_before, u32::MAX as u64);
+
+        // Depths 0 through 16 run; the call at depth 17 fails without executing.
+        let (frames_after, memory_limit_after) = frames(5);
+        assert_eq!(frames_after, U256::from(17));
+        assert_eq!(memory_limit_after, 1 << 20);
+    }
 }
@@ -0,0 +1,135 @@
+//! Contract fee payers backed by the `IGasAllowance` interface.
+//!
+//! An `EvNode` transaction may designate a contract as its fee payer (see
+//! [`ev_primitives::EvNodeTransaction::contract_fee_payer`]). Before gas is deducted, the
+//! handler calls `approveGas(address executor, uint256 amount)` on that contract from
+//! [`GAS_ALLOWANCE_CALLER`]; the contract must return `true` for the transaction to proceed.
+//! The contract then pays for gas exactly like an ECDSA sponsor.
+//!
+//! Once the post-op hook is active, the handler also calls `postOp(uint256 actualGasCost,
+//! bool success)` on the contract after execution, so it can do its own accounting, emit
+//! events or bill the executor in another token. The hook cannot fail the transaction.
+
+use alloy_primitives::{address, keccak256, Address, Bytes, U256};
+
+/// Caller used for `approveGas` calls, the same system address used for system calls.
+pub const GAS_ALLOWANCE_CALLER: Address = address!("0xfffffffffffffffffffffffffffffffffffffffe");
+
+/// Maximum gas available to a single `approveGas` call.
+///
+/// The gas actually used is charged to the transaction as intrinsic gas.
+pub const APPROVE_GAS_STIPEND: u64 = 50_000;
+
+/// Solidity signature of the allowance hook.
+pub const APPROVE_GAS_SIGNATURE: &str = "approveGas(address,uint256)";
+
+/// Maximum gas available to a single `postOp` call.
+///
+/// The gas actually used is charged to the transaction on top of its execution gas.
+pub const POST_OP_GAS_STIPEND: u64 = 50_000;
+
+/// Solidity signature of the post-execution hook.
+pub const POST_OP_SIGNATURE: &str = "postOp(uint256,bool)";
+
+/// Settings for enabling contract fee payers at a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct GasAllowanceSettings {
+    activation_height: u64,
+    post_op_activation_height: Option<u64>,
+}
+
+impl GasAllowanceSettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self {
+            activation_height,
+            post_op_activation_height: None,
+        }
+    }
+
+    /// Calls `postOp` on contract fee payers from `height` onwards.
+    pub const fn with_post_op_activation_height(mut self, height: Option<u64>) -> Self {
+        self.post_op_activation_height = height;
+        self
+    }
+
+    /// Returns the activation height for contract fee payers.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if contract fee payers are accepted at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+
+    /// Returns true if contract fee payers are called back with `postOp` at the given block
+    /// number.
+    pub const fn post_op_active(&self, block_number: u64) -> bool {
+        match self.post_op_activation_height {
+            Some(height) => self.is_active(block_number) && block_number >= height,
+            None => false,
+        }
+    }
+}
+
+/// Encodes an `approveGas(executor, amount)` call.
+pub fn approve_gas_calldata(executor: Address, amount: U256) -> Bytes {
+    let mut data = Vec::with_capacity(4 + 64);
+    data.extend_from_slice(&keccak256(APPROVE_GAS_SIGNATURE)[..4]);
+    data.extend_from_slice(executor.into_word().as_slice());
+    data.extend_from_slice(&amount.to_be_bytes::<32>());
+    data.into()
+}
+
+/// Encodes a `postOp(actualGasCost, success)` call.
+pub fn post_op_calldata(actual_gas_cost: U256, success: bool) -> Bytes {
+    let mut data = Vec::with_capacity(4 + 64);
+    data.extend_from_slice(&keccak256(POST_OP_SIGNATURE)[..4]);
+    data.extend_from_slice(&actual_gas_cost.to_be_bytes::<32>());
+    data.extend_from_slice(&U256::from(success).to_be_bytes::<32>());
+    data.into()
+}
+
+/// Decodes the `bool` returned by `approveGas`.
+///
+/// Anything other than a single ABI word equal to one, including empty output from an
+/// account without code, counts as a denial.
+pub fn is_approved(output: &[u8]) -> bool {
+    output.len() == 32 && output[..31].iter().all(|byte| *byte == 0) && output[31] == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calldata_layout() {
+        let executor = Address::repeat_byte(0x11);
+        let data = approve_gas_calldata(executor, U256::from(7));
+        assert_eq!(data.len(), 68);
+        assert_eq!(&data[..4], &keccak256(APPROVE_GAS_SIGNATURE)[..4]);
+        assert_eq!(&data[16..36], executor.as_slice());
+        assert_eq!(data[67], 7);
+    }
+
+    #[test]
+    fn post_op_calldata_layout() {
+        let data = post_op_calldata(U256::from(7), true);
+        assert_eq!(data.len(), 68);
+        assert_eq!(&data[..4], &keccak256(POST_OP_SIGNATURE)[..4]);
+        assert_eq!(data[35], 7);
+        assert_eq!(data[67], 1);
+    }
+
+    #[test]
+    fn only_true_is_approval() {
+        let mut word = [0u8; 32];
+        assert!(!is_approved(&word));
+        assert!(!is_approved(&[]));
+        word[31] = 1;
+        assert!(is_approved(&word));
+        word[0] = 1;
+        assert!(!is_approved(&word));
+    }
+}
@@ -1,11 +1,28 @@
 //! Execution handler extensions for EV-specific fee policies.
 
 use crate::{
+    access_list::AccessListSettings,
     base_fee::{BaseFeeRedirect, BaseFeeRedirectError},
+    best_effort::{self, BestEffortSettings},
+    call_gas::CallGasLimitSettings,
     deploy::DeployAllowlistSettings,
+    gas_allowance::{
+        self, GasAllowanceSettings, APPROVE_GAS_STIPEND, GAS_ALLOWANCE_CALLER, POST_OP_GAS_STIPEND,
+    },
+    max_tx_gas::MaxTxGasSettings,
+    nonce_lane::NonceLaneSettings,
+    p256_signature::P256SignatureSettings,
+    session::SessionKeySettings,
+    sponsor_allowlist::SponsorAllowlistSettings,
+    sponsor_hash::{Eip712SponsorSettings, SponsorHashMigration},
+    state_expiry::{self, StateExpiryError, StateExpirySettings},
+    system_fee::{SystemFeeMode, SystemFeeSettings},
     tx_env::{BatchCallsTx, SponsorPayerTx},
+    validity::ValidityWindowSettings,
 };
-use alloy_primitives::{TxKind, U256};
+use alloy_primitives::{aliases::U192, Address, Bytes, TxKind, U256};
+use ev_precompiles::{expiry::STATE_EXPIRY_PRECOMPILE_ADDR, nonce_manager::nonce_lane_address};
+use ev_primitives::SponsorHashScheme;
 use reth_revm::{
     inspector::{Inspector, InspectorEvmTr, InspectorHandler},
     revm::{
@@ -18,13 +35,13 @@ use reth_revm::{
         },
         handler::{
             post_execution, EthFrame, EvmTr, EvmTrError, FrameResult, FrameTr, Handler,
-            MainnetHandler,
+            MainnetHandler, SystemCallTx,
         },
         interpreter::{
             gas::{calculate_initial_tx_gas, ACCESS_LIST_ADDRESS, ACCESS_LIST_STORAGE_KEY},
             interpreter::EthInterpreter,
             interpreter_action::FrameInit,
-            Gas, InitialAndFloorGas,
+            Gas, InitialAndFloorGas, InstructionResult,
         },
         primitives::{eip7702, hardfork::SpecId},
         state::{AccountInfo, Bytecode, EvmState},
@@ -32,37 +49,321 @@ use reth_revm::{
 };
 use std::cmp::Ordering;
 
-/// Handler wrapper that mirrors the mainnet handler but applies optional EV-specific policies.
-#[derive(Debug, Clone)]
-pub struct EvHandler<EVM, ERROR, FRAME> {
-    inner: MainnetHandler<EVM, ERROR, FRAME>,
-    redirect: Option<BaseFeeRedirect>,
-    deploy_allowlist: Option<DeployAllowlistSettings>,
+/// EV-specific policies applied by [`EvHandler`], each disabled when unset.
+#[derive(Debug, Clone, Default)]
+pub struct EvHandlerSettings {
+    /// Base-fee redirect policy.
+    pub redirect: Option<BaseFeeRedirect>,
+    /// Deploy allowlist for contract creation.
+    pub deploy_allowlist: Option<DeployAllowlistSettings>,
+    /// `IGasAllowance` contract fee payers.
+    pub gas_allowance: Option<GasAllowanceSettings>,
+    /// Account state expiry.
+    pub state_expiry: Option<StateExpirySettings>,
+    /// Fee policy for system transactions.
+    pub system_fee: Option<SystemFeeSettings>,
+    /// Sponsor signing hash scheme migration.
+    pub sponsor_hash: Option<SponsorHashMigration>,
+    /// EIP-712 sponsorships.
+    pub eip712_sponsor: Option<Eip712SponsorSettings>,
+    /// Per-call gas limits in EvNode batches.
+    pub call_gas_limits: Option<CallGasLimitSettings>,
+    /// Best-effort EvNode batches.
+    pub best_effort: Option<BestEffortSettings>,
+    /// Session-key EvNode transactions.
+    pub session_keys: Option<SessionKeySettings>,
+    /// EvNode transaction validity windows.
+    pub validity_windows: Option<ValidityWindowSettings>,
+    /// EvNode nonce lanes.
+    pub nonce_lanes: Option<NonceLaneSettings>,
+    /// P256-signed EvNode transactions.
+    pub p256_signatures: Option<P256SignatureSettings>,
+    /// Per-transaction cap on the share of block gas.
+    pub max_tx_gas: Option<MaxTxGasSettings>,
+    /// Bounds and pricing of EvNode access lists.
+    pub access_list: Option<AccessListSettings>,
+    /// Fee payers allowed to sponsor transactions.
+    pub sponsor_allowlist: Option<SponsorAllowlistSettings>,
+    /// Addresses treated as warm in every transaction.
+    pub warm_addresses: Vec<Address>,
 }
 
-impl<EVM, ERROR, FRAME> EvHandler<EVM, ERROR, FRAME> {
-    /// Creates a new handler wrapper with the provided redirect policy.
+impl EvHandlerSettings {
+    /// Creates settings with the given redirect policy and deploy allowlist, and every other
+    /// policy disabled.
     pub fn new(
         redirect: Option<BaseFeeRedirect>,
         deploy_allowlist: Option<DeployAllowlistSettings>,
     ) -> Self {
         Self {
-            inner: MainnetHandler::default(),
             redirect,
             deploy_allowlist,
+            ..Default::default()
+        }
+    }
+}
+
+/// Handler wrapper that mirrors the mainnet handler but applies optional EV-specific policies.
+#[derive(Debug, Clone)]
+pub struct EvHandler<EVM, ERROR, FRAME> {
+    inner: MainnetHandler<EVM, ERROR, FRAME>,
+    settings: EvHandlerSettings,
+}
+
+impl<EVM, ERROR, FRAME> EvHandler<EVM, ERROR, FRAME> {
+    /// Creates a new handler wrapper applying the given policies.
+    pub fn new(settings: EvHandlerSettings) -> Self {
+        Self {
+            inner: MainnetHandler::default(),
+            settings,
         }
     }
 
     /// Returns the configured redirect policy, if any.
     pub const fn redirect(&self) -> Option<BaseFeeRedirect> {
-        self.redirect
+        self.settings.redirect
+    }
+
+    /// Rejects transactions touching archived accounts and records the sender, fee payer and
+    /// call targets as active in the current block.
+    fn apply_state_expiry(&self, evm: &mut EVM) -> Result<(), ERROR>
+    where
+        EVM: EvmTr<
+            Context: ContextTr<
+                Journal: JournalTr<State = EvmState>,
+                Tx: SponsorPayerTx + BatchCallsTx,
+            >,
+        >,
+        ERROR: EvmTrError<EVM>,
+    {
+        let block_number = evm
+            .ctx_ref()
+            .block()
+            .number()
+            .try_into()
+            .unwrap_or(u64::MAX);
+        if !self
+            .settings
+            .state_expiry
+            .is_some_and(|settings| settings.is_active(block_number))
+        {
+            return Ok(());
+        }
+
+        let tx = evm.ctx_ref().tx();
+        let mut accounts = vec![tx.caller()];
+        accounts.extend(tx.sponsor());
+        match tx.batch_calls() {
+            Some(calls) => accounts.extend(calls.iter().filter_map(|call| call.to.to().copied())),
+            None => accounts.extend(tx.kind().to().copied()),
+        }
+        // Reviving goes through the registry, which is never archived itself.
+        accounts.retain(|account| *account != STATE_EXPIRY_PRECOMPILE_ADDR);
+        accounts.sort_unstable();
+        accounts.dedup();
+
+        state_expiry::check_and_record_activity(
+            evm.ctx_mut().journal_mut(),
+            &accounts,
+            block_number,
+        )
+        .map_err(|err| match err {
+            StateExpiryError::Database(err) => ERROR::from(err),
+            err @ StateExpiryError::Archived(_) => ERROR::from_string(err.to_string()),
+        })
+    }
+
+    /// Switches the sponsor to the one recovered under the sponsor hash scheme in force.
+    ///
+    /// Recovery yields an address for any hash, so the signature alone does not tell which
+    /// scheme it was made under. Inside the migration transition window, or once EIP-712
+    /// sponsorships are active, another scheme is used only when the preferred sponsor cannot
+    /// cover the maximum gas cost and the other scheme's sponsor can; the migration fallback
+    /// is tried before EIP-712.
+    fn apply_sponsor_hash_scheme(&self, evm: &mut EVM) -> Result<(), ERROR>
+    where
+        EVM: EvmTr<
+            Context: ContextTr<Journal: JournalTr<State = EvmState>, Tx: SponsorPayerTx + Clone>
+                         + ContextSetters,
+        >,
+        ERROR: EvmTrError<EVM>,
+    {
+        if self.settings.sponsor_hash.is_none() && self.settings.eip712_sponsor.is_none() {
+            return Ok(());
+        }
+        let block_number = evm
+            .ctx_ref()
+            .block()
+            .number()
+            .try_into()
+            .unwrap_or(u64::MAX);
+        let (preferred, fallback) = self
+            .settings
+            .sponsor_hash
+            .map_or((SponsorHashScheme::Executor, None), |migration| {
+                migration.schemes_at(block_number)
+            });
+        let eip712 = self
+            .settings
+            .eip712_sponsor
+            .is_some_and(|settings| settings.is_active(block_number))
+            .then_some(SponsorHashScheme::Eip712);
+        let tx = evm.ctx_ref().tx();
+        let Some(mut sponsor) = tx.sponsor_for_scheme(preferred) else {
+            return Ok(());
+        };
+        let alternatives = [fallback, eip712]
+            .map(|scheme| scheme.and_then(|scheme| tx.sponsor_for_scheme(scheme)));
+        if alternatives.iter().any(Option::is_some) {
+            let (max_gas_cost, _) = split_sponsored_fee(
+                U256::from(tx.gas_limit()).saturating_mul(U256::from(tx.max_fee_per_gas())),
+                tx.max_sponsor_fee().map(U256::from),
+            );
+            let journal = evm.ctx_mut().journal_mut();
+            if journal.load_account(sponsor)?.data.info.balance < max_gas_cost {
+                for alternative in alternatives.into_iter().flatten() {
+                    if journal.load_account(alternative)?.data.info.balance >= max_gas_cost {
+                        sponsor = alternative;
+                        break;
+                    }
+                }
+            }
+        }
+        if evm.ctx_ref().tx().sponsor() != Some(sponsor) {
+            let mut tx = evm.ctx_ref().tx().clone();
+            tx.set_sponsor(sponsor);
+            evm.ctx_mut().set_tx(tx);
+        }
+        Ok(())
+    }
+
+    /// Returns the fee policy for the current transaction if it is a system transaction.
+    fn system_fee_mode(&self, evm: &EVM) -> Option<SystemFeeMode>
+    where
+        EVM: EvmTr,
+    {
+        let settings = self.settings.system_fee.as_ref()?;
+        let ctx = evm.ctx_ref();
+        let block_number = ctx.block().number().try_into().unwrap_or(u64::MAX);
+        settings.mode_for(ctx.tx().caller(), block_number)
+    }
+
+    /// Asks a contract fee payer to approve `amount` of gas for `executor`.
+    ///
+    /// Runs `approveGas(executor, amount)` as a frame from [`GAS_ALLOWANCE_CALLER`] with at
+    /// most [`APPROVE_GAS_STIPEND`] gas and charges the gas used to the transaction's
+    /// intrinsic gas. State changes made by the contract (e.g. budget bookkeeping) are kept.
+    fn approve_contract_gas(
+        &self,
+        evm: &mut EVM,
+        init_and_floor_gas: &mut InitialAndFloorGas,
+        fee_payer: Address,
+        executor: Address,
+        amount: U256,
+    ) -> Result<(), ERROR>
+    where
+        EVM: EvmTr<
+            Context: ContextTr<Journal: JournalTr<State = EvmState>, Tx: SystemCallTx + Clone>
+                         + ContextSetters,
+            Frame = FRAME,
+        >,
+        ERROR: EvmTrError<EVM>,
+        FRAME: FrameTr<FrameResult = FrameResult, FrameInit = FrameInit>,
+    {
+        let tx_gas_limit = evm.ctx_ref().tx().gas_limit();
+        let available = tx_gas_limit.saturating_sub(init_and_floor_gas.initial_total_gas);
+        let stipend = available.min(APPROVE_GAS_STIPEND);
+
+        let original_tx = evm.ctx_ref().tx().clone();
+        evm.ctx_mut().set_tx(
+            <<EVM as EvmTr>::Context as ContextTr>::Tx::new_system_tx_with_caller(
+                GAS_ALLOWANCE_CALLER,
+                fee_payer,
+                gas_allowance::approve_gas_calldata(executor, amount),
+            ),
+        );
+        let mut approval = MainnetHandler::<EVM, ERROR, FRAME>::default();
+        let result = approval
+            .first_frame_input(evm, stipend, 0)
+            .and_then(|frame_input| approval.run_exec_loop(evm, frame_input));
+        evm.ctx_mut().set_tx(original_tx);
+        let frame_result = result?;
+
+        let interpreter_result = frame_result.interpreter_result();
+        let approved = interpreter_result.result.is_ok()
+            && gas_allowance::is_approved(&interpreter_result.output);
+        if !approved {
+            return Err(
+                <ERROR as reth_revm::revm::context::result::FromStringError>::from_string(
+                    "gas allowance denied".to_string(),
+                ),
+            );
+        }
+
+        init_and_floor_gas.initial_total_gas = init_and_floor_gas
+            .initial_total_gas
+            .saturating_add(frame_result.gas().spent());
+        Ok(())
+    }
+
+    /// Reports the final gas cost of a sponsored transaction to its contract fee payer.
+    ///
+    /// Runs `postOp(actual_gas_cost, success)` as a frame from [`GAS_ALLOWANCE_CALLER`] with at
+    /// most [`POST_OP_GAS_STIPEND`] gas, bounded by the gas the transaction has left, and
+    /// charges the gas used to the transaction. A reverting hook only loses its own state
+    /// changes; the transaction's outcome stands.
+    fn call_post_op(
+        &self,
+        evm: &mut EVM,
+        exec_result: &mut FrameResult,
+        fee_payer: Address,
+        actual_gas_cost: U256,
+        success: bool,
+    ) -> Result<(), ERROR>
+    where
+        EVM: EvmTr<
+            Context: ContextTr<Journal: JournalTr<State = EvmState>, Tx: SystemCallTx + Clone>
+                         + ContextSetters,
+            Frame = FRAME,
+        >,
+        ERROR: EvmTrError<EVM>,
+        FRAME: FrameTr<FrameResult = FrameResult, FrameInit = FrameInit>,
+    {
+        let stipend = exec_result.gas().remaining().min(POST_OP_GAS_STIPEND);
+        if stipend == 0 {
+            return Ok(());
+        }
+
+        let original_tx = evm.ctx_ref().tx().clone();
+        evm.ctx_mut().set_tx(
+            <<EVM as EvmTr>::Context as ContextTr>::Tx::new_system_tx_with_caller(
+                GAS_ALLOWANCE_CALLER,
+                fee_payer,
+                gas_allowance::post_op_calldata(actual_gas_cost, success),
+            ),
+        );
+        let mut post_op = MainnetHandler::<EVM, ERROR, FRAME>::default();
+        let result = post_op
+            .first_frame_input(evm, stipend, 0)
+            .and_then(|frame_input| post_op.run_exec_loop(evm, frame_input));
+        evm.ctx_mut().set_tx(original_tx);
+        let used = result?.gas().spent();
+
+        let gas = exec_result.gas();
+        let mut charged = Gas::new_spent(gas.limit());
+        charged.erase_cost(gas.remaining().saturating_sub(used));
+        charged.record_refund(gas.refunded());
+        charged.set_state_gas_spent(gas.state_gas_spent());
+        charged.set_reservoir(gas.reservoir());
+        *exec_result.gas_mut() = charged;
+        Ok(())
     }
 
     const fn deploy_allowlist_for_block(
         &self,
         block_number: u64,
     ) -> Option<&DeployAllowlistSettings> {
-        match self.deploy_allowlist.as_ref() {
+        match self.settings.deploy_allowlist.as_ref() {
             Some(settings) if settings.is_active(block_number) => Some(settings),
             _ => None,
         }
@@ -102,7 +403,7 @@ where
     EVM: EvmTr<
         Context: ContextTr<
             Journal: JournalTr<State = EvmState>,
-            Tx: SponsorPayerTx + BatchCallsTx,
+            Tx: SystemCallTx + SponsorPayerTx + BatchCallsTx,
         > + ContextSetters,
         Frame = FRAME,
     >,
@@ -115,16 +416,29 @@ where
     type HaltReason = HaltReason;
 
     fn validate_env(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
-        self.inner.validate_env(evm)
+        self.inner.validate_env(evm)?;
+        // Skipped along with the block gas limit check, as it is for calls over RPC.
+        let ctx = evm.ctx_ref();
+        if let Some(settings) = self.settings.max_tx_gas {
+            if !ctx.cfg().is_block_gas_limit_disabled() {
+                let block_number = ctx.block().number().try_into().unwrap_or(u64::MAX);
+                settings
+                    .check(ctx.tx().gas_limit(), ctx.block().gas_limit(), block_number)
+                    .map_err(|err| Self::Error::from_string(err.to_string()))?;
+            }
+        }
+        Ok(())
     }
 
     fn validate_initial_tx_gas(
         &self,
         evm: &mut Self::Evm,
     ) -> Result<InitialAndFloorGas, Self::Error> {
-        {
+        let (access_list_gas, gas_limit) = {
             let ctx = evm.ctx_ref();
             let tx = ctx.tx();
+            let block_number = ctx.block().number().try_into().unwrap_or(u64::MAX);
+            let mut access_list_gas = 0;
             if let Some(calls) = tx.batch_calls() {
                 if calls.is_empty() {
                     return Err(Self::Error::from_string(
@@ -136,25 +450,143 @@ where
                         "only the first call may be CREATE".into(),
                     ));
                 }
+                if calls.iter().any(|call| call.gas_limit.is_some())
+                    && !active(
+                        self.settings
+                            .call_gas_limits
+                            .map(|settings| settings.activation_height()),
+                        block_number,
+                    )
+                {
+                    return Err(Self::Error::from_string(
+                        "per-call gas limits are not active".into(),
+                    ));
+                }
+                if !tx.batch_revert_mode().is_atomic() {
+                    if !active(
+                        self.settings
+                            .best_effort
+                            .map(|settings| settings.activation_height()),
+                        block_number,
+                    ) {
+                        return Err(Self::Error::from_string(
+                            "best-effort batches are not active".into(),
+                        ));
+                    }
+                    if calls[0].to.is_create() {
+                        return Err(Self::Error::from_string(
+                            "best-effort batches may not deploy contracts".into(),
+                        ));
+                    }
+                }
+                if let Some(session) = tx.batch_session() {
+                    if !active(
+                        self.settings
+                            .session_keys
+                            .map(|settings| settings.activation_height()),
+                        block_number,
+                    ) {
+                        return Err(Self::Error::from_string(
+                            "session keys are not active".into(),
+                        ));
+                    }
+                    let timestamp = ctx.block().timestamp().saturating_to();
+                    session
+                        .check_scope(ctx.cfg().chain_id(), calls, timestamp)
+                        .map_err(|err| Self::Error::from_string(err.to_string()))?;
+                }
+                if let Some(window) = tx.batch_validity_window() {
+                    if !active(
+                        self.settings
+                            .validity_windows
+                            .map(|settings| settings.activation_height()),
+                        block_number,
+                    ) {
+                        return Err(Self::Error::from_string(
+                            "validity windows are not active".into(),
+                        ));
+                    }
+                    window
+                        .check(ctx.block().timestamp().saturating_to())
+                        .map_err(|err| Self::Error::from_string(err.to_string()))?;
+                }
+                if tx.batch_nonce_lane().is_some() {
+                    if !active(
+                        self.settings
+                            .nonce_lanes
+                            .map(|settings| settings.activation_height()),
+                        block_number,
+                    ) {
+                        return Err(Self::Error::from_string(
+                            "nonce lanes are not active".into(),
+                        ));
+                    }
+                    // A deployment derives its address from, and bumps, the account nonce.
+                    if calls[0].to.is_create() {
+                        return Err(Self::Error::from_string(
+                            "nonce lane transactions may not deploy contracts".into(),
+                        ));
+                    }
+                }
+                if tx.batch_p256_signed()
+                    && !active(
+                        self.settings
+                            .p256_signatures
+                            .map(|settings| settings.activation_height()),
+                        block_number,
+                    )
+                {
+                    return Err(Self::Error::from_string(
+                        "P256 executor signatures are not active".into(),
+                    ));
+                }
+                if let Some(settings) = self
+                    .settings
+                    .access_list
+                    .filter(|settings| settings.is_active(block_number))
+                {
+                    let storage_keys: Vec<usize> = tx
+                        .access_list()
+                        .into_iter()
+                        .flatten()
+                        .map(|item| item.storage_slots().count())
+                        .collect();
+                    settings
+                        .check(storage_keys.iter().copied())
+                        .map_err(|err| Self::Error::from_string(err.to_string()))?;
+                    access_list_gas = settings.extra_gas(
+                        storage_keys.len() as u64,
+                        storage_keys.iter().sum::<usize>() as u64,
+                    );
+                }
                 if calls.len() > 1 {
-                    return validate_batch_initial_tx_gas(
+                    let gas = validate_batch_initial_tx_gas(
                         tx,
                         calls,
                         ctx.cfg().spec().into(),
                         ctx.cfg().is_eip7623_disabled(),
                         ctx.cfg().is_amsterdam_eip8037_enabled(),
                         ctx.cfg().tx_gas_limit_cap(),
-                    )
-                    .map_err(From::from);
+                    )?;
+                    return charge_extra_initial_gas(gas, access_list_gas, tx.gas_limit())
+                        .map_err(From::from);
                 }
             }
-        }
+            (access_list_gas, tx.gas_limit())
+        };
 
-        self.inner.validate_initial_tx_gas(evm)
+        let gas = self.inner.validate_initial_tx_gas(evm)?;
+        charge_extra_initial_gas(gas, access_list_gas, gas_limit).map_err(From::from)
     }
 
     fn load_accounts(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
-        self.inner.load_accounts(evm)
+        self.inner.load_accounts(evm)?;
+        // Loading an account into the journal marks it warm for the rest of the transaction.
+        let journal = evm.ctx_mut().journal_mut();
+        for address in &self.settings.warm_addresses {
+            journal.load_account(*address)?;
+        }
+        Ok(())
     }
 
     fn apply_eip7702_auth_list(
@@ -168,19 +600,55 @@ where
     fn validate_against_state_and_deduct_caller(
         &self,
         evm: &mut Self::Evm,
-        _init_and_floor_gas: &mut InitialAndFloorGas,
+        init_and_floor_gas: &mut InitialAndFloorGas,
     ) -> Result<(), Self::Error> {
         self.ensure_deploy_allowed(evm)?;
+        self.apply_sponsor_hash_scheme(evm)?;
+        self.apply_state_expiry(evm)?;
 
-        let ctx = evm.ctx_mut();
+        let ctx = evm.ctx_ref();
         let tx = ctx.tx();
+        let block_number = ctx.block().number().try_into().unwrap_or(u64::MAX);
         if tx.sponsor_signature_invalid() {
             return Err(Self::Error::from_string("invalid sponsor signature".into()));
         }
-        let sponsor = tx.sponsor();
+        if let Some(settings) = &self.settings.sponsor_allowlist {
+            settings
+                .check(tx.sponsor(), block_number)
+                .map_err(|err| Self::Error::from_string(err.to_string()))?;
+        }
+        let contract_fee_payer = match (tx.sponsor(), tx.sponsor_is_contract()) {
+            (Some(fee_payer), true) => {
+                // Before activation the marker is just an unrecoverable signature.
+                if !active(
+                    self.settings
+                        .gas_allowance
+                        .map(|settings| settings.activation_height()),
+                    block_number,
+                ) {
+                    return Err(Self::Error::from_string("invalid sponsor signature".into()));
+                }
+                let (max_gas_cost, _) = split_sponsored_fee(
+                    U256::from(tx.gas_limit()).saturating_mul(U256::from(tx.max_fee_per_gas())),
+                    tx.max_sponsor_fee().map(U256::from),
+                );
+                Some((fee_payer, tx.caller(), max_gas_cost))
+            }
+            _ => None,
+        };
+
+        let system_fee_mode = self.system_fee_mode(evm);
+        let ctx = evm.ctx_mut();
+        let tx = ctx.tx();
+        let (fee_payer, max_sponsor_fee) = match system_fee_mode {
+            Some(SystemFeeMode::Charged(account)) => (Some(account), None),
+            _ => (tx.sponsor(), tx.max_sponsor_fee().map(U256::from)),
+        };
         let caller_address = tx.caller();
         let total_value = tx.batch_total_value();
-        let is_call = tx.kind().is_call();
+        let nonce_lane = tx.batch_nonce_lane();
+        // Transactions in a nonce lane leave the caller's own nonce alone.
+        let is_call = tx.kind().is_call() && nonce_lane.is_none();
         let basefee = ctx.block().basefee() as u128;
         let blob_price = ctx.block().blob_gasprice().unwrap_or_default();
         let is_balance_check_disabled = ctx.cfg().is_balance_check_disabled();
@@ -188,12 +656,34 @@ where
         let is_nonce_check_disabled = ctx.cfg().is_nonce_check_disabled();
 
         let (tx, journal) = ctx.tx_journal_mut();
-        if let Some(sponsor) = sponsor {
+        if let Some(key) = nonce_lane {
+            validate_and_bump_nonce_lane::<_, _, Self::Error>(
+                journal,
+                tx,
+                caller_address,
+                key,
+                is_nonce_check_disabled,
+            )?;
+        }
+        let is_nonce_check_disabled = is_nonce_check_disabled || nonce_lane.is_some();
+        if system_fee_mode == Some(SystemFeeMode::Waived) {
+            validate_caller_and_bump_nonce::<_, _, Self::Error>(
+                journal,
+                tx,
+                caller_address,
+                total_value,
+                is_call,
+                is_balance_check_disabled,
+                is_eip3607_disabled,
+                is_nonce_check_disabled,
+            )?;
+        } else if let Some(fee_payer) = fee_payer {
             validate_and_deduct_sponsored_tx::<_, _, Self::Error>(
                 journal,
                 tx,
                 caller_address,
-                sponsor,
+                fee_payer,
+                max_sponsor_fee,
                 total_value,
                 is_call,
                 basefee,
@@ -215,6 +705,22 @@ where
             )?;
         }
 
+        // The contract fee payer's code only runs for a transaction that passed the nonce and
+        // balance checks.
+        if let Some((fee_payer, executor, max_gas_cost)) = contract_fee_payer {
+            self.approve_contract_gas(evm, init_and_floor_gas, fee_payer, executor, max_gas_cost)?;
+            let gas_limit = evm.ctx_ref().tx().gas_limit();
+            if init_and_floor_gas.initial_total_gas > gas_limit {
+                return Err(
+                    reth_revm::revm::context_interface::result::InvalidTransaction::CallGasCostMoreThanGasLimit {
+                        gas_limit,
+                        initial_gas: init_and_floor_gas.initial_total_gas,
+                    }
+                    .into(),
+                );
+            }
+        }
+
         Ok(())
     }
 
@@ -232,13 +738,18 @@ where
         evm: &mut Self::Evm,
         init_and_floor_gas: &InitialAndFloorGas,
     ) -> Result<FrameResult, Self::Error> {
+        let best_effort = !evm.ctx().tx().batch_revert_mode().is_atomic();
         let calls = match evm.ctx().tx().batch_calls() {
             Some([]) => {
                 return Err(Self::Error::from_string(
                     "evnode transaction must include at least one call".into(),
                 ));
             }
-            Some(calls) if calls.len() > 1 => calls.to_vec(),
+            // A capped or best-effort single call still needs the batch loop to bound its
+            // frame's gas or to record its failure.
+            Some(calls) if calls.len() > 1 || calls[0].gas_limit.is_some() || best_effort => {
+                calls.to_vec()
+            }
             _ => return self.inner.execution(evm, init_and_floor_gas),
         };
 
@@ -258,18 +769,41 @@ where
         // set_batch_call only modifies (kind, value, data) - the nonce is intentionally
         // shared since a batch is a single atomic transaction with one nonce.
         // Note: only the first call may be CREATE (enforced in validate_initial_tx_gas).
-        for call in &calls {
+        for (index, call) in calls.iter().enumerate() {
             let mut call_tx = base_tx.clone();
             call_tx.set_batch_call(call);
             evm.ctx_mut().set_tx(call_tx);
-            let first_frame_input = self
-                .inner
-                .first_frame_input(evm, remaining_gas, reservoir)?;
+            // A capped call only gets its own gas; the rest is withheld for the calls after it.
+            let call_gas = call
+                .gas_limit
+                .map_or(remaining_gas, |limit| limit.min(remaining_gas));
+            let withheld_gas = remaining_gas - call_gas;
+            let first_frame_input = self.inner.first_frame_input(evm, call_gas, reservoir)?;
             let mut frame_result = self.inner.run_exec_loop(evm, first_frame_input)?;
             let instruction_result = frame_result.interpreter_result().result;
-            total_refunded = total_refunded.saturating_add(frame_result.gas().refunded());
-            remaining_gas = frame_result.gas().remaining();
+            // A halted call consumes all the gas it was given.
+            remaining_gas = withheld_gas
+                + if instruction_result.is_ok_or_revert() {
+                    frame_result.gas().remaining()
+                } else {
+                    0
+                };
             reservoir = frame_result.gas().reservoir();
+
+            if best_effort && !instruction_result.is_ok() {
+                // The call's frame already reverted its own changes, including its value
+                // transfer; record the failure and go on with the next call.
+                reservoir = reservoir.saturating_add(frame_result.gas().state_gas_spent());
+                let output = frame_result.interpreter_result().output.clone();
+                evm.ctx_mut()
+                    .journal_mut()
+                    .log(best_effort::call_failed_log(index, &output));
+                mark_call_succeeded(&mut frame_result);
+                last_result = Some(frame_result);
+                continue;
+            }
+
+            total_refunded = total_refunded.saturating_add(frame_result.gas().refunded());
             total_state_gas_spent =
                 total_state_gas_spent.saturating_add(frame_result.gas().state_gas_spent());
 
@@ -361,19 +895,61 @@ where
         exec_result: &mut <FRAME as FrameTr>::FrameResult,
     ) -> Result<(), Self::Error> {
         // For sponsored transactions, reimburse the sponsor instead of the caller
-        let sponsor = evm.ctx().tx().sponsor();
-        if let Some(sponsor) = sponsor {
-            let gas = exec_result.gas();
+        let system_fee_mode = self.system_fee_mode(evm);
+        let (fee_payer, max_sponsor_fee) = match system_fee_mode {
+            Some(SystemFeeMode::Waived) => return Ok(()),
+            Some(SystemFeeMode::Charged(account)) => (Some(account), None),
+            _ => (
+                evm.ctx().tx().sponsor(),
+                evm.ctx().tx().max_sponsor_fee().map(U256::from),
+            ),
+        };
+        if let Some(fee_payer) = fee_payer {
             let basefee = evm.ctx().block().basefee() as u128;
+            let block_number = evm.ctx().block().number().try_into().unwrap_or(u64::MAX);
             let effective_gas_price = evm.ctx().tx().effective_gas_price(basefee);
-            let reimbursement = U256::from(
-                effective_gas_price
-                    .saturating_mul((gas.remaining() + gas.refunded() as u64) as u128),
-            );
+            let upfront = U256::from(evm.ctx().tx().gas_limit())
+                .saturating_mul(U256::from(effective_gas_price));
+            let reimbursement_for = |gas: &Gas| {
+                U256::from(
+                    effective_gas_price
+                        .saturating_mul((gas.remaining() + gas.refunded() as u64) as u128),
+                )
+            };
+
+            if system_fee_mode.is_none()
+                && evm.ctx().tx().sponsor_is_contract()
+                && self
+                    .settings
+                    .gas_allowance
+                    .is_some_and(|settings| settings.post_op_active(block_number))
+            {
+                let actual_gas_cost = sponsor_gas_cost(
+                    upfront,
+                    max_sponsor_fee,
+                    reimbursement_for(exec_result.gas()),
+                );
+                let success = exec_result.interpreter_result().result.is_ok();
+                self.call_post_op(evm, exec_result, fee_payer, actual_gas_cost, success)?;
+            }
+
+            let caller = evm.ctx().tx().caller();
+            let reimbursement = reimbursement_for(exec_result.gas());
+            // The caller covered the part of the upfront fee above the sponsor's cap, so it is
+            // refunded first; the sponsor only gets back what it actually overpaid.
+            let (_, caller_paid) = split_sponsored_fee(upfront, max_sponsor_fee);
+            let caller_refund = reimbursement.min(caller_paid);
             let journal = evm.ctx_mut().journal_mut();
-            let mut sponsor_account = journal.load_account_mut(sponsor)?.data;
-            let new_balance = sponsor_account.balance().saturating_add(reimbursement);
-            sponsor_account.set_balance(new_balance);
+            if !caller_refund.is_zero() {
+                let mut caller_account = journal.load_account_mut(caller)?.data;
+                let new_balance = caller_account.balance().saturating_add(caller_refund);
+                caller_account.set_balance(new_balance);
+            }
+            let mut fee_payer_account = journal.load_account_mut(fee_payer)?.data;
+            let new_balance = fee_payer_account
+                .balance()
+                .saturating_add(reimbursement - caller_refund);
+            fee_payer_account.set_balance(new_balance);
             Ok(())
         } else {
             self.inner.reimburse_caller(evm, exec_result)
@@ -385,10 +961,18 @@ where
         evm: &mut Self::Evm,
         exec_result: &mut <FRAME as FrameTr>::FrameResult,
     ) -> Result<(), Self::Error> {
+        // Waived fees were never collected and burned fees are not paid out.
+        if matches!(
+            self.system_fee_mode(evm),
+            Some(SystemFeeMode::Waived | SystemFeeMode::Burned)
+        ) {
+            return Ok(());
+        }
+
         let gas = exec_result.gas();
         let spent = gas.spent_sub_refunded();
 
-        if let (Some(redirect), true) = (self.redirect, spent != 0) {
+        if let (Some(redirect), true) = (self.settings.redirect, spent != 0) {
             redirect
                 .apply(evm.ctx(), spent)
                 .map_err(|BaseFeeRedirectError::Database(err)| Self::Error::from(err))?;
@@ -410,7 +994,10 @@ where
 impl<EVM, ERROR> InspectorHandler for EvHandler<EVM, ERROR, EthFrame<EthInterpreter>>
 where
     EVM: InspectorEvmTr<
-        Context: ContextTr<Journal: JournalTr<State = EvmState>, Tx: SponsorPayerTx + BatchCallsTx>,
+        Context: ContextTr<
+            Journal: JournalTr<State = EvmState>,
+            Tx: SystemCallTx + SponsorPayerTx + BatchCallsTx,
+        >,
         Frame = EthFrame<EthInterpreter>,
         Inspector: Inspector<<EVM as EvmTr>::Context, EthInterpreter>,
     >,
@@ -592,6 +1179,40 @@ fn validate_batch_initial_tx_gas<Tx: Transaction>(
     Ok(gas)
 }
 
+/// Adds `extra_gas` to the intrinsic gas of a transaction, which must still fit its gas limit.
+fn charge_extra_initial_gas(
+    mut gas: InitialAndFloorGas,
+    extra_gas: u64,
+    gas_limit: u64,
+) -> Result<InitialAndFloorGas, reth_revm::revm::context_interface::result::InvalidTransaction> {
+    if extra_gas == 0 {
+        return Ok(gas);
+    }
+    gas.initial_total_gas = gas.initial_total_gas.saturating_add(extra_gas);
+    if gas.initial_total_gas > gas_limit {
+        return Err(
+            reth_revm::revm::context_interface::result::InvalidTransaction::CallGasCostMoreThanGasLimit {
+                gas_limit,
+                initial_gas: gas.initial_total_gas,
+            },
+        );
+    }
+    Ok(gas)
+}
+
+/// Turns the result of a failed call of a best-effort batch into an empty successful return,
+/// so the batch succeeds when its last call fails.
+fn mark_call_succeeded(frame_result: &mut FrameResult) {
+    let result = match frame_result {
+        FrameResult::Call(outcome) => &mut outcome.result,
+        FrameResult::Create(outcome) => &mut outcome.result,
+    };
+    result.result = InstructionResult::Stop;
+    result.output = Bytes::new();
+}
+
+/// Sets the gas of a finished batch, refunding `remaining_gas`: the gas left by the last
+/// call executed plus any gas withheld from it.
 fn finalize_batch_gas(
     frame_result: &mut FrameResult,
     tx_gas_limit: u64,
@@ -602,9 +1223,7 @@ fn finalize_batch_gas(
 ) {
     let instruction_result = frame_result.interpreter_result().result;
     let mut gas = Gas::new_spent(tx_gas_limit);
-    if instruction_result.is_ok_or_revert() {
-        gas.erase_cost(remaining_gas);
-    }
+    gas.erase_cost(remaining_gas);
     if instruction_result.is_ok() {
         gas.record_refund(refund);
         gas.set_state_gas_spent(state_gas_spent);
@@ -616,17 +1235,15 @@ fn finalize_batch_gas(
     *frame_result.gas_mut() = gas;
 }
 
-/// Validates and deducts fees for a sponsored transaction.
-/// The sponsor pays the gas fees while the caller pays the value transfer.
+/// Validates the caller of a transaction whose gas is not paid by the caller and bumps its
+/// nonce. Only the value transfer is checked against the caller's balance.
 #[allow(clippy::too_many_arguments)]
-fn validate_and_deduct_sponsored_tx<Tx, J, E>(
+fn validate_caller_and_bump_nonce<Tx, J, E>(
     journal: &mut J,
     tx: &Tx,
     caller_address: alloy_primitives::Address,
-    sponsor: alloy_primitives::Address,
     total_value: U256,
     is_call: bool,
-    basefee: u128,
     is_balance_check_disabled: bool,
     is_eip3607_disabled: bool,
     is_nonce_check_disabled: bool,
@@ -637,81 +1254,47 @@ where
     E: From<reth_revm::revm::context_interface::result::InvalidTransaction>
         + From<<J::Database as reth_revm::Database>::Error>,
 {
-    // Validate caller's nonce/code and balance for value transfer
-    {
-        let mut caller = journal.load_account_with_code_mut(caller_address)?.data;
-        validate_account_nonce_and_code(
-            &caller.account().info,
-            tx,
-            is_eip3607_disabled,
-            is_nonce_check_disabled,
-        )?;
-
-        // Only validate that caller has enough balance for the value transfer.
-        // Do NOT pre-deduct the value - it will be transferred during execution.
-        // This matches the mainnet behavior where only gas is pre-deducted.
-        let balance = *caller.balance();
-        if !is_balance_check_disabled && balance < total_value {
-            return Err(
-                reth_revm::revm::context_interface::result::InvalidTransaction::LackOfFundForMaxFee {
-                    fee: Box::new(total_value),
-                    balance: Box::new(balance),
-                }
-                .into(),
-            );
-        }
-
-        // Nonce handling for batches:
-        // - CALL batches: increment nonce here (standard pre-execution behavior)
-        // - CREATE batches: nonce is incremented during CREATE frame execution,
-        //   which also uses it for contract address derivation
-        if is_call {
-            let nonce = caller.nonce();
-            caller.set_nonce(nonce.saturating_add(1));
-        }
-    }
+    let mut caller = journal.load_account_with_code_mut(caller_address)?.data;
+    validate_account_nonce_and_code(
+        &caller.account().info,
+        tx,
+        is_eip3607_disabled,
+        is_nonce_check_disabled,
+    )?;
 
-    // Validate and deduct gas from sponsor
-    let mut sponsor_account = journal.load_account_with_code_mut(sponsor)?.data;
-    let sponsor_balance = *sponsor_account.balance();
-    let max_gas_cost = U256::from(tx.gas_limit()).saturating_mul(U256::from(tx.max_fee_per_gas()));
-    if !is_balance_check_disabled && sponsor_balance < max_gas_cost {
+    // Only validate that caller has enough balance for the value transfer.
+    // Do NOT pre-deduct the value - it will be transferred during execution.
+    // This matches the mainnet behavior where only gas is pre-deducted.
+    let balance = *caller.balance();
+    if !is_balance_check_disabled && balance < total_value {
         return Err(
             reth_revm::revm::context_interface::result::InvalidTransaction::LackOfFundForMaxFee {
-                fee: Box::new(max_gas_cost),
-                balance: Box::new(sponsor_balance),
+                fee: Box::new(total_value),
+                balance: Box::new(balance),
             }
             .into(),
         );
     }
 
-    // Note: We deduct effective_gas_price (not max_fee_per_gas) upfront.
-    // This is safe because effective_gas_price <= max_fee_per_gas by construction,
-    // and the check above ensures sponsor can cover the worst case (max_gas_cost).
-    // This approach is more gas-efficient than deducting max upfront and reimbursing.
-    let effective_gas_price = tx.effective_gas_price(basefee);
-    let gas_cost = U256::from(tx.gas_limit()).saturating_mul(U256::from(effective_gas_price));
-    let mut new_sponsor_balance = sponsor_balance.saturating_sub(gas_cost);
-    if is_balance_check_disabled {
-        new_sponsor_balance = new_sponsor_balance.max(gas_cost);
+    // Nonce handling for batches:
+    // - CALL batches: increment nonce here (standard pre-execution behavior)
+    // - CREATE batches: nonce is incremented during CREATE frame execution,
+    //   which also uses it for contract address derivation
+    if is_call {
+        let nonce = caller.nonce();
+        caller.set_nonce(nonce.saturating_add(1));
     }
-    sponsor_account.set_balance(new_sponsor_balance);
 
     Ok(())
 }
 
-/// Validates and deducts fees for a normal (non-sponsored) transaction.
-/// The caller pays both gas fees and value transfer.
-#[allow(clippy::too_many_arguments)]
-fn validate_and_deduct_normal_tx<Tx, J, E>(
+/// Validates the nonce of a transaction in a nonce lane against the lane's sequence, kept as
+/// the nonce of the lane's account, and bumps it.
+fn validate_and_bump_nonce_lane<Tx, J, E>(
     journal: &mut J,
     tx: &Tx,
-    caller_address: alloy_primitives::Address,
-    is_call: bool,
-    basefee: u128,
-    blob_price: u128,
-    is_balance_check_disabled: bool,
-    is_eip3607_disabled: bool,
+    caller_address: Address,
+    key: U192,
     is_nonce_check_disabled: bool,
 ) -> Result<(), E>
 where
@@ -720,22 +1303,151 @@ where
     E: From<reth_revm::revm::context_interface::result::InvalidTransaction>
         + From<<J::Database as reth_revm::Database>::Error>,
 {
-    let mut caller = journal.load_account_with_code_mut(caller_address)?.data;
-    validate_account_nonce_and_code(
-        &caller.account().info,
-        tx,
-        is_eip3607_disabled,
-        is_nonce_check_disabled,
-    )?;
-    let new_caller_balance = calculate_caller_fee(
-        *caller.balance(),
-        tx,
-        basefee,
-        blob_price,
-        is_balance_check_disabled,
-    )?;
-    caller.set_balance(new_caller_balance);
-    if is_call {
+    let mut lane = journal
+        .load_account_with_code_mut(nonce_lane_address(caller_address, key))?
+        .data;
+    validate_account_nonce_and_code(&lane.account().info, tx, true, is_nonce_check_disabled)?;
+    let nonce = lane.nonce();
+    lane.set_nonce(nonce.saturating_add(1));
+    Ok(())
+}
+
+/// Splits a gas fee into the sponsor's share, at most `max_sponsor_fee`, and the caller's
+/// share.
+/// Returns true if a feature activated at `height` is active at `block`; `None` means the
+/// feature is not configured.
+const fn active(height: Option<u64>, block: u64) -> bool {
+    match height {
+        Some(height) => block >= height,
+        None => false,
+    }
+}
+
+fn split_sponsored_fee(fee: U256, max_sponsor_fee: Option<U256>) -> (U256, U256) {
+    let sponsor_share = max_sponsor_fee.map_or(fee, |cap| fee.min(cap));
+    (sponsor_share, fee - sponsor_share)
+}
+
+/// Returns the sponsor's share of a sponsored transaction's gas cost once `reimbursement` of
+/// the `upfront` fee has been returned, caller first.
+fn sponsor_gas_cost(upfront: U256, max_sponsor_fee: Option<U256>, reimbursement: U256) -> U256 {
+    let (sponsor_paid, caller_paid) = split_sponsored_fee(upfront, max_sponsor_fee);
+    let sponsor_refund = reimbursement - reimbursement.min(caller_paid);
+    sponsor_paid.saturating_sub(sponsor_refund)
+}
+
+/// Validates and deducts fees for a sponsored transaction.
+/// The sponsor pays the gas fees, up to `max_sponsor_fee` if set, while the caller pays the
+/// value transfer and any gas fees above the cap.
+#[allow(clippy::too_many_arguments)]
+fn validate_and_deduct_sponsored_tx<Tx, J, E>(
+    journal: &mut J,
+    tx: &Tx,
+    caller_address: alloy_primitives::Address,
+    sponsor: alloy_primitives::Address,
+    max_sponsor_fee: Option<U256>,
+    total_value: U256,
+    is_call: bool,
+    basefee: u128,
+    is_balance_check_disabled: bool,
+    is_eip3607_disabled: bool,
+    is_nonce_check_disabled: bool,
+) -> Result<(), E>
+where
+    Tx: Transaction,
+    J: JournalTr<State = EvmState>,
+    E: From<reth_revm::revm::context_interface::result::InvalidTransaction>
+        + From<<J::Database as reth_revm::Database>::Error>,
+{
+    let (max_gas_cost, max_caller_gas_cost) = split_sponsored_fee(
+        U256::from(tx.gas_limit()).saturating_mul(U256::from(tx.max_fee_per_gas())),
+        max_sponsor_fee,
+    );
+    validate_caller_and_bump_nonce::<_, _, E>(
+        journal,
+        tx,
+        caller_address,
+        total_value.saturating_add(max_caller_gas_cost),
+        is_call,
+        is_balance_check_disabled,
+        is_eip3607_disabled,
+        is_nonce_check_disabled,
+    )?;
+
+    // Validate and deduct gas from sponsor
+    let mut sponsor_account = journal.load_account_with_code_mut(sponsor)?.data;
+    let sponsor_balance = *sponsor_account.balance();
+    if !is_balance_check_disabled && sponsor_balance < max_gas_cost {
+        return Err(
+            reth_revm::revm::context_interface::result::InvalidTransaction::LackOfFundForMaxFee {
+                fee: Box::new(max_gas_cost),
+                balance: Box::new(sponsor_balance),
+            }
+            .into(),
+        );
+    }
+
+    // Note: We deduct effective_gas_price (not max_fee_per_gas) upfront.
+    // This is safe because effective_gas_price <= max_fee_per_gas by construction,
+    // and the check above ensures sponsor can cover the worst case (max_gas_cost).
+    // This approach is more gas-efficient than deducting max upfront and reimbursing.
+    let effective_gas_price = tx.effective_gas_price(basefee);
+    let (gas_cost, caller_gas_cost) = split_sponsored_fee(
+        U256::from(tx.gas_limit()).saturating_mul(U256::from(effective_gas_price)),
+        max_sponsor_fee,
+    );
+    let mut new_sponsor_balance = sponsor_balance.saturating_sub(gas_cost);
+    if is_balance_check_disabled {
+        new_sponsor_balance = new_sponsor_balance.max(gas_cost);
+    }
+    sponsor_account.set_balance(new_sponsor_balance);
+
+    // Whatever exceeds the sponsor's cap falls back to the caller.
+    if !caller_gas_cost.is_zero() {
+        let mut caller = journal.load_account_mut(caller_address)?.data;
+        let new_caller_balance = caller.balance().saturating_sub(caller_gas_cost);
+        caller.set_balance(new_caller_balance);
+    }
+
+    Ok(())
+}
+
+/// Validates and deducts fees for a normal (non-sponsored) transaction.
+/// The caller pays both gas fees and value transfer.
+#[allow(clippy::too_many_arguments)]
+fn validate_and_deduct_normal_tx<Tx, J, E>(
+    journal: &mut J,
+    tx: &Tx,
+    caller_address: alloy_primitives::Address,
+    is_call: bool,
+    basefee: u128,
+    blob_price: u128,
+    is_balance_check_disabled: bool,
+    is_eip3607_disabled: bool,
+    is_nonce_check_disabled: bool,
+) -> Result<(), E>
+where
+    Tx: Transaction,
+    J: JournalTr<State = EvmState>,
+    E: From<reth_revm::revm::context_interface::result::InvalidTransaction>
+        + From<<J::Database as reth_revm::Database>::Error>,
+{
+    let mut caller = journal.load_account_with_code_mut(caller_address)?.data;
+    validate_account_nonce_and_code(
+        &caller.account().info,
+        tx,
+        is_eip3607_disabled,
+        is_nonce_check_disabled,
+    )?;
+    let new_caller_balance = calculate_caller_fee(
+        *caller.balance(),
+        tx,
+        basefee,
+        blob_price,
+        is_balance_check_disabled,
+    )?;
+    caller.set_balance(new_caller_balance);
+    if is_call {
         let nonce = caller.nonce();
         caller.set_nonce(nonce.saturating_add(1));
     }
@@ -746,9 +1458,16 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{DeployAllowlistSettings, EvEvm, EvTxEnv, EvTxEvmFactory};
-    use alloy_primitives::{address, Address, Bytes, TxKind, B256, U256};
-    use ev_primitives::Call;
+    use crate::{
+        best_effort::BATCH_STATUS_ADDRESS, BestEffortSettings, CallGasLimitSettings,
+        DeployAllowlistSettings, EvEvm, EvTxEnv, EvTxEvmFactory, GasAllowanceSettings,
+        NonceLaneSettings, P256SignatureSettings, PrecompileWarmupSettings, SessionKeySettings,
+        SponsorHashMigration, ValidityWindowSettings,
+    };
+    use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+    use ev_primitives::{
+        Call, RevertMode, SessionAuthorization, SponsorHashScheme, ValidityWindow,
+    };
     use reth_revm::{
         inspector::NoOpInspector,
         revm::{
@@ -787,6 +1506,17 @@ mod tests {
     const GAS_PRICE: u128 = 200;
     const STORAGE_RUNTIME: [u8; 6] = [0x60, 0x01, 0x60, 0x00, 0x55, 0x00];
     const REVERT_RUNTIME: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+    /// Jumps back to its first instruction until it runs out of gas.
+    const LOOP_RUNTIME: [u8; 4] = [0x5b, 0x60, 0x00, 0x56];
+    /// Returns the ABI word `true` regardless of input.
+    const APPROVE_RUNTIME: [u8; 10] = [0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3];
+    /// Returns the ABI word `false` regardless of input.
+    const DENY_RUNTIME: [u8; 10] = [0x60, 0x00, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3];
+    /// Stores the first two argument words in slots 0 and 1, then returns the ABI word `true`.
+    const RECORDING_RUNTIME: [u8; 22] = [
+        0x60, 0x04, 0x35, 0x60, 0x00, 0x55, 0x60, 0x24, 0x35, 0x60, 0x01, 0x55, 0x60, 0x01, 0x60,
+        0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+    ];
 
     #[test]
     fn reward_beneficiary_redirects_base_fee_sink() {
@@ -853,11 +1583,13 @@ mod tests {
                 to: TxKind::Call(address!("0x00000000000000000000000000000000000000bb")),
                 value: U256::ZERO,
                 input: Bytes::new(),
+                gas_limit: None,
             },
             Call {
                 to: TxKind::Call(address!("0x00000000000000000000000000000000000000cc")),
                 value: U256::ZERO,
                 input: Bytes::from(vec![0x01, 0x00, 0x02]),
+                gas_limit: None,
             },
         ];
 
@@ -892,6 +1624,7 @@ mod tests {
             to: TxKind::Call(address!("0x00000000000000000000000000000000000000dd")),
             value: U256::ZERO,
             input: Bytes::from(vec![0x11; 64]),
+            gas_limit: None,
         }];
 
         let err =
@@ -966,11 +1699,13 @@ mod tests {
                 to: TxKind::Call(storage_contract),
                 value: U256::ZERO,
                 input: Bytes::new(),
+                gas_limit: None,
             },
             Call {
                 to: TxKind::Call(revert_contract),
                 value: U256::ZERO,
                 input: Bytes::new(),
+                gas_limit: None,
             },
         ];
 
@@ -1054,11 +1789,13 @@ mod tests {
                 to: TxKind::Create,
                 value: U256::ZERO,
                 input: Bytes::new(),
+                gas_limit: None,
             },
             Call {
                 to: TxKind::Call(revert_contract),
                 value: U256::ZERO,
                 input: Bytes::new(),
+                gas_limit: None,
             },
         ];
 
@@ -1136,11 +1873,13 @@ mod tests {
                 to: TxKind::Call(storage_contract),
                 value: U256::ZERO,
                 input: Bytes::new(),
+                gas_limit: None,
             },
             Call {
                 to: TxKind::Call(storage_contract),
                 value: U256::ZERO,
                 input: Bytes::new(),
+                gas_limit: None,
             },
         ];
 
@@ -1177,9 +1916,11 @@ mod tests {
         assert!(slot.is_changed());
     }
 
-    #[test]
-    fn batch_execution_rejects_empty_calls() {
+    fn transact_capped_batch(
+        call_gas_limits: Option<CallGasLimitSettings>,
+    ) -> Result<ExecutionResult, String> {
         let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let looping_contract = address!("0x0000000000000000000000000000000000000bbb");
 
         let mut state = State::builder()
             .with_database(CacheDB::<EmptyDB>::default())
@@ -1197,6 +1938,19 @@ mod tests {
             },
         );
 
+        state.insert_account(
+            looping_contract,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 1,
+                code_hash: alloy_primitives::keccak256(LOOP_RUNTIME.as_slice()),
+                code: Some(RevmBytecode::new_raw(Bytes::copy_from_slice(
+                    LOOP_RUNTIME.as_slice(),
+                ))),
+                account_id: None,
+            },
+        );
+
         let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
         evm_env.cfg_env.chain_id = 1;
         evm_env.cfg_env.spec = SpecId::CANCUN;
@@ -1204,7 +1958,17 @@ mod tests {
         evm_env.block_env.gas_limit = 30_000_000;
         evm_env.block_env.number = U256::from(1);
 
-        let mut evm = EvTxEvmFactory::default().create_evm(state, evm_env);
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                call_gas_limits,
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
+
+        let calls = vec![
+            Call::transfer(looping_contract, U256::ZERO).with_gas_limit(10_000),
+            Call::transfer(caller, U256::ZERO),
+        ];
 
         let tx_env = TxEnv {
             caller,
@@ -1216,22 +1980,40 @@ mod tests {
             ..Default::default()
         };
 
-        let tx = EvTxEnv::with_calls(tx_env, Vec::new());
+        evm.transact_raw(EvTxEnv::with_calls(tx_env, calls))
+            .map(|result_and_state| result_and_state.result)
+            .map_err(|err| err.to_string())
+    }
 
-        let err = evm
-            .transact_raw(tx)
-            .expect_err("empty call batch should reject");
-        assert!(
-            err.to_string()
-                .contains("evnode transaction must include at least one call"),
-            "unexpected error: {err:?}"
-        );
+    #[test]
+    fn capped_call_only_consumes_its_own_gas() {
+        let result = transact_capped_batch(Some(CallGasLimitSettings::new(0)))
+            .expect("capped batch should execute");
+
+        assert!(matches!(result, ExecutionResult::Halt { .. }));
+        // Intrinsic gas of both calls plus the gas given to the halted call; the rest of the
+        // batch's gas is refunded.
+        assert_eq!(result.gas().tx_gas_used(), 2 * 21_000 + 10_000);
     }
 
     #[test]
-    fn sponsored_tx_rejects_when_balance_below_max_fee() {
+    fn capped_call_rejected_before_activation() {
+        for settings in [None, Some(CallGasLimitSettings::new(2))] {
+            let err = transact_capped_batch(settings).expect_err("capped batch should reject");
+            assert!(
+                err.contains("per-call gas limits are not active"),
+                "unexpected error: {err}"
+            );
+        }
+    }
+
+    fn transact_best_effort_batch(
+        best_effort: Option<BestEffortSettings>,
+    ) -> Result<(ExecutionResult, EvmState), String> {
         let caller = address!("0x0000000000000000000000000000000000000aaa");
-        let sponsor = address!("0x0000000000000000000000000000000000000bbb");
+        let storage_contract = address!("0x0000000000000000000000000000000000000bbb");
+        let reverting_contract = address!("0x0000000000000000000000000000000000000ccc");
+        let recipient = address!("0x0000000000000000000000000000000000000ddd");
 
         let mut state = State::builder()
             .with_database(CacheDB::<EmptyDB>::default())
@@ -1249,16 +2031,21 @@ mod tests {
             },
         );
 
-        state.insert_account(
-            sponsor,
-            AccountInfo {
-                balance: U256::from(500_000u64),
-                nonce: 0,
-                code_hash: KECCAK_EMPTY,
-                code: None,
-                account_id: None,
-            },
-        );
+        for (contract, runtime) in [
+            (storage_contract, STORAGE_RUNTIME.as_slice()),
+            (reverting_contract, REVERT_RUNTIME.as_slice()),
+        ] {
+            state.insert_account(
+                contract,
+                AccountInfo {
+                    balance: U256::ZERO,
+                    nonce: 1,
+                    code_hash: alloy_primitives::keccak256(runtime),
+                    code: Some(RevmBytecode::new_raw(Bytes::copy_from_slice(runtime))),
+                    account_id: None,
+                },
+            );
+        }
 
         let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
         evm_env.cfg_env.chain_id = 1;
@@ -1267,53 +2054,84 @@ mod tests {
         evm_env.block_env.gas_limit = 30_000_000;
         evm_env.block_env.number = U256::from(1);
 
-        let mut evm = EvTxEvmFactory::default().create_evm(state, evm_env);
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                best_effort,
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
 
-        let calls = vec![Call {
-            to: TxKind::Call(address!("0x0000000000000000000000000000000000000ccc")),
-            value: U256::ZERO,
-            input: Bytes::new(),
-        }];
+        let calls = vec![
+            Call::transfer(storage_contract, U256::ZERO),
+            Call::transfer(reverting_contract, U256::from(5)),
+            Call::transfer(recipient, U256::from(7)),
+        ];
 
         let tx_env = TxEnv {
             caller,
-            gas_limit: 100_000,
-            gas_price: 100,
+            gas_limit: 200_000,
+            gas_price: 1,
             gas_priority_fee: Some(1),
             chain_id: Some(1),
             tx_type: TransactionType::Eip1559.into(),
             ..Default::default()
         };
 
-        let tx = EvTxEnv::with_calls_and_sponsor(tx_env, calls, sponsor);
+        let tx = EvTxEnv::with_calls(tx_env, calls).with_revert_mode(RevertMode::BestEffort);
+        evm.transact_raw(tx)
+            .map(|result_and_state| (result_and_state.result, result_and_state.state))
+            .map_err(|err| err.to_string())
+    }
 
-        let err = evm
-            .transact_raw(tx)
-            .expect_err("sponsor should need max fee coverage");
-        assert!(
-            matches!(
-                err,
-                EVMError::Transaction(InvalidTransaction::LackOfFundForMaxFee { .. })
-            ),
-            "unexpected error: {err:?}"
-        );
+    #[test]
+    fn best_effort_batch_records_failed_call() {
+        let (result, state) = transact_best_effort_batch(Some(BestEffortSettings::new(0)))
+            .expect("best-effort batch should execute");
+
+        assert!(matches!(result, ExecutionResult::Success { .. }));
+        // Only the reverting call is recorded, by its position in the batch.
+        let logs = result.logs();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address, BATCH_STATUS_ADDRESS);
+        assert_eq!(logs[0].topics()[1], B256::with_last_byte(1));
+
+        // The calls around the failed one keep their effects; its value transfer is undone.
+        let slot = state
+            .get(&address!("0x0000000000000000000000000000000000000bbb"))
+            .and_then(|account| account.storage.get(&U256::ZERO))
+            .expect("slot 0 should be written");
+        assert_eq!(slot.present_value, U256::from(1));
+        let reverting = state.get(&address!("0x0000000000000000000000000000000000000ccc"));
+        assert!(reverting.is_none_or(|account| account.info.balance.is_zero()));
+        let recipient = state
+            .get(&address!("0x0000000000000000000000000000000000000ddd"))
+            .expect("recipient should be loaded");
+        assert_eq!(recipient.info.balance, U256::from(7));
     }
 
-    /// Tests that sponsored transactions with `max_fee_per_gas` < `base_fee` are rejected.
-    ///
-    /// This validation happens in revm's `validate_env` (delegated via inner handler)
-    /// BEFORE our custom `validate_and_deduct_sponsored_tx` runs. This test serves
-    /// as a regression test to ensure EIP-1559 fee validation is not bypassed.
     #[test]
-    fn sponsored_tx_rejects_when_max_fee_below_basefee() {
+    fn best_effort_batch_rejected_before_activation() {
+        for settings in [None, Some(BestEffortSettings::new(2))] {
+            let err =
+                transact_best_effort_batch(settings).expect_err("best-effort batch should reject");
+            assert!(
+                err.contains("best-effort batches are not active"),
+                "unexpected error: {err}"
+            );
+        }
+    }
+
+    fn transact_session_batch(
+        session_keys: Option<SessionKeySettings>,
+        timestamp: u64,
+    ) -> Result<ExecutionResult, String> {
         let caller = address!("0x0000000000000000000000000000000000000aaa");
-        let sponsor = address!("0x0000000000000000000000000000000000000bbb");
+        let recipient = address!("0x0000000000000000000000000000000000000ddd");
 
         let mut state = State::builder()
             .with_database(CacheDB::<EmptyDB>::default())
             .with_bundle_update()
             .build();
-
         state.insert_account(
             caller,
             AccountInfo {
@@ -1325,11 +2143,86 @@ mod tests {
             },
         );
 
-        // Sponsor has plenty of balance - the rejection should be due to fee, not balance
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(1);
+        evm_env.block_env.timestamp = U256::from(timestamp);
+
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                session_keys,
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
+
+        let session = SessionAuthorization {
+            chain_id: 1,
+            session_key: address!("0x0000000000000000000000000000000000000eee"),
+            targets: vec![recipient],
+            max_value: U256::from(10),
+            expiry: 100,
+        };
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 100_000,
+            gas_price: 1,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+        let calls = vec![
+            Call::transfer(recipient, U256::from(3)),
+            Call::transfer(recipient, U256::from(7)),
+        ];
+
+        let tx = EvTxEnv::with_calls(tx_env, calls).with_session(Some(session));
+        evm.transact_raw(tx)
+            .map(|result_and_state| result_and_state.result)
+            .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn session_batch_checked_against_scope() {
+        let settings = Some(SessionKeySettings::new(0));
+        let result = transact_session_batch(settings, 100).expect("session batch should execute");
+        assert!(matches!(result, ExecutionResult::Success { .. }));
+
+        let err = transact_session_batch(settings, 101).expect_err("expired session");
+        assert!(
+            err.contains("session authorization expired at 100"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn session_batch_rejected_before_activation() {
+        for settings in [None, Some(SessionKeySettings::new(2))] {
+            let err = transact_session_batch(settings, 0).expect_err("session batch should reject");
+            assert!(
+                err.contains("session keys are not active"),
+                "unexpected error: {err}"
+            );
+        }
+    }
+
+    fn transact_windowed_batch(
+        validity_windows: Option<ValidityWindowSettings>,
+        timestamp: u64,
+    ) -> Result<ExecutionResult, String> {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
         state.insert_account(
-            sponsor,
+            caller,
             AccountInfo {
-                balance: U256::from(100_000_000u64),
+                balance: U256::from(10_000_000_000u64),
                 nonce: 0,
                 code_hash: KECCAK_EMPTY,
                 code: None,
@@ -1340,172 +2233,1350 @@ mod tests {
         let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
         evm_env.cfg_env.chain_id = 1;
         evm_env.cfg_env.spec = SpecId::CANCUN;
-        evm_env.block_env.basefee = 100; // basefee = 100
+        evm_env.block_env.basefee = 1;
         evm_env.block_env.gas_limit = 30_000_000;
         evm_env.block_env.number = U256::from(1);
+        evm_env.block_env.timestamp = U256::from(timestamp);
 
-        let mut evm = EvTxEvmFactory::default().create_evm(state, evm_env);
-
-        let calls = vec![Call {
-            to: TxKind::Call(address!("0x0000000000000000000000000000000000000ccc")),
-            value: U256::ZERO,
-            input: Bytes::new(),
-        }];
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                validity_windows,
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
 
-        // max_fee_per_gas (50) < basefee (100)
         let tx_env = TxEnv {
             caller,
             gas_limit: 100_000,
-            gas_price: 50,
+            gas_price: 1,
             gas_priority_fee: Some(1),
             chain_id: Some(1),
             tx_type: TransactionType::Eip1559.into(),
             ..Default::default()
         };
+        let calls = vec![Call::transfer(
+            address!("0x0000000000000000000000000000000000000ddd"),
+            U256::from(1),
+        )];
+
+        let tx = EvTxEnv::with_calls(tx_env, calls).with_validity_window(Some(ValidityWindow {
+            valid_after: 50,
+            valid_until: 100,
+        }));
+        evm.transact_raw(tx)
+            .map(|result_and_state| result_and_state.result)
+            .map_err(|err| err.to_string())
+    }
 
-        let tx = EvTxEnv::with_calls_and_sponsor(tx_env, calls, sponsor);
-        let result = evm.transact_raw(tx);
+    #[test]
+    fn windowed_batch_checked_against_block_timestamp() {
+        let settings = Some(ValidityWindowSettings::new(0));
+        for timestamp in [50, 100] {
+            let result = transact_windowed_batch(settings, timestamp)
+                .expect("batch inside its window should execute");
+            assert!(matches!(result, ExecutionResult::Success { .. }));
+        }
 
+        let err = transact_windowed_batch(settings, 49).expect_err("window not open yet");
         assert!(
-            result.is_err(),
-            "Transaction with max_fee < basefee should be rejected"
+            err.contains("transaction is not valid before timestamp 50"),
+            "unexpected error: {err}"
         );
-
-        let err = result.unwrap_err();
+        let err = transact_windowed_batch(settings, 101).expect_err("window closed");
         assert!(
-            matches!(
-                err,
-                EVMError::Transaction(InvalidTransaction::GasPriceLessThanBasefee)
-            ),
-            "Expected GasPriceLessThanBasefee error, got: {err:?}"
+            err.contains("transaction expired at timestamp 100"),
+            "unexpected error: {err}"
         );
+
+        for settings in [None, Some(ValidityWindowSettings::new(2))] {
+            let err = transact_windowed_batch(settings, 75).expect_err("windows not active");
+            assert!(
+                err.contains("validity windows are not active"),
+                "unexpected error: {err}"
+            );
+        }
     }
 
-    #[test]
-    fn reject_deploy_for_non_allowlisted_caller() {
-        let allowlisted = address!("0x00000000000000000000000000000000000000aa");
-        let caller = address!("0x00000000000000000000000000000000000000bb");
-        let allowlist = DeployAllowlistSettings::new(vec![allowlisted], 0);
+    fn transact_lane_batch(
+        nonce_lanes: Option<NonceLaneSettings>,
+        nonce: u64,
+    ) -> Result<(ExecutionResult, EvmState), String> {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let lane = nonce_lane_address(caller, U192::from(1));
 
-        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
-        ctx.block.number = U256::from(1);
-        ctx.cfg.spec = SpecId::CANCUN;
-        ctx.tx.caller = caller;
-        ctx.tx.kind = TxKind::Create;
-        ctx.tx.gas_limit = 1_000_000;
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
+        state.insert_account(
+            caller,
+            AccountInfo {
+                balance: U256::from(10_000_000_000u64),
+                nonce: 5,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
+        );
+        state.insert_account(
+            lane,
+            AccountInfo {
+                nonce: 1,
+                ..Default::default()
+            },
+        );
 
-        let mut evm = build_test_evm(ctx, None, None);
-        let handler: TestHandler = EvHandler::new(None, Some(allowlist));
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(1);
 
-        let mut init_and_floor_gas = InitialAndFloorGas::default();
-        let result =
-            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
-        assert!(matches!(result, Err(EVMError::Custom(_))));
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                nonce_lanes,
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
+
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 100_000,
+            gas_price: 1,
+            gas_priority_fee: Some(1),
+            nonce,
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+        let calls = vec![Call::transfer(
+            address!("0x0000000000000000000000000000000000000ddd"),
+            U256::from(1),
+        )];
+
+        let tx = EvTxEnv::with_calls(tx_env, calls).with_nonce_lane(Some(U192::from(1)));
+        evm.transact_raw(tx)
+            .map(|result_and_state| (result_and_state.result, result_and_state.state))
+            .map_err(|err| err.to_string())
     }
 
     #[test]
-    fn allow_deploy_when_allowlist_is_empty() {
-        let caller = address!("0x00000000000000000000000000000000000000cc");
-        let allowlist = DeployAllowlistSettings::new(vec![], 0);
+    fn lane_batch_checked_against_lane_sequence() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let lane = nonce_lane_address(caller, U192::from(1));
+        let settings = Some(NonceLaneSettings::new(0));
 
-        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
-        ctx.block.number = U256::from(1);
-        ctx.cfg.spec = SpecId::CANCUN;
-        ctx.cfg.disable_nonce_check = true;
-        ctx.tx.caller = caller;
-        ctx.tx.kind = TxKind::Create;
-        ctx.tx.gas_limit = 1_000_000;
-        // gas_price=0 so no balance is required
-        ctx.tx.gas_price = 0;
+        let (result, state) =
+            transact_lane_batch(settings, 1).expect("batch at the lane sequence should execute");
+        assert!(matches!(result, ExecutionResult::Success { .. }));
+        assert_eq!(state[&lane].info.nonce, 2);
+        assert_eq!(state[&caller].info.nonce, 5);
 
-        let mut evm = build_test_evm(ctx, None, None);
-        let handler: TestHandler = EvHandler::new(None, Some(allowlist));
+        for nonce in [0, 2] {
+            transact_lane_batch(settings, nonce).expect_err("nonce outside the lane sequence");
+        }
 
-        let mut init_and_floor_gas = InitialAndFloorGas::default();
-        let result =
-            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
-        assert!(
-            result.is_ok(),
-            "empty allowlist should allow any caller to deploy, got: {result:?}"
+        for settings in [None, Some(NonceLaneSettings::new(2))] {
+            let err = transact_lane_batch(settings, 1).expect_err("nonce lanes not active");
+            assert!(
+                err.contains("nonce lanes are not active"),
+                "unexpected error: {err}"
+            );
+        }
+    }
+
+    fn transact_p256_batch(
+        p256_signatures: Option<P256SignatureSettings>,
+    ) -> Result<ExecutionResult, String> {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
+        state.insert_account(
+            caller,
+            AccountInfo {
+                balance: U256::from(10_000_000_000u64),
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
         );
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(1);
+
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                p256_signatures,
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
+
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 100_000,
+            gas_price: 1,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+        let calls = vec![Call::transfer(
+            address!("0x0000000000000000000000000000000000000ddd"),
+            U256::from(1),
+        )];
+
+        let tx = EvTxEnv::with_calls(tx_env, calls).with_p256_signed(true);
+        evm.transact_raw(tx)
+            .map(|result_and_state| result_and_state.result)
+            .map_err(|err| err.to_string())
     }
 
     #[test]
-    fn allow_deploy_when_allowlist_is_none() {
-        let caller = address!("0x00000000000000000000000000000000000000dd");
+    fn max_tx_gas_caps_share_of_block() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let transact = |max_tx_gas: Option<MaxTxGasSettings>, gas_limit: u64| {
+            let mut state = State::builder()
+                .with_database(CacheDB::<EmptyDB>::default())
+                .with_bundle_update()
+                .build();
+            state.insert_account(
+                caller,
+                AccountInfo {
+                    balance: U256::from(10_000_000_000u64),
+                    nonce: 0,
+                    code_hash: KECCAK_EMPTY,
+                    code: None,
+                    account_id: None,
+                },
+            );
 
-        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
-        ctx.block.number = U256::from(1);
-        ctx.cfg.spec = SpecId::CANCUN;
-        ctx.cfg.disable_nonce_check = true;
-        ctx.tx.caller = caller;
-        ctx.tx.kind = TxKind::Create;
-        ctx.tx.gas_limit = 1_000_000;
-        ctx.tx.gas_price = 0;
+            let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+            evm_env.cfg_env.chain_id = 1;
+            evm_env.cfg_env.spec = SpecId::CANCUN;
+            evm_env.block_env.basefee = 1;
+            evm_env.block_env.gas_limit = 1_000_000;
+            evm_env.block_env.number = U256::from(1);
+
+            let mut evm = EvTxEvmFactory::default()
+                .with_handler_settings(EvHandlerSettings {
+                    max_tx_gas,
+                    ..Default::default()
+                })
+                .create_evm(state, evm_env);
+            let tx_env = TxEnv {
+                caller,
+                kind: TxKind::Call(address!("0x0000000000000000000000000000000000000ddd")),
+                gas_limit,
+                gas_price: 1,
+                gas_priority_fee: Some(1),
+                chain_id: Some(1),
+                tx_type: TransactionType::Eip1559.into(),
+                ..Default::default()
+            };
+            evm.transact_raw(EvTxEnv::from(tx_env))
+                .map(|result_and_state| result_and_state.result)
+                .map_err(|err| err.to_string())
+        };
 
-        let mut evm = build_test_evm(ctx, None, None);
-        let handler: TestHandler = EvHandler::new(None, None);
+        let half = Some(MaxTxGasSettings::new(5_000, 0));
+        assert!(transact(half, 500_000).is_ok());
+        let err = transact(half, 500_001).expect_err("above half of the block");
+        assert!(
+            err.contains("exceeds the per-transaction maximum of 500000"),
+            "unexpected error: {err}"
+        );
+        assert!(transact(None, 500_001).is_ok());
+        assert!(transact(Some(MaxTxGasSettings::new(5_000, 2)), 500_001).is_ok());
+    }
 
-        let mut init_and_floor_gas = InitialAndFloorGas::default();
-        let result =
-            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
+    #[test]
+    fn sponsor_allowlist_rejects_unlisted_fee_payers() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let approved = address!("0x0000000000000000000000000000000000000bbb");
+        let other = address!("0x0000000000000000000000000000000000000ccc");
+        let transact = |allowlist: Option<SponsorAllowlistSettings>, sponsor: Address| {
+            let mut state = State::builder()
+                .with_database(CacheDB::<EmptyDB>::default())
+                .with_bundle_update()
+                .build();
+            for account in [caller, sponsor] {
+                state.insert_account(
+                    account,
+                    AccountInfo {
+                        balance: U256::from(10_000_000_000u64),
+                        nonce: 0,
+                        code_hash: KECCAK_EMPTY,
+                        code: None,
+                        account_id: None,
+                    },
+                );
+            }
+
+            let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+            evm_env.cfg_env.chain_id = 1;
+            evm_env.cfg_env.spec = SpecId::CANCUN;
+            evm_env.block_env.basefee = 1;
+            evm_env.block_env.gas_limit = 30_000_000;
+            evm_env.block_env.number = U256::from(1);
+
+            let mut evm = EvTxEvmFactory::default()
+                .with_handler_settings(EvHandlerSettings {
+                    sponsor_allowlist: allowlist,
+                    ..Default::default()
+                })
+                .create_evm(state, evm_env);
+            let tx_env = TxEnv {
+                caller,
+                gas_limit: 100_000,
+                gas_price: 1,
+                gas_priority_fee: Some(1),
+                chain_id: Some(1),
+                tx_type: TransactionType::Eip1559.into(),
+                ..Default::default()
+            };
+            let calls = vec![Call::transfer(
+                address!("0x0000000000000000000000000000000000000ddd"),
+                U256::from(1),
+            )];
+            evm.transact_raw(EvTxEnv::with_calls_and_sponsor(tx_env, calls, sponsor))
+                .map(|result_and_state| result_and_state.result)
+                .map_err(|err| err.to_string())
+        };
+
+        let allowlist = SponsorAllowlistSettings::new(vec![approved], 0);
+        assert!(transact(Some(allowlist.clone()), approved).is_ok());
+        let err = transact(Some(allowlist), other).expect_err("unlisted sponsor");
         assert!(
-            result.is_ok(),
-            "no allowlist configured should allow any caller to deploy, got: {result:?}"
+            err.contains("is not allowed to sponsor transactions"),
+            "unexpected error: {err}"
         );
+        assert!(transact(None, other).is_ok());
+        let pending = SponsorAllowlistSettings::new(vec![approved], 2);
+        assert!(transact(Some(pending), other).is_ok());
     }
 
     #[test]
-    fn allow_deploy_for_allowlisted_caller() {
-        let caller = address!("0x00000000000000000000000000000000000000ee");
-        let allowlist = DeployAllowlistSettings::new(vec![caller], 0);
+    fn p256_signed_batch_needs_activation() {
+        let result = transact_p256_batch(Some(P256SignatureSettings::new(0)))
+            .expect("P256-signed batch should execute once active");
+        assert!(matches!(result, ExecutionResult::Success { .. }));
+
+        for settings in [None, Some(P256SignatureSettings::new(2))] {
+            let err = transact_p256_batch(settings).expect_err("P256 signatures not active");
+            assert!(
+                err.contains("P256 executor signatures are not active"),
+                "unexpected error: {err}"
+            );
+        }
+    }
 
-        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
-        ctx.block.number = U256::from(1);
-        ctx.cfg.spec = SpecId::CANCUN;
-        ctx.cfg.disable_nonce_check = true;
-        ctx.tx.caller = caller;
-        ctx.tx.kind = TxKind::Create;
-        ctx.tx.gas_limit = 1_000_000;
-        ctx.tx.gas_price = 0;
+    #[test]
+    fn batch_execution_rejects_empty_calls() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
 
-        let mut evm = build_test_evm(ctx, None, None);
-        let handler: TestHandler = EvHandler::new(None, Some(allowlist));
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
 
-        let mut init_and_floor_gas = InitialAndFloorGas::default();
-        let result =
-            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
+        state.insert_account(
+            caller,
+            AccountInfo {
+                balance: U256::from(10_000_000_000u64),
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
+        );
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(1);
+
+        let mut evm = EvTxEvmFactory::default().create_evm(state, evm_env);
+
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 200_000,
+            gas_price: 1,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+
+        let tx = EvTxEnv::with_calls(tx_env, Vec::new());
+
+        let err = evm
+            .transact_raw(tx)
+            .expect_err("empty call batch should reject");
         assert!(
-            result.is_ok(),
-            "allowlisted caller should be allowed to deploy, got: {result:?}"
+            err.to_string()
+                .contains("evnode transaction must include at least one call"),
+            "unexpected error: {err:?}"
         );
     }
 
     #[test]
-    fn call_tx_allowed_for_non_allowlisted_caller() {
-        let allowed = address!("0x00000000000000000000000000000000000000aa");
-        let caller = address!("0x00000000000000000000000000000000000000ff");
-        let allowlist = DeployAllowlistSettings::new(vec![allowed], 0);
+    fn sponsored_tx_rejects_when_balance_below_max_fee() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let sponsor = address!("0x0000000000000000000000000000000000000bbb");
 
-        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
-        ctx.block.number = U256::from(1);
-        ctx.cfg.spec = SpecId::CANCUN;
-        ctx.cfg.disable_nonce_check = true;
-        ctx.tx.caller = caller;
-        ctx.tx.kind = TxKind::Call(Address::ZERO);
-        ctx.tx.gas_limit = 1_000_000;
-        ctx.tx.gas_price = 0;
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
 
-        let mut evm = build_test_evm(ctx, None, None);
-        let handler: TestHandler = EvHandler::new(None, Some(allowlist));
+        state.insert_account(
+            caller,
+            AccountInfo {
+                balance: U256::from(10_000_000_000u64),
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
+        );
 
-        let mut init_and_floor_gas = InitialAndFloorGas::default();
-        let result =
-            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
-        assert!(
-            result.is_ok(),
-            "CALL tx should be allowed regardless of allowlist, got: {result:?}"
+        state.insert_account(
+            sponsor,
+            AccountInfo {
+                balance: U256::from(500_000u64),
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
         );
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(1);
+
+        let mut evm = EvTxEvmFactory::default().create_evm(state, evm_env);
+
+        let calls = vec![Call {
+            to: TxKind::Call(address!("0x0000000000000000000000000000000000000ccc")),
+            value: U256::ZERO,
+            input: Bytes::new(),
+            gas_limit: None,
+        }];
+
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 100_000,
+            gas_price: 100,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+
+        let tx = EvTxEnv::with_calls_and_sponsor(tx_env, calls, sponsor);
+
+        let err = evm
+            .transact_raw(tx)
+            .expect_err("sponsor should need max fee coverage");
+        assert!(
+            matches!(
+                err,
+                EVMError::Transaction(InvalidTransaction::LackOfFundForMaxFee { .. })
+            ),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    /// Tests that sponsored transactions with `max_fee_per_gas` < `base_fee` are rejected.
+    ///
+    /// This validation happens in revm's `validate_env` (delegated via inner handler)
+    /// BEFORE our custom `validate_and_deduct_sponsored_tx` runs. This test serves
+    /// as a regression test to ensure EIP-1559 fee validation is not bypassed.
+    #[test]
+    fn sponsored_tx_rejects_when_max_fee_below_basefee() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let sponsor = address!("0x0000000000000000000000000000000000000bbb");
+
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
+
+        state.insert_account(
+            caller,
+            AccountInfo {
+                balance: U256::from(10_000_000_000u64),
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
+        );
+
+        // Sponsor has plenty of balance - the rejection should be due to fee, not balance
+        state.insert_account(
+            sponsor,
+            AccountInfo {
+                balance: U256::from(100_000_000u64),
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
+        );
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 100; // basefee = 100
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(1);
+
+        let mut evm = EvTxEvmFactory::default().create_evm(state, evm_env);
+
+        let calls = vec![Call {
+            to: TxKind::Call(address!("0x0000000000000000000000000000000000000ccc")),
+            value: U256::ZERO,
+            input: Bytes::new(),
+            gas_limit: None,
+        }];
+
+        // max_fee_per_gas (50) < basefee (100)
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 100_000,
+            gas_price: 50,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+
+        let tx = EvTxEnv::with_calls_and_sponsor(tx_env, calls, sponsor);
+        let result = evm.transact_raw(tx);
+
+        assert!(
+            result.is_err(),
+            "Transaction with max_fee < basefee should be rejected"
+        );
+
+        let err = result.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                EVMError::Transaction(InvalidTransaction::GasPriceLessThanBasefee)
+            ),
+            "Expected GasPriceLessThanBasefee error, got: {err:?}"
+        );
+    }
+
+    #[test]
+    fn capped_sponsor_falls_back_to_caller_for_excess_fee() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let sponsor = address!("0x0000000000000000000000000000000000000bbb");
+        let caller_balance = U256::from(10_000_000_000u64);
+        let sponsor_balance = U256::from(100_000u64);
+
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
+        for (account, balance) in [(caller, caller_balance), (sponsor, sponsor_balance)] {
+            state.insert_account(
+                account,
+                AccountInfo {
+                    balance,
+                    nonce: 0,
+                    code_hash: KECCAK_EMPTY,
+                    code: None,
+                    account_id: None,
+                },
+            );
+        }
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(1);
+
+        let mut evm = EvTxEvmFactory::default().create_evm(state, evm_env);
+
+        let calls = vec![Call {
+            to: TxKind::Call(address!("0x0000000000000000000000000000000000000ccc")),
+            value: U256::ZERO,
+            input: Bytes::new(),
+            gas_limit: None,
+        }];
+
+        // The sponsor could not cover the max fee (1_000_000) on its own, but only has to
+        // cover its cap.
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 100_000,
+            gas_price: 10,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+        let tx = EvTxEnv::with_calls_and_capped_sponsor(tx_env, calls, sponsor, 30_000);
+
+        let result_and_state = evm
+            .transact_raw(tx)
+            .expect("capped sponsorship should execute");
+        let gas_used = result_and_state.result.gas().tx_gas_used();
+        // Effective gas price is basefee + priority fee.
+        let fee = U256::from(gas_used) * U256::from(2);
+        assert!(fee > U256::from(30_000));
+
+        let state = result_and_state.state;
+        assert_eq!(
+            sponsor_balance - balance_of(&state, sponsor),
+            U256::from(30_000)
+        );
+        assert_eq!(
+            caller_balance - balance_of(&state, caller),
+            fee - U256::from(30_000)
+        );
+    }
+
+    #[test]
+    fn reject_deploy_for_non_allowlisted_caller() {
+        let allowlisted = address!("0x00000000000000000000000000000000000000aa");
+        let caller = address!("0x00000000000000000000000000000000000000bb");
+        let allowlist = DeployAllowlistSettings::new(vec![allowlisted], 0);
+
+        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
+        ctx.block.number = U256::from(1);
+        ctx.cfg.spec = SpecId::CANCUN;
+        ctx.tx.caller = caller;
+        ctx.tx.kind = TxKind::Create;
+        ctx.tx.gas_limit = 1_000_000;
+
+        let mut evm = build_test_evm(ctx, None, None);
+        let handler: TestHandler = EvHandler::new(EvHandlerSettings::new(None, Some(allowlist)));
+
+        let mut init_and_floor_gas = InitialAndFloorGas::default();
+        let result =
+            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
+        assert!(matches!(result, Err(EVMError::Custom(_))));
+    }
+
+    #[test]
+    fn allow_deploy_when_allowlist_is_empty() {
+        let caller = address!("0x00000000000000000000000000000000000000cc");
+        let allowlist = DeployAllowlistSettings::new(vec![], 0);
+
+        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
+        ctx.block.number = U256::from(1);
+        ctx.cfg.spec = SpecId::CANCUN;
+        ctx.cfg.disable_nonce_check = true;
+        ctx.tx.caller = caller;
+        ctx.tx.kind = TxKind::Create;
+        ctx.tx.gas_limit = 1_000_000;
+        // gas_price=0 so no balance is required
+        ctx.tx.gas_price = 0;
+
+        let mut evm = build_test_evm(ctx, None, None);
+        let handler: TestHandler = EvHandler::new(EvHandlerSettings::new(None, Some(allowlist)));
+
+        let mut init_and_floor_gas = InitialAndFloorGas::default();
+        let result =
+            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
+        assert!(
+            result.is_ok(),
+            "empty allowlist should allow any caller to deploy, got: {result:?}"
+        );
+    }
+
+    #[test]
+    fn allow_deploy_when_allowlist_is_none() {
+        let caller = address!("0x00000000000000000000000000000000000000dd");
+
+        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
+        ctx.block.number = U256::from(1);
+        ctx.cfg.spec = SpecId::CANCUN;
+        ctx.cfg.disable_nonce_check = true;
+        ctx.tx.caller = caller;
+        ctx.tx.kind = TxKind::Create;
+        ctx.tx.gas_limit = 1_000_000;
+        ctx.tx.gas_price = 0;
+
+        let mut evm = build_test_evm(ctx, None, None);
+        let handler: TestHandler = EvHandler::new(EvHandlerSettings::default());
+
+        let mut init_and_floor_gas = InitialAndFloorGas::default();
+        let result =
+            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
+        assert!(
+            result.is_ok(),
+            "no allowlist configured should allow any caller to deploy, got: {result:?}"
+        );
+    }
+
+    #[test]
+    fn allow_deploy_for_allowlisted_caller() {
+        let caller = address!("0x00000000000000000000000000000000000000ee");
+        let allowlist = DeployAllowlistSettings::new(vec![caller], 0);
+
+        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
+        ctx.block.number = U256::from(1);
+        ctx.cfg.spec = SpecId::CANCUN;
+        ctx.cfg.disable_nonce_check = true;
+        ctx.tx.caller = caller;
+        ctx.tx.kind = TxKind::Create;
+        ctx.tx.gas_limit = 1_000_000;
+        ctx.tx.gas_price = 0;
+
+        let mut evm = build_test_evm(ctx, None, None);
+        let handler: TestHandler = EvHandler::new(EvHandlerSettings::new(None, Some(allowlist)));
+
+        let mut init_and_floor_gas = InitialAndFloorGas::default();
+        let result =
+            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
+        assert!(
+            result.is_ok(),
+            "allowlisted caller should be allowed to deploy, got: {result:?}"
+        );
+    }
+
+    #[test]
+    fn call_tx_allowed_for_non_allowlisted_caller() {
+        let allowed = address!("0x00000000000000000000000000000000000000aa");
+        let caller = address!("0x00000000000000000000000000000000000000ff");
+        let allowlist = DeployAllowlistSettings::new(vec![allowed], 0);
+
+        let mut ctx = Context::mainnet().with_db(EmptyDB::default());
+        ctx.block.number = U256::from(1);
+        ctx.cfg.spec = SpecId::CANCUN;
+        ctx.cfg.disable_nonce_check = true;
+        ctx.tx.caller = caller;
+        ctx.tx.kind = TxKind::Call(Address::ZERO);
+        ctx.tx.gas_limit = 1_000_000;
+        ctx.tx.gas_price = 0;
+
+        let mut evm = build_test_evm(ctx, None, None);
+        let handler: TestHandler = EvHandler::new(EvHandlerSettings::new(None, Some(allowlist)));
+
+        let mut init_and_floor_gas = InitialAndFloorGas::default();
+        let result =
+            handler.validate_against_state_and_deduct_caller(&mut evm, &mut init_and_floor_gas);
+        assert!(
+            result.is_ok(),
+            "CALL tx should be allowed regardless of allowlist, got: {result:?}"
+        );
+    }
+
+    const CONTRACT_FEE_PAYER_BALANCE: u64 = 1_000_000_000;
+
+    fn transact_with_contract_fee_payer(
+        fee_payer_runtime: &[u8],
+        gas_allowance: Option<GasAllowanceSettings>,
+        caller_nonce: u64,
+    ) -> Result<reth_revm::revm::context::result::ResultAndState<HaltReason>, String> {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let fee_payer = address!("0x0000000000000000000000000000000000000bbb");
+
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
+
+        state.insert_account(
+            caller,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: caller_nonce,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
+        );
+
+        state.insert_account(
+            fee_payer,
+            AccountInfo {
+                balance: U256::from(CONTRACT_FEE_PAYER_BALANCE),
+                nonce: 1,
+                code_hash: alloy_primitives::keccak256(fee_payer_runtime),
+                code: Some(RevmBytecode::new_raw(Bytes::copy_from_slice(
+                    fee_payer_runtime,
+                ))),
+                account_id: None,
+            },
+        );
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(1);
+
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                gas_allowance,
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
+
+        let calls = vec![Call {
+            to: TxKind::Call(address!("0x0000000000000000000000000000000000000ccc")),
+            value: U256::ZERO,
+            input: Bytes::new(),
+            gas_limit: None,
+        }];
+
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 100_000,
+            gas_price: 10,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+
+        let tx = EvTxEnv::with_calls_and_contract_sponsor(tx_env, calls, fee_payer);
+        evm.transact_raw(tx).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn contract_fee_payer_pays_after_approval() {
+        let fee_payer = address!("0x0000000000000000000000000000000000000bbb");
+        let result_and_state = transact_with_contract_fee_payer(
+            &APPROVE_RUNTIME,
+            Some(GasAllowanceSettings::new(0)),
+            0,
+        )
+        .expect("approved contract fee payer should sponsor the tx");
+
+        assert!(matches!(
+            result_and_state.result,
+            ExecutionResult::Success { .. }
+        ));
+        // The approval call is charged on top of the base intrinsic gas.
+        assert!(result_and_state.result.gas().tx_gas_used() > 21_000);
+
+        let fee_payer_account = result_and_state
+            .state
+            .get(&fee_payer)
+            .expect("fee payer should be loaded");
+        let charged = U256::from(CONTRACT_FEE_PAYER_BALANCE) - fee_payer_account.info.balance;
+        assert_eq!(
+            charged,
+            U256::from(result_and_state.result.gas().tx_gas_used()) * U256::from(2)
+        );
+    }
+
+    #[test]
+    fn contract_fee_payer_receives_post_op() {
+        let fee_payer = address!("0x0000000000000000000000000000000000000bbb");
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let slot = |state: &EvmState, index: u64| {
+            state[&fee_payer].storage[&U256::from(index)].present_value
+        };
+
+        // Without the hook, the last call the contract saw was `approveGas(executor, amount)`.
+        let result_and_state = transact_with_contract_fee_payer(
+            &RECORDING_RUNTIME,
+            Some(GasAllowanceSettings::new(0)),
+            0,
+        )
+        .expect("approved contract fee payer should sponsor the tx");
+        assert_eq!(
+            slot(&result_and_state.state, 0),
+            U256::from_be_slice(caller.as_slice())
+        );
+
+        let result_and_state = transact_with_contract_fee_payer(
+            &RECORDING_RUNTIME,
+            Some(GasAllowanceSettings::new(0).with_post_op_activation_height(Some(0))),
+            0,
+        )
+        .expect("approved contract fee payer should sponsor the tx");
+        let actual_gas_cost = slot(&result_and_state.state, 0);
+        assert_eq!(slot(&result_and_state.state, 1), U256::from(1));
+
+        // The reported cost excludes the `postOp` call, which is charged on top of it.
+        let charged = U256::from(CONTRACT_FEE_PAYER_BALANCE)
+            - result_and_state.state[&fee_payer].info.balance;
+        assert_eq!(
+            charged,
+            U256::from(result_and_state.result.gas().tx_gas_used()) * U256::from(2)
+        );
+        assert!(!actual_gas_cost.is_zero() && actual_gas_cost < charged);
+    }
+
+    #[test]
+    fn contract_fee_payer_rejected_when_denied() {
+        let err =
+            transact_with_contract_fee_payer(&DENY_RUNTIME, Some(GasAllowanceSettings::new(0)), 0)
+                .expect_err("denied allowance should reject the tx");
+        assert!(
+            err.contains("gas allowance denied"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn contract_fee_payer_not_called_for_invalid_nonce() {
+        // A denying fee payer would reject the tx too; the nonce check must fail first.
+        let err =
+            transact_with_contract_fee_payer(&DENY_RUNTIME, Some(GasAllowanceSettings::new(0)), 1)
+                .expect_err("stale nonce should reject the tx");
+        assert!(err.contains("nonce"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn contract_fee_payer_rejected_before_activation() {
+        for settings in [None, Some(GasAllowanceSettings::new(10))] {
+            let err = transact_with_contract_fee_payer(&APPROVE_RUNTIME, settings, 0)
+                .expect_err("contract fee payer should be rejected before activation");
+            assert!(
+                err.contains("invalid sponsor signature"),
+                "unexpected error: {err}"
+            );
+        }
+    }
+
+    fn transact_with_state_expiry(
+        archived_target: bool,
+    ) -> Result<reth_revm::revm::context::result::ResultAndState<HaltReason>, String> {
+        use ev_precompiles::expiry::{archive_slot, witness_commitment};
+
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let target = address!("0x0000000000000000000000000000000000000ccc");
+
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
+        state.insert_account(
+            caller,
+            AccountInfo {
+                balance: U256::from(10_000_000_u64),
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
+        );
+        if archived_target {
+            state.insert_account_with_storage(
+                STATE_EXPIRY_PRECOMPILE_ADDR,
+                AccountInfo {
+                    nonce: 1,
+                    ..Default::default()
+                },
+                [(
+                    archive_slot(target),
+                    witness_commitment(target, 1, U256::from(1)).into(),
+                )]
+                .into_iter()
+                .collect(),
+            );
+        }
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(7);
+
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                state_expiry: Some(StateExpirySettings::new(0, 100)),
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
+
+        let tx_env = TxEnv {
+            caller,
+            kind: TxKind::Call(target),
+            gas_limit: 100_000,
+            gas_price: 10,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+        evm.transact_raw(EvTxEnv::from(tx_env))
+            .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn state_expiry_records_activity() {
+        use ev_precompiles::expiry::last_active_slot;
+
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let result_and_state =
+            transact_with_state_expiry(false).expect("live accounts should transact");
+        let registry = result_and_state
+            .state
+            .get(&STATE_EXPIRY_PRECOMPILE_ADDR)
+            .expect("registry account should be touched");
+        let recorded = registry
+            .storage
+            .get(&last_active_slot(caller))
+            .expect("caller activity should be recorded");
+        assert_eq!(recorded.present_value, U256::from(7));
+    }
+
+    #[test]
+    fn state_expiry_rejects_archived_target() {
+        let err = transact_with_state_expiry(true)
+            .expect_err("transactions to archived accounts should be rejected");
+        assert!(err.contains("is archived"), "unexpected error: {err}");
+    }
+
+    /// Gas used by a call to a contract that reads the balance of a system address.
+    fn balance_probe_gas(warmup: Option<PrecompileWarmupSettings>) -> u64 {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let probe = address!("0x0000000000000000000000000000000000000ccc");
+        let system = address!("0x0000000000000000000000000000000000000ddd");
+        // PUSH20 system, BALANCE, POP, STOP
+        let mut runtime = vec![0x73];
+        runtime.extend_from_slice(system.as_slice());
+        runtime.extend_from_slice(&[0x31, 0x50, 0x00]);
+
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
+        state.insert_account(
+            caller,
+            AccountInfo {
+                balance: U256::from(10_000_000_u64),
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
+        );
+        state.insert_account(
+            probe,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 1,
+                code_hash: alloy_primitives::keccak256(&runtime),
+                code: Some(RevmBytecode::new_raw(Bytes::from(runtime))),
+                account_id: None,
+            },
+        );
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(7);
+
+        let mut evm = EvTxEvmFactory::default()
+            .with_precompile_warmup(warmup.map(|settings| settings.with_addresses(vec![system])))
+            .create_evm(state, evm_env);
+
+        let tx_env = TxEnv {
+            caller,
+            kind: TxKind::Call(probe),
+            gas_limit: 100_000,
+            gas_price: 10,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+        evm.transact_raw(EvTxEnv::from(tx_env))
+            .expect("probe call should succeed")
+            .result
+            .gas()
+            .tx_gas_used()
+    }
+
+    #[test]
+    fn precompile_warmup_charges_warm_access() {
+        let cold = balance_probe_gas(None);
+        let warm = balance_probe_gas(Some(PrecompileWarmupSettings::new(0)));
+        // EIP-2929: a cold account access costs 2600 gas, a warm one 100.
+        assert_eq!(cold - warm, 2_500);
+    }
+
+    #[test]
+    fn precompile_warmup_waits_for_activation() {
+        let cold = balance_probe_gas(None);
+        let pending = balance_probe_gas(Some(PrecompileWarmupSettings::new(8)));
+        assert_eq!(cold, pending);
+    }
+
+    fn transact_system_tx(mode: SystemFeeMode) -> EvmState {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let target = address!("0x0000000000000000000000000000000000000ccc");
+        let fee_account = address!("0x0000000000000000000000000000000000000ddd");
+        let beneficiary = address!("0x0000000000000000000000000000000000000eee");
+
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
+        for account in [caller, fee_account] {
+            state.insert_account(
+                account,
+                AccountInfo {
+                    balance: U256::from(10_000_000_u64),
+                    nonce: 0,
+                    code_hash: KECCAK_EMPTY,
+                    code: None,
+                    account_id: None,
+                },
+            );
+        }
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.beneficiary = beneficiary;
+        evm_env.block_env.gas_limit = 30_000_000;
+
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                system_fee: Some(SystemFeeSettings::new(vec![caller], mode, 0)),
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
+
+        let tx_env = TxEnv {
+            caller,
+            kind: TxKind::Call(target),
+            gas_limit: 100_000,
+            gas_price: 10,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+        let result_and_state = evm
+            .transact_raw(EvTxEnv::from(tx_env))
+            .expect("system transaction should execute");
+        assert!(result_and_state.result.is_success());
+        assert_eq!(result_and_state.result.gas().tx_gas_used(), 21_000);
+        result_and_state.state
+    }
+
+    fn balance_of(state: &EvmState, account: Address) -> U256 {
+        state
+            .get(&account)
+            .map_or(U256::ZERO, |account| account.info.balance)
+    }
+
+    #[test]
+    fn system_fee_waived_charges_nobody() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let beneficiary = address!("0x0000000000000000000000000000000000000eee");
+        let state = transact_system_tx(SystemFeeMode::Waived);
+        assert_eq!(balance_of(&state, caller), U256::from(10_000_000_u64));
+        assert_eq!(state[&caller].info.nonce, 1);
+        assert_eq!(balance_of(&state, beneficiary), U256::ZERO);
+    }
+
+    #[test]
+    fn system_fee_charged_to_fee_account() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let fee_account = address!("0x0000000000000000000000000000000000000ddd");
+        let beneficiary = address!("0x0000000000000000000000000000000000000eee");
+        let state = transact_system_tx(SystemFeeMode::Charged(fee_account));
+        assert_eq!(balance_of(&state, caller), U256::from(10_000_000_u64));
+        // 21_000 gas at an effective price of 2 (base fee 1 + tip 1).
+        assert_eq!(
+            balance_of(&state, fee_account),
+            U256::from(10_000_000_u64 - 42_000)
+        );
+        assert_eq!(balance_of(&state, beneficiary), U256::from(21_000));
+    }
+
+    #[test]
+    fn system_fee_burned_pays_out_nothing() {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        let beneficiary = address!("0x0000000000000000000000000000000000000eee");
+        let state = transact_system_tx(SystemFeeMode::Burned);
+        assert_eq!(
+            balance_of(&state, caller),
+            U256::from(10_000_000_u64 - 42_000)
+        );
+        assert_eq!(balance_of(&state, beneficiary), U256::ZERO);
+    }
+
+    /// Runs a sponsored transaction whose sponsor signature recovers to different sponsors
+    /// under each scheme, with only the [`SponsorHashScheme::FeePayer`] sponsor funded.
+    fn transact_with_legacy_sponsorship(
+        block_number: u64,
+    ) -> (Address, Result<EvmState, TestError>) {
+        transact_with_sponsorship(
+            block_number,
+            Some(SponsorHashMigration::new(
+                SponsorHashScheme::FeePayer,
+                1,
+                10,
+            )),
+            None,
+            SponsorHashScheme::FeePayer,
+        )
+    }
+
+    /// Runs a sponsored transaction whose sponsor signature recovers to different sponsors
+    /// under each scheme, with only the sponsor recovered under `funded` funded.
+    fn transact_with_sponsorship(
+        block_number: u64,
+        sponsor_hash: Option<SponsorHashMigration>,
+        eip712_sponsor: Option<Eip712SponsorSettings>,
+        funded: SponsorHashScheme,
+    ) -> (Address, Result<EvmState, TestError>) {
+        let caller = address!("0x0000000000000000000000000000000000000aaa");
+        // Any valid `r` recovers an address for every hash; the generator's x-coordinate is one.
+        let signature = Signature::new(
+            U256::from_be_slice(&alloy_primitives::hex!(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+            )),
+            U256::from(1),
+            false,
+        );
+        let executor_hash = B256::repeat_byte(0x01);
+        let fee_payer_hash = B256::repeat_byte(0x02);
+        let eip712_hash = B256::repeat_byte(0x03);
+        let funded_hash = match funded {
+            SponsorHashScheme::Executor => executor_hash,
+            SponsorHashScheme::FeePayer => fee_payer_hash,
+            SponsorHashScheme::Eip712 => eip712_hash,
+        };
+        let funded_sponsor = signature
+            .recover_address_from_prehash(&funded_hash)
+            .expect("recoverable sponsor signature");
+
+        let mut state = State::builder()
+            .with_database(CacheDB::<EmptyDB>::default())
+            .with_bundle_update()
+            .build();
+        state.insert_account(
+            funded_sponsor,
+            AccountInfo {
+                balance: U256::from(10_000_000_u64),
+                nonce: 0,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+                account_id: None,
+            },
+        );
+
+        let mut evm_env: EvmEnv<SpecId> = EvmEnv::default();
+        evm_env.cfg_env.chain_id = 1;
+        evm_env.cfg_env.spec = SpecId::CANCUN;
+        evm_env.block_env.basefee = 1;
+        evm_env.block_env.gas_limit = 30_000_000;
+        evm_env.block_env.number = U256::from(block_number);
+
+        let mut evm = EvTxEvmFactory::default()
+            .with_handler_settings(EvHandlerSettings {
+                sponsor_hash,
+                eip712_sponsor,
+                ..Default::default()
+            })
+            .create_evm(state, evm_env);
+
+        let calls = vec![Call {
+            to: TxKind::Call(address!("0x0000000000000000000000000000000000000ccc")),
+            value: U256::ZERO,
+            input: Bytes::new(),
+            gas_limit: None,
+        }];
+        let tx_env = TxEnv {
+            caller,
+            gas_limit: 100_000,
+            gas_price: 10,
+            gas_priority_fee: Some(1),
+            chain_id: Some(1),
+            tx_type: TransactionType::Eip1559.into(),
+            ..Default::default()
+        };
+        let tx = EvTxEnv::with_calls_and_sponsor_signature(
+            tx_env,
+            calls,
+            signature,
+            executor_hash,
+            fee_payer_hash,
+            eip712_hash,
+        );
+
+        let result = evm.transact_raw(tx).map(|result| result.state);
+        (funded_sponsor, result)
+    }
+
+    #[test]
+    fn sponsor_hash_legacy_scheme_before_transition() {
+        let (legacy_sponsor, result) = transact_with_legacy_sponsorship(0);
+        let state = result.expect("legacy sponsorship should be accepted");
+        assert!(balance_of(&state, legacy_sponsor) < U256::from(10_000_000_u64));
+    }
+
+    #[test]
+    fn sponsor_hash_falls_back_to_legacy_scheme_during_transition() {
+        let (legacy_sponsor, result) = transact_with_legacy_sponsorship(5);
+        let state = result.expect("legacy sponsorship should be accepted during transition");
+        assert!(balance_of(&state, legacy_sponsor) < U256::from(10_000_000_u64));
+    }
+
+    #[test]
+    fn sponsor_hash_rejects_legacy_scheme_after_activation() {
+        let (_, result) = transact_with_legacy_sponsorship(10);
+        let err = result.expect_err("legacy sponsorship should be rejected after activation");
+        assert!(
+            matches!(
+                err,
+                EVMError::Transaction(InvalidTransaction::LackOfFundForMaxFee { .. })
+            ),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn eip712_sponsorship_accepted_from_activation() {
+        let eip712_sponsor = Some(Eip712SponsorSettings::new(5));
+
+        let (sponsor, result) =
+            transact_with_sponsorship(5, None, eip712_sponsor, SponsorHashScheme::Eip712);
+        let state = result.expect("eip712 sponsorship should be accepted once active");
+        assert!(balance_of(&state, sponsor) < U256::from(10_000_000_u64));
+
+        let (_, result) =
+            transact_with_sponsorship(4, None, eip712_sponsor, SponsorHashScheme::Eip712);
+        assert!(matches!(
+            result,
+            Err(EVMError::Transaction(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            ))
+        ));
+
+        let (sponsor, result) =
+            transact_with_sponsorship(4, None, eip712_sponsor, SponsorHashScheme::Executor);
+        let state = result.expect("raw sponsorship should be accepted");
+        assert!(balance_of(&state, sponsor) < U256::from(10_000_000_u64));
     }
 
     fn build_test_evm(
@@ -1514,7 +3585,11 @@ mod tests {
         deploy_allowlist: Option<DeployAllowlistSettings>,
     ) -> TestEvm {
         let inner = ctx.build_mainnet_with_inspector(NoOpInspector);
-        EvEvm::from_inner(inner, redirect, deploy_allowlist, false)
+        EvEvm::from_inner(
+            inner,
+            EvHandlerSettings::new(redirect, deploy_allowlist),
+            false,
+        )
     }
 
     fn setup_evm(redirect: BaseFeeRedirect, beneficiary: Address) -> (TestEvm, TestHandler) {
@@ -1533,7 +3608,7 @@ mod tests {
             journal.load_account(beneficiary).unwrap();
         }
 
-        let handler: TestHandler = EvHandler::new(Some(redirect), None);
+        let handler: TestHandler = EvHandler::new(EvHandlerSettings::new(Some(redirect), None));
         (evm, handler)
     }
 
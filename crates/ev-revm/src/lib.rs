@@ -1,24 +1,63 @@
 //! Base-fee redirect extensions for `revm`.
 
+pub mod access_list;
 pub mod api;
 pub mod base_fee;
+pub mod best_effort;
+pub mod call_gas;
 pub mod config;
 /// Deploy allowlist configuration helpers.
 pub mod deploy;
 pub mod evm;
+pub mod evm_version;
 pub mod factory;
+pub mod gas_allowance;
 pub mod handler;
+pub mod limits;
+pub mod max_tx_gas;
+pub mod metering;
+pub mod min_gas_price;
+pub mod nonce_lane;
+pub mod p256_signature;
+pub mod session;
+pub mod sponsor_allowlist;
+pub mod sponsor_handoff;
+pub mod sponsor_hash;
+pub mod state_expiry;
+pub mod system_fee;
 /// EV-specific transaction environment extensions.
 pub mod tx_env;
+pub mod validity;
+pub mod warmup;
 
+pub use access_list::{AccessListSettings, AccessListTooLarge};
 pub use api::EvBuilder;
-pub use base_fee::{BaseFeeRedirect, BaseFeeRedirectError};
+pub use base_fee::{BaseFeeRedirect, BaseFeeRedirectError, MAX_BURN_BPS};
+pub use best_effort::BestEffortSettings;
+pub use call_gas::CallGasLimitSettings;
 pub use config::{BaseFeeConfig, ConfigError};
 pub use deploy::DeployAllowlistSettings;
 pub use evm::{DefaultEvEvm, EvEvm};
+pub use evm_version::{parse_evm_version, EvmVersionSettings};
 pub use factory::{
     with_ev_handler, BaseFeeRedirectSettings, ContractSizeLimitSettings, EvEvmFactory,
-    EvTxEvmFactory, MintPrecompileSettings,
+    EvTxEvmFactory, FeaturesPrecompileSettings, InboxPrecompileSettings, MintPrecompileSettings,
+    P256VerifyPrecompileSettings, StakingPrecompileSettings, SystemChannelSettings,
 };
-pub use handler::EvHandler;
+pub use gas_allowance::GasAllowanceSettings;
+pub use handler::{EvHandler, EvHandlerSettings};
+pub use limits::EvmLimitsSettings;
+pub use max_tx_gas::{MaxTxGasSettings, TxGasAboveMaximum, MAX_TX_GAS_BPS};
+pub use metering::{DimensionPricing, GasDimensions};
+pub use min_gas_price::{GasPriceBelowMinimum, MinGasPriceSettings};
+pub use nonce_lane::NonceLaneSettings;
+pub use p256_signature::P256SignatureSettings;
+pub use session::SessionKeySettings;
+pub use sponsor_allowlist::{SponsorAllowlistSettings, SponsorNotAllowed};
+pub use sponsor_handoff::RecoveredSponsors;
+pub use sponsor_hash::{Eip712SponsorSettings, SponsorHashMigration};
+pub use state_expiry::StateExpirySettings;
+pub use system_fee::{SystemFeeMode, SystemFeeSettings};
 pub use tx_env::EvTxEnv;
+pub use validity::ValidityWindowSettings;
+pub use warmup::PrecompileWarmupSettings;
@@ -0,0 +1,77 @@
+//! Chain-wide caps on EVM memory and call depth.
+//!
+//! Once active, a call nested deeper than the configured depth fails with `CallTooDeep`, as it
+//! would at the protocol's 1024 limit, and memory expansion beyond the configured size halts
+//! the frame with `MemoryLimitOOG`. The memory cap covers the whole active call stack, since
+//! frames share one memory buffer in revm.
+
+/// Smallest accepted call depth cap; enough for proxies, routers and a few nested calls.
+pub const MIN_CALL_DEPTH: u64 = 16;
+
+/// Largest accepted call depth cap, the protocol's own limit.
+pub const MAX_CALL_DEPTH: u64 = 1024;
+
+/// Smallest accepted memory cap, in bytes.
+pub const MIN_MEMORY_LIMIT: u64 = 1 << 20;
+
+/// Largest accepted memory cap, in bytes, revm's default.
+pub const MAX_MEMORY_LIMIT: u64 = u32::MAX as u64;
+
+/// Settings for capping EVM memory and call depth from a specific block height.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EvmLimitsSettings {
+    activation_height: u64,
+    max_call_depth: Option<u64>,
+    memory_limit: Option<u64>,
+}
+
+impl EvmLimitsSettings {
+    /// Creates a new settings object without caps.
+    pub const fn new(activation_height: u64) -> Self {
+        Self {
+            activation_height,
+            max_call_depth: None,
+            memory_limit: None,
+        }
+    }
+
+    /// Caps the call depth, the top-level call being at depth 0.
+    pub const fn with_max_call_depth(mut self, max_call_depth: Option<u64>) -> Self {
+        self.max_call_depth = max_call_depth;
+        self
+    }
+
+    /// Caps the memory of the active call stack, in bytes.
+    pub const fn with_memory_limit(mut self, memory_limit: Option<u64>) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    /// Returns the activation height for the caps.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if the caps apply at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+
+    /// Returns the call depth cap for the given block number, if any.
+    pub const fn max_call_depth_for_block(&self, block_number: u64) -> Option<u64> {
+        if self.is_active(block_number) {
+            self.max_call_depth
+        } else {
+            None
+        }
+    }
+
+    /// Returns the memory cap for the given block number, if any.
+    pub const fn memory_limit_for_block(&self, block_number: u64) -> Option<u64> {
+        if self.is_active(block_number) {
+            self.memory_limit
+        } else {
+            None
+        }
+    }
+}
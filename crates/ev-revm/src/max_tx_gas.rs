@@ -0,0 +1,97 @@
+//! Cap on the share of a block a single transaction may use.
+//!
+//! Once active, a transaction whose gas limit exceeds the configured fraction of the block gas
+//! limit is rejected by the handler, so one large transaction cannot fill a short block and
+//! crowd out payment traffic. The txpool applies the same check against the latest block.
+
+use thiserror::Error;
+
+/// Largest accepted cap, the whole block.
+pub const MAX_TX_GAS_BPS: u16 = 10_000;
+
+/// Error returned when a transaction may use more gas than the per-transaction cap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("transaction gas limit {gas_limit} exceeds the per-transaction maximum of {maximum}")]
+pub struct TxGasAboveMaximum {
+    /// Gas limit of the transaction.
+    pub gas_limit: u64,
+    /// Most gas a transaction may use in the block.
+    pub maximum: u64,
+}
+
+/// Settings for capping the gas of a single transaction from a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxTxGasSettings {
+    max_bps: u16,
+    activation_height: u64,
+}
+
+impl MaxTxGasSettings {
+    /// Creates a new settings object capping transactions at `max_bps` basis points of the
+    /// block gas limit.
+    pub const fn new(max_bps: u16, activation_height: u64) -> Self {
+        Self {
+            max_bps,
+            activation_height,
+        }
+    }
+
+    /// Returns the cap in basis points of the block gas limit.
+    pub const fn max_bps(&self) -> u16 {
+        self.max_bps
+    }
+
+    /// Returns the activation height for the cap.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if the cap applies at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+
+    /// Returns the most gas a transaction may use in a block with `block_gas_limit`.
+    pub const fn max_tx_gas(&self, block_gas_limit: u64) -> u64 {
+        (block_gas_limit as u128 * self.max_bps as u128 / MAX_TX_GAS_BPS as u128) as u64
+    }
+
+    /// Checks a transaction with `gas_limit` in the given block.
+    pub const fn check(
+        &self,
+        gas_limit: u64,
+        block_gas_limit: u64,
+        block_number: u64,
+    ) -> Result<(), TxGasAboveMaximum> {
+        let maximum = self.max_tx_gas(block_gas_limit);
+        if !self.is_active(block_number) || gas_limit <= maximum {
+            return Ok(());
+        }
+        Err(TxGasAboveMaximum { gas_limit, maximum })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caps_transactions_at_share_of_block_after_activation() {
+        let settings = MaxTxGasSettings::new(5_000, 10);
+        assert_eq!(settings.max_tx_gas(30_000_000), 15_000_000);
+
+        assert!(settings.check(20_000_000, 30_000_000, 9).is_ok());
+        assert!(settings.check(15_000_000, 30_000_000, 10).is_ok());
+        assert_eq!(
+            settings.check(15_000_001, 30_000_000, 10),
+            Err(TxGasAboveMaximum {
+                gas_limit: 15_000_001,
+                maximum: 15_000_000,
+            })
+        );
+        assert_eq!(
+            MaxTxGasSettings::new(MAX_TX_GAS_BPS, 0).max_tx_gas(u64::MAX),
+            u64::MAX
+        );
+    }
+}
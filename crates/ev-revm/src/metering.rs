@@ -0,0 +1,186 @@
+//! Multi-dimensional gas metering.
+//!
+//! Every transaction is measured along three dimensions:
+//! - compute: regular (non-state) gas used by execution;
+//! - state growth: bytes added to the state by new accounts, new storage slots and deployed
+//!   code;
+//! - DA: bytes of the EIP-2718 encoded transaction that have to be posted to the DA layer.
+//!
+//! Transactions are still charged their one-dimensional gas. The measured dimensions are
+//! reported alongside receipts, and [`DimensionPricing`] describes how they would be priced
+//! into a single gas amount once a chain opts in at an activation height.
+
+use reth_revm::revm::state::EvmState;
+
+/// State bytes attributed to a newly created account (key, nonce, balance, storage root and
+/// code hash).
+pub const NEW_ACCOUNT_STATE_BYTES: u64 = 104;
+
+/// State bytes attributed to a storage slot written for the first time (key and value).
+pub const NEW_SLOT_STATE_BYTES: u64 = 64;
+
+/// Resource usage of a single transaction, split by dimension.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasDimensions {
+    /// Regular gas used by execution.
+    pub compute: u64,
+    /// Bytes added to the state.
+    pub state_growth: u64,
+    /// Encoded transaction bytes posted to the DA layer.
+    pub da: u64,
+}
+
+impl GasDimensions {
+    /// Measures an executed transaction from the regular gas it used, the state it changed and
+    /// its encoded length.
+    pub fn measure(regular_gas_used: u64, state: &EvmState, encoded_len: u64) -> Self {
+        let mut state_growth = 0u64;
+        for account in state.values() {
+            if account.is_loaded_as_not_existing() && !account.is_empty() {
+                state_growth += NEW_ACCOUNT_STATE_BYTES;
+            }
+            if account.is_created() {
+                let code_len = account.info.code.as_ref().map_or(0, |code| code.len());
+                state_growth += code_len as u64;
+            }
+            let new_slots = account
+                .storage
+                .values()
+                .filter(|slot| slot.original_value().is_zero() && !slot.present_value().is_zero())
+                .count() as u64;
+            state_growth += new_slots * NEW_SLOT_STATE_BYTES;
+        }
+        Self {
+            compute: regular_gas_used,
+            state_growth,
+            da: encoded_len,
+        }
+    }
+}
+
+/// Per-dimension prices used to fold [`GasDimensions`] into a single gas amount from a specific
+/// block height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionPricing {
+    activation_height: u64,
+    state_growth_gas_per_byte: u64,
+    da_gas_per_byte: u64,
+}
+
+impl DimensionPricing {
+    /// Creates pricing that charges `state_growth_gas_per_byte` and `da_gas_per_byte` on top of
+    /// compute gas from `activation_height`.
+    pub const fn new(
+        activation_height: u64,
+        state_growth_gas_per_byte: u64,
+        da_gas_per_byte: u64,
+    ) -> Self {
+        Self {
+            activation_height,
+            state_growth_gas_per_byte,
+            da_gas_per_byte,
+        }
+    }
+
+    /// Returns the activation height for the pricing.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns the gas charged per byte of state growth.
+    pub const fn state_growth_gas_per_byte(&self) -> u64 {
+        self.state_growth_gas_per_byte
+    }
+
+    /// Returns the gas charged per DA byte.
+    pub const fn da_gas_per_byte(&self) -> u64 {
+        self.da_gas_per_byte
+    }
+
+    /// Returns true if the pricing applies at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+
+    /// Returns the gas `dimensions` amount to when each dimension is priced separately.
+    pub const fn priced_gas(&self, dimensions: &GasDimensions) -> u64 {
+        dimensions
+            .compute
+            .saturating_add(
+                dimensions
+                    .state_growth
+                    .saturating_mul(self.state_growth_gas_per_byte),
+            )
+            .saturating_add(dimensions.da.saturating_mul(self.da_gas_per_byte))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+    use reth_revm::revm::state::{Account, AccountInfo, Bytecode, EvmStorageSlot};
+
+    #[test]
+    fn measures_new_accounts_slots_and_code() {
+        let mut state = EvmState::default();
+
+        let mut created = Account::from(AccountInfo {
+            balance: U256::from(1),
+            code: Some(Bytecode::new_raw(vec![0x60, 0x00, 0x60, 0x00].into())),
+            ..Default::default()
+        });
+        created.mark_touch();
+        created.mark_created();
+        created.mark_loaded_as_not_existing();
+        created.storage.insert(
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(7), 0),
+        );
+        state.insert(
+            address!("0x00000000000000000000000000000000000000c1"),
+            created,
+        );
+
+        let mut existing = Account::from(AccountInfo {
+            balance: U256::from(5),
+            ..Default::default()
+        });
+        existing.mark_touch();
+        // Overwriting and clearing existing slots does not grow the state.
+        existing.storage.insert(
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::from(1), U256::from(2), 0),
+        );
+        existing.storage.insert(
+            U256::from(2),
+            EvmStorageSlot::new_changed(U256::from(1), U256::ZERO, 0),
+        );
+        state.insert(
+            address!("0x00000000000000000000000000000000000000e1"),
+            existing,
+        );
+
+        let dimensions = GasDimensions::measure(50_000, &state, 120);
+        assert_eq!(dimensions.compute, 50_000);
+        assert_eq!(
+            dimensions.state_growth,
+            NEW_ACCOUNT_STATE_BYTES + NEW_SLOT_STATE_BYTES + 4
+        );
+        assert_eq!(dimensions.da, 120);
+    }
+
+    #[test]
+    fn pricing_folds_dimensions() {
+        let pricing = DimensionPricing::new(10, 2, 16);
+        assert!(!pricing.is_active(9));
+        assert!(pricing.is_active(10));
+
+        let dimensions = GasDimensions {
+            compute: 21_000,
+            state_growth: 64,
+            da: 110,
+        };
+        assert_eq!(pricing.priced_gas(&dimensions), 21_000 + 128 + 1_760);
+    }
+}
@@ -0,0 +1,153 @@
+//! Minimum effective gas price policy.
+//!
+//! The same check runs in the txpool and the payload builder, so a transaction paying less
+//! than the chain minimum is turned away whether it was submitted over RPC or arrived in the
+//! Engine API transactions list. Transactions from exempt senders, and transactions whose
+//! calls all target free-gas addresses, are accepted at any price.
+
+use alloy_primitives::{Address, TxKind};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Error returned when a transaction pays less than the minimum gas price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("effective gas price {effective_gas_price} is below the minimum of {minimum}")]
+pub struct GasPriceBelowMinimum {
+    /// Gas price the transaction would pay.
+    pub effective_gas_price: u128,
+    /// Minimum gas price of the chain.
+    pub minimum: u128,
+}
+
+/// Settings for the minimum effective gas price and its exemptions.
+#[derive(Debug, Clone)]
+pub struct MinGasPriceSettings {
+    min_gas_price: u128,
+    exempt_senders: Arc<[Address]>,
+    free_gas_targets: Arc<[Address]>,
+    activation_height: u64,
+}
+
+impl MinGasPriceSettings {
+    /// Creates a new settings object.
+    pub fn new(
+        min_gas_price: u128,
+        exempt_senders: Vec<Address>,
+        free_gas_targets: Vec<Address>,
+        activation_height: u64,
+    ) -> Self {
+        let mut exempt_senders = exempt_senders;
+        exempt_senders.sort_unstable();
+        exempt_senders.dedup();
+        let mut free_gas_targets = free_gas_targets;
+        free_gas_targets.sort_unstable();
+        free_gas_targets.dedup();
+        Self {
+            min_gas_price,
+            exempt_senders: Arc::from(exempt_senders),
+            free_gas_targets: Arc::from(free_gas_targets),
+            activation_height,
+        }
+    }
+
+    /// Returns the minimum effective gas price.
+    pub const fn min_gas_price(&self) -> u128 {
+        self.min_gas_price
+    }
+
+    /// Returns the senders exempt from the minimum.
+    pub fn exempt_senders(&self) -> &[Address] {
+        &self.exempt_senders
+    }
+
+    /// Returns the call targets whose transactions are exempt from the minimum.
+    pub fn free_gas_targets(&self) -> &[Address] {
+        &self.free_gas_targets
+    }
+
+    /// Returns the activation height for the policy.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if the policy applies at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+
+    /// Returns true if a transaction from `sender` calling `targets` is exempt. A transaction
+    /// is only exempt through its targets if every call goes to a free-gas address; contract
+    /// creations never are.
+    pub fn is_exempt(&self, sender: Address, targets: impl IntoIterator<Item = TxKind>) -> bool {
+        if self.exempt_senders.binary_search(&sender).is_ok() {
+            return true;
+        }
+        let mut targets = targets.into_iter().peekable();
+        targets.peek().is_some()
+            && targets.all(|target| match target {
+                TxKind::Call(to) => self.free_gas_targets.binary_search(&to).is_ok(),
+                TxKind::Create => false,
+            })
+    }
+
+    /// Checks a transaction paying `effective_gas_price` in the given block.
+    pub fn check(
+        &self,
+        sender: Address,
+        targets: impl IntoIterator<Item = TxKind>,
+        effective_gas_price: u128,
+        block_number: u64,
+    ) -> Result<(), GasPriceBelowMinimum> {
+        if !self.is_active(block_number)
+            || effective_gas_price >= self.min_gas_price
+            || self.is_exempt(sender, targets)
+        {
+            return Ok(());
+        }
+        Err(GasPriceBelowMinimum {
+            effective_gas_price,
+            minimum: self.min_gas_price,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_underpriced_transactions_after_activation() {
+        let sender = Address::repeat_byte(0x01);
+        let target = TxKind::Call(Address::repeat_byte(0x02));
+        let settings = MinGasPriceSettings::new(1_000, Vec::new(), Vec::new(), 10);
+
+        assert!(settings.check(sender, [target], 0, 9).is_ok());
+        assert!(settings.check(sender, [target], 1_000, 10).is_ok());
+        assert_eq!(
+            settings.check(sender, [target], 999, 10),
+            Err(GasPriceBelowMinimum {
+                effective_gas_price: 999,
+                minimum: 1_000,
+            })
+        );
+    }
+
+    #[test]
+    fn exempts_senders_and_free_gas_targets() {
+        let system = Address::repeat_byte(0x01);
+        let free = Address::repeat_byte(0x02);
+        let other = Address::repeat_byte(0x03);
+        let settings = MinGasPriceSettings::new(1_000, vec![system], vec![free], 0);
+
+        assert!(settings.check(system, [TxKind::Create], 0, 0).is_ok());
+        assert!(settings
+            .check(other, [TxKind::Call(free), TxKind::Call(free)], 0, 0)
+            .is_ok());
+        // Every call has to target a free-gas address.
+        assert!(settings
+            .check(other, [TxKind::Call(free), TxKind::Call(other)], 0, 0)
+            .is_err());
+        assert!(settings.check(other, [TxKind::Create], 0, 0).is_err());
+        assert!(settings.check(other, [], 0, 0).is_err());
+    }
+}
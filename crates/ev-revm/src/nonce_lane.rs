@@ -0,0 +1,34 @@
+//! Nonce lanes of EvNode transactions.
+//!
+//! An EvNode transaction with a non-zero `nonce_key` is ordered by the sequence of that lane
+//! instead of its executor's account nonce, so an executor can keep several independent
+//! streams of transactions in flight. The handler checks and bumps the nonce of the lane's
+//! account, see [`ev_precompiles::nonce_manager::nonce_lane_address`], and leaves the
+//! executor's nonce untouched; the nonce manager precompile reports lane sequences to
+//! contracts.
+//!
+//! Lane transactions change the transaction encoding, so they are rejected before the
+//! activation height.
+
+/// Settings for accepting nonce lanes from a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct NonceLaneSettings {
+    activation_height: u64,
+}
+
+impl NonceLaneSettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self { activation_height }
+    }
+
+    /// Returns the activation height for nonce lanes.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if nonce lanes are accepted at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+}
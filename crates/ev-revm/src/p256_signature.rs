@@ -0,0 +1,31 @@
+//! P256 executor signatures of EvNode transactions.
+//!
+//! An executor without a secp256k1 key, such as a passkey user, signs with a P256 key or a
+//! WebAuthn authenticator; see [`ev_primitives::P256Signature`]. The signature is verified
+//! when the sender is recovered, like a secp256k1 executor signature.
+//!
+//! P256 signatures change the transaction encoding, so they are rejected before the
+//! activation height.
+
+/// Settings for accepting P256 executor signatures from a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct P256SignatureSettings {
+    activation_height: u64,
+}
+
+impl P256SignatureSettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self { activation_height }
+    }
+
+    /// Returns the activation height for P256 executor signatures.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if P256 executor signatures are accepted at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+}
@@ -0,0 +1,32 @@
+//! Session-key EvNode transactions.
+//!
+//! A transaction carrying an [`ev_primitives::SignedSessionAuthorization`] is recovered to the
+//! primary account that signed the authorization. Before executing it, the handler checks the
+//! authorization's scope against the including block: chain, expiry timestamp, call targets
+//! and total value transferred.
+//!
+//! Session authorizations change the transaction encoding, so they are rejected before the
+//! activation height.
+
+/// Settings for accepting session-key transactions from a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct SessionKeySettings {
+    activation_height: u64,
+}
+
+impl SessionKeySettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self { activation_height }
+    }
+
+    /// Returns the activation height for session keys.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if session-key transactions are accepted at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+}
@@ -0,0 +1,91 @@
+//! Allowlist of the fee payers that may sponsor transactions.
+//!
+//! Once active, a sponsored transaction whose fee payer is not on the list is rejected by the
+//! handler, so only approved paymasters can cover other accounts' gas. Contract fee payers are
+//! checked like signing sponsors. The txpool applies the same check on submission.
+
+use alloy_primitives::Address;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Error returned when a transaction is sponsored by a fee payer outside the allowlist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("fee payer {sponsor} is not allowed to sponsor transactions")]
+pub struct SponsorNotAllowed {
+    /// Fee payer of the transaction.
+    pub sponsor: Address,
+}
+
+/// Settings for restricting sponsorship to allowlisted fee payers from a specific block height.
+#[derive(Debug, Clone)]
+pub struct SponsorAllowlistSettings {
+    allowlist: Arc<[Address]>,
+    activation_height: u64,
+}
+
+impl SponsorAllowlistSettings {
+    /// Creates a new settings object allowing only the fee payers in `allowlist`.
+    pub fn new(mut allowlist: Vec<Address>, activation_height: u64) -> Self {
+        allowlist.sort_unstable();
+        Self {
+            allowlist: Arc::from(allowlist),
+            activation_height,
+        }
+    }
+
+    /// Returns the allowlisted fee payers.
+    pub fn allowlist(&self) -> &[Address] {
+        &self.allowlist
+    }
+
+    /// Returns the activation height for the allowlist.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if the allowlist applies at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+
+    /// Returns true if `sponsor` is on the allowlist.
+    pub fn is_allowed(&self, sponsor: Address) -> bool {
+        self.allowlist.binary_search(&sponsor).is_ok()
+    }
+
+    /// Checks the fee payer of a transaction in the given block; unsponsored transactions
+    /// always pass.
+    pub fn check(
+        &self,
+        sponsor: Option<Address>,
+        block_number: u64,
+    ) -> Result<(), SponsorNotAllowed> {
+        match sponsor {
+            Some(sponsor) if self.is_active(block_number) && !self.is_allowed(sponsor) => {
+                Err(SponsorNotAllowed { sponsor })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn only_allowlisted_sponsors_pass_after_activation() {
+        let approved = address!("0x00000000000000000000000000000000000000aa");
+        let other = address!("0x00000000000000000000000000000000000000bb");
+        let settings = SponsorAllowlistSettings::new(vec![approved], 10);
+
+        assert!(settings.check(Some(other), 9).is_ok());
+        assert!(settings.check(Some(approved), 10).is_ok());
+        assert!(settings.check(None, 10).is_ok());
+        assert_eq!(
+            settings.check(Some(other), 10),
+            Err(SponsorNotAllowed { sponsor: other })
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Sponsors recovered by the txpool, handed to execution so they are not recovered again.
+//!
+//! The txpool recovers the sponsor of every ECDSA sponsorship it admits, under each sponsor
+//! hash scheme it had to check, and keeps them on the pooled transaction. The payload builder
+//! takes them along with the transaction when building its EVM environment (see
+//! [`crate::EvTxEnv::from_recovered_tx_with_sponsors`]) instead of recovering the sponsor anew,
+//! so the builder and the pool see the same addresses. The transaction hash commits to both
+//! signatures, so they can only ever hold what recovery would return; transactions that did
+//! not come from the pool are recovered at execution.
+
+use alloy_primitives::Address;
+use ev_primitives::SponsorHashScheme;
+
+/// Sponsor recovered from a fee-payer signature under each sponsor hash scheme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecoveredSponsors {
+    executor: Option<Address>,
+    fee_payer: Option<Address>,
+    eip712: Option<Address>,
+}
+
+impl RecoveredSponsors {
+    /// Returns the sponsor recovered under `scheme`, if it was.
+    pub const fn get(&self, scheme: SponsorHashScheme) -> Option<Address> {
+        match scheme {
+            SponsorHashScheme::Executor => self.executor,
+            SponsorHashScheme::FeePayer => self.fee_payer,
+            SponsorHashScheme::Eip712 => self.eip712,
+        }
+    }
+
+    /// Records the sponsor recovered under `scheme`.
+    pub const fn insert(&mut self, scheme: SponsorHashScheme, sponsor: Address) {
+        match scheme {
+            SponsorHashScheme::Executor => self.executor = Some(sponsor),
+            SponsorHashScheme::FeePayer => self.fee_payer = Some(sponsor),
+            SponsorHashScheme::Eip712 => self.eip712 = Some(sponsor),
+        }
+    }
+
+    /// Returns whether no sponsor was recorded.
+    pub const fn is_empty(&self) -> bool {
+        self.executor.is_none() && self.fee_payer.is_none() && self.eip712.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_sponsor_per_scheme() {
+        let sponsor = Address::repeat_byte(0xaa);
+        let legacy = Address::repeat_byte(0xbb);
+
+        let mut sponsors = RecoveredSponsors::default();
+        assert!(sponsors.is_empty());
+        sponsors.insert(SponsorHashScheme::Executor, sponsor);
+        sponsors.insert(SponsorHashScheme::FeePayer, legacy);
+
+        assert!(!sponsors.is_empty());
+        assert_eq!(sponsors.get(SponsorHashScheme::Executor), Some(sponsor));
+        assert_eq!(sponsors.get(SponsorHashScheme::FeePayer), Some(legacy));
+        assert_eq!(sponsors.get(SponsorHashScheme::Eip712), None);
+    }
+}
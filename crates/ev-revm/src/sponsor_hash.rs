@@ -0,0 +1,124 @@
+//! Migration between sponsor signing hash schemes.
+//!
+//! Sponsorships signed before an upgrade keep working while clients move to the new
+//! [`SponsorHashScheme`]: before the transition height only the legacy scheme is accepted,
+//! during the transition window both are, and from the activation height only the new one.
+//!
+//! EIP-712 sponsorships are not migrated to: once activated, they are accepted alongside
+//! whichever raw scheme is in force.
+
+use ev_primitives::SponsorHashScheme;
+
+/// Settings for migrating the sponsor signing hash from one scheme to the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SponsorHashMigration {
+    legacy: SponsorHashScheme,
+    transition_height: u64,
+    activation_height: u64,
+}
+
+impl SponsorHashMigration {
+    /// Creates a migration away from `legacy`. Both schemes are accepted in
+    /// `[transition_height, activation_height)`.
+    pub const fn new(
+        legacy: SponsorHashScheme,
+        transition_height: u64,
+        activation_height: u64,
+    ) -> Self {
+        Self {
+            legacy,
+            transition_height,
+            activation_height,
+        }
+    }
+
+    /// Returns the scheme being migrated away from.
+    pub const fn legacy(&self) -> SponsorHashScheme {
+        self.legacy
+    }
+
+    /// Returns the scheme being migrated to.
+    pub const fn target(&self) -> SponsorHashScheme {
+        match self.legacy {
+            SponsorHashScheme::FeePayer => SponsorHashScheme::Executor,
+            SponsorHashScheme::Executor | SponsorHashScheme::Eip712 => SponsorHashScheme::FeePayer,
+        }
+    }
+
+    /// Returns the height from which both schemes are accepted.
+    pub const fn transition_height(&self) -> u64 {
+        self.transition_height
+    }
+
+    /// Returns the height from which only the target scheme is accepted.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns the preferred scheme at the given block and, inside the transition window, the
+    /// scheme accepted as a fallback.
+    pub const fn schemes_at(
+        &self,
+        block_number: u64,
+    ) -> (SponsorHashScheme, Option<SponsorHashScheme>) {
+        if block_number < self.transition_height {
+            (self.legacy, None)
+        } else if block_number < self.activation_height {
+            (self.target(), Some(self.legacy))
+        } else {
+            (self.target(), None)
+        }
+    }
+}
+
+/// Settings for accepting EIP-712 sponsorships from a specific block height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip712SponsorSettings {
+    activation_height: u64,
+}
+
+impl Eip712SponsorSettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self { activation_height }
+    }
+
+    /// Returns the activation height for EIP-712 sponsorships.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if EIP-712 sponsorships are accepted at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_both_schemes_during_transition() {
+        let migration = SponsorHashMigration::new(SponsorHashScheme::FeePayer, 10, 20);
+        assert_eq!(migration.schemes_at(9), (SponsorHashScheme::FeePayer, None));
+        assert_eq!(
+            migration.schemes_at(10),
+            (
+                SponsorHashScheme::Executor,
+                Some(SponsorHashScheme::FeePayer)
+            )
+        );
+        assert_eq!(
+            migration.schemes_at(19),
+            (
+                SponsorHashScheme::Executor,
+                Some(SponsorHashScheme::FeePayer)
+            )
+        );
+        assert_eq!(
+            migration.schemes_at(20),
+            (SponsorHashScheme::Executor, None)
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Account state expiry prototype.
+//!
+//! Once active, the handler records the block in which each transaction's sender, fee payer
+//! and call target were last active in the registry precompile's storage, and rejects
+//! transactions involving archived accounts. Archiving and reviving happen through the
+//! registry precompile (see [`ev_precompiles::expiry`]).
+
+use alloy_primitives::{Address, Bytes, U256};
+use ev_precompiles::expiry::{archive_slot, last_active_slot, STATE_EXPIRY_PRECOMPILE_ADDR};
+use reth_revm::revm::{
+    context_interface::{journaled_state::account::JournaledAccountTr, JournalTr},
+    state::{Bytecode, EvmState},
+};
+use thiserror::Error;
+
+/// Settings for account state expiry at a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct StateExpirySettings {
+    activation_height: u64,
+    expiry_period: u64,
+}
+
+impl StateExpirySettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64, expiry_period: u64) -> Self {
+        Self {
+            activation_height,
+            expiry_period,
+        }
+    }
+
+    /// Returns the activation height for state expiry.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns the number of blocks without activity after which an account can be archived.
+    pub const fn expiry_period(&self) -> u64 {
+        self.expiry_period
+    }
+
+    /// Returns true if state expiry is enforced at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+}
+
+/// Error raised when a transaction involves an archived account.
+#[derive(Debug, Error)]
+pub enum StateExpiryError<DbError> {
+    /// The account has been archived and must be revived first.
+    #[error("account {0} is archived; revive it before use")]
+    Archived(Address),
+    /// Database error while reading the registry.
+    #[error("failed to access state expiry registry: {0}")]
+    Database(DbError),
+}
+
+/// Rejects the transaction if any of `accounts` is archived, then records them as active in
+/// `block_number`.
+pub(crate) fn check_and_record_activity<J>(
+    journal: &mut J,
+    accounts: &[Address],
+    block_number: u64,
+) -> Result<(), StateExpiryError<<J::Database as reth_revm::Database>::Error>>
+where
+    J: JournalTr<State = EvmState>,
+{
+    ensure_registry_account(journal).map_err(StateExpiryError::Database)?;
+    for account in accounts {
+        let archived = journal
+            .sload(STATE_EXPIRY_PRECOMPILE_ADDR, archive_slot(*account))
+            .map_err(StateExpiryError::Database)?
+            .data;
+        if !archived.is_zero() {
+            return Err(StateExpiryError::Archived(*account));
+        }
+    }
+    for account in accounts {
+        journal
+            .sstore(
+                STATE_EXPIRY_PRECOMPILE_ADDR,
+                last_active_slot(*account),
+                U256::from(block_number),
+            )
+            .map_err(StateExpiryError::Database)?;
+    }
+    journal.touch_account(STATE_EXPIRY_PRECOMPILE_ADDR);
+    Ok(())
+}
+
+/// Loads the registry account, giving it code and a nonce on first use so its storage
+/// survives state clearing, as the precompile itself does.
+fn ensure_registry_account<J>(
+    journal: &mut J,
+) -> Result<(), <J::Database as reth_revm::Database>::Error>
+where
+    J: JournalTr<State = EvmState>,
+{
+    let missing = journal
+        .load_account(STATE_EXPIRY_PRECOMPILE_ADDR)?
+        .data
+        .is_loaded_as_not_existing();
+    if missing {
+        journal.set_code(
+            STATE_EXPIRY_PRECOMPILE_ADDR,
+            Bytecode::new_raw(Bytes::from_static(&[0xFE])),
+        );
+        journal
+            .load_account_mut(STATE_EXPIRY_PRECOMPILE_ADDR)?
+            .data
+            .set_nonce(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Fee policy for system transactions.
+//!
+//! Transactions sent by a configured system sender (e.g. the deposit inbox relayer or a
+//! scheduler account) are not meant to compete for blockspace, so their gas is settled by a
+//! chain-level policy instead of the regular fee rules.
+
+use alloy_primitives::Address;
+use std::sync::Arc;
+
+/// How gas fees of system transactions are settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemFeeMode {
+    /// No fees are charged and nothing is credited to the beneficiary or the base-fee sink.
+    Waived,
+    /// Fees are charged to the given account instead of the sender and paid out normally.
+    Charged(Address),
+    /// The fee payer is charged as usual, but nothing is credited to the beneficiary or the
+    /// base-fee sink.
+    Burned,
+}
+
+/// Settings identifying system transactions and the fee policy applied to them.
+#[derive(Debug, Clone)]
+pub struct SystemFeeSettings {
+    senders: Arc<[Address]>,
+    mode: SystemFeeMode,
+    activation_height: u64,
+}
+
+impl SystemFeeSettings {
+    /// Creates a new settings object for the given system senders.
+    pub fn new(senders: Vec<Address>, mode: SystemFeeMode, activation_height: u64) -> Self {
+        let mut senders = senders;
+        senders.sort_unstable();
+        senders.dedup();
+        Self {
+            senders: Arc::from(senders),
+            mode,
+            activation_height,
+        }
+    }
+
+    /// Returns the addresses whose transactions are system transactions.
+    pub fn senders(&self) -> &[Address] {
+        &self.senders
+    }
+
+    /// Returns the configured fee policy.
+    pub const fn mode(&self) -> SystemFeeMode {
+        self.mode
+    }
+
+    /// Returns the activation height for the policy.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns the fee policy for a transaction from `caller` in the given block, or `None`
+    /// when regular fee rules apply.
+    pub fn mode_for(&self, caller: Address, block_number: u64) -> Option<SystemFeeMode> {
+        (block_number >= self.activation_height && self.senders.binary_search(&caller).is_ok())
+            .then_some(self.mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_to_senders_after_activation() {
+        let sender = Address::repeat_byte(0x01);
+        let settings = SystemFeeSettings::new(vec![sender, sender], SystemFeeMode::Burned, 10);
+        assert_eq!(settings.senders(), &[sender]);
+        assert_eq!(settings.mode_for(sender, 9), None);
+        assert_eq!(settings.mode_for(sender, 10), Some(SystemFeeMode::Burned));
+        assert_eq!(settings.mode_for(Address::repeat_byte(0x02), 10), None);
+    }
+}
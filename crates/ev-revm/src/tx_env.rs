@@ -1,6 +1,9 @@
+use crate::sponsor_handoff::RecoveredSponsors;
 use alloy_evm::{FromRecoveredTx, FromTxWithEncoded, TransactionEnvMut};
-use alloy_primitives::{Address, Bytes, U256};
-use ev_primitives::{Call, EvTxEnvelope};
+use alloy_primitives::{aliases::U192, Address, Bytes, Signature, U256};
+use ev_primitives::{
+    Call, EvTxEnvelope, RevertMode, SessionAuthorization, SponsorHashScheme, ValidityWindow,
+};
 use reth_revm::revm::{
     context::TxEnv,
     context_interface::{
@@ -40,7 +43,23 @@ pub struct EvTxEnv {
     /// Stored because trait methods don't return `Result`, so validation errors
     /// must be deferred until the handler can process them.
     sponsor_signature_invalid: bool,
+    /// Whether the sponsor is a contract fee payer approved through `IGasAllowance`.
+    sponsor_is_contract: bool,
+    /// ECDSA sponsor signature with its signing hash under each scheme, kept for recovering
+    /// the sponsor under another scheme during a sponsor hash migration.
+    sponsor_signature: Option<SponsorSignature>,
+    /// Most the sponsor pays in gas fees; the caller pays any excess.
+    max_sponsor_fee: Option<u128>,
     calls: Vec<Call>,
+    revert_mode: RevertMode,
+    /// Scope of the session key that signed the transaction for the caller, if any.
+    session: Option<SessionAuthorization>,
+    /// Block timestamps the transaction may be included between, if bounded.
+    validity_window: Option<ValidityWindow>,
+    /// Nonce lane the transaction's nonce is checked against, if not the caller's nonce.
+    nonce_lane: Option<U192>,
+    /// Whether the executor signed with a P256 key instead of secp256k1.
+    p256_signed: bool,
     batch_value: U256,
     is_evnode: bool,
 }
@@ -53,7 +72,15 @@ impl EvTxEnv {
             inner,
             sponsor: None,
             sponsor_signature_invalid: false,
+            sponsor_is_contract: false,
+            sponsor_signature: None,
+            max_sponsor_fee: None,
             calls: Vec::new(),
+            revert_mode: RevertMode::Atomic,
+            session: None,
+            validity_window: None,
+            nonce_lane: None,
+            p256_signed: false,
             is_evnode: false,
         }
     }
@@ -78,16 +105,94 @@ impl EvTxEnv {
         self.sponsor_signature_invalid
     }
 
+    /// Returns whether the sponsor is a contract fee payer.
+    pub const fn sponsor_is_contract(&self) -> bool {
+        self.sponsor_is_contract
+    }
+
     /// Returns the batch calls for this transaction.
     pub fn calls(&self) -> &[Call] {
         &self.calls
     }
 
+    /// Returns how the batch handles a failing call.
+    pub const fn revert_mode(&self) -> RevertMode {
+        self.revert_mode
+    }
+
+    /// Returns the scope of the session key that signed the transaction, if any.
+    pub const fn session(&self) -> Option<&SessionAuthorization> {
+        self.session.as_ref()
+    }
+
+    /// Returns the block timestamps the transaction may be included between, if bounded.
+    pub const fn validity_window(&self) -> Option<ValidityWindow> {
+        self.validity_window
+    }
+
+    /// Returns the nonce lane of the transaction, or `None` for the caller's account nonce.
+    pub const fn nonce_lane(&self) -> Option<U192> {
+        self.nonce_lane
+    }
+
+    /// Returns whether the executor signed with a P256 key.
+    pub const fn p256_signed(&self) -> bool {
+        self.p256_signed
+    }
+
     /// Returns the total value across all calls.
     pub const fn batch_value(&self) -> U256 {
         self.batch_value
     }
 
+    /// Builds an unsigned `EvNode` environment executing `calls` in order, e.g. to simulate a
+    /// batch before it is signed. Caller, gas and fee fields are taken from `inner`.
+    pub fn with_calls(mut inner: TxEnv, calls: Vec<Call>) -> Self {
+        let batch_value = calls
+            .iter()
+            .fold(U256::ZERO, |acc, call| acc.saturating_add(call.value));
+        if let Some(first) = calls.first() {
+            inner.kind = first.to;
+            inner.data = first.input.clone();
+        }
+        inner.value = batch_value;
+        let mut env = Self::new(inner);
+        env.calls = calls;
+        env.batch_value = batch_value;
+        env.is_evnode = true;
+        env
+    }
+
+    /// Sets how the batch handles a failing call.
+    pub const fn with_revert_mode(mut self, revert_mode: RevertMode) -> Self {
+        self.revert_mode = revert_mode;
+        self
+    }
+
+    /// Sets the scope of the session key the batch is signed with.
+    pub fn with_session(mut self, session: Option<SessionAuthorization>) -> Self {
+        self.session = session;
+        self
+    }
+
+    /// Sets the block timestamps the batch may be included between.
+    pub const fn with_validity_window(mut self, validity_window: Option<ValidityWindow>) -> Self {
+        self.validity_window = validity_window;
+        self
+    }
+
+    /// Sets the nonce lane the batch's nonce is checked against.
+    pub const fn with_nonce_lane(mut self, nonce_lane: Option<U192>) -> Self {
+        self.nonce_lane = nonce_lane;
+        self
+    }
+
+    /// Marks the batch as signed by its executor with a P256 key.
+    pub const fn with_p256_signed(mut self, p256_signed: bool) -> Self {
+        self.p256_signed = p256_signed;
+        self
+    }
+
     /// Updates the inner `TxEnv` to represent a single call from the batch.
     pub fn set_call(&mut self, call: &Call) {
         self.inner.kind = call.to;
@@ -96,6 +201,17 @@ impl EvTxEnv {
     }
 }
 
+/// Sponsor signature of an `EvNode` transaction and the hashes it may have been signed over.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SponsorSignature {
+    signature: Signature,
+    executor_hash: B256,
+    fee_payer_hash: B256,
+    eip712_hash: B256,
+    /// Sponsors the txpool already recovered from `signature`.
+    recovered: RecoveredSponsors,
+}
+
 impl From<TxEnv> for EvTxEnv {
     fn from(inner: TxEnv) -> Self {
         Self {
@@ -103,7 +219,15 @@ impl From<TxEnv> for EvTxEnv {
             inner,
             sponsor: None,
             sponsor_signature_invalid: false,
+            sponsor_is_contract: false,
+            sponsor_signature: None,
+            max_sponsor_fee: None,
             calls: Vec::new(),
+            revert_mode: RevertMode::Atomic,
+            session: None,
+            validity_window: None,
+            nonce_lane: None,
+            p256_signed: false,
             is_evnode: false,
         }
     }
@@ -208,18 +332,48 @@ impl alloy_evm::ToTxEnv<Self> for EvTxEnv {
 
 impl FromRecoveredTx<EvTxEnvelope> for EvTxEnv {
     fn from_recovered_tx(tx: &EvTxEnvelope, sender: Address) -> Self {
+        Self::from_recovered_tx_with_sponsors(tx, sender, RecoveredSponsors::default())
+    }
+}
+
+impl EvTxEnv {
+    /// Builds the environment of `tx` signed by `sender`, reusing `recovered`, the sponsors the
+    /// txpool already recovered from its fee-payer signature, instead of recovering them again.
+    pub fn from_recovered_tx_with_sponsors(
+        tx: &EvTxEnvelope,
+        sender: Address,
+        recovered: RecoveredSponsors,
+    ) -> Self {
         match tx {
             EvTxEnvelope::Ethereum(inner) => Self::new(TxEnv::from_recovered_tx(inner, sender)),
             EvTxEnvelope::EvNode(ev) => {
-                let (sponsor, sponsor_signature_invalid) =
-                    if let Some(signature) = ev.tx().fee_payer_signature.as_ref() {
-                        match ev.tx().recover_sponsor(sender, signature) {
-                            Ok(sponsor) => (Some(sponsor), false),
-                            Err(_) => (None, true),
+                let (sponsor, sponsor_signature_invalid, sponsor_is_contract) =
+                    if let Some(fee_payer) = ev.tx().contract_fee_payer() {
+                        (Some(fee_payer), false, true)
+                    } else if let Some(signature) = ev.tx().fee_payer_signature.as_ref() {
+                        match recovered
+                            .get(SponsorHashScheme::Executor)
+                            .map_or_else(|| ev.tx().recover_sponsor(sender, signature), Ok)
+                        {
+                            Ok(sponsor) => (Some(sponsor), false, false),
+                            Err(_) => (None, true, false),
                         }
                     } else {
-                        (None, false)
+                        (None, false, false)
                     };
+                let sponsor_signature = ev
+                    .tx()
+                    .fee_payer_signature
+                    .filter(|_| sponsor.is_some() && !sponsor_is_contract)
+                    .map(|signature| SponsorSignature {
+                        signature,
+                        executor_hash: ev.tx().sponsor_signing_hash(sender),
+                        fee_payer_hash: ev
+                            .tx()
+                            .sponsor_signing_hash_with(SponsorHashScheme::FeePayer, sender),
+                        eip712_hash: ev.tx().sponsor_signing_hash_eip712(sender),
+                        recovered,
+                    });
                 let calls = ev.tx().calls.clone();
                 let batch_value = calls
                     .iter()
@@ -252,7 +406,19 @@ impl FromRecoveredTx<EvTxEnvelope> for EvTxEnv {
                     inner: env,
                     sponsor,
                     sponsor_signature_invalid,
+                    sponsor_is_contract,
+                    sponsor_signature,
+                    max_sponsor_fee: sponsor.and(ev.tx().max_sponsor_fee),
                     calls,
+                    revert_mode: ev.tx().revert_mode,
+                    session: ev
+                        .tx()
+                        .session
+                        .as_ref()
+                        .map(|session| session.authorization.clone()),
+                    validity_window: ev.tx().validity_window(),
+                    nonce_lane: ev.tx().nonce_lane(),
+                    p256_signed: ev.tx().p256_signature.is_some(),
                     batch_value,
                     is_evnode: true,
                 }
@@ -291,12 +457,30 @@ pub trait SponsorPayerTx {
     fn sponsor(&self) -> Option<Address>;
     /// Returns whether the sponsor signature was invalid.
     fn sponsor_signature_invalid(&self) -> bool;
+    /// Returns whether the sponsor is a contract that must approve the gas via `IGasAllowance`.
+    fn sponsor_is_contract(&self) -> bool;
+    /// Recovers the sponsor of an ECDSA sponsorship under the given sponsor hash scheme.
+    fn sponsor_for_scheme(&self, scheme: SponsorHashScheme) -> Option<Address>;
+    /// Replaces the sponsor, e.g. with one recovered under another sponsor hash scheme.
+    fn set_sponsor(&mut self, sponsor: Address);
+    /// Returns the most the sponsor pays in gas fees, if capped.
+    fn max_sponsor_fee(&self) -> Option<u128>;
 }
 
 /// Batch-call helpers for EV transactions.
 pub trait BatchCallsTx {
     /// Returns the batch calls, if present.
     fn batch_calls(&self) -> Option<&[Call]>;
+    /// Returns how the batch handles a failing call.
+    fn batch_revert_mode(&self) -> RevertMode;
+    /// Returns the scope of the session key that signed the batch, if any.
+    fn batch_session(&self) -> Option<&SessionAuthorization>;
+    /// Returns the block timestamps the batch may be included between, if bounded.
+    fn batch_validity_window(&self) -> Option<ValidityWindow>;
+    /// Returns the nonce lane of the batch, or `None` for the caller's account nonce.
+    fn batch_nonce_lane(&self) -> Option<U192>;
+    /// Returns whether the executor signed the batch with a P256 key.
+    fn batch_p256_signed(&self) -> bool;
     /// Returns the total value across all calls.
     fn batch_total_value(&self) -> U256;
     /// Sets the inner `TxEnv` to the given call.
@@ -311,6 +495,31 @@ impl SponsorPayerTx for EvTxEnv {
     fn sponsor_signature_invalid(&self) -> bool {
         self.sponsor_signature_invalid
     }
+
+    fn sponsor_is_contract(&self) -> bool {
+        self.sponsor_is_contract
+    }
+
+    fn sponsor_for_scheme(&self, scheme: SponsorHashScheme) -> Option<Address> {
+        let sponsor = self.sponsor_signature.as_ref()?;
+        if let Some(recovered) = sponsor.recovered.get(scheme) {
+            return Some(recovered);
+        }
+        let hash = match scheme {
+            SponsorHashScheme::Executor => &sponsor.executor_hash,
+            SponsorHashScheme::FeePayer => &sponsor.fee_payer_hash,
+            SponsorHashScheme::Eip712 => &sponsor.eip712_hash,
+        };
+        sponsor.signature.recover_address_from_prehash(hash).ok()
+    }
+
+    fn set_sponsor(&mut self, sponsor: Address) {
+        self.sponsor = Some(sponsor);
+    }
+
+    fn max_sponsor_fee(&self) -> Option<u128> {
+        self.max_sponsor_fee
+    }
 }
 
 impl BatchCallsTx for EvTxEnv {
@@ -322,6 +531,26 @@ impl BatchCallsTx for EvTxEnv {
         }
     }
 
+    fn batch_revert_mode(&self) -> RevertMode {
+        self.revert_mode
+    }
+
+    fn batch_session(&self) -> Option<&SessionAuthorization> {
+        self.session.as_ref()
+    }
+
+    fn batch_validity_window(&self) -> Option<ValidityWindow> {
+        self.validity_window
+    }
+
+    fn batch_nonce_lane(&self) -> Option<U192> {
+        self.nonce_lane
+    }
+
+    fn batch_p256_signed(&self) -> bool {
+        self.p256_signed
+    }
+
     fn batch_total_value(&self) -> U256 {
         self.batch_value
     }
@@ -339,6 +568,20 @@ impl SponsorPayerTx for TxEnv {
     fn sponsor_signature_invalid(&self) -> bool {
         false
     }
+
+    fn sponsor_is_contract(&self) -> bool {
+        false
+    }
+
+    fn sponsor_for_scheme(&self, _scheme: SponsorHashScheme) -> Option<Address> {
+        None
+    }
+
+    fn set_sponsor(&mut self, _sponsor: Address) {}
+
+    fn max_sponsor_fee(&self) -> Option<u128> {
+        None
+    }
 }
 
 impl BatchCallsTx for TxEnv {
@@ -346,6 +589,26 @@ impl BatchCallsTx for TxEnv {
         None
     }
 
+    fn batch_revert_mode(&self) -> RevertMode {
+        RevertMode::Atomic
+    }
+
+    fn batch_session(&self) -> Option<&SessionAuthorization> {
+        None
+    }
+
+    fn batch_validity_window(&self) -> Option<ValidityWindow> {
+        None
+    }
+
+    fn batch_nonce_lane(&self) -> Option<U192> {
+        None
+    }
+
+    fn batch_p256_signed(&self) -> bool {
+        false
+    }
+
     fn batch_total_value(&self) -> U256 {
         self.value
     }
@@ -359,30 +622,17 @@ impl BatchCallsTx for TxEnv {
 
 #[cfg(test)]
 mod tests {
-    use super::{BatchCallsTx, EvTxEnv};
+    use super::{BatchCallsTx, EvTxEnv, SponsorPayerTx, SponsorSignature};
+    use crate::sponsor_handoff::RecoveredSponsors;
     use alloy_evm::FromRecoveredTx;
-    use alloy_primitives::{Address, Bytes, Signature, TxKind, U256};
-    use ev_primitives::{Call, EvNodeSignedTx, EvNodeTransaction, EvTxEnvelope};
+    use alloy_primitives::{aliases::U192, Address, Bytes, Signature, TxKind, B256, U256};
+    use ev_primitives::{
+        contract_fee_payer_signature, Call, EvNodeSignedTx, EvNodeTransaction, EvTxEnvelope,
+        RevertMode, SponsorHashScheme,
+    };
     use reth_revm::revm::context::TxEnv;
 
     impl EvTxEnv {
-        /// Test helper to build an `EvTxEnv` with batch calls pre-populated.
-        pub fn with_calls(mut inner: TxEnv, calls: Vec<Call>) -> Self {
-            let batch_value = calls
-                .iter()
-                .fold(U256::ZERO, |acc, call| acc.saturating_add(call.value));
-            if let Some(first) = calls.first() {
-                inner.kind = first.to;
-                inner.data = first.input.clone();
-            }
-            inner.value = batch_value;
-            let mut env = Self::new(inner);
-            env.calls = calls;
-            env.batch_value = batch_value;
-            env.is_evnode = true;
-            env
-        }
-
         /// Test helper to build an `EvTxEnv` with batch calls and a sponsor.
         pub fn with_calls_and_sponsor(
             mut inner: TxEnv,
@@ -405,12 +655,61 @@ mod tests {
             env.is_evnode = true;
             env
         }
+
+        /// Test helper to build an `EvTxEnv` with batch calls and a contract fee payer.
+        pub fn with_calls_and_contract_sponsor(
+            inner: TxEnv,
+            calls: Vec<Call>,
+            fee_payer: Address,
+        ) -> Self {
+            let mut env = Self::with_calls_and_sponsor(inner, calls, fee_payer);
+            env.sponsor_is_contract = true;
+            env
+        }
+
+        /// Test helper to build an `EvTxEnv` with batch calls and a sponsor paying at most
+        /// `max_sponsor_fee`.
+        pub fn with_calls_and_capped_sponsor(
+            inner: TxEnv,
+            calls: Vec<Call>,
+            sponsor: Address,
+            max_sponsor_fee: u128,
+        ) -> Self {
+            let mut env = Self::with_calls_and_sponsor(inner, calls, sponsor);
+            env.max_sponsor_fee = Some(max_sponsor_fee);
+            env
+        }
+
+        /// Test helper to build an `EvTxEnv` with batch calls and an ECDSA sponsor signature
+        /// over the given hash for each sponsor hash scheme.
+        pub fn with_calls_and_sponsor_signature(
+            inner: TxEnv,
+            calls: Vec<Call>,
+            signature: Signature,
+            executor_hash: B256,
+            fee_payer_hash: B256,
+            eip712_hash: B256,
+        ) -> Self {
+            let sponsor = signature
+                .recover_address_from_prehash(&executor_hash)
+                .expect("recoverable sponsor signature");
+            let mut env = Self::with_calls_and_sponsor(inner, calls, sponsor);
+            env.sponsor_signature = Some(SponsorSignature {
+                signature,
+                executor_hash,
+                fee_payer_hash,
+                eip712_hash,
+                recovered: RecoveredSponsors::default(),
+            });
+            env
+        }
     }
 
     fn sample_evnode_tx() -> EvNodeTransaction {
         EvNodeTransaction {
             chain_id: 1,
             nonce: 1,
+            nonce_key: U192::ZERO,
             max_priority_fee_per_gas: 1,
             max_fee_per_gas: 2,
             gas_limit: 21_000,
@@ -418,9 +717,16 @@ mod tests {
                 to: TxKind::Call(Address::ZERO),
                 value: U256::ZERO,
                 input: Bytes::default(),
+                gas_limit: None,
             }],
             access_list: Default::default(),
+            revert_mode: RevertMode::Atomic,
+            valid_after: None,
+            valid_until: None,
+            session: None,
             fee_payer_signature: None,
+            max_sponsor_fee: None,
+            p256_signature: None,
         }
     }
 
@@ -451,6 +757,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_recovered_tx_with_sponsors_uses_recovered_sponsor() {
+        let executor = Address::from([0x55; 20]);
+        let sponsor = Address::from([0x66; 20]);
+        let mut tx = sample_evnode_tx();
+        tx.fee_payer_signature = Some(signature_with_parity(27, 2, 2));
+        let signed = EvNodeSignedTx::new_unhashed(tx, signature_with_parity(27, 1, 1));
+
+        let mut recovered = RecoveredSponsors::default();
+        recovered.insert(SponsorHashScheme::Executor, sponsor);
+        let env = EvTxEnv::from_recovered_tx_with_sponsors(
+            &EvTxEnvelope::EvNode(signed),
+            executor,
+            recovered,
+        );
+
+        assert_eq!(env.sponsor(), Some(sponsor));
+        assert_eq!(
+            env.sponsor_for_scheme(SponsorHashScheme::Executor),
+            Some(sponsor)
+        );
+        assert!(!env.sponsor_signature_invalid());
+    }
+
+    #[test]
+    fn from_recovered_tx_detects_contract_fee_payer() {
+        let executor = Address::from([0x11; 20]);
+        let fee_payer = Address::from([0x44; 20]);
+        let mut tx = sample_evnode_tx();
+        tx.fee_payer_signature = Some(contract_fee_payer_signature(fee_payer));
+
+        let signed = EvNodeSignedTx::new_unhashed(tx, signature_with_parity(27, 1, 1));
+        let env = EvTxEnv::from_recovered_tx(&EvTxEnvelope::EvNode(signed), executor);
+
+        assert_eq!(env.sponsor(), Some(fee_payer));
+        assert!(env.sponsor_is_contract());
+        assert!(!env.sponsor_signature_invalid());
+    }
+
     #[test]
     fn from_recovered_tx_allows_missing_sponsor_signature() {
         let executor = Address::from([0x22; 20]);
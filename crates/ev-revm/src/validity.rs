@@ -0,0 +1,31 @@
+//! Validity windows of EvNode transactions.
+//!
+//! A transaction may carry an [`ev_primitives::ValidityWindow`] of block timestamps, bound by
+//! both the executor and sponsor signatures. The handler rejects it in any block outside
+//! the window, so a sponsor's liability ends when the window closes.
+//!
+//! Validity windows change the transaction encoding, so they are rejected before the
+//! activation height.
+
+/// Settings for accepting transactions with a validity window from a specific block height.
+#[derive(Debug, Clone, Copy)]
+pub struct ValidityWindowSettings {
+    activation_height: u64,
+}
+
+impl ValidityWindowSettings {
+    /// Creates a new settings object.
+    pub const fn new(activation_height: u64) -> Self {
+        Self { activation_height }
+    }
+
+    /// Returns the activation height for validity windows.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns true if validity windows are accepted at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+}
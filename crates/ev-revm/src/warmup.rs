@@ -0,0 +1,47 @@
+//! EIP-2929 warm-up of Evolve system addresses.
+//!
+//! Once active, the handler adds every Evolve precompile installed for the block (mint,
+//! deposit inbox, system channels, state expiry registry), plus any additional configured
+//! addresses, to the warm set at the start of each transaction. Calls through wrapper
+//! contracts then pay the warm access cost regardless of whether the transaction's access list
+//! named them.
+
+use alloy_primitives::Address;
+
+/// Settings for warming Evolve system addresses from a specific block height.
+#[derive(Debug, Clone, Default)]
+pub struct PrecompileWarmupSettings {
+    activation_height: u64,
+    addresses: Vec<Address>,
+}
+
+impl PrecompileWarmupSettings {
+    /// Creates a new settings object warming the installed Evolve precompiles.
+    pub const fn new(activation_height: u64) -> Self {
+        Self {
+            activation_height,
+            addresses: Vec::new(),
+        }
+    }
+
+    /// Also warms `addresses`, such as system contracts that are not precompiles.
+    pub fn with_addresses(mut self, addresses: Vec<Address>) -> Self {
+        self.addresses = addresses;
+        self
+    }
+
+    /// Returns the activation height for the warm-up.
+    pub const fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns the additional addresses warmed alongside the precompiles.
+    pub fn addresses(&self) -> &[Address] {
+        &self.addresses
+    }
+
+    /// Returns true if the warm-up applies at the given block number.
+    pub const fn is_active(&self, block_number: u64) -> bool {
+        block_number >= self.activation_height
+    }
+}
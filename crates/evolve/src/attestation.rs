@@ -0,0 +1,181 @@
+use alloy_primitives::{keccak256, Address, Bytes, Signature, B256};
+use alloy_rpc_types_engine::PayloadId;
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, sync::Mutex};
+
+/// Maximum number of build attestations retained before the oldest are evicted.
+pub const DEFAULT_MAX_BUILD_ATTESTATIONS: usize = 1024;
+
+/// Domain separator hashed into every attestation digest, so the operator key cannot be
+/// tricked into signing a commitment that is also valid elsewhere.
+pub const BUILD_ATTESTATION_DOMAIN: &[u8] = b"evolve-build-attestation-v1";
+
+/// Commitment to a produced payload, signed by the operator key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildAttestation {
+    /// Payload id the block was built for.
+    pub payload_id: PayloadId,
+    /// Number of the built block.
+    pub block_number: u64,
+    /// Hash of the built block.
+    pub block_hash: B256,
+    /// Transactions root of the built block.
+    pub transactions_root: B256,
+    /// Hash of the payload attributes the block was built from.
+    pub attributes_hash: B256,
+    /// Address of the operator key.
+    pub signer: Address,
+    /// 65-byte signature over [`BuildAttestation::digest`].
+    pub signature: Bytes,
+}
+
+impl BuildAttestation {
+    /// Returns the digest signed by the operator:
+    /// `keccak256(domain ‖ payload_id ‖ block_hash ‖ transactions_root ‖ attributes_hash)`.
+    pub fn digest(
+        payload_id: PayloadId,
+        block_hash: B256,
+        transactions_root: B256,
+        attributes_hash: B256,
+    ) -> B256 {
+        let mut preimage = Vec::with_capacity(BUILD_ATTESTATION_DOMAIN.len() + 8 + 3 * 32);
+        preimage.extend_from_slice(BUILD_ATTESTATION_DOMAIN);
+        preimage.extend_from_slice(payload_id.0.as_slice());
+        preimage.extend_from_slice(block_hash.as_slice());
+        preimage.extend_from_slice(transactions_root.as_slice());
+        preimage.extend_from_slice(attributes_hash.as_slice());
+        keccak256(preimage)
+    }
+
+    /// Recovers the address that signed this attestation, if the signature is well formed.
+    pub fn recover_signer(&self) -> Option<Address> {
+        let signature = Signature::try_from(self.signature.as_ref()).ok()?;
+        let digest = Self::digest(
+            self.payload_id,
+            self.block_hash,
+            self.transactions_root,
+            self.attributes_hash,
+        );
+        signature.recover_address_from_prehash(&digest).ok()
+    }
+}
+
+/// Bounded store of build attestations keyed by block hash, written by the payload builder
+/// and read by the `evolve_getBuildAttestation` RPC. Only nodes configured with an operator
+/// key record any.
+#[derive(Debug)]
+pub struct BuildAttestationStore {
+    attestations: Mutex<VecDeque<BuildAttestation>>,
+    capacity: usize,
+}
+
+impl BuildAttestationStore {
+    /// Creates a store that retains at most `capacity` attestations.
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            attestations: Mutex::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Signs an attestation for a built block with the operator key `signer` and stores it.
+    pub fn attest(
+        &self,
+        signer: &PrivateKeySigner,
+        payload_id: PayloadId,
+        block_number: u64,
+        block_hash: B256,
+        transactions_root: B256,
+        attributes_hash: B256,
+    ) -> Option<BuildAttestation> {
+        let digest =
+            BuildAttestation::digest(payload_id, block_hash, transactions_root, attributes_hash);
+        let signature = signer.sign_hash_sync(&digest).ok()?;
+        let attestation = BuildAttestation {
+            payload_id,
+            block_number,
+            block_hash,
+            transactions_root,
+            attributes_hash,
+            signer: signer.address(),
+            signature: Bytes::copy_from_slice(&signature.as_bytes()),
+        };
+
+        if self.capacity > 0 {
+            let mut attestations = self.attestations.lock().unwrap_or_else(|e| e.into_inner());
+            attestations.retain(|existing| existing.block_hash != block_hash);
+            while attestations.len() >= self.capacity {
+                attestations.pop_front();
+            }
+            attestations.push_back(attestation.clone());
+        }
+        Some(attestation)
+    }
+
+    /// Returns the attestation recorded for `block_hash`, if still retained.
+    pub fn get(&self, block_hash: &B256) -> Option<BuildAttestation> {
+        let attestations = self.attestations.lock().unwrap_or_else(|e| e.into_inner());
+        attestations
+            .iter()
+            .find(|attestation| attestation.block_hash == *block_hash)
+            .cloned()
+    }
+}
+
+impl Default for BuildAttestationStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUILD_ATTESTATIONS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attestation_recovers_operator() {
+        let store = BuildAttestationStore::new(4);
+        let signer = PrivateKeySigner::random();
+        let operator = signer.address();
+        let block_hash = B256::repeat_byte(0xbb);
+        let attestation = store
+            .attest(
+                &signer,
+                PayloadId::new([1; 8]),
+                7,
+                block_hash,
+                B256::repeat_byte(0xcc),
+                B256::repeat_byte(0xdd),
+            )
+            .unwrap();
+        assert_eq!(attestation.signer, operator);
+        assert_eq!(attestation.recover_signer(), Some(operator));
+        assert_eq!(store.get(&block_hash), Some(attestation.clone()));
+
+        // Any change to the commitment invalidates the signature.
+        let mut tampered = attestation;
+        tampered.transactions_root = B256::repeat_byte(0xee);
+        assert_ne!(tampered.recover_signer(), Some(operator));
+    }
+
+    #[test]
+    fn store_evicts_oldest_attestation() {
+        let store = BuildAttestationStore::new(2);
+        let signer = PrivateKeySigner::random();
+        for byte in 1..=3u8 {
+            store.attest(
+                &signer,
+                PayloadId::new([byte; 8]),
+                byte.into(),
+                B256::repeat_byte(byte),
+                B256::ZERO,
+                B256::ZERO,
+            );
+        }
+        assert!(store.get(&B256::repeat_byte(1)).is_none());
+        assert!(store.get(&B256::repeat_byte(3)).is_some());
+    }
+}
@@ -0,0 +1,306 @@
+use crate::timestamp::TimestampAdjustment;
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::PayloadId;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, sync::Mutex};
+
+/// Maximum number of build reports retained before the oldest are evicted.
+pub const DEFAULT_MAX_BUILD_REPORTS: usize = 64;
+
+/// Why a candidate transaction was included in or excluded from a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxInclusionReason {
+    /// The transaction was executed and included in the block.
+    Included,
+    /// The transaction's max fee per gas is below the block base fee, or its effective gas
+    /// price is below the chain minimum.
+    FeeTooLow,
+    /// The transaction's gas limit exceeds the gas remaining in the block.
+    GasBudget,
+    /// The transaction depends on a prior nonce that is not present.
+    Dependency,
+    /// The raw transaction bytes could not be decoded.
+    DecodeFailed,
+    /// The transaction belongs to a bundle that could not be included as a whole.
+    BundleReverted,
+    /// The payload already held the `maxTxCount` transactions it was limited to.
+    TxCountLimit,
+    /// The transaction type is disabled on this chain.
+    TxTypeDisabled,
+    /// The EvNode transaction carries more calls than the chain allows.
+    TooManyCalls,
+    /// Execution failed for any other reason; see the decision detail.
+    Error,
+}
+
+/// Channel through which an included transaction reached the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxSource {
+    /// Sent by ev-node in the payload attributes.
+    Attributes,
+    /// Pulled from the local txpool (dev mode).
+    Pool,
+    /// Placed by a node-side channel: the maintenance or encrypted lane.
+    System,
+}
+
+impl TxSource {
+    /// All sources, in reporting order.
+    pub const ALL: [Self; 3] = [Self::Attributes, Self::Pool, Self::System];
+
+    /// Label used for the source in metrics.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Attributes => "attributes",
+            Self::Pool => "pool",
+            Self::System => "system",
+        }
+    }
+}
+
+/// Number of transactions included in a block from each [`TxSource`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InclusionMix {
+    /// Transactions taken from the payload attributes.
+    pub attributes: u64,
+    /// Transactions taken from the txpool.
+    pub pool: u64,
+    /// Transactions placed by system channels.
+    pub system: u64,
+}
+
+impl InclusionMix {
+    /// Counts one included transaction from `source`.
+    pub fn record(&mut self, source: TxSource) {
+        *self.count_mut(source) += 1;
+    }
+
+    /// Returns the number of transactions included from `source`.
+    pub const fn count(&self, source: TxSource) -> u64 {
+        match source {
+            TxSource::Attributes => self.attributes,
+            TxSource::Pool => self.pool,
+            TxSource::System => self.system,
+        }
+    }
+
+    /// Total number of included transactions.
+    pub const fn total(&self) -> u64 {
+        self.attributes + self.pool + self.system
+    }
+
+    fn count_mut(&mut self, source: TxSource) -> &mut u64 {
+        match source {
+            TxSource::Attributes => &mut self.attributes,
+            TxSource::Pool => &mut self.pool,
+            TxSource::System => &mut self.system,
+        }
+    }
+}
+
+/// Inclusion decision for a single candidate transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxInclusionDecision {
+    /// Position of the transaction in the candidate list.
+    pub index: usize,
+    /// Transaction hash, if the transaction could be decoded.
+    pub hash: Option<B256>,
+    /// Outcome for this transaction.
+    pub reason: TxInclusionReason,
+    /// Gas used when the transaction was included.
+    pub gas_used: Option<u64>,
+    /// Human-readable error detail for excluded transactions.
+    pub detail: Option<String>,
+}
+
+/// Per-transaction inclusion trace for a single payload build.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildReport {
+    /// Number of the block being built.
+    pub block_number: u64,
+    /// Gas limit the payload was built against.
+    pub gas_limit: u64,
+    /// Total gas used by included transactions.
+    pub gas_used: u64,
+    /// Decisions for every candidate transaction, in candidate order.
+    pub transactions: Vec<TxInclusionDecision>,
+    /// Timestamp policy applied because the requested timestamp did not advance past the
+    /// parent's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<TimestampAdjustment>,
+    /// Included transactions by the channel that supplied them.
+    #[serde(default)]
+    pub sources: InclusionMix,
+}
+
+impl BuildReport {
+    /// Creates an empty report for the given block.
+    pub const fn new(block_number: u64, gas_limit: u64) -> Self {
+        Self {
+            block_number,
+            gas_limit,
+            gas_used: 0,
+            transactions: Vec::new(),
+            timestamp: None,
+            sources: InclusionMix::default(),
+        }
+    }
+
+    /// Records an included transaction.
+    pub fn record_included(&mut self, index: usize, hash: B256, gas_used: u64) {
+        self.gas_used += gas_used;
+        self.transactions.push(TxInclusionDecision {
+            index,
+            hash: Some(hash),
+            reason: TxInclusionReason::Included,
+            gas_used: Some(gas_used),
+            detail: None,
+        });
+    }
+
+    /// Records an excluded transaction along with why it was dropped.
+    pub fn record_excluded(
+        &mut self,
+        index: usize,
+        hash: Option<B256>,
+        reason: TxInclusionReason,
+        detail: impl Into<String>,
+    ) {
+        self.transactions.push(TxInclusionDecision {
+            index,
+            hash,
+            reason,
+            gas_used: None,
+            detail: Some(detail.into()),
+        });
+    }
+
+    /// Number of transactions that made it into the block.
+    pub fn included_count(&self) -> usize {
+        self.transactions
+            .iter()
+            .filter(|decision| decision.reason == TxInclusionReason::Included)
+            .count()
+    }
+}
+
+/// Bounded, insertion-ordered store of build reports keyed by payload id, written by the
+/// payload builder and read by the `evolve_getBuildReport` RPC.
+#[derive(Debug)]
+pub struct BuildReportStore {
+    reports: Mutex<VecDeque<(PayloadId, BuildReport)>>,
+    capacity: usize,
+}
+
+impl BuildReportStore {
+    /// Creates a store that retains at most `capacity` reports.
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            reports: Mutex::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Stores a report, replacing any earlier report for the same payload and
+    /// evicting the oldest entry once the store is full.
+    pub fn insert(&self, payload_id: PayloadId, report: BuildReport) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut reports = self.reports.lock().unwrap_or_else(|e| e.into_inner());
+        reports.retain(|(id, _)| *id != payload_id);
+        while reports.len() >= self.capacity {
+            reports.pop_front();
+        }
+        reports.push_back((payload_id, report));
+    }
+
+    /// Returns the report recorded for `payload_id`, if still retained.
+    pub fn get(&self, payload_id: &PayloadId) -> Option<BuildReport> {
+        let reports = self.reports.lock().unwrap_or_else(|e| e.into_inner());
+        reports
+            .iter()
+            .find(|(id, _)| id == payload_id)
+            .map(|(_, report)| report.clone())
+    }
+}
+
+impl Default for BuildReportStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUILD_REPORTS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload_id(n: u8) -> PayloadId {
+        PayloadId::new([n; 8])
+    }
+
+    #[test]
+    fn store_evicts_oldest_report() {
+        let store = BuildReportStore::new(2);
+        store.insert(payload_id(1), BuildReport::new(1, 30_000_000));
+        store.insert(payload_id(2), BuildReport::new(2, 30_000_000));
+        store.insert(payload_id(3), BuildReport::new(3, 30_000_000));
+
+        assert!(store.get(&payload_id(1)).is_none());
+        assert_eq!(store.get(&payload_id(2)).unwrap().block_number, 2);
+        assert_eq!(store.get(&payload_id(3)).unwrap().block_number, 3);
+    }
+
+    #[test]
+    fn store_replaces_report_for_same_payload() {
+        let store = BuildReportStore::new(2);
+        store.insert(payload_id(1), BuildReport::new(1, 30_000_000));
+        let mut rebuilt = BuildReport::new(1, 30_000_000);
+        rebuilt.record_included(0, B256::ZERO, 21_000);
+        store.insert(payload_id(1), rebuilt);
+        store.insert(payload_id(2), BuildReport::new(2, 30_000_000));
+
+        let report = store.get(&payload_id(1)).expect("report retained");
+        assert_eq!(report.included_count(), 1);
+        assert_eq!(report.gas_used, 21_000);
+    }
+
+    #[test]
+    fn report_serializes_camel_case_reasons() {
+        let mut report = BuildReport::new(7, 30_000_000);
+        report.record_excluded(
+            0,
+            None,
+            TxInclusionReason::FeeTooLow,
+            "gas price less than basefee",
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["blockNumber"], 7);
+        assert_eq!(json["transactions"][0]["reason"], "feeTooLow");
+        assert_eq!(json["transactions"][0]["hash"], serde_json::Value::Null);
+        assert_eq!(json["sources"]["pool"], 0);
+    }
+
+    #[test]
+    fn inclusion_mix_counts_per_source() {
+        let mut mix = InclusionMix::default();
+        mix.record(TxSource::Attributes);
+        mix.record(TxSource::Attributes);
+        mix.record(TxSource::System);
+
+        assert_eq!(mix.count(TxSource::Attributes), 2);
+        assert_eq!(mix.count(TxSource::Pool), 0);
+        assert_eq!(mix.count(TxSource::System), 1);
+        assert_eq!(mix.total(), 3);
+        assert_eq!(
+            serde_json::to_value(mix).unwrap(),
+            serde_json::json!({"attributes": 2, "pool": 0, "system": 1})
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use alloy_consensus::Transaction;
+use alloy_eips::Decodable2718;
+use alloy_primitives::{keccak256, Bytes, B256};
+use ev_primitives::TransactionSigned;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, ops::Range, sync::Mutex};
+
+/// Maximum number of transactions in a single bundle.
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 16;
+
+/// Maximum number of bundles accepted for a single target block.
+pub const MAX_BUNDLES_PER_BLOCK: usize = 64;
+
+/// Maximum number of blocks past the head a bundle may target.
+pub const MAX_BUNDLE_TARGET_DISTANCE: u64 = 64;
+
+/// Maximum number of bundles held across all target blocks.
+pub const MAX_PENDING_BUNDLES: usize = 1024;
+
+/// Errors returned when a bundle is rejected.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundleError {
+    /// The bundle has no transactions.
+    #[error("bundle is empty")]
+    Empty,
+    /// The bundle has more transactions than allowed.
+    #[error("bundle has {0} transactions, maximum is {MAX_BUNDLE_TRANSACTIONS}")]
+    TooManyTransactions(usize),
+    /// A transaction could not be decoded.
+    #[error("bundle transaction {index} could not be decoded: {reason}")]
+    InvalidTransaction {
+        /// Position of the transaction in the bundle.
+        index: usize,
+        /// Decoding error.
+        reason: String,
+    },
+    /// The same transaction appears twice in the bundle.
+    #[error("bundle contains transaction {0} more than once")]
+    DuplicateTransaction(B256),
+    /// The target block has already been built.
+    #[error("target block {target} is not after the current head {head}")]
+    TargetBlockPassed {
+        /// Requested target block.
+        target: u64,
+        /// Last block built by this node.
+        head: u64,
+    },
+    /// The target block is too far past the head.
+    #[error(
+        "target block {target} is more than {MAX_BUNDLE_TARGET_DISTANCE} blocks past the \
+         current head {head}"
+    )]
+    TargetBlockTooFar {
+        /// Requested target block.
+        target: u64,
+        /// Last block built by this node.
+        head: u64,
+    },
+    /// A transaction is already part of another bundle for the same block.
+    #[error("transaction {0} is already bundled for the target block")]
+    AlreadyBundled(B256),
+    /// The target block has reached its bundle limit.
+    #[error("target block already has {MAX_BUNDLES_PER_BLOCK} bundles")]
+    TooManyBundles,
+    /// The pool holds its maximum number of bundles.
+    #[error("bundle pool already holds {MAX_PENDING_BUNDLES} bundles")]
+    PoolFull,
+}
+
+/// A group of transactions that must be included contiguously in `target_block`, or not at
+/// all.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    /// Keccak hash of the concatenated transaction hashes.
+    pub hash: B256,
+    /// Block the bundle must land in.
+    pub target_block: u64,
+    /// EIP-2718 encoded transactions, in execution order.
+    pub transactions: Vec<Bytes>,
+    /// Hashes of `transactions`.
+    pub tx_hashes: Vec<B256>,
+    /// Sum of the transactions' gas limits.
+    pub gas_limit: u64,
+}
+
+impl Bundle {
+    /// Decodes and validates the transactions of a bundle for `target_block`.
+    pub fn new(transactions: Vec<Bytes>, target_block: u64) -> Result<Self, BundleError> {
+        if transactions.is_empty() {
+            return Err(BundleError::Empty);
+        }
+        if transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(BundleError::TooManyTransactions(transactions.len()));
+        }
+
+        let mut tx_hashes: Vec<B256> = Vec::with_capacity(transactions.len());
+        let mut gas_limit = 0u64;
+        for (index, raw) in transactions.iter().enumerate() {
+            let tx = TransactionSigned::decode_2718_exact(raw.as_ref()).map_err(|err| {
+                BundleError::InvalidTransaction {
+                    index,
+                    reason: err.to_string(),
+                }
+            })?;
+            gas_limit = gas_limit.saturating_add(tx.gas_limit());
+            let hash = keccak256(raw);
+            if tx_hashes.contains(&hash) {
+                return Err(BundleError::DuplicateTransaction(hash));
+            }
+            tx_hashes.push(hash);
+        }
+
+        Ok(Self {
+            hash: bundle_hash(&tx_hashes),
+            target_block,
+            transactions,
+            tx_hashes,
+            gas_limit,
+        })
+    }
+
+    /// Returns the positions of the bundle in `candidates` if all of its transactions appear
+    /// contiguously and in order.
+    pub fn locate(&self, candidates: &[B256]) -> Option<Range<usize>> {
+        let start = candidates
+            .iter()
+            .position(|hash| *hash == self.tx_hashes[0])?;
+        let range = start..start + self.tx_hashes.len();
+        (candidates.get(range.clone())? == self.tx_hashes.as_slice()).then_some(range)
+    }
+}
+
+/// Computes the bundle hash as the keccak hash of the concatenated transaction hashes.
+pub fn bundle_hash(tx_hashes: &[B256]) -> B256 {
+    let mut buf = Vec::with_capacity(tx_hashes.len() * 32);
+    for hash in tx_hashes {
+        buf.extend_from_slice(hash.as_slice());
+    }
+    keccak256(buf)
+}
+
+#[derive(Debug, Default)]
+struct BundlePoolInner {
+    /// Last block built by this node.
+    head: u64,
+    /// Pending bundles keyed by target block, in arrival order.
+    bundles: BTreeMap<u64, Vec<Bundle>>,
+}
+
+/// Pending bundles awaiting their target block, written by `evolve_sendBundle` and read by
+/// the txpool RPC and the payload builder.
+#[derive(Debug, Default)]
+pub struct BundlePool {
+    inner: Mutex<BundlePoolInner>,
+}
+
+impl BundlePool {
+    /// Creates an empty pool for a chain whose last block is `head`.
+    pub fn new(head: u64) -> Self {
+        Self {
+            inner: Mutex::new(BundlePoolInner {
+                head,
+                bundles: BTreeMap::new(),
+            }),
+        }
+    }
+
+    /// Adds a bundle, returning its hash.
+    pub fn insert(&self, bundle: Bundle) -> Result<B256, BundleError> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if bundle.target_block <= inner.head {
+            return Err(BundleError::TargetBlockPassed {
+                target: bundle.target_block,
+                head: inner.head,
+            });
+        }
+        if bundle.target_block - inner.head > MAX_BUNDLE_TARGET_DISTANCE {
+            return Err(BundleError::TargetBlockTooFar {
+                target: bundle.target_block,
+                head: inner.head,
+            });
+        }
+
+        let total: usize = inner.bundles.values().map(Vec::len).sum();
+        let pending = inner.bundles.entry(bundle.target_block).or_default();
+        if let Some(existing) = pending.iter().find(|b| b.hash == bundle.hash) {
+            return Ok(existing.hash);
+        }
+        if pending.len() >= MAX_BUNDLES_PER_BLOCK {
+            return Err(BundleError::TooManyBundles);
+        }
+        if total >= MAX_PENDING_BUNDLES {
+            return Err(BundleError::PoolFull);
+        }
+        if let Some(hash) = bundle
+            .tx_hashes
+            .iter()
+            .find(|hash| pending.iter().any(|b| b.tx_hashes.contains(hash)))
+        {
+            return Err(BundleError::AlreadyBundled(*hash));
+        }
+
+        let hash = bundle.hash;
+        pending.push(bundle);
+        Ok(hash)
+    }
+
+    /// Bundles targeting `block_number`, in arrival order.
+    pub fn bundles_for_block(&self, block_number: u64) -> Vec<Bundle> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner
+            .bundles
+            .get(&block_number)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Bundles targeting the block after the last one built.
+    pub fn bundles_for_next_block(&self) -> Vec<Bundle> {
+        let next = self.inner.lock().unwrap_or_else(|e| e.into_inner()).head + 1;
+        self.bundles_for_block(next)
+    }
+
+    /// Records that `block_number` was built and drops bundles targeting earlier blocks.
+    ///
+    /// Bundles for `block_number` itself are kept so the payload can be rebuilt.
+    pub fn advance_to(&self, block_number: u64) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if block_number <= inner.head {
+            return;
+        }
+        inner.head = block_number;
+        inner.bundles = inner.bundles.split_off(&block_number);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle(tx_hashes: Vec<B256>, target_block: u64) -> Bundle {
+        Bundle {
+            hash: bundle_hash(&tx_hashes),
+            target_block,
+            transactions: vec![Bytes::new(); tx_hashes.len()],
+            gas_limit: 21_000 * tx_hashes.len() as u64,
+            tx_hashes,
+        }
+    }
+
+    #[test]
+    fn rejects_empty_bundle() {
+        assert_eq!(Bundle::new(vec![], 1), Err(BundleError::Empty));
+    }
+
+    #[test]
+    fn locate_requires_contiguous_order() {
+        let (a, b, c) = (
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            B256::repeat_byte(3),
+        );
+        let bundle = bundle(vec![a, b], 1);
+
+        assert_eq!(bundle.locate(&[c, a, b]), Some(1..3));
+        assert_eq!(bundle.locate(&[a, c, b]), None);
+        assert_eq!(bundle.locate(&[b, a]), None);
+        assert_eq!(bundle.locate(&[c, a]), None);
+    }
+
+    #[test]
+    fn pool_rejects_overlapping_and_stale_bundles() {
+        let pool = BundlePool::default();
+        let (a, b) = (B256::repeat_byte(1), B256::repeat_byte(2));
+
+        pool.insert(bundle(vec![a, b], 5)).unwrap();
+        assert_eq!(
+            pool.insert(bundle(vec![b], 5)),
+            Err(BundleError::AlreadyBundled(b))
+        );
+        assert!(pool.insert(bundle(vec![b], 6)).is_ok());
+
+        pool.advance_to(5);
+        assert_eq!(pool.bundles_for_block(5).len(), 1);
+        assert_eq!(pool.bundles_for_next_block().len(), 1);
+        assert_eq!(
+            pool.insert(bundle(vec![a], 5)),
+            Err(BundleError::TargetBlockPassed { target: 5, head: 5 })
+        );
+
+        pool.advance_to(6);
+        assert!(pool.bundles_for_block(5).is_empty());
+    }
+
+    #[test]
+    fn pool_bounds_target_distance_and_size() {
+        let pool = BundlePool::new(10);
+        let far = 10 + MAX_BUNDLE_TARGET_DISTANCE;
+
+        assert_eq!(
+            pool.insert(bundle(vec![B256::repeat_byte(1)], far + 1)),
+            Err(BundleError::TargetBlockTooFar {
+                target: far + 1,
+                head: 10
+            })
+        );
+        assert!(pool.insert(bundle(vec![B256::repeat_byte(1)], far)).is_ok());
+
+        let pool = BundlePool::new(0);
+        for i in 0..MAX_PENDING_BUNDLES {
+            let target = 1 + (i / MAX_BUNDLES_PER_BLOCK) as u64;
+            let hash = B256::from(alloy_primitives::U256::from(i + 1));
+            pool.insert(bundle(vec![hash], target)).unwrap();
+        }
+        let target = 1 + (MAX_PENDING_BUNDLES / MAX_BUNDLES_PER_BLOCK) as u64;
+        assert_eq!(
+            pool.insert(bundle(vec![B256::ZERO], target)),
+            Err(BundleError::PoolFull)
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use alloy_primitives::{keccak256, Address, U256};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, ops::RangeInclusive, sync::Mutex};
+
+/// Number of index bits of the active address sketch (4096 registers, ~1.6% standard error).
+const SKETCH_PRECISION: u32 = 12;
+
+/// Gas fees split by component.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTotals {
+    /// Base fee portion (`gas_used * base_fee_per_gas`).
+    pub base_fee: U256,
+    /// Part of the base fee that was burned rather than credited to the base-fee sink.
+    pub base_fee_burned: U256,
+    /// Priority fee portion paid to the block beneficiary.
+    pub priority_fee: U256,
+}
+
+impl FeeTotals {
+    fn add(&mut self, other: &Self) {
+        self.base_fee = self.base_fee.saturating_add(other.base_fee);
+        self.base_fee_burned = self.base_fee_burned.saturating_add(other.base_fee_burned);
+        self.priority_fee = self.priority_fee.saturating_add(other.priority_fee);
+    }
+}
+
+/// KPIs of a single block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStats {
+    /// Block number.
+    pub block_number: u64,
+    /// Transaction count keyed by transaction type (e.g. `eip1559`, `evnode`).
+    pub transactions_by_type: BTreeMap<String, u64>,
+    /// Transactions whose gas was paid by a sponsor.
+    pub sponsored_transactions: u64,
+    /// Transactions whose gas was paid by the sender.
+    pub unsponsored_transactions: u64,
+    /// Fees paid in the block.
+    pub fees: FeeTotals,
+}
+
+/// Net change of the native supply over a range of blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SupplyChange {
+    /// Total balance increase over all accounts.
+    pub increase: U256,
+    /// Total balance decrease over all accounts.
+    pub decrease: U256,
+}
+
+/// Chain KPIs aggregated over the blocks observed since the node started.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainStats {
+    /// Number of blocks aggregated.
+    pub blocks: u64,
+    /// Transaction count keyed by transaction type.
+    pub transactions_by_type: BTreeMap<String, u64>,
+    /// Transactions whose gas was paid by a sponsor.
+    pub sponsored_transactions: u64,
+    /// Transactions whose gas was paid by the sender.
+    pub unsponsored_transactions: u64,
+    /// Estimated number of distinct transaction senders.
+    pub unique_active_addresses: u64,
+    /// Fees paid over all aggregated blocks.
+    pub fees: FeeTotals,
+    /// Native supply as of the latest block, when known.
+    ///
+    /// Only known when the blocks since genesis were all observed, since it is derived from
+    /// the genesis allocation and the balance changes of every block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub native_supply: Option<U256>,
+    /// KPIs of the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest: Option<BlockStats>,
+}
+
+/// Tracks chain KPIs as canonical blocks are committed, fed by the task following canonical
+/// blocks and served by `evolve_getChainStats`.
+#[derive(Debug, Default)]
+pub struct ChainStatsTracker {
+    inner: Mutex<TrackerState>,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    stats: ChainStats,
+    active_addresses: AddressSketch,
+    /// Supply as of the given block.
+    supply: Option<(u64, U256)>,
+}
+
+impl ChainStatsTracker {
+    /// Sets the native supply as of `block_number`, typically the genesis allocation.
+    pub fn seed_supply(&self, block_number: u64, supply: U256) {
+        let mut state = self.inner.lock().expect("chain stats lock poisoned");
+        state.supply = Some((block_number, supply));
+        state.stats.native_supply = Some(supply);
+    }
+
+    /// Adds a committed block.
+    pub fn record_block(&self, block: BlockStats, senders: impl IntoIterator<Item = Address>) {
+        let mut state = self.inner.lock().expect("chain stats lock poisoned");
+        for sender in senders {
+            state.active_addresses.insert(sender);
+        }
+        let stats = &mut state.stats;
+        stats.blocks += 1;
+        for (tx_type, count) in &block.transactions_by_type {
+            *stats
+                .transactions_by_type
+                .entry(tx_type.clone())
+                .or_default() += count;
+        }
+        stats.sponsored_transactions += block.sponsored_transactions;
+        stats.unsponsored_transactions += block.unsponsored_transactions;
+        stats.fees.add(&block.fees);
+        stats.latest = Some(block);
+        state.stats.unique_active_addresses = state.active_addresses.estimate();
+    }
+
+    /// Applies the supply change of the committed blocks in `range`.
+    ///
+    /// The supply becomes unknown if `range` does not directly follow the last tracked block.
+    pub fn apply_supply(&self, range: RangeInclusive<u64>, change: SupplyChange) {
+        let mut state = self.inner.lock().expect("chain stats lock poisoned");
+        state.supply = match state.supply {
+            Some((tip, supply)) if tip.checked_add(1) == Some(*range.start()) => Some((
+                *range.end(),
+                supply
+                    .saturating_add(change.increase)
+                    .saturating_sub(change.decrease),
+            )),
+            _ => None,
+        };
+        state.stats.native_supply = state.supply.map(|(_, supply)| supply);
+    }
+
+    /// Undoes the supply change of the reverted blocks in `range`.
+    pub fn revert_supply(&self, range: RangeInclusive<u64>, change: SupplyChange) {
+        let mut state = self.inner.lock().expect("chain stats lock poisoned");
+        state.supply = match state.supply {
+            Some((tip, supply)) if tip == *range.end() => Some((
+                range.start().saturating_sub(1),
+                supply
+                    .saturating_add(change.decrease)
+                    .saturating_sub(change.increase),
+            )),
+            _ => None,
+        };
+        state.stats.native_supply = state.supply.map(|(_, supply)| supply);
+    }
+
+    /// Returns the current statistics.
+    pub fn snapshot(&self) -> ChainStats {
+        self.inner
+            .lock()
+            .expect("chain stats lock poisoned")
+            .stats
+            .clone()
+    }
+}
+
+/// HyperLogLog sketch estimating the number of distinct addresses in fixed memory.
+#[derive(Debug)]
+struct AddressSketch {
+    registers: Box<[u8]>,
+}
+
+impl Default for AddressSketch {
+    fn default() -> Self {
+        Self {
+            registers: vec![0; 1 << SKETCH_PRECISION].into_boxed_slice(),
+        }
+    }
+}
+
+impl AddressSketch {
+    fn insert(&mut self, address: Address) {
+        let hash = u64::from_be_bytes(keccak256(address)[..8].try_into().expect("8 bytes"));
+        let index = (hash >> (64 - SKETCH_PRECISION)) as usize;
+        let rank = ((hash << SKETCH_PRECISION).leading_zeros() + 1).min(64 - SKETCH_PRECISION + 1);
+        self.registers[index] = self.registers[index].max(rank as u8);
+    }
+
+    fn estimate(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-i32::from(rank)))
+            .sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&rank| rank == 0).count();
+        let estimate = if raw <= 2.5 * m && zeros > 0 {
+            // Linear counting is more accurate for small cardinalities.
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        };
+        estimate.round() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sketch_estimates_distinct_addresses() {
+        let mut sketch = AddressSketch::default();
+        for i in 0..10_000u64 {
+            let mut bytes = [0u8; 20];
+            bytes[12..].copy_from_slice(&i.to_be_bytes());
+            // Repeated inserts must not inflate the estimate.
+            sketch.insert(Address::from(bytes));
+            sketch.insert(Address::from(bytes));
+        }
+        let estimate = sketch.estimate();
+        assert!(
+            (9_500..=10_500).contains(&estimate),
+            "estimate {estimate} too far from 10000"
+        );
+    }
+
+    #[test]
+    fn supply_follows_contiguous_blocks() {
+        let tracker = ChainStatsTracker::default();
+        tracker.seed_supply(0, U256::from(1_000));
+        tracker.apply_supply(
+            1..=2,
+            SupplyChange {
+                increase: U256::from(50),
+                decrease: U256::from(20),
+            },
+        );
+        assert_eq!(tracker.snapshot().native_supply, Some(U256::from(1_030)));
+
+        tracker.revert_supply(
+            2..=2,
+            SupplyChange {
+                increase: U256::from(10),
+                decrease: U256::ZERO,
+            },
+        );
+        assert_eq!(tracker.snapshot().native_supply, Some(U256::from(1_020)));
+
+        // A gap makes the supply unknown.
+        tracker.apply_supply(5..=5, SupplyChange::default());
+        assert_eq!(tracker.snapshot().native_supply, None);
+    }
+
+    #[test]
+    fn aggregates_block_stats() {
+        let tracker = ChainStatsTracker::default();
+        let sender = Address::repeat_byte(0x01);
+        for block_number in 1..=2 {
+            tracker.record_block(
+                BlockStats {
+                    block_number,
+                    transactions_by_type: BTreeMap::from([("evnode".to_string(), 2)]),
+                    sponsored_transactions: 1,
+                    unsponsored_transactions: 1,
+                    fees: FeeTotals {
+                        base_fee: U256::from(100),
+                        base_fee_burned: U256::from(25),
+                        priority_fee: U256::from(10),
+                    },
+                },
+                [sender],
+            );
+        }
+
+        let stats = tracker.snapshot();
+        assert_eq!(stats.blocks, 2);
+        assert_eq!(stats.transactions_by_type["evnode"], 4);
+        assert_eq!(stats.sponsored_transactions, 2);
+        assert_eq!(stats.unique_active_addresses, 1);
+        assert_eq!(stats.fees.base_fee, U256::from(200));
+        assert_eq!(stats.fees.base_fee_burned, U256::from(50));
+        assert_eq!(stats.latest.map(|block| block.block_number), Some(2));
+    }
+}
@@ -0,0 +1,60 @@
+use alloy_primitives::{keccak256, B256};
+
+/// Number of blocks covered by a commitment when the chainspec does not set a window.
+pub const DEFAULT_BLOCK_COMMITMENT_WINDOW: u64 = 64;
+
+/// Largest accepted commitment window.
+pub const MAX_BLOCK_COMMITMENT_WINDOW: u64 = 1024;
+
+/// Computes the Merkle root committing to `hashes`, the block hashes of a window in ascending
+/// block order.
+///
+/// Leaves are the block hashes themselves. Each level hashes adjacent pairs as
+/// `keccak256(left || right)`; a trailing node without a sibling is carried up unchanged, so
+/// a single-block window commits to that block's hash. An empty window commits to
+/// [`B256::ZERO`].
+pub fn block_commitment_root(hashes: &[B256]) -> B256 {
+    if hashes.is_empty() {
+        return B256::ZERO;
+    }
+    let mut level = hashes.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => keccak256([left.as_slice(), right.as_slice()].concat()),
+                [single] => *single,
+                _ => unreachable!("chunks of two"),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(left: B256, right: B256) -> B256 {
+        keccak256([left.as_slice(), right.as_slice()].concat())
+    }
+
+    #[test]
+    fn root_pairs_leaves_and_promotes_odd_nodes() {
+        let [a, b, c] = [1u8, 2, 3].map(B256::repeat_byte);
+
+        assert_eq!(block_commitment_root(&[]), B256::ZERO);
+        assert_eq!(block_commitment_root(&[a]), a);
+        assert_eq!(block_commitment_root(&[a, b]), node(a, b));
+        assert_eq!(block_commitment_root(&[a, b, c]), node(node(a, b), c));
+    }
+
+    #[test]
+    fn root_depends_on_order() {
+        let [a, b] = [1u8, 2].map(B256::repeat_byte);
+        assert_ne!(
+            block_commitment_root(&[a, b]),
+            block_commitment_root(&[b, a])
+        );
+    }
+}
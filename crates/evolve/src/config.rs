@@ -1,3 +1,4 @@
+use reth_transaction_pool::{PoolConfig, SubPoolLimit};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -61,3 +62,47 @@ pub fn set_current_block_gas_limit(gas_limit: u64) {
 pub fn current_block_gas_limit() -> u64 {
     CURRENT_BLOCK_GAS_LIMIT.load(Ordering::Relaxed)
 }
+
+/// Size limit of a txpool sub-pool; exceeding either bound triggers eviction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubpoolLimit {
+    /// Maximum number of transactions.
+    pub max_transactions: usize,
+    /// Maximum total size in bytes.
+    pub max_bytes: usize,
+}
+
+/// Eviction limits of the txpool's sub-pools.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolLimits {
+    /// Limit of the pending sub-pool.
+    pub pending: SubpoolLimit,
+    /// Limit of the base-fee sub-pool.
+    pub basefee: SubpoolLimit,
+    /// Limit of the queued sub-pool.
+    pub queued: SubpoolLimit,
+    /// Limit of the blob sub-pool.
+    pub blob: SubpoolLimit,
+}
+
+impl From<&SubPoolLimit> for SubpoolLimit {
+    fn from(limit: &SubPoolLimit) -> Self {
+        Self {
+            max_transactions: limit.max_txs,
+            max_bytes: limit.max_size,
+        }
+    }
+}
+
+impl From<&PoolConfig> for TxpoolLimits {
+    fn from(config: &PoolConfig) -> Self {
+        Self {
+            pending: (&config.pending_limit).into(),
+            basefee: (&config.basefee_limit).into(),
+            queued: (&config.queued_limit).into(),
+            blob: (&config.blob_limit).into(),
+        }
+    }
+}
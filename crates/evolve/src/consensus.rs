@@ -1,6 +1,11 @@
 //! Evolve custom consensus implementation that allows same timestamps across blocks.
 
-use ev_primitives::{Block, BlockBody, EvPrimitives, Receipt};
+use crate::randao::{derive_prev_randao, PrevRandaoPolicy};
+use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, TxReceipt};
+use alloy_primitives::Bloom;
+use ev_primitives::{
+    Block, BlockBody, EvNodeTxDisabled, EvPrimitives, EvTxEnvelope, MaxBatchCalls, Receipt,
+};
 use reth_chainspec::ChainSpec;
 use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator, ReceiptRootBloom};
 use reth_consensus_common::validation::{
@@ -12,7 +17,12 @@ use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_execution_types::BlockExecutionResult;
 use reth_node_api::{FullNodeTypes, NodeTypes};
 use reth_primitives_traits::{RecoveredBlock, SealedBlock, SealedHeader};
-use std::sync::Arc;
+use serde::Deserialize;
+use std::{
+    collections::BTreeSet,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 /// Builder for `EvolveConsensus`
 #[derive(Debug, Default, Clone)]
@@ -39,10 +49,59 @@ where
     type Consensus = Arc<dyn FullConsensus<EvPrimitives>>;
 
     async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
-        Ok(Arc::new(EvolveConsensus::new(ctx.chain_spec())) as Self::Consensus)
+        Ok(Arc::new(EvolveConsensus::from_chain_spec(ctx.chain_spec())?) as Self::Consensus)
     }
 }
 
+/// Consensus-related settings read from the chainspec `evolve` extras.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ChainspecConsensusConfig {
+    /// Block height from which header receipts roots are verified against execution.
+    #[serde(default, rename = "receiptsRootValidationActivationHeight")]
+    receipts_root_validation_activation_height: Option<u64>,
+    /// Block height from which header logs blooms are verified against execution.
+    #[serde(default, rename = "logsBloomValidationActivationHeight")]
+    logs_bloom_validation_activation_height: Option<u64>,
+    /// Maximum number of seconds a header timestamp may be ahead of the local clock.
+    #[serde(default, rename = "maxTimestampDrift")]
+    max_timestamp_drift: Option<u64>,
+    /// Minimum number of seconds between a block and its parent.
+    #[serde(default, rename = "minBlockTime")]
+    min_block_time: Option<u64>,
+    /// Block height at which the minimum block time is enforced.
+    #[serde(default, rename = "minBlockTimeActivationHeight")]
+    min_block_time_activation_height: Option<u64>,
+    /// Source of block `prev_randao` values.
+    #[serde(default, rename = "prevRandaoPolicy")]
+    prev_randao_policy: PrevRandaoPolicy,
+    /// Block height at which the `prev_randao` policy is enforced.
+    #[serde(default, rename = "prevRandaoPolicyActivationHeight")]
+    prev_randao_policy_activation_height: Option<u64>,
+    /// Block height from which blocks may carry withdrawals. Disabled when unset.
+    #[serde(default, rename = "withdrawalsActivationHeight")]
+    withdrawals_activation_height: Option<u64>,
+    /// Whether blocks may carry EvNode (0x76) transactions. Enabled when unset.
+    #[serde(default, rename = "enableEvNodeTx")]
+    enable_evnode_tx: Option<bool>,
+    /// Maximum number of calls in one EvNode transaction. Unbounded when unset.
+    #[serde(default, rename = "maxBatchCalls")]
+    max_batch_calls: Option<u64>,
+    /// Block height at which the EvNode call limit is enforced.
+    #[serde(default, rename = "maxBatchCallsActivationHeight")]
+    max_batch_calls_activation_height: Option<u64>,
+}
+
+/// Returns the paths of the `evolve` chainspec extras the consensus settings do not read.
+pub fn unread_consensus_extras(
+    extras: serde_json::Value,
+) -> Result<BTreeSet<String>, serde_json::Error> {
+    let mut unread = BTreeSet::new();
+    serde_ignored::deserialize::<_, _, ChainspecConsensusConfig>(extras, |path| {
+        unread.insert(path.to_string());
+    })?;
+    Ok(unread)
+}
+
 /// Evolve consensus implementation that allows blocks with the same timestamp.
 ///
 /// This consensus implementation wraps the standard Ethereum beacon consensus
@@ -52,18 +111,167 @@ where
 pub struct EvolveConsensus {
     /// Inner Ethereum beacon consensus for standard validation
     inner: EthBeaconConsensus<ChainSpec>,
+    /// First block whose receipts root is checked after execution.
+    receipts_root_activation_height: u64,
+    /// First block whose logs bloom is checked after execution.
+    logs_bloom_activation_height: u64,
+    /// Maximum seconds a header timestamp may run ahead of the local clock, if enforced.
+    max_timestamp_drift: Option<u64>,
+    /// Minimum block time in seconds and the height from which it applies, if enforced.
+    min_block_time: Option<(u64, u64)>,
+    /// `prev_randao` policy and the height from which it applies.
+    prev_randao_policy: (PrevRandaoPolicy, u64),
+    /// Height from which non-empty withdrawals are accepted, if enabled.
+    withdrawals_activation_height: Option<u64>,
+    /// Whether blocks may carry EvNode (0x76) transactions.
+    evnode_tx_enabled: bool,
+    /// Limit on the number of calls per EvNode transaction, if enforced.
+    max_batch_calls: Option<MaxBatchCalls>,
 }
 
 impl EvolveConsensus {
     /// Create a new Evolve consensus instance
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
         let inner = EthBeaconConsensus::new(chain_spec);
-        Self { inner }
+        Self {
+            inner,
+            receipts_root_activation_height: 0,
+            logs_bloom_activation_height: 0,
+            max_timestamp_drift: None,
+            min_block_time: None,
+            prev_randao_policy: (PrevRandaoPolicy::Attributes, 0),
+            withdrawals_activation_height: None,
+            evnode_tx_enabled: true,
+            max_batch_calls: None,
+        }
+    }
+
+    /// Create a new Evolve consensus instance, applying the validation toggles configured in
+    /// the chainspec `evolve` extras.
+    pub fn from_chain_spec(chain_spec: Arc<ChainSpec>) -> eyre::Result<Self> {
+        let config = chain_spec
+            .genesis
+            .config
+            .extra_fields
+            .get_deserialized::<ChainspecConsensusConfig>("evolve")
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut consensus = Self::new(chain_spec)
+            .with_receipts_root_activation_height(
+                config
+                    .receipts_root_validation_activation_height
+                    .unwrap_or(0),
+            )
+            .with_logs_bloom_activation_height(
+                config.logs_bloom_validation_activation_height.unwrap_or(0),
+            );
+        if let Some(drift) = config.max_timestamp_drift {
+            consensus = consensus.with_max_timestamp_drift(drift);
+        }
+        if let Some(block_time) = config.min_block_time.filter(|secs| *secs > 0) {
+            consensus = consensus.with_min_block_time(
+                block_time,
+                config.min_block_time_activation_height.unwrap_or(0),
+            );
+        }
+        consensus = consensus.with_prev_randao_policy(
+            config.prev_randao_policy,
+            config.prev_randao_policy_activation_height.unwrap_or(0),
+        );
+        if let Some(height) = config.withdrawals_activation_height {
+            consensus = consensus.with_withdrawals_activation_height(height);
+        }
+        consensus = consensus.with_evnode_tx_enabled(config.enable_evnode_tx.unwrap_or(true));
+        if let Some(max_calls) = config.max_batch_calls {
+            consensus = consensus.with_max_batch_calls(MaxBatchCalls::new(
+                max_calls,
+                config.max_batch_calls_activation_height.unwrap_or(0),
+            ));
+        }
+        Ok(consensus)
+    }
+
+    /// Skip receipts root verification for blocks below `height`.
+    ///
+    /// Intended for migrated chains whose historical headers carry incorrect receipts roots.
+    pub const fn with_receipts_root_activation_height(mut self, height: u64) -> Self {
+        self.receipts_root_activation_height = height;
+        self
+    }
+
+    /// Skip logs bloom verification for blocks below `height`.
+    ///
+    /// Intended for migrated chains whose historical headers carry incorrect logs blooms.
+    pub const fn with_logs_bloom_activation_height(mut self, height: u64) -> Self {
+        self.logs_bloom_activation_height = height;
+        self
+    }
+
+    /// Reject headers whose timestamp is more than `secs` seconds ahead of the local clock.
+    pub const fn with_max_timestamp_drift(mut self, secs: u64) -> Self {
+        self.max_timestamp_drift = Some(secs);
+        self
+    }
+
+    /// Require each block from `activation_height` onwards to be at least `secs` seconds
+    /// after its parent.
+    ///
+    /// This disables the equal-timestamp allowance for those blocks.
+    pub const fn with_min_block_time(mut self, secs: u64, activation_height: u64) -> Self {
+        self.min_block_time = Some((secs, activation_height));
+        self
+    }
+
+    /// Enforce `policy` for the `prev_randao` of blocks from `activation_height` onwards.
+    pub const fn with_prev_randao_policy(
+        mut self,
+        policy: PrevRandaoPolicy,
+        activation_height: u64,
+    ) -> Self {
+        self.prev_randao_policy = (policy, activation_height);
+        self
+    }
+
+    /// Accept blocks carrying Engine API withdrawals from `height` onwards.
+    ///
+    /// Without this, blocks with a non-empty withdrawals list are rejected.
+    pub const fn with_withdrawals_activation_height(mut self, height: u64) -> Self {
+        self.withdrawals_activation_height = Some(height);
+        self
+    }
+
+    /// Accept or reject blocks carrying EvNode (0x76) transactions.
+    ///
+    /// Chains that only run vanilla Ethereum transactions can turn the type off entirely.
+    pub const fn with_evnode_tx_enabled(mut self, enabled: bool) -> Self {
+        self.evnode_tx_enabled = enabled;
+        self
+    }
+
+    /// Reject blocks carrying an EvNode transaction with more calls than `limit` allows.
+    pub const fn with_max_batch_calls(mut self, limit: MaxBatchCalls) -> Self {
+        self.max_batch_calls = Some(limit);
+        self
     }
 }
 
 impl HeaderValidator for EvolveConsensus {
     fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+        if let Some(drift) = self.max_timestamp_drift {
+            let present_timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            let timestamp = header.header().timestamp;
+            if timestamp > present_timestamp.saturating_add(drift) {
+                return Err(ConsensusError::TimestampIsInFuture {
+                    timestamp,
+                    present_timestamp,
+                });
+            }
+        }
+
         // Use inner consensus for basic header validation
         self.inner.validate_header(header)
     }
@@ -84,6 +292,28 @@ impl HeaderValidator for EvolveConsensus {
             });
         }
 
+        if let Some((block_time, activation_height)) = self.min_block_time {
+            if h.number >= activation_height
+                && h.timestamp < ph.timestamp.saturating_add(block_time)
+            {
+                return Err(ConsensusError::Other(format!(
+                    "block timestamp {} is less than {block_time}s after parent timestamp {}",
+                    h.timestamp, ph.timestamp
+                )));
+            }
+        }
+
+        let (randao_policy, randao_activation_height) = self.prev_randao_policy;
+        if randao_policy.is_derived() && h.number >= randao_activation_height {
+            let expected = derive_prev_randao(parent.hash(), ph.mix_hash);
+            if h.mix_hash != expected {
+                return Err(ConsensusError::Other(format!(
+                    "block prev_randao {} does not match derived value {expected}",
+                    h.mix_hash
+                )));
+            }
+        }
+
         validate_against_parent_gas_limit(header, parent, &self.inner.chain_spec())?;
 
         validate_against_parent_eip1559_base_fee(
@@ -109,6 +339,47 @@ impl Consensus<Block> for EvolveConsensus {
         &self,
         block: &SealedBlock<Block>,
     ) -> Result<(), ConsensusError> {
+        let has_withdrawals = block
+            .body()
+            .withdrawals
+            .as_ref()
+            .is_some_and(|w| !w.is_empty());
+        let withdrawals_enabled = self
+            .withdrawals_activation_height
+            .is_some_and(|height| block.header().number >= height);
+        if has_withdrawals && !withdrawals_enabled {
+            return Err(ConsensusError::Other(format!(
+                "block {} contains withdrawals but withdrawals are not enabled",
+                block.header().number
+            )));
+        }
+
+        if !self.evnode_tx_enabled
+            && block
+                .body()
+                .transactions
+                .iter()
+                .any(|tx| matches!(tx, EvTxEnvelope::EvNode(_)))
+        {
+            return Err(ConsensusError::Other(format!(
+                "block {}: {EvNodeTxDisabled}",
+                block.header().number
+            )));
+        }
+
+        if let Some(limit) = self.max_batch_calls {
+            for tx in &block.body().transactions {
+                if let EvTxEnvelope::EvNode(signed) = tx {
+                    if let Err(err) = limit.check(signed.tx(), block.header().number) {
+                        return Err(ConsensusError::Other(format!(
+                            "block {}: {err}",
+                            block.header().number
+                        )));
+                    }
+                }
+            }
+        }
+
         // use inner consensus for pre-execution validation
         self.inner.validate_block_pre_execution(block)
     }
@@ -121,6 +392,40 @@ impl FullConsensus<EvPrimitives> for EvolveConsensus {
         result: &BlockExecutionResult<Receipt>,
         receipt_root_bloom: Option<ReceiptRootBloom>,
     ) -> Result<(), ConsensusError> {
+        let header = block.header();
+        let check_receipts_root = header.number() >= self.receipts_root_activation_height;
+        let check_logs_bloom = header.number() >= self.logs_bloom_activation_height;
+
+        // For legacy blocks, substitute the header's own values for the checks that are not yet
+        // active so the inner consensus still enforces gas used and requests.
+        let receipt_root_bloom = if check_receipts_root && check_logs_bloom {
+            receipt_root_bloom
+        } else {
+            let (receipts_root, logs_bloom) = receipt_root_bloom.unwrap_or_else(|| {
+                let receipts_with_bloom = result
+                    .receipts
+                    .iter()
+                    .map(TxReceipt::with_bloom_ref)
+                    .collect::<Vec<_>>();
+                let logs_bloom = receipts_with_bloom
+                    .iter()
+                    .fold(Bloom::ZERO, |bloom, r| bloom | r.bloom_ref());
+                (calculate_receipt_root(&receipts_with_bloom), logs_bloom)
+            });
+            Some((
+                if check_receipts_root {
+                    receipts_root
+                } else {
+                    header.receipts_root()
+                },
+                if check_logs_bloom {
+                    logs_bloom
+                } else {
+                    header.logs_bloom()
+                },
+            ))
+        };
+
         <EthBeaconConsensus<ChainSpec> as FullConsensus<EvPrimitives>>::validate_block_post_execution(&self.inner, block, result, receipt_root_bloom)
     }
 }
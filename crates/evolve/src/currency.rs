@@ -0,0 +1,96 @@
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+
+/// Decimals of the native currency when the chainspec does not configure any.
+pub const DEFAULT_NATIVE_DECIMALS: u8 = 18;
+
+/// Largest supported number of decimals; `10^77` is the largest power of ten that fits a
+/// `U256`, and anything close to it leaves no room for balances.
+pub const MAX_NATIVE_DECIMALS: u8 = 36;
+
+/// Native currency metadata of the chain.
+///
+/// Balances, fees and values on chain are always denominated in the smallest unit (wei on
+/// an 18-decimals chain); the metadata only describes how clients should display them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeCurrency {
+    /// Human readable name, e.g. `Ether`.
+    pub name: String,
+    /// Ticker symbol, e.g. `ETH`.
+    pub symbol: String,
+    /// Number of decimals of the smallest unit.
+    #[serde(default = "default_decimals")]
+    pub decimals: u8,
+}
+
+const fn default_decimals() -> u8 {
+    DEFAULT_NATIVE_DECIMALS
+}
+
+impl Default for NativeCurrency {
+    fn default() -> Self {
+        Self {
+            name: "Ether".to_string(),
+            symbol: "ETH".to_string(),
+            decimals: DEFAULT_NATIVE_DECIMALS,
+        }
+    }
+}
+
+impl NativeCurrency {
+    /// Number of smallest units in one whole coin, `10^decimals`.
+    pub fn unit(&self) -> U256 {
+        U256::from(10u64).pow(U256::from(self.decimals))
+    }
+
+    /// Converts whole coins to smallest units, saturating on overflow.
+    pub fn to_base_units(&self, whole: u128) -> U256 {
+        U256::from(whole).saturating_mul(self.unit())
+    }
+
+    /// Splits an amount of smallest units into whole coins and the fractional remainder.
+    pub fn split(&self, amount: U256) -> (U256, U256) {
+        let unit = self.unit();
+        (amount / unit, amount % unit)
+    }
+
+    /// Formats an amount of smallest units as a decimal string followed by the symbol,
+    /// e.g. `1.5 ETH`.
+    pub fn format(&self, amount: U256) -> String {
+        let (whole, fraction) = self.split(amount);
+        if fraction.is_zero() {
+            return format!("{whole} {}", self.symbol);
+        }
+        let fraction = format!("{fraction:0>width$}", width = self.decimals as usize);
+        format!("{whole}.{} {}", fraction.trim_end_matches('0'), self.symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_follows_decimals() {
+        let eth = NativeCurrency::default();
+        assert_eq!(eth.unit(), U256::from(1_000_000_000_000_000_000u128));
+
+        let six = NativeCurrency {
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            decimals: 6,
+        };
+        assert_eq!(six.to_base_units(3), U256::from(3_000_000u64));
+        assert_eq!(six.format(U256::from(1_500_000u64)), "1.5 TST");
+        assert_eq!(six.format(U256::from(2_000_000u64)), "2 TST");
+        assert_eq!(six.format(U256::from(42u64)), "0.000042 TST");
+    }
+
+    #[test]
+    fn decimals_default_to_eighteen() {
+        let currency: NativeCurrency =
+            serde_json::from_str(r#"{"name":"Gas","symbol":"GAS"}"#).unwrap();
+        assert_eq!(currency.decimals, DEFAULT_NATIVE_DECIMALS);
+    }
+}
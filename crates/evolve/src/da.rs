@@ -0,0 +1,237 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+/// Errors returned when a DA status update is rejected.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DaFinalityError {
+    /// The update moves a tag to a lower block.
+    #[error("{tag} block {new} is below the current {tag} block {current}")]
+    BlockRegression {
+        /// Tag being updated.
+        tag: &'static str,
+        /// Currently recorded block number.
+        current: u64,
+        /// Block number in the update.
+        new: u64,
+    },
+    /// The update moves a tag to a lower DA height.
+    #[error("{tag} DA height {new} is below the current {tag} DA height {current}")]
+    DaHeightRegression {
+        /// Tag being updated.
+        tag: &'static str,
+        /// Currently recorded DA height.
+        current: u64,
+        /// DA height in the update.
+        new: u64,
+    },
+    /// The finalized block would be ahead of the safe block.
+    #[error("finalized block {finalized} is ahead of safe block {safe}")]
+    FinalizedAheadOfSafe {
+        /// Resulting safe block number.
+        safe: u64,
+        /// Resulting finalized block number.
+        finalized: u64,
+    },
+}
+
+/// An execution block together with the DA height it was included in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaBlockRef {
+    /// Execution block number.
+    pub block_number: u64,
+    /// Execution block hash.
+    pub block_hash: B256,
+    /// DA layer height the block was included in.
+    pub da_height: u64,
+}
+
+/// DA-backed block tags.
+///
+/// `safe` is the highest block included on the DA layer, `finalized` the highest block whose
+/// DA inclusion is final.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaStatus {
+    /// Highest DA-included block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safe: Option<DaBlockRef>,
+    /// Highest block with final DA inclusion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalized: Option<DaBlockRef>,
+}
+
+/// Tracks DA inclusion and finality reported by ev-node, written by `evolve_setDaStatus` and
+/// read by the task that maps it onto the `safe` and `finalized` block tags.
+///
+/// Both tags only move forward, and `finalized` never passes `safe`. A finalized block is
+/// always DA-included, so advancing `finalized` past `safe` also advances `safe`.
+#[derive(Debug)]
+pub struct DaFinalityTracker {
+    status: watch::Sender<DaStatus>,
+}
+
+impl Default for DaFinalityTracker {
+    fn default() -> Self {
+        Self {
+            status: watch::channel(DaStatus::default()).0,
+        }
+    }
+}
+
+impl DaFinalityTracker {
+    /// Returns the current DA-backed tags.
+    pub fn status(&self) -> DaStatus {
+        *self.status.borrow()
+    }
+
+    /// Subscribes to tag changes.
+    pub fn subscribe(&self) -> watch::Receiver<DaStatus> {
+        self.status.subscribe()
+    }
+
+    /// Applies an update, returning the resulting status.
+    ///
+    /// Missing tags in `update` leave the recorded ones unchanged.
+    pub fn update(&self, update: DaStatus) -> Result<DaStatus, DaFinalityError> {
+        let mut result = Ok(DaStatus::default());
+        self.status
+            .send_if_modified(|status| match apply(*status, update) {
+                Ok(next) => {
+                    result = Ok(next);
+                    let changed = *status != next;
+                    *status = next;
+                    changed
+                }
+                Err(err) => {
+                    result = Err(err);
+                    false
+                }
+            });
+        result
+    }
+}
+
+fn apply(current: DaStatus, update: DaStatus) -> Result<DaStatus, DaFinalityError> {
+    let finalized = merge("finalized", current.finalized, update.finalized)?;
+    let mut safe = merge("safe", current.safe, update.safe)?;
+
+    if let Some(finalized) = finalized {
+        match safe {
+            Some(safe) if safe.block_number >= finalized.block_number => {}
+            Some(safe) if update.safe.is_some() => {
+                return Err(DaFinalityError::FinalizedAheadOfSafe {
+                    safe: safe.block_number,
+                    finalized: finalized.block_number,
+                })
+            }
+            // A finalized block is included, so it is at least safe.
+            _ => safe = Some(finalized),
+        }
+    }
+
+    Ok(DaStatus { safe, finalized })
+}
+
+fn merge(
+    tag: &'static str,
+    current: Option<DaBlockRef>,
+    new: Option<DaBlockRef>,
+) -> Result<Option<DaBlockRef>, DaFinalityError> {
+    let (Some(current), Some(new)) = (current, new) else {
+        return Ok(new.or(current));
+    };
+    if new.block_number < current.block_number {
+        return Err(DaFinalityError::BlockRegression {
+            tag,
+            current: current.block_number,
+            new: new.block_number,
+        });
+    }
+    if new.da_height < current.da_height {
+        return Err(DaFinalityError::DaHeightRegression {
+            tag,
+            current: current.da_height,
+            new: new.da_height,
+        });
+    }
+    Ok(Some(new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(block_number: u64, da_height: u64) -> DaBlockRef {
+        DaBlockRef {
+            block_number,
+            block_hash: B256::with_last_byte(block_number as u8),
+            da_height,
+        }
+    }
+
+    #[test]
+    fn tags_only_move_forward() {
+        let tracker = DaFinalityTracker::default();
+        let status = tracker
+            .update(DaStatus {
+                safe: Some(block(10, 3)),
+                finalized: Some(block(5, 2)),
+            })
+            .unwrap();
+        assert_eq!(status.safe, Some(block(10, 3)));
+
+        let err = tracker
+            .update(DaStatus {
+                safe: Some(block(9, 3)),
+                finalized: None,
+            })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DaFinalityError::BlockRegression { tag: "safe", .. }
+        ));
+
+        let err = tracker
+            .update(DaStatus {
+                safe: None,
+                finalized: Some(block(6, 1)),
+            })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DaFinalityError::DaHeightRegression {
+                tag: "finalized",
+                ..
+            }
+        ));
+        assert_eq!(tracker.status().finalized, Some(block(5, 2)));
+    }
+
+    #[test]
+    fn finalized_never_passes_safe() {
+        let tracker = DaFinalityTracker::default();
+        let status = tracker
+            .update(DaStatus {
+                safe: None,
+                finalized: Some(block(4, 1)),
+            })
+            .unwrap();
+        assert_eq!(status.safe, Some(block(4, 1)));
+
+        let err = tracker
+            .update(DaStatus {
+                safe: Some(block(6, 2)),
+                finalized: Some(block(8, 3)),
+            })
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DaFinalityError::FinalizedAheadOfSafe {
+                safe: 6,
+                finalized: 8
+            }
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use alloy_primitives::{keccak256, Bytes, FixedBytes, B256};
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, sync::Mutex};
+
+/// Maximum number of encrypted transactions held in the lane.
+pub const MAX_ENCRYPTED_POOL_SIZE: usize = 4096;
+
+/// Maximum ciphertext size accepted for a single encrypted transaction.
+pub const MAX_ENCRYPTED_TX_BYTES: usize = 128 * 1024;
+
+/// Errors returned when an encrypted transaction is rejected or cannot be decrypted.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EncryptedTxError {
+    /// The ciphertext is empty or larger than [`MAX_ENCRYPTED_TX_BYTES`].
+    #[error("ciphertext size {0} is out of bounds")]
+    InvalidSize(usize),
+    /// The lane is full.
+    #[error("encrypted transaction pool is full")]
+    PoolFull,
+    /// The same ciphertext was already submitted.
+    #[error("encrypted transaction {0} already known")]
+    AlreadyKnown(B256),
+    /// The revealed key does not decrypt the ciphertext.
+    #[error("decryption failed")]
+    DecryptionFailed,
+}
+
+/// A transaction encrypted to the committee key of `key_id`.
+///
+/// The plaintext is the EIP-2718 encoding of the transaction, sealed with AES-256-GCM under
+/// the key the committee reveals for `key_id`. The big-endian `key_id` is bound as
+/// associated data so a ciphertext cannot be replayed under a different key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedTransaction {
+    /// Identifier of the decryption key, typically an epoch or target block number.
+    pub key_id: u64,
+    /// AES-GCM nonce.
+    pub nonce: FixedBytes<12>,
+    /// Sealed transaction bytes, including the authentication tag.
+    pub ciphertext: Bytes,
+}
+
+impl EncryptedTransaction {
+    /// Identifier of the encrypted transaction, the keccak hash of its ciphertext.
+    pub fn id(&self) -> B256 {
+        keccak256(&self.ciphertext)
+    }
+
+    /// Encrypts a raw transaction for `key_id`.
+    pub fn seal(key_id: u64, key: &B256, nonce: FixedBytes<12>, raw_tx: &[u8]) -> Self {
+        let ciphertext = Aes256Gcm::new(key.as_slice().into())
+            .encrypt(
+                Nonce::from_slice(nonce.as_slice()),
+                Payload {
+                    msg: raw_tx,
+                    aad: &key_id.to_be_bytes(),
+                },
+            )
+            .expect("AES-GCM encryption of in-memory data is infallible");
+        Self {
+            key_id,
+            nonce,
+            ciphertext: ciphertext.into(),
+        }
+    }
+
+    /// Decrypts the transaction with the revealed key, returning the raw transaction bytes.
+    pub fn open(&self, key: &B256) -> Result<Bytes, EncryptedTxError> {
+        Aes256Gcm::new(key.as_slice().into())
+            .decrypt(
+                Nonce::from_slice(self.nonce.as_slice()),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &self.key_id.to_be_bytes(),
+                },
+            )
+            .map(Into::into)
+            .map_err(|_| EncryptedTxError::DecryptionFailed)
+    }
+}
+
+/// A decryption key revealed for a block, supplied in the payload attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecryptionKey {
+    /// Identifier of the key.
+    pub key_id: u64,
+    /// AES-256 key.
+    pub key: B256,
+}
+
+/// Arrival-ordered lane of encrypted transactions awaiting their decryption key, written by
+/// `evolve_sendEncryptedTransaction` and drained by the payload builder once the matching
+/// key is revealed.
+#[derive(Debug)]
+pub struct EncryptedPool {
+    txs: Mutex<VecDeque<EncryptedTransaction>>,
+    capacity: usize,
+}
+
+impl EncryptedPool {
+    /// Creates a lane that holds at most `capacity` transactions.
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            txs: Mutex::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Appends an encrypted transaction, returning its id.
+    pub fn insert(&self, tx: EncryptedTransaction) -> Result<B256, EncryptedTxError> {
+        let size = tx.ciphertext.len();
+        if size == 0 || size > MAX_ENCRYPTED_TX_BYTES {
+            return Err(EncryptedTxError::InvalidSize(size));
+        }
+        let id = tx.id();
+        let mut txs = self.txs.lock().unwrap_or_else(|e| e.into_inner());
+        if txs.iter().any(|pending| pending.id() == id) {
+            return Err(EncryptedTxError::AlreadyKnown(id));
+        }
+        if txs.len() >= self.capacity {
+            return Err(EncryptedTxError::PoolFull);
+        }
+        txs.push_back(tx);
+        Ok(id)
+    }
+
+    /// Pending transactions sealed for one of `keys`, in arrival order.
+    pub fn revealed(&self, keys: &[DecryptionKey]) -> Vec<(EncryptedTransaction, B256)> {
+        let txs = self.txs.lock().unwrap_or_else(|e| e.into_inner());
+        txs.iter()
+            .filter_map(|tx| {
+                keys.iter()
+                    .find(|key| key.key_id == tx.key_id)
+                    .map(|key| (tx.clone(), key.key))
+            })
+            .collect()
+    }
+
+    /// Drops every transaction sealed for one of `key_ids`, once their block has been built.
+    pub fn remove_revealed(&self, key_ids: &[u64]) {
+        let mut txs = self.txs.lock().unwrap_or_else(|e| e.into_inner());
+        txs.retain(|tx| !key_ids.contains(&tx.key_id));
+    }
+
+    /// Number of pending encrypted transactions.
+    pub fn len(&self) -> usize {
+        self.txs.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Returns `true` if no encrypted transactions are pending.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for EncryptedPool {
+    fn default() -> Self {
+        Self::new(MAX_ENCRYPTED_POOL_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: B256 = B256::repeat_byte(7);
+
+    #[test]
+    fn seal_and_open_round_trip() {
+        let tx = EncryptedTransaction::seal(3, &KEY, FixedBytes::repeat_byte(1), b"raw tx");
+        assert_eq!(tx.open(&KEY).unwrap().as_ref(), b"raw tx");
+        assert_eq!(
+            tx.open(&B256::repeat_byte(8)),
+            Err(EncryptedTxError::DecryptionFailed)
+        );
+    }
+
+    #[test]
+    fn key_id_is_authenticated() {
+        let mut tx = EncryptedTransaction::seal(3, &KEY, FixedBytes::repeat_byte(1), b"raw tx");
+        tx.key_id = 4;
+        assert_eq!(tx.open(&KEY), Err(EncryptedTxError::DecryptionFailed));
+    }
+
+    #[test]
+    fn revealed_preserves_arrival_order() {
+        let pool = EncryptedPool::new(8);
+        let first = EncryptedTransaction::seal(1, &KEY, FixedBytes::repeat_byte(1), b"first");
+        let other = EncryptedTransaction::seal(2, &KEY, FixedBytes::repeat_byte(2), b"other");
+        let second = EncryptedTransaction::seal(1, &KEY, FixedBytes::repeat_byte(3), b"second");
+        pool.insert(first.clone()).unwrap();
+        pool.insert(other).unwrap();
+        pool.insert(second.clone()).unwrap();
+        assert_eq!(
+            pool.insert(first.clone()),
+            Err(EncryptedTxError::AlreadyKnown(first.id()))
+        );
+
+        let revealed = pool.revealed(&[DecryptionKey {
+            key_id: 1,
+            key: KEY,
+        }]);
+        let ids: Vec<_> = revealed.iter().map(|(tx, _)| tx.id()).collect();
+        assert_eq!(ids, vec![first.id(), second.id()]);
+
+        pool.remove_revealed(&[1]);
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -0,0 +1,268 @@
+use alloy_primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+/// Default width of the window fees are sampled over, in seconds of block time.
+pub const DEFAULT_FEE_WINDOW_SECS: u64 = 10;
+
+/// Default percentile of sampled priority fees suggested by the estimator.
+pub const DEFAULT_FEE_PERCENTILE: f64 = 60.0;
+
+/// Fee data sampled from a single block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockFees {
+    /// Block number.
+    pub number: u64,
+    /// Block timestamp, in seconds.
+    pub timestamp: u64,
+    /// Gas used by the block.
+    pub gas_used: u64,
+    /// Effective priority fee per gas of each transaction in the block.
+    pub tips: Vec<u128>,
+}
+
+/// Suggested fees for a transaction targeting the next block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEstimate {
+    /// Base fee of the next block.
+    pub base_fee_per_gas: U256,
+    /// Suggested priority fee: the requested percentile of recent tips, raised to the
+    /// txpool's minimum priority fee, or zero while blocks run below the gas target.
+    pub max_priority_fee_per_gas: U256,
+    /// Suggested fee cap: twice the next base fee plus the priority fee.
+    pub max_fee_per_gas: U256,
+    /// Percentile the priority fee was taken at.
+    pub percentile: f64,
+    /// Width of the sampled window, in seconds.
+    pub window_secs: u64,
+    /// Number of blocks in the window.
+    pub blocks: u64,
+    /// Number of transactions whose tips were sampled.
+    pub transactions: u64,
+    /// Whether the latest block used less gas than the chain's gas target, so transactions
+    /// need no tip to be included.
+    #[serde(default)]
+    pub below_gas_target: bool,
+    /// Account the base fee is redirected to instead of being burned, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_sink: Option<Address>,
+}
+
+/// Tracks recent priority fees over a window of block time rather than a block count. Fed by
+/// the task following canonical blocks and served by `evolve_estimateFeePerGas`.
+///
+/// With sub-second blocks a fixed block count covers a varying and often very short span of
+/// time, so a burst of blocks would push everything else out. Sampling by timestamp keeps the
+/// estimate stable whatever the block rate.
+#[derive(Debug)]
+pub struct FeeEstimator {
+    window_secs: AtomicU64,
+    /// Gas target of the chain, zero when it has none.
+    gas_target: AtomicU64,
+    inner: Mutex<EstimatorState>,
+}
+
+#[derive(Debug, Default)]
+struct EstimatorState {
+    blocks: VecDeque<BlockFees>,
+    next_base_fee: u64,
+    base_fee_sink: Option<Address>,
+}
+
+impl FeeEstimator {
+    /// Creates an empty estimator sampling the last `window_secs` seconds of blocks.
+    pub fn new(window_secs: u64) -> Self {
+        Self {
+            window_secs: AtomicU64::new(window_secs),
+            gas_target: AtomicU64::new(0),
+            inner: Mutex::new(EstimatorState::default()),
+        }
+    }
+
+    /// Sets the width of the sampled window, in seconds.
+    pub fn set_window_secs(&self, window_secs: u64) {
+        self.window_secs.store(window_secs, Ordering::Relaxed);
+    }
+
+    /// Sets the gas used per block below which no tip is suggested.
+    pub fn set_gas_target(&self, gas_target: Option<u64>) {
+        self.gas_target
+            .store(gas_target.unwrap_or_default(), Ordering::Relaxed);
+    }
+
+    /// Adds a committed block, along with the base fee and base fee sink of the block after
+    /// it, and drops blocks that fell out of the window.
+    pub fn record_block(
+        &self,
+        block: BlockFees,
+        next_base_fee: u64,
+        base_fee_sink: Option<Address>,
+    ) {
+        let window_secs = self.window_secs.load(Ordering::Relaxed);
+        let mut state = self.inner.lock().expect("fee estimator lock poisoned");
+        // A block replacing one already sampled means the chain reorganized.
+        while state
+            .blocks
+            .back()
+            .is_some_and(|last| last.number >= block.number)
+        {
+            state.blocks.pop_back();
+        }
+        let oldest = block.timestamp.saturating_sub(window_secs);
+        while state
+            .blocks
+            .front()
+            .is_some_and(|first| first.timestamp < oldest)
+        {
+            state.blocks.pop_front();
+        }
+        state.blocks.push_back(block);
+        state.next_base_fee = next_base_fee;
+        state.base_fee_sink = base_fee_sink;
+    }
+
+    /// Drops the samples of blocks from `first_reverted` onwards.
+    pub fn revert(&self, first_reverted: u64) {
+        let mut state = self.inner.lock().expect("fee estimator lock poisoned");
+        state.blocks.retain(|block| block.number < first_reverted);
+    }
+
+    /// Suggests fees at the given tip `percentile`, never below `min_priority_fee` unless the
+    /// latest block ran below the gas target, in which case no tip is needed.
+    pub fn estimate(&self, percentile: f64, min_priority_fee: u128) -> FeeEstimate {
+        let state = self.inner.lock().expect("fee estimator lock poisoned");
+        let mut tips: Vec<u128> = state
+            .blocks
+            .iter()
+            .flat_map(|block| block.tips.iter().copied())
+            .collect();
+        tips.sort_unstable();
+
+        let gas_target = self.gas_target.load(Ordering::Relaxed);
+        let below_gas_target = state
+            .blocks
+            .back()
+            .is_some_and(|latest| latest.gas_used < gas_target);
+        let tip = if below_gas_target {
+            0
+        } else {
+            percentile_of(&tips, percentile).max(min_priority_fee)
+        };
+        let base_fee = U256::from(state.next_base_fee);
+        FeeEstimate {
+            base_fee_per_gas: base_fee,
+            max_priority_fee_per_gas: U256::from(tip),
+            max_fee_per_gas: base_fee
+                .saturating_mul(U256::from(2))
+                .saturating_add(U256::from(tip)),
+            percentile,
+            window_secs: self.window_secs.load(Ordering::Relaxed),
+            blocks: state.blocks.len() as u64,
+            transactions: tips.len() as u64,
+            below_gas_target,
+            base_fee_sink: state.base_fee_sink,
+        }
+    }
+}
+
+impl Default for FeeEstimator {
+    fn default() -> Self {
+        Self::new(DEFAULT_FEE_WINDOW_SECS)
+    }
+}
+
+/// Nearest-rank percentile of sorted `values`; zero when there are none.
+fn percentile_of(values: &[u128], percentile: f64) -> u128 {
+    if values.is_empty() {
+        return 0;
+    }
+    let rank = (percentile / 100.0 * values.len() as f64).ceil() as usize;
+    values[rank.clamp(1, values.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(number: u64, timestamp: u64, tips: &[u128]) -> BlockFees {
+        BlockFees {
+            number,
+            timestamp,
+            gas_used: 21_000 * tips.len() as u64,
+            tips: tips.to_vec(),
+        }
+    }
+
+    #[test]
+    fn window_is_measured_in_block_time() {
+        let estimator = FeeEstimator::new(10);
+        estimator.record_block(block(1, 100, &[50]), 7, None);
+        // A burst of sub-second blocks sharing a timestamp keeps older samples in the window.
+        for number in 2..=40 {
+            estimator.record_block(block(number, 109, &[]), 7, None);
+        }
+        assert_eq!(
+            estimator.estimate(50.0, 0).max_priority_fee_per_gas,
+            U256::from(50)
+        );
+
+        estimator.record_block(block(41, 111, &[1, 2, 3, 4]), 8, None);
+        let estimate = estimator.estimate(50.0, 0);
+        assert_eq!(estimate.transactions, 4);
+        assert_eq!(estimate.max_priority_fee_per_gas, U256::from(2));
+        assert_eq!(estimate.base_fee_per_gas, U256::from(8));
+        assert_eq!(estimate.max_fee_per_gas, U256::from(18));
+    }
+
+    #[test]
+    fn tip_respects_minimum_and_reorgs() {
+        let estimator = FeeEstimator::new(10);
+        assert_eq!(
+            estimator.estimate(60.0, 5).max_priority_fee_per_gas,
+            U256::from(5)
+        );
+
+        estimator.record_block(block(1, 100, &[100]), 7, None);
+        estimator.record_block(block(2, 101, &[200]), 7, None);
+        estimator.revert(2);
+        assert_eq!(
+            estimator.estimate(100.0, 0).max_priority_fee_per_gas,
+            U256::from(100)
+        );
+
+        estimator.record_block(block(2, 101, &[300]), 7, None);
+        estimator.record_block(block(2, 101, &[400]), 7, None);
+        let estimate = estimator.estimate(100.0, 0);
+        assert_eq!(estimate.blocks, 2);
+        assert_eq!(estimate.max_priority_fee_per_gas, U256::from(400));
+    }
+
+    #[test]
+    fn no_tip_suggested_below_gas_target() {
+        let estimator = FeeEstimator::new(10);
+        estimator.set_gas_target(Some(50_000));
+        estimator.record_block(block(1, 100, &[100, 200, 300]), 7, None);
+        let estimate = estimator.estimate(60.0, 5);
+        assert!(!estimate.below_gas_target);
+        assert_eq!(estimate.max_priority_fee_per_gas, U256::from(200));
+
+        estimator.record_block(block(2, 101, &[400]), 7, None);
+        let estimate = estimator.estimate(60.0, 5);
+        assert!(estimate.below_gas_target);
+        assert_eq!(estimate.max_priority_fee_per_gas, U256::ZERO);
+        assert_eq!(estimate.max_fee_per_gas, U256::from(14));
+
+        estimator.set_gas_target(None);
+        assert_eq!(
+            estimator.estimate(60.0, 5).max_priority_fee_per_gas,
+            U256::from(300)
+        );
+    }
+}
@@ -11,16 +11,75 @@ pub mod types;
 /// Configuration for Evolve functionality.
 pub mod config;
 
+/// Per-payload transaction inclusion reports.
+pub mod build_report;
+
+/// Operator-signed commitments to produced payloads.
+pub mod attestation;
+
+/// Rolling Merkle commitments over the hashes of recent blocks.
+pub mod commitment;
+
+/// Native currency metadata.
+pub mod currency;
+
+/// Atomic transaction bundles targeting a specific block.
+pub mod bundle;
+
+/// Encrypted transaction lane with decryption at build time.
+pub mod encrypted;
+
+/// Operator-injected maintenance transactions selected ahead of regular pool traffic.
+pub mod maintenance;
+
 /// RPC modules for Evolve functionality.
 pub mod rpc;
 
 /// Custom consensus implementation for Evolve.
 pub mod consensus;
 
+/// Block `prev_randao` source policy.
+pub mod randao;
+
+/// Shutdown coordination between payload building and process exit.
+pub mod shutdown;
+
+/// DA inclusion and finality backing the `safe` and `finalized` block tags.
+pub mod da;
+
+/// Chain-level KPIs aggregated from committed blocks.
+pub mod chain_stats;
+
+/// Fee suggestions from priority fees sampled over a window of block time.
+pub mod fee_estimator;
+
+/// Handling of payload timestamps that do not advance past the parent's.
+pub mod timestamp;
+
+/// Post-build audit of transaction ordering against a declared policy.
+pub mod ordering;
+
 #[cfg(test)]
 mod tests;
 
 // Re-export public types
+pub use attestation::{BuildAttestation, BuildAttestationStore};
+pub use build_report::{
+    BuildReport, InclusionMix, TxInclusionDecision, TxInclusionReason, TxSource,
+};
+pub use bundle::{Bundle, BundleError, BundlePool};
+pub use chain_stats::{BlockStats, ChainStats, ChainStatsTracker, FeeTotals, SupplyChange};
+pub use commitment::{
+    block_commitment_root, DEFAULT_BLOCK_COMMITMENT_WINDOW, MAX_BLOCK_COMMITMENT_WINDOW,
+};
 pub use config::{EvolveConfig, DEFAULT_MAX_TXPOOL_BYTES, DEFAULT_MAX_TXPOOL_GAS};
-pub use consensus::{EvolveConsensus, EvolveConsensusBuilder};
+pub use consensus::{unread_consensus_extras, EvolveConsensus, EvolveConsensusBuilder};
+pub use currency::NativeCurrency;
+pub use da::{DaBlockRef, DaFinalityError, DaFinalityTracker, DaStatus};
+pub use encrypted::{DecryptionKey, EncryptedPool, EncryptedTransaction, EncryptedTxError};
+pub use fee_estimator::{BlockFees, FeeEstimate, FeeEstimator};
+pub use maintenance::{MaintenanceLane, MaintenanceLaneError};
+pub use ordering::{AuditedTx, OrderingAudit, OrderingPolicy, OrderingReport, OrderingViolation};
+pub use randao::{derive_prev_randao, PrevRandaoPolicy};
+pub use timestamp::{TimestampAdjustment, TimestampError, TimestampPolicy};
 pub use types::{EvolvePayloadAttributes, PayloadAttributesError};
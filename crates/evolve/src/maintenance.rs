@@ -0,0 +1,177 @@
+use alloy_primitives::{Address, B256};
+use std::{collections::HashSet, sync::Mutex};
+
+/// Maximum number of transactions flagged for the maintenance lane at once.
+pub const MAX_MAINTENANCE_LANE_SIZE: usize = 256;
+
+/// Errors returned when a transaction cannot be flagged for the maintenance lane.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MaintenanceLaneError {
+    /// The lane is full.
+    #[error("maintenance lane is full")]
+    LaneFull,
+    /// The transaction is already flagged.
+    #[error("maintenance transaction {0} already known")]
+    AlreadyKnown(B256),
+}
+
+/// Operator-injected transactions that are selected ahead of regular pool traffic and are
+/// exempt from the chain's minimum gas price.
+///
+/// The transactions themselves live in the txpool like any other local transaction; the lane
+/// only records their hashes, so nonce ordering, replacement and eviction stay with the pool.
+/// It is written by `evolve_sendMaintenanceTransaction` and read by the txpool validator, the
+/// txpool RPC and the payload builder.
+#[derive(Debug)]
+pub struct MaintenanceLane {
+    hashes: Mutex<HashSet<B256>>,
+    capacity: usize,
+}
+
+impl MaintenanceLane {
+    /// Creates a lane that flags at most `capacity` transactions.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            hashes: Mutex::new(HashSet::new()),
+            capacity,
+        }
+    }
+
+    /// Flags a transaction for the lane.
+    pub fn insert(&self, hash: B256) -> Result<(), MaintenanceLaneError> {
+        let mut hashes = self.hashes.lock().unwrap_or_else(|e| e.into_inner());
+        if hashes.contains(&hash) {
+            return Err(MaintenanceLaneError::AlreadyKnown(hash));
+        }
+        if hashes.len() >= self.capacity {
+            return Err(MaintenanceLaneError::LaneFull);
+        }
+        hashes.insert(hash);
+        Ok(())
+    }
+
+    /// Removes the flag from a transaction. Returns false if it was not flagged.
+    pub fn remove(&self, hash: &B256) -> bool {
+        self.hashes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(hash)
+    }
+
+    /// Returns `true` if the transaction is flagged for the lane.
+    pub fn contains(&self, hash: &B256) -> bool {
+        self.hashes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(hash)
+    }
+
+    /// Keeps only the flags for which `keep` returns true, typically the transactions still
+    /// in the pool.
+    pub fn retain(&self, mut keep: impl FnMut(&B256) -> bool) {
+        self.hashes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|hash| keep(hash));
+    }
+
+    /// Splits `txs`, given in the order the pool would include them, into the lane
+    /// transactions and the rest, each keeping its relative order.
+    ///
+    /// `key` returns the hash and sender of a transaction. A lane transaction stays with the
+    /// rest when a regular transaction of the same sender precedes it, as moving it ahead
+    /// would break the sender's nonce sequence.
+    pub fn split<T>(
+        &self,
+        txs: impl IntoIterator<Item = T>,
+        key: impl Fn(&T) -> (B256, Address),
+    ) -> (Vec<T>, Vec<T>) {
+        let flagged = self
+            .hashes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let mut lane = Vec::new();
+        let mut rest = Vec::new();
+        let mut blocked = HashSet::new();
+        for tx in txs {
+            let (hash, sender) = key(&tx);
+            if flagged.contains(&hash) && !blocked.contains(&sender) {
+                lane.push(tx);
+            } else {
+                blocked.insert(sender);
+                rest.push(tx);
+            }
+        }
+        (lane, rest)
+    }
+
+    /// Number of flagged transactions.
+    pub fn len(&self) -> usize {
+        self.hashes.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Returns `true` if no transactions are flagged.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for MaintenanceLane {
+    fn default() -> Self {
+        Self::new(MAX_MAINTENANCE_LANE_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPERATOR: Address = Address::repeat_byte(1);
+    const USER: Address = Address::repeat_byte(2);
+
+    #[test]
+    fn insert_rejects_duplicates_and_overflow() {
+        let lane = MaintenanceLane::new(1);
+        lane.insert(B256::repeat_byte(1)).unwrap();
+        assert_eq!(
+            lane.insert(B256::repeat_byte(1)),
+            Err(MaintenanceLaneError::AlreadyKnown(B256::repeat_byte(1)))
+        );
+        assert_eq!(
+            lane.insert(B256::repeat_byte(2)),
+            Err(MaintenanceLaneError::LaneFull)
+        );
+
+        lane.retain(|_| false);
+        assert!(lane.is_empty());
+    }
+
+    #[test]
+    fn split_moves_lane_transactions_ahead_without_breaking_nonces() {
+        let lane = MaintenanceLane::new(8);
+        let oracle = B256::repeat_byte(0xa1);
+        let admin = B256::repeat_byte(0xa2);
+        let after_user_tx = B256::repeat_byte(0xa3);
+        for hash in [oracle, admin, after_user_tx] {
+            lane.insert(hash).unwrap();
+        }
+
+        let user = B256::repeat_byte(0xb1);
+        let user_next = B256::repeat_byte(0xb2);
+        let txs = [
+            (user, USER),
+            (oracle, OPERATOR),
+            (user_next, USER),
+            (after_user_tx, USER),
+            (admin, OPERATOR),
+        ];
+        let (flagged, rest) = lane.split(txs, |tx| *tx);
+
+        assert_eq!(flagged, vec![(oracle, OPERATOR), (admin, OPERATOR)]);
+        assert_eq!(
+            rest,
+            vec![(user, USER), (user_next, USER), (after_user_tx, USER)]
+        );
+    }
+}
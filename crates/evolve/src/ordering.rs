@@ -0,0 +1,351 @@
+use alloy_primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, fmt, str::FromStr, sync::Mutex, time::Instant};
+
+/// Maximum number of offending pairs kept per audited block.
+pub const MAX_REPORTED_VIOLATIONS: usize = 32;
+
+/// Maximum number of ordering reports retained before the oldest are evicted.
+pub const DEFAULT_MAX_ORDERING_REPORTS: usize = 1024;
+
+/// Order a sequencer claims to include transactions in.
+///
+/// Transactions of the same sender are always in nonce order, so only pairs of different
+/// senders are held to the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[repr(u8)]
+pub enum OrderingPolicy {
+    /// Higher effective tips first, as the txpool orders pending transactions.
+    Tip = 1,
+    /// Earlier arrival in the txpool first.
+    Fifo = 2,
+}
+
+impl fmt::Display for OrderingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Tip => "tip",
+            Self::Fifo => "fifo",
+        })
+    }
+}
+
+impl FromStr for OrderingPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tip" => Ok(Self::Tip),
+            "fifo" => Ok(Self::Fifo),
+            other => Err(format!(
+                "unknown ordering policy {other:?} (expected tip or fifo)"
+            )),
+        }
+    }
+}
+
+/// A transaction of a built block, as seen by the ordering audit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditedTx {
+    /// Position in the block.
+    pub index: usize,
+    /// Transaction hash.
+    pub hash: B256,
+    /// Recovered sender.
+    pub sender: Address,
+    /// Called address; `None` for contract creations.
+    pub to: Option<Address>,
+    /// Effective tip per gas at the block's base fee.
+    pub tip: u128,
+    /// When the txpool received the transaction; unknown for transactions that never
+    /// passed through this node's pool.
+    pub arrival: Option<Instant>,
+    /// Placed by a rule that takes precedence over the policy (maintenance lane, bundle,
+    /// encrypted lane), and therefore not audited.
+    pub exempt: bool,
+}
+
+/// A pair of transactions included in the opposite order of the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderingViolation {
+    /// Position of the transaction included first.
+    pub earlier_index: usize,
+    /// Hash of the transaction included first.
+    pub earlier_hash: B256,
+    /// Position of the transaction the policy places first.
+    pub later_index: usize,
+    /// Hash of the transaction the policy places first.
+    pub later_hash: B256,
+}
+
+impl fmt::Display for OrderingViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {} should precede {} at {}",
+            self.later_hash, self.later_index, self.earlier_hash, self.earlier_index
+        )
+    }
+}
+
+/// Result of auditing a block against an [`OrderingPolicy`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderingAudit {
+    /// Number of audited pairs of different senders.
+    pub checked_pairs: usize,
+    /// Number of pairs in the opposite order of the policy.
+    pub violations: usize,
+    /// The first offending pairs, up to [`MAX_REPORTED_VIOLATIONS`].
+    pub examples: Vec<OrderingViolation>,
+}
+
+impl OrderingAudit {
+    /// Returns true if no pair violates the policy.
+    pub const fn is_compliant(&self) -> bool {
+        self.violations == 0
+    }
+}
+
+impl OrderingPolicy {
+    /// Checks every pair of non-exempt transactions from different senders in `txs`, given
+    /// in block order.
+    ///
+    /// Under [`OrderingPolicy::Fifo`], pairs with an unknown arrival time are skipped.
+    pub fn audit(self, txs: &[AuditedTx]) -> OrderingAudit {
+        let mut audit = OrderingAudit::default();
+        let audited: Vec<&AuditedTx> = txs.iter().filter(|tx| !tx.exempt).collect();
+        for (position, earlier) in audited.iter().enumerate() {
+            for later in &audited[position + 1..] {
+                if earlier.sender == later.sender {
+                    continue;
+                }
+                let out_of_order = match self {
+                    Self::Tip => Some(later.tip > earlier.tip),
+                    Self::Fifo => earlier
+                        .arrival
+                        .zip(later.arrival)
+                        .map(|(earlier, later)| later < earlier),
+                };
+                let Some(out_of_order) = out_of_order else {
+                    continue;
+                };
+                audit.checked_pairs += 1;
+                if out_of_order {
+                    audit.violations += 1;
+                    if audit.examples.len() < MAX_REPORTED_VIOLATIONS {
+                        audit.examples.push(OrderingViolation {
+                            earlier_index: earlier.index,
+                            earlier_hash: earlier.hash,
+                            later_index: later.index,
+                            later_hash: later.hash,
+                        });
+                    }
+                }
+            }
+        }
+        audit
+    }
+}
+
+/// Ordering quality of a built block, published via `evolve_getOrderingReport`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderingReport {
+    /// Block number.
+    pub block_number: u64,
+    /// Block hash.
+    pub block_hash: B256,
+    /// Number of transactions in the block.
+    pub transactions: usize,
+    /// Number of sandwich patterns, see [`count_sandwiches`].
+    pub sandwich_patterns: usize,
+    /// Pairs of different senders ordered against [`OrderingPolicy::Tip`].
+    pub tip_inversions: usize,
+    /// Pairs of different senders compared by tip.
+    pub tip_checked_pairs: usize,
+    /// Pairs of different senders ordered against [`OrderingPolicy::Fifo`].
+    pub fifo_inversions: usize,
+    /// Pairs of different senders compared by arrival; pairs with an unknown arrival are
+    /// not compared.
+    pub fifo_checked_pairs: usize,
+}
+
+impl OrderingReport {
+    /// Computes the report of a block from its transactions, given in block order.
+    pub fn new(block_number: u64, block_hash: B256, txs: &[AuditedTx]) -> Self {
+        let tip = OrderingPolicy::Tip.audit(txs);
+        let fifo = OrderingPolicy::Fifo.audit(txs);
+        Self {
+            block_number,
+            block_hash,
+            transactions: txs.len(),
+            sandwich_patterns: count_sandwiches(txs),
+            tip_inversions: tip.violations,
+            tip_checked_pairs: tip.checked_pairs,
+            fifo_inversions: fifo.violations,
+            fifo_checked_pairs: fifo.checked_pairs,
+        }
+    }
+}
+
+/// Counts adjacent transaction triples `A, V, B` in which `A` and `B` come from the same
+/// sender, `V` from another one, and all three call the same contract: the shape of a
+/// front-run and back-run around a victim.
+///
+/// Exempt transactions are included, since a bundle can carry a sandwich too.
+pub fn count_sandwiches(txs: &[AuditedTx]) -> usize {
+    txs.windows(3)
+        .filter(|window| {
+            let [front, victim, back] = window else {
+                return false;
+            };
+            front.to.is_some()
+                && front.sender == back.sender
+                && front.sender != victim.sender
+                && front.to == victim.to
+                && front.to == back.to
+        })
+        .count()
+}
+
+/// Bounded, insertion-ordered store of ordering reports keyed by block hash, written by the
+/// payload builder and read by the `evolve_getOrderingReport` RPC.
+#[derive(Debug)]
+pub struct OrderingReportStore {
+    reports: Mutex<VecDeque<OrderingReport>>,
+    capacity: usize,
+}
+
+impl OrderingReportStore {
+    /// Creates a store that retains at most `capacity` reports.
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            reports: Mutex::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Stores a report, replacing any earlier report for the same block and evicting the
+    /// oldest entry once the store is full.
+    pub fn insert(&self, report: OrderingReport) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut reports = self.reports.lock().unwrap_or_else(|e| e.into_inner());
+        reports.retain(|stored| stored.block_hash != report.block_hash);
+        while reports.len() >= self.capacity {
+            reports.pop_front();
+        }
+        reports.push_back(report);
+    }
+
+    /// Returns the report recorded for `block_hash`, if still retained.
+    pub fn get(&self, block_hash: &B256) -> Option<OrderingReport> {
+        let reports = self.reports.lock().unwrap_or_else(|e| e.into_inner());
+        reports
+            .iter()
+            .find(|report| report.block_hash == *block_hash)
+            .cloned()
+    }
+}
+
+impl Default for OrderingReportStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ORDERING_REPORTS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn tx(index: usize, sender: u8, tip: u128, arrival: Option<Instant>) -> AuditedTx {
+        AuditedTx {
+            index,
+            hash: B256::with_last_byte(index as u8),
+            sender: Address::repeat_byte(sender),
+            to: Some(Address::repeat_byte(0xee)),
+            tip,
+            arrival,
+            exempt: false,
+        }
+    }
+
+    #[test]
+    fn tip_audit_reports_lower_tips_ahead_of_higher_ones() {
+        let txs = [
+            tx(0, 1, 10, None),
+            // Same sender as the first, so its higher tip is not a violation.
+            tx(1, 1, 30, None),
+            tx(2, 2, 20, None),
+            AuditedTx {
+                exempt: true,
+                ..tx(3, 3, 100, None)
+            },
+        ];
+        let audit = OrderingPolicy::Tip.audit(&txs);
+        assert_eq!(audit.checked_pairs, 2);
+        assert_eq!(audit.violations, 1);
+        assert_eq!(audit.examples[0].earlier_index, 0);
+        assert_eq!(audit.examples[0].later_index, 2);
+        assert!(!audit.is_compliant());
+    }
+
+    #[test]
+    fn fifo_audit_skips_unknown_arrivals() {
+        let start = Instant::now();
+        let at = |millis| Some(start + Duration::from_millis(millis));
+        let txs = [tx(0, 1, 0, at(5)), tx(1, 2, 0, None), tx(2, 3, 0, at(1))];
+        let audit = OrderingPolicy::Fifo.audit(&txs);
+        assert_eq!(audit.checked_pairs, 1);
+        assert_eq!(audit.violations, 1);
+
+        let in_order = [tx(0, 1, 0, at(1)), tx(1, 2, 0, at(5))];
+        assert!(OrderingPolicy::Fifo.audit(&in_order).is_compliant());
+    }
+
+    #[test]
+    fn report_counts_sandwiches_and_inversions() {
+        let start = Instant::now();
+        let at = |millis| Some(start + Duration::from_millis(millis));
+        let txs = [
+            // Sender 1 brackets sender 2 on the same contract.
+            tx(0, 1, 30, at(3)),
+            tx(1, 2, 20, at(1)),
+            tx(2, 1, 10, at(4)),
+            // Same shape on different contracts is not a sandwich.
+            AuditedTx {
+                to: Some(Address::repeat_byte(0xdd)),
+                ..tx(3, 3, 5, None)
+            },
+        ];
+        let report = OrderingReport::new(9, B256::repeat_byte(9), &txs);
+        assert_eq!(report.transactions, 4);
+        assert_eq!(report.sandwich_patterns, 1);
+        // Pairs of different senders: (0,1), (0,3), (1,2), (1,3), (2,3); only (1,2) is
+        // ordered against tips.
+        assert_eq!(report.tip_checked_pairs, 5);
+        assert_eq!(report.tip_inversions, 1);
+        // Only (0,1) and (1,2) have known arrivals; (0,1) is out of arrival order.
+        assert_eq!(report.fifo_checked_pairs, 2);
+        assert_eq!(report.fifo_inversions, 1);
+
+        let store = OrderingReportStore::new(1);
+        store.insert(report.clone());
+        assert_eq!(store.get(&report.block_hash), Some(report));
+        store.insert(OrderingReport::new(10, B256::repeat_byte(10), &[]));
+        assert!(store.get(&B256::repeat_byte(9)).is_none());
+    }
+
+    #[test]
+    fn policy_round_trips_through_cli_names() {
+        for policy in [OrderingPolicy::Tip, OrderingPolicy::Fifo] {
+            assert_eq!(policy.to_string().parse::<OrderingPolicy>(), Ok(policy));
+        }
+        assert!("lifo".parse::<OrderingPolicy>().is_err());
+    }
+}
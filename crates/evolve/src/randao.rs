@@ -0,0 +1,58 @@
+use alloy_primitives::{keccak256, B256};
+use serde::{Deserialize, Serialize};
+
+/// Where the `prev_randao` value of a block comes from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrevRandaoPolicy {
+    /// Use whatever value ev-node supplies in the payload attributes.
+    #[default]
+    Attributes,
+    /// Ignore the supplied value and derive it from the parent block with
+    /// [`derive_prev_randao`]. Imported blocks must carry the derived value.
+    Derived,
+}
+
+impl PrevRandaoPolicy {
+    /// Returns true if blocks must carry the derived `prev_randao`.
+    pub const fn is_derived(&self) -> bool {
+        matches!(self, Self::Derived)
+    }
+}
+
+/// Deterministically derives the `prev_randao` of a child block as
+/// `keccak256(parent_hash ++ parent_mix_hash)`.
+///
+/// ev-node can compute the same value, so the derivation is reproducible
+/// by any full node without trusting the sequencer's randomness.
+pub fn derive_prev_randao(parent_hash: B256, parent_mix_hash: B256) -> B256 {
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(parent_hash.as_slice());
+    buf[32..].copy_from_slice(parent_mix_hash.as_slice());
+    keccak256(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derivation_depends_on_both_inputs() {
+        let a = derive_prev_randao(B256::repeat_byte(1), B256::repeat_byte(2));
+        assert_eq!(
+            a,
+            derive_prev_randao(B256::repeat_byte(1), B256::repeat_byte(2))
+        );
+        assert_ne!(
+            a,
+            derive_prev_randao(B256::repeat_byte(2), B256::repeat_byte(1))
+        );
+    }
+
+    #[test]
+    fn policy_deserializes_from_camel_case() {
+        let policy: PrevRandaoPolicy = serde_json::from_str("\"derived\"").unwrap();
+        assert_eq!(policy, PrevRandaoPolicy::Derived);
+        assert_eq!(PrevRandaoPolicy::default(), PrevRandaoPolicy::Attributes);
+    }
+}
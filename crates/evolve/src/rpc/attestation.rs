@@ -0,0 +1,36 @@
+use crate::attestation::{BuildAttestation, BuildAttestationStore};
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::sync::Arc;
+
+/// Evolve build attestation RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveAttestationApi {
+    /// Returns the operator-signed attestation of the payload that produced `block_hash`, if
+    /// this node built it and still retains the attestation.
+    #[method(name = "getBuildAttestation")]
+    async fn get_build_attestation(&self, block_hash: B256) -> RpcResult<Option<BuildAttestation>>;
+}
+
+/// Implementation of the Evolve build attestation RPC API
+#[derive(Debug)]
+pub struct EvolveAttestationApiImpl {
+    /// Store the payload builder records attestations into
+    store: Arc<BuildAttestationStore>,
+}
+
+impl EvolveAttestationApiImpl {
+    /// Creates a new instance serving the attestations recorded in `store`.
+    pub const fn new(store: Arc<BuildAttestationStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl EvolveAttestationApiServer for EvolveAttestationApiImpl {
+    async fn get_build_attestation(&self, block_hash: B256) -> RpcResult<Option<BuildAttestation>> {
+        Ok(self.store.get(&block_hash))
+    }
+}
@@ -0,0 +1,35 @@
+use crate::build_report::{BuildReport, BuildReportStore};
+use alloy_rpc_types_engine::PayloadId;
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::sync::Arc;
+
+/// Evolve build report RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveBuildReportApi {
+    /// Returns the per-transaction inclusion trace recorded for `payload_id`, if any.
+    #[method(name = "getBuildReport")]
+    async fn get_build_report(&self, payload_id: PayloadId) -> RpcResult<Option<BuildReport>>;
+}
+
+/// Implementation of the Evolve build report RPC API
+#[derive(Debug)]
+pub struct EvolveBuildReportApiImpl {
+    /// Store the payload builder records reports into
+    store: Arc<BuildReportStore>,
+}
+
+impl EvolveBuildReportApiImpl {
+    /// Creates a new instance serving the reports of `store`.
+    pub const fn new(store: Arc<BuildReportStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl EvolveBuildReportApiServer for EvolveBuildReportApiImpl {
+    async fn get_build_report(&self, payload_id: PayloadId) -> RpcResult<Option<BuildReport>> {
+        Ok(self.store.get(&payload_id))
+    }
+}
@@ -0,0 +1,39 @@
+use crate::bundle::{Bundle, BundlePool};
+use alloy_primitives::{Bytes, B256, U64};
+use async_trait::async_trait;
+use jsonrpsee::types::{error::INVALID_PARAMS_CODE, ErrorObject};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::sync::Arc;
+
+/// Evolve bundle RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveBundleApi {
+    /// Submits transactions that must be included contiguously in `target_block`, or not at
+    /// all. Returns the bundle hash.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(&self, txs: Vec<Bytes>, target_block: U64) -> RpcResult<B256>;
+}
+
+/// Implementation of the Evolve bundle RPC API
+#[derive(Debug)]
+pub struct EvolveBundleApiImpl {
+    /// Pool the payload builder draws bundles from
+    pool: Arc<BundlePool>,
+}
+
+impl EvolveBundleApiImpl {
+    /// Creates a new instance adding bundles to `pool`.
+    pub const fn new(pool: Arc<BundlePool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EvolveBundleApiServer for EvolveBundleApiImpl {
+    async fn send_bundle(&self, txs: Vec<Bytes>, target_block: U64) -> RpcResult<B256> {
+        Bundle::new(txs, target_block.to())
+            .and_then(|bundle| self.pool.insert(bundle))
+            .map_err(|err| ErrorObject::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>))
+    }
+}
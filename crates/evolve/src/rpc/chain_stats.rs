@@ -0,0 +1,34 @@
+use crate::chain_stats::{ChainStats, ChainStatsTracker};
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::sync::Arc;
+
+/// Evolve chain statistics RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveChainStatsApi {
+    /// Returns chain KPIs aggregated over the blocks committed since the node started.
+    #[method(name = "getChainStats")]
+    async fn get_chain_stats(&self) -> RpcResult<ChainStats>;
+}
+
+/// Implementation of the Evolve chain statistics RPC API
+#[derive(Debug)]
+pub struct EvolveChainStatsApiImpl {
+    /// Tracker fed by the task following canonical blocks
+    tracker: Arc<ChainStatsTracker>,
+}
+
+impl EvolveChainStatsApiImpl {
+    /// Creates a new instance serving the statistics of `tracker`.
+    pub const fn new(tracker: Arc<ChainStatsTracker>) -> Self {
+        Self { tracker }
+    }
+}
+
+#[async_trait]
+impl EvolveChainStatsApiServer for EvolveChainStatsApiImpl {
+    async fn get_chain_stats(&self) -> RpcResult<ChainStats> {
+        Ok(self.tracker.snapshot())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::currency::NativeCurrency;
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+
+/// Chain configuration reported by `evolve_getConfig`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvolveChainConfig {
+    /// Native currency metadata.
+    pub native_currency: NativeCurrency,
+}
+
+/// Evolve chain configuration RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveConfigApi {
+    /// Returns the chainspec-derived Evolve configuration.
+    #[method(name = "getConfig")]
+    async fn get_config(&self) -> RpcResult<EvolveChainConfig>;
+}
+
+/// Implementation of the Evolve chain configuration RPC API
+#[derive(Debug, Default)]
+pub struct EvolveConfigApiImpl {
+    config: EvolveChainConfig,
+}
+
+impl EvolveConfigApiImpl {
+    /// Creates a new instance serving the given configuration.
+    pub const fn new(config: EvolveChainConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl EvolveConfigApiServer for EvolveConfigApiImpl {
+    async fn get_config(&self) -> RpcResult<EvolveChainConfig> {
+        Ok(self.config.clone())
+    }
+}
@@ -0,0 +1,46 @@
+use crate::da::{DaFinalityTracker, DaStatus};
+use async_trait::async_trait;
+use jsonrpsee::types::{error::INVALID_PARAMS_CODE, ErrorObject};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::sync::Arc;
+
+/// Evolve DA finality RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveDaApi {
+    /// Records the highest DA-included (`safe`) and DA-finalized (`finalized`) blocks
+    /// reported by ev-node. Returns the resulting status.
+    #[method(name = "setDaStatus")]
+    async fn set_da_status(&self, update: DaStatus) -> RpcResult<DaStatus>;
+
+    /// Returns the DA-backed `safe` and `finalized` blocks.
+    #[method(name = "getDaStatus")]
+    async fn get_da_status(&self) -> RpcResult<DaStatus>;
+}
+
+/// Implementation of the Evolve DA finality RPC API
+#[derive(Debug)]
+pub struct EvolveDaApiImpl {
+    /// Tracker mapped onto the `safe` and `finalized` block tags
+    tracker: Arc<DaFinalityTracker>,
+}
+
+impl EvolveDaApiImpl {
+    /// Creates a new instance recording DA status in `tracker`.
+    pub const fn new(tracker: Arc<DaFinalityTracker>) -> Self {
+        Self { tracker }
+    }
+}
+
+#[async_trait]
+impl EvolveDaApiServer for EvolveDaApiImpl {
+    async fn set_da_status(&self, update: DaStatus) -> RpcResult<DaStatus> {
+        self.tracker
+            .update(update)
+            .map_err(|err| ErrorObject::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>))
+    }
+
+    async fn get_da_status(&self) -> RpcResult<DaStatus> {
+        Ok(self.tracker.status())
+    }
+}
@@ -0,0 +1,48 @@
+use crate::encrypted::{EncryptedPool, EncryptedTransaction};
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use jsonrpsee::types::{error::INVALID_PARAMS_CODE, ErrorObject};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::sync::Arc;
+
+/// Evolve encrypted transaction RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveEncryptedApi {
+    /// Queues a transaction encrypted to a committee key. It is decrypted and executed in
+    /// arrival order once the key is revealed in the payload attributes. Returns the
+    /// encrypted transaction id.
+    #[method(name = "sendEncryptedTransaction")]
+    async fn send_encrypted_transaction(&self, tx: EncryptedTransaction) -> RpcResult<B256>;
+
+    /// Returns the number of encrypted transactions awaiting their key.
+    #[method(name = "encryptedPoolSize")]
+    async fn encrypted_pool_size(&self) -> RpcResult<usize>;
+}
+
+/// Implementation of the Evolve encrypted transaction RPC API
+#[derive(Debug)]
+pub struct EvolveEncryptedApiImpl {
+    /// Lane the payload builder decrypts from
+    pool: Arc<EncryptedPool>,
+}
+
+impl EvolveEncryptedApiImpl {
+    /// Creates a new instance queueing transactions in `pool`.
+    pub const fn new(pool: Arc<EncryptedPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EvolveEncryptedApiServer for EvolveEncryptedApiImpl {
+    async fn send_encrypted_transaction(&self, tx: EncryptedTransaction) -> RpcResult<B256> {
+        self.pool
+            .insert(tx)
+            .map_err(|err| ErrorObject::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>))
+    }
+
+    async fn encrypted_pool_size(&self) -> RpcResult<usize> {
+        Ok(self.pool.len())
+    }
+}
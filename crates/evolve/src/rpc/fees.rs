@@ -0,0 +1,70 @@
+use crate::fee_estimator::{FeeEstimate, FeeEstimator, DEFAULT_FEE_PERCENTILE};
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use jsonrpsee::types::{error::INVALID_PARAMS_CODE, ErrorObject};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::sync::Arc;
+
+/// Evolve fee estimation RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveFeeApi {
+    /// Suggests fees for the next block from the priority fees paid over the configured
+    /// window of block time, at `percentile` (0-100, default 60).
+    #[method(name = "estimateFeePerGas")]
+    async fn estimate_fee_per_gas(&self, percentile: Option<f64>) -> RpcResult<FeeEstimate>;
+}
+
+/// `eth_maxPriorityFeePerGas` answered by the Evolve fee estimator.
+///
+/// Registered in place of the default by `ev-dev`, so wallets and tooling that fill in fees
+/// automatically use the same suggestion as `evolve_estimateFeePerGas`.
+#[rpc(server)]
+pub trait EvolveFeeDefaultsApi {
+    /// Returns the priority fee suggested at the default percentile.
+    #[method(name = "eth_maxPriorityFeePerGas")]
+    async fn max_priority_fee_per_gas(&self) -> RpcResult<U256>;
+}
+
+/// Implementation of the Evolve fee estimation RPC APIs
+#[derive(Debug, Clone)]
+pub struct EvolveFeeApiImpl {
+    estimator: Arc<FeeEstimator>,
+    min_priority_fee: u128,
+}
+
+impl EvolveFeeApiImpl {
+    /// Creates a new instance answered from `estimator`, never suggesting less than the
+    /// txpool's `min_priority_fee`.
+    pub fn new(estimator: Arc<FeeEstimator>, min_priority_fee: Option<u128>) -> Self {
+        Self {
+            estimator,
+            min_priority_fee: min_priority_fee.unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl EvolveFeeApiServer for EvolveFeeApiImpl {
+    async fn estimate_fee_per_gas(&self, percentile: Option<f64>) -> RpcResult<FeeEstimate> {
+        let percentile = percentile.unwrap_or(DEFAULT_FEE_PERCENTILE);
+        if !(0.0..=100.0).contains(&percentile) {
+            return Err(ErrorObject::owned(
+                INVALID_PARAMS_CODE,
+                format!("percentile {percentile} is not in 0..=100"),
+                None::<()>,
+            ));
+        }
+        Ok(self.estimator.estimate(percentile, self.min_priority_fee))
+    }
+}
+
+#[async_trait]
+impl EvolveFeeDefaultsApiServer for EvolveFeeApiImpl {
+    async fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
+        Ok(self
+            .estimator
+            .estimate(DEFAULT_FEE_PERCENTILE, self.min_priority_fee)
+            .max_priority_fee_per_gas)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::maintenance::MaintenanceLane;
+use alloy_consensus::transaction::{SignerRecoverable, TxHashRef};
+use alloy_eips::Decodable2718;
+use alloy_primitives::{Bytes, B256};
+use async_trait::async_trait;
+use ev_primitives::TransactionSigned;
+use jsonrpsee::types::{error::INVALID_PARAMS_CODE, ErrorObject};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use std::sync::Arc;
+
+/// Evolve maintenance transaction RPC API trait
+///
+/// Served on the authenticated Engine API endpoint only.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveMaintenanceApi {
+    /// Submits a signed transaction to the maintenance lane. It is pooled as a local
+    /// transaction, exempt from the minimum gas price, and selected ahead of regular pool
+    /// traffic. Returns the transaction hash.
+    #[method(name = "sendMaintenanceTransaction")]
+    async fn send_maintenance_transaction(&self, tx: Bytes) -> RpcResult<B256>;
+
+    /// Returns the number of transactions flagged for the maintenance lane.
+    #[method(name = "maintenanceLaneSize")]
+    async fn maintenance_lane_size(&self) -> RpcResult<usize>;
+}
+
+/// Implementation of the Evolve maintenance transaction RPC API
+#[derive(Debug)]
+pub struct EvolveMaintenanceApiImpl<Pool> {
+    /// Transaction pool the lane transactions are added to
+    pool: Pool,
+    /// Lane flagging the transactions
+    lane: Arc<MaintenanceLane>,
+}
+
+impl<Pool> EvolveMaintenanceApiImpl<Pool> {
+    /// Creates a new instance adding transactions to `pool` and flagging them in `lane`.
+    pub const fn new(pool: Pool, lane: Arc<MaintenanceLane>) -> Self {
+        Self { pool, lane }
+    }
+}
+
+fn invalid_params(err: impl ToString) -> ErrorObject<'static> {
+    ErrorObject::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>)
+}
+
+#[async_trait]
+impl<Pool> EvolveMaintenanceApiServer for EvolveMaintenanceApiImpl<Pool>
+where
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Send
+        + Sync
+        + 'static,
+{
+    async fn send_maintenance_transaction(&self, tx: Bytes) -> RpcResult<B256> {
+        let tx = TransactionSigned::decode_2718_exact(tx.as_ref()).map_err(invalid_params)?;
+        let hash = *tx.tx_hash();
+        let tx = tx.try_into_recovered().map_err(invalid_params)?;
+
+        // Flagged before it reaches the pool, so the validator already exempts it from the
+        // minimum gas price.
+        self.lane.insert(hash).map_err(invalid_params)?;
+        if let Err(err) = self
+            .pool
+            .add_consensus_transaction(tx, TransactionOrigin::Local)
+            .await
+        {
+            self.lane.remove(&hash);
+            return Err(invalid_params(err));
+        }
+        Ok(hash)
+    }
+
+    async fn maintenance_lane_size(&self) -> RpcResult<usize> {
+        Ok(self.lane.len())
+    }
+}
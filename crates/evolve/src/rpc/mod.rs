@@ -1,4 +1,52 @@
 /// Evolve RPC modules
 pub mod txpool;
 
+/// Build report RPC module
+pub mod build_report;
+
+/// Ordering report RPC module
+pub mod ordering;
+
+/// Build attestation RPC module
+pub mod attestation;
+
+/// Bundle submission RPC module
+pub mod bundle;
+
+/// Encrypted transaction submission RPC module
+pub mod encrypted;
+
+/// Maintenance transaction submission RPC module
+pub mod maintenance;
+
+/// Chain configuration RPC module
+pub mod config;
+
+/// Node status RPC module
+pub mod status;
+
+/// DA finality RPC module
+pub mod da;
+
+/// Chain statistics RPC module
+pub mod chain_stats;
+
+/// Fee estimation RPC module
+pub mod fees;
+
+/// EIP-5792 wallet call RPC module
+pub mod wallet;
+
+pub use attestation::EvolveAttestationApiImpl;
+pub use build_report::EvolveBuildReportApiImpl;
+pub use bundle::EvolveBundleApiImpl;
+pub use chain_stats::EvolveChainStatsApiImpl;
+pub use config::{EvolveChainConfig, EvolveConfigApiImpl};
+pub use da::EvolveDaApiImpl;
+pub use encrypted::EvolveEncryptedApiImpl;
+pub use fees::EvolveFeeApiImpl;
+pub use maintenance::EvolveMaintenanceApiImpl;
+pub use ordering::EvolveOrderingApiImpl;
+pub use status::EvolveStatusApiImpl;
 pub use txpool::{create_evolve_txpool_module, EvolveTxpoolApiImpl};
+pub use wallet::{WalletCallsApiImpl, WalletCallsBackend};
@@ -0,0 +1,35 @@
+use crate::ordering::{OrderingReport, OrderingReportStore};
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::sync::Arc;
+
+/// Evolve ordering report RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveOrderingApi {
+    /// Returns the ordering report recorded for the block `block_hash`, if any.
+    #[method(name = "getOrderingReport")]
+    async fn get_ordering_report(&self, block_hash: B256) -> RpcResult<Option<OrderingReport>>;
+}
+
+/// Implementation of the Evolve ordering report RPC API
+#[derive(Debug)]
+pub struct EvolveOrderingApiImpl {
+    /// Store the payload builder records reports into
+    store: Arc<OrderingReportStore>,
+}
+
+impl EvolveOrderingApiImpl {
+    /// Creates a new instance serving the reports recorded in `store`.
+    pub const fn new(store: Arc<OrderingReportStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl EvolveOrderingApiServer for EvolveOrderingApiImpl {
+    async fn get_ordering_report(&self, block_hash: B256) -> RpcResult<Option<OrderingReport>> {
+        Ok(self.store.get(&block_hash))
+    }
+}
@@ -0,0 +1,34 @@
+use crate::shutdown::{NodeStatus, ShutdownCoordinator};
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::sync::Arc;
+
+/// Evolve node status RPC API trait
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveStatusApi {
+    /// Returns whether the node is running or draining for shutdown.
+    #[method(name = "nodeStatus")]
+    async fn node_status(&self) -> RpcResult<NodeStatus>;
+}
+
+/// Implementation of the Evolve node status RPC API
+#[derive(Debug)]
+pub struct EvolveStatusApiImpl {
+    /// Coordinator of the node's shutdown
+    coordinator: Arc<ShutdownCoordinator>,
+}
+
+impl EvolveStatusApiImpl {
+    /// Creates a new instance reporting the status of `coordinator`.
+    pub const fn new(coordinator: Arc<ShutdownCoordinator>) -> Self {
+        Self { coordinator }
+    }
+}
+
+#[async_trait]
+impl EvolveStatusApiServer for EvolveStatusApiImpl {
+    async fn node_status(&self) -> RpcResult<NodeStatus> {
+        Ok(self.coordinator.status())
+    }
+}
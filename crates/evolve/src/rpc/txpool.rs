@@ -1,10 +1,20 @@
-use crate::config::current_block_gas_limit;
-use alloy_primitives::Bytes;
+use crate::{
+    bundle::BundlePool,
+    config::{current_block_gas_limit, SubpoolLimit, TxpoolLimits},
+    maintenance::MaintenanceLane,
+};
+use alloy_consensus::Typed2718;
+use alloy_primitives::{Bytes, B256};
 use async_trait::async_trait;
 use jsonrpsee::tracing::debug;
 use jsonrpsee_core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
 
 /// Evolve txpool RPC API trait
 #[rpc(server, namespace = "txpoolExt")]
@@ -12,6 +22,45 @@ pub trait EvolveTxpoolApi {
     /// Get transactions from the pool up to the configured `max_bytes` limit
     #[method(name = "getTxs")]
     async fn get_txs(&self) -> RpcResult<Vec<Bytes>>;
+
+    /// Report pool usage against the selection caps and the sub-pool eviction limits
+    #[method(name = "inspect", aliases = ["evolve_txpoolInspect"])]
+    async fn inspect(&self) -> RpcResult<TxpoolInspect>;
+}
+
+/// Usage of a single txpool sub-pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubpoolUsage {
+    /// Number of transactions in the sub-pool.
+    pub transactions: usize,
+    /// Total size of the sub-pool in bytes.
+    pub bytes: usize,
+    /// Limit past which the pool evicts transactions, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eviction_watermark: Option<SubpoolLimit>,
+}
+
+/// Txpool usage returned by `evolve_txpoolInspect`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolInspect {
+    /// Executable transactions.
+    pub pending: SubpoolUsage,
+    /// Transactions waiting for the base fee to drop.
+    pub basefee: SubpoolUsage,
+    /// Transactions with nonce gaps or insufficient balance.
+    pub queued: SubpoolUsage,
+    /// Blob transactions.
+    pub blob: SubpoolUsage,
+    /// Sum of the gas limits of pending transactions.
+    pub pending_gas: u64,
+    /// Byte cap applied by `txpoolExt_getTxs` (0 = unlimited).
+    pub max_txpool_bytes: u64,
+    /// Gas cap applied by `txpoolExt_getTxs` (0 = unlimited).
+    pub max_txpool_gas: u64,
+    /// Number of pooled transactions by EIP-2718 type.
+    pub by_type: BTreeMap<u8, usize>,
 }
 
 /// Implementation of the Evolve txpool RPC API
@@ -21,24 +70,51 @@ pub struct EvolveTxpoolApiImpl<Pool> {
     pool: Pool,
     /// Maximum bytes allowed for transaction selection
     max_bytes: u64,
+    /// Bundles held back from selection until their target block
+    bundles: Arc<BundlePool>,
+    /// Lane whose transactions are selected ahead of all others
+    maintenance: Arc<MaintenanceLane>,
+    /// Sub-pool eviction limits the pool was built with, if known
+    limits: Option<TxpoolLimits>,
 }
 
 impl<Pool> EvolveTxpoolApiImpl<Pool> {
     /// Creates a new instance of `TxpoolApi`.
-    pub const fn new(pool: Pool, max_bytes: u64) -> Self {
-        Self { pool, max_bytes }
+    pub fn new(pool: Pool, max_bytes: u64) -> Self {
+        Self {
+            pool,
+            max_bytes,
+            bundles: Arc::default(),
+            maintenance: Arc::default(),
+            limits: None,
+        }
+    }
+
+    /// Sets the pool the bundles targeting the next block are selected from.
+    pub fn with_bundles(mut self, bundles: Arc<BundlePool>) -> Self {
+        self.bundles = bundles;
+        self
+    }
+
+    /// Sets the maintenance lane whose transactions are selected first.
+    pub fn with_maintenance_lane(mut self, lane: Arc<MaintenanceLane>) -> Self {
+        self.maintenance = lane;
+        self
+    }
+
+    /// Sets the sub-pool eviction limits reported by `evolve_txpoolInspect`.
+    pub const fn with_limits(mut self, limits: TxpoolLimits) -> Self {
+        self.limits = Some(limits);
+        self
     }
 }
 
 /// Creates a new Evolve txpool RPC module
-pub const fn create_evolve_txpool_module<Pool>(
-    pool: Pool,
-    max_bytes: u64,
-) -> EvolveTxpoolApiImpl<Pool>
+pub fn create_evolve_txpool_module<Pool>(pool: Pool, max_bytes: u64) -> EvolveTxpoolApiImpl<Pool>
 where
     Pool: TransactionPool + Send + Sync + 'static,
 {
-    EvolveTxpoolApiImpl { pool, max_bytes }
+    EvolveTxpoolApiImpl::new(pool, max_bytes)
 }
 
 #[async_trait]
@@ -59,30 +135,72 @@ where
         // Determine the active gas cap for selection
         let gas_cap = current_block_gas_limit();
 
-        // Use best_transactions() which returns an iterator of transactions
-        // ordered by their priority (gas price/priority fee)
-        for best_tx in self.pool.best_transactions() {
-            // Convert for gas introspection and encoding
-            let tx = best_tx.transaction.clone().into_consensus_with2718();
+        let bundles = self.bundles.bundles_for_next_block();
+        let bundled: HashSet<B256> = bundles
+            .iter()
+            .flat_map(|bundle| bundle.tx_hashes.iter().copied())
+            .collect();
 
-            // Size and gas of this tx
-            let sz = best_tx.encoded_length() as u64;
-            let gas = best_tx.gas_limit();
+        // Use best_transactions() which returns an iterator of transactions
+        // ordered by their priority (gas price/priority fee). Maintenance lane
+        // transactions are taken out of that order and selected first.
+        let (maintenance, regular) = self.maintenance.split(
+            self.pool
+                .best_transactions()
+                .filter(|tx| !bundled.contains(tx.hash())),
+            |tx| (*tx.hash(), tx.sender()),
+        );
 
+        let mut fits = |sz: u64, gas: u64| {
             // Enforce byte cap if configured (> 0)
             if self.max_bytes > 0 && total_bytes + sz > self.max_bytes {
-                break;
+                return false;
             }
             // Enforce gas cap if configured (> 0)
             if gas_cap > 0 && total_gas + gas > gas_cap {
+                return false;
+            }
+            total_bytes += sz;
+            total_gas += gas;
+            true
+        };
+
+        // A lane transaction that does not fit holds back the rest of the lane, which may
+        // depend on its nonce, and leaves no room for regular traffic either.
+        let mut lane_selected = true;
+        for best_tx in maintenance {
+            if !fits(best_tx.encoded_length() as u64, best_tx.gas_limit()) {
+                lane_selected = false;
                 break;
             }
+            let tx = best_tx.transaction.clone().into_consensus_with2718();
+            selected_txs.push(tx.encoded_bytes().clone());
+        }
+        if lane_selected {
+            // Bundles for the next block go next so they stay contiguous; a bundle that does
+            // not fit the caps is skipped as a whole.
+            for bundle in bundles {
+                let sz: u64 = bundle.transactions.iter().map(|tx| tx.len() as u64).sum();
+                if !fits(sz, bundle.gas_limit) {
+                    continue;
+                }
+                selected_txs.extend(bundle.transactions);
+            }
 
-            let bz = tx.encoded_bytes();
-            selected_txs.push(bz.clone());
+            for best_tx in regular {
+                // Convert for gas introspection and encoding
+                let tx = best_tx.transaction.clone().into_consensus_with2718();
 
-            total_bytes += sz;
-            total_gas += gas;
+                // Size and gas of this tx
+                let sz = best_tx.encoded_length() as u64;
+                let gas = best_tx.gas_limit();
+                if !fits(sz, gas) {
+                    break;
+                }
+
+                let bz = tx.encoded_bytes();
+                selected_txs.push(bz.clone());
+            }
         }
 
         debug!(
@@ -93,6 +211,40 @@ where
         );
         Ok(selected_txs)
     }
+
+    async fn inspect(&self) -> RpcResult<TxpoolInspect> {
+        let size = self.pool.pool_size();
+        let limits = self.limits;
+        let usage = |transactions, bytes, limit: fn(TxpoolLimits) -> SubpoolLimit| SubpoolUsage {
+            transactions,
+            bytes,
+            eviction_watermark: limits.map(limit),
+        };
+
+        let pending_gas = self
+            .pool
+            .pending_transactions()
+            .iter()
+            .map(|tx| tx.gas_limit())
+            .sum();
+
+        let all = self.pool.all_transactions();
+        let mut by_type = BTreeMap::new();
+        for tx in all.pending.iter().chain(all.queued.iter()) {
+            *by_type.entry(tx.transaction.ty()).or_default() += 1;
+        }
+
+        Ok(TxpoolInspect {
+            pending: usage(size.pending, size.pending_size, |l| l.pending),
+            basefee: usage(size.basefee, size.basefee_size, |l| l.basefee),
+            queued: usage(size.queued, size.queued_size, |l| l.queued),
+            blob: usage(size.blob, size.blob_size, |l| l.blob),
+            pending_gas,
+            max_txpool_bytes: self.max_bytes,
+            max_txpool_gas: current_block_gas_limit(),
+            by_type,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -109,6 +261,39 @@ mod tests {
         assert_eq!(config.max_txpool_gas, DEFAULT_MAX_TXPOOL_GAS);
     }
 
+    #[test]
+    fn test_inspect_serialization() {
+        use super::{SubpoolUsage, TxpoolInspect};
+        use crate::config::SubpoolLimit;
+
+        let usage = SubpoolUsage {
+            transactions: 2,
+            bytes: 300,
+            eviction_watermark: Some(SubpoolLimit {
+                max_transactions: 10,
+                max_bytes: 1000,
+            }),
+        };
+        let inspect = TxpoolInspect {
+            pending: usage,
+            basefee: SubpoolUsage {
+                eviction_watermark: None,
+                ..usage
+            },
+            queued: usage,
+            blob: usage,
+            pending_gas: 42_000,
+            max_txpool_bytes: 1000,
+            max_txpool_gas: 30_000_000,
+            by_type: [(2u8, 2usize)].into(),
+        };
+        let json = serde_json::to_value(&inspect).unwrap();
+        assert_eq!(json["pending"]["evictionWatermark"]["maxBytes"], 1000);
+        assert!(json["basefee"].get("evictionWatermark").is_none());
+        assert_eq!(json["byType"]["2"], 2);
+        assert_eq!(json["pendingGas"], 42_000);
+    }
+
     #[test]
     fn test_evolve_txpool_api_creation() {
         // This test verifies that we can create the API with different max_bytes values
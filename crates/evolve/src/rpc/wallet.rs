@@ -0,0 +1,502 @@
+//! EIP-5792 call batches sent as EvNode transactions.
+//!
+//! `wallet_sendCalls` turns the calls of an EIP-5792 request into one atomic EvNode batch sent
+//! from the node's account, and `wallet_getCallsStatus` reports it from the batch receipt. The
+//! batch hash is the call batch id unless the caller picks its own id.
+
+use alloy_primitives::{Address, Bytes, TxKind, B256, U256, U64};
+use async_trait::async_trait;
+use ev_primitives::Call;
+use jsonrpsee::types::{error::INVALID_PARAMS_CODE, ErrorObject};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+/// EIP-5792 version of requests and status responses.
+pub const WALLET_CALLS_VERSION: &str = "2.0.0";
+
+/// Most calls a `wallet_sendCalls` request may contain.
+pub const MAX_WALLET_CALLS: usize = 128;
+
+/// Most caller-chosen batch ids remembered; the oldest are forgotten first.
+pub const MAX_TRACKED_CALL_IDS: usize = 4096;
+
+/// Status of a batch that is in the pool.
+pub const CALLS_STATUS_PENDING: u16 = 100;
+/// Status of a batch included in a block.
+pub const CALLS_STATUS_CONFIRMED: u16 = 200;
+/// Status of a batch included in a block that reverted.
+pub const CALLS_STATUS_REVERTED: u16 = 500;
+
+/// EIP-1193 error code for requests from an account the node does not send for.
+const UNAUTHORIZED_CODE: i32 = 4100;
+/// EIP-5792 error code for a required capability the node does not support.
+const UNSUPPORTED_CAPABILITY_CODE: i32 = 5700;
+/// EIP-5792 error code for a chain id other than the node's.
+const UNSUPPORTED_CHAIN_ID_CODE: i32 = 5710;
+/// EIP-5792 error code for a caller-chosen id already in use.
+const DUPLICATE_ID_CODE: i32 = 5720;
+/// EIP-5792 error code for an id without a batch.
+const UNKNOWN_BUNDLE_ID_CODE: i32 = 5730;
+/// EIP-5792 error code for a batch with too many calls.
+const BUNDLE_TOO_LARGE_CODE: i32 = 5740;
+
+/// Capabilities of a request or call, keyed by name.
+pub type WalletCapabilities = BTreeMap<String, serde_json::Value>;
+
+/// A call of a `wallet_sendCalls` request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletCall {
+    /// Called address; a contract is created when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// Calldata, or init code of a created contract.
+    #[serde(default)]
+    pub data: Bytes,
+    /// Value sent with the call.
+    #[serde(default)]
+    pub value: U256,
+    /// Capabilities of the call.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub capabilities: WalletCapabilities,
+}
+
+impl From<WalletCall> for Call {
+    fn from(call: WalletCall) -> Self {
+        Self {
+            to: call.to.map_or(TxKind::Create, TxKind::Call),
+            value: call.value,
+            input: call.data,
+            gas_limit: None,
+        }
+    }
+}
+
+/// `wallet_sendCalls` request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletSendCallsRequest {
+    /// EIP-5792 version of the request.
+    pub version: String,
+    /// Caller-chosen batch id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Account sending the calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Address>,
+    /// Chain the calls are sent on.
+    pub chain_id: U64,
+    /// Whether the calls must execute atomically. EvNode batches always do.
+    pub atomic_required: bool,
+    /// Calls executed in order.
+    pub calls: Vec<WalletCall>,
+    /// Capabilities of the request.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub capabilities: WalletCapabilities,
+}
+
+/// `wallet_sendCalls` response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletSendCallsResult {
+    /// Id to query the batch status with.
+    pub id: String,
+}
+
+/// Log of a batch receipt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletCallsLog {
+    /// Emitting contract.
+    pub address: Address,
+    /// Log data.
+    pub data: Bytes,
+    /// Log topics.
+    pub topics: Vec<B256>,
+}
+
+/// Receipt of an included batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletCallsReceipt {
+    /// Logs of every call.
+    pub logs: Vec<WalletCallsLog>,
+    /// `1` if the batch succeeded, `0` if it reverted.
+    pub status: U64,
+    /// Hash of the including block.
+    pub block_hash: B256,
+    /// Number of the including block.
+    pub block_number: U64,
+    /// Gas used by the batch.
+    pub gas_used: U64,
+    /// Hash of the EvNode transaction.
+    pub transaction_hash: B256,
+}
+
+/// `wallet_getCallsStatus` response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletCallsStatus {
+    /// EIP-5792 version of the response.
+    pub version: String,
+    /// Batch id.
+    pub id: String,
+    /// Chain the batch was sent on.
+    pub chain_id: U64,
+    /// [`CALLS_STATUS_PENDING`], [`CALLS_STATUS_CONFIRMED`] or [`CALLS_STATUS_REVERTED`].
+    pub status: u16,
+    /// Whether the calls executed atomically.
+    pub atomic: bool,
+    /// Receipt of the batch once included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipts: Option<Vec<WalletCallsReceipt>>,
+}
+
+/// State of a sent batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalletBatchState {
+    /// The batch waits in the pool.
+    Pending,
+    /// The batch was included in a block.
+    Included(WalletCallsReceipt),
+}
+
+/// Node side of the wallet call RPCs: signs batches for its account and looks them up.
+#[async_trait]
+pub trait WalletCallsBackend: Send + Sync {
+    /// Returns the account batches are sent from.
+    fn account(&self) -> Address;
+
+    /// Returns the chain id batches are signed for.
+    fn chain_id(&self) -> u64;
+
+    /// Signs and submits an atomic batch of `calls` and returns its hash.
+    async fn send_calls(&self, calls: Vec<Call>) -> Result<B256, ErrorObject<'static>>;
+
+    /// Returns the state of the batch with `tx_hash`, or `None` if it is unknown.
+    fn batch_state(&self, tx_hash: B256) -> Result<Option<WalletBatchState>, ErrorObject<'static>>;
+}
+
+/// Caller-chosen batch ids and the batches they name.
+#[derive(Debug, Default)]
+struct CallIds {
+    hashes: HashMap<String, B256>,
+    order: VecDeque<String>,
+}
+
+impl CallIds {
+    fn contains(&self, id: &str) -> bool {
+        self.hashes.contains_key(id)
+    }
+
+    fn insert(&mut self, id: String, tx_hash: B256) {
+        if self.order.len() == MAX_TRACKED_CALL_IDS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.hashes.remove(&oldest);
+            }
+        }
+        self.order.push_back(id.clone());
+        self.hashes.insert(id, tx_hash);
+    }
+
+    /// Resolves `id` to a batch hash: a caller-chosen id, or the hash itself.
+    fn resolve(&self, id: &str) -> Option<B256> {
+        self.hashes.get(id).copied().or_else(|| id.parse().ok())
+    }
+}
+
+fn wallet_error(code: i32, message: impl ToString) -> ErrorObject<'static> {
+    ErrorObject::owned(code, message.to_string(), None::<()>)
+}
+
+/// Returns the first capability in `capabilities` not marked optional.
+fn required_capability(capabilities: &WalletCapabilities) -> Option<&str> {
+    capabilities
+        .iter()
+        .find(|(_, value)| value.get("optional") != Some(&serde_json::Value::Bool(true)))
+        .map(|(name, _)| name.as_str())
+}
+
+/// Checks `request` against the node's `account` and `chain_id` and returns its calls.
+fn batch_calls(
+    request: WalletSendCallsRequest,
+    account: Address,
+    chain_id: u64,
+) -> Result<Vec<Call>, ErrorObject<'static>> {
+    if request.version != WALLET_CALLS_VERSION {
+        return Err(wallet_error(
+            INVALID_PARAMS_CODE,
+            format!(
+                "unsupported version {}, expected {WALLET_CALLS_VERSION}",
+                request.version
+            ),
+        ));
+    }
+    if request.chain_id.to::<u64>() != chain_id {
+        return Err(wallet_error(
+            UNSUPPORTED_CHAIN_ID_CODE,
+            format!("unsupported chain id {}", request.chain_id),
+        ));
+    }
+    if let Some(from) = request.from.filter(|from| *from != account) {
+        return Err(wallet_error(
+            UNAUTHORIZED_CODE,
+            format!("calls can only be sent from {account}, not {from}"),
+        ));
+    }
+    if request.calls.is_empty() {
+        return Err(wallet_error(INVALID_PARAMS_CODE, "no calls"));
+    }
+    if request.calls.len() > MAX_WALLET_CALLS {
+        return Err(wallet_error(
+            BUNDLE_TOO_LARGE_CODE,
+            format!("at most {MAX_WALLET_CALLS} calls are supported"),
+        ));
+    }
+    let unsupported = required_capability(&request.capabilities).or_else(|| {
+        request
+            .calls
+            .iter()
+            .find_map(|call| required_capability(&call.capabilities))
+    });
+    if let Some(name) = unsupported {
+        return Err(wallet_error(
+            UNSUPPORTED_CAPABILITY_CODE,
+            format!("unsupported capability {name}"),
+        ));
+    }
+    Ok(request.calls.into_iter().map(Call::from).collect())
+}
+
+/// EIP-5792 wallet call RPC API.
+#[rpc(server, namespace = "wallet")]
+pub trait WalletCallsApi {
+    /// Sends `calls` from the node's account as one atomic EvNode batch and returns the id to
+    /// query its status with.
+    #[method(name = "sendCalls")]
+    async fn send_calls(&self, request: WalletSendCallsRequest)
+        -> RpcResult<WalletSendCallsResult>;
+
+    /// Returns the status of the batch with `id`, with its receipt once included.
+    #[method(name = "getCallsStatus")]
+    async fn get_calls_status(&self, id: String) -> RpcResult<WalletCallsStatus>;
+
+    /// Returns the capabilities of `address` per chain id, limited to `chain_ids` if given.
+    #[method(name = "getCapabilities")]
+    async fn get_capabilities(
+        &self,
+        address: Address,
+        chain_ids: Option<Vec<U64>>,
+    ) -> RpcResult<BTreeMap<String, WalletCapabilities>>;
+}
+
+/// Implementation of the EIP-5792 wallet call RPC API.
+#[derive(Debug)]
+pub struct WalletCallsApiImpl<Backend> {
+    backend: Arc<Backend>,
+    ids: Mutex<CallIds>,
+}
+
+impl<Backend> WalletCallsApiImpl<Backend> {
+    /// Creates a new instance sending batches through `backend`.
+    pub fn new(backend: Arc<Backend>) -> Self {
+        Self {
+            backend,
+            ids: Mutex::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<Backend> WalletCallsApiServer for WalletCallsApiImpl<Backend>
+where
+    Backend: WalletCallsBackend + 'static,
+{
+    async fn send_calls(
+        &self,
+        request: WalletSendCallsRequest,
+    ) -> RpcResult<WalletSendCallsResult> {
+        let id = request.id.clone();
+        if id.as_deref().is_some_and(|id| {
+            self.ids
+                .lock()
+                .expect("call ids lock poisoned")
+                .contains(id)
+        }) {
+            return Err(wallet_error(DUPLICATE_ID_CODE, "duplicate batch id"));
+        }
+        let calls = batch_calls(request, self.backend.account(), self.backend.chain_id())?;
+        let tx_hash = self.backend.send_calls(calls).await?;
+        let id = match id {
+            Some(id) => {
+                self.ids
+                    .lock()
+                    .expect("call ids lock poisoned")
+                    .insert(id.clone(), tx_hash);
+                id
+            }
+            None => tx_hash.to_string(),
+        };
+        Ok(WalletSendCallsResult { id })
+    }
+
+    async fn get_calls_status(&self, id: String) -> RpcResult<WalletCallsStatus> {
+        let unknown = || wallet_error(UNKNOWN_BUNDLE_ID_CODE, "unknown batch id");
+        let tx_hash = self
+            .ids
+            .lock()
+            .expect("call ids lock poisoned")
+            .resolve(&id)
+            .ok_or_else(unknown)?;
+        let (status, receipts) = match self.backend.batch_state(tx_hash)?.ok_or_else(unknown)? {
+            WalletBatchState::Pending => (CALLS_STATUS_PENDING, None),
+            WalletBatchState::Included(receipt) => {
+                let status = if receipt.status.is_zero() {
+                    CALLS_STATUS_REVERTED
+                } else {
+                    CALLS_STATUS_CONFIRMED
+                };
+                (status, Some(vec![receipt]))
+            }
+        };
+        Ok(WalletCallsStatus {
+            version: WALLET_CALLS_VERSION.to_string(),
+            id,
+            chain_id: U64::from(self.backend.chain_id()),
+            status,
+            atomic: true,
+            receipts,
+        })
+    }
+
+    async fn get_capabilities(
+        &self,
+        address: Address,
+        chain_ids: Option<Vec<U64>>,
+    ) -> RpcResult<BTreeMap<String, WalletCapabilities>> {
+        let chain_id = U64::from(self.backend.chain_id());
+        let mut capabilities = BTreeMap::new();
+        if address == self.backend.account()
+            && chain_ids.is_none_or(|chain_ids| chain_ids.contains(&chain_id))
+        {
+            capabilities.insert(
+                format!("{chain_id:#x}"),
+                WalletCapabilities::from([(
+                    "atomic".to_string(),
+                    serde_json::json!({ "status": "supported" }),
+                )]),
+            );
+        }
+        Ok(capabilities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: Address = Address::repeat_byte(0xa1);
+
+    fn request() -> WalletSendCallsRequest {
+        serde_json::from_value(serde_json::json!({
+            "version": "2.0.0",
+            "chainId": "0x539",
+            "from": ACCOUNT,
+            "atomicRequired": true,
+            "calls": [
+                { "to": Address::repeat_byte(0x70), "value": "0x1" },
+                { "data": "0x6000", "capabilities": { "paymasterService": { "optional": true } } }
+            ],
+            "capabilities": {}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn request_calls_become_an_evnode_batch() {
+        let calls = batch_calls(request(), ACCOUNT, 1337).unwrap();
+        assert_eq!(
+            calls,
+            vec![
+                Call {
+                    to: TxKind::Call(Address::repeat_byte(0x70)),
+                    value: U256::from(1),
+                    input: Bytes::new(),
+                    gas_limit: None,
+                },
+                Call {
+                    to: TxKind::Create,
+                    value: U256::ZERO,
+                    input: Bytes::from_static(&[0x60, 0x00]),
+                    gas_limit: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_requests_are_refused() {
+        let code = |request: WalletSendCallsRequest| {
+            batch_calls(request, ACCOUNT, 1337).unwrap_err().code()
+        };
+        assert_eq!(
+            code(WalletSendCallsRequest {
+                chain_id: U64::from(1),
+                ..request()
+            }),
+            UNSUPPORTED_CHAIN_ID_CODE
+        );
+        assert_eq!(
+            code(WalletSendCallsRequest {
+                from: Some(Address::repeat_byte(0xb0)),
+                ..request()
+            }),
+            UNAUTHORIZED_CODE
+        );
+        assert_eq!(
+            code(WalletSendCallsRequest {
+                capabilities: WalletCapabilities::from([(
+                    "paymasterService".to_string(),
+                    serde_json::json!({ "url": "https://example.com" }),
+                )]),
+                ..request()
+            }),
+            UNSUPPORTED_CAPABILITY_CODE
+        );
+        assert_eq!(
+            code(WalletSendCallsRequest {
+                calls: vec![request().calls[0].clone(); MAX_WALLET_CALLS + 1],
+                ..request()
+            }),
+            BUNDLE_TOO_LARGE_CODE
+        );
+        assert_eq!(
+            code(WalletSendCallsRequest {
+                version: "1.0".to_string(),
+                ..request()
+            }),
+            INVALID_PARAMS_CODE
+        );
+    }
+
+    #[test]
+    fn call_ids_resolve_to_batch_hashes() {
+        let mut ids = CallIds::default();
+        let tx_hash = B256::repeat_byte(0x11);
+        assert_eq!(ids.resolve(&tx_hash.to_string()), Some(tx_hash));
+        assert_eq!(ids.resolve("checkout-1"), None);
+
+        ids.insert("checkout-1".to_string(), tx_hash);
+        assert!(ids.contains("checkout-1"));
+        assert_eq!(ids.resolve("checkout-1"), Some(tx_hash));
+
+        for index in 0..MAX_TRACKED_CALL_IDS {
+            ids.insert(format!("id-{index}"), B256::ZERO);
+        }
+        assert!(!ids.contains("checkout-1"));
+        assert_eq!(ids.hashes.len(), MAX_TRACKED_CALL_IDS);
+    }
+}
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+use tokio::sync::Notify;
+
+/// Default time to wait for in-flight payload jobs when the node is shutting down.
+pub const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Lifecycle state reported to ev-node via `evolve_nodeStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NodeState {
+    /// Accepting new payload jobs.
+    Running,
+    /// Shutting down: no new payload jobs are started, in-flight ones are finishing.
+    Draining,
+}
+
+/// Snapshot of the node lifecycle returned by `evolve_nodeStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStatus {
+    /// Current lifecycle state.
+    pub state: NodeState,
+    /// Number of payload jobs currently being built.
+    pub in_flight_payloads: usize,
+}
+
+/// Coordinates shutdown so a SIGTERM never interrupts a half-built payload.
+///
+/// Payload jobs hold a [`PayloadJobGuard`] while building. Once [`Self::drain`] is called,
+/// new jobs are refused and the drain completes when the last guard is dropped. One
+/// coordinator is shared by the payload builder and the status RPC of a node.
+#[derive(Debug)]
+pub struct ShutdownCoordinator {
+    draining: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl ShutdownCoordinator {
+    /// Creates a coordinator in the running state.
+    pub fn new() -> Self {
+        Self {
+            draining: AtomicBool::new(false),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+        }
+    }
+
+    /// Registers a payload job, or returns `None` if the node is draining.
+    pub fn begin_payload_job(&self) -> Option<PayloadJobGuard<'_>> {
+        if self.draining.load(Ordering::Acquire) {
+            return None;
+        }
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        Some(PayloadJobGuard { coordinator: self })
+    }
+
+    /// Returns the current lifecycle status.
+    pub fn status(&self) -> NodeStatus {
+        let state = if self.draining.load(Ordering::Acquire) {
+            NodeState::Draining
+        } else {
+            NodeState::Running
+        };
+        NodeStatus {
+            state,
+            in_flight_payloads: self.in_flight.load(Ordering::Acquire),
+        }
+    }
+
+    /// Stops accepting payload jobs and waits up to `timeout` for in-flight jobs to finish.
+    ///
+    /// Returns `true` if all jobs finished before the timeout.
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        self.draining.store(true, Ordering::Release);
+        tokio::time::timeout(timeout, async {
+            loop {
+                let idle = self.idle.notified();
+                if self.in_flight.load(Ordering::Acquire) == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Marks a payload job as in flight until dropped.
+#[derive(Debug)]
+pub struct PayloadJobGuard<'a> {
+    coordinator: &'a ShutdownCoordinator,
+}
+
+impl Drop for PayloadJobGuard<'_> {
+    fn drop(&mut self) {
+        if self.coordinator.in_flight.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.coordinator.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn drain_waits_for_in_flight_jobs() {
+        let coordinator = std::sync::Arc::new(ShutdownCoordinator::new());
+        let guard = coordinator.begin_payload_job().expect("running");
+        assert_eq!(coordinator.status().in_flight_payloads, 1);
+
+        let drainer = {
+            let coordinator = coordinator.clone();
+            tokio::spawn(async move { coordinator.drain(Duration::from_secs(5)).await })
+        };
+        tokio::task::yield_now().await;
+        assert!(coordinator.begin_payload_job().is_none());
+        assert_eq!(coordinator.status().state, NodeState::Draining);
+
+        drop(guard);
+        assert!(
+            drainer.await.unwrap(),
+            "drain should complete once the job ends"
+        );
+    }
+
+    #[tokio::test]
+    async fn drain_times_out_with_stuck_job() {
+        let coordinator = ShutdownCoordinator::new();
+        let _guard = coordinator.begin_payload_job().expect("running");
+        assert!(!coordinator.drain(Duration::from_millis(10)).await);
+    }
+}
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// How the payload builder treats a requested timestamp that is not after the parent's.
+///
+/// With one-second timestamps and sub-second block times, ev-node regularly requests two
+/// consecutive payloads for the same second.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[repr(u8)]
+pub enum TimestampPolicy {
+    /// Build with the parent's timestamp, which Evolve consensus accepts. Timestamps before
+    /// the parent's are rejected.
+    #[default]
+    Grace = 0,
+    /// Build with the parent's timestamp plus one second.
+    Increment = 1,
+    /// Reject the payload.
+    Reject = 2,
+}
+
+impl TimestampPolicy {
+    /// Returns the timestamp to build with for `requested` on top of a parent at
+    /// `parent_timestamp`.
+    pub fn resolve(self, requested: u64, parent_timestamp: u64) -> Result<u64, TimestampError> {
+        if requested > parent_timestamp {
+            return Ok(requested);
+        }
+        match self {
+            Self::Grace if requested == parent_timestamp => Ok(requested),
+            Self::Increment => Ok(parent_timestamp.saturating_add(1)),
+            Self::Grace | Self::Reject => Err(TimestampError {
+                policy: self,
+                requested,
+                parent_timestamp,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for TimestampPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Grace => "grace",
+            Self::Increment => "increment",
+            Self::Reject => "reject",
+        })
+    }
+}
+
+impl FromStr for TimestampPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "grace" => Ok(Self::Grace),
+            "increment" => Ok(Self::Increment),
+            "reject" => Ok(Self::Reject),
+            other => Err(format!(
+                "unknown timestamp policy {other:?} (expected grace, increment or reject)"
+            )),
+        }
+    }
+}
+
+/// A payload timestamp refused under the configured [`TimestampPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "payload timestamp {requested} does not advance past parent timestamp {parent_timestamp} \
+     (timestamp policy: {policy})"
+)]
+pub struct TimestampError {
+    /// Policy that refused the timestamp.
+    pub policy: TimestampPolicy,
+    /// Timestamp requested in the payload attributes.
+    pub requested: u64,
+    /// Timestamp of the parent block.
+    pub parent_timestamp: u64,
+}
+
+/// Timestamp handling applied to a payload, recorded in its build report when the requested
+/// timestamp did not advance past the parent's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimestampAdjustment {
+    /// Policy in effect.
+    pub policy: TimestampPolicy,
+    /// Timestamp requested in the payload attributes.
+    pub requested: u64,
+    /// Timestamp of the parent block.
+    pub parent_timestamp: u64,
+    /// Timestamp the payload was built with.
+    pub applied: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policies_resolve_colliding_timestamps() {
+        assert_eq!(TimestampPolicy::Grace.resolve(100, 100), Ok(100));
+        assert_eq!(TimestampPolicy::Increment.resolve(100, 100), Ok(101));
+        assert_eq!(TimestampPolicy::Increment.resolve(99, 100), Ok(101));
+        assert!(TimestampPolicy::Reject.resolve(100, 100).is_err());
+        assert!(TimestampPolicy::Grace.resolve(99, 100).is_err());
+
+        // Advancing timestamps are left alone under every policy.
+        for policy in [
+            TimestampPolicy::Grace,
+            TimestampPolicy::Increment,
+            TimestampPolicy::Reject,
+        ] {
+            assert_eq!(policy.resolve(101, 100), Ok(101));
+        }
+    }
+
+    #[test]
+    fn policy_round_trips_through_cli_names() {
+        for policy in [
+            TimestampPolicy::Grace,
+            TimestampPolicy::Increment,
+            TimestampPolicy::Reject,
+        ] {
+            assert_eq!(policy.to_string().parse::<TimestampPolicy>(), Ok(policy));
+        }
+        assert!("bump".parse::<TimestampPolicy>().is_err());
+    }
+}
@@ -1,6 +1,8 @@
+use alloy_eips::eip4895::Withdrawal;
 use alloy_primitives::{Address, B256};
 use ev_primitives::TransactionSigned;
 use serde::{Deserialize, Serialize};
+use std::ops::Range;
 
 /// Payload attributes for the Evolve Reth node
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +23,18 @@ pub struct EvolvePayloadAttributes {
     pub block_number: u64,
     /// Slot number for post-Amsterdam payloads.
     pub slot_number: Option<u64>,
+    /// Whether to record a per-transaction inclusion report for this payload.
+    #[serde(default)]
+    pub build_report: bool,
+    /// Maximum number of transactions to include in the payload.
+    #[serde(default)]
+    pub max_tx_count: Option<u64>,
+    /// Withdrawals to credit at the end of the block, when enabled by the chainspec.
+    #[serde(default)]
+    pub withdrawals: Vec<Withdrawal>,
+    /// Ranges of `transactions` that must be included together or not at all.
+    #[serde(default)]
+    pub bundles: Vec<Range<usize>>,
 }
 
 impl EvolvePayloadAttributes {
@@ -43,6 +57,10 @@ impl EvolvePayloadAttributes {
             parent_hash,
             block_number,
             slot_number: None,
+            build_report: false,
+            max_tx_count: None,
+            withdrawals: Vec::new(),
+            bundles: Vec::new(),
         }
     }
 
@@ -52,6 +70,30 @@ impl EvolvePayloadAttributes {
         self
     }
 
+    /// Enables recording a per-transaction inclusion report for this payload.
+    pub const fn with_build_report(mut self, build_report: bool) -> Self {
+        self.build_report = build_report;
+        self
+    }
+
+    /// Limits the number of transactions included in the payload.
+    pub const fn with_max_tx_count(mut self, max_tx_count: Option<u64>) -> Self {
+        self.max_tx_count = max_tx_count;
+        self
+    }
+
+    /// Sets the withdrawals to credit at the end of the block.
+    pub fn with_withdrawals(mut self, withdrawals: Vec<Withdrawal>) -> Self {
+        self.withdrawals = withdrawals;
+        self
+    }
+
+    /// Sets the transaction ranges that must be included atomically.
+    pub fn with_bundles(mut self, bundles: Vec<Range<usize>>) -> Self {
+        self.bundles = bundles;
+        self
+    }
+
     /// Validates the payload attributes
     pub const fn validate(&self) -> Result<(), PayloadAttributesError> {
         // For evolve, empty transactions are allowed (empty blocks are valid)
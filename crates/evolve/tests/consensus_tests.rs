@@ -1,10 +1,14 @@
 //! Tests for Evolve consensus implementation
 
 use alloy_consensus::Header;
-use evolve_ev_reth::consensus::EvolveConsensus;
-use reth_chainspec::MAINNET;
-use reth_consensus::{ConsensusError, HeaderValidator};
-use reth_primitives_traits::SealedHeader;
+use alloy_genesis::Genesis;
+use ev_primitives::{Block, Receipt};
+use evolve_ev_reth::{consensus::EvolveConsensus, derive_prev_randao, PrevRandaoPolicy};
+use reth_chainspec::{ChainSpecBuilder, MAINNET};
+use reth_consensus::{ConsensusError, FullConsensus, HeaderValidator};
+use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::{RecoveredBlock, SealedHeader};
+use std::sync::Arc;
 
 fn create_test_header(number: u64, parent_hash: [u8; 32], timestamp: u64) -> SealedHeader {
     let header = Header {
@@ -170,3 +174,156 @@ fn test_evolve_consensus_validates_block_number() {
         "Evolve consensus should validate block number"
     );
 }
+
+fn create_legacy_block(receipts_root: [u8; 32]) -> RecoveredBlock<Block> {
+    let header = Header {
+        number: 5,
+        timestamp: 1000,
+        gas_limit: 30_000_000,
+        gas_used: 0,
+        receipts_root: receipts_root.into(),
+        ..Default::default()
+    };
+    RecoveredBlock::new_unhashed(Block::new(header, Default::default()), vec![])
+}
+
+fn empty_execution_result() -> BlockExecutionResult<Receipt> {
+    BlockExecutionResult {
+        receipts: vec![],
+        requests: Default::default(),
+        gas_used: 0,
+        blob_gas_used: 0,
+    }
+}
+
+#[test]
+fn test_evolve_consensus_rejects_bad_receipts_root_by_default() {
+    let chain_spec = Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build());
+    let consensus = EvolveConsensus::new(chain_spec);
+
+    let block = create_legacy_block([1u8; 32]);
+    let result = consensus.validate_block_post_execution(&block, &empty_execution_result(), None);
+    assert!(
+        matches!(result, Err(ConsensusError::BodyReceiptRootDiff(_))),
+        "receipts root mismatch should be rejected, got {result:?}"
+    );
+}
+
+#[test]
+fn test_evolve_consensus_skips_receipts_root_before_activation() {
+    let chain_spec = Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build());
+    let consensus = EvolveConsensus::new(chain_spec).with_receipts_root_activation_height(6);
+
+    // Block 5 is below the activation height, so its bad receipts root is tolerated.
+    let block = create_legacy_block([1u8; 32]);
+    let result = consensus.validate_block_post_execution(&block, &empty_execution_result(), None);
+    assert!(
+        result.is_ok(),
+        "legacy receipts root should be accepted: {result:?}"
+    );
+
+    // Once active, the same mismatch is rejected again.
+    let chain_spec = Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build());
+    let consensus = EvolveConsensus::new(chain_spec).with_receipts_root_activation_height(5);
+    let result = consensus.validate_block_post_execution(&block, &empty_execution_result(), None);
+    assert!(
+        result.is_err(),
+        "receipts root should be enforced at activation"
+    );
+}
+
+#[test]
+fn test_evolve_consensus_reads_toggles_from_chainspec() {
+    let mut genesis = Genesis::default();
+    genesis.config.extra_fields.insert(
+        "evolve".to_string(),
+        serde_json::json!({ "receiptsRootValidationActivationHeight": 100 }),
+    );
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::mainnet()
+            .genesis(genesis)
+            .cancun_activated()
+            .build(),
+    );
+    let consensus = EvolveConsensus::from_chain_spec(chain_spec).expect("valid extras");
+
+    let block = create_legacy_block([1u8; 32]);
+    let result = consensus.validate_block_post_execution(&block, &empty_execution_result(), None);
+    assert!(
+        result.is_ok(),
+        "chainspec toggle should skip legacy receipts root: {result:?}"
+    );
+}
+
+#[test]
+fn test_evolve_consensus_rejects_timestamp_beyond_drift() {
+    let consensus = EvolveConsensus::new(MAINNET.clone()).with_max_timestamp_drift(15);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let header = create_test_header(1, [0u8; 32], now + 3600);
+    assert!(matches!(
+        consensus.validate_header(&header),
+        Err(ConsensusError::TimestampIsInFuture { .. })
+    ));
+}
+
+#[test]
+fn test_evolve_consensus_enforces_min_block_time_after_activation() {
+    let consensus = EvolveConsensus::new(MAINNET.clone()).with_min_block_time(2, 2);
+    let parent = create_test_header(1, [0u8; 32], 1000);
+
+    let child_header = Header {
+        number: 2,
+        parent_hash: parent.hash(),
+        timestamp: 1001,
+        gas_limit: 30_000_000,
+        gas_used: 0,
+        ..Default::default()
+    };
+    let child = SealedHeader::new(child_header, [1u8; 32].into());
+    assert!(
+        consensus
+            .validate_header_against_parent(&child, &parent)
+            .is_err(),
+        "block closer than the minimum block time should be rejected"
+    );
+
+    // Before activation the equal-timestamp allowance still applies.
+    let consensus = EvolveConsensus::new(MAINNET.clone()).with_min_block_time(2, 3);
+    assert!(consensus
+        .validate_header_against_parent(&child, &parent)
+        .is_ok());
+}
+
+#[test]
+fn test_evolve_consensus_enforces_derived_prev_randao() {
+    let consensus =
+        EvolveConsensus::new(MAINNET.clone()).with_prev_randao_policy(PrevRandaoPolicy::Derived, 0);
+    let parent = create_test_header(1, [0u8; 32], 1000);
+
+    let mut child_header = Header {
+        number: 2,
+        parent_hash: parent.hash(),
+        timestamp: 1000,
+        gas_limit: 30_000_000,
+        gas_used: 0,
+        mix_hash: [7u8; 32].into(),
+        ..Default::default()
+    };
+    let child = SealedHeader::new(child_header.clone(), [1u8; 32].into());
+    assert!(
+        consensus
+            .validate_header_against_parent(&child, &parent)
+            .is_err(),
+        "arbitrary prev_randao should be rejected under the derived policy"
+    );
+
+    child_header.mix_hash = derive_prev_randao(parent.hash(), parent.mix_hash);
+    let child = SealedHeader::new(child_header, [1u8; 32].into());
+    assert!(consensus
+        .validate_header_against_parent(&child, &parent)
+        .is_ok());
+}
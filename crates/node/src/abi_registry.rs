@@ -0,0 +1,309 @@
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_json_abi::{Function, JsonAbi};
+use alloy_primitives::{hex, Address, Selector};
+use async_trait::async_trait;
+use jsonrpsee::types::{error::INTERNAL_ERROR_CODE, ErrorObject};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+use thiserror::Error;
+
+/// Errors raised while loading or persisting an [`AbiRegistry`].
+#[derive(Debug, Error)]
+pub enum AbiRegistryError {
+    /// The registry file could not be read or written.
+    #[error("ABI registry I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The registry file is not a JSON object mapping addresses to ABIs.
+    #[error("invalid ABI registry: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A decoded function call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedFunction {
+    /// Function name, e.g. `transfer`.
+    pub name: String,
+    /// Function signature, e.g. `transfer(address,uint256)`.
+    pub signature: String,
+    /// Decoded arguments, or `null` if the input does not match the function's parameters.
+    pub args: Option<Vec<DecodedArg>>,
+}
+
+/// A decoded function argument.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedArg {
+    /// Parameter name from the ABI; empty for unnamed parameters.
+    pub name: String,
+    /// Solidity type of the parameter.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Decoded value. Integers are decimal strings and byte strings are hex.
+    pub value: serde_json::Value,
+}
+
+/// Contract ABIs known to the node, keyed by address.
+///
+/// Backed by an optional JSON file mapping addresses to ABIs, rewritten on every change.
+/// Calls to a registered address are decoded with its ABI; calls to other addresses fall back
+/// to any registered function with a matching selector, which covers proxies and token
+/// clones without registering each of them.
+#[derive(Debug, Default)]
+pub struct AbiRegistry {
+    path: Option<PathBuf>,
+    contracts: RwLock<BTreeMap<Address, JsonAbi>>,
+}
+
+impl AbiRegistry {
+    /// Opens the registry stored at `path`, starting empty if the file does not exist yet.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, AbiRegistryError> {
+        let path = path.into();
+        let contracts = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self {
+            path: Some(path),
+            contracts: RwLock::new(contracts),
+        })
+    }
+
+    /// Returns the file backing the registry, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Registers `abi` for `address`, replacing any previous ABI, and persists the registry.
+    pub fn register(&self, address: Address, abi: JsonAbi) -> Result<(), AbiRegistryError> {
+        let mut contracts = self.contracts.write().expect("ABI registry lock poisoned");
+        contracts.insert(address, abi);
+        self.persist(&contracts)
+    }
+
+    /// Removes the ABI of `address` and persists the registry. Returns false if none was
+    /// registered.
+    pub fn remove(&self, address: &Address) -> Result<bool, AbiRegistryError> {
+        let mut contracts = self.contracts.write().expect("ABI registry lock poisoned");
+        if contracts.remove(address).is_none() {
+            return Ok(false);
+        }
+        self.persist(&contracts)?;
+        Ok(true)
+    }
+
+    /// Returns the addresses with a registered ABI.
+    pub fn addresses(&self) -> Vec<Address> {
+        let contracts = self.contracts.read().expect("ABI registry lock poisoned");
+        contracts.keys().copied().collect()
+    }
+
+    /// Returns the number of registered contracts.
+    pub fn len(&self) -> usize {
+        self.contracts
+            .read()
+            .expect("ABI registry lock poisoned")
+            .len()
+    }
+
+    /// Returns true if no contract is registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Decodes a call of `input` to `to`, if its selector is known.
+    pub fn decode(&self, to: Option<Address>, input: &[u8]) -> Option<DecodedFunction> {
+        let selector = Selector::from_slice(input.get(..4)?);
+        let contracts = self.contracts.read().expect("ABI registry lock poisoned");
+        let function = to
+            .and_then(|to| contracts.get(&to))
+            .and_then(|abi| find_function(abi, selector))
+            .or_else(|| {
+                contracts
+                    .values()
+                    .find_map(|abi| find_function(abi, selector))
+            })?;
+        let args = function.abi_decode_input(&input[4..]).ok().map(|values| {
+            function
+                .inputs
+                .iter()
+                .zip(&values)
+                .map(|(param, value)| DecodedArg {
+                    name: param.name.clone(),
+                    ty: param.selector_type().into_owned(),
+                    value: dyn_value_json(value),
+                })
+                .collect()
+        });
+        Some(DecodedFunction {
+            name: function.name.clone(),
+            signature: function.signature(),
+            args,
+        })
+    }
+
+    fn persist(&self, contracts: &BTreeMap<Address, JsonAbi>) -> Result<(), AbiRegistryError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        // Write a sibling file and rename it over the registry, so a crash mid-write never
+        // leaves a truncated registry behind.
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(contracts)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+fn find_function(abi: &JsonAbi, selector: Selector) -> Option<&Function> {
+    abi.functions()
+        .find(|function| function.selector() == selector)
+}
+
+fn dyn_value_json(value: &DynSolValue) -> serde_json::Value {
+    match value {
+        DynSolValue::Bool(value) => (*value).into(),
+        DynSolValue::Int(value, _) => value.to_string().into(),
+        DynSolValue::Uint(value, _) => value.to_string().into(),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]).into(),
+        DynSolValue::Address(address) => address.to_string().into(),
+        DynSolValue::Function(function) => function.to_string().into(),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes).into(),
+        DynSolValue::String(value) => value.clone().into(),
+        seq => seq
+            .as_fixed_seq()
+            .or_else(|| seq.as_array())
+            .unwrap_or_default()
+            .iter()
+            .map(dyn_value_json)
+            .collect(),
+    }
+}
+
+/// ABI registry management RPC API.
+///
+/// Served on the authenticated Engine API endpoint only.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveAbiRegistryApi {
+    /// Registers the ABI of a contract, replacing any previous one.
+    #[method(name = "registerAbi")]
+    async fn register_abi(&self, address: Address, abi: JsonAbi) -> RpcResult<()>;
+
+    /// Removes the ABI of a contract. Returns false if none was registered.
+    #[method(name = "removeAbi")]
+    async fn remove_abi(&self, address: Address) -> RpcResult<bool>;
+
+    /// Returns the addresses with a registered ABI.
+    #[method(name = "listAbis")]
+    async fn list_abis(&self) -> RpcResult<Vec<Address>>;
+}
+
+/// Implementation of the ABI registry management RPC.
+#[derive(Debug)]
+pub struct EvolveAbiRegistryApiImpl {
+    registry: Arc<AbiRegistry>,
+}
+
+impl EvolveAbiRegistryApiImpl {
+    /// Creates a new instance managing `registry`.
+    pub const fn new(registry: Arc<AbiRegistry>) -> Self {
+        Self { registry }
+    }
+}
+
+#[async_trait]
+impl EvolveAbiRegistryApiServer for EvolveAbiRegistryApiImpl {
+    async fn register_abi(&self, address: Address, abi: JsonAbi) -> RpcResult<()> {
+        self.registry
+            .register(address, abi)
+            .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))
+    }
+
+    async fn remove_abi(&self, address: Address) -> RpcResult<bool> {
+        self.registry
+            .remove(&address)
+            .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))
+    }
+
+    async fn list_abis(&self) -> RpcResult<Vec<Address>> {
+        Ok(self.registry.addresses())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, hex, U256};
+
+    const ERC20_ABI: &str = r#"[{"type":"function","name":"transfer","stateMutability":"nonpayable",
+        "inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],
+        "outputs":[{"name":"","type":"bool"}]}]"#;
+
+    fn transfer_input(to: Address, amount: u64) -> Vec<u8> {
+        let abi: JsonAbi = serde_json::from_str(ERC20_ABI).unwrap();
+        abi.function("transfer").unwrap()[0]
+            .abi_encode_input(&[to.into(), U256::from(amount).into()])
+            .unwrap()
+    }
+
+    #[test]
+    fn decodes_registered_and_fallback_calls() {
+        let token = address!("0x00000000000000000000000000000000000000aa");
+        let clone = address!("0x00000000000000000000000000000000000000bb");
+        let recipient = address!("0x00000000000000000000000000000000000000cc");
+        let registry = AbiRegistry::default();
+        assert_eq!(
+            registry.decode(Some(token), &transfer_input(recipient, 7)),
+            None
+        );
+
+        registry
+            .register(token, serde_json::from_str(ERC20_ABI).unwrap())
+            .unwrap();
+        let decoded = registry
+            .decode(Some(token), &transfer_input(recipient, 7))
+            .unwrap();
+        assert_eq!(decoded.name, "transfer");
+        assert_eq!(decoded.signature, "transfer(address,uint256)");
+        let args = decoded.args.unwrap();
+        assert_eq!(args[0].name, "to");
+        assert_eq!(args[0].value, recipient.to_string());
+        assert_eq!(args[1].ty, "uint256");
+        assert_eq!(args[1].value, "7");
+
+        // Unregistered targets fall back to any known function with the same selector.
+        let fallback = registry
+            .decode(Some(clone), &transfer_input(recipient, 7))
+            .unwrap();
+        assert_eq!(fallback.name, "transfer");
+
+        // Malformed arguments still name the function.
+        let truncated = registry.decode(Some(token), &hex!("a9059cbb0000")).unwrap();
+        assert_eq!(truncated.args, None);
+    }
+
+    #[test]
+    fn registry_persists_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("abis.json");
+        let token = address!("0x00000000000000000000000000000000000000aa");
+
+        let registry = AbiRegistry::open(&path).unwrap();
+        assert!(registry.is_empty());
+        registry
+            .register(token, serde_json::from_str(ERC20_ABI).unwrap())
+            .unwrap();
+
+        let reopened = AbiRegistry::open(&path).unwrap();
+        assert_eq!(reopened.addresses(), vec![token]);
+        assert!(reopened.remove(&token).unwrap());
+        assert!(!reopened.remove(&token).unwrap());
+        assert!(AbiRegistry::open(&path).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,311 @@
+use alloy_consensus::{transaction::TxHashRef, Transaction as _};
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, TxKind, B256, U256, U64};
+use async_trait::async_trait;
+use ev_primitives::EvTxEnvelope;
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_storage_api::{
+    errors::provider::ProviderError, BlockIdReader, BlockNumReader, BlockReader, ChangeSetReader,
+    StateProviderFactory, TransactionVariant,
+};
+use serde::{Deserialize, Serialize};
+
+/// Most blocks a single `evolve_getAccountActivity` call scans.
+pub const MAX_ACTIVITY_BLOCKS: u64 = 1_000;
+
+/// Balance of an account changed by a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBalanceChange {
+    /// Block that changed the balance.
+    pub block_number: U64,
+    /// Balance before the block.
+    pub previous: U256,
+    /// Balance after the block.
+    pub balance: U256,
+}
+
+/// Nonce of an account changed by a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcNonceChange {
+    /// Block that changed the nonce.
+    pub block_number: U64,
+    /// Nonce before the block.
+    pub previous: U64,
+    /// Nonce after the block.
+    pub nonce: U64,
+}
+
+/// A transaction involving an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcActivityTransaction {
+    /// Block the transaction is included in.
+    pub block_number: U64,
+    /// Transaction hash.
+    pub hash: B256,
+    /// Whether the account sent the transaction, as its signer or EvNode executor.
+    pub outgoing: bool,
+    /// Whether the account is the recipient of a non-EvNode transaction.
+    pub incoming: bool,
+    /// Whether the account paid the gas of an EvNode transaction as its fee payer.
+    pub sponsored: bool,
+    /// Positions of the EvNode batch calls targeting the account.
+    pub batch_calls: Vec<U64>,
+}
+
+/// Activity of an account over a block range, returned by `evolve_getAccountActivity`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountActivity {
+    /// First block scanned.
+    pub from_block: U64,
+    /// Last block scanned; below `fromBlock` when there is nothing to scan yet.
+    pub to_block: U64,
+    /// Block to resume from when the range was cut at [`MAX_ACTIVITY_BLOCKS`], `null` once
+    /// the latest block is covered.
+    pub next_block: Option<U64>,
+    /// Balance changes, in block order.
+    pub balance_changes: Vec<RpcBalanceChange>,
+    /// Nonce changes, in block order.
+    pub nonce_changes: Vec<RpcNonceChange>,
+    /// Transactions involving the account, in block order.
+    pub transactions: Vec<RpcActivityTransaction>,
+}
+
+/// Returns how `tx`, sent by `sender`, involves `address`, or `None` if it does not.
+pub fn transaction_activity(
+    tx: &EvTxEnvelope,
+    sender: Address,
+    address: Address,
+    block_number: u64,
+) -> Option<RpcActivityTransaction> {
+    let (incoming, sponsored, batch_calls) = match tx {
+        EvTxEnvelope::Ethereum(inner) => (inner.to() == Some(address), false, Vec::new()),
+        EvTxEnvelope::EvNode(ev) => {
+            let batch_calls = ev
+                .tx()
+                .calls
+                .iter()
+                .enumerate()
+                .filter(|(_, call)| call.to == TxKind::Call(address))
+                .map(|(index, _)| U64::from(index))
+                .collect();
+            let sponsored = ev.tx().fee_payer(sender) == Some(address);
+            (false, sponsored, batch_calls)
+        }
+    };
+    let outgoing = sender == address;
+    (outgoing || incoming || sponsored || !batch_calls.is_empty()).then(|| RpcActivityTransaction {
+        block_number: U64::from(block_number),
+        hash: *tx.tx_hash(),
+        outgoing,
+        incoming,
+        sponsored,
+        batch_calls,
+    })
+}
+
+/// Collects the activity of `address` from `from` up to the latest block, scanning at most
+/// [`MAX_ACTIVITY_BLOCKS`] blocks.
+///
+/// Balance and nonce changes come from each block's account changeset, so blocks whose
+/// account history has been pruned cannot be scanned.
+pub fn account_activity<P>(
+    provider: &P,
+    address: Address,
+    from: u64,
+) -> Result<RpcAccountActivity, ProviderError>
+where
+    P: BlockReader<Block = ev_primitives::Block>
+        + BlockNumReader
+        + ChangeSetReader
+        + StateProviderFactory,
+{
+    let latest = provider.best_block_number()?;
+    let to = latest.min(from.saturating_add(MAX_ACTIVITY_BLOCKS - 1));
+    let mut activity = RpcAccountActivity {
+        from_block: U64::from(from),
+        to_block: U64::from(to),
+        next_block: (to < latest).then(|| U64::from(to + 1)),
+        balance_changes: Vec::new(),
+        nonce_changes: Vec::new(),
+        transactions: Vec::new(),
+    };
+
+    for block_number in from..=to {
+        let before = provider
+            .account_block_changeset(block_number)?
+            .into_iter()
+            .find(|change| change.address == address);
+        if let Some(before) = before {
+            let before = before.info.unwrap_or_default();
+            let after = provider
+                .history_by_block_number(block_number)?
+                .basic_account(&address)?
+                .unwrap_or_default();
+            if before.balance != after.balance {
+                activity.balance_changes.push(RpcBalanceChange {
+                    block_number: U64::from(block_number),
+                    previous: before.balance,
+                    balance: after.balance,
+                });
+            }
+            if before.nonce != after.nonce {
+                activity.nonce_changes.push(RpcNonceChange {
+                    block_number: U64::from(block_number),
+                    previous: U64::from(before.nonce),
+                    nonce: U64::from(after.nonce),
+                });
+            }
+        }
+
+        let Some(block) =
+            provider.recovered_block(block_number.into(), TransactionVariant::WithHash)?
+        else {
+            continue;
+        };
+        activity
+            .transactions
+            .extend(block.transactions_recovered().filter_map(|tx| {
+                let (tx, sender) = tx.into_parts();
+                transaction_activity(tx, sender, address, block_number)
+            }));
+    }
+    Ok(activity)
+}
+
+/// Evolve account activity RPC API.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveActivityApi {
+    /// Returns the balance changes, nonce changes and transactions of `address` from
+    /// `fromBlock` on, including EvNode batches calling it. At most
+    /// [`MAX_ACTIVITY_BLOCKS`] blocks are scanned per call; `nextBlock` tells where to resume.
+    #[method(name = "getAccountActivity")]
+    async fn get_account_activity(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+    ) -> RpcResult<RpcAccountActivity>;
+}
+
+/// Implementation of the account activity RPC.
+#[derive(Debug)]
+pub struct EvolveActivityApiImpl<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> EvolveActivityApiImpl<Provider> {
+    /// Creates a new instance reading blocks and account history from `provider`.
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+fn internal_error(err: impl ToString) -> ErrorObject<'static> {
+    ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+#[async_trait]
+impl<Provider> EvolveActivityApiServer for EvolveActivityApiImpl<Provider>
+where
+    Provider: BlockReader<Block = ev_primitives::Block>
+        + BlockNumReader
+        + BlockIdReader
+        + ChangeSetReader
+        + StateProviderFactory
+        + Clone
+        + 'static,
+{
+    async fn get_account_activity(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+    ) -> RpcResult<RpcAccountActivity> {
+        let from = self
+            .provider
+            .convert_block_number(from_block)
+            .map_err(internal_error)?
+            .ok_or_else(|| {
+                ErrorObject::owned(INVALID_PARAMS_CODE, "unknown fromBlock", None::<()>)
+            })?;
+        let provider = self.provider.clone();
+        tokio::task::spawn_blocking(move || account_activity(&provider, address, from))
+            .await
+            .map_err(internal_error)?
+            .map_err(internal_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Signed, TxEnvelope, TxLegacy};
+    use alloy_primitives::{address, aliases::U192, Bytes, Signature};
+    use ev_primitives::{Call, EvNodeTransaction, RevertMode};
+
+    const ACCOUNT: Address = address!("0x00000000000000000000000000000000000000aa");
+    const OTHER: Address = address!("0x00000000000000000000000000000000000000bb");
+
+    fn signature() -> Signature {
+        Signature::new(U256::from(1), U256::from(2), false)
+    }
+
+    fn call(to: Address) -> Call {
+        Call {
+            to: TxKind::Call(to),
+            value: U256::ZERO,
+            input: Bytes::new(),
+            gas_limit: None,
+        }
+    }
+
+    #[test]
+    fn finds_senders_recipients_and_batch_targets() {
+        let transfer = EvTxEnvelope::Ethereum(TxEnvelope::Legacy(Signed::new_unhashed(
+            TxLegacy {
+                to: TxKind::Call(ACCOUNT),
+                ..Default::default()
+            },
+            signature(),
+        )));
+        let incoming = transaction_activity(&transfer, OTHER, ACCOUNT, 7).unwrap();
+        assert!(incoming.incoming && !incoming.outgoing);
+        assert_eq!(incoming.block_number, U64::from(7));
+        assert!(transaction_activity(&transfer, OTHER, Address::ZERO, 7).is_none());
+
+        let batch = EvTxEnvelope::EvNode(Signed::new_unhashed(
+            EvNodeTransaction {
+                chain_id: 1,
+                nonce: 0,
+                nonce_key: U192::ZERO,
+                max_priority_fee_per_gas: 1,
+                max_fee_per_gas: 1,
+                gas_limit: 100_000,
+                calls: vec![call(OTHER), call(ACCOUNT), call(ACCOUNT)],
+                access_list: Default::default(),
+                revert_mode: RevertMode::Atomic,
+                valid_after: None,
+                valid_until: None,
+                session: None,
+                fee_payer_signature: None,
+                max_sponsor_fee: None,
+                p256_signature: None,
+            },
+            signature(),
+        ));
+        let target = transaction_activity(&batch, OTHER, ACCOUNT, 8).unwrap();
+        assert!(!target.outgoing && !target.incoming && !target.sponsored);
+        assert_eq!(target.batch_calls, vec![U64::from(1), U64::from(2)]);
+
+        let sent = transaction_activity(&batch, ACCOUNT, ACCOUNT, 8).unwrap();
+        assert!(sent.outgoing);
+        assert_eq!(sent.hash, *batch.tx_hash());
+    }
+}
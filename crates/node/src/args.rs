@@ -1,5 +1,389 @@
+use crate::{
+    call_cache::DEFAULT_CALL_CACHE_TTL_MS,
+    engine_compression::DEFAULT_ENGINE_COMPRESSION_ADDR,
+    faucet::{
+        DEFAULT_FAUCET_ADDR, DEFAULT_FAUCET_ADDRESS_COOLDOWN_SECS, DEFAULT_FAUCET_AMOUNT,
+        DEFAULT_FAUCET_IP_COOLDOWN_SECS, DEFAULT_FAUCET_MAX_AMOUNT,
+    },
+    graphql::DEFAULT_GRAPHQL_ADDR,
+    load_shed::DEFAULT_BLOCK_INTERVAL_MS,
+    payload_jobs::DEFAULT_PAYLOAD_JOB_TTL_MS,
+    screening::{TransactionScreener, DEFAULT_SCREENING_TIMEOUT_MS},
+    signer::load_signer_key,
+    sponsorship::DEFAULT_SPONSORSHIP_POLICY_TIMEOUT_MS,
+};
+use alloy_primitives::Address;
+use alloy_signer_local::PrivateKeySigner;
 use clap::Args;
+use evolve_ev_reth::{
+    fee_estimator::DEFAULT_FEE_WINDOW_SECS, shutdown::DEFAULT_SHUTDOWN_DRAIN_TIMEOUT,
+    OrderingPolicy, TimestampPolicy,
+};
+use reqwest::Url;
+use serde::Serialize;
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 
-/// Evolve CLI arguments (currently empty; reserved for future toggles).
-#[derive(Debug, Clone, Default, Args)]
-pub struct EvolveArgs {}
+/// Evolve CLI arguments.
+#[derive(Debug, Clone, Args, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvolveArgs {
+    /// Record a per-transaction inclusion report for every built payload, retrievable via
+    /// `evolve_getBuildReport`. Individual payloads can opt in with the `buildReport`
+    /// payload attribute instead.
+    #[arg(long = "ev-reth.build-report", default_value_t = false)]
+    pub build_report: bool,
+
+    /// Seconds to wait for in-flight payload jobs to finish on shutdown before exiting.
+    #[arg(long = "ev-reth.shutdown-drain-timeout", default_value_t = DEFAULT_SHUTDOWN_DRAIN_TIMEOUT.as_secs())]
+    pub shutdown_drain_timeout: u64,
+
+    /// In `--dev` mode, start building the next block from the txpool as soon as a new head
+    /// is committed, and serve it if the following payload request matches.
+    #[arg(long = "ev-reth.speculative-building", default_value_t = false)]
+    pub speculative_building: bool,
+
+    /// Milliseconds a payload job may go unfetched before it is dropped and its build
+    /// cancelled. `0` keeps jobs until the builder deadline.
+    #[arg(long = "ev-reth.payload-job-ttl-ms", default_value_t = DEFAULT_PAYLOAD_JOB_TTL_MS)]
+    pub payload_job_ttl_ms: u64,
+
+    /// Drive the `safe` and `finalized` block tags from the DA inclusion reported via
+    /// `evolve_setDaStatus`, instead of the hashes in forkchoice updates.
+    #[arg(long = "ev-reth.da-finality", default_value_t = false)]
+    pub da_finality: bool,
+
+    /// Serve blocks, transactions, receipts and accounts over GraphQL at
+    /// `--ev-reth.graphql-addr`.
+    #[arg(long = "ev-reth.graphql", default_value_t = false)]
+    pub graphql: bool,
+
+    /// Listen address of the GraphQL endpoint.
+    #[arg(long = "ev-reth.graphql-addr", default_value = DEFAULT_GRAPHQL_ADDR)]
+    pub graphql_addr: SocketAddr,
+
+    /// Also serve the authenticated Engine API at `--ev-reth.engine-compression-addr`, accepting
+    /// zstd or gzip encoded requests and compressing responses on request.
+    #[arg(long = "ev-reth.engine-compression", default_value_t = false)]
+    pub engine_compression: bool,
+
+    /// Listen address of the compressed Engine API endpoint.
+    #[arg(
+        long = "ev-reth.engine-compression-addr",
+        default_value = DEFAULT_ENGINE_COMPRESSION_ADDR
+    )]
+    pub engine_compression_addr: SocketAddr,
+
+    /// How to build a payload whose requested timestamp is not after its parent's: `grace`
+    /// keeps an equal timestamp, `increment` uses the parent's plus one second, `reject`
+    /// fails the build.
+    #[arg(long = "ev-reth.timestamp-policy", default_value_t = TimestampPolicy::Grace)]
+    pub timestamp_policy: TimestampPolicy,
+
+    /// Serve repeated identical `eth_call` requests against the same block from a short-lived
+    /// cache, and expose the same cached call as `evolve_call`.
+    #[arg(long = "ev-reth.call-cache", default_value_t = false)]
+    pub call_cache: bool,
+
+    /// Milliseconds a cached `eth_call` result is reused for.
+    #[arg(long = "ev-reth.call-cache-ttl-ms", default_value_t = DEFAULT_CALL_CACHE_TTL_MS)]
+    pub call_cache_ttl_ms: u64,
+
+    /// Seconds of block time `evolve_estimateFeePerGas` samples priority fees over.
+    #[arg(long = "ev-reth.fee-window-secs", default_value_t = DEFAULT_FEE_WINDOW_SECS)]
+    pub fee_window_secs: u64,
+
+    /// File holding a hex-encoded operator key. When set, every payload this node builds is
+    /// signed and the attestation served by `evolve_getBuildAttestation`.
+    #[arg(long = "ev-reth.attestation-key-file", value_name = "PATH")]
+    pub attestation_key_file: Option<PathBuf>,
+
+    /// Limit, then reject, tracing and simulation RPC calls while payload builds take a large
+    /// share of `--ev-reth.block-interval-ms`.
+    #[arg(long = "ev-reth.rpc-load-shedding", default_value_t = false)]
+    pub rpc_load_shedding: bool,
+
+    /// Expected interval between blocks, in milliseconds, that build times are compared
+    /// against for RPC load shedding.
+    #[arg(long = "ev-reth.block-interval-ms", default_value_t = DEFAULT_BLOCK_INTERVAL_MS)]
+    pub block_interval_ms: u64,
+
+    /// JSON file mapping contract addresses to ABIs, used to decode calls in
+    /// `evolve_getCallsByTransaction` and `evolve_debugExecutePayload`. Created on the first
+    /// `evolve_registerAbi`; without it, registered ABIs are kept in memory only.
+    #[arg(long = "ev-reth.abi-registry", value_name = "PATH")]
+    pub abi_registry: Option<PathBuf>,
+
+    /// Index the total native supply after every block in the data directory and serve it via
+    /// `evolve_getSupply` and `evolve_getSupplyHistory`. Backfilling needs the account history
+    /// of every block since genesis.
+    #[arg(long = "ev-reth.supply-index", default_value_t = false)]
+    pub supply_index: bool,
+
+    /// After every payload build, check the block's transaction order against the given
+    /// policy (`tip` or `fifo`) and log each pair of transactions included in the wrong
+    /// order.
+    #[arg(long = "ev-reth.ordering-audit", value_name = "POLICY")]
+    pub ordering_audit: Option<OrderingPolicy>,
+
+    /// After every payload build, record an ordering report (sandwich patterns, tip and
+    /// arrival order inversions) for the block, retrievable via `evolve_getOrderingReport`.
+    #[arg(long = "ev-reth.ordering-report", default_value_t = false)]
+    pub ordering_report: bool,
+
+    /// URL of an external screening service every transaction is POSTed to before it enters
+    /// the pool. Only transactions the service allows are admitted.
+    #[arg(long = "ev-reth.screening-url", value_name = "URL")]
+    pub screening_url: Option<Url>,
+
+    /// Milliseconds the screening service has to answer.
+    #[arg(long = "ev-reth.screening-timeout-ms", default_value_t = DEFAULT_SCREENING_TIMEOUT_MS)]
+    pub screening_timeout_ms: u64,
+
+    /// Reject transactions while the screening service fails or times out, instead of
+    /// admitting them.
+    #[arg(long = "ev-reth.screening-fail-closed", default_value_t = false)]
+    pub screening_fail_closed: bool,
+
+    /// Reject EvNode transactions whose executor signature also verifies in the sponsor
+    /// signing domain, or whose fee payer signature also verifies in the executor domain.
+    /// Costs a few extra signature recoveries per pooled transaction.
+    #[arg(long = "ev-reth.signing-domain-audit", default_value_t = false)]
+    pub signing_domain_audit: bool,
+
+    /// File holding a hex-encoded faucet key. When set, `evolve_requestFunds` is served on a
+    /// public endpoint at `--ev-reth.faucet-addr`, sending funds from that key.
+    #[arg(long = "ev-reth.faucet-key-file", value_name = "PATH")]
+    pub faucet_key_file: Option<PathBuf>,
+
+    /// Listen address of the public faucet endpoint.
+    #[arg(long = "ev-reth.faucet-addr", default_value = DEFAULT_FAUCET_ADDR)]
+    pub faucet_addr: SocketAddr,
+
+    /// Mint faucet funds through the mint precompile instead of transferring them from the
+    /// faucet account. The faucet key must be the mint admin or on its allowlist.
+    #[arg(long = "ev-reth.faucet-mint", default_value_t = false)]
+    pub faucet_mint: bool,
+
+    /// Wei sent by a faucet request that does not ask for an amount.
+    #[arg(long = "ev-reth.faucet-amount", default_value_t = DEFAULT_FAUCET_AMOUNT)]
+    pub faucet_amount: u128,
+
+    /// Most wei a single faucet request may ask for.
+    #[arg(long = "ev-reth.faucet-max-amount", default_value_t = DEFAULT_FAUCET_MAX_AMOUNT)]
+    pub faucet_max_amount: u128,
+
+    /// Seconds before the faucet funds the same address again.
+    #[arg(
+        long = "ev-reth.faucet-address-cooldown-secs",
+        default_value_t = DEFAULT_FAUCET_ADDRESS_COOLDOWN_SECS
+    )]
+    pub faucet_address_cooldown_secs: u64,
+
+    /// Seconds before the faucet serves the same client IP again.
+    #[arg(
+        long = "ev-reth.faucet-ip-cooldown-secs",
+        default_value_t = DEFAULT_FAUCET_IP_COOLDOWN_SECS
+    )]
+    pub faucet_ip_cooldown_secs: u64,
+
+    /// File holding a hex-encoded sponsor key. When set, `evolve_submitTransferIntent` relays
+    /// EIP-3009 token transfers in transactions sent and paid for by that key, and with a
+    /// sponsorship policy `evnode_requestSponsorship` sponsors transactions with it.
+    #[arg(long = "ev-reth.sponsor-key-file", value_name = "PATH")]
+    pub sponsor_key_file: Option<PathBuf>,
+
+    /// Tokens whose EIP-3009 transfers `evolve_submitTransferIntent` relays.
+    #[arg(
+        long = "ev-reth.transfer-intent-tokens",
+        value_name = "ADDRESS",
+        value_delimiter = ','
+    )]
+    pub transfer_intent_tokens: Vec<Address>,
+
+    /// JSON file of sponsorship rules. When set, `evnode_requestSponsorship` signs the fee
+    /// payer slot of EvNode transactions the rules allow with the sponsor key.
+    #[arg(
+        long = "ev-reth.sponsorship-policy-file",
+        value_name = "PATH",
+        conflicts_with = "sponsorship_policy_url",
+        requires = "sponsor_key_file"
+    )]
+    pub sponsorship_policy_file: Option<PathBuf>,
+
+    /// URL of a sponsorship policy service each `evnode_requestSponsorship` request is POSTed
+    /// to. Only transactions the service approves are signed by the sponsor key.
+    #[arg(
+        long = "ev-reth.sponsorship-policy-url",
+        value_name = "URL",
+        requires = "sponsor_key_file"
+    )]
+    pub sponsorship_policy_url: Option<Url>,
+
+    /// Milliseconds the sponsorship policy service has to answer.
+    #[arg(
+        long = "ev-reth.sponsorship-policy-timeout-ms",
+        default_value_t = DEFAULT_SPONSORSHIP_POLICY_TIMEOUT_MS
+    )]
+    pub sponsorship_policy_timeout_ms: u64,
+
+    /// File holding a hex-encoded executor key. When set, `evnode_sendTransaction` fills in,
+    /// signs and submits EvNode batches from that key. Only enable it on permissioned nodes:
+    /// every caller spends the key's funds.
+    #[arg(long = "ev-reth.executor-key-file", value_name = "PATH")]
+    pub executor_key_file: Option<PathBuf>,
+}
+
+impl Default for EvolveArgs {
+    fn default() -> Self {
+        Self {
+            build_report: false,
+            shutdown_drain_timeout: DEFAULT_SHUTDOWN_DRAIN_TIMEOUT.as_secs(),
+            speculative_building: false,
+            payload_job_ttl_ms: DEFAULT_PAYLOAD_JOB_TTL_MS,
+            da_finality: false,
+            graphql: false,
+            graphql_addr: DEFAULT_GRAPHQL_ADDR.parse().expect("valid default address"),
+            engine_compression: false,
+            engine_compression_addr: DEFAULT_ENGINE_COMPRESSION_ADDR
+                .parse()
+                .expect("valid default address"),
+            timestamp_policy: TimestampPolicy::Grace,
+            call_cache: false,
+            call_cache_ttl_ms: DEFAULT_CALL_CACHE_TTL_MS,
+            fee_window_secs: DEFAULT_FEE_WINDOW_SECS,
+            attestation_key_file: None,
+            rpc_load_shedding: false,
+            block_interval_ms: DEFAULT_BLOCK_INTERVAL_MS,
+            abi_registry: None,
+            supply_index: false,
+            ordering_audit: None,
+            ordering_report: false,
+            screening_url: None,
+            screening_timeout_ms: DEFAULT_SCREENING_TIMEOUT_MS,
+            screening_fail_closed: false,
+            signing_domain_audit: false,
+            faucet_key_file: None,
+            faucet_addr: DEFAULT_FAUCET_ADDR.parse().expect("valid default address"),
+            faucet_mint: false,
+            faucet_amount: DEFAULT_FAUCET_AMOUNT,
+            faucet_max_amount: DEFAULT_FAUCET_MAX_AMOUNT,
+            faucet_address_cooldown_secs: DEFAULT_FAUCET_ADDRESS_COOLDOWN_SECS,
+            faucet_ip_cooldown_secs: DEFAULT_FAUCET_IP_COOLDOWN_SECS,
+            sponsor_key_file: None,
+            transfer_intent_tokens: Vec::new(),
+            sponsorship_policy_file: None,
+            sponsorship_policy_url: None,
+            sponsorship_policy_timeout_ms: DEFAULT_SPONSORSHIP_POLICY_TIMEOUT_MS,
+            executor_key_file: None,
+        }
+    }
+}
+
+impl EvolveArgs {
+    /// Returns the settings the node's components run with, loading the attestation key if
+    /// one is configured.
+    pub fn node_settings(&self) -> eyre::Result<NodeSettings> {
+        Ok(NodeSettings {
+            timestamp_policy: self.timestamp_policy,
+            speculative_building: self.speculative_building,
+            payload_job_ttl: (self.payload_job_ttl_ms != 0)
+                .then_some(Duration::from_millis(self.payload_job_ttl_ms)),
+            build_reports: self.build_report,
+            ordering_audit: self.ordering_audit,
+            ordering_reports: self.ordering_report,
+            signing_domain_audit: self.signing_domain_audit,
+            screener: self.screening_url.clone().map(|url| {
+                TransactionScreener::new(
+                    url,
+                    Duration::from_millis(self.screening_timeout_ms),
+                    self.screening_fail_closed,
+                )
+            }),
+            fee_window_secs: self.fee_window_secs,
+            attestation_signer: self
+                .attestation_key_file
+                .as_deref()
+                .map(load_signer_key)
+                .transpose()?,
+            load_shedding: self.rpc_load_shedding.then_some(self.block_interval_ms),
+        })
+    }
+}
+
+/// Settings this node runs with from its `--ev-reth.*` flags, as opposed to the chainspec
+/// settings every node of the chain shares.
+///
+/// Carried on the node's [`EvolvePayloadBuilderConfig`](crate::config::EvolvePayloadBuilderConfig),
+/// so each component reads them from the configuration it is built with.
+#[derive(Debug, Clone)]
+pub struct NodeSettings {
+    /// How payloads whose requested timestamp is not after their parent's are built.
+    pub timestamp_policy: TimestampPolicy,
+    /// Whether the next block is built ahead of its payload request in `--dev` mode.
+    pub speculative_building: bool,
+    /// How long a payload job may go unfetched, `None` to keep jobs until the builder
+    /// deadline.
+    pub payload_job_ttl: Option<Duration>,
+    /// Whether a build report is recorded for every built payload.
+    pub build_reports: bool,
+    /// Policy the transaction order of every built block is audited against.
+    pub ordering_audit: Option<OrderingPolicy>,
+    /// Whether an ordering report is recorded for every built block.
+    pub ordering_reports: bool,
+    /// Whether the txpool rejects EvNode signatures that also verify in the other signing
+    /// domain.
+    pub signing_domain_audit: bool,
+    /// External service pool transactions are screened by.
+    pub screener: Option<TransactionScreener>,
+    /// Seconds of block time fee estimates sample priority fees over.
+    pub fee_window_secs: u64,
+    /// Operator key built payloads are attested with.
+    pub attestation_signer: Option<PrivateKeySigner>,
+    /// Block interval, in milliseconds, build times are compared against to shed expensive
+    /// RPC calls, `None` to never shed them.
+    pub load_shedding: Option<u64>,
+}
+
+impl NodeSettings {
+    /// Returns the settings of a node started without any `--ev-reth.*` flags.
+    pub const fn new() -> Self {
+        Self {
+            timestamp_policy: TimestampPolicy::Grace,
+            speculative_building: false,
+            payload_job_ttl: Some(Duration::from_millis(DEFAULT_PAYLOAD_JOB_TTL_MS)),
+            build_reports: false,
+            ordering_audit: None,
+            ordering_reports: false,
+            signing_domain_audit: false,
+            screener: None,
+            fee_window_secs: DEFAULT_FEE_WINDOW_SECS,
+            attestation_signer: None,
+            load_shedding: None,
+        }
+    }
+}
+
+impl Default for NodeSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_ttl_disables_expiry() {
+        let mut args = EvolveArgs {
+            payload_job_ttl_ms: 0,
+            ..Default::default()
+        };
+        assert_eq!(args.node_settings().unwrap().payload_job_ttl, None);
+        args.payload_job_ttl_ms = 250;
+        assert_eq!(
+            args.node_settings().unwrap().payload_job_ttl,
+            Some(Duration::from_millis(250))
+        );
+    }
+}
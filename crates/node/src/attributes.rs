@@ -1,9 +1,10 @@
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{keccak256, Address, Bytes, B256};
 use alloy_rpc_types::{
     engine::{PayloadAttributes as RpcPayloadAttributes, PayloadId},
     Withdrawal,
 };
+use evolve_ev_reth::DecryptionKey;
 use reth_chainspec::EthereumHardforks;
 use reth_engine_local::payload::LocalPayloadAttributesBuilder;
 use reth_ethereum::node::api::payload::PayloadAttributes;
@@ -18,10 +19,33 @@ pub struct EvolveEnginePayloadAttributes {
     #[serde(flatten)]
     pub inner: RpcPayloadAttributes,
     /// Transactions to be included in the payload (passed via Engine API).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub transactions: Option<Vec<Bytes>>,
     /// Optional gas limit for the payload.
-    #[serde(rename = "gasLimit")]
+    #[serde(default, rename = "gasLimit", skip_serializing_if = "Option::is_none")]
     pub gas_limit: Option<u64>,
+    /// Record a per-transaction inclusion report, retrievable via `evolve_getBuildReport`.
+    #[serde(
+        default,
+        rename = "buildReport",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub build_report: Option<bool>,
+    /// Maximum number of transactions to include; candidates past the limit are left out.
+    #[serde(
+        default,
+        rename = "maxTxCount",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_tx_count: Option<u64>,
+    /// Keys revealed for this block; encrypted transactions sealed for them are decrypted and
+    /// executed ahead of `transactions`.
+    #[serde(
+        default,
+        rename = "decryptionKeys",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub decryption_keys: Option<Vec<DecryptionKey>>,
 }
 
 impl PayloadAttributes for EvolveEnginePayloadAttributes {
@@ -46,12 +70,39 @@ impl PayloadAttributes for EvolveEnginePayloadAttributes {
     }
 }
 
+impl EvolveEnginePayloadAttributes {
+    /// Hash committed to by build attestations:
+    /// `keccak256(parent_hash ‖ timestamp ‖ prev_randao ‖ suggested_fee_recipient ‖ gas_limit ‖
+    /// max_tx_count ‖ keccak256(keccak256(tx_0) ‖ … ‖ keccak256(tx_n)))`, with integers as
+    /// 8-byte big-endian values and unset limits as zero.
+    pub fn commitment_hash(&self, parent_hash: B256) -> B256 {
+        let transactions = self.transactions.as_deref().unwrap_or_default();
+        let mut tx_hashes = Vec::with_capacity(transactions.len() * 32);
+        for tx in transactions {
+            tx_hashes.extend_from_slice(keccak256(tx).as_slice());
+        }
+
+        let mut preimage = Vec::with_capacity(32 + 8 + 32 + 20 + 8 + 8 + 32);
+        preimage.extend_from_slice(parent_hash.as_slice());
+        preimage.extend_from_slice(&self.inner.timestamp.to_be_bytes());
+        preimage.extend_from_slice(self.inner.prev_randao.as_slice());
+        preimage.extend_from_slice(self.inner.suggested_fee_recipient.as_slice());
+        preimage.extend_from_slice(&self.gas_limit.unwrap_or_default().to_be_bytes());
+        preimage.extend_from_slice(&self.max_tx_count.unwrap_or_default().to_be_bytes());
+        preimage.extend_from_slice(keccak256(tx_hashes).as_slice());
+        keccak256(preimage)
+    }
+}
+
 impl From<RpcPayloadAttributes> for EvolveEnginePayloadAttributes {
     fn from(inner: RpcPayloadAttributes) -> Self {
         Self {
             inner,
             transactions: None,
             gas_limit: None,
+            build_report: None,
+            max_tx_count: None,
+            decryption_keys: None,
         }
     }
 }
@@ -91,6 +142,64 @@ impl PayloadAttributesBuilder<EvolveEnginePayloadAttributes>
             inner,
             transactions: None,
             gas_limit: None,
+            build_report: None,
+            max_tx_count: None,
+            decryption_keys: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serde_matches_ev_node_wire_format() {
+        let value = json!({
+            "timestamp": "0x65f2d5d7",
+            "prevRandao": B256::repeat_byte(0x01),
+            "suggestedFeeRecipient": Address::repeat_byte(0x02),
+            "withdrawals": [],
+            "parentBeaconBlockRoot": B256::repeat_byte(0x03),
+            "transactions": ["0x01"],
+            "gasLimit": 30_000_000
+        });
+        let attributes: EvolveEnginePayloadAttributes =
+            serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(attributes.inner.timestamp, 0x65f2d5d7);
+        assert_eq!(
+            attributes.transactions,
+            Some(vec![Bytes::from_static(&[0x01])])
+        );
+        assert_eq!(attributes.gas_limit, Some(30_000_000));
+        assert_eq!(attributes.build_report, None);
+        assert_eq!(attributes.max_tx_count, None);
+        assert_eq!(attributes.decryption_keys, None);
+
+        // Unset evolve fields are omitted rather than serialized as `null`.
+        assert_eq!(serde_json::to_value(&attributes).unwrap(), value);
+    }
+
+    #[test]
+    fn commitment_hash_covers_transactions_and_limits() {
+        let mut attributes = EvolveEnginePayloadAttributes::from(RpcPayloadAttributes {
+            timestamp: 1,
+            prev_randao: B256::repeat_byte(0x01),
+            suggested_fee_recipient: Address::repeat_byte(0x02),
+            withdrawals: None,
+            parent_beacon_block_root: None,
+            slot_number: None,
+        });
+        let parent = B256::repeat_byte(0x03);
+        let empty = attributes.commitment_hash(parent);
+        assert_ne!(empty, attributes.commitment_hash(B256::ZERO));
+
+        attributes.transactions = Some(vec![Bytes::from_static(&[0x01])]);
+        let with_tx = attributes.commitment_hash(parent);
+        assert_ne!(with_tx, empty);
+
+        attributes.gas_limit = Some(30_000_000);
+        assert_ne!(attributes.commitment_hash(parent), with_tx);
+    }
+}
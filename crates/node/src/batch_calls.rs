@@ -0,0 +1,165 @@
+use alloy_primitives::{Address, Bytes, Selector, TxKind, B256, U256, U64};
+use async_trait::async_trait;
+use ev_primitives::{Call, EvTxEnvelope, TransactionSigned};
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_storage_api::TransactionsProvider;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::abi_registry::{AbiRegistry, DecodedFunction};
+
+/// A decoded call of an EvNode batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBatchCall {
+    /// Position of the call in the batch.
+    pub index: U64,
+    /// Call target, `null` for a contract creation.
+    pub to: Option<Address>,
+    /// Value transferred.
+    pub value: U256,
+    /// First four bytes of the input, for calls carrying at least that much data.
+    pub selector: Option<Selector>,
+    /// Function called, if its ABI is in the registry.
+    pub function: Option<DecodedFunction>,
+    /// Call data.
+    pub input: Bytes,
+    /// Execution gas cap of the call, omitted for uncapped calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+}
+
+/// Decodes `calls` in batch order, with functions and arguments from `registry`.
+pub fn decode_calls(calls: &[Call], registry: &AbiRegistry) -> Vec<RpcBatchCall> {
+    calls
+        .iter()
+        .enumerate()
+        .map(|(index, call)| {
+            let (to, selector) = match call.to {
+                TxKind::Call(to) => (Some(to), call.input.get(..4).map(Selector::from_slice)),
+                // Creation input is init code, not a function call.
+                TxKind::Create => (None, None),
+            };
+            RpcBatchCall {
+                index: U64::from(index),
+                to,
+                value: call.value,
+                selector,
+                function: selector.and_then(|_| registry.decode(to, &call.input)),
+                input: call.input.clone(),
+                gas_limit: call.gas_limit.map(U64::from),
+            }
+        })
+        .collect()
+}
+
+/// Evolve batch call RPC API.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveBatchCallsApi {
+    /// Returns the decoded calls of a stored EvNode transaction, or `null` if the transaction
+    /// is unknown.
+    #[method(name = "getCallsByTransaction")]
+    async fn get_calls_by_transaction(&self, hash: B256) -> RpcResult<Option<Vec<RpcBatchCall>>>;
+}
+
+/// Implementation of the batch call RPC.
+#[derive(Debug)]
+pub struct EvolveBatchCallsApiImpl<Provider> {
+    provider: Provider,
+    registry: Arc<AbiRegistry>,
+}
+
+impl<Provider> EvolveBatchCallsApiImpl<Provider> {
+    /// Creates a new instance decoding calls with `registry`.
+    pub const fn new(provider: Provider, registry: Arc<AbiRegistry>) -> Self {
+        Self { provider, registry }
+    }
+}
+
+#[async_trait]
+impl<Provider> EvolveBatchCallsApiServer for EvolveBatchCallsApiImpl<Provider>
+where
+    Provider: TransactionsProvider<Transaction = TransactionSigned> + Send + Sync + 'static,
+{
+    async fn get_calls_by_transaction(&self, hash: B256) -> RpcResult<Option<Vec<RpcBatchCall>>> {
+        let tx = self
+            .provider
+            .transaction_by_hash(hash)
+            .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))?;
+        match tx {
+            None => Ok(None),
+            Some(EvTxEnvelope::EvNode(ev)) => {
+                Ok(Some(decode_calls(&ev.tx().calls, &self.registry)))
+            }
+            Some(EvTxEnvelope::Ethereum(_)) => Err(ErrorObject::owned(
+                INVALID_PARAMS_CODE,
+                "not an EvNode transaction",
+                None::<()>,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes};
+
+    #[test]
+    fn decodes_batch_calls() {
+        let token = address!("0x00000000000000000000000000000000000000aa");
+        let registry = AbiRegistry::default();
+        registry
+            .register(
+                token,
+                serde_json::from_str(
+                    r#"[{"type":"function","name":"approve","stateMutability":"nonpayable",
+                        "inputs":[{"name":"spender","type":"address"},{"name":"amount","type":"uint256"}],
+                        "outputs":[{"name":"","type":"bool"}]}]"#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let calls = vec![
+            Call {
+                to: TxKind::Call(token),
+                value: U256::ZERO,
+                input: bytes!("095ea7b30000"),
+                gas_limit: None,
+            },
+            Call {
+                to: TxKind::Call(token),
+                value: U256::from(5),
+                input: bytes!("01"),
+                gas_limit: None,
+            },
+            Call {
+                to: TxKind::Create,
+                value: U256::ZERO,
+                input: bytes!("60806040"),
+                gas_limit: None,
+            },
+        ];
+
+        let decoded = decode_calls(&calls, &registry);
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].to, Some(token));
+        assert_eq!(
+            decoded[0].selector,
+            Some(Selector::from([0x09, 0x5e, 0xa7, 0xb3]))
+        );
+        let function = decoded[0].function.as_ref().unwrap();
+        assert_eq!(function.signature, "approve(address,uint256)");
+        assert_eq!(function.args, None);
+        assert_eq!(decoded[1].index, U64::from(1));
+        assert_eq!(decoded[1].selector, None);
+        assert_eq!(decoded[2].to, None);
+        assert_eq!(decoded[2].selector, None);
+        assert_eq!(decoded[2].function, None);
+    }
+}
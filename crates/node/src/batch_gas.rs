@@ -0,0 +1,333 @@
+use alloy_consensus::Header;
+use alloy_eips::{eip2930::AccessList, BlockId};
+use alloy_primitives::{Address, Bytes, U64};
+use async_trait::async_trait;
+use ev_primitives::Call;
+use ev_revm::EvTxEnv;
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_evm::{ConfigureEvm, Evm};
+use reth_revm::{
+    database::StateProviderDatabase,
+    revm::{
+        context::TxEnv,
+        context_interface::{result::ExecutionResult, transaction::TransactionType},
+    },
+    State,
+};
+use reth_storage_api::{
+    errors::provider::ProviderError, BlockIdReader, HeaderProvider, StateProviderFactory,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::EvolveEvmConfig;
+
+/// Most calls a batch passed to `evolve_estimateBatchGas` may contain.
+pub const MAX_ESTIMATED_CALLS: usize = 128;
+
+/// JSON-RPC error code for reverted execution, as used by `eth_estimateGas`.
+const EXECUTION_REVERTED_CODE: i32 = 3;
+
+/// EvNode batch whose gas `evolve_estimateBatchGas` estimates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBatchGasRequest {
+    /// Executor of the batch.
+    pub from: Address,
+    /// Calls executed in order.
+    pub calls: Vec<Call>,
+    /// EIP-2930 access list.
+    #[serde(default)]
+    pub access_list: AccessList,
+}
+
+/// Gas estimate returned by `evolve_estimateBatchGas`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBatchGasEstimate {
+    /// Lowest gas limit the whole batch succeeds with.
+    pub gas: U64,
+    /// Gas used by the batch, after refunds.
+    pub gas_used: U64,
+    /// Gas used by each call in batch order, including its intrinsic cost: how much the gas
+    /// used grows when the call is appended to the calls before it.
+    pub calls: Vec<U64>,
+}
+
+/// Errors returned by `evolve_estimateBatchGas`.
+#[derive(Debug, Error)]
+pub enum BatchGasError {
+    /// The batch is empty or longer than [`MAX_ESTIMATED_CALLS`].
+    #[error("batch must contain between 1 and {MAX_ESTIMATED_CALLS} calls")]
+    CallCount,
+    /// The requested block is unknown.
+    #[error("unknown block")]
+    UnknownBlock,
+    /// A call reverted at the highest gas limit.
+    #[error("execution reverted in call {index}")]
+    Reverted {
+        /// Position of the reverting call.
+        index: usize,
+        /// Revert data.
+        output: Bytes,
+    },
+    /// A call halted at the highest gas limit.
+    #[error("call {index} halted: {reason}")]
+    Halted {
+        /// Position of the halting call.
+        index: usize,
+        /// Halt reason.
+        reason: String,
+    },
+    /// The batch up to a call is not a valid transaction, e.g. because a call other than the
+    /// first creates a contract or the caller cannot cover the transferred value.
+    #[error("invalid batch at call {index}: {reason}")]
+    Invalid {
+        /// Position of the call that made the batch invalid.
+        index: usize,
+        /// Validation error.
+        reason: String,
+    },
+    /// The block's header or state could not be read.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<BatchGasError> for ErrorObject<'static> {
+    fn from(err: BatchGasError) -> Self {
+        match err {
+            BatchGasError::Reverted { ref output, .. } => Self::owned(
+                EXECUTION_REVERTED_CODE,
+                err.to_string(),
+                Some(output.clone()),
+            ),
+            BatchGasError::Provider(_) => {
+                Self::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+            }
+            _ => Self::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>),
+        }
+    }
+}
+
+/// Why a single execution of a batch prefix did not succeed.
+enum Failure {
+    Reverted(Bytes),
+    Halted(String),
+    Invalid(String),
+}
+
+impl Failure {
+    fn at(self, index: usize) -> BatchGasError {
+        match self {
+            Self::Reverted(output) => BatchGasError::Reverted { index, output },
+            Self::Halted(reason) => BatchGasError::Halted { index, reason },
+            Self::Invalid(reason) => BatchGasError::Invalid { index, reason },
+        }
+    }
+}
+
+/// Returns the lowest gas limit in `gas_used..=cap` that `succeeds`, given that it succeeds at
+/// `cap`.
+///
+/// Limits above the gas used are tried because refunds are deducted after execution and
+/// every nested call holds back 1/64 of the remaining gas.
+pub fn search_gas_limit(gas_used: u64, cap: u64, mut succeeds: impl FnMut(u64) -> bool) -> u64 {
+    if gas_used >= cap || succeeds(gas_used) {
+        return gas_used.min(cap);
+    }
+    let (mut low, mut high) = (gas_used, cap);
+    // Most batches only need the gas held back by nested calls on top of what they use.
+    let optimistic = gas_used.saturating_mul(64) / 63;
+    if optimistic > low && optimistic < high {
+        if succeeds(optimistic) {
+            high = optimistic;
+        } else {
+            low = optimistic;
+        }
+    }
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if succeeds(mid) {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    high
+}
+
+/// Estimates `request` on top of the state after `block`. Nothing is written to the database.
+///
+/// Each prefix of the batch is executed at the highest gas limit to attribute gas to its
+/// last call, then the gas limit of the whole batch is searched for.
+pub(crate) fn estimate_batch_gas<Provider>(
+    provider: &Provider,
+    evm_config: &EvolveEvmConfig,
+    block: BlockId,
+    request: RpcBatchGasRequest,
+) -> Result<RpcBatchGasEstimate, BatchGasError>
+where
+    Provider: StateProviderFactory + BlockIdReader + HeaderProvider<Header = Header>,
+{
+    if request.calls.is_empty() || request.calls.len() > MAX_ESTIMATED_CALLS {
+        return Err(BatchGasError::CallCount);
+    }
+    let hash = provider
+        .block_hash_for_id(block)?
+        .ok_or(BatchGasError::UnknownBlock)?;
+    let header = provider.header(hash)?.ok_or(BatchGasError::UnknownBlock)?;
+    let state = provider.history_by_block_hash(hash)?;
+
+    let mut evm_env = evm_config
+        .evm_env(&header)
+        .unwrap_or_else(|err| match err {});
+    // Like `eth_estimateGas` without fee fields: gas is free, so the caller only has to cover
+    // the transferred value, and the batch is estimated whatever the caller's next nonce.
+    evm_env.cfg_env.disable_base_fee = true;
+    evm_env.cfg_env.disable_nonce_check = true;
+    let cap = evm_env
+        .block_env
+        .gas_limit
+        .min(evm_env.cfg_env.tx_gas_limit_cap());
+    let base_tx = TxEnv {
+        caller: request.from,
+        chain_id: Some(evm_env.cfg_env.chain_id),
+        access_list: request.access_list,
+        tx_type: TransactionType::Eip1559.into(),
+        ..Default::default()
+    };
+    let db = State::builder()
+        .with_database(StateProviderDatabase::new(&state))
+        .build();
+    let mut evm = evm_config.evm_with_env(db, evm_env);
+
+    let mut run = |calls: &[Call], gas_limit: u64| -> Result<u64, Failure> {
+        let tx = TxEnv {
+            gas_limit,
+            ..base_tx.clone()
+        };
+        let result = evm
+            .transact(EvTxEnv::with_calls(tx, calls.to_vec()))
+            .map_err(|err| Failure::Invalid(err.to_string()))?
+            .result;
+        match result {
+            ExecutionResult::Success { .. } => Ok(result.gas().tx_gas_used()),
+            ExecutionResult::Revert { output, .. } => Err(Failure::Reverted(output)),
+            ExecutionResult::Halt { reason, .. } => Err(Failure::Halted(format!("{reason:?}"))),
+        }
+    };
+
+    let mut calls = Vec::with_capacity(request.calls.len());
+    let mut gas_used = 0;
+    for index in 0..request.calls.len() {
+        let prefix_gas_used = run(&request.calls[..=index], cap).map_err(|err| err.at(index))?;
+        calls.push(U64::from(prefix_gas_used.saturating_sub(gas_used)));
+        gas_used = prefix_gas_used;
+    }
+    let gas = search_gas_limit(gas_used, cap, |limit| run(&request.calls, limit).is_ok());
+
+    Ok(RpcBatchGasEstimate {
+        gas: U64::from(gas),
+        gas_used: U64::from(gas_used),
+        calls,
+    })
+}
+
+/// Gas estimation RPC API for EvNode batches.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveBatchGasApi {
+    /// Estimates the gas limit of an EvNode batch executed by `from` on top of `block` (latest
+    /// by default), with the gas used by each call.
+    ///
+    /// Fails with the reverting call's index and revert data if the batch reverts.
+    #[method(name = "estimateBatchGas")]
+    async fn estimate_batch_gas(
+        &self,
+        request: RpcBatchGasRequest,
+        block: Option<BlockId>,
+    ) -> RpcResult<RpcBatchGasEstimate>;
+}
+
+/// Implementation of the batch gas estimation RPC.
+#[derive(Debug)]
+pub struct EvolveBatchGasApiImpl<Provider> {
+    provider: Provider,
+    evm_config: EvolveEvmConfig,
+}
+
+impl<Provider> EvolveBatchGasApiImpl<Provider> {
+    /// Creates a new instance executing batches with `evm_config` on state read from
+    /// `provider`.
+    pub const fn new(provider: Provider, evm_config: EvolveEvmConfig) -> Self {
+        Self {
+            provider,
+            evm_config,
+        }
+    }
+}
+
+#[async_trait]
+impl<Provider> EvolveBatchGasApiServer for EvolveBatchGasApiImpl<Provider>
+where
+    Provider:
+        StateProviderFactory + BlockIdReader + HeaderProvider<Header = Header> + Clone + 'static,
+{
+    async fn estimate_batch_gas(
+        &self,
+        request: RpcBatchGasRequest,
+        block: Option<BlockId>,
+    ) -> RpcResult<RpcBatchGasEstimate> {
+        let provider = self.provider.clone();
+        let evm_config = self.evm_config.clone();
+        let block = block.unwrap_or_default();
+        tokio::task::spawn_blocking(move || {
+            estimate_batch_gas(&provider, &evm_config, block, request)
+        })
+        .await
+        .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))?
+        .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_finds_lowest_succeeding_limit() {
+        // Refunds and the 63/64 rule make the batch need more than it ends up using.
+        let mut attempts = 0;
+        let gas = search_gas_limit(50_000, 30_000_000, |limit| {
+            attempts += 1;
+            limit >= 58_123
+        });
+        assert_eq!(gas, 58_123);
+        assert!(attempts < 30);
+
+        assert_eq!(search_gas_limit(21_000, 30_000_000, |_| true), 21_000);
+        assert_eq!(
+            search_gas_limit(50_000, 30_000_000, |limit| limit >= 50_500),
+            50_500
+        );
+    }
+
+    #[test]
+    fn request_uses_call_encoding() {
+        let request: RpcBatchGasRequest = serde_json::from_value(serde_json::json!({
+            "from": "0x00000000000000000000000000000000000000aa",
+            "calls": [
+                { "to": "0x00000000000000000000000000000000000000bb", "value": "0x1", "input": "0x" },
+                { "to": null, "value": "0x0", "input": "0x6080" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(request.calls.len(), 2);
+        assert!(request.calls[1].to.is_create());
+        assert!(request.access_list.is_empty());
+    }
+}
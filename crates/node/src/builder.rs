@@ -1,14 +1,25 @@
 use crate::{
-    config::EvolvePayloadBuilderConfig, executor::EvEvmConfig, tracing_ext::RecordDurationOnDrop,
+    config::{EvolvePayloadBuilderConfig, ResolvedEvolveConfig},
+    error::EvolveEngineError,
+    executor::EvEvmConfig,
+    tracing_ext::RecordDurationOnDrop,
 };
 use alloy_consensus::{
     transaction::{Transaction, TxHashRef},
     Header,
 };
-use alloy_primitives::Address;
-use ev_revm::EvTxEvmFactory;
-use evolve_ev_reth::EvolvePayloadAttributes;
-use reth_chainspec::{ChainSpec, ChainSpecProvider};
+use alloy_evm::{
+    block::{BlockExecutionError, BlockValidationError},
+    InvalidTxError, WithTxEnv,
+};
+use alloy_primitives::{Address, Bytes, TxKind, B256};
+use ev_primitives::{EvNodeTxDisabled, EvTxEnvelope};
+use ev_revm::{EvTxEnv, EvTxEvmFactory, MinGasPriceSettings, RecoveredSponsors};
+use evolve_ev_reth::{
+    block_commitment_root, derive_prev_randao, BuildReport, EvolvePayloadAttributes,
+    TxInclusionReason,
+};
+use reth_chainspec::{ChainSpec, ChainSpecProvider, EthChainSpec};
 use reth_errors::RethError;
 use reth_evm::{
     execute::{BlockBuilder, BlockBuilderOutcome},
@@ -17,8 +28,13 @@ use reth_evm::{
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_primitives_traits::{SealedBlock, SealedHeader, SignedTransaction};
 use reth_provider::{HeaderProvider, StateProviderFactory};
-use reth_revm::{database::StateProviderDatabase, State};
-use std::sync::Arc;
+use reth_revm::{
+    database::StateProviderDatabase, revm::context_interface::result::InvalidTransaction, State,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use tracing::{debug, debug_span, info, instrument};
 
 type EvolveEthEvmConfig = EvEvmConfig<ChainSpec, EvTxEvmFactory>;
@@ -76,13 +92,100 @@ where
         &self,
         attributes: EvolvePayloadAttributes,
     ) -> Result<SealedBlock<ev_primitives::Block>, PayloadBuilderError> {
+        self.build_payload_with_report(attributes)
+            .await
+            .map(|(block, _)| block)
+    }
+
+    /// Builds a payload and, when `attributes.build_report` is set, returns the
+    /// inclusion decision recorded for every candidate transaction.
+    ///
+    /// Report indices refer to positions in `attributes.transactions`.
+    ///
+    /// Bundles in `attributes.bundles` are all-or-nothing: when any of their transactions
+    /// fails, the block is rebuilt without the whole bundle.
+    ///
+    /// Once `attributes.max_tx_count` transactions are included, the remaining candidates
+    /// are left out; a bundle that does not fit in full is left out entirely.
+    pub async fn build_payload_with_report(
+        &self,
+        attributes: EvolvePayloadAttributes,
+    ) -> Result<(SealedBlock<ev_primitives::Block>, Option<BuildReport>), PayloadBuilderError> {
+        self.build_payload_with_sponsors(attributes, &HashMap::new())
+            .await
+    }
+
+    /// Builds a payload like [`Self::build_payload_with_report`], executing the sponsored
+    /// transactions in `sponsors`, keyed by hash, with the sponsors the txpool recovered for
+    /// them instead of recovering them again.
+    pub async fn build_payload_with_sponsors(
+        &self,
+        attributes: EvolvePayloadAttributes,
+        sponsors: &HashMap<B256, RecoveredSponsors>,
+    ) -> Result<(SealedBlock<ev_primitives::Block>, Option<BuildReport>), PayloadBuilderError> {
         let _duration = RecordDurationOnDrop::new();
 
         // Validate attributes
         attributes
             .validate()
-            .map_err(|e| PayloadBuilderError::Internal(RethError::Other(Box::new(e))))?;
+            .map_err(|e| PayloadBuilderError::other(EvolveEngineError::from(e)))?;
+
+        let mut skipped = HashSet::new();
+        loop {
+            let (sealed_block, mut report, failed) =
+                self.execute_payload(&attributes, sponsors, &skipped)?;
+
+            // Every pass drops at least one more bundle, so this terminates.
+            let reverted: Vec<_> = attributes
+                .bundles
+                .iter()
+                .filter(|range| {
+                    !skipped.contains(&range.start) && range.clone().any(|i| failed.contains(&i))
+                })
+                .collect();
+            if reverted.is_empty() {
+                if let Some(report) = report.as_mut() {
+                    for &index in &skipped {
+                        let tx = &attributes.transactions[index];
+                        report.record_excluded(
+                            index,
+                            Some(*tx.tx_hash()),
+                            TxInclusionReason::BundleReverted,
+                            "another transaction in the bundle failed",
+                        );
+                    }
+                    report.transactions.sort_by_key(|decision| decision.index);
+                }
+                return Ok((sealed_block, report));
+            }
+
+            for range in reverted {
+                info!(
+                    target: "ev-reth",
+                    start = range.start,
+                    len = range.len(),
+                    "bundle transaction failed; rebuilding without the bundle"
+                );
+                skipped.extend(range.clone());
+            }
+        }
+    }
 
+    /// Executes the candidate transactions not in `skipped` and seals the block, returning
+    /// the indices of the transactions that failed.
+    fn execute_payload(
+        &self,
+        attributes: &EvolvePayloadAttributes,
+        sponsors: &HashMap<B256, RecoveredSponsors>,
+        skipped: &HashSet<usize>,
+    ) -> Result<
+        (
+            SealedBlock<ev_primitives::Block>,
+            Option<BuildReport>,
+            HashSet<usize>,
+        ),
+        PayloadBuilderError,
+    > {
         // Get the latest state provider
         let state_provider = self.client.latest().map_err(PayloadBuilderError::other)?;
 
@@ -98,9 +201,9 @@ where
             .client
             .header(attributes.parent_hash)
             .map_err(PayloadBuilderError::other)?
-            .ok_or_else(|| {
-                PayloadBuilderError::Internal(RethError::Other("Parent header not found".into()))
-            })?;
+            .ok_or(PayloadBuilderError::MissingParentHeader(
+                attributes.parent_hash,
+            ))?;
         let block_number = parent_header.number + 1;
         let sealed_parent = SealedHeader::new(parent_header, attributes.parent_hash);
 
@@ -125,16 +228,46 @@ where
             }
         }
 
+        // Withdrawals are only credited once enabled; before that they are dropped so blocks
+        // stay identical to those built by older versions.
+        let withdrawals = if self.config.withdrawals_enabled_for_block(block_number) {
+            attributes.withdrawals.clone()
+        } else {
+            if !attributes.withdrawals.is_empty() {
+                tracing::warn!(
+                    target: "ev-reth",
+                    count = attributes.withdrawals.len(),
+                    block_number,
+                    "ignoring withdrawals in payload attributes; withdrawals are not enabled"
+                );
+            }
+            Vec::new()
+        };
+
+        // Under the derived policy the supplied value is ignored in favour of one every node
+        // can reproduce from the parent block.
+        let prev_randao = if self.config.derives_prev_randao_for_block(block_number) {
+            derive_prev_randao(sealed_parent.hash(), sealed_parent.mix_hash)
+        } else {
+            attributes.prev_randao
+        };
+
+        // Once active, the extra data commits to the window of blocks ending at the parent.
+        let extra_data = match self.config.block_commitment_window_for_block(block_number) {
+            Some(window) => Bytes::from(self.block_commitment(&sealed_parent, window)?),
+            None => Bytes::default(),
+        };
+
         let next_block_attrs = NextBlockEnvAttributes {
             timestamp: attributes.timestamp,
             suggested_fee_recipient,
-            prev_randao: attributes.prev_randao,
+            prev_randao,
             gas_limit,
-            parent_beacon_block_root: Some(alloy_primitives::B256::ZERO), // Set to zero for evolve blocks
+            parent_beacon_block_root: Some(B256::ZERO), // Set to zero for evolve blocks
             // For post-Shanghai/Cancun chains, an empty withdrawals list is valid
             // and ensures version-specific fields are initialized.
-            withdrawals: Some(Default::default()),
-            extra_data: Default::default(),
+            withdrawals: Some(withdrawals.into()),
+            extra_data,
             slot_number: attributes.slot_number,
         };
 
@@ -148,12 +281,81 @@ where
             .apply_pre_execution_changes()
             .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
 
+        let mut report = attributes
+            .build_report
+            .then(|| BuildReport::new(block_number, gas_limit));
+
+        // Attribute-sourced transactions are held to the same minimum as pool transactions,
+        // priced at this block's base fee.
+        let min_gas_price = self.config.min_gas_price_settings().map(
+            |(min_gas_price, exempt_senders, free_gas_targets, activation)| {
+                MinGasPriceSettings::new(
+                    min_gas_price,
+                    exempt_senders,
+                    free_gas_targets,
+                    activation,
+                )
+            },
+        );
+        let base_fee = self
+            .client
+            .chain_spec()
+            .next_block_base_fee(sealed_parent.header(), attributes.timestamp);
+
         // Execute transactions
         info!(
             tx_count = attributes.transactions.len(),
             "executing transactions"
         );
+        let max_batch_calls = self.config.max_batch_calls();
+        let mut failed = HashSet::new();
+        let mut included = 0u64;
         for (i, tx) in attributes.transactions.iter().enumerate() {
+            if skipped.contains(&i) {
+                continue;
+            }
+            if matches!(tx, EvTxEnvelope::EvNode(_)) && !self.config.evnode_tx_enabled() {
+                failed.insert(i);
+                if let Some(report) = report.as_mut() {
+                    report.record_excluded(
+                        i,
+                        Some(*tx.tx_hash()),
+                        TxInclusionReason::TxTypeDisabled,
+                        EvNodeTxDisabled.to_string(),
+                    );
+                }
+                continue;
+            }
+            if let (EvTxEnvelope::EvNode(signed), Some(max_batch_calls)) = (tx, max_batch_calls) {
+                if let Err(err) = max_batch_calls.check(signed.tx(), block_number) {
+                    failed.insert(i);
+                    if let Some(report) = report.as_mut() {
+                        report.record_excluded(
+                            i,
+                            Some(*tx.tx_hash()),
+                            TxInclusionReason::TooManyCalls,
+                            err.to_string(),
+                        );
+                    }
+                    continue;
+                }
+            }
+            // Counted as a failure so that a bundle cut by the limit is left out as a whole.
+            if attributes
+                .max_tx_count
+                .is_some_and(|max_tx_count| included >= max_tx_count)
+            {
+                failed.insert(i);
+                if let Some(report) = report.as_mut() {
+                    report.record_excluded(
+                        i,
+                        Some(*tx.tx_hash()),
+                        TxInclusionReason::TxCountLimit,
+                        "transaction count limit reached",
+                    );
+                }
+                continue;
+            }
             let _span = debug_span!("execute_tx",
                 index = i,
                 hash = %tx.tx_hash(),
@@ -168,12 +370,64 @@ where
                 ))
             })?;
 
-            match builder.execute_transaction(recovered_tx) {
+            // Maintenance lane transactions are exempt from the minimum.
+            if let Some(settings) = min_gas_price
+                .as_ref()
+                .filter(|_| !self.config.handles.maintenance.contains(tx.tx_hash()))
+            {
+                let targets: Vec<TxKind> = match tx {
+                    EvTxEnvelope::Ethereum(tx) => vec![tx.kind()],
+                    EvTxEnvelope::EvNode(signed) => {
+                        signed.tx().calls.iter().map(|call| call.to).collect()
+                    }
+                };
+                if let Err(err) = settings.check(
+                    recovered_tx.signer(),
+                    targets,
+                    tx.effective_gas_price(base_fee),
+                    block_number,
+                ) {
+                    tracing::warn!(error = %err, tx_hash = %tx.tx_hash(), "transaction below minimum gas price");
+                    failed.insert(i);
+                    if let Some(report) = report.as_mut() {
+                        report.record_excluded(
+                            i,
+                            Some(*tx.tx_hash()),
+                            TxInclusionReason::FeeTooLow,
+                            err.to_string(),
+                        );
+                    }
+                    continue;
+                }
+            }
+
+            let tx_env = EvTxEnv::from_recovered_tx_with_sponsors(
+                tx,
+                recovered_tx.signer(),
+                sponsors.get(tx.tx_hash()).copied().unwrap_or_default(),
+            );
+            match builder.execute_transaction(WithTxEnv {
+                tx_env,
+                tx: Arc::new(recovered_tx),
+            }) {
                 Ok(gas_used) => {
                     debug!(gas_used = ?gas_used, "transaction executed successfully");
+                    included += 1;
+                    if let Some(report) = report.as_mut() {
+                        report.record_included(i, *tx.tx_hash(), gas_used);
+                    }
                 }
                 Err(err) => {
                     tracing::warn!(error = ?err, tx_hash = %tx.tx_hash(), "transaction execution failed");
+                    failed.insert(i);
+                    if let Some(report) = report.as_mut() {
+                        report.record_excluded(
+                            i,
+                            Some(*tx.tx_hash()),
+                            classify_execution_error(&err),
+                            err.to_string(),
+                        );
+                    }
                 }
             }
         }
@@ -199,15 +453,56 @@ where
         );
 
         // Return the sealed block
-        Ok(sealed_block)
+        Ok((sealed_block, report, failed))
+    }
+
+    /// Computes the block commitment over the last `window` blocks, ending at `parent`.
+    fn block_commitment(
+        &self,
+        parent: &SealedHeader<Header>,
+        window: u64,
+    ) -> Result<B256, PayloadBuilderError> {
+        let mut hashes = vec![parent.hash()];
+        let mut header = parent.header().clone();
+        while (hashes.len() as u64) < window && header.number > 0 {
+            let hash = header.parent_hash;
+            header = self
+                .client
+                .header(hash)
+                .map_err(PayloadBuilderError::other)?
+                .ok_or_else(|| {
+                    PayloadBuilderError::Internal(RethError::Other(
+                        format!("Ancestor header {hash} not found for block commitment").into(),
+                    ))
+                })?;
+            hashes.push(hash);
+        }
+        hashes.reverse();
+        Ok(block_commitment_root(&hashes))
     }
 }
 
-/// Creates a new payload builder service.
+/// Maps a transaction execution error to the inclusion reason reported for it.
+fn classify_execution_error(err: &BlockExecutionError) -> TxInclusionReason {
+    match err.as_validation() {
+        Some(BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas { .. }) => {
+            TxInclusionReason::GasBudget
+        }
+        Some(BlockValidationError::InvalidTx { error, .. }) => match error.as_invalid_tx_err() {
+            Some(InvalidTransaction::GasPriceLessThanBasefee) => TxInclusionReason::FeeTooLow,
+            Some(InvalidTransaction::NonceTooHigh { .. }) => TxInclusionReason::Dependency,
+            _ => TxInclusionReason::Error,
+        },
+        _ => TxInclusionReason::Error,
+    }
+}
+
+/// Creates a new payload builder service using the node's resolved evolve configuration.
 pub fn create_payload_builder_service<Client>(
     client: Arc<Client>,
     evm_config: EvolveEthEvmConfig,
-) -> Option<EvolvePayloadBuilder<Client>>
+    config: &ResolvedEvolveConfig,
+) -> EvolvePayloadBuilder<Client>
 where
     Client: StateProviderFactory
         + HeaderProvider<Header = Header>
@@ -216,22 +511,7 @@ where
         + Sync
         + 'static,
 {
-    let chain_spec = client.chain_spec();
-
-    let config = match EvolvePayloadBuilderConfig::from_chain_spec(&chain_spec) {
-        Ok(config) => config,
-        Err(err) => {
-            tracing::warn!(target: "ev-reth", error = ?err, "Failed to parse chainspec extras");
-            return None;
-        }
-    };
-
-    if let Err(err) = config.validate() {
-        tracing::warn!(target: "ev-reth", error = ?err, "Invalid evolve payload builder configuration");
-        return None;
-    }
-
-    Some(EvolvePayloadBuilder::new(client, evm_config, config))
+    EvolvePayloadBuilder::new(client, evm_config, config.config().clone())
 }
 
 #[cfg(test)]
@@ -402,4 +682,136 @@ mod tests {
         assert!(span.has_field("nonce"), "span missing nonce field");
         assert!(span.has_field("gas_limit"), "span missing gas_limit field");
     }
+
+    /// Builds payload attributes holding two zero-priced legacy transfers on top of the test
+    /// genesis, and a builder for them with the given config adjustments.
+    fn zero_priced_payload(
+        configure: impl FnOnce(&mut EvolvePayloadBuilderConfig),
+    ) -> (
+        EvolvePayloadBuilder<MockEthProvider>,
+        EvolvePayloadAttributes,
+    ) {
+        use alloy_consensus::TxLegacy;
+        use alloy_primitives::{Bytes, ChainId, Signature, U256};
+
+        let genesis: alloy_genesis::Genesis =
+            serde_json::from_str(include_str!("../../tests/assets/genesis.json"))
+                .expect("valid genesis");
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(reth_chainspec::Chain::from_id(1234))
+                .genesis(genesis)
+                .cancun_activated()
+                .build(),
+        );
+
+        let provider = MockEthProvider::default();
+        let genesis_hash = B256::from_slice(
+            &hex::decode("2b8bbb1ea1e04f9c9809b4b278a8687806edc061a356c7dbc491930d8e922503")
+                .unwrap(),
+        );
+        let genesis_state_root = B256::from_slice(
+            &hex::decode("05e9954443da80d86f2104e56ffdfd98fe21988730684360104865b3dc8191b4")
+                .unwrap(),
+        );
+
+        let genesis_header = Header {
+            state_root: genesis_state_root,
+            number: 0,
+            gas_limit: 30_000_000,
+            timestamp: 1710338135,
+            base_fee_per_gas: Some(0),
+            excess_blob_gas: Some(0),
+            blob_gas_used: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        provider.add_header(genesis_hash, genesis_header);
+
+        let mut config = EvolvePayloadBuilderConfig::from_chain_spec(chain_spec.as_ref()).unwrap();
+        configure(&mut config);
+        let evm_config = EvolveEvmConfig::new(chain_spec);
+        let builder = EvolvePayloadBuilder::new(Arc::new(provider), evm_config, config);
+
+        let transactions = (0..2)
+            .map(|nonce| {
+                let legacy_tx = TxLegacy {
+                    chain_id: Some(ChainId::from(1234u64)),
+                    nonce,
+                    gas_price: 0,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::ZERO),
+                    value: U256::ZERO,
+                    input: Bytes::default(),
+                };
+                let signed = alloy_consensus::Signed::new_unhashed(
+                    reth_ethereum_primitives::Transaction::Legacy(legacy_tx),
+                    Signature::test_signature(),
+                );
+                EvTxEnvelope::Ethereum(reth_ethereum_primitives::TransactionSigned::from(signed))
+            })
+            .collect();
+
+        let attributes = EvolvePayloadAttributes::new(
+            transactions,
+            Some(30_000_000),
+            1710338136,
+            B256::random(),
+            Address::random(),
+            genesis_hash,
+            1,
+        )
+        .with_build_report(true);
+        (builder, attributes)
+    }
+
+    #[tokio::test]
+    async fn max_tx_count_leaves_out_excess_transactions() {
+        let (builder, attributes) = zero_priced_payload(|_| {});
+        let attributes = attributes.with_max_tx_count(Some(0));
+
+        let (block, report) = builder
+            .build_payload_with_report(attributes)
+            .await
+            .expect("build should succeed");
+
+        assert_eq!(block.transaction_count(), 0);
+        let report = report.expect("build report should be recorded");
+        assert_eq!(report.transactions.len(), 2);
+        assert!(report
+            .transactions
+            .iter()
+            .all(|decision| decision.reason == TxInclusionReason::TxCountLimit));
+    }
+
+    #[tokio::test]
+    async fn min_gas_price_leaves_out_zero_priced_transactions() {
+        let (builder, attributes) = zero_priced_payload(|config| {
+            config.min_gas_price = Some(1);
+        });
+
+        let (block, report) = builder
+            .build_payload_with_report(attributes)
+            .await
+            .expect("build should succeed");
+
+        assert_eq!(block.transaction_count(), 0);
+        let report = report.expect("build report should be recorded");
+        assert_eq!(report.transactions.len(), 2);
+        assert!(report
+            .transactions
+            .iter()
+            .all(|decision| decision.reason == TxInclusionReason::FeeTooLow));
+
+        // Calls to a free-gas target are still included.
+        let (builder, attributes) = zero_priced_payload(|config| {
+            config.min_gas_price = Some(1);
+            config.free_gas_targets = vec![Address::ZERO];
+        });
+        let block = builder
+            .build_payload(attributes)
+            .await
+            .expect("build should succeed");
+        assert_eq!(block.transaction_count(), 2);
+    }
 }
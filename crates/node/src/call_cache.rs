@@ -0,0 +1,275 @@
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{keccak256, Bytes, B256};
+use alloy_rpc_types_eth::{
+    state::{EvmOverrides, StateOverride},
+    BlockOverrides,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_provider::CanonStateSubscriptions;
+use reth_rpc_convert::RpcConvert;
+use reth_rpc_eth_api::helpers::EthCall;
+use reth_storage_api::BlockIdReader;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::rpc::{EvRpcTypes, EvTransactionRequest};
+
+/// Default lifetime of a cached call result, in milliseconds.
+pub const DEFAULT_CALL_CACHE_TTL_MS: u64 = 2_000;
+
+/// Most call results held at once.
+pub const MAX_CALL_CACHE_ENTRIES: usize = 4_096;
+
+/// Short-lived cache of `eth_call` results keyed by block hash and request.
+///
+/// A result is only ever served for the exact block it was computed on, so entries never go
+/// stale; they are dropped after the TTL, or as soon as a new canonical head makes their
+/// block no longer the one `latest` resolves to.
+#[derive(Debug)]
+pub struct CallCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(B256, B256), (Instant, Bytes)>>,
+}
+
+impl CallCache {
+    /// Creates an empty cache whose entries live for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached output of `request_hash` executed on `block_hash`, if still live.
+    pub fn get(&self, block_hash: B256, request_hash: B256) -> Option<Bytes> {
+        let mut entries = self.entries.lock().expect("call cache lock poisoned");
+        let key = (block_hash, request_hash);
+        match entries.get(&key) {
+            Some((inserted, output)) if inserted.elapsed() < self.ttl => Some(output.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Stores the output of `request_hash` executed on `block_hash`.
+    ///
+    /// When the cache is full, expired entries are evicted first; if it is still full the
+    /// result is not cached.
+    pub fn insert(&self, block_hash: B256, request_hash: B256, output: Bytes) {
+        let mut entries = self.entries.lock().expect("call cache lock poisoned");
+        if entries.len() >= MAX_CALL_CACHE_ENTRIES {
+            let ttl = self.ttl;
+            entries.retain(|_, (inserted, _)| inserted.elapsed() < ttl);
+            if entries.len() >= MAX_CALL_CACHE_ENTRIES {
+                return;
+            }
+        }
+        entries.insert((block_hash, request_hash), (Instant::now(), output));
+    }
+
+    /// Drops every entry computed on a block other than the new canonical `head`.
+    pub fn retain_head(&self, head: B256) {
+        self.entries
+            .lock()
+            .expect("call cache lock poisoned")
+            .retain(|(block_hash, _), _| *block_hash == head);
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.lock().expect("call cache lock poisoned").len()
+    }
+
+    /// Returns true if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Invalidates `cache` whenever the canonical head changes. Runs until the canonical state
+/// stream ends.
+pub async fn run_call_cache_invalidation<P>(provider: P, cache: Arc<CallCache>)
+where
+    P: CanonStateSubscriptions,
+{
+    let mut canon_state = provider.canonical_state_stream();
+    while let Some(notification) = canon_state.next().await {
+        cache.retain_head(notification.tip().hash());
+    }
+}
+
+/// `eth_call` served through a [`CallCache`], and its `evolve_call` alias.
+///
+/// Registered in place of the default `eth_call` when the cache is enabled.
+#[rpc(server)]
+pub trait EvolveCallApi {
+    /// Executes a call on the given block, reusing a recent identical result if any.
+    #[method(name = "eth_call")]
+    async fn call(
+        &self,
+        request: EvTransactionRequest,
+        block: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<Bytes>;
+
+    /// Same as `eth_call`.
+    #[method(name = "evolve_call")]
+    async fn evolve_call(
+        &self,
+        request: EvTransactionRequest,
+        block: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<Bytes>;
+}
+
+/// Implementation of the cached call RPC.
+#[derive(Debug)]
+pub struct EvolveCallApiImpl<Provider, Eth> {
+    provider: Provider,
+    eth: Eth,
+    cache: Arc<CallCache>,
+}
+
+impl<Provider, Eth> EvolveCallApiImpl<Provider, Eth> {
+    /// Creates a new instance executing cache misses with `eth`.
+    pub const fn new(provider: Provider, eth: Eth, cache: Arc<CallCache>) -> Self {
+        Self {
+            provider,
+            eth,
+            cache,
+        }
+    }
+}
+
+impl<Provider, Eth> EvolveCallApiImpl<Provider, Eth>
+where
+    Provider: BlockIdReader + Send + Sync + 'static,
+    Eth: EthCall<RpcConvert: RpcConvert<Network = EvRpcTypes>> + Send + Sync + 'static,
+{
+    async fn cached_call(
+        &self,
+        request: EvTransactionRequest,
+        block: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<Bytes> {
+        let block = block.unwrap_or_default();
+        // The pending block changes with every pool update, so it is never cached.
+        if block == BlockId::Number(BlockNumberOrTag::Pending) {
+            return self
+                .eth
+                .call(
+                    request,
+                    Some(block),
+                    EvmOverrides::new(state_overrides, block_overrides),
+                )
+                .await
+                .map_err(Into::into);
+        }
+
+        let block_hash = self
+            .provider
+            .block_hash_for_id(block)
+            .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))?
+            .ok_or_else(|| ErrorObject::owned(INVALID_PARAMS_CODE, "unknown block", None::<()>))?;
+        let request_hash = serde_json::to_vec(&(&request, &state_overrides, &block_overrides))
+            .map(keccak256)
+            .map_err(|err| ErrorObject::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>))?;
+
+        if let Some(output) = self.cache.get(block_hash, request_hash) {
+            metrics::counter!("ev_reth_call_cache_requests_total", "result" => "hit").increment(1);
+            return Ok(output);
+        }
+        metrics::counter!("ev_reth_call_cache_requests_total", "result" => "miss").increment(1);
+
+        // Execute on the resolved hash so the result matches its key even if the head moves.
+        let output = self
+            .eth
+            .call(
+                request,
+                Some(block_hash.into()),
+                EvmOverrides::new(state_overrides, block_overrides),
+            )
+            .await
+            .map_err(Into::into)?;
+        self.cache.insert(block_hash, request_hash, output.clone());
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl<Provider, Eth> EvolveCallApiServer for EvolveCallApiImpl<Provider, Eth>
+where
+    Provider: BlockIdReader + Send + Sync + 'static,
+    Eth: EthCall<RpcConvert: RpcConvert<Network = EvRpcTypes>> + Send + Sync + 'static,
+{
+    async fn call(
+        &self,
+        request: EvTransactionRequest,
+        block: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<Bytes> {
+        self.cached_call(request, block, state_overrides, block_overrides)
+            .await
+    }
+
+    async fn evolve_call(
+        &self,
+        request: EvTransactionRequest,
+        block: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<Bytes> {
+        self.cached_call(request, block, state_overrides, block_overrides)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let cache = CallCache::new(Duration::ZERO);
+        cache.insert(
+            B256::with_last_byte(1),
+            B256::with_last_byte(2),
+            Bytes::from_static(&[1]),
+        );
+        assert_eq!(
+            cache.get(B256::with_last_byte(1), B256::with_last_byte(2)),
+            None
+        );
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn new_head_drops_other_blocks() {
+        let cache = CallCache::new(Duration::from_secs(60));
+        let (old_head, new_head) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let request = B256::with_last_byte(3);
+        cache.insert(old_head, request, Bytes::from_static(&[1]));
+        cache.insert(new_head, request, Bytes::from_static(&[2]));
+
+        cache.retain_head(new_head);
+        assert_eq!(cache.get(old_head, request), None);
+        assert_eq!(cache.get(new_head, request), Some(Bytes::from_static(&[2])));
+    }
+}
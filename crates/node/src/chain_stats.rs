@@ -0,0 +1,206 @@
+use alloy_consensus::{BlockHeader, Transaction, TxReceipt, Typed2718};
+use alloy_genesis::Genesis;
+use alloy_primitives::U256;
+use ev_primitives::{EvPrimitives, EvTxEnvelope, Receipt, EVNODE_TX_TYPE_ID};
+use ev_revm::MAX_BURN_BPS;
+use evolve_ev_reth::chain_stats::{BlockStats, ChainStats, SupplyChange};
+use futures::StreamExt;
+use reth_execution_types::ExecutionOutcome;
+use reth_primitives_traits::{BlockBody, RecoveredBlock};
+use reth_provider::CanonStateSubscriptions;
+
+use crate::config::EvolvePayloadBuilderConfig;
+
+/// Aggregates chain KPIs from canonical blocks into the chain statistics tracker of `config`
+/// and exports them as Prometheus metrics.
+///
+/// The native supply starts from `genesis_supply`, so it is only reported while every block
+/// since genesis has been observed by this task. The burned share of the base fee follows the
+/// redirect policy of `config`. Runs until the canonical state stream ends.
+pub async fn run_chain_stats<P>(
+    provider: P,
+    genesis_supply: U256,
+    config: EvolvePayloadBuilderConfig,
+) where
+    P: CanonStateSubscriptions<Primitives = EvPrimitives>,
+{
+    let tracker = &config.handles.chain_stats;
+    tracker.seed_supply(0, genesis_supply);
+    let mut canon_state = provider.canonical_state_stream();
+    while let Some(notification) = canon_state.next().await {
+        if let Some(reverted) = notification.reverted() {
+            tracker.revert_supply(
+                reverted.range(),
+                supply_change(reverted.execution_outcome()),
+            );
+        }
+        let committed = notification.committed();
+        for (block, receipts) in committed.blocks_and_receipts() {
+            let burn_bps = config.base_fee_burn_bps_for_block(block.header().number());
+            let stats = block_stats(block, receipts, burn_bps);
+            record_block_metrics(&stats);
+            tracker.record_block(stats, block.senders().iter().copied());
+        }
+        tracker.apply_supply(
+            committed.range(),
+            supply_change(committed.execution_outcome()),
+        );
+        record_chain_metrics(&tracker.snapshot());
+    }
+}
+
+/// Returns the native supply allocated in `genesis`.
+pub fn genesis_supply(genesis: &Genesis) -> U256 {
+    genesis.alloc.values().fold(U256::ZERO, |supply, account| {
+        supply.saturating_add(account.balance)
+    })
+}
+
+/// Computes the KPIs of a single block from its transactions and receipts, with `burn_bps`
+/// basis points of the base fee burned.
+pub fn block_stats(
+    block: &RecoveredBlock<ev_primitives::Block>,
+    receipts: &[Receipt],
+    burn_bps: u16,
+) -> BlockStats {
+    let header = block.header();
+    let base_fee = header.base_fee_per_gas().unwrap_or_default();
+    let mut stats = BlockStats {
+        block_number: header.number(),
+        ..Default::default()
+    };
+    stats.fees.base_fee = U256::from(header.gas_used()).saturating_mul(U256::from(base_fee));
+    stats.fees.base_fee_burned = burned_share(stats.fees.base_fee, burn_bps);
+
+    let mut cumulative_gas_used = 0;
+    for (tx, receipt) in block.body().transactions().iter().zip(receipts) {
+        *stats
+            .transactions_by_type
+            .entry(tx_type_name(tx.ty()).to_string())
+            .or_default() += 1;
+        match tx {
+            EvTxEnvelope::EvNode(ev) if ev.tx().fee_payer_signature.is_some() => {
+                stats.sponsored_transactions += 1
+            }
+            _ => stats.unsponsored_transactions += 1,
+        }
+
+        let gas_used = receipt.cumulative_gas_used() - cumulative_gas_used;
+        cumulative_gas_used = receipt.cumulative_gas_used();
+        let tip = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+        stats.fees.priority_fee = stats
+            .fees
+            .priority_fee
+            .saturating_add(U256::from(gas_used).saturating_mul(U256::from(tip)));
+    }
+    stats
+}
+
+/// Returns the part of `base_fee` burned under a `burn_bps` policy. Matches the redirect,
+/// which credits the sink with the rounded-down remainder.
+fn burned_share(base_fee: U256, burn_bps: u16) -> U256 {
+    let credited = base_fee.saturating_mul(U256::from(MAX_BURN_BPS - burn_bps.min(MAX_BURN_BPS)))
+        / U256::from(MAX_BURN_BPS);
+    base_fee - credited
+}
+
+/// Sums the balance changes of all accounts touched by a range of blocks.
+fn supply_change(outcome: &ExecutionOutcome<Receipt>) -> SupplyChange {
+    outcome
+        .bundle
+        .state
+        .values()
+        .fold(SupplyChange::default(), |mut change, account| {
+            let before = account
+                .original_info
+                .as_ref()
+                .map_or(U256::ZERO, |info| info.balance);
+            let after = account
+                .info
+                .as_ref()
+                .map_or(U256::ZERO, |info| info.balance);
+            if after > before {
+                change.increase = change.increase.saturating_add(after - before);
+            } else {
+                change.decrease = change.decrease.saturating_add(before - after);
+            }
+            change
+        })
+}
+
+const fn tx_type_name(ty: u8) -> &'static str {
+    match ty {
+        0x00 => "legacy",
+        0x01 => "eip2930",
+        0x02 => "eip1559",
+        0x03 => "eip4844",
+        0x04 => "eip7702",
+        EVNODE_TX_TYPE_ID => "evnode",
+        _ => "unknown",
+    }
+}
+
+fn record_block_metrics(stats: &BlockStats) {
+    for (tx_type, count) in &stats.transactions_by_type {
+        metrics::counter!("ev_reth_chain_transactions_total", "type" => tx_type.clone())
+            .increment(*count);
+    }
+    metrics::counter!("ev_reth_chain_gas_payer_transactions_total", "payer" => "sponsor")
+        .increment(stats.sponsored_transactions);
+    metrics::counter!("ev_reth_chain_gas_payer_transactions_total", "payer" => "sender")
+        .increment(stats.unsponsored_transactions);
+    metrics::gauge!("ev_reth_chain_block_number").set(stats.block_number as f64);
+}
+
+fn record_chain_metrics(stats: &ChainStats) {
+    // Wei amounts exceed what a Prometheus sample holds exactly, so they are exported as
+    // (approximate) gauges of the running totals.
+    metrics::gauge!("ev_reth_chain_fees_wei", "component" => "base_fee")
+        .set(stats.fees.base_fee.saturating_to::<u128>() as f64);
+    metrics::gauge!("ev_reth_chain_fees_wei", "component" => "base_fee_burned")
+        .set(stats.fees.base_fee_burned.saturating_to::<u128>() as f64);
+    metrics::gauge!("ev_reth_chain_fees_wei", "component" => "priority_fee")
+        .set(stats.fees.priority_fee.saturating_to::<u128>() as f64);
+    metrics::gauge!("ev_reth_chain_active_addresses").set(stats.unique_active_addresses as f64);
+    if let Some(supply) = stats.native_supply {
+        metrics::gauge!("ev_reth_chain_native_supply_wei")
+            .set(supply.saturating_to::<u128>() as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::GenesisAccount;
+    use alloy_primitives::Address;
+
+    #[test]
+    fn genesis_supply_sums_allocations() {
+        let genesis = Genesis::default().extend_accounts([
+            (
+                Address::repeat_byte(0x01),
+                GenesisAccount::default().with_balance(U256::from(100)),
+            ),
+            (
+                Address::repeat_byte(0x02),
+                GenesisAccount::default().with_balance(U256::from(23)),
+            ),
+        ]);
+        assert_eq!(genesis_supply(&genesis), U256::from(123));
+    }
+
+    #[test]
+    fn burned_share_matches_redirect_rounding() {
+        assert_eq!(burned_share(U256::from(1_000), 0), U256::ZERO);
+        assert_eq!(burned_share(U256::from(1_000), 2_500), U256::from(250));
+        assert_eq!(burned_share(U256::from(999), 3_333), U256::from(333));
+        assert_eq!(burned_share(U256::from(999), MAX_BURN_BPS), U256::from(999));
+    }
+
+    #[test]
+    fn names_transaction_types() {
+        assert_eq!(tx_type_name(0x02), "eip1559");
+        assert_eq!(tx_type_name(EVNODE_TX_TYPE_ID), "evnode");
+        assert_eq!(tx_type_name(0x7f), "unknown");
+    }
+}
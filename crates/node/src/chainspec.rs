@@ -3,7 +3,7 @@ use eyre::{bail, eyre, Result, WrapErr};
 use reth_chainspec::{BaseFeeParamsKind, ChainSpec, DEV, HOLESKY, HOODI, MAINNET, SEPOLIA};
 use reth_cli::chainspec::{parse_genesis, ChainSpecParser};
 use serde::Deserialize;
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
 
 /// Chains supported by ev-reth. First value should be used as the default.
 pub const SUPPORTED_CHAINS: &[&str] = &["mainnet", "sepolia", "holesky", "hoodi", "dev"];
@@ -68,6 +68,17 @@ fn parse_eip1559_overrides(genesis: &Genesis) -> Result<EvolveEip1559Config> {
     }
 }
 
+/// Returns the paths of the `evolve` extras the EIP-1559 overrides do not read.
+pub(crate) fn unread_eip1559_extras(
+    extras: serde_json::Value,
+) -> Result<BTreeSet<String>, serde_json::Error> {
+    let mut unread = BTreeSet::new();
+    serde_ignored::deserialize::<_, _, EvolveEip1559Config>(extras, |path| {
+        unread.insert(path.to_string());
+    })?;
+    Ok(unread)
+}
+
 fn apply_genesis_overrides(genesis: &mut Genesis, overrides: &EvolveEip1559Config) -> Result<()> {
     let Some(initial_base_fee) = overrides.initial_base_fee_per_gas else {
         return Ok(());
@@ -0,0 +1,619 @@
+//! `ev-reth chaos-engine`: adversarial Engine API sequences replayed against a running node.
+//!
+//! A consensus client with bugs can send valid Engine API calls in orders ev-node never does:
+//! the same attributes twice, a forkchoice update before the block it points at, siblings built
+//! from conflicting attributes, or attributes whose timestamp runs backwards. Each
+//! [`EngineScenario`] generates such a sequence on top of the node's current head, with the
+//! response every step must get. The same sequences run in the e2e tests through any
+//! [`EngineTransport`].
+//!
+//! Steps refer to values returned by earlier steps as `"${name}"`, as in the Engine API
+//! fixtures. A step binds names to parts of its result; binding a name twice requires the same
+//! value both times.
+//!
+//! The scenarios build and import real blocks, so only run the command against a devnet.
+
+use crate::report_command::{CommandReport, ReportCommand};
+use alloy_primitives::{Address, B256, U64};
+use alloy_rpc_types_engine::{Claims, JwtSecret};
+use async_trait::async_trait;
+use clap::{Parser, ValueEnum};
+use eyre::{bail, eyre, WrapErr};
+use reqwest::Url;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Payload id no node hands out.
+const UNKNOWN_PAYLOAD_ID: &str = "0xffffffffffffffff";
+
+/// Engine API error code of an unknown payload id.
+const UNKNOWN_PAYLOAD_CODE: i64 = -38001;
+
+/// Adversarial Engine API sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum EngineScenario {
+    /// The same attributes sent twice must yield the same payload id and payload.
+    DuplicatePayloadId,
+    /// Conflicting attributes at the same height build sibling blocks, and forkchoice moves
+    /// between them.
+    ConflictingAttributes,
+    /// A forkchoice update points at a block before the block is imported.
+    OutOfOrderForkchoice,
+    /// Attributes with a timestamp before the head's are rejected, as the `grace` and
+    /// `reject` timestamp policies cannot build them.
+    TimestampRegression,
+}
+
+impl EngineScenario {
+    /// All scenarios, in the order they run.
+    pub const ALL: [Self; 4] = [
+        Self::DuplicatePayloadId,
+        Self::ConflictingAttributes,
+        Self::OutOfOrderForkchoice,
+        Self::TimestampRegression,
+    ];
+
+    /// Name of the scenario on the command line.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::DuplicatePayloadId => "duplicate-payload-id",
+            Self::ConflictingAttributes => "conflicting-attributes",
+            Self::OutOfOrderForkchoice => "out-of-order-forkchoice",
+            Self::TimestampRegression => "timestamp-regression",
+        }
+    }
+
+    /// Returns the steps of the scenario on top of `head`, or none if `head` does not allow
+    /// the scenario.
+    pub fn steps(&self, head: &ChainHead) -> Vec<EngineStep> {
+        match self {
+            Self::DuplicatePayloadId => duplicate_payload_id(head),
+            Self::ConflictingAttributes => conflicting_attributes(head),
+            Self::OutOfOrderForkchoice => out_of_order_forkchoice(head),
+            Self::TimestampRegression => timestamp_regression(head),
+        }
+    }
+}
+
+impl fmt::Display for EngineScenario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Canonical head the scenarios build on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainHead {
+    /// Hash of the head block.
+    pub hash: B256,
+    /// Timestamp of the head block.
+    pub timestamp: u64,
+    /// Gas limit of the head block.
+    pub gas_limit: u64,
+}
+
+impl ChainHead {
+    /// Reads the head from an `eth_getBlockByNumber` result.
+    pub fn from_block(block: &Value) -> eyre::Result<Self> {
+        let field = |name: &str| {
+            block
+                .get(name)
+                .cloned()
+                .ok_or_else(|| eyre!("head block has no `{name}`"))
+        };
+        Ok(Self {
+            hash: serde_json::from_value(field("hash")?)?,
+            timestamp: serde_json::from_value::<U64>(field("timestamp")?)?.to(),
+            gas_limit: serde_json::from_value::<U64>(field("gasLimit")?)?.to(),
+        })
+    }
+}
+
+/// Response a step must get.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expect {
+    /// Any result.
+    Result,
+    /// A result or a JSON-RPC error; the node only has to answer.
+    Answered,
+    /// A result with one of the given payload statuses.
+    Status(&'static [&'static str]),
+    /// A JSON-RPC error with the given code.
+    Error(i64),
+    /// A JSON-RPC error or an `INVALID` payload status.
+    Rejected,
+}
+
+const VALID: Expect = Expect::Status(&["VALID"]);
+
+/// One Engine API call of a scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngineStep {
+    /// Engine API method.
+    pub method: &'static str,
+    /// Parameters, possibly containing `"${name}"` placeholders.
+    pub params: Vec<Value>,
+    /// Response the call must get.
+    pub expect: Expect,
+    /// Names bound to JSON pointers into the result; ignored if the call returned an error.
+    pub binds: Vec<(&'static str, &'static str)>,
+}
+
+impl EngineStep {
+    fn new(method: &'static str, params: Vec<Value>, expect: Expect) -> Self {
+        Self {
+            method,
+            params,
+            expect,
+            binds: Vec::new(),
+        }
+    }
+
+    fn bind(mut self, name: &'static str, pointer: &'static str) -> Self {
+        self.binds.push((name, pointer));
+        self
+    }
+}
+
+fn forkchoice(head: impl Into<Value>, attributes: Option<Value>, expect: Expect) -> EngineStep {
+    let head = head.into();
+    EngineStep::new(
+        "engine_forkchoiceUpdatedV3",
+        vec![
+            json!({
+                "headBlockHash": head,
+                "safeBlockHash": head,
+                "finalizedBlockHash": B256::ZERO,
+            }),
+            attributes.unwrap_or(Value::Null),
+        ],
+        expect,
+    )
+}
+
+fn attributes(head: &ChainHead, timestamp: u64, salt: u8) -> Value {
+    json!({
+        "timestamp": U64::from(timestamp),
+        "prevRandao": B256::with_last_byte(salt),
+        "suggestedFeeRecipient": Address::with_last_byte(salt),
+        "withdrawals": [],
+        "parentBeaconBlockRoot": B256::ZERO,
+        "transactions": [],
+        "gasLimit": U64::from(head.gas_limit),
+    })
+}
+
+fn get_payload(payload_id: &str) -> EngineStep {
+    EngineStep::new(
+        "engine_getPayloadV3",
+        vec![payload_id.into()],
+        Expect::Result,
+    )
+}
+
+fn new_payload(payload: &str) -> EngineStep {
+    EngineStep::new(
+        "engine_newPayloadV3",
+        vec![payload.into(), json!([]), json!(B256::ZERO)],
+        VALID,
+    )
+}
+
+fn duplicate_payload_id(head: &ChainHead) -> Vec<EngineStep> {
+    let attrs = attributes(head, head.timestamp + 1, 1);
+    vec![
+        forkchoice(head.hash.to_string(), Some(attrs.clone()), VALID)
+            .bind("payloadId", "/payloadId"),
+        forkchoice(head.hash.to_string(), Some(attrs), VALID).bind("payloadId", "/payloadId"),
+        get_payload("${payloadId}").bind("blockHash", "/executionPayload/blockHash"),
+        // A resolved job may be gone; if it is not, it must return the same payload.
+        EngineStep::new(
+            "engine_getPayloadV3",
+            vec!["${payloadId}".into()],
+            Expect::Answered,
+        )
+        .bind("blockHash", "/executionPayload/blockHash"),
+        EngineStep::new(
+            "engine_getPayloadV3",
+            vec![UNKNOWN_PAYLOAD_ID.into()],
+            Expect::Error(UNKNOWN_PAYLOAD_CODE),
+        ),
+        forkchoice(head.hash.to_string(), None, VALID),
+    ]
+}
+
+fn conflicting_attributes(head: &ChainHead) -> Vec<EngineStep> {
+    let timestamp = head.timestamp + 1;
+    vec![
+        forkchoice(
+            head.hash.to_string(),
+            Some(attributes(head, timestamp, 1)),
+            VALID,
+        )
+        .bind("payloadId", "/payloadId"),
+        forkchoice(
+            head.hash.to_string(),
+            Some(attributes(head, timestamp, 2)),
+            VALID,
+        )
+        .bind("siblingPayloadId", "/payloadId"),
+        get_payload("${payloadId}")
+            .bind("payload", "/executionPayload")
+            .bind("blockHash", "/executionPayload/blockHash"),
+        get_payload("${siblingPayloadId}")
+            .bind("siblingPayload", "/executionPayload")
+            .bind("siblingBlockHash", "/executionPayload/blockHash"),
+        new_payload("${payload}"),
+        new_payload("${siblingPayload}"),
+        forkchoice("${siblingBlockHash}", None, VALID),
+        forkchoice("${blockHash}", None, VALID),
+    ]
+}
+
+fn out_of_order_forkchoice(head: &ChainHead) -> Vec<EngineStep> {
+    vec![
+        forkchoice(
+            head.hash.to_string(),
+            Some(attributes(head, head.timestamp + 1, 1)),
+            VALID,
+        )
+        .bind("payloadId", "/payloadId"),
+        get_payload("${payloadId}")
+            .bind("payload", "/executionPayload")
+            .bind("blockHash", "/executionPayload/blockHash"),
+        // A node that kept the block it built may already know it.
+        forkchoice("${blockHash}", None, Expect::Status(&["SYNCING", "VALID"])),
+        new_payload("${payload}"),
+        forkchoice("${blockHash}", None, VALID),
+        forkchoice(head.hash.to_string(), None, VALID),
+        forkchoice("${blockHash}", None, VALID),
+    ]
+}
+
+fn timestamp_regression(head: &ChainHead) -> Vec<EngineStep> {
+    let Some(timestamp) = head.timestamp.checked_sub(1) else {
+        return Vec::new();
+    };
+    vec![
+        forkchoice(
+            head.hash.to_string(),
+            Some(attributes(head, timestamp, 1)),
+            Expect::Rejected,
+        ),
+        forkchoice(head.hash.to_string(), None, VALID),
+    ]
+}
+
+/// Replaces bound placeholders in a step parameter.
+fn substitute(value: &Value, bindings: &HashMap<&str, Value>) -> eyre::Result<Value> {
+    if let Some(name) = placeholder(value) {
+        return bindings
+            .get(name)
+            .cloned()
+            .ok_or_else(|| eyre!("step uses unbound placeholder ${{{name}}}"));
+    }
+    Ok(match value {
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| substitute(item, bindings))
+                .collect::<eyre::Result<_>>()?,
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, field)| Ok((key.clone(), substitute(field, bindings)?)))
+                .collect::<eyre::Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+/// Returns the binding name of a `"${name}"` placeholder.
+fn placeholder(value: &Value) -> Option<&str> {
+    value.as_str()?.strip_prefix("${")?.strip_suffix('}')
+}
+
+/// Returns the payload status of a `forkchoiceUpdated` or `newPayload` result.
+fn payload_status(result: &Value) -> Option<&str> {
+    result
+        .pointer("/payloadStatus/status")
+        .or_else(|| result.pointer("/status"))?
+        .as_str()
+}
+
+/// Checks a step's response against its expectation.
+fn check_response(expect: Expect, response: &Result<Value, Value>) -> Result<(), String> {
+    match (expect, response) {
+        (Expect::Result, Ok(_)) | (Expect::Answered, _) => Ok(()),
+        (Expect::Status(statuses), Ok(result)) => match payload_status(result) {
+            Some(status) if statuses.contains(&status) => Ok(()),
+            status => Err(format!("expected status {statuses:?}, got {status:?}")),
+        },
+        (Expect::Error(code), Err(err)) if err["code"] == code => Ok(()),
+        (Expect::Error(code), _) => Err(format!("expected error {code}, got {response:?}")),
+        (Expect::Rejected, Err(_)) => Ok(()),
+        (Expect::Rejected, Ok(result)) if payload_status(result) == Some("INVALID") => Ok(()),
+        (Expect::Rejected, Ok(result)) => Err(format!("expected a rejection, got {result}")),
+        (Expect::Result | Expect::Status(_), Err(err)) => Err(format!("unexpected error {err}")),
+    }
+}
+
+/// Engine API endpoint the scenarios run against.
+#[async_trait]
+pub trait EngineTransport: Send + Sync {
+    /// Calls `method`, returning its result or its JSON-RPC error object.
+    ///
+    /// Failing to reach the node is an error of its own: the node must answer every call.
+    async fn call(&self, method: &str, params: Vec<Value>) -> eyre::Result<Result<Value, Value>>;
+}
+
+/// Outcome of one scenario.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScenarioOutcome {
+    /// Scenario that ran.
+    pub scenario: EngineScenario,
+    /// Number of steps the node answered as expected.
+    pub steps_passed: usize,
+    /// Number of steps in the scenario; zero if the head did not allow it.
+    pub steps: usize,
+    /// Why the scenario failed, if it did.
+    pub failure: Option<String>,
+}
+
+/// Reads the canonical head of the node behind `transport`.
+pub async fn chain_head<T: EngineTransport + ?Sized>(transport: &T) -> eyre::Result<ChainHead> {
+    let block = transport
+        .call("eth_getBlockByNumber", vec!["latest".into(), false.into()])
+        .await?
+        .map_err(|err| eyre!("eth_getBlockByNumber failed: {err}"))?;
+    ChainHead::from_block(&block)
+}
+
+/// Runs `scenario` on top of the current head of the node behind `transport`.
+///
+/// Returns an error only if the node could not be reached; unexpected answers are reported
+/// in the outcome.
+pub async fn run_scenario<T: EngineTransport + ?Sized>(
+    transport: &T,
+    scenario: EngineScenario,
+) -> eyre::Result<ScenarioOutcome> {
+    let steps = scenario.steps(&chain_head(transport).await?);
+    let mut outcome = ScenarioOutcome {
+        scenario,
+        steps_passed: 0,
+        steps: steps.len(),
+        failure: None,
+    };
+    let mut bindings = HashMap::new();
+    for (index, step) in steps.iter().enumerate() {
+        let params = step
+            .params
+            .iter()
+            .map(|param| substitute(param, &bindings))
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let response = transport
+            .call(step.method, params)
+            .await
+            .wrap_err_with(|| format!("step {index} ({})", step.method))?;
+        let checked = check_response(step.expect, &response).and_then(|()| {
+            let Ok(result) = &response else {
+                return Ok(());
+            };
+            for (name, pointer) in &step.binds {
+                let value = result
+                    .pointer(pointer)
+                    .ok_or_else(|| format!("result has no `{pointer}` to bind ${{{name}}}"))?;
+                match bindings.get(name) {
+                    Some(bound) if bound != value => {
+                        return Err(format!("expected ${{{name}}} = {bound}, got {value}"))
+                    }
+                    Some(_) => {}
+                    None => {
+                        bindings.insert(*name, value.clone());
+                    }
+                }
+            }
+            Ok(())
+        });
+        if let Err(err) = checked {
+            outcome.failure = Some(format!("step {index} ({}): {err}", step.method));
+            break;
+        }
+        outcome.steps_passed += 1;
+    }
+    Ok(outcome)
+}
+
+/// Engine API transport over HTTP, authenticated with the node's JWT secret.
+#[derive(Debug)]
+pub struct AuthHttpTransport {
+    client: reqwest::Client,
+    url: Url,
+    secret: JwtSecret,
+}
+
+impl AuthHttpTransport {
+    /// Creates a transport calling the Engine API at `url`.
+    pub fn new(url: Url, secret: JwtSecret) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            secret,
+        }
+    }
+}
+
+#[async_trait]
+impl EngineTransport for AuthHttpTransport {
+    async fn call(&self, method: &str, params: Vec<Value>) -> eyre::Result<Result<Value, Value>> {
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let token = self.secret.encode(&Claims { iat, exp: None })?;
+        let mut response: Value = self
+            .client
+            .post(self.url.clone())
+            .bearer_auth(token)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        match (response.get_mut("result"), response.get_mut("error")) {
+            (_, Some(error)) => Ok(Err(error.take())),
+            (Some(result), None) => Ok(Ok(result.take())),
+            (None, None) => bail!("malformed JSON-RPC response: {response}"),
+        }
+    }
+}
+
+/// Outcomes of all scenarios run by `ev-reth chaos-engine`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChaosReport {
+    /// Outcomes in the order the scenarios ran.
+    pub outcomes: Vec<ScenarioOutcome>,
+}
+
+impl CommandReport for ChaosReport {
+    /// Returns true if no scenario failed.
+    fn is_healthy(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|outcome| outcome.failure.is_none())
+    }
+}
+
+impl fmt::Display for ChaosReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for outcome in &self.outcomes {
+            match &outcome.failure {
+                Some(failure) => writeln!(f, "[FAIL] {}: {failure}", outcome.scenario)?,
+                None if outcome.steps == 0 => {
+                    writeln!(f, "[SKIP] {}: not possible on this head", outcome.scenario)?
+                }
+                None => writeln!(f, "[PASS] {}: {} steps", outcome.scenario, outcome.steps)?,
+            }
+        }
+        let failed = self
+            .outcomes
+            .iter()
+            .filter(|outcome| outcome.failure.is_some())
+            .count();
+        write!(
+            f,
+            "{} passed, {failed} failed",
+            self.outcomes.len() - failed
+        )
+    }
+}
+
+/// `ev-reth chaos-engine`: replays adversarial Engine API sequences against a running node.
+#[derive(Debug, Parser)]
+#[command(name = "ev-reth chaos-engine")]
+pub struct ChaosEngineCommand {
+    /// Engine API endpoint of the node.
+    #[arg(long = "engine-url", default_value = "http://127.0.0.1:8551")]
+    pub engine_url: Url,
+    /// Path to the JWT secret the node's Engine API expects.
+    #[arg(long = "jwt-secret")]
+    pub jwt_secret: PathBuf,
+    /// Scenario to run; may be repeated. Runs every scenario when omitted.
+    #[arg(long = "scenario", value_enum)]
+    pub scenarios: Vec<EngineScenario>,
+}
+
+impl ReportCommand for ChaosEngineCommand {
+    const NAME: &'static str = "chaos-engine";
+
+    type Report = ChaosReport;
+
+    /// Runs the selected scenarios in order against the node.
+    fn run(self) -> eyre::Result<ChaosReport> {
+        let secret = JwtSecret::from_file(&self.jwt_secret)
+            .wrap_err_with(|| format!("reading JWT secret {}", self.jwt_secret.display()))?;
+        let transport = AuthHttpTransport::new(self.engine_url, secret);
+        let scenarios = if self.scenarios.is_empty() {
+            EngineScenario::ALL.to_vec()
+        } else {
+            self.scenarios
+        };
+
+        tokio::runtime::Runtime::new()?.block_on(async {
+            let mut report = ChaosReport::default();
+            for scenario in scenarios {
+                report
+                    .outcomes
+                    .push(run_scenario(&transport, scenario).await?);
+            }
+            Ok(report)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn head(timestamp: u64) -> ChainHead {
+        ChainHead {
+            hash: B256::repeat_byte(0x11),
+            timestamp,
+            gas_limit: 30_000_000,
+        }
+    }
+
+    /// Every placeholder a scenario uses is bound by an earlier step.
+    #[test]
+    fn scenarios_only_use_bound_placeholders() {
+        fn placeholders<'a>(value: &'a Value, found: &mut Vec<&'a str>) {
+            match value {
+                Value::Array(items) => items.iter().for_each(|item| placeholders(item, found)),
+                Value::Object(fields) => fields.values().for_each(|f| placeholders(f, found)),
+                other => found.extend(placeholder(other)),
+            }
+        }
+
+        for scenario in EngineScenario::ALL {
+            let mut bound = Vec::new();
+            for step in scenario.steps(&head(10)) {
+                let mut used = Vec::new();
+                step.params.iter().for_each(|p| placeholders(p, &mut used));
+                for name in used {
+                    assert!(bound.contains(&name), "{scenario}: ${{{name}}} unbound");
+                }
+                bound.extend(step.binds.iter().map(|(name, _)| *name));
+            }
+        }
+    }
+
+    #[test]
+    fn timestamp_regression_needs_a_later_head() {
+        assert!(EngineScenario::TimestampRegression
+            .steps(&head(0))
+            .is_empty());
+        let steps = EngineScenario::TimestampRegression.steps(&head(10));
+        assert_eq!(steps[0].params[1]["timestamp"], "0x9");
+        assert_eq!(steps[0].expect, Expect::Rejected);
+    }
+
+    #[test]
+    fn responses_checked_against_expectations() {
+        let valid = Ok(json!({ "payloadStatus": { "status": "VALID" }, "payloadId": "0x1" }));
+        let invalid = Ok(json!({ "status": "INVALID" }));
+        let unknown = Err(json!({ "code": -38001, "message": "Unknown payload" }));
+
+        assert!(check_response(VALID, &valid).is_ok());
+        assert!(check_response(Expect::Status(&["SYNCING"]), &valid).is_err());
+        assert!(check_response(VALID, &unknown).is_err());
+        assert!(check_response(Expect::Rejected, &invalid).is_ok());
+        assert!(check_response(Expect::Rejected, &unknown).is_ok());
+        assert!(check_response(Expect::Rejected, &valid).is_err());
+        assert!(check_response(Expect::Error(UNKNOWN_PAYLOAD_CODE), &unknown).is_ok());
+        assert!(check_response(Expect::Error(UNKNOWN_PAYLOAD_CODE), &valid).is_err());
+    }
+}
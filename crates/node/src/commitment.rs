@@ -0,0 +1,107 @@
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{B256, U64};
+use async_trait::async_trait;
+use evolve_ev_reth::block_commitment_root;
+use jsonrpsee::types::{error::INTERNAL_ERROR_CODE, ErrorObject};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_storage_api::{BlockHashReader, BlockIdReader};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Rolling block commitment returned by `evolve_getBlockCommitment`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockCommitment {
+    /// Merkle root over the hashes of blocks `fromBlock..=toBlock`.
+    pub root: B256,
+    /// First block covered by the commitment.
+    pub from_block: U64,
+    /// Last block covered by the commitment.
+    pub to_block: U64,
+}
+
+/// Returns the blocks covered by the commitment of `window` blocks ending at `block_number`.
+pub fn commitment_range(block_number: u64, window: u64) -> RangeInclusive<u64> {
+    block_number.saturating_sub(window.saturating_sub(1))..=block_number
+}
+
+/// Block commitment RPC API for shared sequencing and settlement layers.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveCommitmentApi {
+    /// Returns the commitment over the window of canonical blocks ending at `block` (latest
+    /// by default), or `null` if the block is unknown.
+    ///
+    /// Once enabled in the chainspec, block `n + 1` carries the commitment ending at block `n`
+    /// as its extra data.
+    #[method(name = "getBlockCommitment")]
+    async fn get_block_commitment(
+        &self,
+        block: Option<BlockNumberOrTag>,
+    ) -> RpcResult<Option<RpcBlockCommitment>>;
+}
+
+/// Implementation of the block commitment RPC.
+#[derive(Debug)]
+pub struct EvolveCommitmentApiImpl<Provider> {
+    provider: Provider,
+    /// Number of blocks covered by a commitment.
+    window: u64,
+}
+
+impl<Provider> EvolveCommitmentApiImpl<Provider> {
+    /// Creates a new instance committing to `window` blocks read from `provider`.
+    pub const fn new(provider: Provider, window: u64) -> Self {
+        Self { provider, window }
+    }
+}
+
+fn internal_error(err: impl ToString) -> ErrorObject<'static> {
+    ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+#[async_trait]
+impl<Provider> EvolveCommitmentApiServer for EvolveCommitmentApiImpl<Provider>
+where
+    Provider: BlockIdReader + BlockHashReader + Send + Sync + 'static,
+{
+    async fn get_block_commitment(
+        &self,
+        block: Option<BlockNumberOrTag>,
+    ) -> RpcResult<Option<RpcBlockCommitment>> {
+        let Some(block_number) = self
+            .provider
+            .convert_block_number(block.unwrap_or_default())
+            .map_err(internal_error)?
+        else {
+            return Ok(None);
+        };
+        let range = commitment_range(block_number, self.window);
+        let hashes = self
+            .provider
+            .canonical_hashes_range(*range.start(), block_number + 1)
+            .map_err(internal_error)?;
+        if hashes.len() as u64 != block_number + 1 - range.start() {
+            // The block is beyond the canonical head, e.g. a pending tag resolved ahead of it.
+            return Ok(None);
+        }
+        Ok(Some(RpcBlockCommitment {
+            root: block_commitment_root(&hashes),
+            from_block: U64::from(*range.start()),
+            to_block: U64::from(block_number),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commitment_range_is_clamped_at_genesis() {
+        assert_eq!(commitment_range(100, 64), 37..=100);
+        assert_eq!(commitment_range(10, 64), 0..=10);
+        assert_eq!(commitment_range(0, 64), 0..=0);
+        assert_eq!(commitment_range(5, 1), 5..=5);
+    }
+}
@@ -1,12 +1,45 @@
-use alloy_primitives::Address;
+use alloy_primitives::{Address, U256};
+use ev_primitives::{EvNodeTxTypeAlias, MaxBatchCalls, SponsorHashScheme};
+use ev_revm::{
+    limits::{MAX_CALL_DEPTH, MAX_MEMORY_LIMIT, MIN_CALL_DEPTH, MIN_MEMORY_LIMIT},
+    parse_evm_version, AccessListSettings, SystemFeeMode, MAX_BURN_BPS, MAX_TX_GAS_BPS,
+};
+use evolve_ev_reth::{
+    attestation::BuildAttestationStore,
+    build_report::BuildReportStore,
+    bundle::BundlePool,
+    chain_stats::ChainStatsTracker,
+    currency::{NativeCurrency, MAX_NATIVE_DECIMALS},
+    da::DaFinalityTracker,
+    encrypted::EncryptedPool,
+    fee_estimator::FeeEstimator,
+    maintenance::MaintenanceLane,
+    ordering::OrderingReportStore,
+    shutdown::ShutdownCoordinator,
+    PrevRandaoPolicy, DEFAULT_BLOCK_COMMITMENT_WINDOW, MAX_BLOCK_COMMITMENT_WINDOW,
+};
 use reth_chainspec::ChainSpec;
+use reth_revm::revm::primitives::hardfork::SpecId;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeSet, HashSet},
+    ops::Deref,
+    sync::{Arc, OnceLock},
+};
+
+use crate::{
+    args::NodeSettings,
+    error::EngineErrorStore,
+    pending_block::PendingBlockStore,
+    state_patch::{StatePatch, StatePatchReceiptStore, MAX_STATE_PATCHES},
+};
 
 /// Default contract size limit in bytes (24KB per EIP-170).
 pub const DEFAULT_CONTRACT_SIZE_LIMIT: usize = 24 * 1024;
 /// Maximum number of addresses allowed in the deploy allowlist.
 pub const MAX_DEPLOY_ALLOWLIST_LEN: usize = 1024;
+/// Maximum number of addresses allowed in the sponsor allowlist.
+pub const MAX_SPONSOR_ALLOWLIST_LEN: usize = 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct ChainspecEvolveConfig {
@@ -14,6 +47,9 @@ struct ChainspecEvolveConfig {
     pub base_fee_sink: Option<Address>,
     #[serde(default, rename = "baseFeeRedirectActivationHeight")]
     pub base_fee_redirect_activation_height: Option<u64>,
+    /// Share of the base fee burned instead of redirected, in basis points.
+    #[serde(default, rename = "baseFeeBurnBps")]
+    pub base_fee_burn_bps: Option<u16>,
     #[serde(default, rename = "mintAdmin")]
     pub mint_admin: Option<Address>,
     #[serde(default, rename = "mintPrecompileActivationHeight")]
@@ -30,6 +66,240 @@ struct ChainspecEvolveConfig {
     /// Block height at which deploy allowlist enforcement activates.
     #[serde(default, rename = "deployAllowlistActivationHeight")]
     pub deploy_allowlist_activation_height: Option<u64>,
+    /// Source of block `prev_randao` values.
+    #[serde(default, rename = "prevRandaoPolicy")]
+    pub prev_randao_policy: PrevRandaoPolicy,
+    /// Block height at which the `prev_randao` policy activates.
+    #[serde(default, rename = "prevRandaoPolicyActivationHeight")]
+    pub prev_randao_policy_activation_height: Option<u64>,
+    /// Block height from which Engine API withdrawals are credited. Disabled when unset.
+    #[serde(default, rename = "withdrawalsActivationHeight")]
+    pub withdrawals_activation_height: Option<u64>,
+    /// Address allowed to submit deposits to the inbox precompile.
+    #[serde(default, rename = "depositInboxRelayer")]
+    pub deposit_inbox_relayer: Option<Address>,
+    /// Block height at which the deposit inbox precompile activates.
+    #[serde(default, rename = "depositInboxActivationHeight")]
+    pub deposit_inbox_activation_height: Option<u64>,
+    /// Block height from which `IGasAllowance` contract fee payers are accepted.
+    #[serde(default, rename = "gasAllowanceActivationHeight")]
+    pub gas_allowance_activation_height: Option<u64>,
+    /// Block height from which contract fee payers are called back with `postOp`.
+    #[serde(default, rename = "gasAllowancePostOpActivationHeight")]
+    pub gas_allowance_post_op_activation_height: Option<u64>,
+    /// Native currency name, symbol and decimals.
+    #[serde(default, rename = "nativeCurrency")]
+    pub native_currency: Option<NativeCurrency>,
+    /// Pinned EVM version, either a single name or a schedule with activation heights.
+    #[serde(default, rename = "evmVersion")]
+    pub evm_version: Option<ChainspecEvmVersion>,
+    /// Blocks without activity after which an account may be archived.
+    #[serde(default, rename = "stateExpiryPeriod")]
+    pub state_expiry_period: Option<u64>,
+    /// Block height at which state expiry activates.
+    #[serde(default, rename = "stateExpiryActivationHeight")]
+    pub state_expiry_activation_height: Option<u64>,
+    /// Blocks a staking withdrawal unbonds before it can be claimed.
+    #[serde(default, rename = "stakingUnbondingPeriod")]
+    pub staking_unbonding_period: Option<u64>,
+    /// Stake an account needs to join the validator set.
+    #[serde(default, rename = "stakingMinimumStake")]
+    pub staking_minimum_stake: Option<U256>,
+    /// Block height at which the native staking precompile activates.
+    #[serde(default, rename = "stakingActivationHeight")]
+    pub staking_activation_height: Option<u64>,
+    /// Senders whose transactions are settled by the system fee policy.
+    #[serde(default, rename = "systemTxSenders")]
+    pub system_tx_senders: Option<Vec<Address>>,
+    /// How gas fees of system transactions are settled.
+    #[serde(default, rename = "systemTxFeePolicy")]
+    pub system_tx_fee_policy: Option<SystemTxFeePolicy>,
+    /// Account charged for system transaction fees under the `charged` policy.
+    #[serde(default, rename = "systemFeeAccount")]
+    pub system_fee_account: Option<Address>,
+    /// Block height at which the system fee policy activates.
+    #[serde(default, rename = "systemTxFeeActivationHeight")]
+    pub system_tx_fee_activation_height: Option<u64>,
+    /// Block height at which the system channel precompile activates.
+    #[serde(default, rename = "systemChannelActivationHeight")]
+    pub system_channel_activation_height: Option<u64>,
+    /// Block height at which the feature bitmap precompile activates.
+    #[serde(default, rename = "featuresPrecompileActivationHeight")]
+    pub features_precompile_activation_height: Option<u64>,
+    /// Block height at which the RIP-7212 P256 verification precompile activates.
+    #[serde(default, rename = "p256VerifyPrecompileActivationHeight")]
+    pub p256_verify_precompile_activation_height: Option<u64>,
+    /// Sponsor hash scheme existing sponsorships were signed with.
+    #[serde(default, rename = "sponsorHashLegacyScheme")]
+    pub sponsor_hash_legacy_scheme: Option<SponsorHashScheme>,
+    /// Block height from which sponsorships are accepted under both schemes.
+    #[serde(default, rename = "sponsorHashTransitionHeight")]
+    pub sponsor_hash_transition_height: Option<u64>,
+    /// Block height from which only the new sponsor hash scheme is accepted.
+    #[serde(default, rename = "sponsorHashActivationHeight")]
+    pub sponsor_hash_activation_height: Option<u64>,
+    /// Block height from which EIP-712 sponsorships are accepted.
+    #[serde(default, rename = "sponsorEip712ActivationHeight")]
+    pub sponsor_eip712_activation_height: Option<u64>,
+    /// Whether EvNode (0x76) transactions are accepted.
+    #[serde(default, rename = "enableEvNodeTx")]
+    pub enable_evnode_tx: Option<bool>,
+    /// Block height from which Evolve precompiles are warm in every transaction.
+    #[serde(default, rename = "precompileWarmupActivationHeight")]
+    pub precompile_warmup_activation_height: Option<u64>,
+    /// Additional system addresses warmed alongside the precompiles.
+    #[serde(default, rename = "precompileWarmupAddresses")]
+    pub precompile_warmup_addresses: Option<Vec<Address>>,
+    /// Block height from which gas dimensions are priced separately.
+    #[serde(default, rename = "multiDimGasActivationHeight")]
+    pub multi_dim_gas_activation_height: Option<u64>,
+    /// Gas charged per byte of state growth once dimensions are priced.
+    #[serde(default, rename = "stateGrowthGasPerByte")]
+    pub state_growth_gas_per_byte: Option<u64>,
+    /// Gas charged per DA byte once dimensions are priced.
+    #[serde(default, rename = "daGasPerByte")]
+    pub da_gas_per_byte: Option<u64>,
+    /// Minimum effective gas price, in wei.
+    #[serde(default, rename = "minGasPrice")]
+    pub min_gas_price: Option<u128>,
+    /// Senders exempt from the minimum gas price, in addition to the system senders.
+    #[serde(default, rename = "minGasPriceExemptSenders")]
+    pub min_gas_price_exempt_senders: Option<Vec<Address>>,
+    /// Call targets whose transactions are exempt from the minimum gas price.
+    #[serde(default, rename = "freeGasTargets")]
+    pub free_gas_targets: Option<Vec<Address>>,
+    /// Block height from which the minimum gas price is enforced.
+    #[serde(default, rename = "minGasPriceActivationHeight")]
+    pub min_gas_price_activation_height: Option<u64>,
+    /// Lowest gas limit accepted for incoming payloads.
+    #[serde(default, rename = "minBlockGasLimit")]
+    pub min_block_gas_limit: Option<u64>,
+    /// Highest gas limit accepted for incoming payloads.
+    #[serde(default, rename = "maxBlockGasLimit")]
+    pub max_block_gas_limit: Option<u64>,
+    /// Largest change of the gas limit from the parent's, in basis points of the parent's.
+    #[serde(default, rename = "maxGasLimitChangeBps")]
+    pub max_gas_limit_change_bps: Option<u16>,
+    /// Block height from which the gas limit bounds are enforced.
+    #[serde(default, rename = "gasLimitBoundsActivationHeight")]
+    pub gas_limit_bounds_activation_height: Option<u64>,
+    /// Largest share of the block gas limit one transaction may use, in basis points.
+    #[serde(default, rename = "maxTxGasBps")]
+    pub max_tx_gas_bps: Option<u16>,
+    /// Block height from which the per-transaction gas share is enforced.
+    #[serde(default, rename = "maxTxGasActivationHeight")]
+    pub max_tx_gas_activation_height: Option<u64>,
+    /// Largest number of calls one EvNode transaction may carry.
+    #[serde(default, rename = "maxBatchCalls")]
+    pub max_batch_calls: Option<u64>,
+    /// Block height from which the EvNode call limit is enforced.
+    #[serde(default, rename = "maxBatchCallsActivationHeight")]
+    pub max_batch_calls_activation_height: Option<u64>,
+    /// Largest number of entries in the access list of one EvNode transaction.
+    #[serde(default, rename = "maxAccessListEntries")]
+    pub max_access_list_entries: Option<u64>,
+    /// Largest number of storage keys in one access list entry of an EvNode transaction.
+    #[serde(default, rename = "maxAccessListStorageKeys")]
+    pub max_access_list_storage_keys: Option<u64>,
+    /// Gas charged per access list entry of an EvNode transaction on top of EIP-2930.
+    #[serde(default, rename = "accessListAddressGas")]
+    pub access_list_address_gas: Option<u64>,
+    /// Gas charged per access list storage key of an EvNode transaction on top of EIP-2930.
+    #[serde(default, rename = "accessListStorageKeyGas")]
+    pub access_list_storage_key_gas: Option<u64>,
+    /// Block height from which the EvNode access list limits and pricing apply.
+    #[serde(default, rename = "accessListActivationHeight")]
+    pub access_list_activation_height: Option<u64>,
+    /// Optional allowlist of fee payers permitted to sponsor transactions.
+    #[serde(default, rename = "sponsorAllowlist")]
+    pub sponsor_allowlist: Option<Vec<Address>>,
+    /// Block height at which sponsor allowlist enforcement activates.
+    #[serde(default, rename = "sponsorAllowlistActivationHeight")]
+    pub sponsor_allowlist_activation_height: Option<u64>,
+    /// Gas used per block below which tips are not required.
+    #[serde(default, rename = "gasTarget")]
+    pub gas_target: Option<u64>,
+    /// Number of recent blocks covered by the rolling block commitment.
+    #[serde(default, rename = "blockCommitmentWindow")]
+    pub block_commitment_window: Option<u64>,
+    /// Block height from which the block commitment is written into header extra data.
+    #[serde(default, rename = "blockCommitmentActivationHeight")]
+    pub block_commitment_activation_height: Option<u64>,
+    /// Deepest call nesting allowed, the top-level call being at depth 0.
+    #[serde(default, rename = "evmMaxCallDepth")]
+    pub evm_max_call_depth: Option<u64>,
+    /// Largest memory of the active call stack, in bytes.
+    #[serde(default, rename = "evmMemoryLimit")]
+    pub evm_memory_limit: Option<u64>,
+    /// Block height from which the EVM call depth and memory caps apply.
+    #[serde(default, rename = "evmLimitsActivationHeight")]
+    pub evm_limits_activation_height: Option<u64>,
+    /// Block height from which EvNode calls may carry their own gas limit.
+    #[serde(default, rename = "callGasLimitActivationHeight")]
+    pub call_gas_limit_activation_height: Option<u64>,
+    /// Block height from which EvNode batches may opt into best-effort execution.
+    #[serde(default, rename = "bestEffortActivationHeight")]
+    pub best_effort_activation_height: Option<u64>,
+    /// Block height from which EvNode transactions may be signed by scoped session keys.
+    #[serde(default, rename = "sessionKeyActivationHeight")]
+    pub session_key_activation_height: Option<u64>,
+    /// Block height from which EvNode transactions may carry a validity window.
+    #[serde(default, rename = "validityWindowActivationHeight")]
+    pub validity_window_activation_height: Option<u64>,
+    /// Block height from which EvNode transactions may use nonce lanes.
+    #[serde(default, rename = "nonceLaneActivationHeight")]
+    pub nonce_lane_activation_height: Option<u64>,
+    /// Block height from which EvNode executors may sign with a P256 key.
+    #[serde(default, rename = "p256SignatureActivationHeight")]
+    pub p256_signature_activation_height: Option<u64>,
+    /// Type byte raw EvNode transactions may use instead of `0x76`.
+    #[serde(
+        default,
+        rename = "evNodeTxTypeAlias",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub evnode_tx_type_alias: Option<u8>,
+    /// Block height from which the EvNode transaction type alias is accepted.
+    #[serde(default, rename = "evNodeTxTypeAliasActivationHeight")]
+    pub evnode_tx_type_alias_activation_height: Option<u64>,
+    /// Whether submitted EvNode transactions typed `0x76` are refused once the alias is active.
+    #[serde(default, rename = "evNodeTxTypeRefuseCanonical")]
+    pub evnode_tx_type_refuse_canonical: Option<bool>,
+    /// One-time state edits applied at the start of given blocks.
+    #[serde(default, rename = "statePatches")]
+    pub state_patches: Option<Vec<StatePatch>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum ChainspecEvmVersion {
+    /// A single version active from genesis.
+    Single(String),
+    /// Versions with the block heights they activate at.
+    Schedule(Vec<EvmVersionActivation>),
+}
+
+/// An EVM version pinned from `activation_height` onwards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmVersionActivation {
+    /// EVM version name, e.g. `cancun`.
+    pub version: String,
+    /// Block height at which the version activates.
+    #[serde(default)]
+    pub activation_height: u64,
+}
+
+/// Fee policy for transactions sent by the configured system senders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SystemTxFeePolicy {
+    /// No fees are charged.
+    Waived,
+    /// Fees are charged to `systemFeeAccount` instead of the sender.
+    Charged,
+    /// Fees are charged but not paid out to the beneficiary or the base-fee sink.
+    Burned,
 }
 
 /// Configuration for the Evolve payload builder
@@ -41,6 +311,9 @@ pub struct EvolvePayloadBuilderConfig {
     /// Optional activation height for base-fee redirect; defaults to 0 when sink set.
     #[serde(default)]
     pub base_fee_redirect_activation_height: Option<u64>,
+    /// Optional share of the base fee, in basis points, burned rather than redirected.
+    #[serde(default)]
+    pub base_fee_burn_bps: Option<u16>,
     /// Optional mint precompile admin address sourced from the chainspec.
     #[serde(default)]
     pub mint_admin: Option<Address>,
@@ -59,6 +332,232 @@ pub struct EvolvePayloadBuilderConfig {
     /// Block height at which deploy allowlist enforcement activates.
     #[serde(default)]
     pub deploy_allowlist_activation_height: Option<u64>,
+    /// Source of block `prev_randao` values.
+    #[serde(default)]
+    pub prev_randao_policy: PrevRandaoPolicy,
+    /// Block height at which the `prev_randao` policy activates.
+    #[serde(default)]
+    pub prev_randao_policy_activation_height: Option<u64>,
+    /// Block height from which Engine API withdrawals are credited. Disabled when unset.
+    #[serde(default)]
+    pub withdrawals_activation_height: Option<u64>,
+    /// Optional relayer address for the deposit inbox precompile.
+    #[serde(default)]
+    pub deposit_inbox_relayer: Option<Address>,
+    /// Optional activation height for the deposit inbox; defaults to 0 when relayer set.
+    #[serde(default)]
+    pub deposit_inbox_activation_height: Option<u64>,
+    /// Block height from which contract fee payers are accepted. Disabled when unset.
+    #[serde(default)]
+    pub gas_allowance_activation_height: Option<u64>,
+    /// Block height from which contract fee payers are called back with `postOp` after
+    /// execution. Disabled when unset.
+    #[serde(default)]
+    pub gas_allowance_post_op_activation_height: Option<u64>,
+    /// Native currency metadata. Defaults to Ether with 18 decimals when unset.
+    #[serde(default)]
+    pub native_currency: Option<NativeCurrency>,
+    /// Pinned EVM versions. Empty follows the chainspec's hardfork schedule.
+    #[serde(default)]
+    pub evm_version: Vec<EvmVersionActivation>,
+    /// Blocks without activity after which an account may be archived. Disabled when unset.
+    #[serde(default)]
+    pub state_expiry_period: Option<u64>,
+    /// Optional activation height for state expiry; defaults to 0 when the period is set.
+    #[serde(default)]
+    pub state_expiry_activation_height: Option<u64>,
+    /// Blocks a staking withdrawal unbonds before it can be claimed. Staking is disabled
+    /// when unset.
+    #[serde(default)]
+    pub staking_unbonding_period: Option<u64>,
+    /// Stake an account needs to join the validator set; defaults to 0.
+    #[serde(default)]
+    pub staking_minimum_stake: Option<U256>,
+    /// Optional activation height for native staking; defaults to 0 when the period is set.
+    #[serde(default)]
+    pub staking_activation_height: Option<u64>,
+    /// Senders whose transactions are settled by the system fee policy.
+    #[serde(default)]
+    pub system_tx_senders: Vec<Address>,
+    /// Fee policy for system transactions. Regular fee rules apply when unset.
+    #[serde(default)]
+    pub system_tx_fee_policy: Option<SystemTxFeePolicy>,
+    /// Account charged for system transaction fees under the `charged` policy.
+    #[serde(default)]
+    pub system_fee_account: Option<Address>,
+    /// Optional activation height for the system fee policy; defaults to 0.
+    #[serde(default)]
+    pub system_tx_fee_activation_height: Option<u64>,
+    /// Optional activation height for the system channel precompile. Disabled when unset.
+    #[serde(default)]
+    pub system_channel_activation_height: Option<u64>,
+    /// Optional activation height for the read-only precompile reporting active Evolve
+    /// features to contracts. Disabled when unset.
+    #[serde(default)]
+    pub features_precompile_activation_height: Option<u64>,
+    /// Optional activation height for the RIP-7212 P256 verification precompile at `0x100`.
+    /// Disabled when unset.
+    #[serde(default)]
+    pub p256_verify_precompile_activation_height: Option<u64>,
+    /// Sponsor hash scheme being migrated away from. The executor-bound scheme applies
+    /// when unset.
+    #[serde(default)]
+    pub sponsor_hash_legacy_scheme: Option<SponsorHashScheme>,
+    /// Height from which sponsorships are accepted under both schemes.
+    #[serde(default)]
+    pub sponsor_hash_transition_height: Option<u64>,
+    /// Height from which only the new sponsor hash scheme is accepted.
+    #[serde(default)]
+    pub sponsor_hash_activation_height: Option<u64>,
+    /// Height from which sponsors may sign EIP-712 typed data instead of the raw sponsor
+    /// hash. Disabled when unset.
+    #[serde(default)]
+    pub sponsor_eip712_activation_height: Option<u64>,
+    /// Whether EvNode (0x76) transactions are accepted; enabled when unset.
+    #[serde(default)]
+    pub enable_evnode_tx: Option<bool>,
+    /// Optional activation height from which Evolve precompiles are warm in every
+    /// transaction. Disabled when unset.
+    #[serde(default)]
+    pub precompile_warmup_activation_height: Option<u64>,
+    /// Additional system addresses warmed alongside the precompiles.
+    #[serde(default)]
+    pub precompile_warmup_addresses: Vec<Address>,
+    /// Optional activation height from which compute, state growth and DA gas are priced
+    /// separately. Disabled when unset.
+    #[serde(default)]
+    pub multi_dim_gas_activation_height: Option<u64>,
+    /// Gas per byte of state growth once dimensions are priced (defaults to 0).
+    #[serde(default)]
+    pub state_growth_gas_per_byte: Option<u64>,
+    /// Gas per DA byte once dimensions are priced (defaults to 0).
+    #[serde(default)]
+    pub da_gas_per_byte: Option<u64>,
+    /// Optional minimum effective gas price enforced by the txpool and payload builder.
+    #[serde(default)]
+    pub min_gas_price: Option<u128>,
+    /// Senders exempt from the minimum gas price, in addition to the system senders.
+    #[serde(default)]
+    pub min_gas_price_exempt_senders: Vec<Address>,
+    /// Call targets whose transactions are exempt from the minimum gas price.
+    #[serde(default)]
+    pub free_gas_targets: Vec<Address>,
+    /// Activation height for the minimum gas price (defaults to 0).
+    #[serde(default)]
+    pub min_gas_price_activation_height: Option<u64>,
+    /// Optional lowest gas limit accepted for incoming payloads.
+    #[serde(default)]
+    pub min_block_gas_limit: Option<u64>,
+    /// Optional highest gas limit accepted for incoming payloads.
+    #[serde(default)]
+    pub max_block_gas_limit: Option<u64>,
+    /// Optional per-block gas limit change limit, in basis points of the parent's gas limit.
+    #[serde(default)]
+    pub max_gas_limit_change_bps: Option<u16>,
+    /// Activation height for the gas limit bounds (defaults to 0).
+    #[serde(default)]
+    pub gas_limit_bounds_activation_height: Option<u64>,
+    /// Optional largest share of the block gas limit one transaction may use, in basis
+    /// points.
+    #[serde(default)]
+    pub max_tx_gas_bps: Option<u16>,
+    /// Activation height for the per-transaction gas share (defaults to 0).
+    #[serde(default)]
+    pub max_tx_gas_activation_height: Option<u64>,
+    /// Optional largest number of calls one EvNode transaction may carry.
+    #[serde(default)]
+    pub max_batch_calls: Option<u64>,
+    /// Activation height for the EvNode call limit (defaults to 0).
+    #[serde(default)]
+    pub max_batch_calls_activation_height: Option<u64>,
+    /// Optional largest number of entries in the access list of one EvNode transaction.
+    #[serde(default)]
+    pub max_access_list_entries: Option<u64>,
+    /// Optional largest number of storage keys in one access list entry of an EvNode
+    /// transaction.
+    #[serde(default)]
+    pub max_access_list_storage_keys: Option<u64>,
+    /// Extra gas per EvNode access list entry (defaults to 0).
+    #[serde(default)]
+    pub access_list_address_gas: Option<u64>,
+    /// Extra gas per EvNode access list storage key (defaults to 0).
+    #[serde(default)]
+    pub access_list_storage_key_gas: Option<u64>,
+    /// Activation height for the EvNode access list limits and pricing (defaults to 0).
+    #[serde(default)]
+    pub access_list_activation_height: Option<u64>,
+    /// Allowlist of fee payers permitted to sponsor transactions.
+    #[serde(default)]
+    pub sponsor_allowlist: Vec<Address>,
+    /// Block height at which sponsor allowlist enforcement activates.
+    #[serde(default)]
+    pub sponsor_allowlist_activation_height: Option<u64>,
+    /// Optional gas used per block below which the txpool waives its minimum priority fee
+    /// and fee suggestions drop the tip to zero.
+    #[serde(default)]
+    pub gas_target: Option<u64>,
+    /// Optional number of recent blocks covered by the rolling block commitment (defaults to
+    /// [`DEFAULT_BLOCK_COMMITMENT_WINDOW`]).
+    #[serde(default)]
+    pub block_commitment_window: Option<u64>,
+    /// Optional activation height from which built blocks carry the block commitment in their
+    /// extra data. Disabled when unset.
+    #[serde(default)]
+    pub block_commitment_activation_height: Option<u64>,
+    /// Optional call depth cap, below the protocol's 1024. Uncapped when unset.
+    #[serde(default)]
+    pub evm_max_call_depth: Option<u64>,
+    /// Optional cap on the memory of the active call stack, in bytes. Uncapped when unset.
+    #[serde(default)]
+    pub evm_memory_limit: Option<u64>,
+    /// Activation height for the EVM call depth and memory caps (defaults to 0).
+    #[serde(default)]
+    pub evm_limits_activation_height: Option<u64>,
+    /// Block height from which EvNode calls may carry their own gas limit. Disabled when
+    /// unset.
+    #[serde(default)]
+    pub call_gas_limit_activation_height: Option<u64>,
+    /// Block height from which EvNode batches may be best-effort, recording failed calls
+    /// instead of reverting. Disabled when unset.
+    #[serde(default)]
+    pub best_effort_activation_height: Option<u64>,
+    /// Block height from which EvNode transactions may be signed by a session key acting for
+    /// a primary account within a signed scope. Disabled when unset.
+    #[serde(default)]
+    pub session_key_activation_height: Option<u64>,
+    /// Block height from which EvNode transactions may bound the block timestamps they are
+    /// valid at. Disabled when unset.
+    #[serde(default)]
+    pub validity_window_activation_height: Option<u64>,
+    /// Block height from which EvNode transactions may be ordered by a nonce lane of their
+    /// executor instead of its account nonce, and the nonce manager precompile is installed.
+    /// Disabled when unset.
+    #[serde(default)]
+    pub nonce_lane_activation_height: Option<u64>,
+    /// Block height from which EvNode executors may sign with a P256 key or a WebAuthn
+    /// authenticator instead of secp256k1. Disabled when unset.
+    #[serde(default)]
+    pub p256_signature_activation_height: Option<u64>,
+    /// Optional type byte raw EvNode transactions may use instead of `0x76`, for chains where
+    /// `0x76` belongs to another transaction type.
+    #[serde(default)]
+    pub evnode_tx_type_alias: Option<u8>,
+    /// Activation height for the EvNode transaction type alias (defaults to 0).
+    #[serde(default)]
+    pub evnode_tx_type_alias_activation_height: Option<u64>,
+    /// Whether submitted EvNode transactions typed `0x76` are refused once the alias is
+    /// active (defaults to false).
+    #[serde(default)]
+    pub evnode_tx_type_refuse_canonical: Option<bool>,
+    /// One-time state edits applied by the executor at the start of their blocks.
+    #[serde(default)]
+    pub state_patches: Vec<StatePatch>,
+    /// Settings of this node from its CLI flags; never read from the chainspec.
+    #[serde(skip)]
+    pub node: NodeSettings,
+    /// Runtime state shared by this node's components; never read from the chainspec.
+    #[serde(skip)]
+    pub handles: NodeHandles,
 }
 
 impl EvolvePayloadBuilderConfig {
@@ -68,14 +567,94 @@ impl EvolvePayloadBuilderConfig {
             base_fee_sink: None,
             mint_admin: None,
             base_fee_redirect_activation_height: None,
+            base_fee_burn_bps: None,
             mint_precompile_activation_height: None,
             contract_size_limit: None,
             contract_size_limit_activation_height: None,
             deploy_allowlist: Vec::new(),
             deploy_allowlist_activation_height: None,
+            prev_randao_policy: PrevRandaoPolicy::Attributes,
+            prev_randao_policy_activation_height: None,
+            withdrawals_activation_height: None,
+            deposit_inbox_relayer: None,
+            deposit_inbox_activation_height: None,
+            gas_allowance_activation_height: None,
+            gas_allowance_post_op_activation_height: None,
+            native_currency: None,
+            evm_version: Vec::new(),
+            state_expiry_period: None,
+            state_expiry_activation_height: None,
+            staking_unbonding_period: None,
+            staking_minimum_stake: None,
+            staking_activation_height: None,
+            system_tx_senders: Vec::new(),
+            system_tx_fee_policy: None,
+            system_fee_account: None,
+            system_tx_fee_activation_height: None,
+            system_channel_activation_height: None,
+            features_precompile_activation_height: None,
+            p256_verify_precompile_activation_height: None,
+            sponsor_hash_legacy_scheme: None,
+            sponsor_hash_transition_height: None,
+            sponsor_hash_activation_height: None,
+            sponsor_eip712_activation_height: None,
+            enable_evnode_tx: None,
+            precompile_warmup_activation_height: None,
+            precompile_warmup_addresses: Vec::new(),
+            multi_dim_gas_activation_height: None,
+            state_growth_gas_per_byte: None,
+            da_gas_per_byte: None,
+            min_gas_price: None,
+            min_gas_price_exempt_senders: Vec::new(),
+            free_gas_targets: Vec::new(),
+            min_gas_price_activation_height: None,
+            min_block_gas_limit: None,
+            max_block_gas_limit: None,
+            max_gas_limit_change_bps: None,
+            gas_limit_bounds_activation_height: None,
+            max_tx_gas_bps: None,
+            max_tx_gas_activation_height: None,
+            max_batch_calls: None,
+            max_batch_calls_activation_height: None,
+            max_access_list_entries: None,
+            max_access_list_storage_keys: None,
+            access_list_address_gas: None,
+            access_list_storage_key_gas: None,
+            access_list_activation_height: None,
+            sponsor_allowlist: Vec::new(),
+            sponsor_allowlist_activation_height: None,
+            gas_target: None,
+            block_commitment_window: None,
+            block_commitment_activation_height: None,
+            evm_max_call_depth: None,
+            evm_memory_limit: None,
+            evm_limits_activation_height: None,
+            call_gas_limit_activation_height: None,
+            best_effort_activation_height: None,
+            session_key_activation_height: None,
+            validity_window_activation_height: None,
+            nonce_lane_activation_height: None,
+            p256_signature_activation_height: None,
+            evnode_tx_type_alias: None,
+            evnode_tx_type_alias_activation_height: None,
+            evnode_tx_type_refuse_canonical: None,
+            state_patches: Vec::new(),
+            node: NodeSettings::new(),
+            handles: NodeHandles::default(),
         }
     }
 
+    /// Returns the paths of the `evolve` extras this configuration does not read.
+    pub(crate) fn unread_extras(
+        extras: serde_json::Value,
+    ) -> Result<BTreeSet<String>, serde_json::Error> {
+        let mut unread = BTreeSet::new();
+        serde_ignored::deserialize::<_, _, ChainspecEvolveConfig>(extras, |path| {
+            unread.insert(path.to_string());
+        })?;
+        Ok(unread)
+    }
+
     /// Builds the configuration from the provided chain spec extras.
     pub fn from_chain_spec(spec: &ChainSpec) -> Result<Self, ConfigError> {
         let mut config = Self::default();
@@ -88,6 +667,7 @@ impl EvolvePayloadBuilderConfig {
             let extras = extra.map_err(ConfigError::InvalidExtras)?;
             config.base_fee_sink = extras.base_fee_sink;
             config.base_fee_redirect_activation_height = extras.base_fee_redirect_activation_height;
+            config.base_fee_burn_bps = extras.base_fee_burn_bps;
             config.mint_admin =
                 extras
                     .mint_admin
@@ -118,6 +698,96 @@ impl EvolvePayloadBuilderConfig {
                     config.deploy_allowlist_activation_height = Some(0);
                 }
             }
+
+            config.prev_randao_policy = extras.prev_randao_policy;
+            config.prev_randao_policy_activation_height =
+                extras.prev_randao_policy_activation_height;
+            config.withdrawals_activation_height = extras.withdrawals_activation_height;
+            config.deposit_inbox_relayer =
+                extras.deposit_inbox_relayer.filter(|addr| !addr.is_zero());
+            config.deposit_inbox_activation_height = extras.deposit_inbox_activation_height;
+            config.gas_allowance_activation_height = extras.gas_allowance_activation_height;
+            config.gas_allowance_post_op_activation_height =
+                extras.gas_allowance_post_op_activation_height;
+            config.native_currency = extras.native_currency;
+            config.evm_version = match extras.evm_version {
+                Some(ChainspecEvmVersion::Single(version)) => vec![EvmVersionActivation {
+                    version,
+                    activation_height: 0,
+                }],
+                Some(ChainspecEvmVersion::Schedule(schedule)) => schedule,
+                None => Vec::new(),
+            };
+            config.state_expiry_period = extras.state_expiry_period;
+            config.state_expiry_activation_height = extras.state_expiry_activation_height;
+            config.staking_unbonding_period = extras.staking_unbonding_period;
+            config.staking_minimum_stake = extras.staking_minimum_stake;
+            config.staking_activation_height = extras.staking_activation_height;
+            config.system_tx_senders = extras.system_tx_senders.unwrap_or_default();
+            config.system_tx_fee_policy = extras.system_tx_fee_policy;
+            config.system_fee_account = extras.system_fee_account;
+            config.system_tx_fee_activation_height = extras.system_tx_fee_activation_height;
+            config.system_channel_activation_height = extras.system_channel_activation_height;
+            config.features_precompile_activation_height =
+                extras.features_precompile_activation_height;
+            config.p256_verify_precompile_activation_height =
+                extras.p256_verify_precompile_activation_height;
+            config.sponsor_hash_legacy_scheme = extras.sponsor_hash_legacy_scheme;
+            config.sponsor_hash_transition_height = extras.sponsor_hash_transition_height;
+            config.sponsor_hash_activation_height = extras.sponsor_hash_activation_height;
+            config.sponsor_eip712_activation_height = extras.sponsor_eip712_activation_height;
+            config.enable_evnode_tx = extras.enable_evnode_tx;
+            config.precompile_warmup_activation_height = extras.precompile_warmup_activation_height;
+            config.precompile_warmup_addresses =
+                extras.precompile_warmup_addresses.unwrap_or_default();
+            config.multi_dim_gas_activation_height = extras.multi_dim_gas_activation_height;
+            config.state_growth_gas_per_byte = extras.state_growth_gas_per_byte;
+            config.da_gas_per_byte = extras.da_gas_per_byte;
+            config.min_gas_price = extras.min_gas_price;
+            config.min_gas_price_exempt_senders =
+                extras.min_gas_price_exempt_senders.unwrap_or_default();
+            config.free_gas_targets = extras.free_gas_targets.unwrap_or_default();
+            config.min_gas_price_activation_height = extras.min_gas_price_activation_height;
+            config.min_block_gas_limit = extras.min_block_gas_limit;
+            config.max_block_gas_limit = extras.max_block_gas_limit;
+            config.max_gas_limit_change_bps = extras.max_gas_limit_change_bps;
+            config.gas_limit_bounds_activation_height = extras.gas_limit_bounds_activation_height;
+            config.max_tx_gas_bps = extras.max_tx_gas_bps;
+            config.max_tx_gas_activation_height = extras.max_tx_gas_activation_height;
+            config.max_batch_calls = extras.max_batch_calls;
+            config.max_batch_calls_activation_height = extras.max_batch_calls_activation_height;
+            config.max_access_list_entries = extras.max_access_list_entries;
+            config.max_access_list_storage_keys = extras.max_access_list_storage_keys;
+            config.access_list_address_gas = extras.access_list_address_gas;
+            config.access_list_storage_key_gas = extras.access_list_storage_key_gas;
+            config.access_list_activation_height = extras.access_list_activation_height;
+            if let Some(allowlist) = extras.sponsor_allowlist {
+                config.sponsor_allowlist = allowlist;
+                config.sponsor_allowlist_activation_height =
+                    extras.sponsor_allowlist_activation_height;
+                if !config.sponsor_allowlist.is_empty()
+                    && config.sponsor_allowlist_activation_height.is_none()
+                {
+                    config.sponsor_allowlist_activation_height = Some(0);
+                }
+            }
+            config.gas_target = extras.gas_target;
+            config.block_commitment_window = extras.block_commitment_window;
+            config.block_commitment_activation_height = extras.block_commitment_activation_height;
+            config.evm_max_call_depth = extras.evm_max_call_depth;
+            config.evm_memory_limit = extras.evm_memory_limit;
+            config.evm_limits_activation_height = extras.evm_limits_activation_height;
+            config.call_gas_limit_activation_height = extras.call_gas_limit_activation_height;
+            config.best_effort_activation_height = extras.best_effort_activation_height;
+            config.session_key_activation_height = extras.session_key_activation_height;
+            config.validity_window_activation_height = extras.validity_window_activation_height;
+            config.nonce_lane_activation_height = extras.nonce_lane_activation_height;
+            config.p256_signature_activation_height = extras.p256_signature_activation_height;
+            config.evnode_tx_type_alias = extras.evnode_tx_type_alias;
+            config.evnode_tx_type_alias_activation_height =
+                extras.evnode_tx_type_alias_activation_height;
+            config.evnode_tx_type_refuse_canonical = extras.evnode_tx_type_refuse_canonical;
+            config.state_patches = extras.state_patches.unwrap_or_default();
         }
 
         Ok(config)
@@ -156,461 +826,2048 @@ impl EvolvePayloadBuilderConfig {
         }
     }
 
+    /// Returns the native currency metadata, defaulting to Ether with 18 decimals.
+    pub fn native_currency(&self) -> NativeCurrency {
+        self.native_currency.clone().unwrap_or_default()
+    }
+
     /// Validates the configuration
     pub fn validate(&self) -> Result<(), ConfigError> {
-        self.validate_deploy_allowlist()
+        self.validate_base_fee_burn()?;
+        self.validate_deploy_allowlist()?;
+        self.validate_native_currency()?;
+        self.validate_evm_version()?;
+        self.validate_state_expiry()?;
+        self.validate_staking()?;
+        self.validate_system_tx_fee()?;
+        self.validate_system_channel()?;
+        self.validate_min_gas_price()?;
+        self.validate_gas_limit_bounds()?;
+        self.validate_max_tx_gas()?;
+        self.validate_max_batch_calls()?;
+        self.validate_access_list()?;
+        self.validate_sponsor_allowlist()?;
+        self.validate_evnode_tx_type_alias()?;
+        self.validate_gas_target()?;
+        self.validate_block_commitment()?;
+        self.validate_evm_limits()?;
+        self.validate_state_patches()?;
+        self.validate_sponsor_hash_migration()
     }
 
-    fn validate_deploy_allowlist(&self) -> Result<(), ConfigError> {
-        let allowlist_len = self.deploy_allowlist.len();
-        if allowlist_len > MAX_DEPLOY_ALLOWLIST_LEN {
-            return Err(ConfigError::InvalidDeployAllowlist(format!(
-                "deployAllowlist has {allowlist_len} entries (max {MAX_DEPLOY_ALLOWLIST_LEN})"
-            )));
-        }
+    /// Returns the legacy sponsor hash scheme with the transition and activation heights of
+    /// its migration, if configured.
+    pub fn sponsor_hash_migration(&self) -> Option<(SponsorHashScheme, u64, u64)> {
+        Some((
+            self.sponsor_hash_legacy_scheme?,
+            self.sponsor_hash_transition_height?,
+            self.sponsor_hash_activation_height?,
+        ))
+    }
 
-        let mut seen = HashSet::with_capacity(allowlist_len);
-        for addr in &self.deploy_allowlist {
-            if addr.is_zero() {
-                return Err(ConfigError::InvalidDeployAllowlist(
-                    "deployAllowlist contains zero address".to_string(),
-                ));
-            }
-            if !seen.insert(*addr) {
-                return Err(ConfigError::InvalidDeployAllowlist(
-                    "deployAllowlist contains duplicate entries".to_string(),
-                ));
+    fn validate_sponsor_hash_migration(&self) -> Result<(), ConfigError> {
+        match (
+            self.sponsor_hash_legacy_scheme,
+            self.sponsor_hash_transition_height,
+            self.sponsor_hash_activation_height,
+        ) {
+            (None, None, None) => Ok(()),
+            (Some(SponsorHashScheme::Eip712), _, _) => Err(ConfigError::InvalidSponsorHashMigration(
+                "sponsorHashLegacyScheme must be executor or feePayer; EIP-712 sponsorships are \
+                 enabled with sponsorEip712ActivationHeight"
+                    .to_string(),
+            )),
+            (Some(_), Some(transition), Some(activation)) if transition > activation => {
+                Err(ConfigError::InvalidSponsorHashMigration(
+                    "sponsorHashTransitionHeight must not exceed sponsorHashActivationHeight"
+                        .to_string(),
+                ))
             }
+            (Some(_), Some(_), Some(_)) => Ok(()),
+            _ => Err(ConfigError::InvalidSponsorHashMigration(
+                "sponsorHashLegacyScheme, sponsorHashTransitionHeight and \
+                 sponsorHashActivationHeight must be set together"
+                    .to_string(),
+            )),
         }
+    }
 
-        Ok(())
+    /// Returns the system transaction senders, fee mode and activation height (defaulting
+    /// to 0), if a policy is configured.
+    pub fn system_fee_settings(&self) -> Option<(Vec<Address>, SystemFeeMode, u64)> {
+        let mode = match self.system_tx_fee_policy? {
+            SystemTxFeePolicy::Waived => SystemFeeMode::Waived,
+            SystemTxFeePolicy::Charged => SystemFeeMode::Charged(self.system_fee_account?),
+            SystemTxFeePolicy::Burned => SystemFeeMode::Burned,
+        };
+        let activation = self.system_tx_fee_activation_height.unwrap_or(0);
+        Some((self.system_tx_senders.clone(), mode, activation))
     }
 
-    /// Returns the configured base-fee redirect sink and activation height (defaulting to 0).
-    pub fn base_fee_redirect_settings(&self) -> Option<(Address, u64)> {
-        self.base_fee_sink.map(|sink| {
-            let activation = self.base_fee_redirect_activation_height.unwrap_or(0);
-            (sink, activation)
-        })
+    fn validate_system_tx_fee(&self) -> Result<(), ConfigError> {
+        let Some(policy) = self.system_tx_fee_policy else {
+            if self.system_tx_senders.is_empty() && self.system_fee_account.is_none() {
+                return Ok(());
+            }
+            return Err(ConfigError::InvalidSystemTxFee(
+                "systemTxSenders and systemFeeAccount require systemTxFeePolicy".to_string(),
+            ));
+        };
+        if self.system_tx_senders.is_empty() {
+            return Err(ConfigError::InvalidSystemTxFee(
+                "systemTxFeePolicy requires at least one systemTxSenders entry".to_string(),
+            ));
+        }
+        if self.system_tx_senders.iter().any(Address::is_zero) {
+            return Err(ConfigError::InvalidSystemTxFee(
+                "systemTxSenders contains zero address".to_string(),
+            ));
+        }
+        match (policy, self.system_fee_account) {
+            (SystemTxFeePolicy::Charged, None) => Err(ConfigError::InvalidSystemTxFee(
+                "the charged policy requires systemFeeAccount".to_string(),
+            )),
+            (SystemTxFeePolicy::Waived | SystemTxFeePolicy::Burned, Some(_)) => {
+                Err(ConfigError::InvalidSystemTxFee(
+                    "systemFeeAccount is only used by the charged policy".to_string(),
+                ))
+            }
+            _ => Ok(()),
+        }
     }
 
-    /// Returns the mint precompile admin and activation height (defaulting to 0).
-    pub fn mint_precompile_settings(&self) -> Option<(Address, u64)> {
-        self.mint_admin.map(|admin| {
-            let activation = self.mint_precompile_activation_height.unwrap_or(0);
-            (admin, activation)
-        })
+    /// Returns the minimum gas price, exempt senders (including the system senders), free-gas
+    /// targets and activation height (defaulting to 0), if a minimum is configured.
+    pub fn min_gas_price_settings(&self) -> Option<(u128, Vec<Address>, Vec<Address>, u64)> {
+        let min_gas_price = self.min_gas_price?;
+        let exempt_senders = self
+            .min_gas_price_exempt_senders
+            .iter()
+            .chain(&self.system_tx_senders)
+            .copied()
+            .collect();
+        Some((
+            min_gas_price,
+            exempt_senders,
+            self.free_gas_targets.clone(),
+            self.min_gas_price_activation_height.unwrap_or(0),
+        ))
     }
 
-    /// Returns the sink if the redirect is active for the provided block number.
-    pub fn base_fee_sink_for_block(&self, block_number: u64) -> Option<Address> {
-        self.base_fee_redirect_settings()
-            .and_then(|(sink, activation)| (block_number >= activation).then_some(sink))
+    /// Returns the minimum and maximum payload gas limit, the permitted per-block change in
+    /// basis points and the activation height (defaulting to 0), if any bound is configured.
+    pub fn gas_limit_bounds_settings(&self) -> Option<(u64, u64, Option<u16>, u64)> {
+        if self.min_block_gas_limit.is_none()
+            && self.max_block_gas_limit.is_none()
+            && self.max_gas_limit_change_bps.is_none()
+        {
+            return None;
+        }
+        Some((
+            self.min_block_gas_limit.unwrap_or(0),
+            self.max_block_gas_limit.unwrap_or(u64::MAX),
+            self.max_gas_limit_change_bps,
+            self.gas_limit_bounds_activation_height.unwrap_or(0),
+        ))
     }
-}
 
-/// Errors that can occur during configuration validation
-#[derive(Debug, thiserror::Error)]
-pub enum ConfigError {
-    /// Invalid configuration provided
-    #[error("Invalid config")]
-    InvalidConfig,
-    /// Chainspec extras contained invalid values
-    #[error("Invalid evolve extras in chainspec: {0}")]
-    InvalidExtras(#[from] serde_json::Error),
-    /// Deploy allowlist configuration invalid
-    #[error("Invalid deploy allowlist configuration: {0}")]
-    InvalidDeployAllowlist(String),
-}
+    /// Returns the senders allowed to advance system channel nonces and the activation height
+    /// of the system channel precompile, if configured.
+    pub fn system_channel_settings(&self) -> Option<(Vec<Address>, u64)> {
+        self.system_channel_activation_height
+            .map(|activation| (self.system_tx_senders.clone(), activation))
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use alloy_genesis::Genesis;
-    use alloy_primitives::{address, Address};
-    use reth_chainspec::ChainSpecBuilder;
-    use serde_json::json;
+    fn validate_system_channel(&self) -> Result<(), ConfigError> {
+        if self.system_channel_activation_height.is_some() && self.system_tx_senders.is_empty() {
+            return Err(ConfigError::InvalidSystemChannel(
+                "systemChannelActivationHeight requires at least one systemTxSenders entry"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
 
-    fn create_test_chainspec_with_extras(extras: Option<serde_json::Value>) -> ChainSpec {
-        let mut builder = ChainSpecBuilder::mainnet();
+    fn validate_base_fee_burn(&self) -> Result<(), ConfigError> {
+        let Some(burn_bps) = self.base_fee_burn_bps else {
+            return Ok(());
+        };
+        if burn_bps > MAX_BURN_BPS {
+            return Err(ConfigError::InvalidBaseFeeBurn(format!(
+                "baseFeeBurnBps {burn_bps} exceeds {MAX_BURN_BPS}"
+            )));
+        }
+        if self.base_fee_sink.is_none() {
+            return Err(ConfigError::InvalidBaseFeeBurn(
+                "baseFeeBurnBps requires baseFeeSink".to_string(),
+            ));
+        }
+        Ok(())
+    }
 
-        if let Some(extras_value) = extras {
-            // Create a genesis with evolve extras
-            let mut genesis = Genesis::default();
-            genesis
-                .config
-                .extra_fields
-                .insert("evolve".to_string(), extras_value);
-            builder = builder.genesis(genesis);
+    fn validate_gas_limit_bounds(&self) -> Result<(), ConfigError> {
+        let Some((min, max, max_change_bps, _)) = self.gas_limit_bounds_settings() else {
+            if self.gas_limit_bounds_activation_height.is_some() {
+                return Err(ConfigError::InvalidGasLimitBounds(
+                    "gasLimitBoundsActivationHeight requires minBlockGasLimit, maxBlockGasLimit \
+                     or maxGasLimitChangeBps"
+                        .to_string(),
+                ));
+            }
+            return Ok(());
+        };
+        if min > max {
+            return Err(ConfigError::InvalidGasLimitBounds(format!(
+                "minBlockGasLimit {min} exceeds maxBlockGasLimit {max}"
+            )));
+        }
+        if let Some(bps) = max_change_bps {
+            if bps == 0 || bps > 10_000 {
+                return Err(ConfigError::InvalidGasLimitBounds(format!(
+                    "maxGasLimitChangeBps {bps} must be between 1 and 10000"
+                )));
+            }
         }
+        Ok(())
+    }
 
-        builder.build()
+    /// Returns the per-transaction share of the block gas limit in basis points and its
+    /// activation height (defaulting to 0), if configured.
+    pub fn max_tx_gas_settings(&self) -> Option<(u16, u64)> {
+        self.max_tx_gas_bps
+            .map(|bps| (bps, self.max_tx_gas_activation_height.unwrap_or(0)))
     }
 
-    #[test]
-    fn test_basefee_sink_some_address() {
-        // Test case when base_fee_sink is Some(address)
-        let test_address = address!("0000000000000000000000000000000000000001");
-        let extras = json!({
-            "baseFeeSink": test_address
-        });
+    fn validate_max_tx_gas(&self) -> Result<(), ConfigError> {
+        match self.max_tx_gas_bps {
+            None if self.max_tx_gas_activation_height.is_some() => {
+                Err(ConfigError::InvalidMaxTxGas(
+                    "maxTxGasActivationHeight requires maxTxGasBps".to_string(),
+                ))
+            }
+            Some(bps) if bps == 0 || bps > MAX_TX_GAS_BPS => Err(ConfigError::InvalidMaxTxGas(
+                format!("maxTxGasBps {bps} must be between 1 and {MAX_TX_GAS_BPS}"),
+            )),
+            _ => Ok(()),
+        }
+    }
 
-        let chainspec = create_test_chainspec_with_extras(Some(extras));
-        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+    /// Returns the limit on calls per EvNode transaction, if configured.
+    pub fn max_batch_calls(&self) -> Option<MaxBatchCalls> {
+        self.max_batch_calls.map(|max_calls| {
+            MaxBatchCalls::new(
+                max_calls,
+                self.max_batch_calls_activation_height.unwrap_or(0),
+            )
+        })
+    }
 
-        assert_eq!(config.base_fee_sink, Some(test_address));
-        assert_eq!(config.mint_admin, None);
-        assert_eq!(config.base_fee_redirect_activation_height, Some(0));
-        assert_eq!(config.mint_precompile_activation_height, None);
+    fn validate_max_batch_calls(&self) -> Result<(), ConfigError> {
+        match self.max_batch_calls {
+            None if self.max_batch_calls_activation_height.is_some() => {
+                Err(ConfigError::InvalidMaxBatchCalls(
+                    "maxBatchCallsActivationHeight requires maxBatchCalls".to_string(),
+                ))
+            }
+            Some(0) => Err(ConfigError::InvalidMaxBatchCalls(
+                "maxBatchCalls must be at least 1".to_string(),
+            )),
+            _ => Ok(()),
+        }
     }
 
-    #[test]
-    fn test_mint_admin_some_address() {
-        let mint_admin = address!("00000000000000000000000000000000000000aa");
-        let extras = json!({
-            "mintAdmin": mint_admin
-        });
+    /// Returns the EvNode access list limits and pricing, if configured.
+    pub fn access_list_settings(&self) -> Option<AccessListSettings> {
+        let max_entries = self.max_access_list_entries?;
+        let max_storage_keys = self.max_access_list_storage_keys?;
+        Some(AccessListSettings::new(
+            max_entries,
+            max_storage_keys,
+            self.access_list_address_gas.unwrap_or(0),
+            self.access_list_storage_key_gas.unwrap_or(0),
+            self.access_list_activation_height.unwrap_or(0),
+        ))
+    }
 
-        let chainspec = create_test_chainspec_with_extras(Some(extras));
-        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+    fn validate_access_list(&self) -> Result<(), ConfigError> {
+        match (
+            self.max_access_list_entries,
+            self.max_access_list_storage_keys,
+        ) {
+            (Some(_), Some(_)) => Ok(()),
+            (Some(_), None) | (None, Some(_)) => Err(ConfigError::InvalidAccessList(
+                "maxAccessListEntries and maxAccessListStorageKeys must be set together"
+                    .to_string(),
+            )),
+            (None, None)
+                if self.access_list_address_gas.is_some()
+                    || self.access_list_storage_key_gas.is_some()
+                    || self.access_list_activation_height.is_some() =>
+            {
+                Err(ConfigError::InvalidAccessList(
+                    "access list pricing and activation require maxAccessListEntries and \
+                     maxAccessListStorageKeys"
+                        .to_string(),
+                ))
+            }
+            (None, None) => Ok(()),
+        }
+    }
 
-        assert_eq!(config.base_fee_sink, None);
-        assert_eq!(config.mint_admin, Some(mint_admin));
-        assert_eq!(config.base_fee_redirect_activation_height, None);
-        assert_eq!(config.mint_precompile_activation_height, Some(0));
+    /// Returns the sponsor allowlist and activation height (defaulting to 0) if configured.
+    pub fn sponsor_allowlist_settings(&self) -> Option<(Vec<Address>, u64)> {
+        if self.sponsor_allowlist.is_empty() {
+            None
+        } else {
+            let activation = self.sponsor_allowlist_activation_height.unwrap_or(0);
+            Some((self.sponsor_allowlist.clone(), activation))
+        }
     }
 
-    #[test]
-    fn test_activation_heights_override() {
-        let sink = address!("0000000000000000000000000000000000000002");
-        let admin = address!("00000000000000000000000000000000000000bb");
-        let extras = json!({
-            "baseFeeSink": sink,
-            "baseFeeRedirectActivationHeight": 42,
-            "mintAdmin": admin,
-            "mintPrecompileActivationHeight": 64
-        });
+    fn validate_sponsor_allowlist(&self) -> Result<(), ConfigError> {
+        let allowlist_len = self.sponsor_allowlist.len();
+        if allowlist_len > MAX_SPONSOR_ALLOWLIST_LEN {
+            return Err(ConfigError::InvalidSponsorAllowlist(format!(
+                "sponsorAllowlist has {allowlist_len} entries (max {MAX_SPONSOR_ALLOWLIST_LEN})"
+            )));
+        }
 
-        let chainspec = create_test_chainspec_with_extras(Some(extras));
-        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        let mut seen = HashSet::with_capacity(allowlist_len);
+        for addr in &self.sponsor_allowlist {
+            if addr.is_zero() {
+                return Err(ConfigError::InvalidSponsorAllowlist(
+                    "sponsorAllowlist contains zero address".to_string(),
+                ));
+            }
+            if !seen.insert(*addr) {
+                return Err(ConfigError::InvalidSponsorAllowlist(
+                    "sponsorAllowlist contains duplicate entries".to_string(),
+                ));
+            }
+        }
 
-        assert_eq!(config.base_fee_sink, Some(sink));
-        assert_eq!(config.base_fee_redirect_activation_height, Some(42));
-        assert_eq!(config.mint_admin, Some(admin));
-        assert_eq!(config.mint_precompile_activation_height, Some(64));
+        Ok(())
     }
 
-    #[test]
-    fn test_mint_admin_zero_disables() {
-        let extras = json!({
-            "mintAdmin": "0x0000000000000000000000000000000000000000"
-        });
-
-        let chainspec = create_test_chainspec_with_extras(Some(extras));
-        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
-
-        assert_eq!(config.mint_admin, None);
-        assert_eq!(config.mint_precompile_activation_height, None);
+    /// Returns the scheduled state patches, in chainspec order.
+    pub fn state_patches(&self) -> &[StatePatch] {
+        &self.state_patches
+    }
+
+    fn validate_state_patches(&self) -> Result<(), ConfigError> {
+        if self.state_patches.len() > MAX_STATE_PATCHES {
+            return Err(ConfigError::InvalidStatePatch(format!(
+                "statePatches has {} entries (max {MAX_STATE_PATCHES})",
+                self.state_patches.len()
+            )));
+        }
+
+        let mut heights = HashSet::with_capacity(self.state_patches.len());
+        for patch in &self.state_patches {
+            let height = patch.height;
+            if height == 0 {
+                return Err(ConfigError::InvalidStatePatch(
+                    "state patch height must be greater than zero; edit the genesis alloc instead"
+                        .to_string(),
+                ));
+            }
+            if !heights.insert(height) {
+                return Err(ConfigError::InvalidStatePatch(format!(
+                    "more than one state patch at height {height}"
+                )));
+            }
+            if patch.reason.trim().is_empty() {
+                return Err(ConfigError::InvalidStatePatch(format!(
+                    "state patch at height {height} has no reason"
+                )));
+            }
+            if patch.accounts.is_empty() || patch.accounts.values().any(|edit| edit.is_empty()) {
+                return Err(ConfigError::InvalidStatePatch(format!(
+                    "state patch at height {height} has an account without edits"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the type byte raw EvNode transactions may use instead of `0x76`, with its
+    /// activation height (defaulting to 0), if configured and valid.
+    pub fn evnode_tx_type_alias(&self) -> Option<EvNodeTxTypeAlias> {
+        let alias = EvNodeTxTypeAlias::new(
+            self.evnode_tx_type_alias?,
+            self.evnode_tx_type_alias_activation_height.unwrap_or(0),
+        )
+        .ok()?;
+        Some(alias.with_canonical_refused(self.evnode_tx_type_refuse_canonical.unwrap_or(false)))
+    }
+
+    fn validate_evnode_tx_type_alias(&self) -> Result<(), ConfigError> {
+        match self.evnode_tx_type_alias {
+            None if self.evnode_tx_type_alias_activation_height.is_some()
+                || self.evnode_tx_type_refuse_canonical.is_some() =>
+            {
+                Err(ConfigError::InvalidEvNodeTxTypeAlias(
+                    "evNodeTxTypeAliasActivationHeight and evNodeTxTypeRefuseCanonical require \
+                     evNodeTxTypeAlias"
+                        .to_string(),
+                ))
+            }
+            Some(alias) => EvNodeTxTypeAlias::new(alias, 0)
+                .map(|_| ())
+                .map_err(|err| ConfigError::InvalidEvNodeTxTypeAlias(err.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns whether a block using `gas_used` ran below the gas target, if one is set.
+    pub fn below_gas_target(&self, gas_used: u64) -> bool {
+        self.gas_target.is_some_and(|target| gas_used < target)
+    }
+
+    fn validate_gas_target(&self) -> Result<(), ConfigError> {
+        let Some(target) = self.gas_target else {
+            return Ok(());
+        };
+        if target == 0 {
+            return Err(ConfigError::InvalidGasTarget(
+                "gasTarget must be greater than zero".to_string(),
+            ));
+        }
+        if let Some(max) = self.max_block_gas_limit.filter(|max| target > *max) {
+            return Err(ConfigError::InvalidGasTarget(format!(
+                "gasTarget {target} exceeds maxBlockGasLimit {max}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_min_gas_price(&self) -> Result<(), ConfigError> {
+        if self.min_gas_price.is_none()
+            && (!self.min_gas_price_exempt_senders.is_empty()
+                || !self.free_gas_targets.is_empty()
+                || self.min_gas_price_activation_height.is_some())
+        {
+            return Err(ConfigError::InvalidMinGasPrice(
+                "minGasPriceExemptSenders, freeGasTargets and minGasPriceActivationHeight \
+                 require minGasPrice"
+                    .to_string(),
+            ));
+        }
+        if self
+            .min_gas_price_exempt_senders
+            .iter()
+            .chain(&self.free_gas_targets)
+            .any(Address::is_zero)
+        {
+            return Err(ConfigError::InvalidMinGasPrice(
+                "exemption list contains zero address".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the extra warmed addresses and activation height of the precompile warm-up, if
+    /// configured.
+    pub fn precompile_warmup_settings(&self) -> Option<(Vec<Address>, u64)> {
+        self.precompile_warmup_activation_height
+            .map(|activation| (self.precompile_warmup_addresses.clone(), activation))
+    }
+
+    /// Returns the multi-dimensional gas pricing `(activation_height, state_growth_gas_per_byte,
+    /// da_gas_per_byte)`, if configured.
+    pub fn dimension_pricing_settings(&self) -> Option<(u64, u64, u64)> {
+        self.multi_dim_gas_activation_height.map(|activation| {
+            (
+                activation,
+                self.state_growth_gas_per_byte.unwrap_or(0),
+                self.da_gas_per_byte.unwrap_or(0),
+            )
+        })
+    }
+
+    /// Returns the number of recent blocks covered by the rolling block commitment.
+    pub fn block_commitment_window(&self) -> u64 {
+        self.block_commitment_window
+            .unwrap_or(DEFAULT_BLOCK_COMMITMENT_WINDOW)
+    }
+
+    /// Returns the block commitment `(window, activation_height)` if built blocks carry it in
+    /// their extra data.
+    pub fn block_commitment_settings(&self) -> Option<(u64, u64)> {
+        self.block_commitment_activation_height
+            .map(|activation| (self.block_commitment_window(), activation))
+    }
+
+    /// Returns the commitment window if block `block_number` carries the commitment over its
+    /// ancestors in its extra data.
+    pub fn block_commitment_window_for_block(&self, block_number: u64) -> Option<u64> {
+        self.block_commitment_settings()
+            .and_then(|(window, activation)| (block_number >= activation).then_some(window))
+    }
+
+    fn validate_block_commitment(&self) -> Result<(), ConfigError> {
+        let window = self.block_commitment_window();
+        if window == 0 || window > MAX_BLOCK_COMMITMENT_WINDOW {
+            return Err(ConfigError::InvalidBlockCommitment(format!(
+                "blockCommitmentWindow {window} must be between 1 and {MAX_BLOCK_COMMITMENT_WINDOW}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the EVM `(max_call_depth, memory_limit, activation_height)` if either cap is
+    /// configured, with the activation height defaulting to 0.
+    pub fn evm_limits_settings(&self) -> Option<(Option<u64>, Option<u64>, u64)> {
+        if self.evm_max_call_depth.is_none() && self.evm_memory_limit.is_none() {
+            return None;
+        }
+        Some((
+            self.evm_max_call_depth,
+            self.evm_memory_limit,
+            self.evm_limits_activation_height.unwrap_or(0),
+        ))
+    }
+
+    fn validate_evm_limits(&self) -> Result<(), ConfigError> {
+        if self.evm_limits_settings().is_none() && self.evm_limits_activation_height.is_some() {
+            return Err(ConfigError::InvalidEvmLimits(
+                "evmLimitsActivationHeight requires evmMaxCallDepth or evmMemoryLimit".to_string(),
+            ));
+        }
+        if let Some(depth) = self.evm_max_call_depth {
+            if !(MIN_CALL_DEPTH..=MAX_CALL_DEPTH).contains(&depth) {
+                return Err(ConfigError::InvalidEvmLimits(format!(
+                    "evmMaxCallDepth {depth} must be between {MIN_CALL_DEPTH} and {MAX_CALL_DEPTH}"
+                )));
+            }
+        }
+        if let Some(limit) = self.evm_memory_limit {
+            if !(MIN_MEMORY_LIMIT..=MAX_MEMORY_LIMIT).contains(&limit) {
+                return Err(ConfigError::InvalidEvmLimits(format!(
+                    "evmMemoryLimit {limit} must be between {MIN_MEMORY_LIMIT} and {MAX_MEMORY_LIMIT}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the state expiry `(activation_height, expiry_period)`, if configured.
+    pub fn state_expiry_settings(&self) -> Option<(u64, u64)> {
+        self.state_expiry_period
+            .map(|period| (self.state_expiry_activation_height.unwrap_or(0), period))
+    }
+
+    fn validate_state_expiry(&self) -> Result<(), ConfigError> {
+        match (
+            self.state_expiry_period,
+            self.state_expiry_activation_height,
+        ) {
+            (Some(0), _) => Err(ConfigError::InvalidStateExpiry(
+                "stateExpiryPeriod must be greater than zero".to_string(),
+            )),
+            (None, Some(_)) => Err(ConfigError::InvalidStateExpiry(
+                "stateExpiryActivationHeight requires stateExpiryPeriod".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the native staking `(minimum_stake, unbonding_period, activation_height)`, if
+    /// configured.
+    pub fn staking_settings(&self) -> Option<(U256, u64, u64)> {
+        self.staking_unbonding_period.map(|period| {
+            (
+                self.staking_minimum_stake.unwrap_or_default(),
+                period,
+                self.staking_activation_height.unwrap_or(0),
+            )
+        })
+    }
+
+    fn validate_staking(&self) -> Result<(), ConfigError> {
+        match self.staking_unbonding_period {
+            Some(0) => Err(ConfigError::InvalidStaking(
+                "stakingUnbondingPeriod must be greater than zero".to_string(),
+            )),
+            None if self.staking_minimum_stake.is_some()
+                || self.staking_activation_height.is_some() =>
+            {
+                Err(ConfigError::InvalidStaking(
+                    "stakingMinimumStake and stakingActivationHeight require \
+                     stakingUnbondingPeriod"
+                        .to_string(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the pinned EVM versions as `(activation_height, spec)` pairs, or `None` when
+    /// the chainspec's hardfork schedule applies.
+    ///
+    /// Unknown version names are skipped; [`Self::validate`] rejects them.
+    pub fn evm_version_schedule(&self) -> Option<Vec<(u64, SpecId)>> {
+        if self.evm_version.is_empty() {
+            return None;
+        }
+        Some(
+            self.evm_version
+                .iter()
+                .filter_map(|entry| {
+                    parse_evm_version(&entry.version).map(|spec| (entry.activation_height, spec))
+                })
+                .collect(),
+        )
+    }
+
+    fn validate_evm_version(&self) -> Result<(), ConfigError> {
+        let mut seen = HashSet::with_capacity(self.evm_version.len());
+        for entry in &self.evm_version {
+            if parse_evm_version(&entry.version).is_none() {
+                return Err(ConfigError::InvalidEvmVersion(format!(
+                    "unknown evmVersion {:?}",
+                    entry.version
+                )));
+            }
+            if !seen.insert(entry.activation_height) {
+                return Err(ConfigError::InvalidEvmVersion(format!(
+                    "evmVersion has more than one entry at height {}",
+                    entry.activation_height
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_native_currency(&self) -> Result<(), ConfigError> {
+        let Some(currency) = &self.native_currency else {
+            return Ok(());
+        };
+        if currency.symbol.trim().is_empty() {
+            return Err(ConfigError::InvalidNativeCurrency(
+                "nativeCurrency.symbol must not be empty".to_string(),
+            ));
+        }
+        if currency.decimals > MAX_NATIVE_DECIMALS {
+            return Err(ConfigError::InvalidNativeCurrency(format!(
+                "nativeCurrency.decimals is {} (max {MAX_NATIVE_DECIMALS})",
+                currency.decimals
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_deploy_allowlist(&self) -> Result<(), ConfigError> {
+        let allowlist_len = self.deploy_allowlist.len();
+        if allowlist_len > MAX_DEPLOY_ALLOWLIST_LEN {
+            return Err(ConfigError::InvalidDeployAllowlist(format!(
+                "deployAllowlist has {allowlist_len} entries (max {MAX_DEPLOY_ALLOWLIST_LEN})"
+            )));
+        }
+
+        let mut seen = HashSet::with_capacity(allowlist_len);
+        for addr in &self.deploy_allowlist {
+            if addr.is_zero() {
+                return Err(ConfigError::InvalidDeployAllowlist(
+                    "deployAllowlist contains zero address".to_string(),
+                ));
+            }
+            if !seen.insert(*addr) {
+                return Err(ConfigError::InvalidDeployAllowlist(
+                    "deployAllowlist contains duplicate entries".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the configured base-fee redirect sink and activation height (defaulting to 0).
+    pub fn base_fee_redirect_settings(&self) -> Option<(Address, u64)> {
+        self.base_fee_sink.map(|sink| {
+            let activation = self.base_fee_redirect_activation_height.unwrap_or(0);
+            (sink, activation)
+        })
+    }
+
+    /// Returns the share of the base fee burned rather than credited to the sink, in basis
+    /// points (defaulting to 0).
+    pub fn base_fee_burn_bps(&self) -> u16 {
+        self.base_fee_burn_bps.unwrap_or(0)
+    }
+
+    /// Returns the share of the base fee burned in the given block, in basis points. Without
+    /// an active redirect the whole base fee is burned, as on Ethereum.
+    pub fn base_fee_burn_bps_for_block(&self, block_number: u64) -> u16 {
+        if self.base_fee_sink_for_block(block_number).is_some() {
+            self.base_fee_burn_bps()
+        } else {
+            MAX_BURN_BPS
+        }
+    }
+
+    /// Returns the mint precompile admin and activation height (defaulting to 0).
+    pub fn mint_precompile_settings(&self) -> Option<(Address, u64)> {
+        self.mint_admin.map(|admin| {
+            let activation = self.mint_precompile_activation_height.unwrap_or(0);
+            (admin, activation)
+        })
+    }
+
+    /// Returns true if the builder must derive `prev_randao` itself for the given block.
+    pub fn derives_prev_randao_for_block(&self, block_number: u64) -> bool {
+        self.prev_randao_policy.is_derived()
+            && block_number >= self.prev_randao_policy_activation_height.unwrap_or(0)
+    }
+
+    /// Returns the deposit inbox relayer and activation height (defaulting to 0).
+    pub fn deposit_inbox_settings(&self) -> Option<(Address, u64)> {
+        self.deposit_inbox_relayer.map(|relayer| {
+            let activation = self.deposit_inbox_activation_height.unwrap_or(0);
+            (relayer, activation)
+        })
+    }
+
+    /// Returns true if withdrawals from payload attributes are credited for the given block.
+    pub fn withdrawals_enabled_for_block(&self, block_number: u64) -> bool {
+        self.withdrawals_activation_height
+            .is_some_and(|activation| block_number >= activation)
+    }
+
+    /// Returns true if contract fee payers are accepted for the given block.
+    pub fn gas_allowance_enabled_for_block(&self, block_number: u64) -> bool {
+        self.gas_allowance_activation_height
+            .is_some_and(|activation| block_number >= activation)
+    }
+
+    /// Returns true unless the chainspec disables EvNode (0x76) transactions.
+    pub fn evnode_tx_enabled(&self) -> bool {
+        self.enable_evnode_tx.unwrap_or(true)
+    }
+
+    /// Returns the sink if the redirect is active for the provided block number.
+    pub fn base_fee_sink_for_block(&self, block_number: u64) -> Option<Address> {
+        self.base_fee_redirect_settings()
+            .and_then(|(sink, activation)| (block_number >= activation).then_some(sink))
+    }
+}
+
+/// Runtime state shared by the components of one node: what the payload builder records and
+/// the RPC modules serve.
+///
+/// Clones share the same state, so every component built from one [`ResolvedEvolveConfig`]
+/// sees the same stores.
+#[derive(Debug, Clone, Default)]
+pub struct NodeHandles {
+    /// Build reports recorded by the payload builder for `evolve_getBuildReport`.
+    pub build_reports: Arc<BuildReportStore>,
+    /// Shutdown coordinator payload jobs register with and `evolve_nodeStatus` reports.
+    pub shutdown: Arc<ShutdownCoordinator>,
+    /// Bundles submitted through `evolve_sendBundle`, awaiting their target block.
+    pub bundles: Arc<BundlePool>,
+    /// Encrypted transactions queued through `evolve_sendEncryptedTransaction`.
+    pub encrypted: Arc<EncryptedPool>,
+    /// DA status reported through `evolve_setDaStatus`.
+    pub da_finality: Arc<DaFinalityTracker>,
+    /// Chain KPIs aggregated from canonical blocks for `evolve_getChainStats`.
+    pub chain_stats: Arc<ChainStatsTracker>,
+    /// Priority fees sampled from canonical blocks for `evolve_estimateFeePerGas`.
+    pub fee_estimator: Arc<FeeEstimator>,
+    /// Build attestations signed by the payload builder for `evolve_getBuildAttestation`.
+    pub attestations: Arc<BuildAttestationStore>,
+    /// Transactions flagged through `evolve_sendMaintenanceTransaction`.
+    pub maintenance: Arc<MaintenanceLane>,
+    /// Errors of failed payload jobs, attached to `engine_getPayload` errors.
+    pub engine_errors: Arc<EngineErrorStore>,
+    /// Ordering reports of built blocks for `evolve_getOrderingReport`.
+    pub ordering_reports: Arc<OrderingReportStore>,
+    /// Receipts of applied state patches for `evolve_getStatePatchReceipt`.
+    pub state_patch_receipts: Arc<StatePatchReceiptStore>,
+    /// Latest payload built by this node for `evolve_getPendingBlock`.
+    pub pending_block: Arc<PendingBlockStore>,
+}
+
+/// Evolve configuration parsed and validated once, shared by the pool, executor, payload
+/// builder, engine validator and RPC modules of a node through its [`SharedEvolveConfig`].
+///
+/// Invalid extras fail the node build instead of falling back to defaults in some components.
+#[derive(Debug)]
+pub struct ResolvedEvolveConfig(EvolvePayloadBuilderConfig);
+
+impl ResolvedEvolveConfig {
+    /// Parses and validates the configuration of `chain_spec`.
+    pub fn from_chain_spec(chain_spec: &ChainSpec) -> Result<Self, ConfigError> {
+        Self::new(EvolvePayloadBuilderConfig::from_chain_spec(chain_spec)?)
+    }
+
+    /// Validates `config`, e.g. the chainspec configuration with operator overrides applied.
+    pub fn new(config: EvolvePayloadBuilderConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        Ok(Self(config))
+    }
+
+    /// Runs the node with the settings of its CLI flags.
+    pub fn with_node_settings(mut self, node: NodeSettings) -> Self {
+        self.0.node = node;
+        self
+    }
+
+    /// Returns the resolved configuration.
+    pub const fn config(&self) -> &EvolvePayloadBuilderConfig {
+        &self.0
+    }
+}
+
+impl Deref for ResolvedEvolveConfig {
+    type Target = EvolvePayloadBuilderConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Evolve configuration of one node, handed to each of its component builders.
+///
+/// Either resolved up front, or from the chain spec by the first component built; every
+/// component of the node then gets the same [`ResolvedEvolveConfig`].
+#[derive(Debug, Clone, Default)]
+pub struct SharedEvolveConfig(Arc<OnceLock<Arc<ResolvedEvolveConfig>>>);
+
+impl SharedEvolveConfig {
+    /// Shares an already resolved configuration.
+    pub fn new(config: Arc<ResolvedEvolveConfig>) -> Self {
+        Self(Arc::new(OnceLock::from(config)))
+    }
+
+    /// Returns the node's configuration, resolving it from `chain_spec` if no component has
+    /// yet.
+    pub fn get_or_resolve(
+        &self,
+        chain_spec: &ChainSpec,
+    ) -> Result<Arc<ResolvedEvolveConfig>, ConfigError> {
+        if let Some(config) = self.0.get() {
+            return Ok(config.clone());
+        }
+        let config = Arc::new(ResolvedEvolveConfig::from_chain_spec(chain_spec)?);
+        Ok(self.0.get_or_init(|| config).clone())
+    }
+}
+
+/// Errors that can occur during configuration validation
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// Invalid configuration provided
+    #[error("Invalid config")]
+    InvalidConfig,
+    /// Chainspec extras contained invalid values
+    #[error("Invalid evolve extras in chainspec: {0}")]
+    InvalidExtras(#[from] serde_json::Error),
+    /// Deploy allowlist configuration invalid
+    #[error("Invalid deploy allowlist configuration: {0}")]
+    InvalidDeployAllowlist(String),
+    /// Native currency configuration invalid
+    #[error("Invalid native currency configuration: {0}")]
+    InvalidNativeCurrency(String),
+    /// EVM version configuration invalid
+    #[error("Invalid EVM version configuration: {0}")]
+    InvalidEvmVersion(String),
+    /// State expiry configuration invalid
+    #[error("Invalid state expiry configuration: {0}")]
+    InvalidStateExpiry(String),
+    /// Native staking configuration invalid
+    #[error("Invalid native staking configuration: {0}")]
+    InvalidStaking(String),
+    /// System transaction fee policy invalid
+    #[error("Invalid system transaction fee configuration: {0}")]
+    InvalidSystemTxFee(String),
+    /// Sponsor hash migration invalid
+    #[error("Invalid sponsor hash migration: {0}")]
+    InvalidSponsorHashMigration(String),
+    /// Base fee burn share invalid
+    #[error("Invalid base fee burn configuration: {0}")]
+    InvalidBaseFeeBurn(String),
+    /// Minimum gas price configuration invalid
+    #[error("Invalid minimum gas price configuration: {0}")]
+    InvalidMinGasPrice(String),
+    /// Gas limit bounds invalid
+    #[error("Invalid gas limit bounds: {0}")]
+    InvalidGasLimitBounds(String),
+    /// Per-transaction gas share invalid
+    #[error("Invalid per-transaction gas share: {0}")]
+    InvalidMaxTxGas(String),
+    /// EvNode call limit invalid
+    #[error("Invalid EvNode call limit: {0}")]
+    InvalidMaxBatchCalls(String),
+    /// EvNode access list limits invalid
+    #[error("Invalid EvNode access list configuration: {0}")]
+    InvalidAccessList(String),
+    /// Sponsor allowlist configuration invalid
+    #[error("Invalid sponsor allowlist configuration: {0}")]
+    InvalidSponsorAllowlist(String),
+    /// State patch schedule invalid
+    #[error("Invalid state patch configuration: {0}")]
+    InvalidStatePatch(String),
+    /// EvNode transaction type alias invalid
+    #[error("Invalid EvNode transaction type alias: {0}")]
+    InvalidEvNodeTxTypeAlias(String),
+    /// Gas target invalid
+    #[error("Invalid gas target: {0}")]
+    InvalidGasTarget(String),
+    /// System channel configuration invalid
+    #[error("Invalid system channel configuration: {0}")]
+    InvalidSystemChannel(String),
+    /// Block commitment configuration invalid
+    #[error("Invalid block commitment configuration: {0}")]
+    InvalidBlockCommitment(String),
+    /// EVM call depth or memory caps invalid
+    #[error("Invalid EVM limits: {0}")]
+    InvalidEvmLimits(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::Genesis;
+    use alloy_primitives::{address, Address};
+    use reth_chainspec::ChainSpecBuilder;
+    use serde_json::json;
+
+    fn create_test_chainspec_with_extras(extras: Option<serde_json::Value>) -> ChainSpec {
+        let mut builder = ChainSpecBuilder::mainnet();
+
+        if let Some(extras_value) = extras {
+            // Create a genesis with evolve extras
+            let mut genesis = Genesis::default();
+            genesis
+                .config
+                .extra_fields
+                .insert("evolve".to_string(), extras_value);
+            builder = builder.genesis(genesis);
+        }
+
+        builder.build()
+    }
+
+    #[test]
+    fn test_basefee_sink_some_address() {
+        // Test case when base_fee_sink is Some(address)
+        let test_address = address!("0000000000000000000000000000000000000001");
+        let extras = json!({
+            "baseFeeSink": test_address
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert_eq!(config.base_fee_sink, Some(test_address));
+        assert_eq!(config.mint_admin, None);
+        assert_eq!(config.base_fee_redirect_activation_height, Some(0));
+        assert_eq!(config.mint_precompile_activation_height, None);
+    }
+
+    #[test]
+    fn test_mint_admin_some_address() {
+        let mint_admin = address!("00000000000000000000000000000000000000aa");
+        let extras = json!({
+            "mintAdmin": mint_admin
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert_eq!(config.base_fee_sink, None);
+        assert_eq!(config.mint_admin, Some(mint_admin));
+        assert_eq!(config.base_fee_redirect_activation_height, None);
+        assert_eq!(config.mint_precompile_activation_height, Some(0));
+    }
+
+    #[test]
+    fn test_activation_heights_override() {
+        let sink = address!("0000000000000000000000000000000000000002");
+        let admin = address!("00000000000000000000000000000000000000bb");
+        let extras = json!({
+            "baseFeeSink": sink,
+            "baseFeeRedirectActivationHeight": 42,
+            "mintAdmin": admin,
+            "mintPrecompileActivationHeight": 64
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert_eq!(config.base_fee_sink, Some(sink));
+        assert_eq!(config.base_fee_redirect_activation_height, Some(42));
+        assert_eq!(config.mint_admin, Some(admin));
+        assert_eq!(config.mint_precompile_activation_height, Some(64));
+    }
+
+    #[test]
+    fn test_mint_admin_zero_disables() {
+        let extras = json!({
+            "mintAdmin": "0x0000000000000000000000000000000000000000"
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert_eq!(config.mint_admin, None);
+        assert_eq!(config.mint_precompile_activation_height, None);
+    }
+
+    #[test]
+    fn test_basefee_sink_none() {
+        // Test case when base_fee_sink is not present (None)
+        let extras = json!({});
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert_eq!(config.base_fee_sink, None);
+        assert_eq!(config.base_fee_redirect_activation_height, None);
+    }
+
+    #[test]
+    fn test_no_ev_reth_extras() {
+        // Test case when no evolve extras are present at all
+        let chainspec = create_test_chainspec_with_extras(None);
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert_eq!(config.base_fee_sink, None);
+        assert_eq!(config.mint_admin, None);
+        assert_eq!(config.base_fee_redirect_activation_height, None);
+        assert_eq!(config.mint_precompile_activation_height, None);
+    }
+
+    #[test]
+    fn test_basefee_sink_invalid_address() {
+        // Test case when base_fee_sink has invalid format (Error case)
+        let extras = json!({
+            "baseFeeSink": "not_a_valid_address"
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let result = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec);
+
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), ConfigError::InvalidExtras(_)));
+    }
+
+    #[test]
+    fn test_basefee_sink_wrong_type() {
+        // Test case when base_fee_sink has wrong type (Error case)
+        let extras = json!({
+            "baseFeeSink": 12345
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let result = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec);
+
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), ConfigError::InvalidExtras(_)));
+    }
+
+    #[test]
+    fn test_default_config() {
+        // Test default configuration
+        let config = EvolvePayloadBuilderConfig::default();
+        assert_eq!(config.base_fee_sink, None);
+        assert_eq!(config.mint_admin, None);
+        assert_eq!(config.base_fee_redirect_activation_height, None);
+        assert_eq!(config.mint_precompile_activation_height, None);
+        assert!(config.deploy_allowlist.is_empty());
+        assert_eq!(config.deploy_allowlist_activation_height, None);
+    }
+
+    #[test]
+    fn test_new_config() {
+        // Test new() constructor
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.base_fee_sink, None);
+        assert_eq!(config.mint_admin, None);
+        assert_eq!(config.base_fee_redirect_activation_height, None);
+        assert_eq!(config.mint_precompile_activation_height, None);
+        assert_eq!(config.contract_size_limit, None);
+        assert!(config.deploy_allowlist.is_empty());
+        assert_eq!(config.deploy_allowlist_activation_height, None);
+    }
+
+    #[test]
+    fn test_validate_always_ok() {
+        // Test that validate returns Ok for defaults
+        let config = EvolvePayloadBuilderConfig::new();
+        assert!(config.validate().is_ok());
+
+        let config_with_sink = EvolvePayloadBuilderConfig {
+            base_fee_sink: Some(address!("0000000000000000000000000000000000000001")),
+            mint_admin: Some(address!("00000000000000000000000000000000000000aa")),
+            base_fee_redirect_activation_height: Some(0),
+            mint_precompile_activation_height: Some(0),
+            ..Default::default()
+        };
+        assert!(config_with_sink.validate().is_ok());
+    }
+
+    #[test]
+    fn test_deploy_allowlist_defaults_activation_to_zero() {
+        let allowlist = vec![
+            address!("00000000000000000000000000000000000000aa"),
+            address!("00000000000000000000000000000000000000bb"),
+        ];
+        let extras = json!({
+            "deployAllowlist": allowlist
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert_eq!(config.deploy_allowlist.len(), 2);
+        assert_eq!(config.deploy_allowlist_activation_height, Some(0));
+    }
+
+    #[test]
+    fn test_deploy_allowlist_rejects_zero_address() {
+        let extras = json!({
+            "deployAllowlist": [
+                "0x0000000000000000000000000000000000000000"
+            ]
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidDeployAllowlist(_))
+        ));
+    }
+
+    #[test]
+    fn test_deploy_allowlist_rejects_duplicates() {
+        let dup = address!("00000000000000000000000000000000000000aa");
+        let extras = json!({
+            "deployAllowlist": [dup, dup]
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidDeployAllowlist(_))
+        ));
+    }
+
+    #[test]
+    fn test_deploy_allowlist_rejects_too_many_entries() {
+        let mut allowlist = Vec::new();
+        for i in 0..=MAX_DEPLOY_ALLOWLIST_LEN {
+            let mut bytes = [0u8; 20];
+            bytes[12..].copy_from_slice(&(i as u64 + 1).to_be_bytes());
+            let addr = Address::new(bytes);
+            allowlist.push(addr);
+        }
+        let config = EvolvePayloadBuilderConfig {
+            deploy_allowlist: allowlist,
+            deploy_allowlist_activation_height: Some(0),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidDeployAllowlist(_))
+        ));
+    }
+
+    #[test]
+    fn test_base_fee_sink_for_block() {
+        let sink = address!("0000000000000000000000000000000000000003");
+        let mut config = EvolvePayloadBuilderConfig {
+            base_fee_sink: Some(sink),
+            base_fee_redirect_activation_height: Some(5),
+            ..Default::default()
+        };
+
+        assert_eq!(config.base_fee_sink_for_block(4), None);
+        assert_eq!(config.base_fee_sink_for_block(5), Some(sink));
+        assert_eq!(config.base_fee_sink_for_block(10), Some(sink));
+
+        config.base_fee_redirect_activation_height = None;
+        assert_eq!(config.base_fee_sink_for_block(0), Some(sink));
+    }
+
+    #[test]
+    fn test_chainspec_evolve_config_deserialization() {
+        // Test direct deserialization of ChainspecEvolveConfig
+        let json_with_sink = json!({
+            "baseFeeSink": "0x0000000000000000000000000000000000000001",
+            "mintAdmin": "0x00000000000000000000000000000000000000aa"
+        });
+
+        let config: ChainspecEvolveConfig = serde_json::from_value(json_with_sink).unwrap();
+        assert_eq!(
+            config.base_fee_sink,
+            Some(address!("0000000000000000000000000000000000000001"))
+        );
+        assert_eq!(
+            config.mint_admin,
+            Some(address!("00000000000000000000000000000000000000aa"))
+        );
+
+        let json_without_sink = json!({});
+        let config: ChainspecEvolveConfig = serde_json::from_value(json_without_sink).unwrap();
+        assert_eq!(config.base_fee_sink, None);
+        assert_eq!(config.mint_admin, None);
+    }
+
+    #[test]
+    fn test_contract_size_limit_default() {
+        // Test default contract size limit (24KB per EIP-170)
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.contract_size_limit, None);
+        assert_eq!(config.contract_size_limit_settings(), None);
+        // When no custom limit is set, use EIP-170 default for any block
+        assert_eq!(
+            config.contract_size_limit_for_block(0),
+            DEFAULT_CONTRACT_SIZE_LIMIT
+        );
+        assert_eq!(config.contract_size_limit_for_block(0), 24 * 1024);
+    }
+
+    #[test]
+    fn test_contract_size_limit_from_chainspec() {
+        // Test contract size limit from chainspec with activation height
+        let extras = json!({
+            "contractSizeLimit": 131072,
+            "contractSizeLimitActivationHeight": 100
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        assert_eq!(config.contract_size_limit, Some(131072));
+        assert_eq!(config.contract_size_limit_activation_height, Some(100));
+        assert_eq!(config.contract_size_limit_settings(), Some((131072, 100)));
+    }
+
+    #[test]
+    fn test_contract_size_limit_respects_activation_height() {
+        // Test that contract size limit respects activation height
+        let extras = json!({
+            "contractSizeLimit": 131072,
+            "contractSizeLimitActivationHeight": 100
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+
+        // Before activation: use EIP-170 default
+        assert_eq!(
+            config.contract_size_limit_for_block(0),
+            DEFAULT_CONTRACT_SIZE_LIMIT
+        );
+        assert_eq!(
+            config.contract_size_limit_for_block(99),
+            DEFAULT_CONTRACT_SIZE_LIMIT
+        );
+
+        // At and after activation: use custom limit
+        assert_eq!(config.contract_size_limit_for_block(100), 131072);
+        assert_eq!(config.contract_size_limit_for_block(1000), 131072);
     }
 
     #[test]
-    fn test_basefee_sink_none() {
-        // Test case when base_fee_sink is not present (None)
-        let extras = json!({});
+    fn test_contract_size_limit_defaults_activation_to_zero() {
+        // Test that activation height defaults to 0 when limit is set but height is not
+        let extras = json!({
+            "contractSizeLimit": 131072
+        });
 
         let chainspec = create_test_chainspec_with_extras(Some(extras));
         let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
 
-        assert_eq!(config.base_fee_sink, None);
-        assert_eq!(config.base_fee_redirect_activation_height, None);
+        assert_eq!(config.contract_size_limit, Some(131072));
+        assert_eq!(config.contract_size_limit_activation_height, None);
+        // Settings method defaults activation to 0
+        assert_eq!(config.contract_size_limit_settings(), Some((131072, 0)));
+        // Limit is active from block 0
+        assert_eq!(config.contract_size_limit_for_block(0), 131072);
     }
 
     #[test]
-    fn test_no_ev_reth_extras() {
-        // Test case when no evolve extras are present at all
-        let chainspec = create_test_chainspec_with_extras(None);
+    fn test_contract_size_limit_not_set_uses_default() {
+        // Test that missing contractSizeLimit uses EIP-170 default
+        let extras = json!({
+            "baseFeeSink": "0x0000000000000000000000000000000000000001"
+        });
+
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
         let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
 
-        assert_eq!(config.base_fee_sink, None);
-        assert_eq!(config.mint_admin, None);
-        assert_eq!(config.base_fee_redirect_activation_height, None);
-        assert_eq!(config.mint_precompile_activation_height, None);
+        assert_eq!(config.contract_size_limit, None);
+        assert_eq!(config.contract_size_limit_settings(), None);
+        assert_eq!(
+            config.contract_size_limit_for_block(0),
+            DEFAULT_CONTRACT_SIZE_LIMIT
+        );
+        assert_eq!(
+            config.contract_size_limit_for_block(1000000),
+            DEFAULT_CONTRACT_SIZE_LIMIT
+        );
     }
 
     #[test]
-    fn test_basefee_sink_invalid_address() {
-        // Test case when base_fee_sink has invalid format (Error case)
+    fn test_prev_randao_policy_from_chainspec() {
         let extras = json!({
-            "baseFeeSink": "not_a_valid_address"
+            "prevRandaoPolicy": "derived",
+            "prevRandaoPolicyActivationHeight": 10
         });
 
         let chainspec = create_test_chainspec_with_extras(Some(extras));
-        let result = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec);
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
 
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), ConfigError::InvalidExtras(_)));
+        assert_eq!(config.prev_randao_policy, PrevRandaoPolicy::Derived);
+        assert!(!config.derives_prev_randao_for_block(9));
+        assert!(config.derives_prev_randao_for_block(10));
+
+        let config = EvolvePayloadBuilderConfig::new();
+        assert!(!config.derives_prev_randao_for_block(10));
     }
 
     #[test]
-    fn test_basefee_sink_wrong_type() {
-        // Test case when base_fee_sink has wrong type (Error case)
+    fn test_withdrawals_disabled_unless_configured() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert!(!config.withdrawals_enabled_for_block(0));
+
+        let extras = json!({ "withdrawalsActivationHeight": 3 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(!config.withdrawals_enabled_for_block(2));
+        assert!(config.withdrawals_enabled_for_block(3));
+    }
+
+    #[test]
+    fn test_gas_allowance_disabled_unless_configured() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert!(!config.gas_allowance_enabled_for_block(0));
+
+        let extras = json!({ "gasAllowanceActivationHeight": 10 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(!config.gas_allowance_enabled_for_block(9));
+        assert!(config.gas_allowance_enabled_for_block(10));
+    }
+
+    #[test]
+    fn test_evnode_tx_enabled_unless_disabled() {
+        assert!(EvolvePayloadBuilderConfig::new().evnode_tx_enabled());
+
+        let extras = json!({ "enableEvNodeTx": false });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(!config.evnode_tx_enabled());
+    }
+
+    #[test]
+    fn test_precompile_warmup() {
+        assert_eq!(
+            EvolvePayloadBuilderConfig::new().precompile_warmup_settings(),
+            None
+        );
+
+        let extra = address!("0x00000000000000000000000000000000000000f2");
         let extras = json!({
-            "baseFeeSink": 12345
+            "precompileWarmupActivationHeight": 10,
+            "precompileWarmupAddresses": [extra]
         });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert_eq!(config.precompile_warmup_settings(), Some((vec![extra], 10)));
+    }
 
+    #[test]
+    fn test_dimension_pricing() {
+        assert_eq!(
+            EvolvePayloadBuilderConfig::new().dimension_pricing_settings(),
+            None
+        );
+
+        let extras = json!({
+            "multiDimGasActivationHeight": 100,
+            "daGasPerByte": 16
+        });
         let chainspec = create_test_chainspec_with_extras(Some(extras));
-        let result = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec);
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert_eq!(config.dimension_pricing_settings(), Some((100, 0, 16)));
+    }
 
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), ConfigError::InvalidExtras(_)));
+    #[test]
+    fn test_min_gas_price() {
+        let relayer = address!("0x00000000000000000000000000000000000000a1");
+        let exempt = address!("0x00000000000000000000000000000000000000b1");
+        let free = address!("0x00000000000000000000000000000000000000c1");
+        let extras = json!({
+            "systemTxSenders": [relayer],
+            "systemTxFeePolicy": "waived",
+            "minGasPrice": 1000000000u64,
+            "minGasPriceExemptSenders": [exempt],
+            "freeGasTargets": [free]
+        });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.min_gas_price_settings(),
+            Some((1_000_000_000, vec![exempt, relayer], vec![free], 0))
+        );
+
+        let extras = json!({ "freeGasTargets": [free] });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidMinGasPrice(_))
+        ));
     }
 
     #[test]
-    fn test_default_config() {
-        // Test default configuration
-        let config = EvolvePayloadBuilderConfig::default();
-        assert_eq!(config.base_fee_sink, None);
-        assert_eq!(config.mint_admin, None);
-        assert_eq!(config.base_fee_redirect_activation_height, None);
-        assert_eq!(config.mint_precompile_activation_height, None);
-        assert!(config.deploy_allowlist.is_empty());
-        assert_eq!(config.deploy_allowlist_activation_height, None);
+    fn test_system_channel_settings() {
+        let sender = address!("00000000000000000000000000000000000000cc");
+        let extras = json!({
+            "systemTxSenders": [sender],
+            "systemChannelActivationHeight": 7
+        });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.system_channel_settings(), Some((vec![sender], 7)));
+
+        let extras = json!({ "systemChannelActivationHeight": 0 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidSystemChannel(_))
+        ));
+    }
+
+    #[test]
+    fn test_base_fee_burn_bps() {
+        let sink = address!("00000000000000000000000000000000000000fe");
+        let extras = json!({
+            "baseFeeSink": sink,
+            "baseFeeRedirectActivationHeight": 10,
+            "baseFeeBurnBps": 2500
+        });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.base_fee_burn_bps(), 2500);
+        assert_eq!(config.base_fee_burn_bps_for_block(9), MAX_BURN_BPS);
+        assert_eq!(config.base_fee_burn_bps_for_block(10), 2500);
+
+        let extras = json!({ "baseFeeSink": sink, "baseFeeBurnBps": 10_001 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidBaseFeeBurn(_))
+        ));
+
+        let extras = json!({ "baseFeeBurnBps": 5000 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidBaseFeeBurn(_))
+        ));
+    }
+
+    #[test]
+    fn test_gas_limit_bounds_settings() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.gas_limit_bounds_settings(), None);
+
+        let extras = json!({ "minBlockGasLimit": 30_000_000, "maxGasLimitChangeBps": 100 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.gas_limit_bounds_settings(),
+            Some((30_000_000, u64::MAX, Some(100), 0))
+        );
+
+        let extras = json!({ "minBlockGasLimit": 60_000_000, "maxBlockGasLimit": 30_000_000 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidGasLimitBounds(_))
+        ));
+
+        let extras = json!({ "maxGasLimitChangeBps": 0 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidGasLimitBounds(_))
+        ));
+    }
+
+    #[test]
+    fn test_max_tx_gas_settings() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.max_tx_gas_settings(), None);
+
+        let extras = json!({ "maxTxGasBps": 5000, "maxTxGasActivationHeight": 10 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.max_tx_gas_settings(), Some((5000, 10)));
+
+        for extras in [
+            json!({ "maxTxGasBps": 0 }),
+            json!({ "maxTxGasBps": 10_001 }),
+            json!({ "maxTxGasActivationHeight": 10 }),
+        ] {
+            let chainspec = create_test_chainspec_with_extras(Some(extras));
+            let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(ConfigError::InvalidMaxTxGas(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_max_batch_calls() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.max_batch_calls(), None);
+
+        let extras = json!({ "maxBatchCalls": 64, "maxBatchCallsActivationHeight": 10 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.max_batch_calls(), Some(MaxBatchCalls::new(64, 10)));
+
+        for extras in [
+            json!({ "maxBatchCalls": 0 }),
+            json!({ "maxBatchCallsActivationHeight": 10 }),
+        ] {
+            let chainspec = create_test_chainspec_with_extras(Some(extras));
+            let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(ConfigError::InvalidMaxBatchCalls(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_access_list_settings() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.access_list_settings(), None);
+
+        let extras = json!({
+            "maxAccessListEntries": 16,
+            "maxAccessListStorageKeys": 32,
+            "accessListStorageKeyGas": 100,
+            "accessListActivationHeight": 10
+        });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.access_list_settings(),
+            Some(AccessListSettings::new(16, 32, 0, 100, 10))
+        );
+
+        for extras in [
+            json!({ "maxAccessListEntries": 16 }),
+            json!({ "maxAccessListStorageKeys": 32 }),
+            json!({ "accessListAddressGas": 1000 }),
+            json!({ "accessListActivationHeight": 10 }),
+        ] {
+            let chainspec = create_test_chainspec_with_extras(Some(extras));
+            let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(ConfigError::InvalidAccessList(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_sponsor_allowlist() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.sponsor_allowlist_settings(), None);
+
+        let sponsor = address!("0x00000000000000000000000000000000000000aa");
+        let extras = json!({ "sponsorAllowlist": [sponsor] });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.sponsor_allowlist_settings(),
+            Some((vec![sponsor], 0))
+        );
+
+        for extras in [
+            json!({ "sponsorAllowlist": [Address::ZERO] }),
+            json!({ "sponsorAllowlist": [sponsor, sponsor] }),
+        ] {
+            let chainspec = create_test_chainspec_with_extras(Some(extras));
+            let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(ConfigError::InvalidSponsorAllowlist(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_state_patches() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert!(config.state_patches().is_empty());
+
+        let patch = |height: u64, reason: &str, edit: serde_json::Value| {
+            json!({
+                "height": height,
+                "reason": reason,
+                "accounts": { "0x00000000000000000000000000000000000000aa": edit }
+            })
+        };
+        let extras = json!({
+            "statePatches": [
+                patch(100, "proposal 7", json!({ "balance": "0x64" })),
+                patch(200, "proposal 9", json!({ "nonce": 3 })),
+            ]
+        });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.state_patches().len(), 2);
+        assert_eq!(config.state_patches()[1].height, 200);
+
+        for patches in [
+            json!([patch(0, "genesis", json!({ "nonce": 1 }))]),
+            json!([
+                patch(100, "proposal 7", json!({ "nonce": 1 })),
+                patch(100, "proposal 8", json!({ "nonce": 2 })),
+            ]),
+            json!([patch(100, " ", json!({ "nonce": 1 }))]),
+            json!([patch(100, "proposal 7", json!({}))]),
+        ] {
+            let chainspec =
+                create_test_chainspec_with_extras(Some(json!({ "statePatches": patches })));
+            let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(ConfigError::InvalidStatePatch(_))
+            ));
+        }
+
+        let unknown_field =
+            json!({ "statePatches": [patch(100, "proposal 7", json!({ "code_hash": "0x00" }))] });
+        let chainspec = create_test_chainspec_with_extras(Some(unknown_field));
+        assert!(EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).is_err());
+    }
+
+    #[test]
+    fn test_shared_config_is_resolved_once_per_node() {
+        let spec = create_test_chainspec_with_extras(Some(json!({
+            "baseFeeSink": "0x00000000000000000000000000000000000000b1"
+        })));
+        let shared = SharedEvolveConfig::default();
+        let first = shared.get_or_resolve(&spec).unwrap();
+        // Clones handed to other components share the resolved configuration.
+        let second = shared
+            .clone()
+            .get_or_resolve(&create_test_chainspec_with_extras(None))
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(
+            second.base_fee_sink,
+            Some(address!("00000000000000000000000000000000000000b1"))
+        );
+
+        // A separate node resolves its own configuration.
+        let other = SharedEvolveConfig::default()
+            .get_or_resolve(&create_test_chainspec_with_extras(None))
+            .unwrap();
+        assert_eq!(other.base_fee_sink, None);
+
+        let provided = Arc::new(ResolvedEvolveConfig::from_chain_spec(&spec).unwrap());
+        let shared = SharedEvolveConfig::new(provided.clone());
+        assert!(Arc::ptr_eq(
+            &shared
+                .get_or_resolve(&create_test_chainspec_with_extras(None))
+                .unwrap(),
+            &provided
+        ));
+
+        // Invalid extras fail every component instead of falling back to defaults.
+        let invalid = create_test_chainspec_with_extras(Some(json!({
+            "baseFeeBurnBps": 10_001
+        })));
+        let shared = SharedEvolveConfig::default();
+        assert!(shared.get_or_resolve(&invalid).is_err());
+        assert!(shared.get_or_resolve(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_evnode_tx_type_alias() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.evnode_tx_type_alias(), None);
+
+        let extras = json!({
+            "evNodeTxTypeAlias": "0x7a",
+            "evNodeTxTypeAliasActivationHeight": 10,
+            "evNodeTxTypeRefuseCanonical": true
+        });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        let alias = config.evnode_tx_type_alias().unwrap();
+        assert_eq!(alias.alias(), 0x7a);
+        assert_eq!(alias.activation_height(), 10);
+        assert!(alias.refuses_canonical());
+
+        for extras in [
+            json!({ "evNodeTxTypeAlias": "0x2" }),
+            json!({ "evNodeTxTypeAlias": "0x76" }),
+            json!({ "evNodeTxTypeAlias": "0x80" }),
+            json!({ "evNodeTxTypeRefuseCanonical": true }),
+        ] {
+            let chainspec = create_test_chainspec_with_extras(Some(extras));
+            let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(ConfigError::InvalidEvNodeTxTypeAlias(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_gas_target() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert!(!config.below_gas_target(0));
+
+        let extras = json!({ "gasTarget": 15_000_000 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config.below_gas_target(14_999_999));
+        assert!(!config.below_gas_target(15_000_000));
+
+        for extras in [
+            json!({ "gasTarget": 0 }),
+            json!({ "gasTarget": 40_000_000, "maxBlockGasLimit": 30_000_000 }),
+        ] {
+            let chainspec = create_test_chainspec_with_extras(Some(extras));
+            let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(ConfigError::InvalidGasTarget(_))
+            ));
+        }
     }
 
     #[test]
-    fn test_new_config() {
-        // Test new() constructor
+    fn test_block_commitment_settings() {
         let config = EvolvePayloadBuilderConfig::new();
-        assert_eq!(config.base_fee_sink, None);
-        assert_eq!(config.mint_admin, None);
-        assert_eq!(config.base_fee_redirect_activation_height, None);
-        assert_eq!(config.mint_precompile_activation_height, None);
-        assert_eq!(config.contract_size_limit, None);
-        assert!(config.deploy_allowlist.is_empty());
-        assert_eq!(config.deploy_allowlist_activation_height, None);
-    }
+        assert_eq!(config.block_commitment_settings(), None);
+        assert_eq!(
+            config.block_commitment_window(),
+            DEFAULT_BLOCK_COMMITMENT_WINDOW
+        );
 
-    #[test]
-    fn test_validate_always_ok() {
-        // Test that validate returns Ok for defaults
-        let config = EvolvePayloadBuilderConfig::new();
+        let extras = json!({ "blockCommitmentWindow": 16, "blockCommitmentActivationHeight": 5 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
         assert!(config.validate().is_ok());
+        assert_eq!(config.block_commitment_settings(), Some((16, 5)));
+        assert_eq!(config.block_commitment_window_for_block(4), None);
+        assert_eq!(config.block_commitment_window_for_block(5), Some(16));
 
-        let config_with_sink = EvolvePayloadBuilderConfig {
-            base_fee_sink: Some(address!("0000000000000000000000000000000000000001")),
-            mint_admin: Some(address!("00000000000000000000000000000000000000aa")),
-            base_fee_redirect_activation_height: Some(0),
-            mint_precompile_activation_height: Some(0),
-            ..Default::default()
-        };
-        assert!(config_with_sink.validate().is_ok());
+        let extras = json!({ "blockCommitmentWindow": MAX_BLOCK_COMMITMENT_WINDOW + 1 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidBlockCommitment(_))
+        ));
     }
 
     #[test]
-    fn test_deploy_allowlist_defaults_activation_to_zero() {
-        let allowlist = vec![
-            address!("00000000000000000000000000000000000000aa"),
-            address!("00000000000000000000000000000000000000bb"),
-        ];
-        let extras = json!({
-            "deployAllowlist": allowlist
-        });
+    fn test_evm_limits_settings() {
+        assert_eq!(
+            EvolvePayloadBuilderConfig::new().evm_limits_settings(),
+            None
+        );
 
+        let extras = json!({ "evmMaxCallDepth": 64, "evmMemoryLimit": 4194304 });
         let chainspec = create_test_chainspec_with_extras(Some(extras));
         let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.evm_limits_settings(),
+            Some((Some(64), Some(4194304), 0))
+        );
 
-        assert_eq!(config.deploy_allowlist.len(), 2);
-        assert_eq!(config.deploy_allowlist_activation_height, Some(0));
+        for extras in [
+            json!({ "evmMaxCallDepth": MIN_CALL_DEPTH - 1 }),
+            json!({ "evmMaxCallDepth": MAX_CALL_DEPTH + 1 }),
+            json!({ "evmMemoryLimit": 1024 }),
+            json!({ "evmLimitsActivationHeight": 10 }),
+        ] {
+            let chainspec = create_test_chainspec_with_extras(Some(extras));
+            let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+            assert!(matches!(
+                config.validate(),
+                Err(ConfigError::InvalidEvmLimits(_))
+            ));
+        }
     }
 
     #[test]
-    fn test_deploy_allowlist_rejects_zero_address() {
+    fn test_native_currency() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.native_currency(), NativeCurrency::default());
+
         let extras = json!({
-            "deployAllowlist": [
-                "0x0000000000000000000000000000000000000000"
-            ]
+            "nativeCurrency": { "name": "Test", "symbol": "TST", "decimals": 6 }
         });
-
         let chainspec = create_test_chainspec_with_extras(Some(extras));
         let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.native_currency().decimals, 6);
+        assert_eq!(config.native_currency().symbol, "TST");
 
+        let extras = json!({
+            "nativeCurrency": { "name": "Test", "symbol": "TST", "decimals": 80 }
+        });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
         assert!(matches!(
             config.validate(),
-            Err(ConfigError::InvalidDeployAllowlist(_))
+            Err(ConfigError::InvalidNativeCurrency(_))
         ));
     }
 
     #[test]
-    fn test_deploy_allowlist_rejects_duplicates() {
-        let dup = address!("00000000000000000000000000000000000000aa");
+    fn test_staking() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.staking_settings(), None);
+
         let extras = json!({
-            "deployAllowlist": [dup, dup]
+            "stakingUnbondingPeriod": 100,
+            "stakingMinimumStake": "0x3e8",
+            "stakingActivationHeight": 20
         });
-
         let chainspec = create_test_chainspec_with_extras(Some(extras));
         let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.staking_settings(), Some((U256::from(1000), 100, 20)));
 
+        let extras = json!({ "stakingUnbondingPeriod": 0 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
         assert!(matches!(
             config.validate(),
-            Err(ConfigError::InvalidDeployAllowlist(_))
+            Err(ConfigError::InvalidStaking(_))
         ));
-    }
-
-    #[test]
-    fn test_deploy_allowlist_rejects_too_many_entries() {
-        let mut allowlist = Vec::new();
-        for i in 0..=MAX_DEPLOY_ALLOWLIST_LEN {
-            let mut bytes = [0u8; 20];
-            bytes[12..].copy_from_slice(&(i as u64 + 1).to_be_bytes());
-            let addr = Address::new(bytes);
-            allowlist.push(addr);
-        }
-        let config = EvolvePayloadBuilderConfig {
-            deploy_allowlist: allowlist,
-            deploy_allowlist_activation_height: Some(0),
-            ..Default::default()
-        };
 
+        let extras = json!({ "stakingMinimumStake": "0x1" });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
         assert!(matches!(
             config.validate(),
-            Err(ConfigError::InvalidDeployAllowlist(_))
+            Err(ConfigError::InvalidStaking(_))
         ));
     }
 
     #[test]
-    fn test_base_fee_sink_for_block() {
-        let sink = address!("0000000000000000000000000000000000000003");
-        let mut config = EvolvePayloadBuilderConfig {
-            base_fee_sink: Some(sink),
-            base_fee_redirect_activation_height: Some(5),
-            ..Default::default()
-        };
+    fn test_state_expiry() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.state_expiry_settings(), None);
 
-        assert_eq!(config.base_fee_sink_for_block(4), None);
-        assert_eq!(config.base_fee_sink_for_block(5), Some(sink));
-        assert_eq!(config.base_fee_sink_for_block(10), Some(sink));
+        let extras = json!({ "stateExpiryPeriod": 1000, "stateExpiryActivationHeight": 50 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.state_expiry_settings(), Some((50, 1000)));
 
-        config.base_fee_redirect_activation_height = None;
-        assert_eq!(config.base_fee_sink_for_block(0), Some(sink));
+        let extras = json!({ "stateExpiryPeriod": 0 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidStateExpiry(_))
+        ));
+
+        let extras = json!({ "stateExpiryActivationHeight": 5 });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidStateExpiry(_))
+        ));
     }
 
     #[test]
-    fn test_chainspec_evolve_config_deserialization() {
-        // Test direct deserialization of ChainspecEvolveConfig
-        let json_with_sink = json!({
-            "baseFeeSink": "0x0000000000000000000000000000000000000001",
-            "mintAdmin": "0x00000000000000000000000000000000000000aa"
-        });
+    fn test_system_tx_fee() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert!(config.system_fee_settings().is_none());
 
-        let config: ChainspecEvolveConfig = serde_json::from_value(json_with_sink).unwrap();
-        assert_eq!(
-            config.base_fee_sink,
-            Some(address!("0000000000000000000000000000000000000001"))
-        );
+        let sender = address!("00000000000000000000000000000000000000dd");
+        let account = address!("00000000000000000000000000000000000000ee");
+        let extras = json!({
+            "systemTxSenders": [sender],
+            "systemTxFeePolicy": "charged",
+            "systemFeeAccount": account,
+            "systemTxFeeActivationHeight": 8
+        });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
         assert_eq!(
-            config.mint_admin,
-            Some(address!("00000000000000000000000000000000000000aa"))
+            config.system_fee_settings(),
+            Some((vec![sender], SystemFeeMode::Charged(account), 8))
         );
 
-        let json_without_sink = json!({});
-        let config: ChainspecEvolveConfig = serde_json::from_value(json_without_sink).unwrap();
-        assert_eq!(config.base_fee_sink, None);
-        assert_eq!(config.mint_admin, None);
+        let extras = json!({ "systemTxSenders": [sender], "systemTxFeePolicy": "charged" });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidSystemTxFee(_))
+        ));
+
+        let extras = json!({ "systemTxSenders": [sender] });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidSystemTxFee(_))
+        ));
+
+        let extras = json!({ "systemTxFeePolicy": "rebated" });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        assert!(matches!(
+            EvolvePayloadBuilderConfig::from_chain_spec(&chainspec),
+            Err(ConfigError::InvalidExtras(_))
+        ));
     }
 
     #[test]
-    fn test_contract_size_limit_default() {
-        // Test default contract size limit (24KB per EIP-170)
+    fn test_sponsor_hash_migration() {
         let config = EvolvePayloadBuilderConfig::new();
-        assert_eq!(config.contract_size_limit, None);
-        assert_eq!(config.contract_size_limit_settings(), None);
-        // When no custom limit is set, use EIP-170 default for any block
+        assert!(config.sponsor_hash_migration().is_none());
+
+        let extras = json!({
+            "sponsorHashLegacyScheme": "feePayer",
+            "sponsorHashTransitionHeight": 100,
+            "sponsorHashActivationHeight": 200
+        });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
         assert_eq!(
-            config.contract_size_limit_for_block(0),
-            DEFAULT_CONTRACT_SIZE_LIMIT
+            config.sponsor_hash_migration(),
+            Some((SponsorHashScheme::FeePayer, 100, 200))
         );
-        assert_eq!(config.contract_size_limit_for_block(0), 24 * 1024);
-    }
 
-    #[test]
-    fn test_contract_size_limit_from_chainspec() {
-        // Test contract size limit from chainspec with activation height
         let extras = json!({
-            "contractSizeLimit": 131072,
-            "contractSizeLimitActivationHeight": 100
+            "sponsorHashLegacyScheme": "executor",
+            "sponsorHashTransitionHeight": 200,
+            "sponsorHashActivationHeight": 100
         });
-
         let chainspec = create_test_chainspec_with_extras(Some(extras));
         let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidSponsorHashMigration(_))
+        ));
 
-        assert_eq!(config.contract_size_limit, Some(131072));
-        assert_eq!(config.contract_size_limit_activation_height, Some(100));
-        assert_eq!(config.contract_size_limit_settings(), Some((131072, 100)));
-    }
+        let extras = json!({ "sponsorHashLegacyScheme": "feePayer" });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidSponsorHashMigration(_))
+        ));
 
-    #[test]
-    fn test_contract_size_limit_respects_activation_height() {
-        // Test that contract size limit respects activation height
         let extras = json!({
-            "contractSizeLimit": 131072,
-            "contractSizeLimitActivationHeight": 100
+            "sponsorHashLegacyScheme": "eip712",
+            "sponsorHashTransitionHeight": 100,
+            "sponsorHashActivationHeight": 200
         });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidSponsorHashMigration(_))
+        ));
 
+        let extras = json!({ "sponsorEip712ActivationHeight": 50 });
         let chainspec = create_test_chainspec_with_extras(Some(extras));
         let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.sponsor_eip712_activation_height, Some(50));
+    }
 
-        // Before activation: use EIP-170 default
-        assert_eq!(
-            config.contract_size_limit_for_block(0),
-            DEFAULT_CONTRACT_SIZE_LIMIT
-        );
+    #[test]
+    fn test_evm_version() {
+        let config = EvolvePayloadBuilderConfig::new();
+        assert_eq!(config.evm_version_schedule(), None);
+
+        let extras = json!({ "evmVersion": "shanghai" });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
         assert_eq!(
-            config.contract_size_limit_for_block(99),
-            DEFAULT_CONTRACT_SIZE_LIMIT
+            config.evm_version_schedule(),
+            Some(vec![(0, SpecId::SHANGHAI)])
         );
 
-        // At and after activation: use custom limit
-        assert_eq!(config.contract_size_limit_for_block(100), 131072);
-        assert_eq!(config.contract_size_limit_for_block(1000), 131072);
-    }
-
-    #[test]
-    fn test_contract_size_limit_defaults_activation_to_zero() {
-        // Test that activation height defaults to 0 when limit is set but height is not
         let extras = json!({
-            "contractSizeLimit": 131072
+            "evmVersion": [
+                { "version": "shanghai" },
+                { "version": "prague", "activationHeight": 100 }
+            ]
         });
-
         let chainspec = create_test_chainspec_with_extras(Some(extras));
         let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.evm_version_schedule(),
+            Some(vec![(0, SpecId::SHANGHAI), (100, SpecId::PRAGUE)])
+        );
 
-        assert_eq!(config.contract_size_limit, Some(131072));
-        assert_eq!(config.contract_size_limit_activation_height, None);
-        // Settings method defaults activation to 0
-        assert_eq!(config.contract_size_limit_settings(), Some((131072, 0)));
-        // Limit is active from block 0
-        assert_eq!(config.contract_size_limit_for_block(0), 131072);
+        let extras = json!({ "evmVersion": "byzantium" });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidEvmVersion(_))
+        ));
     }
 
     #[test]
-    fn test_contract_size_limit_not_set_uses_default() {
-        // Test that missing contractSizeLimit uses EIP-170 default
+    fn test_deposit_inbox_settings() {
+        let relayer = address!("00000000000000000000000000000000000000cc");
+        let extras = json!({ "depositInboxRelayer": relayer });
+        let chainspec = create_test_chainspec_with_extras(Some(extras));
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
+        assert_eq!(config.deposit_inbox_settings(), Some((relayer, 0)));
+
         let extras = json!({
-            "baseFeeSink": "0x0000000000000000000000000000000000000001"
+            "depositInboxRelayer": "0x0000000000000000000000000000000000000000",
+            "depositInboxActivationHeight": 5
         });
-
         let chainspec = create_test_chainspec_with_extras(Some(extras));
         let config = EvolvePayloadBuilderConfig::from_chain_spec(&chainspec).unwrap();
-
-        assert_eq!(config.contract_size_limit, None);
-        assert_eq!(config.contract_size_limit_settings(), None);
-        assert_eq!(
-            config.contract_size_limit_for_block(0),
-            DEFAULT_CONTRACT_SIZE_LIMIT
-        );
-        assert_eq!(
-            config.contract_size_limit_for_block(1000000),
-            DEFAULT_CONTRACT_SIZE_LIMIT
-        );
+        assert_eq!(config.deposit_inbox_settings(), None);
     }
 }
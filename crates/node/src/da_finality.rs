@@ -0,0 +1,90 @@
+use evolve_ev_reth::da::{DaBlockRef, DaFinalityTracker, DaStatus};
+use futures::StreamExt;
+use reth_primitives_traits::SealedHeader;
+use reth_provider::{CanonChainTracker, CanonStateSubscriptions, HeaderProvider};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Keeps the `safe` and `finalized` block tags pinned to the DA-backed blocks recorded in
+/// `tracker` by `evolve_setDaStatus`.
+///
+/// Tags are re-applied whenever the DA status changes and after every canonical head update,
+/// so values asserted in forkchoice updates never outlive the next block. Runs until the
+/// canonical state stream ends.
+pub async fn run_da_finality<P>(provider: P, tracker: Arc<DaFinalityTracker>)
+where
+    P: HeaderProvider + CanonChainTracker<Header = P::Header> + CanonStateSubscriptions,
+{
+    let mut da_status = tracker.subscribe();
+    let mut canon_state = provider.canonical_state_stream();
+    loop {
+        tokio::select! {
+            changed = da_status.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+            }
+            notification = canon_state.next() => {
+                if notification.is_none() {
+                    break;
+                }
+            }
+        }
+        let status = *da_status.borrow_and_update();
+        apply_da_status(&provider, status);
+    }
+}
+
+/// Sets the `safe` and `finalized` block tags from `status`.
+///
+/// Blocks that are not known yet, or whose hash does not match the local chain, are skipped.
+pub fn apply_da_status<P>(provider: &P, status: DaStatus)
+where
+    P: HeaderProvider + CanonChainTracker<Header = P::Header>,
+{
+    if let Some(header) = status
+        .safe
+        .and_then(|block| resolve(provider, block, "safe"))
+    {
+        provider.set_safe(header);
+    }
+    if let Some(header) = status
+        .finalized
+        .and_then(|block| resolve(provider, block, "finalized"))
+    {
+        provider.set_finalized(header);
+    }
+}
+
+fn resolve<P>(provider: &P, block: DaBlockRef, tag: &str) -> Option<SealedHeader<P::Header>>
+where
+    P: HeaderProvider,
+{
+    match provider.sealed_header(block.block_number) {
+        Ok(Some(header)) if header.hash() == block.block_hash => Some(header),
+        Ok(Some(header)) => {
+            warn!(
+                target: "ev-reth",
+                tag,
+                block_number = block.block_number,
+                expected = %block.block_hash,
+                local = %header.hash(),
+                "DA-reported block does not match the local chain"
+            );
+            None
+        }
+        Ok(None) => {
+            debug!(
+                target: "ev-reth",
+                tag,
+                block_number = block.block_number,
+                "DA-reported block not yet known"
+            );
+            None
+        }
+        Err(err) => {
+            warn!(target: "ev-reth", tag, %err, "failed to read DA-reported block");
+            None
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! `evnode_predictDeployAddress` RPC.
+//!
+//! Wallets and sponsors learn where a batch's leading CREATE lands before the executor signs
+//! it. The executor deploys whether or not a fee payer is attached, so the address follows the
+//! executor's nonce as for a plain contract creation.
+
+use alloy_primitives::Address;
+use async_trait::async_trait;
+use ev_primitives::EvNodeTransaction;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+
+/// Batch whose deployment address is requested.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDeployAddressRequest {
+    /// Executor who signs and submits the transaction.
+    pub from: Address,
+    /// Transaction whose first call is a CREATE.
+    pub transaction: EvNodeTransaction,
+}
+
+/// Deployment address prediction RPC API.
+#[rpc(server, namespace = "evnode")]
+pub trait EvNodeDeployAddressApi {
+    /// Returns the address of the contract deployed by the leading CREATE call of `request`'s
+    /// transaction, or `null` if it does not start with one or uses a nonce lane.
+    #[method(name = "predictDeployAddress")]
+    async fn predict_deploy_address(
+        &self,
+        request: RpcDeployAddressRequest,
+    ) -> RpcResult<Option<Address>>;
+}
+
+/// Implementation of the deployment address prediction RPC.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct EvNodeDeployAddressApiImpl;
+
+impl EvNodeDeployAddressApiImpl {
+    /// Creates a new instance.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl EvNodeDeployAddressApiServer for EvNodeDeployAddressApiImpl {
+    async fn predict_deploy_address(
+        &self,
+        request: RpcDeployAddressRequest,
+    ) -> RpcResult<Option<Address>> {
+        Ok(request.transaction.deploy_address(request.from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, TxKind, U256};
+    use ev_primitives::EvNodeTransactionBuilder;
+
+    #[tokio::test]
+    async fn predicts_leading_create() {
+        let from = Address::repeat_byte(0xe0);
+        let api = EvNodeDeployAddressApiImpl::new();
+
+        let transaction = EvNodeTransactionBuilder::new(1)
+            .nonce(7)
+            .add_call(TxKind::Create, U256::ZERO, Bytes::from_static(&[0x00]))
+            .build();
+        let predicted = api
+            .predict_deploy_address(RpcDeployAddressRequest { from, transaction })
+            .await
+            .unwrap();
+        assert_eq!(predicted, Some(from.create(7)));
+
+        let transaction = EvNodeTransactionBuilder::new(1)
+            .add_call(TxKind::Call(from), U256::ZERO, Bytes::new())
+            .build();
+        let predicted = api
+            .predict_deploy_address(RpcDeployAddressRequest { from, transaction })
+            .await
+            .unwrap();
+        assert_eq!(predicted, None);
+    }
+}
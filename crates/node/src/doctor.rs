@@ -0,0 +1,490 @@
+//! `ev-reth doctor`: pre-flight checks of the chainspec against a node data directory.
+//!
+//! The command opens the database read-only, so it can run next to a stopped node before it is
+//! (re)started in production. Every check reports pass, warn or fail; the command exits with
+//! an error if any check fails.
+
+use crate::{
+    chainspec::{unread_eip1559_extras, EvolveChainSpecParser},
+    config::EvolvePayloadBuilderConfig,
+    migrations::Migrator,
+    report_command::{CommandReport, ReportCommand},
+};
+use alloy_primitives::{Address, Bytes};
+use clap::Parser;
+use ev_precompiles::{
+    channel::SYSTEM_CHANNEL_PRECOMPILE_ADDR, expiry::STATE_EXPIRY_PRECOMPILE_ADDR,
+    features::FEATURES_PRECOMPILE_ADDR, inbox::INBOX_PRECOMPILE_ADDR, mint::MINT_PRECOMPILE_ADDR,
+    nonce_manager::NONCE_MANAGER_PRECOMPILE_ADDR, p256_verify::P256_VERIFY_PRECOMPILE_ADDR,
+    staking::STAKING_PRECOMPILE_ADDR,
+};
+use evolve_ev_reth::{unread_consensus_extras, EvolveConsensus};
+use reth_chainspec::ChainSpec;
+use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_ethereum::node::EthereumNode;
+use reth_provider::{BlockNumReader, StateProvider, StateProviderFactory};
+use reth_revm::revm::precompile::Precompiles;
+use std::{fmt, path::Path};
+
+/// Code the Evolve precompiles install at their address when first used.
+const PRECOMPILE_MARKER: [u8; 1] = [0xFE];
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// Nothing to fix.
+    Pass,
+    /// Worth a look, but does not prevent the node from starting.
+    Warn,
+    /// The node should not be started until this is fixed.
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+        })
+    }
+}
+
+/// A named check and its outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorCheck {
+    /// What was checked.
+    pub name: String,
+    /// Outcome of the check.
+    pub status: CheckStatus,
+    /// Explanation of the outcome.
+    pub detail: String,
+}
+
+impl DoctorCheck {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Results of all checks run by `ev-reth doctor`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DoctorReport {
+    /// Checks in the order they ran.
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.status == status)
+            .count()
+    }
+}
+
+impl CommandReport for DoctorReport {
+    /// Returns true if no check failed.
+    fn is_healthy(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != CheckStatus::Fail)
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(f, "[{}] {}: {}", check.status, check.name, check.detail)?;
+        }
+        write!(
+            f,
+            "{} passed, {} warnings, {} failed",
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail)
+        )
+    }
+}
+
+/// Parses the `evolve` extras strictly: every reader must accept them, the payload builder
+/// configuration must validate, and every key must be read by at least one reader.
+///
+/// Returns the check along with the parsed configuration, if it parsed.
+pub fn check_extras(chain_spec: &ChainSpec) -> (DoctorCheck, Option<EvolvePayloadBuilderConfig>) {
+    const NAME: &str = "chainspec extras";
+    let fail = |detail: String| (DoctorCheck::new(NAME, CheckStatus::Fail, detail), None);
+
+    let config = match EvolvePayloadBuilderConfig::from_chain_spec(chain_spec) {
+        Ok(config) => config,
+        Err(err) => return fail(err.to_string()),
+    };
+    if let Err(err) = config.validate() {
+        return fail(err.to_string());
+    }
+    if let Err(err) = EvolveConsensus::from_chain_spec(chain_spec.clone().into()) {
+        return fail(format!("invalid consensus settings: {err}"));
+    }
+
+    let Some(extras) = chain_spec.genesis.config.extra_fields.get("evolve") else {
+        return (
+            DoctorCheck::new(NAME, CheckStatus::Pass, "no evolve extras configured"),
+            Some(config),
+        );
+    };
+    let unknown = match unknown_extras(extras) {
+        Ok(unknown) => unknown,
+        Err(err) => return fail(err.to_string()),
+    };
+    if !unknown.is_empty() {
+        return fail(format!("unknown keys: {}", unknown.join(", ")));
+    }
+    (
+        DoctorCheck::new(NAME, CheckStatus::Pass, "evolve extras parse and validate"),
+        Some(config),
+    )
+}
+
+/// Returns the extras keys no reader uses, which are most likely typos.
+fn unknown_extras(extras: &serde_json::Value) -> Result<Vec<String>, serde_json::Error> {
+    let config = EvolvePayloadBuilderConfig::unread_extras(extras.clone())?;
+    let consensus = unread_consensus_extras(extras.clone())?;
+    let eip1559 = unread_eip1559_extras(extras.clone())?;
+    Ok(config
+        .into_iter()
+        .filter(|key| consensus.contains(key) && eip1559.contains(key))
+        .collect())
+}
+
+/// Checks that the base fee sink is not a precompile, where credited fees would be stranded.
+pub fn check_base_fee_sink(config: &EvolvePayloadBuilderConfig) -> Option<DoctorCheck> {
+    const NAME: &str = "base fee sink";
+    let (sink, _) = config.base_fee_redirect_settings()?;
+    let check = if evolve_precompiles(config)
+        .iter()
+        .any(|(_, addr, _)| *addr == sink)
+        || (config.features_precompile_activation_height.is_some()
+            && sink == FEATURES_PRECOMPILE_ADDR)
+        || (config.nonce_lane_activation_height.is_some() && sink == NONCE_MANAGER_PRECOMPILE_ADDR)
+        || (config.p256_verify_precompile_activation_height.is_some()
+            && sink == P256_VERIFY_PRECOMPILE_ADDR)
+        || Precompiles::latest().contains(&sink)
+    {
+        DoctorCheck::new(NAME, CheckStatus::Fail, format!("{sink} is a precompile"))
+    } else {
+        DoctorCheck::new(NAME, CheckStatus::Pass, format!("{sink}"))
+    };
+    Some(check)
+}
+
+/// Checks the on-disk Evolve schema version against this binary.
+pub fn check_schema(data_dir: &Path) -> DoctorCheck {
+    const NAME: &str = "database schema";
+    match Migrator::new(data_dir).status() {
+        Err(err) => DoctorCheck::new(NAME, CheckStatus::Fail, err.to_string()),
+        Ok(status) if status.on_disk > status.latest => DoctorCheck::new(
+            NAME,
+            CheckStatus::Fail,
+            format!(
+                "version {} on disk is newer than the {} supported by this binary",
+                status.on_disk, status.latest
+            ),
+        ),
+        Ok(status) if !status.pending.is_empty() => DoctorCheck::new(
+            NAME,
+            CheckStatus::Warn,
+            format!(
+                "version {} on disk; {} migration(s) will run at startup",
+                status.on_disk,
+                status.pending.len()
+            ),
+        ),
+        Ok(status) => DoctorCheck::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("version {}", status.on_disk),
+        ),
+    }
+}
+
+/// Lists the configured activation heights, split by whether `head` has reached them.
+pub fn check_activation_heights(config: &EvolvePayloadBuilderConfig, head: u64) -> DoctorCheck {
+    let heights = [
+        (
+            "base fee redirect",
+            config.base_fee_redirect_settings().map(|(_, h)| h),
+        ),
+        (
+            "contract size limit",
+            config.contract_size_limit_settings().map(|(_, h)| h),
+        ),
+        (
+            "deploy allowlist",
+            config.deploy_allowlist_settings().map(|(_, h)| h),
+        ),
+        (
+            "sponsor allowlist",
+            config.sponsor_allowlist_settings().map(|(_, h)| h),
+        ),
+        ("system fee", config.system_fee_settings().map(|(.., h)| h)),
+        (
+            "minimum gas price",
+            config.min_gas_price_settings().map(|(.., h)| h),
+        ),
+        (
+            "gas limit bounds",
+            config.gas_limit_bounds_settings().map(|(.., h)| h),
+        ),
+        (
+            "per-transaction gas share",
+            config.max_tx_gas_settings().map(|(_, h)| h),
+        ),
+        (
+            "EvNode batch call limit",
+            config
+                .max_batch_calls()
+                .map(|limit| limit.activation_height()),
+        ),
+        (
+            "precompile warm-up",
+            config.precompile_warmup_settings().map(|(_, h)| h),
+        ),
+        (
+            "block commitment",
+            config.block_commitment_settings().map(|(_, h)| h),
+        ),
+        ("EVM limits", config.evm_limits_settings().map(|(.., h)| h)),
+        (
+            "per-call gas limits",
+            config.call_gas_limit_activation_height,
+        ),
+        ("best-effort batches", config.best_effort_activation_height),
+        ("session keys", config.session_key_activation_height),
+        ("validity windows", config.validity_window_activation_height),
+        ("nonce lanes", config.nonce_lane_activation_height),
+        (
+            "P256 executor signatures",
+            config.p256_signature_activation_height,
+        ),
+        (
+            "EvNode type alias",
+            config
+                .evnode_tx_type_alias()
+                .map(|alias| alias.activation_height()),
+        ),
+        (
+            "feature bitmap precompile",
+            config.features_precompile_activation_height,
+        ),
+        (
+            "P256 verification precompile",
+            config.p256_verify_precompile_activation_height,
+        ),
+        (
+            "EIP-712 sponsorships",
+            config.sponsor_eip712_activation_height,
+        ),
+    ]
+    .into_iter()
+    .chain(
+        evolve_precompiles(config)
+            .into_iter()
+            .map(|(name, _, h)| (name, Some(h))),
+    )
+    .filter_map(|(name, height)| Some((name, height?)));
+
+    let (active, pending): (Vec<_>, Vec<_>) = heights.partition(|(_, height)| *height <= head);
+    let list = |features: &[(&str, u64)]| {
+        features
+            .iter()
+            .map(|(name, height)| format!("{name} at {height}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let detail = match (active.is_empty(), pending.is_empty()) {
+        (true, true) => format!("no activation heights configured; head is {head}"),
+        (false, true) => format!("head {head}; active: {}", list(&active)),
+        (true, false) => format!("head {head}; pending: {}", list(&pending)),
+        (false, false) => format!(
+            "head {head}; active: {}; pending: {}",
+            list(&active),
+            list(&pending)
+        ),
+    };
+    DoctorCheck::new("activation heights", CheckStatus::Pass, detail)
+}
+
+/// Checks the account of a stateful precompile against its activation height.
+///
+/// The precompiles install [`PRECOMPILE_MARKER`] code when first called, so a marked account
+/// below the activation height means the height was moved past blocks that already used the
+/// precompile, and any other code means a contract occupies the address.
+pub fn check_precompile_account(
+    name: &str,
+    address: Address,
+    activation_height: u64,
+    head: u64,
+    code: Option<&Bytes>,
+) -> DoctorCheck {
+    let name = format!("{name} precompile");
+    let marked = code.is_some_and(|code| code.as_ref() == PRECOMPILE_MARKER);
+    match code {
+        Some(code) if !code.is_empty() && !marked => DoctorCheck::new(
+            name,
+            CheckStatus::Fail,
+            format!("{address} holds {} bytes of contract code", code.len()),
+        ),
+        _ if marked && head < activation_height => DoctorCheck::new(
+            name,
+            CheckStatus::Fail,
+            format!(
+                "{address} was already used, but the precompile only activates at \
+                 {activation_height} (head {head})"
+            ),
+        ),
+        _ if marked => DoctorCheck::new(name, CheckStatus::Pass, format!("{address} is marked")),
+        _ if head >= activation_height => DoctorCheck::new(
+            name,
+            CheckStatus::Warn,
+            format!("{address} is active but not yet marked; it is marked on first use"),
+        ),
+        _ => DoctorCheck::new(
+            name,
+            CheckStatus::Pass,
+            format!("activates at {activation_height}"),
+        ),
+    }
+}
+
+/// Stateful precompiles enabled by `config`, with their addresses and activation heights.
+fn evolve_precompiles(config: &EvolvePayloadBuilderConfig) -> Vec<(&'static str, Address, u64)> {
+    [
+        config
+            .mint_precompile_settings()
+            .map(|(_, h)| ("mint", MINT_PRECOMPILE_ADDR, h)),
+        config
+            .deposit_inbox_settings()
+            .map(|(_, h)| ("deposit inbox", INBOX_PRECOMPILE_ADDR, h)),
+        config
+            .system_channel_settings()
+            .map(|(_, h)| ("system channel", SYSTEM_CHANNEL_PRECOMPILE_ADDR, h)),
+        config
+            .state_expiry_settings()
+            .map(|(h, _)| ("state expiry", STATE_EXPIRY_PRECOMPILE_ADDR, h)),
+        config
+            .staking_settings()
+            .map(|(.., h)| ("native staking", STAKING_PRECOMPILE_ADDR, h)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// `ev-reth doctor`: checks the chainspec and data directory before the node is started.
+#[derive(Debug, Parser)]
+#[command(name = "ev-reth doctor")]
+pub struct DoctorCommand {
+    /// Chain and data directory to check.
+    #[command(flatten)]
+    pub env: EnvironmentArgs<EvolveChainSpecParser>,
+}
+
+impl ReportCommand for DoctorCommand {
+    const NAME: &'static str = "doctor";
+
+    type Report = DoctorReport;
+
+    /// Runs every check against a read-only view of the database.
+    fn run(self) -> eyre::Result<DoctorReport> {
+        let mut report = DoctorReport::default();
+        let chain_spec = self.env.chain.clone();
+
+        let (extras, config) = check_extras(&chain_spec);
+        report.checks.push(extras);
+
+        let Environment {
+            provider_factory,
+            data_dir,
+            ..
+        } = self.env.init::<EthereumNode>(AccessRights::RO)?;
+        report.checks.push(check_schema(data_dir.data_dir()));
+
+        let Some(config) = config else {
+            return Ok(report);
+        };
+        report.checks.extend(check_base_fee_sink(&config));
+
+        let head = provider_factory.best_block_number()?;
+        report.checks.push(check_activation_heights(&config, head));
+
+        let state = provider_factory.latest()?;
+        for (name, address, activation) in evolve_precompiles(&config) {
+            let code = state
+                .account_code(&address)?
+                .map(|code| code.original_bytes());
+            report.checks.push(check_precompile_account(
+                name,
+                address,
+                activation,
+                head,
+                code.as_ref(),
+            ));
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_genesis::Genesis;
+    use reth_chainspec::ChainSpecBuilder;
+    use serde_json::json;
+
+    fn chain_spec_with_extras(extras: serde_json::Value) -> ChainSpec {
+        let mut genesis = Genesis::default();
+        genesis
+            .config
+            .extra_fields
+            .insert("evolve".to_string(), extras);
+        ChainSpecBuilder::mainnet().genesis(genesis).build()
+    }
+
+    #[test]
+    fn extras_check_rejects_unknown_keys() {
+        let sink = Address::repeat_byte(0xfe);
+        let spec = chain_spec_with_extras(json!({
+            "baseFeeSink": sink,
+            "minBlockTime": 1,
+            "baseFeeMaxChangeDenominator": 8
+        }));
+        let (check, config) = check_extras(&spec);
+        assert_eq!(check.status, CheckStatus::Pass, "{}", check.detail);
+        assert!(config.is_some());
+
+        let spec = chain_spec_with_extras(json!({ "baseFeeSinc": sink }));
+        let (check, _) = check_extras(&spec);
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains("baseFeeSinc"), "{}", check.detail);
+    }
+
+    #[test]
+    fn precompile_account_matches_activation_height() {
+        let addr = MINT_PRECOMPILE_ADDR;
+        let marker = Bytes::from_static(&PRECOMPILE_MARKER);
+        let contract = Bytes::from_static(&[0x60, 0x00]);
+
+        let status = |activation, head, code: Option<&Bytes>| {
+            check_precompile_account("mint", addr, activation, head, code).status
+        };
+        assert_eq!(status(10, 20, Some(&marker)), CheckStatus::Pass);
+        assert_eq!(status(10, 5, None), CheckStatus::Pass);
+        assert_eq!(status(10, 20, None), CheckStatus::Warn);
+        assert_eq!(status(30, 20, Some(&marker)), CheckStatus::Fail);
+        assert_eq!(status(10, 20, Some(&contract)), CheckStatus::Fail);
+    }
+}
@@ -0,0 +1,169 @@
+//! Engine API endpoint with compressed request and response bodies.
+//!
+//! `engine_getPayload` and `engine_newPayload` carry whole blocks as JSON, and at sub-second
+//! block times serializing and copying multi-megabyte bodies dominates the round trip between
+//! ev-node and ev-reth. This endpoint serves the same methods as the authenticated Engine API,
+//! behind the same JWT, but accepts `zstd` or `gzip` encoded request bodies and compresses
+//! responses for clients that send a matching `Accept-Encoding`. Clients that send neither get
+//! plain JSON, so ev-node can switch over by changing the engine URL alone.
+
+use alloy_rpc_types_engine::JwtSecret;
+use axum::{
+    extract::{DefaultBodyLimit, State},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use jsonrpsee::RpcModule;
+use std::{net::SocketAddr, sync::Arc};
+use tower_http::{
+    compression::{predicate::SizeAbove, CompressionLayer},
+    decompression::RequestDecompressionLayer,
+};
+use tracing::info;
+
+/// Default listen address of the compressed Engine API endpoint.
+pub const DEFAULT_ENGINE_COMPRESSION_ADDR: &str = "127.0.0.1:8552";
+
+/// Largest request body accepted, after decompression.
+pub const MAX_ENGINE_REQUEST_SIZE: usize = 128 * 1024 * 1024;
+
+/// Responses smaller than this many bytes are sent uncompressed, as compressing them costs
+/// more than it saves.
+const MIN_COMPRESSED_RESPONSE_SIZE: u16 = 1024;
+
+#[derive(Debug)]
+struct EngineState {
+    secret: JwtSecret,
+    engine: RpcModule<()>,
+}
+
+/// Serves the methods of `engine` at `addr` to callers holding a JWT signed with `secret`,
+/// until the listener fails.
+pub async fn serve_compressed_engine(
+    addr: SocketAddr,
+    secret: JwtSecret,
+    engine: RpcModule<()>,
+) -> eyre::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!(target: "ev-reth", %addr, "Compressed Engine API endpoint started");
+    axum::serve(listener, engine_router(secret, engine)).await?;
+    Ok(())
+}
+
+fn engine_router(secret: JwtSecret, engine: RpcModule<()>) -> axum::Router {
+    axum::Router::new()
+        .route("/", axum::routing::post(engine_handler))
+        .with_state(Arc::new(EngineState { secret, engine }))
+        .layer(DefaultBodyLimit::max(MAX_ENGINE_REQUEST_SIZE))
+        .layer(RequestDecompressionLayer::new())
+        .layer(CompressionLayer::new().compress_when(SizeAbove::new(MIN_COMPRESSED_RESPONSE_SIZE)))
+}
+
+/// Handles a single JSON-RPC call; the Engine API does not use batches.
+async fn engine_handler(
+    State(state): State<Arc<EngineState>>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    if let Err(err) = authorize(&state.secret, &headers) {
+        return (StatusCode::UNAUTHORIZED, err).into_response();
+    }
+    match state.engine.raw_json_request(&body, 1).await {
+        Ok((response, _)) => (
+            [(CONTENT_TYPE, "application/json")],
+            response.get().to_owned(),
+        )
+            .into_response(),
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
+    }
+}
+
+/// Checks the bearer token of a request the way the authenticated Engine API does.
+fn authorize(secret: &JwtSecret, headers: &HeaderMap) -> Result<(), String> {
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| "missing bearer token".to_string())?;
+    secret.validate(token).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::Claims;
+    use axum::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
+    use serde_json::{json, Value};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    async fn spawn_endpoint(secret: JwtSecret) -> SocketAddr {
+        let mut engine = RpcModule::new(());
+        engine
+            .register_method("engine_getPayloadV4", |_, _, _| "ab".repeat(4096))
+            .unwrap();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, engine_router(secret, engine))
+                .await
+                .unwrap();
+        });
+        addr
+    }
+
+    fn token(secret: &JwtSecret) -> String {
+        let iat = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        secret.encode(&Claims { iat, exp: None }).unwrap()
+    }
+
+    #[tokio::test]
+    async fn compresses_responses_for_authenticated_callers() {
+        let secret = JwtSecret::random();
+        let url = format!("http://{}/", spawn_endpoint(secret).await);
+        let request =
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "engine_getPayloadV4", "params": [] });
+        let client = reqwest::Client::new();
+
+        let unauthenticated = client.post(&url).json(&request).send().await.unwrap();
+        assert_eq!(unauthenticated.status(), StatusCode::UNAUTHORIZED);
+        let wrong_secret = client
+            .post(&url)
+            .bearer_auth(token(&JwtSecret::random()))
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(wrong_secret.status(), StatusCode::UNAUTHORIZED);
+
+        // Without `Accept-Encoding` the response is plain JSON.
+        let plain: Value = client
+            .post(&url)
+            .bearer_auth(token(&secret))
+            .json(&request)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(plain["result"], "ab".repeat(4096));
+
+        let compressed = client
+            .post(&url)
+            .bearer_auth(token(&secret))
+            .header(ACCEPT_ENCODING, "zstd")
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(compressed.status(), StatusCode::OK);
+        assert_eq!(compressed.headers()[CONTENT_ENCODING], "zstd");
+        assert!(compressed.bytes().await.unwrap().len() < 8192);
+    }
+}
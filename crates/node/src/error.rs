@@ -1,7 +1,32 @@
-use evolve_ev_reth::PayloadAttributesError;
+//! Typed payload errors and how they are surfaced through the Engine API.
+//!
+//! reth answers `engine_getPayload` with `Unknown payload` whenever a payload job failed, so
+//! ev-node cannot tell a node that is shutting down from attributes that will never build.
+//! Failures of payload jobs are recorded per payload id as [`EngineErrorData`], and
+//! [`with_engine_error_data`] attaches the record to the `data` field of the Engine API error.
+
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::PayloadId;
+use evolve_ev_reth::{timestamp::TimestampError, PayloadAttributesError};
+use jsonrpsee::{
+    core::{server::MethodsError, traits::ToRpcParams},
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject, ErrorObjectOwned, Params},
+    RpcModule,
+};
+use jsonrpsee_core::RpcResult;
+use reth_payload_builder::PayloadBuilderError;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
 use thiserror::Error;
 
-/// Custom error type used in payload attributes validation.
+/// Maximum number of payload errors retained before the oldest are evicted.
+pub const DEFAULT_MAX_ENGINE_ERRORS: usize = 64;
+
+/// Custom error type used in payload attributes validation and payload building.
 #[derive(Debug, Error)]
 pub enum EvolveEngineError {
     /// Provided transaction bytes failed to decode.
@@ -13,4 +38,312 @@ pub enum EvolveEngineError {
     /// Underlying evolve payload attribute validation failed.
     #[error("Evolve payload attributes error: {0}")]
     PayloadAttributes(#[from] PayloadAttributesError),
+    /// The payload timestamp was refused by the timestamp policy.
+    #[error(transparent)]
+    InvalidTimestamp(#[from] TimestampError),
+    /// The node is draining for shutdown and does not start new payload jobs.
+    #[error("Node is shutting down")]
+    ShuttingDown,
+    /// The parent block is not (yet) known to the node.
+    #[error("Parent block {0} is not available")]
+    ParentUnavailable(B256),
+    /// The payload job was dropped because its payload was not fetched in time.
+    #[error("Payload job expired before its payload was fetched")]
+    PayloadExpired,
+    /// Payload building failed for any other reason.
+    #[error("Payload build failed: {0}")]
+    Build(PayloadBuilderError),
+}
+
+impl EvolveEngineError {
+    /// Returns the stable code of the error.
+    ///
+    /// `1xxx` codes reject the request itself, `2xxx` codes are transient conditions of the
+    /// node and `3xxx` codes are internal failures.
+    pub const fn code(&self) -> u16 {
+        match self {
+            Self::InvalidTransactionData(_) => 1001,
+            Self::GasLimitExceeded => 1002,
+            Self::PayloadAttributes(_) => 1003,
+            Self::InvalidTimestamp(_) => 1004,
+            Self::ShuttingDown => 2001,
+            Self::ParentUnavailable(_) => 2002,
+            Self::PayloadExpired => 2003,
+            Self::Build(_) => 3001,
+        }
+    }
+
+    /// Returns whether the failure is transient or fatal.
+    pub const fn class(&self) -> EngineErrorClass {
+        match self {
+            Self::ShuttingDown | Self::ParentUnavailable(_) | Self::PayloadExpired => {
+                EngineErrorClass::Transient
+            }
+            Self::InvalidTransactionData(_)
+            | Self::GasLimitExceeded
+            | Self::PayloadAttributes(_)
+            | Self::InvalidTimestamp(_)
+            | Self::Build(_) => EngineErrorClass::Fatal,
+        }
+    }
+
+    /// Returns true if sending the same request again may succeed.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self.class(), EngineErrorClass::Transient)
+    }
+
+    /// Returns the error as surfaced in Engine API error data.
+    pub fn data(&self) -> EngineErrorData {
+        EngineErrorData {
+            code: self.code(),
+            class: self.class(),
+            retryable: self.is_retryable(),
+            message: self.to_string(),
+        }
+    }
+}
+
+impl From<PayloadBuilderError> for EvolveEngineError {
+    fn from(err: PayloadBuilderError) -> Self {
+        match err {
+            PayloadBuilderError::MissingParentHeader(hash)
+            | PayloadBuilderError::MissingParentBlock(hash) => Self::ParentUnavailable(hash),
+            PayloadBuilderError::Other(err) => match err.downcast::<Self>() {
+                Ok(err) => *err,
+                Err(err) => Self::Build(PayloadBuilderError::Other(err)),
+            },
+            err => Self::Build(err),
+        }
+    }
+}
+
+/// Whether retrying a failed Engine API call can succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EngineErrorClass {
+    /// The node cannot serve the call right now; retrying later may succeed.
+    Transient,
+    /// The call fails the same way however often it is retried.
+    Fatal,
+}
+
+/// Structured error attached to the `data` field of Engine API errors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineErrorData {
+    /// Stable error code, see [`EvolveEngineError::code`].
+    pub code: u16,
+    /// Whether the failure is transient or fatal.
+    pub class: EngineErrorClass,
+    /// Whether sending the same request again may succeed.
+    pub retryable: bool,
+    /// Human-readable description of the failure.
+    pub message: String,
+}
+
+/// Bounded store of the last error of recent payload jobs, written by the payload builder and
+/// read by the Engine API wrapper installed with [`with_engine_error_data`].
+#[derive(Debug)]
+pub struct EngineErrorStore {
+    errors: Mutex<VecDeque<(PayloadId, EngineErrorData)>>,
+    capacity: usize,
+}
+
+impl EngineErrorStore {
+    /// Creates a store that retains at most `capacity` errors.
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            errors: Mutex::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Records the error of a payload job, replacing any earlier error for the same payload
+    /// and evicting the oldest entry once the store is full.
+    pub fn insert(&self, payload_id: PayloadId, data: EngineErrorData) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut errors = self.errors.lock().unwrap_or_else(|e| e.into_inner());
+        errors.retain(|(id, _)| *id != payload_id);
+        while errors.len() >= self.capacity {
+            errors.pop_front();
+        }
+        errors.push_back((payload_id, data));
+    }
+
+    /// Returns the error recorded for `payload_id`, if still retained.
+    pub fn get(&self, payload_id: &PayloadId) -> Option<EngineErrorData> {
+        let errors = self.errors.lock().unwrap_or_else(|e| e.into_inner());
+        errors
+            .iter()
+            .find(|(id, _)| id == payload_id)
+            .map(|(_, data)| data.clone())
+    }
+}
+
+impl Default for EngineErrorStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ENGINE_ERRORS)
+    }
+}
+
+/// Records `err` in `errors` as the error of the payload job `payload_id` and converts it for
+/// reth.
+pub fn payload_job_error(
+    errors: &EngineErrorStore,
+    payload_id: PayloadId,
+    err: impl Into<EvolveEngineError>,
+) -> PayloadBuilderError {
+    let err = err.into();
+    errors.insert(payload_id, err.data());
+    PayloadBuilderError::other(err)
+}
+
+/// Positional parameters forwarded verbatim to the wrapped method.
+struct RawParams(Option<Box<RawValue>>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+/// Returns the `engine_getPayloadV*` methods of `engine`, wrapped so that their errors carry
+/// the [`EngineErrorData`] recorded in `errors` for the requested payload.
+///
+/// Other errors and all results are passed through unchanged.
+pub fn with_engine_error_data(
+    engine: RpcModule<()>,
+    errors: Arc<EngineErrorStore>,
+) -> RpcModule<()> {
+    let mut module = RpcModule::new(());
+    let methods: Vec<&'static str> = engine
+        .method_names()
+        .filter(|name| name.starts_with("engine_getPayloadV"))
+        .collect();
+    for method in methods {
+        let engine = engine.clone();
+        let errors = errors.clone();
+        module
+            .register_async_method(method, move |params, _, _| {
+                let engine = engine.clone();
+                let errors = errors.clone();
+                async move { call_with_error_data(&engine, &errors, method, params).await }
+            })
+            .expect("wrapped method names are unique");
+    }
+    module
+}
+
+/// Forwards a call to `method` of `engine`, attaching the error recorded in `errors` for the
+/// payload requested in `params` to a failed call.
+async fn call_with_error_data(
+    engine: &RpcModule<()>,
+    errors: &EngineErrorStore,
+    method: &'static str,
+    params: Params<'static>,
+) -> RpcResult<serde_json::Value> {
+    let payload_id = params.one::<PayloadId>().ok();
+    let raw = params
+        .as_str()
+        .map(|params| RawValue::from_string(params.to_owned()))
+        .transpose()
+        .map_err(internal_error)?;
+    engine
+        .call(method, RawParams(raw))
+        .await
+        .map_err(|err| match err {
+            MethodsError::JsonRpc(err) => attach_error_data(err, errors, payload_id.as_ref()),
+            err => internal_error(err),
+        })
+}
+
+fn internal_error(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+/// Replaces the data of `err` with the error recorded in `errors` for `payload_id`, if any.
+fn attach_error_data(
+    err: ErrorObjectOwned,
+    errors: &EngineErrorStore,
+    payload_id: Option<&PayloadId>,
+) -> ErrorObjectOwned {
+    match payload_id.and_then(|id| errors.get(id)) {
+        Some(data) => ErrorObject::owned(err.code(), err.message().to_string(), Some(data)),
+        None => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use evolve_ev_reth::TimestampPolicy;
+
+    #[test]
+    fn errors_are_classified_by_retryability() {
+        let shutting_down = EvolveEngineError::ShuttingDown.data();
+        assert_eq!(shutting_down.code, 2001);
+        assert_eq!(shutting_down.class, EngineErrorClass::Transient);
+        assert!(shutting_down.retryable);
+
+        let timestamp = EvolveEngineError::from(TimestampError {
+            policy: TimestampPolicy::Reject,
+            requested: 10,
+            parent_timestamp: 10,
+        });
+        assert_eq!(timestamp.code(), 1004);
+        assert!(!timestamp.is_retryable());
+
+        let parent = EvolveEngineError::from(PayloadBuilderError::MissingParentHeader(B256::ZERO));
+        assert!(matches!(parent, EvolveEngineError::ParentUnavailable(_)));
+        assert!(parent.is_retryable());
+
+        // Typed errors survive the round trip through reth's boxed error.
+        let boxed = PayloadBuilderError::other(EvolveEngineError::PayloadExpired);
+        assert!(matches!(
+            EvolveEngineError::from(boxed),
+            EvolveEngineError::PayloadExpired
+        ));
+    }
+
+    #[test]
+    fn error_data_serializes_for_engine_api() {
+        let data = EvolveEngineError::ShuttingDown.data();
+        assert_eq!(
+            serde_json::to_value(&data).unwrap(),
+            serde_json::json!({
+                "code": 2001,
+                "class": "transient",
+                "retryable": true,
+                "message": "Node is shutting down",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn get_payload_errors_carry_recorded_data() {
+        let mut engine = RpcModule::new(());
+        engine
+            .register_method("engine_getPayloadV4", |_, _, _| {
+                Err::<(), _>(ErrorObject::owned(-38001, "Unknown payload", None::<()>))
+            })
+            .unwrap();
+        let errors = Arc::new(EngineErrorStore::default());
+        let wrapped = with_engine_error_data(engine, errors.clone());
+
+        let payload_id = PayloadId::new([7; 8]);
+        errors.insert(payload_id, EvolveEngineError::ShuttingDown.data());
+        let params = serde_json::value::to_raw_value(&[payload_id]).unwrap();
+        let err = wrapped
+            .call::<_, serde_json::Value>("engine_getPayloadV4", RawParams(Some(params)))
+            .await
+            .expect_err("job failed");
+        let MethodsError::JsonRpc(err) = err else {
+            panic!("expected a JSON-RPC error");
+        };
+        assert_eq!(err.code(), -38001);
+        let data: EngineErrorData = serde_json::from_str(err.data().unwrap().get()).unwrap();
+        assert!(data.retryable);
+    }
 }
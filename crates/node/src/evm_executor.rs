@@ -1,5 +1,5 @@
 use core::cmp::min;
-use std::{borrow::Cow, boxed::Box, vec::Vec};
+use std::{borrow::Cow, boxed::Box, sync::Arc, vec::Vec};
 
 use alloy_consensus::{Transaction, TransactionEnvelope, TxReceipt};
 use alloy_eips::{eip7685::Requests, Encodable2718};
@@ -8,7 +8,7 @@ use alloy_evm::{
         state_changes::{balance_increment_state, post_block_balance_increments},
         BlockExecutionError, BlockExecutionResult, BlockExecutor, BlockExecutorFactory,
         BlockValidationError, ExecutableTx, GasOutput, OnStateHook, StateChangePostBlockSource,
-        StateChangeSource, SystemCaller,
+        StateChangePreBlockSource, StateChangeSource, SystemCaller,
     },
     eth::{
         dao_fork, eip6110,
@@ -26,6 +26,9 @@ use reth_revm::{
     database_interface::DatabaseCommitExt,
     revm::{context_interface::result::ResultAndState, DatabaseCommit, Inspector},
 };
+use tracing::info;
+
+use crate::state_patch::{patch_for_block, StatePatch, StatePatchReceiptStore};
 
 /// Execution result wrapper used by the EV block executor.
 ///
@@ -94,6 +97,9 @@ pub struct EvBlockExecutor<'a, Evm, Spec, R: ReceiptBuilder> {
     evm: Evm,
     system_caller: SystemCaller<Spec>,
     receipt_builder: R,
+    /// One-time state patches scheduled in the chainspec.
+    state_patches: &'a [StatePatch],
+    patch_receipts: Option<&'a StatePatchReceiptStore>,
     receipts: Vec<R::Receipt>,
     cumulative_tx_gas_used: u64,
     block_regular_gas_used: u64,
@@ -120,8 +126,22 @@ where
             system_caller: SystemCaller::new(spec.clone()),
             spec,
             receipt_builder,
+            state_patches: &[],
+            patch_receipts: None,
         }
     }
+
+    /// Applies `state_patches` at the start of the blocks they are scheduled for, recording
+    /// their receipts in `patch_receipts`.
+    pub const fn with_state_patches(
+        mut self,
+        state_patches: &'a [StatePatch],
+        patch_receipts: &'a StatePatchReceiptStore,
+    ) -> Self {
+        self.state_patches = state_patches;
+        self.patch_receipts = Some(patch_receipts);
+        self
+    }
 }
 
 impl<Evm, Spec, R> EvBlockExecutor<'_, Evm, Spec, R>
@@ -160,6 +180,31 @@ where
         self.system_caller
             .apply_beacon_root_contract_call(self.ctx.parent_beacon_block_root, &mut self.evm)?;
 
+        let block_number = self.evm.block().number().saturating_to();
+        if let Some(patch) = patch_for_block(self.state_patches, block_number) {
+            let (state, receipt) = patch
+                .apply(self.evm.db_mut(), block_number)
+                .map_err(BlockExecutionError::other)?;
+            info!(
+                target = "ev-reth::executor",
+                block_number,
+                patch_hash = %receipt.patch_hash,
+                accounts = receipt.accounts.len(),
+                reason = %receipt.reason,
+                "Applied state patch"
+            );
+            // alloy-evm has no source for irregular state changes; the source only labels the
+            // change for state hooks.
+            self.system_caller.on_state(
+                StateChangeSource::PreBlock(StateChangePreBlockSource::BlockHashesContract),
+                &state,
+            );
+            self.evm.db_mut().commit(state);
+            if let Some(patch_receipts) = self.patch_receipts {
+                patch_receipts.insert(receipt);
+            }
+        }
+
         Ok(())
     }
 
@@ -339,24 +384,40 @@ where
 }
 
 /// Block executor factory for EV transactions.
-#[derive(Debug, Clone, Default, Copy)]
+#[derive(Debug, Clone, Default)]
 pub struct EvBlockExecutorFactory<R = EvReceiptBuilder, Spec = EthSpec, EvmFactory = EthEvmFactory>
 {
     receipt_builder: R,
     spec: Spec,
     evm_factory: EvmFactory,
+    state_patches: Arc<Vec<StatePatch>>,
+    patch_receipts: Arc<StatePatchReceiptStore>,
 }
 
 impl<R, Spec, EvmFactory> EvBlockExecutorFactory<R, Spec, EvmFactory> {
     /// Creates a new EV block executor factory.
-    pub const fn new(receipt_builder: R, spec: Spec, evm_factory: EvmFactory) -> Self {
+    pub fn new(receipt_builder: R, spec: Spec, evm_factory: EvmFactory) -> Self {
         Self {
             receipt_builder,
             spec,
             evm_factory,
+            state_patches: Arc::default(),
+            patch_receipts: Arc::default(),
         }
     }
 
+    /// Applies `state_patches` in the executors created by this factory, recording their
+    /// receipts in `patch_receipts`.
+    pub fn with_state_patches(
+        mut self,
+        state_patches: Vec<StatePatch>,
+        patch_receipts: Arc<StatePatchReceiptStore>,
+    ) -> Self {
+        self.state_patches = Arc::new(state_patches);
+        self.patch_receipts = patch_receipts;
+        self
+    }
+
     /// Returns the receipt builder used by the factory.
     pub const fn receipt_builder(&self) -> &R {
         &self.receipt_builder
@@ -406,5 +467,6 @@ where
         I: Inspector<EvmF::Context<DB>>,
     {
         EvBlockExecutor::new(evm, ctx, &self.spec, &self.receipt_builder)
+            .with_state_patches(&self.state_patches, &self.patch_receipts)
     }
 }
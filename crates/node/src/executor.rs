@@ -6,8 +6,14 @@ use alloy_evm::{eth::spec::EthExecutorSpec, FromRecoveredTx, FromTxWithEncoded};
 use alloy_primitives::U256;
 use alloy_rpc_types_engine::ExecutionData;
 use ev_revm::{
-    BaseFeeRedirect, BaseFeeRedirectSettings, ContractSizeLimitSettings, DeployAllowlistSettings,
-    EvTxEvmFactory, MintPrecompileSettings,
+    BaseFeeRedirect, BaseFeeRedirectSettings, BestEffortSettings, CallGasLimitSettings,
+    ContractSizeLimitSettings, DeployAllowlistSettings, Eip712SponsorSettings, EvHandlerSettings,
+    EvTxEvmFactory, EvmLimitsSettings, EvmVersionSettings, FeaturesPrecompileSettings,
+    GasAllowanceSettings, InboxPrecompileSettings, MaxTxGasSettings, MintPrecompileSettings,
+    NonceLaneSettings, P256SignatureSettings, P256VerifyPrecompileSettings,
+    PrecompileWarmupSettings, SessionKeySettings, SponsorAllowlistSettings, SponsorHashMigration,
+    StakingPrecompileSettings, StateExpirySettings, SystemChannelSettings, SystemFeeSettings,
+    ValidityWindowSettings,
 };
 use reth_chainspec::{ChainSpec, EthChainSpec};
 use reth_errors::RethError;
@@ -32,14 +38,16 @@ use reth_revm::revm::{
     context_interface::block::BlobExcessGasAndPrice,
     primitives::hardfork::SpecId,
 };
+use std::borrow::Cow;
 use tracing::info;
 
 use crate::{
-    config::EvolvePayloadBuilderConfig,
+    config::{ResolvedEvolveConfig, SharedEvolveConfig},
     evm_executor::{EvBlockExecutorFactory, EvReceiptBuilder},
+    state_patch::{StatePatch, StatePatchReceiptStore},
     EvolveNode,
 };
-use ev_primitives::{EvPrimitives, EvTxEnvelope};
+use ev_primitives::{EvNodeTxTypeAlias, EvPrimitives, EvTxEnvelope};
 use reth_evm_ethereum::{revm_spec, revm_spec_by_timestamp_and_block_number, EthBlockAssembler};
 
 /// Type alias for the EV-aware EVM config we install into the node.
@@ -54,6 +62,10 @@ pub struct EvEvmConfig<C = ChainSpec, EvmFactory = EvTxEvmFactory> {
     pub block_assembler: EthBlockAssembler<C>,
     /// Extra data to include in built blocks.
     pub extra_data: alloy_primitives::Bytes,
+    /// Type byte payload transactions may use instead of `0x76`, if the chain has one.
+    pub evnode_tx_type_alias: Option<EvNodeTxTypeAlias>,
+    /// Evolve configuration the node resolved at startup, shared with the other components.
+    pub evolve_config: Option<std::sync::Arc<ResolvedEvolveConfig>>,
 }
 
 impl<ChainSpec> EvEvmConfig<ChainSpec> {
@@ -77,6 +89,8 @@ impl<ChainSpec, EvmFactory> EvEvmConfig<ChainSpec, EvmFactory> {
                 evm_factory,
             ),
             extra_data: alloy_primitives::Bytes::default(),
+            evnode_tx_type_alias: None,
+            evolve_config: None,
         }
     }
 
@@ -90,6 +104,36 @@ impl<ChainSpec, EvmFactory> EvEvmConfig<ChainSpec, EvmFactory> {
         self.extra_data = extra_data;
         self
     }
+
+    /// Applies the chainspec's one-time state patches at the start of their blocks, recording
+    /// their receipts in `patch_receipts`.
+    pub fn with_state_patches(
+        mut self,
+        state_patches: Vec<StatePatch>,
+        patch_receipts: std::sync::Arc<StatePatchReceiptStore>,
+    ) -> Self {
+        self.executor_factory = self
+            .executor_factory
+            .with_state_patches(state_patches, patch_receipts);
+        self
+    }
+
+    /// Decodes payload transactions typed `alias` as EvNode transactions once it is active.
+    pub const fn with_evnode_tx_type_alias(mut self, alias: EvNodeTxTypeAlias) -> Self {
+        self.evnode_tx_type_alias = Some(alias);
+        self
+    }
+
+    /// Attaches the node's resolved evolve configuration so later components can share it.
+    pub fn with_evolve_config(mut self, config: std::sync::Arc<ResolvedEvolveConfig>) -> Self {
+        self.evolve_config = Some(config);
+        self
+    }
+
+    /// Returns the node's resolved evolve configuration, if one was attached.
+    pub const fn evolve_config(&self) -> Option<&std::sync::Arc<ResolvedEvolveConfig>> {
+        self.evolve_config.as_ref()
+    }
 }
 
 impl<ChainSpec, EvmF> ConfigureEvm for EvEvmConfig<ChainSpec, EvmF>
@@ -385,9 +429,13 @@ where
         payload: &ExecutionData,
     ) -> Result<impl ExecutableTxIterator<Self>, Self::Error> {
         let txs = payload.payload.transactions().clone();
-        let convert = |tx: alloy_primitives::Bytes| {
-            let tx =
-                TxTy::<EvPrimitives>::decode_2718_exact(tx.as_ref()).map_err(RethError::other)?;
+        let block_number = payload.payload.block_number();
+        let tx_type_alias = self.evnode_tx_type_alias;
+        let convert = move |tx: alloy_primitives::Bytes| {
+            let raw = tx_type_alias.map_or(Cow::Borrowed(tx.as_ref()), |alias| {
+                alias.canonicalize(tx.as_ref(), block_number)
+            });
+            let tx = TxTy::<EvPrimitives>::decode_2718_exact(&raw).map_err(RethError::other)?;
             let signer = tx.try_recover().map_err(RethError::other)?;
             Ok::<_, RethError>(tx.with_signer(signer))
         };
@@ -395,17 +443,29 @@ where
     }
 }
 
+/// Logs that `feature` is enabled, if it has an activation height, and returns the height.
+fn logged_activation(feature: &str, activation: Option<u64>) -> Option<u64> {
+    if let Some(activation) = activation {
+        info!(
+            target = "ev-reth::executor",
+            activation_height = activation,
+            "{feature} enabled"
+        );
+    }
+    activation
+}
+
 /// Builds the EV-aware EVM configuration by wrapping the default config with the EV handler.
-pub fn build_evm_config<Node>(ctx: &BuilderContext<Node>) -> eyre::Result<EvolveEvmConfig>
+pub fn build_evm_config<Node>(
+    ctx: &BuilderContext<Node>,
+    evolve_config: std::sync::Arc<ResolvedEvolveConfig>,
+) -> eyre::Result<EvolveEvmConfig>
 where
     Node: FullNodeTypes<Types = EvolveNode>,
     ChainSpec: Hardforks + EthExecutorSpec + EthereumHardforks,
 {
     let chain_spec = ctx.chain_spec();
 
-    let evolve_config = EvolvePayloadBuilderConfig::from_chain_spec(chain_spec.as_ref())?;
-    evolve_config.validate()?;
-
     let redirect = evolve_config
         .base_fee_redirect_settings()
         .map(|(sink, activation)| {
@@ -413,9 +473,12 @@ where
                 target = "ev-reth::executor",
                 fee_sink = ?sink,
                 activation_height = activation,
+                burn_bps = evolve_config.base_fee_burn_bps(),
                 "Base fee redirect enabled"
             );
-            BaseFeeRedirectSettings::new(BaseFeeRedirect::new(sink), activation)
+            let redirect =
+                BaseFeeRedirect::new(sink).with_burn_bps(evolve_config.base_fee_burn_bps());
+            BaseFeeRedirectSettings::new(redirect, activation)
         });
 
     let mint_precompile = evolve_config
@@ -448,21 +511,286 @@ where
                 DeployAllowlistSettings::new(allowlist, activation)
             });
 
-    let factory = EvTxEvmFactory::new(
-        redirect,
-        mint_precompile,
-        deploy_allowlist,
-        contract_size_limit,
-    );
+    let inbox_precompile = evolve_config
+        .deposit_inbox_settings()
+        .map(|(relayer, activation)| {
+            info!(
+                target = "ev-reth::executor",
+                relayer = ?relayer,
+                activation_height = activation,
+                "Deposit inbox precompile enabled"
+            );
+            InboxPrecompileSettings::new(relayer, activation)
+        });
+
+    let staking_precompile =
+        evolve_config
+            .staking_settings()
+            .map(|(minimum_stake, unbonding_period, activation)| {
+                info!(
+                    target = "ev-reth::executor",
+                    minimum_stake = %minimum_stake,
+                    unbonding_period,
+                    activation_height = activation,
+                    "Native staking precompile enabled"
+                );
+                StakingPrecompileSettings::new(minimum_stake, unbonding_period, activation)
+            });
+
+    let system_channel = evolve_config
+        .system_channel_settings()
+        .map(|(senders, activation)| {
+            info!(
+                target = "ev-reth::executor",
+                senders = senders.len(),
+                activation_height = activation,
+                "System channel precompile enabled"
+            );
+            SystemChannelSettings::new(senders, activation)
+        });
 
-    Ok(EvEvmConfig::new_with_evm_factory(chain_spec, factory)
-        .with_extra_data(ctx.payload_builder_config().extra_data()))
+    let features_precompile = logged_activation(
+        "Feature bitmap precompile",
+        evolve_config.features_precompile_activation_height,
+    )
+    .map(FeaturesPrecompileSettings::new);
+
+    let p256_verify_precompile = logged_activation(
+        "P256 verification precompile",
+        evolve_config.p256_verify_precompile_activation_height,
+    )
+    .map(P256VerifyPrecompileSettings::new);
+
+    let gas_allowance = evolve_config
+        .gas_allowance_activation_height
+        .map(|activation| {
+            info!(
+                target = "ev-reth::executor",
+                activation_height = activation,
+                post_op_activation_height = ?evolve_config.gas_allowance_post_op_activation_height,
+                "Contract fee payers enabled"
+            );
+            GasAllowanceSettings::new(activation).with_post_op_activation_height(
+                evolve_config.gas_allowance_post_op_activation_height,
+            )
+        });
+
+    let evm_version = evolve_config.evm_version_schedule().map(|schedule| {
+        info!(
+            target = "ev-reth::executor",
+            schedule = ?schedule,
+            "EVM version pinned"
+        );
+        EvmVersionSettings::new(schedule)
+    });
+
+    let state_expiry = evolve_config
+        .state_expiry_settings()
+        .map(|(activation, period)| {
+            info!(
+                target = "ev-reth::executor",
+                activation_height = activation,
+                expiry_period = period,
+                "State expiry enabled"
+            );
+            StateExpirySettings::new(activation, period)
+        });
+
+    let system_fee = evolve_config
+        .system_fee_settings()
+        .map(|(senders, mode, activation)| {
+            info!(
+                target = "ev-reth::executor",
+                senders = senders.len(),
+                mode = ?mode,
+                activation_height = activation,
+                "System transaction fee policy enabled"
+            );
+            SystemFeeSettings::new(senders, mode, activation)
+        });
+
+    let sponsor_hash =
+        evolve_config
+            .sponsor_hash_migration()
+            .map(|(legacy, transition, activation)| {
+                info!(
+                    target = "ev-reth::executor",
+                    legacy_scheme = ?legacy,
+                    transition_height = transition,
+                    activation_height = activation,
+                    "Sponsor hash migration enabled"
+                );
+                SponsorHashMigration::new(legacy, transition, activation)
+            });
+
+    let eip712_sponsor = logged_activation(
+        "EIP-712 sponsorships",
+        evolve_config.sponsor_eip712_activation_height,
+    )
+    .map(Eip712SponsorSettings::new);
+
+    let precompile_warmup =
+        evolve_config
+            .precompile_warmup_settings()
+            .map(|(addresses, activation)| {
+                info!(
+                    target = "ev-reth::executor",
+                    extra_addresses = addresses.len(),
+                    activation_height = activation,
+                    "Precompile warm-up enabled"
+                );
+                PrecompileWarmupSettings::new(activation).with_addresses(addresses)
+            });
+
+    let call_gas_limits = logged_activation(
+        "Per-call gas limits",
+        evolve_config.call_gas_limit_activation_height,
+    )
+    .map(CallGasLimitSettings::new);
+
+    let best_effort = logged_activation(
+        "Best-effort batches",
+        evolve_config.best_effort_activation_height,
+    )
+    .map(BestEffortSettings::new);
+
+    let session_keys =
+        logged_activation("Session keys", evolve_config.session_key_activation_height)
+            .map(SessionKeySettings::new);
+
+    let validity_windows = logged_activation(
+        "Validity windows",
+        evolve_config.validity_window_activation_height,
+    )
+    .map(ValidityWindowSettings::new);
+
+    let nonce_lanes = logged_activation("Nonce lanes", evolve_config.nonce_lane_activation_height)
+        .map(NonceLaneSettings::new);
+
+    let p256_signatures = logged_activation(
+        "P256 executor signatures",
+        evolve_config.p256_signature_activation_height,
+    )
+    .map(P256SignatureSettings::new);
+
+    let max_tx_gas = evolve_config
+        .max_tx_gas_settings()
+        .map(|(max_bps, activation)| {
+            info!(
+                target = "ev-reth::executor",
+                max_bps,
+                activation_height = activation,
+                "Per-transaction gas share cap enabled"
+            );
+            MaxTxGasSettings::new(max_bps, activation)
+        });
+
+    let access_list = evolve_config.access_list_settings().inspect(|settings| {
+        info!(
+            target = "ev-reth::executor",
+            max_entries = settings.max_entries(),
+            max_storage_keys = settings.max_storage_keys(),
+            activation_height = settings.activation_height(),
+            "EvNode access list limits enabled"
+        );
+    });
+
+    let sponsor_allowlist =
+        evolve_config
+            .sponsor_allowlist_settings()
+            .map(|(allowlist, activation)| {
+                info!(
+                    target = "ev-reth::executor",
+                    allowlist_len = allowlist.len(),
+                    activation_height = activation,
+                    "Sponsor allowlist enabled"
+                );
+                SponsorAllowlistSettings::new(allowlist, activation)
+            });
+
+    let evm_limits =
+        evolve_config
+            .evm_limits_settings()
+            .map(|(max_call_depth, memory_limit, activation)| {
+                info!(
+                    target = "ev-reth::executor",
+                    ?max_call_depth,
+                    ?memory_limit,
+                    activation_height = activation,
+                    "EVM call depth and memory caps enabled"
+                );
+                EvmLimitsSettings::new(activation)
+                    .with_max_call_depth(max_call_depth)
+                    .with_memory_limit(memory_limit)
+            });
+
+    let handler = EvHandlerSettings {
+        deploy_allowlist,
+        gas_allowance,
+        state_expiry,
+        system_fee,
+        sponsor_hash,
+        eip712_sponsor,
+        call_gas_limits,
+        best_effort,
+        session_keys,
+        validity_windows,
+        nonce_lanes,
+        p256_signatures,
+        max_tx_gas,
+        access_list,
+        sponsor_allowlist,
+        ..Default::default()
+    };
+
+    let factory = EvTxEvmFactory::new(redirect, mint_precompile, contract_size_limit)
+        .with_inbox_precompile(inbox_precompile)
+        .with_staking_precompile(staking_precompile)
+        .with_system_channel(system_channel)
+        .with_features_precompile(features_precompile)
+        .with_p256_verify_precompile(p256_verify_precompile)
+        .with_evm_version(evm_version)
+        .with_handler_settings(handler)
+        .with_precompile_warmup(precompile_warmup)
+        .with_evm_limits(evm_limits);
+
+    let mut evm_config = EvEvmConfig::new_with_evm_factory(chain_spec, factory)
+        .with_extra_data(ctx.payload_builder_config().extra_data());
+    for patch in evolve_config.state_patches() {
+        info!(
+            target = "ev-reth::executor",
+            height = patch.height,
+            patch_hash = %patch.hash(),
+            accounts = patch.accounts.len(),
+            reason = %patch.reason,
+            "State patch scheduled"
+        );
+    }
+    if !evolve_config.state_patches().is_empty() {
+        evm_config = evm_config.with_state_patches(
+            evolve_config.state_patches().to_vec(),
+            evolve_config.handles.state_patch_receipts.clone(),
+        );
+    }
+    if let Some(alias) = evolve_config.evnode_tx_type_alias() {
+        evm_config = evm_config.with_evnode_tx_type_alias(alias);
+    }
+    Ok(evm_config.with_evolve_config(evolve_config))
 }
 
 /// Thin wrapper so we can plug the EV executor into the node components builder.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EvolveExecutorBuilder;
+pub struct EvolveExecutorBuilder {
+    config: SharedEvolveConfig,
+}
+
+impl EvolveExecutorBuilder {
+    /// Creates an executor builder that reads the node's shared evolve configuration.
+    pub const fn new(config: SharedEvolveConfig) -> Self {
+        Self { config }
+    }
+}
 
 impl<Node> RethExecutorBuilder<Node> for EvolveExecutorBuilder
 where
@@ -472,6 +800,7 @@ where
     type EVM = EvolveEvmConfig;
 
     async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
-        build_evm_config(ctx)
+        let evolve_config = self.config.get_or_resolve(&ctx.chain_spec())?;
+        build_evm_config(ctx, evolve_config)
     }
 }
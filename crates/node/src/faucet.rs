@@ -0,0 +1,581 @@
+//! Faucet handing out native tokens on dev chains and testnets.
+//!
+//! `evolve_requestFunds` sends an amount of the native token to an address from a faucet key,
+//! either by minting it through the mint precompile, which needs the key to be the mint admin
+//! or on its allowlist, or by transferring it from the key's own balance. ev-dev serves the
+//! method on its regular RPC endpoint. A testnet node serves it on a separate public listener
+//! instead, where every recipient address and client IP is rate limited.
+
+use alloy_consensus::{
+    transaction::{Recovered, TxHashRef},
+    BlockHeader, SignableTransaction, TxEip1559,
+};
+use alloy_primitives::{Address, Bytes, TxKind, B256, U256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::SolCall;
+use async_trait::async_trait;
+use axum::extract::{ConnectInfo, Json, State};
+use ev_precompiles::mint::{INativeToken, MINT_PRECOMPILE_ADDR};
+use ev_primitives::{EvTxEnvelope, TransactionSigned};
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE, METHOD_NOT_FOUND_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_storage_api::{AccountReader, BlockNumReader, HeaderProvider, StateProviderFactory};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+use tracing::info;
+
+use crate::{nonce::next_nonce, signer::DEFAULT_PRIORITY_FEE};
+
+/// Default listen address of the public faucet endpoint.
+pub const DEFAULT_FAUCET_ADDR: &str = "127.0.0.1:8548";
+
+/// Default amount sent per request, in wei (1 token).
+pub const DEFAULT_FAUCET_AMOUNT: u128 = 1_000_000_000_000_000_000;
+
+/// Default largest amount a single request may ask for, in wei (10 tokens).
+pub const DEFAULT_FAUCET_MAX_AMOUNT: u128 = 10 * DEFAULT_FAUCET_AMOUNT;
+
+/// Default seconds before an address can be funded again.
+pub const DEFAULT_FAUCET_ADDRESS_COOLDOWN_SECS: u64 = 24 * 60 * 60;
+
+/// Default seconds before a client IP can request funds again.
+pub const DEFAULT_FAUCET_IP_COOLDOWN_SECS: u64 = 60 * 60;
+
+/// JSON-RPC error code of rate-limited requests.
+pub const FAUCET_RATE_LIMITED_CODE: i32 = -32005;
+
+/// Gas limit of faucet transactions, enough for a mint or a transfer to a contract.
+const FAUCET_GAS_LIMIT: u64 = 100_000;
+
+/// Where the faucet takes the funds it sends from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaucetFunding {
+    /// Mint them through the mint precompile.
+    Mint,
+    /// Transfer them from the faucet account's balance.
+    Transfer,
+}
+
+/// Amounts and rate limits of a faucet.
+#[derive(Debug, Clone)]
+pub struct FaucetConfig {
+    /// Where the funds come from.
+    pub funding: FaucetFunding,
+    /// Amount sent when a request does not ask for one.
+    pub amount: U256,
+    /// Largest amount a request may ask for.
+    pub max_amount: U256,
+    /// Time before an address can be funded again. Zero disables the limit.
+    pub address_cooldown: Duration,
+    /// Time before a client IP can request funds again. Zero disables the limit.
+    pub ip_cooldown: Duration,
+}
+
+impl FaucetConfig {
+    /// Returns the amount to send for a request asking for `amount`.
+    fn checked_amount(&self, amount: Option<U256>) -> Result<U256, FaucetError> {
+        let amount = amount.unwrap_or(self.amount);
+        if amount.is_zero() {
+            return Err(FaucetError::ZeroAmount);
+        }
+        if amount > self.max_amount {
+            return Err(FaucetError::AmountTooHigh {
+                amount,
+                max_amount: self.max_amount,
+            });
+        }
+        Ok(amount)
+    }
+}
+
+/// Errors of a faucet request.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FaucetError {
+    /// The request asked for nothing.
+    #[error("amount must not be zero")]
+    ZeroAmount,
+    /// The request asked for more than the faucet sends at once.
+    #[error("amount {amount} is above the faucet limit of {max_amount}")]
+    AmountTooHigh {
+        /// Amount requested.
+        amount: U256,
+        /// Largest amount a request may ask for.
+        max_amount: U256,
+    },
+    /// The address or client IP was funded too recently.
+    #[error("faucet rate limit reached, retry in {retry_after_secs}s")]
+    RateLimited {
+        /// Seconds until the request is accepted again.
+        retry_after_secs: u64,
+    },
+    /// Error while reading the faucet account or the latest block.
+    #[error("state provider error: {0}")]
+    Provider(String),
+    /// The faucet transaction could not be signed.
+    #[error("failed to sign faucet transaction: {0}")]
+    Signing(String),
+    /// The pool rejected the faucet transaction.
+    #[error("faucet transaction rejected by the pool: {0}")]
+    Pool(String),
+}
+
+impl FaucetError {
+    /// Returns the JSON-RPC error code of the error.
+    pub const fn code(&self) -> i32 {
+        match self {
+            Self::ZeroAmount | Self::AmountTooHigh { .. } => INVALID_PARAMS_CODE,
+            Self::RateLimited { .. } => FAUCET_RATE_LIMITED_CODE,
+            Self::Provider(_) | Self::Signing(_) | Self::Pool(_) => INTERNAL_ERROR_CODE,
+        }
+    }
+}
+
+impl From<FaucetError> for ErrorObject<'static> {
+    fn from(err: FaucetError) -> Self {
+        ErrorObject::owned(err.code(), err.to_string(), None::<()>)
+    }
+}
+
+/// Funds sent by `evolve_requestFunds`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFaucetResponse {
+    /// Hash of the faucet transaction.
+    pub tx_hash: B256,
+    /// Amount sent, in wei.
+    pub amount: U256,
+}
+
+/// Per-address and per-IP cooldowns of a faucet.
+#[derive(Debug, Default)]
+pub struct FaucetRateLimiter {
+    address_cooldown: Duration,
+    ip_cooldown: Duration,
+    addresses: HashMap<Address, Instant>,
+    ips: HashMap<IpAddr, Instant>,
+}
+
+impl FaucetRateLimiter {
+    /// Creates a limiter with the given cooldowns.
+    pub fn new(address_cooldown: Duration, ip_cooldown: Duration) -> Self {
+        Self {
+            address_cooldown,
+            ip_cooldown,
+            ..Default::default()
+        }
+    }
+
+    /// Records a request funding `address` from `client` at `now`, or returns how long until
+    /// neither was funded within its cooldown.
+    pub fn acquire(
+        &mut self,
+        address: Address,
+        client: Option<IpAddr>,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let (address_cooldown, ip_cooldown) = (self.address_cooldown, self.ip_cooldown);
+        self.addresses
+            .retain(|_, at| now.duration_since(*at) < address_cooldown);
+        self.ips
+            .retain(|_, at| now.duration_since(*at) < ip_cooldown);
+
+        let address_wait = self
+            .addresses
+            .get(&address)
+            .map(|at| address_cooldown - now.duration_since(*at));
+        let ip_wait = client
+            .and_then(|ip| self.ips.get(&ip))
+            .map(|at| ip_cooldown - now.duration_since(*at));
+        if let Some(wait) = address_wait.max(ip_wait) {
+            return Err(wait);
+        }
+
+        if !address_cooldown.is_zero() {
+            self.addresses.insert(address, now);
+        }
+        if let Some(ip) = client.filter(|_| !ip_cooldown.is_zero()) {
+            self.ips.insert(ip, now);
+        }
+        Ok(())
+    }
+
+    /// Forgets a request that was not sent, so it does not count against the limits.
+    pub fn release(&mut self, address: Address, client: Option<IpAddr>) {
+        self.addresses.remove(&address);
+        if let Some(ip) = client {
+            self.ips.remove(&ip);
+        }
+    }
+}
+
+/// Faucet sending signed transactions from its key to the pool.
+#[derive(Debug)]
+pub struct Faucet<Provider, Pool> {
+    provider: Provider,
+    pool: Pool,
+    signer: PrivateKeySigner,
+    chain_id: u64,
+    config: FaucetConfig,
+    limiter: Mutex<FaucetRateLimiter>,
+    /// Serializes nonce assignment so concurrent requests do not reuse a nonce.
+    submission: tokio::sync::Mutex<()>,
+}
+
+impl<Provider, Pool> Faucet<Provider, Pool> {
+    /// Creates a faucet sending transactions signed by `signer` on `chain_id`.
+    pub fn new(
+        provider: Provider,
+        pool: Pool,
+        signer: PrivateKeySigner,
+        chain_id: u64,
+        config: FaucetConfig,
+    ) -> Self {
+        let limiter = FaucetRateLimiter::new(config.address_cooldown, config.ip_cooldown);
+        Self {
+            provider,
+            pool,
+            signer,
+            chain_id,
+            config,
+            limiter: Mutex::new(limiter),
+            submission: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Returns the address of the faucet account.
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// Returns the amounts and rate limits of the faucet.
+    pub const fn config(&self) -> &FaucetConfig {
+        &self.config
+    }
+}
+
+impl<Provider, Pool> Faucet<Provider, Pool>
+where
+    Provider: StateProviderFactory + BlockNumReader + HeaderProvider,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    /// Sends `amount`, or the default amount, to `address` for a request from `client`.
+    ///
+    /// Only the per-address limit applies when the client is unknown.
+    pub async fn request_funds(
+        &self,
+        address: Address,
+        amount: Option<U256>,
+        client: Option<IpAddr>,
+    ) -> Result<RpcFaucetResponse, FaucetError> {
+        let amount = self.config.checked_amount(amount)?;
+        self.limiter
+            .lock()
+            .expect("faucet limiter lock poisoned")
+            .acquire(address, client, Instant::now())
+            .map_err(|wait| FaucetError::RateLimited {
+                retry_after_secs: wait.as_secs().max(1),
+            })?;
+
+        let tx_hash = match self.send(address, amount).await {
+            Ok(tx_hash) => tx_hash,
+            Err(err) => {
+                self.limiter
+                    .lock()
+                    .expect("faucet limiter lock poisoned")
+                    .release(address, client);
+                return Err(err);
+            }
+        };
+        info!(target: "ev_reth::faucet", %address, %amount, %tx_hash, "Funded address");
+        Ok(RpcFaucetResponse { tx_hash, amount })
+    }
+
+    async fn send(&self, address: Address, amount: U256) -> Result<B256, FaucetError> {
+        let _submission = self.submission.lock().await;
+        let provider_error =
+            |err: reth_provider::ProviderError| FaucetError::Provider(err.to_string());
+        let faucet = self.signer.address();
+        let latest_nonce = self
+            .provider
+            .latest()
+            .and_then(|state| state.basic_account(&faucet))
+            .map_err(provider_error)?
+            .map_or(0, |account| account.nonce);
+        let pooled = self
+            .pool
+            .get_transactions_by_sender(faucet)
+            .into_iter()
+            .map(|tx| tx.transaction.nonce());
+        let nonce = next_nonce(latest_nonce, pooled).nonce.to::<u64>();
+        let block_number = self.provider.best_block_number().map_err(provider_error)?;
+        let base_fee = self
+            .provider
+            .header_by_number(block_number)
+            .map_err(provider_error)?
+            .and_then(|header| header.base_fee_per_gas())
+            .unwrap_or_default();
+
+        let (to, value, input) = match self.config.funding {
+            FaucetFunding::Mint => (
+                MINT_PRECOMPILE_ADDR,
+                U256::ZERO,
+                Bytes::from(
+                    INativeToken::mintCall {
+                        to: address,
+                        amount,
+                    }
+                    .abi_encode(),
+                ),
+            ),
+            FaucetFunding::Transfer => (address, amount, Bytes::new()),
+        };
+        let tx = TxEip1559 {
+            chain_id: self.chain_id,
+            nonce,
+            gas_limit: FAUCET_GAS_LIMIT,
+            max_fee_per_gas: u128::from(base_fee) * 2 + DEFAULT_PRIORITY_FEE,
+            max_priority_fee_per_gas: DEFAULT_PRIORITY_FEE,
+            to: TxKind::Call(to),
+            value,
+            access_list: Default::default(),
+            input,
+        };
+        let signature = self
+            .signer
+            .sign_hash_sync(&tx.signature_hash())
+            .map_err(|err| FaucetError::Signing(err.to_string()))?;
+        let tx = EvTxEnvelope::Ethereum(tx.into_signed(signature).into());
+        let tx_hash = *tx.tx_hash();
+
+        self.pool
+            .add_consensus_transaction(
+                Recovered::new_unchecked(tx, faucet),
+                TransactionOrigin::Local,
+            )
+            .await
+            .map_err(|err| FaucetError::Pool(err.to_string()))?;
+        Ok(tx_hash)
+    }
+}
+
+/// Faucet RPC API.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveFaucetApi {
+    /// Sends `amount` wei of the native token, or the faucet's default amount, to `address`.
+    #[method(name = "requestFunds")]
+    async fn request_funds(
+        &self,
+        address: Address,
+        amount: Option<U256>,
+    ) -> RpcResult<RpcFaucetResponse>;
+}
+
+/// Implementation of the faucet RPC on a node's regular RPC endpoint.
+///
+/// Client IPs are not known there, so only the per-address limit applies; public faucets are
+/// served with [`serve_faucet`] instead.
+#[derive(Debug)]
+pub struct EvolveFaucetApiImpl<Provider, Pool> {
+    faucet: Arc<Faucet<Provider, Pool>>,
+}
+
+impl<Provider, Pool> EvolveFaucetApiImpl<Provider, Pool> {
+    /// Creates a new instance serving requests from `faucet`.
+    pub const fn new(faucet: Arc<Faucet<Provider, Pool>>) -> Self {
+        Self { faucet }
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> EvolveFaucetApiServer for EvolveFaucetApiImpl<Provider, Pool>
+where
+    Provider: StateProviderFactory + BlockNumReader + HeaderProvider + Send + Sync + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Send
+        + Sync
+        + 'static,
+{
+    async fn request_funds(
+        &self,
+        address: Address,
+        amount: Option<U256>,
+    ) -> RpcResult<RpcFaucetResponse> {
+        Ok(self.faucet.request_funds(address, amount, None).await?)
+    }
+}
+
+/// Single JSON-RPC request to the public faucet endpoint.
+#[derive(Debug, Deserialize)]
+struct FaucetRpcRequest {
+    #[serde(default)]
+    id: serde_json::Value,
+    method: String,
+    #[serde(default)]
+    params: Vec<serde_json::Value>,
+}
+
+/// Serves `evolve_requestFunds` as JSON-RPC on `POST /` at `addr`, rate limiting each client
+/// by its peer address, until the listener fails.
+pub async fn serve_faucet<Provider, Pool>(
+    addr: SocketAddr,
+    faucet: Arc<Faucet<Provider, Pool>>,
+) -> eyre::Result<()>
+where
+    Provider: StateProviderFactory + BlockNumReader + HeaderProvider + Send + Sync + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Send
+        + Sync
+        + 'static,
+{
+    let app = axum::Router::new()
+        .route("/", axum::routing::post(faucet_handler::<Provider, Pool>))
+        .with_state(faucet);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!(target: "ev-reth", %addr, "Faucet endpoint started");
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
+    Ok(())
+}
+
+async fn faucet_handler<Provider, Pool>(
+    State(faucet): State<Arc<Faucet<Provider, Pool>>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    Json(request): Json<FaucetRpcRequest>,
+) -> Json<serde_json::Value>
+where
+    Provider: StateProviderFactory + BlockNumReader + HeaderProvider + Send + Sync + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Send
+        + Sync
+        + 'static,
+{
+    let outcome = match request.method.as_str() {
+        "evolve_requestFunds" => match parse_faucet_params(request.params) {
+            Ok((address, amount)) => faucet
+                .request_funds(address, amount, Some(client.ip()))
+                .await
+                .map_err(ErrorObject::from),
+            Err(err) => Err(err),
+        },
+        method => Err(ErrorObject::owned(
+            METHOD_NOT_FOUND_CODE,
+            format!("method {method} not found"),
+            None::<()>,
+        )),
+    };
+    Json(match outcome {
+        Ok(response) => serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": request.id,
+            "result": response,
+        }),
+        Err(err) => serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": request.id,
+            "error": err,
+        }),
+    })
+}
+
+/// Parses the `[address, amount?]` parameters of `evolve_requestFunds`.
+fn parse_faucet_params(
+    params: Vec<serde_json::Value>,
+) -> Result<(Address, Option<U256>), ErrorObject<'static>> {
+    let invalid = |message: String| ErrorObject::owned(INVALID_PARAMS_CODE, message, None::<()>);
+    if params.is_empty() || params.len() > 2 {
+        return Err(invalid(format!(
+            "expected [address, amount?], got {} parameters",
+            params.len()
+        )));
+    }
+    let mut params = params.into_iter();
+    let address = serde_json::from_value(params.next().unwrap_or_default())
+        .map_err(|err| invalid(format!("invalid address: {err}")))?;
+    let amount = serde_json::from_value(params.next().unwrap_or_default())
+        .map_err(|err| invalid(format!("invalid amount: {err}")))?;
+    Ok((address, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_applies_address_and_ip_cooldowns() {
+        let mut limiter = FaucetRateLimiter::new(Duration::from_secs(100), Duration::from_secs(10));
+        let start = Instant::now();
+        let ip = Some(IpAddr::from([10, 0, 0, 1]));
+        let (alice, bob) = (Address::repeat_byte(0xa1), Address::repeat_byte(0xb0));
+
+        assert_eq!(limiter.acquire(alice, ip, start), Ok(()));
+        assert_eq!(
+            limiter.acquire(alice, None, start + Duration::from_secs(40)),
+            Err(Duration::from_secs(60))
+        );
+        assert_eq!(
+            limiter.acquire(bob, ip, start + Duration::from_secs(4)),
+            Err(Duration::from_secs(6))
+        );
+        assert_eq!(
+            limiter.acquire(bob, ip, start + Duration::from_secs(10)),
+            Ok(())
+        );
+
+        // A request that was not sent does not count.
+        limiter.release(bob, ip);
+        assert_eq!(
+            limiter.acquire(bob, ip, start + Duration::from_secs(11)),
+            Ok(())
+        );
+
+        let mut unlimited = FaucetRateLimiter::new(Duration::ZERO, Duration::ZERO);
+        assert_eq!(unlimited.acquire(alice, ip, start), Ok(()));
+        assert_eq!(unlimited.acquire(alice, ip, start), Ok(()));
+    }
+
+    #[test]
+    fn requested_amount_is_capped() {
+        let config = FaucetConfig {
+            funding: FaucetFunding::Mint,
+            amount: U256::from(5),
+            max_amount: U256::from(10),
+            address_cooldown: Duration::ZERO,
+            ip_cooldown: Duration::ZERO,
+        };
+        assert_eq!(config.checked_amount(None), Ok(U256::from(5)));
+        assert_eq!(
+            config.checked_amount(Some(U256::from(10))),
+            Ok(U256::from(10))
+        );
+        assert_eq!(
+            config.checked_amount(Some(U256::from(11))),
+            Err(FaucetError::AmountTooHigh {
+                amount: U256::from(11),
+                max_amount: U256::from(10)
+            })
+        );
+        assert_eq!(
+            config.checked_amount(Some(U256::ZERO)),
+            Err(FaucetError::ZeroAmount)
+        );
+
+        let params = parse_faucet_params(vec![serde_json::json!(Address::ZERO)]).unwrap();
+        assert_eq!(params, (Address::ZERO, None));
+        assert!(parse_faucet_params(Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use alloy_consensus::{BlockHeader, Transaction};
+use ev_primitives::EvPrimitives;
+use evolve_ev_reth::fee_estimator::BlockFees;
+use futures::StreamExt;
+use reth_chainspec::{ChainSpec, EthChainSpec};
+use reth_primitives_traits::{BlockBody, RecoveredBlock};
+use reth_provider::CanonStateSubscriptions;
+use std::sync::Arc;
+
+use crate::config::EvolvePayloadBuilderConfig;
+
+/// Feeds the tips and gas used of canonical blocks into the fee estimator of `config`, along
+/// with the base fee and base fee sink of the next block, averaging them over the node's fee
+/// window. Runs until the canonical state stream ends.
+pub async fn run_fee_estimator<P>(
+    provider: P,
+    chain_spec: Arc<ChainSpec>,
+    config: EvolvePayloadBuilderConfig,
+) where
+    P: CanonStateSubscriptions<Primitives = EvPrimitives>,
+{
+    let estimator = &config.handles.fee_estimator;
+    estimator.set_gas_target(config.gas_target);
+    estimator.set_window_secs(config.node.fee_window_secs);
+    let mut canon_state = provider.canonical_state_stream();
+    while let Some(notification) = canon_state.next().await {
+        if let Some(reverted) = notification.reverted() {
+            estimator.revert(*reverted.range().start());
+        }
+        for block in notification.committed().blocks_iter() {
+            let header = block.header();
+            let next_base_fee = chain_spec
+                .next_block_base_fee(header, header.timestamp())
+                .unwrap_or_default();
+            let sink = config.base_fee_sink_for_block(header.number() + 1);
+            estimator.record_block(block_fees(block), next_base_fee, sink);
+        }
+    }
+}
+
+/// Collects the effective priority fee of every transaction in `block`.
+pub fn block_fees(block: &RecoveredBlock<ev_primitives::Block>) -> BlockFees {
+    let header = block.header();
+    let base_fee = header.base_fee_per_gas().unwrap_or_default();
+    BlockFees {
+        number: header.number(),
+        timestamp: header.timestamp(),
+        gas_used: header.gas_used(),
+        tips: block
+            .body()
+            .transactions()
+            .iter()
+            .map(|tx| tx.effective_tip_per_gas(base_fee).unwrap_or_default())
+            .collect(),
+    }
+}
@@ -0,0 +1,630 @@
+use alloy_consensus::{
+    transaction::TxHashRef, BlockHeader, Transaction as _, TxReceipt, Typed2718,
+};
+use alloy_eips::{BlockHashOrNumber, BlockId};
+use alloy_primitives::{Address, TxKind, B256, U256};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use ev_primitives::{EvTxEnvelope, Receipt, TransactionSigned};
+use reth_primitives_traits::RecoveredBlock;
+use reth_storage_api::{
+    errors::provider::ProviderResult, AccountReader, BlockIdReader, BlockNumReader, BlockReader,
+    ReceiptProvider, StateProvider, StateProviderFactory, TransactionVariant, TransactionsProvider,
+};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+use tracing::info;
+
+use crate::snapshot::{SnapshotError, StateSnapshot, StateSnapshots, DEFAULT_MAX_STATE_SNAPSHOTS};
+
+/// Default listen address of the GraphQL endpoint.
+pub const DEFAULT_GRAPHQL_ADDR: &str = "127.0.0.1:8547";
+
+/// Most blocks a single `blocks` query may return.
+pub const MAX_GRAPHQL_BLOCK_RANGE: u64 = 100;
+
+/// Schema served by the GraphQL endpoint.
+pub type EvolveGraphqlSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// A block with its transactions and receipts.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Block")]
+pub struct GraphqlBlock {
+    /// Block number.
+    pub number: u64,
+    /// Block hash.
+    pub hash: String,
+    /// Parent block hash.
+    pub parent_hash: String,
+    /// Block timestamp in seconds.
+    pub timestamp: u64,
+    /// Fee recipient.
+    pub miner: String,
+    /// Gas used by all transactions.
+    pub gas_used: u64,
+    /// Block gas limit.
+    pub gas_limit: u64,
+    /// Base fee per gas (hex quantity).
+    pub base_fee_per_gas: Option<String>,
+    /// Transactions in block order.
+    pub transactions: Vec<GraphqlTransaction>,
+}
+
+/// A transaction with its receipt.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Transaction")]
+pub struct GraphqlTransaction {
+    /// Transaction hash.
+    pub hash: String,
+    /// Number of the including block.
+    pub block_number: u64,
+    /// Index within the including block.
+    pub index: u64,
+    /// EIP-2718 transaction type (`118` for EvNode transactions).
+    #[graphql(name = "type")]
+    pub tx_type: u8,
+    /// Sender (the executor for EvNode transactions).
+    pub from: String,
+    /// Sponsor paying the gas of a sponsored EvNode transaction.
+    pub fee_payer: Option<String>,
+    /// Sender nonce.
+    pub nonce: u64,
+    /// Gas limit.
+    pub gas_limit: u64,
+    /// Maximum fee per gas (hex quantity).
+    pub max_fee_per_gas: String,
+    /// Maximum priority fee per gas (hex quantity), for dynamic fee transactions.
+    pub max_priority_fee_per_gas: Option<String>,
+    /// Recipient of the first call, `null` for contract creations.
+    pub to: Option<String>,
+    /// Total value transferred (hex quantity).
+    pub value: String,
+    /// Input of the first call.
+    pub input: String,
+    /// Decoded batch calls of an EvNode transaction; empty for other types.
+    pub calls: Vec<GraphqlCall>,
+    /// Receipt, if available.
+    pub receipt: Option<GraphqlReceipt>,
+}
+
+/// A single call of an EvNode batch.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Call")]
+pub struct GraphqlCall {
+    /// Call target, `null` for a contract creation.
+    pub to: Option<String>,
+    /// Value transferred (hex quantity).
+    pub value: String,
+    /// Call data.
+    pub input: String,
+}
+
+/// A transaction receipt with its fee breakdown.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Receipt")]
+pub struct GraphqlReceipt {
+    /// Whether the transaction succeeded.
+    pub status: bool,
+    /// Gas used by this transaction.
+    pub gas_used: u64,
+    /// Gas used by the block up to and including this transaction.
+    pub cumulative_gas_used: u64,
+    /// Price paid per unit of gas (hex quantity).
+    pub effective_gas_price: String,
+    /// Fees paid, split by component.
+    pub fees: GraphqlFees,
+    /// Emitted logs.
+    pub logs: Vec<GraphqlLog>,
+}
+
+/// Fees of a transaction, split by component.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Fees")]
+pub struct GraphqlFees {
+    /// Base fee portion (hex quantity).
+    pub base_fee: String,
+    /// Priority fee portion paid to the block beneficiary (hex quantity).
+    pub priority_fee: String,
+    /// Total fee (hex quantity).
+    pub total: String,
+    /// Account charged for gas: the sponsor if sponsored, the sender otherwise.
+    pub paid_by: String,
+}
+
+/// A log emitted by a transaction.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Log")]
+pub struct GraphqlLog {
+    /// Index within the block.
+    pub index: u64,
+    /// Emitting contract.
+    pub address: String,
+    /// Indexed topics.
+    pub topics: Vec<String>,
+    /// Non-indexed data.
+    pub data: String,
+}
+
+/// Account state at a given block.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Account")]
+pub struct GraphqlAccount {
+    /// Account address.
+    pub address: String,
+    /// Block the state was read at.
+    pub block_number: u64,
+    /// Balance (hex quantity).
+    pub balance: String,
+    /// Nonce.
+    pub nonce: u64,
+    /// Deployed code, `0x` for externally owned accounts.
+    pub code: String,
+}
+
+/// Chain data served by the GraphQL resolvers.
+///
+/// Calls hit the database and are run on blocking threads.
+trait ChainData: Send + Sync + 'static {
+    fn latest_block_number(&self) -> ProviderResult<u64>;
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<GraphqlBlock>>;
+    fn transaction(&self, hash: B256) -> ProviderResult<Option<GraphqlTransaction>>;
+    fn account(&self, address: Address, block_number: u64) -> ProviderResult<GraphqlAccount>;
+
+    /// Pins the state of the latest block. Backends without snapshots return `None`.
+    fn pin_latest(&self) -> Result<Option<StateSnapshot>, SnapshotError> {
+        Ok(None)
+    }
+}
+
+#[derive(Debug)]
+struct ProviderChainData<P> {
+    provider: P,
+    snapshots: StateSnapshots<P>,
+}
+
+impl<P> ChainData for ProviderChainData<P>
+where
+    P: BlockReader<Block = ev_primitives::Block>
+        + ReceiptProvider<Receipt = Receipt>
+        + TransactionsProvider<Transaction = TransactionSigned>
+        + BlockNumReader
+        + BlockIdReader
+        + StateProviderFactory
+        + Send
+        + Sync
+        + 'static,
+{
+    fn latest_block_number(&self) -> ProviderResult<u64> {
+        self.provider.best_block_number()
+    }
+
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<GraphqlBlock>> {
+        let Some(block) = self
+            .provider
+            .recovered_block(id, TransactionVariant::WithHash)?
+        else {
+            return Ok(None);
+        };
+        let receipts = self.provider.receipts_by_block(id)?;
+        Ok(Some(graphql_block(&block, receipts.as_deref())))
+    }
+
+    fn transaction(&self, hash: B256) -> ProviderResult<Option<GraphqlTransaction>> {
+        let Some((_, meta)) = self.provider.transaction_by_hash_with_meta(hash)? else {
+            return Ok(None);
+        };
+        Ok(self
+            .block(meta.block_hash.into())?
+            .and_then(|block| block.transactions.into_iter().nth(meta.index as usize)))
+    }
+
+    fn account(&self, address: Address, block_number: u64) -> ProviderResult<GraphqlAccount> {
+        let state = self.provider.history_by_block_number(block_number)?;
+        graphql_account(state.as_ref(), address, block_number)
+    }
+
+    fn pin_latest(&self) -> Result<Option<StateSnapshot>, SnapshotError> {
+        self.snapshots.pin(BlockId::latest()).map(Some)
+    }
+}
+
+/// Latest-block snapshot shared by all fields of one request, so that every field resolving
+/// the latest block sees the same block and state even if a new block lands mid-query.
+#[derive(Debug, Clone, Default)]
+struct QuerySnapshot(Arc<Mutex<Option<Arc<StateSnapshot>>>>);
+
+impl QuerySnapshot {
+    fn latest(&self, data: &dyn ChainData) -> Result<Option<Arc<StateSnapshot>>, SnapshotError> {
+        let mut pinned = self.0.lock().expect("query snapshot lock poisoned");
+        if pinned.is_none() {
+            *pinned = data.pin_latest()?.map(Arc::new);
+        }
+        Ok(pinned.clone())
+    }
+
+    fn latest_block_number(&self, data: &dyn ChainData) -> Result<u64, SnapshotError> {
+        match self.latest(data)? {
+            Some(snapshot) => Ok(snapshot.block().number),
+            None => Ok(data.latest_block_number()?),
+        }
+    }
+
+    fn account(
+        &self,
+        data: &dyn ChainData,
+        address: Address,
+        block: Option<u64>,
+    ) -> Result<GraphqlAccount, SnapshotError> {
+        match block {
+            Some(block) => Ok(data.account(address, block)?),
+            None => match self.latest(data)? {
+                Some(snapshot) => Ok(graphql_account(
+                    snapshot.state(),
+                    address,
+                    snapshot.block().number,
+                )?),
+                None => Ok(data.account(address, data.latest_block_number()?)?),
+            },
+        }
+    }
+}
+
+/// Root of the GraphQL query type.
+pub struct QueryRoot {
+    data: Arc<dyn ChainData>,
+}
+
+impl std::fmt::Debug for QueryRoot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryRoot").finish_non_exhaustive()
+    }
+}
+
+impl QueryRoot {
+    async fn blocking<T, F>(&self, ctx: &Context<'_>, f: F) -> async_graphql::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn ChainData, &QuerySnapshot) -> Result<T, SnapshotError> + Send + 'static,
+    {
+        let data = self.data.clone();
+        let snapshot = ctx.data_opt::<QuerySnapshot>().cloned().unwrap_or_default();
+        Ok(tokio::task::spawn_blocking(move || f(data.as_ref(), &snapshot)).await??)
+    }
+}
+
+#[Object]
+impl QueryRoot {
+    /// Returns a block by number or hash, or the latest block if neither is given.
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<u64>,
+        hash: Option<String>,
+    ) -> async_graphql::Result<Option<GraphqlBlock>> {
+        let hash = hash.map(|hash| hash.parse::<B256>()).transpose()?;
+        self.blocking(ctx, move |data, snapshot| {
+            let id = match (hash, number) {
+                (Some(hash), _) => hash.into(),
+                (None, Some(number)) => number.into(),
+                (None, None) => snapshot.latest_block_number(data)?.into(),
+            };
+            Ok(data.block(id)?)
+        })
+        .await
+    }
+
+    /// Returns the blocks in `[from, to]`, at most [`MAX_GRAPHQL_BLOCK_RANGE`] of them.
+    /// `to` defaults to the latest block.
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        from: u64,
+        to: Option<u64>,
+    ) -> async_graphql::Result<Vec<GraphqlBlock>> {
+        if to.is_some_and(|to| to < from) {
+            return Err("`to` is below `from`".into());
+        }
+        if to.unwrap_or(from).saturating_sub(from) >= MAX_GRAPHQL_BLOCK_RANGE {
+            return Err(format!("at most {MAX_GRAPHQL_BLOCK_RANGE} blocks per query").into());
+        }
+        self.blocking(ctx, move |data, snapshot| {
+            let to = match to {
+                Some(to) => to,
+                None => snapshot
+                    .latest_block_number(data)?
+                    .min(from + MAX_GRAPHQL_BLOCK_RANGE - 1),
+            };
+            let mut blocks = Vec::new();
+            for number in from..=to {
+                match data.block(number.into())? {
+                    Some(block) => blocks.push(block),
+                    None => break,
+                }
+            }
+            Ok(blocks)
+        })
+        .await
+    }
+
+    /// Returns a transaction by hash.
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+        hash: String,
+    ) -> async_graphql::Result<Option<GraphqlTransaction>> {
+        let hash = hash.parse::<B256>()?;
+        self.blocking(ctx, move |data, _| Ok(data.transaction(hash)?))
+            .await
+    }
+
+    /// Returns an account at the given block, or at the latest block.
+    async fn account(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        block: Option<u64>,
+    ) -> async_graphql::Result<GraphqlAccount> {
+        let address = address.parse::<Address>()?;
+        self.blocking(ctx, move |data, snapshot| {
+            snapshot.account(data, address, block)
+        })
+        .await
+    }
+}
+
+/// Builds the GraphQL schema backed by `provider`.
+///
+/// Each request resolves the latest block at most once, against a [`StateSnapshot`] held for
+/// the rest of the request.
+pub fn graphql_schema<P>(provider: P) -> EvolveGraphqlSchema
+where
+    P: BlockReader<Block = ev_primitives::Block>
+        + ReceiptProvider<Receipt = Receipt>
+        + TransactionsProvider<Transaction = TransactionSigned>
+        + BlockNumReader
+        + BlockIdReader
+        + StateProviderFactory
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    let snapshots = StateSnapshots::new(provider.clone(), DEFAULT_MAX_STATE_SNAPSHOTS);
+    build_schema(Arc::new(ProviderChainData {
+        provider,
+        snapshots,
+    }))
+}
+
+fn build_schema(data: Arc<dyn ChainData>) -> EvolveGraphqlSchema {
+    Schema::build(QueryRoot { data }, EmptyMutation, EmptySubscription).finish()
+}
+
+/// Serves the GraphQL schema on `POST /graphql` at `addr` until the listener fails.
+pub async fn serve_graphql(addr: SocketAddr, schema: EvolveGraphqlSchema) -> eyre::Result<()> {
+    let app = axum::Router::new()
+        .route("/graphql", axum::routing::post(graphql_handler))
+        .with_state(schema);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!(target: "ev-reth", %addr, "GraphQL endpoint started");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn graphql_handler(
+    axum::extract::State(schema): axum::extract::State<EvolveGraphqlSchema>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    schema
+        .execute(request.into_inner().data(QuerySnapshot::default()))
+        .await
+        .into()
+}
+
+fn graphql_account(
+    state: &dyn StateProvider,
+    address: Address,
+    block_number: u64,
+) -> ProviderResult<GraphqlAccount> {
+    let account = state.basic_account(&address)?.unwrap_or_default();
+    let code = state
+        .account_code(&address)?
+        .map(|code| code.original_bytes())
+        .unwrap_or_default();
+    Ok(GraphqlAccount {
+        address: address.to_string(),
+        block_number,
+        balance: hex_quantity(account.balance),
+        nonce: account.nonce,
+        code: code.to_string(),
+    })
+}
+
+fn graphql_block(
+    block: &RecoveredBlock<ev_primitives::Block>,
+    receipts: Option<&[Receipt]>,
+) -> GraphqlBlock {
+    let header = block.header();
+    let base_fee = header.base_fee_per_gas();
+    let mut log_index = 0;
+    let mut cumulative_gas_used = 0;
+    let transactions = block
+        .transactions_recovered()
+        .enumerate()
+        .map(|(index, tx)| {
+            let (tx, sender) = tx.into_parts();
+            let fee_payer = match tx {
+                EvTxEnvelope::EvNode(ev) => ev.tx().fee_payer(sender),
+                EvTxEnvelope::Ethereum(_) => None,
+            };
+            let calls = match tx {
+                EvTxEnvelope::EvNode(ev) => ev
+                    .tx()
+                    .calls
+                    .iter()
+                    .map(|call| GraphqlCall {
+                        to: match call.to {
+                            TxKind::Call(to) => Some(to.to_string()),
+                            TxKind::Create => None,
+                        },
+                        value: hex_quantity(call.value),
+                        input: call.input.to_string(),
+                    })
+                    .collect(),
+                EvTxEnvelope::Ethereum(_) => Vec::new(),
+            };
+            let receipt = receipts
+                .and_then(|receipts| receipts.get(index))
+                .map(|receipt| {
+                    let gas_used = receipt.cumulative_gas_used() - cumulative_gas_used;
+                    cumulative_gas_used = receipt.cumulative_gas_used();
+                    let effective_gas_price = tx.effective_gas_price(base_fee);
+                    let base_fee_paid =
+                        U256::from(gas_used) * U256::from(base_fee.unwrap_or_default());
+                    let total = U256::from(gas_used) * U256::from(effective_gas_price);
+                    let logs = receipt
+                        .logs()
+                        .iter()
+                        .map(|log| {
+                            let index = log_index;
+                            log_index += 1;
+                            GraphqlLog {
+                                index,
+                                address: log.address.to_string(),
+                                topics: log.topics().iter().map(ToString::to_string).collect(),
+                                data: log.data.data.to_string(),
+                            }
+                        })
+                        .collect();
+                    GraphqlReceipt {
+                        status: receipt.status(),
+                        gas_used,
+                        cumulative_gas_used: receipt.cumulative_gas_used(),
+                        effective_gas_price: hex_quantity(U256::from(effective_gas_price)),
+                        fees: GraphqlFees {
+                            base_fee: hex_quantity(base_fee_paid),
+                            priority_fee: hex_quantity(total.saturating_sub(base_fee_paid)),
+                            total: hex_quantity(total),
+                            paid_by: fee_payer.unwrap_or(sender).to_string(),
+                        },
+                        logs,
+                    }
+                });
+            GraphqlTransaction {
+                hash: tx.tx_hash().to_string(),
+                block_number: header.number(),
+                index: index as u64,
+                tx_type: tx.ty(),
+                from: sender.to_string(),
+                fee_payer: fee_payer.map(|fee_payer| fee_payer.to_string()),
+                nonce: tx.nonce(),
+                gas_limit: tx.gas_limit(),
+                max_fee_per_gas: hex_quantity(U256::from(tx.max_fee_per_gas())),
+                max_priority_fee_per_gas: tx
+                    .max_priority_fee_per_gas()
+                    .map(|fee| hex_quantity(U256::from(fee))),
+                to: tx.to().map(|to| to.to_string()),
+                value: hex_quantity(tx.value()),
+                input: tx.input().to_string(),
+                calls,
+                receipt,
+            }
+        })
+        .collect();
+
+    GraphqlBlock {
+        number: header.number(),
+        hash: block.hash().to_string(),
+        parent_hash: header.parent_hash().to_string(),
+        timestamp: header.timestamp(),
+        miner: header.beneficiary().to_string(),
+        gas_used: header.gas_used(),
+        gas_limit: header.gas_limit(),
+        base_fee_per_gas: base_fee.map(|fee| hex_quantity(U256::from(fee))),
+        transactions,
+    }
+}
+
+fn hex_quantity(value: U256) -> String {
+    format!("{value:#x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+
+    #[derive(Debug)]
+    struct StaticChainData;
+
+    impl ChainData for StaticChainData {
+        fn latest_block_number(&self) -> ProviderResult<u64> {
+            Ok(7)
+        }
+
+        fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<GraphqlBlock>> {
+            Ok(id
+                .as_number()
+                .filter(|number| *number <= 7)
+                .map(|number| GraphqlBlock {
+                    number,
+                    hash: B256::with_last_byte(number as u8).to_string(),
+                    parent_hash: B256::ZERO.to_string(),
+                    timestamp: number * 2,
+                    miner: Address::ZERO.to_string(),
+                    gas_used: 0,
+                    gas_limit: 30_000_000,
+                    base_fee_per_gas: Some(hex_quantity(U256::from(7))),
+                    transactions: Vec::new(),
+                }))
+        }
+
+        fn transaction(&self, _hash: B256) -> ProviderResult<Option<GraphqlTransaction>> {
+            Ok(None)
+        }
+
+        fn account(&self, address: Address, block_number: u64) -> ProviderResult<GraphqlAccount> {
+            Ok(GraphqlAccount {
+                address: address.to_string(),
+                block_number,
+                balance: hex_quantity(U256::from(1_000)),
+                nonce: 1,
+                code: Bytes::new().to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_joined_queries() {
+        let schema = build_schema(Arc::new(StaticChainData));
+        let response = schema
+            .execute(
+                r#"{
+                    block { number baseFeePerGas }
+                    blocks(from: 6) { number }
+                    account(address: "0x0000000000000000000000000000000000000001") {
+                        blockNumber balance
+                    }
+                }"#,
+            )
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["block"]["number"], 7);
+        assert_eq!(data["block"]["baseFeePerGas"], "0x7");
+        assert_eq!(data["blocks"].as_array().unwrap().len(), 2);
+        assert_eq!(data["account"]["blockNumber"], 7);
+        assert_eq!(data["account"]["balance"], "0x3e8");
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_block_ranges() {
+        let schema = build_schema(Arc::new(StaticChainData));
+        let response = schema
+            .execute("{ blocks(from: 0, to: 100) { number } }")
+            .await;
+        assert_eq!(response.errors.len(), 1);
+    }
+}
@@ -5,32 +5,106 @@
 //! - Node configuration
 //! - RPC interfaces
 
+/// Address-keyed ABI registry for decoding calls in RPC responses.
+pub mod abi_registry;
+/// `evolve_getAccountActivity` RPC for syncing wallets.
+pub mod activity;
 /// CLI argument handling for evolve-specific options.
 pub mod args;
 /// Evolve-specific payload attribute wiring.
 pub mod attributes;
+/// `evolve_getCallsByTransaction` RPC decoding EvNode batch calls.
+pub mod batch_calls;
+/// `evolve_estimateBatchGas` RPC with per-call gas of EvNode batches.
+pub mod batch_gas;
 /// Builder module for payload construction and related utilities.
 pub mod builder;
+/// Short-lived `eth_call` result cache invalidated on new canonical heads.
+pub mod call_cache;
+/// Chain KPI aggregation from committed blocks.
+pub mod chain_stats;
 /// Chainspec parser with ev-reth overrides.
 pub mod chainspec;
+/// Adversarial Engine API sequences and the `ev-reth chaos-engine` command.
+pub mod chaos_engine;
+/// Rolling block commitments and the `evolve_getBlockCommitment` RPC.
+pub mod commitment;
 /// Configuration types and validation for the Evolve payload builder.
 pub mod config;
+/// DA-backed `safe` and `finalized` block tags.
+pub mod da_finality;
+/// `evnode_predictDeployAddress` RPC for sponsored contract deployments.
+pub mod deploy_address;
+/// `ev-reth doctor` pre-flight checks of the chainspec and data directory.
+pub mod doctor;
+/// Engine API endpoint with zstd or gzip compressed bodies.
+pub mod engine_compression;
 /// Shared error types for evolve node wiring.
 pub mod error;
 /// EV-specific EVM executor building blocks.
 pub mod evm_executor;
 /// Executor wiring for EV aware execution.
 pub mod executor;
+/// `evolve_requestFunds` faucet for dev chains and testnets.
+pub mod faucet;
+/// Background sampling of block tips for `evolve_estimateFeePerGas`.
+pub mod fee_estimator;
+/// Optional GraphQL endpoint for blocks, transactions, receipts and accounts.
+pub mod graphql;
+/// Shedding of expensive RPC calls while payload building is under pressure.
+pub mod load_shed;
+/// Runtime log filter changes through `evolve_setLogFilter`.
+pub mod log_filter;
+/// Per-transaction compute, state growth and DA gas dimensions.
+pub mod metering;
+/// Versioned migrations for Evolve-specific data and the `ev-reth db` commands.
+pub mod migrations;
 /// Node composition and payload types.
 pub mod node;
+/// `evolve_getNextNonce` RPC for relayers.
+pub mod nonce;
+/// `evolve_debugExecutePayload` RPC for re-executing payloads with tracing.
+pub mod payload_debug;
+/// Expiry of payload jobs whose payload is never fetched.
+pub mod payload_jobs;
 /// Payload service integration.
 pub mod payload_service;
 /// Payload types for `EvPrimitives`.
 pub mod payload_types;
+/// `evolve_getPendingBlock` preview of the block being built.
+pub mod pending_block;
+/// `eth_sendRawTransaction` accepting the chain's EvNode transaction type alias.
+pub mod raw_transaction;
+/// Standalone `ev-reth` subcommands that print a report.
+pub mod report_command;
 /// RPC wiring for EvTxEnvelope support.
 pub mod rpc;
+/// Optional screening of pool transactions by an external policy service.
+pub mod screening;
+/// `evnode_sendTransaction` batches signed by a node-managed executor key.
+pub mod send_transaction;
+/// Keys the node signs with and defaults of the transactions it sends itself.
+pub mod signer;
+/// `evnode_simulateBatch` RPC previewing the effects of unsigned EvNode batches.
+pub mod simulate_batch;
+/// Pinned state views for reads spanning several lookups.
+pub mod snapshot;
+/// Speculative next-block building for pool-driven chains.
+pub mod speculative;
+/// `evnode_requestSponsorship` paymaster endpoint.
+pub mod sponsorship;
+/// `ev-reth audit-sponsorships` scan for reused sponsor signatures.
+pub mod sponsorship_audit;
+/// Resolved startup configuration and its changes between restarts.
+pub mod startup_config;
+/// Chainspec-scheduled one-time state patches and the `evolve_getStatePatchReceipt` RPC.
+pub mod state_patch;
+/// Native supply index and the `evolve_getSupply` RPC.
+pub mod supply;
 /// Drop guard for recording `duration_ms` on tracing spans.
 pub(crate) mod tracing_ext;
+/// `evolve_submitTransferIntent` relaying of gasless EIP-3009 token transfers.
+pub mod transfer_intent;
 /// Transaction pool wiring and validation.
 pub mod txpool;
 /// Payload validator integration.
@@ -40,14 +114,19 @@ pub mod validator;
 mod test_utils;
 
 // Re-export public types for convenience.
-pub use args::EvolveArgs;
+pub use args::{EvolveArgs, NodeSettings};
 pub use attributes::EvolveEnginePayloadAttributes;
 pub use builder::{create_payload_builder_service, EvolvePayloadBuilder};
 pub use chainspec::EvolveChainSpecParser;
-pub use config::{ConfigError, EvolvePayloadBuilderConfig};
-pub use error::EvolveEngineError;
+pub use config::{
+    ConfigError, EvolvePayloadBuilderConfig, ResolvedEvolveConfig, SharedEvolveConfig,
+};
+pub use error::{EngineErrorClass, EngineErrorData, EvolveEngineError};
 pub use executor::{build_evm_config, EvolveEvmConfig, EvolveExecutorBuilder};
 pub use node::{log_startup, EvolveEngineTypes, EvolveNode, EvolveNodeAddOns};
 pub use payload_service::{EvolveEnginePayloadBuilder, EvolvePayloadBuilderBuilder};
-pub use payload_types::EvBuiltPayload;
-pub use validator::{EvolveEngineValidator, EvolveEngineValidatorBuilder};
+pub use payload_types::{EvBuiltPayload, EvPayloadEnvelope};
+pub use validator::{
+    EvolveEngineValidator, EvolveEngineValidatorBuilder, GasLimitBounds, GasLimitBoundsError,
+    ParentGasLimits,
+};
@@ -0,0 +1,335 @@
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, Notification, RpcServiceT},
+    types::{ErrorObject, Id, Request},
+    MethodResponse,
+};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tower::Layer;
+
+/// Default interval between blocks the build latency is compared against, in milliseconds.
+pub const DEFAULT_BLOCK_INTERVAL_MS: u64 = 1000;
+
+/// Build pressure (average build time over block interval) from which expensive calls are
+/// limited to [`DEGRADED_CONCURRENCY`] at a time.
+pub const SOFT_PRESSURE: f64 = 0.5;
+
+/// Build pressure from which expensive calls are rejected outright.
+pub const HARD_PRESSURE: f64 = 0.8;
+
+/// Expensive calls served concurrently while under soft pressure.
+pub const DEGRADED_CONCURRENCY: usize = 1;
+
+/// Number of block intervals without a build after which pressure is considered gone.
+const STALE_INTERVALS: u32 = 10;
+
+/// Weight of the latest build in the moving average of build times.
+const BUILD_TIME_SMOOTHING: f64 = 0.3;
+
+/// JSON-RPC error code returned for shed requests.
+pub const SHED_ERROR_CODE: i32 = -32005;
+
+/// Returns true for tracing and simulation calls, which are shed under build pressure.
+pub fn is_expensive_method(method: &str) -> bool {
+    method.starts_with("debug_trace")
+        || method.starts_with("trace_")
+        || matches!(
+            method,
+            "eth_simulateV1" | "eth_callMany" | "eth_estimateGas" | "eth_createAccessList"
+        )
+}
+
+/// Why an expensive call was not admitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShedReason {
+    /// Pressure is at or above [`HARD_PRESSURE`].
+    Overloaded,
+    /// Pressure is at or above [`SOFT_PRESSURE`] and the degraded concurrency is in use.
+    Degraded,
+}
+
+impl ShedReason {
+    /// Label used for the reason in metrics.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Overloaded => "overloaded",
+            Self::Degraded => "degraded",
+        }
+    }
+}
+
+/// Tracks how close payload building runs to the block interval and admits expensive RPC
+/// calls accordingly.
+///
+/// One shedder is shared by the payload builder, which feeds it, and the RPC middleware.
+#[derive(Debug)]
+pub struct LoadShedder {
+    enabled: bool,
+    block_interval_ms: u64,
+    builds: Mutex<Option<BuildLatency>>,
+    in_flight: AtomicUsize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BuildLatency {
+    average_ms: f64,
+    last_build: Instant,
+}
+
+impl Default for LoadShedder {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl LoadShedder {
+    /// Creates a shedder comparing builds against `block_interval_ms`, or one admitting every
+    /// call when `None`.
+    pub fn new(block_interval_ms: Option<u64>) -> Self {
+        Self {
+            enabled: block_interval_ms.is_some(),
+            block_interval_ms: block_interval_ms
+                .unwrap_or(DEFAULT_BLOCK_INTERVAL_MS)
+                .max(1),
+            builds: Mutex::new(None),
+            in_flight: AtomicUsize::new(0),
+        }
+    }
+
+    /// Records the duration of a finished payload build.
+    pub fn record_build(&self, duration: Duration) {
+        let now = Instant::now();
+        let sample = duration.as_secs_f64() * 1000.0;
+        let mut builds = self.builds.lock().unwrap_or_else(|e| e.into_inner());
+        let average_ms = builds.map_or(sample, |latency| {
+            BUILD_TIME_SMOOTHING * sample + (1.0 - BUILD_TIME_SMOOTHING) * latency.average_ms
+        });
+        *builds = Some(BuildLatency {
+            average_ms,
+            last_build: now,
+        });
+        drop(builds);
+        metrics::gauge!("ev_reth_rpc_build_pressure").set(self.pressure_at(now));
+    }
+
+    /// Returns the average build time as a fraction of the block interval, or zero when no
+    /// block has been built recently.
+    pub fn pressure_at(&self, now: Instant) -> f64 {
+        let interval_ms = self.block_interval_ms;
+        let Some(latency) = *self.builds.lock().unwrap_or_else(|e| e.into_inner()) else {
+            return 0.0;
+        };
+        let stale_after = Duration::from_millis(interval_ms) * STALE_INTERVALS;
+        if now.saturating_duration_since(latency.last_build) > stale_after {
+            return 0.0;
+        }
+        latency.average_ms / interval_ms as f64
+    }
+
+    /// Admits an expensive call, returning a guard that must be held while it runs.
+    pub fn try_admit(&self) -> Result<InFlightGuard<'_>, ShedReason> {
+        if !self.enabled {
+            return Ok(self.enter());
+        }
+        let pressure = self.pressure_at(Instant::now());
+        if pressure >= HARD_PRESSURE {
+            return Err(ShedReason::Overloaded);
+        }
+        let guard = self.enter();
+        if pressure >= SOFT_PRESSURE && guard.concurrent > DEGRADED_CONCURRENCY {
+            return Err(ShedReason::Degraded);
+        }
+        Ok(guard)
+    }
+
+    fn enter(&self) -> InFlightGuard<'_> {
+        let concurrent = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        InFlightGuard {
+            shedder: self,
+            concurrent,
+        }
+    }
+}
+
+/// Counts an expensive call as in flight until dropped.
+#[derive(Debug)]
+pub struct InFlightGuard<'a> {
+    shedder: &'a LoadShedder,
+    concurrent: usize,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.shedder.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn shed_error(reason: ShedReason) -> ErrorObject<'static> {
+    ErrorObject::owned(
+        SHED_ERROR_CODE,
+        format!(
+            "request shed while the node is busy building payloads ({}); retry later",
+            reason.as_str()
+        ),
+        None::<()>,
+    )
+}
+
+fn record_shed(method: &str, reason: ShedReason) {
+    metrics::counter!(
+        "ev_reth_rpc_shed_requests_total",
+        "method" => method.to_string(),
+        "reason" => reason.as_str()
+    )
+    .increment(1);
+}
+
+/// RPC middleware layer shedding expensive calls under build pressure.
+#[derive(Debug, Clone, Default)]
+pub struct RpcLoadShedLayer {
+    shedder: Arc<LoadShedder>,
+}
+
+impl RpcLoadShedLayer {
+    /// Creates a layer admitting expensive calls through `shedder`.
+    pub const fn new(shedder: Arc<LoadShedder>) -> Self {
+        Self { shedder }
+    }
+}
+
+impl<S> Layer<S> for RpcLoadShedLayer {
+    type Service = RpcLoadShedService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcLoadShedService {
+            inner,
+            shedder: self.shedder.clone(),
+        }
+    }
+}
+
+/// Service produced by [`RpcLoadShedLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcLoadShedService<S> {
+    inner: S,
+    shedder: Arc<LoadShedder>,
+}
+
+impl<S> RpcServiceT for RpcLoadShedService<S>
+where
+    S: RpcServiceT<
+            MethodResponse = MethodResponse,
+            BatchResponse = MethodResponse,
+            NotificationResponse = MethodResponse,
+        > + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = MethodResponse;
+    type NotificationResponse = MethodResponse;
+    type BatchResponse = MethodResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let inner = self.inner.clone();
+        let shedder = self.shedder.clone();
+        async move {
+            if !is_expensive_method(req.method_name()) {
+                return inner.call(req).await;
+            }
+            match shedder.try_admit() {
+                Ok(_guard) => inner.call(req).await,
+                Err(reason) => {
+                    record_shed(req.method_name(), reason);
+                    MethodResponse::error(req.id(), shed_error(reason))
+                }
+            }
+        }
+    }
+
+    fn batch<'a>(&self, batch: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let inner = self.inner.clone();
+        let shedder = self.shedder.clone();
+        async move {
+            // Calls inside a batch bypass `call`, so a batch holding any expensive call is
+            // admitted or shed as a whole.
+            let expensive = batch.iter().find_map(|entry| match entry {
+                Ok(BatchEntry::Call(req)) if is_expensive_method(req.method_name()) => {
+                    Some(req.method_name().to_string())
+                }
+                _ => None,
+            });
+            let Some(method) = expensive else {
+                return inner.batch(batch).await;
+            };
+            match shedder.try_admit() {
+                Ok(_guard) => inner.batch(batch).await,
+                Err(reason) => {
+                    record_shed(&method, reason);
+                    MethodResponse::error(Id::Null, shed_error(reason))
+                }
+            }
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expensive_methods() {
+        assert!(is_expensive_method("debug_traceTransaction"));
+        assert!(is_expensive_method("trace_block"));
+        assert!(is_expensive_method("eth_simulateV1"));
+        assert!(!is_expensive_method("eth_call"));
+        assert!(!is_expensive_method("eth_sendRawTransaction"));
+    }
+
+    #[test]
+    fn admission_follows_build_pressure() {
+        let shedder = LoadShedder::new(Some(1000));
+        assert_eq!(shedder.pressure_at(Instant::now()), 0.0);
+        {
+            let _first = shedder.try_admit().unwrap();
+            assert!(shedder.try_admit().is_ok());
+        }
+
+        // Soft pressure: one expensive call at a time.
+        shedder.record_build(Duration::from_millis(600));
+        let first = shedder.try_admit().unwrap();
+        assert_eq!(shedder.try_admit().unwrap_err(), ShedReason::Degraded);
+        drop(first);
+        assert!(shedder.try_admit().is_ok());
+
+        // Hard pressure: nothing expensive is admitted.
+        for _ in 0..10 {
+            shedder.record_build(Duration::from_millis(950));
+        }
+        assert_eq!(shedder.try_admit().unwrap_err(), ShedReason::Overloaded);
+
+        // Pressure lapses when no block has been built for a while.
+        let later = Instant::now() + Duration::from_secs(11);
+        assert_eq!(shedder.pressure_at(later), 0.0);
+    }
+
+    #[test]
+    fn disabled_shedder_admits_everything() {
+        let shedder = LoadShedder::default();
+        shedder.record_build(Duration::from_secs(5));
+        assert!(shedder.try_admit().is_ok());
+    }
+}
@@ -0,0 +1,259 @@
+//! `evolve_setLogFilter` admin RPC.
+//!
+//! Lets operators raise or lower the log level of one subsystem while the node runs, e.g. to
+//! debug the txpool validator during an incident without restarting the sequencer. The node's
+//! filter is rebuilt from the directives it started with (`RUST_LOG`, or `info`) followed by one
+//! `target=level` directive per override, and handed to the binary's tracing subscriber.
+
+use async_trait::async_trait;
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+use tracing::level_filters::LevelFilter;
+
+/// Subsystem names accepted in place of a tracing target, and the targets their events use.
+pub const SUBSYSTEM_TARGETS: &[(&str, &[&str])] = &[
+    (
+        "payload_builder",
+        &[
+            "ev_node::builder",
+            "ev_node::payload_service",
+            "ev_node::payload_jobs",
+        ],
+    ),
+    ("pool_validator", &["ev_node::txpool", "ev-reth::txpool"]),
+    ("executor", &["ev-reth::executor"]),
+];
+
+/// Applies a filter, given as comma-separated directives, to the node's tracing subscriber.
+pub type ReloadLogFilter = dyn Fn(&str) -> Result<(), String> + Send + Sync;
+
+/// Errors returned when changing the log filter.
+#[derive(Debug, Error)]
+pub enum LogFilterError {
+    /// The target is empty or contains characters that are not allowed in a directive.
+    #[error("invalid log target {0:?}")]
+    InvalidTarget(String),
+    /// The level is not one of `off`, `error`, `warn`, `info`, `debug` or `trace`.
+    #[error("invalid log level {0:?}")]
+    InvalidLevel(String),
+    /// The subscriber rejected the filter.
+    #[error("failed to reload log filter: {0}")]
+    Reload(String),
+}
+
+impl From<LogFilterError> for ErrorObject<'static> {
+    fn from(err: LogFilterError) -> Self {
+        let code = match err {
+            LogFilterError::Reload(_) => INTERNAL_ERROR_CODE,
+            _ => INVALID_PARAMS_CODE,
+        };
+        Self::owned(code, err.to_string(), None::<()>)
+    }
+}
+
+/// Log filter of the node: its startup directives and the per-target overrides set since.
+#[derive(Clone)]
+pub struct LogFilter {
+    base: String,
+    overrides: Arc<Mutex<BTreeMap<String, LevelFilter>>>,
+    reload: Arc<ReloadLogFilter>,
+}
+
+impl fmt::Debug for LogFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogFilter")
+            .field("base", &self.base)
+            .field("overrides", &self.overrides)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LogFilter {
+    /// Creates a filter starting from the `base` directives, applied with `reload`.
+    pub fn new(
+        base: impl Into<String>,
+        reload: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            base: base.into(),
+            overrides: Arc::default(),
+            reload: Arc::new(reload),
+        }
+    }
+
+    /// Sets the level of `target`, or of every target of a subsystem named in
+    /// [`SUBSYSTEM_TARGETS`], and applies the resulting filter. `None` removes the override.
+    ///
+    /// Returns the directives now in effect.
+    pub fn set(&self, target: &str, level: Option<&str>) -> Result<String, LogFilterError> {
+        let targets = resolve_targets(target)?;
+        let level = level
+            .map(|level| {
+                level
+                    .parse::<LevelFilter>()
+                    .map_err(|_| LogFilterError::InvalidLevel(level.to_string()))
+            })
+            .transpose()?;
+
+        let mut overrides = self.overrides.lock().unwrap_or_else(|e| e.into_inner());
+        let mut updated = overrides.clone();
+        for target in targets {
+            match level {
+                Some(level) => updated.insert(target, level),
+                None => updated.remove(&target),
+            };
+        }
+        let directives = self.directives(&updated);
+        (self.reload)(&directives).map_err(LogFilterError::Reload)?;
+        *overrides = updated;
+        Ok(directives)
+    }
+
+    /// Returns the directives currently in effect.
+    pub fn current(&self) -> String {
+        self.directives(&self.overrides.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Startup directives not naming an overridden target, followed by the overrides.
+    fn directives(&self, overrides: &BTreeMap<String, LevelFilter>) -> String {
+        self.base
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| {
+                !directive.is_empty()
+                    && directive
+                        .split_once('=')
+                        .is_none_or(|(target, _)| !overrides.contains_key(target))
+            })
+            .map(str::to_string)
+            .chain(
+                overrides
+                    .iter()
+                    // `LevelFilter` displays upper case, directives are conventionally lower case.
+                    .map(|(target, level)| {
+                        format!("{target}={}", level.to_string().to_lowercase())
+                    }),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Expands a subsystem name into its targets, or checks that `target` is a plain target.
+fn resolve_targets(target: &str) -> Result<Vec<String>, LogFilterError> {
+    if let Some((_, targets)) = SUBSYSTEM_TARGETS.iter().find(|(name, _)| *name == target) {
+        return Ok(targets.iter().map(|target| target.to_string()).collect());
+    }
+    let valid = !target.is_empty()
+        && target
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':'));
+    if !valid {
+        return Err(LogFilterError::InvalidTarget(target.to_string()));
+    }
+    Ok(vec![target.to_string()])
+}
+
+/// Log filter RPC API.
+///
+/// Served on the authenticated Engine API endpoint only.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveLogFilterApi {
+    /// Sets the log level of a tracing target, or of a subsystem such as `payload_builder` or
+    /// `pool_validator`, until the node restarts. Omitting the level restores the startup
+    /// filter for the target. Returns the filter directives now in effect.
+    #[method(name = "setLogFilter")]
+    async fn set_log_filter(&self, target: String, level: Option<String>) -> RpcResult<String>;
+}
+
+/// Implementation of the log filter RPC.
+#[derive(Debug)]
+pub struct EvolveLogFilterApiImpl {
+    filter: LogFilter,
+}
+
+impl EvolveLogFilterApiImpl {
+    /// Creates a new instance changing `filter`.
+    pub const fn new(filter: LogFilter) -> Self {
+        Self { filter }
+    }
+}
+
+#[async_trait]
+impl EvolveLogFilterApiServer for EvolveLogFilterApiImpl {
+    async fn set_log_filter(&self, target: String, level: Option<String>) -> RpcResult<String> {
+        let directives = self.filter.set(&target, level.as_deref())?;
+        tracing::info!(log_target = %target, filter = %directives, "Log filter changed");
+        Ok(directives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording_filter(base: &str) -> (LogFilter, Arc<Mutex<Vec<String>>>) {
+        let applied = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&applied);
+        let filter = LogFilter::new(base, move |directives| {
+            recorded.lock().unwrap().push(directives.to_string());
+            Ok(())
+        });
+        (filter, applied)
+    }
+
+    #[test]
+    fn overrides_targets_on_top_of_startup_filter() {
+        let (filter, applied) = recording_filter("info,ev_node::txpool=warn");
+
+        assert_eq!(
+            filter.set("mint_precompile", Some("debug")).unwrap(),
+            "info,ev_node::txpool=warn,mint_precompile=debug"
+        );
+        // A subsystem expands to its targets and replaces their startup directives.
+        assert_eq!(
+            filter.set("pool_validator", Some("trace")).unwrap(),
+            "info,ev-reth::txpool=trace,ev_node::txpool=trace,mint_precompile=debug"
+        );
+        assert_eq!(
+            filter.set("pool_validator", None).unwrap(),
+            "info,ev_node::txpool=warn,mint_precompile=debug"
+        );
+        assert_eq!(applied.lock().unwrap().len(), 3);
+        assert_eq!(
+            filter.current(),
+            "info,ev_node::txpool=warn,mint_precompile=debug"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input_and_keeps_filter_on_reload_failure() {
+        let (filter, applied) = recording_filter("info");
+        assert!(matches!(
+            filter.set("a=b,c", Some("debug")),
+            Err(LogFilterError::InvalidTarget(_))
+        ));
+        assert!(matches!(
+            filter.set("mint_precompile", Some("loud")),
+            Err(LogFilterError::InvalidLevel(_))
+        ));
+        assert!(applied.lock().unwrap().is_empty());
+
+        let failing = LogFilter::new("info", |_| Err("subscriber gone".to_string()));
+        assert!(matches!(
+            failing.set("mint_precompile", Some("debug")),
+            Err(LogFilterError::Reload(_))
+        ));
+        assert_eq!(failing.current(), "info");
+    }
+}
@@ -0,0 +1,160 @@
+use alloy_eips::Encodable2718;
+use alloy_evm::block::{BlockExecutor, TxResult};
+use alloy_primitives::B256;
+use ev_revm::{DimensionPricing, GasDimensions};
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::RecoveredBlock;
+use reth_revm::{
+    database::StateProviderDatabase, revm::context_interface::result::ResultAndState, State,
+};
+use reth_storage_api::{BlockReader, StateProviderFactory, TransactionVariant};
+
+use crate::EvolveEvmConfig;
+
+/// Gas dimensions of a transaction, and the gas they amount to once priced separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeteredGas {
+    /// Measured dimensions.
+    pub dimensions: GasDimensions,
+    /// Gas under multi-dimensional pricing, if active at the transaction's block.
+    pub priced_gas: Option<u64>,
+}
+
+impl MeteredGas {
+    /// Prices `dimensions` of a transaction in `block_number` with the chain's `pricing`.
+    pub fn new(
+        dimensions: GasDimensions,
+        pricing: Option<DimensionPricing>,
+        block_number: u64,
+    ) -> Self {
+        let priced_gas = pricing
+            .filter(|pricing| pricing.is_active(block_number))
+            .map(|pricing| pricing.priced_gas(&dimensions));
+        Self {
+            dimensions,
+            priced_gas,
+        }
+    }
+}
+
+/// Meters the gas dimensions of included transactions for RPC receipts.
+///
+/// Dimensions are measured by re-executing the transaction's block on top of its parent state,
+/// so every node reports the same values for a transaction, whether or not it executed the
+/// block itself. Receipts of blocks whose parent state is no longer available are served
+/// without them.
+#[derive(Debug, Clone)]
+pub struct GasMeter<Provider> {
+    provider: Provider,
+    evm_config: EvolveEvmConfig,
+    pricing: Option<DimensionPricing>,
+}
+
+impl<Provider> GasMeter<Provider> {
+    /// Creates a meter re-executing blocks read from `provider` with `evm_config`, and pricing
+    /// their dimensions with the chain's multi-dimensional gas `pricing`, if configured.
+    pub const fn new(
+        provider: Provider,
+        evm_config: EvolveEvmConfig,
+        pricing: Option<DimensionPricing>,
+    ) -> Self {
+        Self {
+            provider,
+            evm_config,
+            pricing,
+        }
+    }
+}
+
+impl<Provider> GasMeter<Provider>
+where
+    Provider: BlockReader<Block = ev_primitives::Block> + StateProviderFactory,
+{
+    /// Returns the metered gas of the first `count` transactions of block `block_hash`.
+    pub fn meter_block(&self, block_hash: B256, count: usize) -> Result<Vec<MeteredGas>, String> {
+        let block = self
+            .provider
+            .recovered_block(block_hash.into(), TransactionVariant::WithHash)
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| format!("block {block_hash} not found"))?;
+        let block_number = block.header().number;
+        Ok(
+            measure_block(&self.provider, &self.evm_config, &block, count)?
+                .into_iter()
+                .map(|dimensions| MeteredGas::new(dimensions, self.pricing, block_number))
+                .collect(),
+        )
+    }
+}
+
+/// Executes the first `count` transactions of `block` on top of its parent state, returning
+/// their gas dimensions. Nothing is written to the database.
+fn measure_block<Provider>(
+    provider: &Provider,
+    evm_config: &EvolveEvmConfig,
+    block: &RecoveredBlock<ev_primitives::Block>,
+    count: usize,
+) -> Result<Vec<GasDimensions>, String>
+where
+    Provider: StateProviderFactory,
+{
+    let parent = provider
+        .history_by_block_hash(block.header().parent_hash)
+        .map_err(|err| format!("parent state unavailable: {err}"))?;
+    let mut db = State::builder()
+        .with_database(StateProviderDatabase::new(&parent))
+        .build();
+    let mut executor = evm_config
+        .executor_for_block(&mut db, block.sealed_block())
+        .map_err(|err| err.to_string())?;
+    executor
+        .apply_pre_execution_changes()
+        .map_err(|err| err.to_string())?;
+
+    let mut dimensions = Vec::with_capacity(count);
+    for tx in block.transactions_recovered().take(count) {
+        let encoded_len = tx.encode_2718_len() as u64;
+        let output = executor
+            .execute_transaction_without_commit(tx)
+            .map_err(|err| err.to_string())?;
+        let ResultAndState { result, state } = output.result();
+        dimensions.push(GasDimensions::measure(
+            result.gas().block_regular_gas_used(),
+            state,
+            encoded_len,
+        ));
+        executor.commit_transaction(output);
+    }
+    Ok(dimensions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dimensions(compute: u64) -> GasDimensions {
+        GasDimensions {
+            compute,
+            state_growth: 64,
+            da: 100,
+        }
+    }
+
+    #[test]
+    fn prices_from_activation_height() {
+        let pricing = Some(DimensionPricing::new(10, 1, 2));
+
+        assert_eq!(
+            MeteredGas::new(dimensions(21_000), pricing, 9).priced_gas,
+            None
+        );
+        assert_eq!(
+            MeteredGas::new(dimensions(21_000), pricing, 10).priced_gas,
+            Some(21_000 + 64 + 200)
+        );
+        assert_eq!(
+            MeteredGas::new(dimensions(21_000), None, 10).priced_gas,
+            None
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! Versioned migrations for Evolve-specific data kept alongside the reth database.
+//!
+//! The schema version of that data is recorded in [`SCHEMA_VERSION_FILE`] in the node's data
+//! directory. Migrations run in version order, and the recorded version is advanced after each
+//! one, so an interrupted run resumes at the first migration that did not complete. A data
+//! directory written by a newer binary is never touched.
+
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File in the data directory recording the Evolve schema version.
+pub const SCHEMA_VERSION_FILE: &str = "evolve-schema.json";
+
+/// Directory in the data directory holding Evolve-specific data.
+pub const EVOLVE_DATA_DIR: &str = "evolve";
+
+/// Migrations known to this binary, in version order.
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "create the Evolve data directory",
+    apply: create_evolve_data_dir,
+}];
+
+/// Errors raised while reading the schema version or running migrations.
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    /// The schema version file could not be read or written.
+    #[error("schema version I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The schema version file is malformed.
+    #[error("invalid schema version file: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The data directory was written by a newer binary.
+    #[error(
+        "on-disk Evolve schema version {on_disk} is newer than the {supported} supported by this \
+         binary; upgrade ev-reth or restore a compatible data directory"
+    )]
+    NewerOnDisk {
+        /// Version recorded in the data directory.
+        on_disk: u64,
+        /// Latest version this binary can migrate to.
+        supported: u64,
+    },
+    /// A migration step failed; the schema version stays at the previous step.
+    #[error("migration to version {version} failed: {reason}")]
+    Failed {
+        /// Version of the failed migration.
+        version: u64,
+        /// Why the migration failed.
+        reason: String,
+    },
+}
+
+/// A single schema migration.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Schema version after the migration has run.
+    pub version: u64,
+    /// Short description shown by `db status`.
+    pub description: &'static str,
+    /// Applies the migration to the given data directory.
+    pub apply: fn(&Path) -> Result<(), MigrationError>,
+}
+
+fn create_evolve_data_dir(data_dir: &Path) -> Result<(), MigrationError> {
+    std::fs::create_dir_all(data_dir.join(EVOLVE_DATA_DIR))?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SchemaVersion {
+    version: u64,
+}
+
+/// Schema version of a data directory and the migrations it is missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    /// Version recorded in the data directory; zero for a directory never migrated.
+    pub on_disk: u64,
+    /// Latest version this binary can migrate to.
+    pub latest: u64,
+    /// Versions and descriptions of the migrations still to run.
+    pub pending: Vec<(u64, &'static str)>,
+}
+
+/// Runs [`Migration`]s against a node data directory.
+#[derive(Debug)]
+pub struct Migrator<'a> {
+    data_dir: PathBuf,
+    migrations: &'a [Migration],
+}
+
+impl Migrator<'static> {
+    /// Creates a migrator applying [`MIGRATIONS`] to `data_dir`.
+    pub fn new(data_dir: impl Into<PathBuf>) -> Self {
+        Self::with_migrations(data_dir, MIGRATIONS)
+    }
+}
+
+impl<'a> Migrator<'a> {
+    /// Creates a migrator applying `migrations`, which must be in increasing version order.
+    pub fn with_migrations(data_dir: impl Into<PathBuf>, migrations: &'a [Migration]) -> Self {
+        debug_assert!(migrations.windows(2).all(|w| w[0].version < w[1].version));
+        Self {
+            data_dir: data_dir.into(),
+            migrations,
+        }
+    }
+
+    /// Returns the latest version the migrations lead to.
+    pub fn latest_version(&self) -> u64 {
+        self.migrations
+            .last()
+            .map_or(0, |migration| migration.version)
+    }
+
+    /// Returns the version recorded in the data directory, or zero if none is.
+    pub fn on_disk_version(&self) -> Result<u64, MigrationError> {
+        match std::fs::read_to_string(self.data_dir.join(SCHEMA_VERSION_FILE)) {
+            Ok(contents) => Ok(serde_json::from_str::<SchemaVersion>(&contents)?.version),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Returns the schema status of the data directory.
+    pub fn status(&self) -> Result<MigrationStatus, MigrationError> {
+        let on_disk = self.on_disk_version()?;
+        Ok(MigrationStatus {
+            on_disk,
+            latest: self.latest_version(),
+            pending: self
+                .migrations
+                .iter()
+                .filter(|migration| migration.version > on_disk)
+                .map(|migration| (migration.version, migration.description))
+                .collect(),
+        })
+    }
+
+    /// Runs every pending migration and returns the versions applied.
+    ///
+    /// Fails without changing anything if the data directory is newer than this binary.
+    pub fn migrate(&self) -> Result<Vec<u64>, MigrationError> {
+        let on_disk = self.on_disk_version()?;
+        let supported = self.latest_version();
+        if on_disk > supported {
+            return Err(MigrationError::NewerOnDisk { on_disk, supported });
+        }
+
+        let mut applied = Vec::new();
+        for migration in self.migrations.iter().filter(|m| m.version > on_disk) {
+            (migration.apply)(&self.data_dir).map_err(|err| MigrationError::Failed {
+                version: migration.version,
+                reason: err.to_string(),
+            })?;
+            self.record_version(migration.version)?;
+            tracing::info!(
+                target: "ev-reth::migrations",
+                version = migration.version,
+                description = migration.description,
+                "applied Evolve schema migration"
+            );
+            applied.push(migration.version);
+        }
+        Ok(applied)
+    }
+
+    fn record_version(&self, version: u64) -> Result<(), MigrationError> {
+        std::fs::create_dir_all(&self.data_dir)?;
+        let path = self.data_dir.join(SCHEMA_VERSION_FILE);
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&SchemaVersion { version })?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+/// `ev-reth db` commands managing the Evolve schema.
+///
+/// Handled before reth's own `db` command, which serves every other subcommand.
+#[derive(Debug, Parser)]
+#[command(name = "ev-reth db")]
+pub struct DbCommand {
+    /// Data directory of the node.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub datadir: PathBuf,
+
+    /// Command to run.
+    #[command(subcommand)]
+    pub command: DbSubcommand,
+}
+
+/// Subcommands of [`DbCommand`].
+#[derive(Debug, Clone, Copy, Subcommand)]
+pub enum DbSubcommand {
+    /// Run pending Evolve schema migrations.
+    Migrate,
+    /// Show the on-disk Evolve schema version and pending migrations.
+    Status,
+}
+
+impl DbSubcommand {
+    /// Names of the subcommands, used to route `ev-reth db <name>` away from reth.
+    pub const NAMES: [&'static str; 2] = ["migrate", "status"];
+}
+
+impl DbCommand {
+    /// Runs the command, printing its outcome.
+    pub fn run(self) -> Result<(), MigrationError> {
+        let migrator = Migrator::new(&self.datadir);
+        match self.command {
+            DbSubcommand::Migrate => {
+                let applied = migrator.migrate()?;
+                if applied.is_empty() {
+                    println!(
+                        "Evolve schema is up to date at version {}",
+                        migrator.latest_version()
+                    );
+                } else {
+                    println!(
+                        "Applied {} migration(s); Evolve schema is at version {}",
+                        applied.len(),
+                        migrator.latest_version()
+                    );
+                }
+            }
+            DbSubcommand::Status => {
+                let status = migrator.status()?;
+                println!("On-disk Evolve schema version: {}", status.on_disk);
+                println!("Latest supported version:      {}", status.latest);
+                if status.on_disk > status.latest {
+                    println!("The data directory was written by a newer ev-reth.");
+                }
+                for (version, description) in &status.pending {
+                    println!("  pending {version}: {description}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failing(_: &Path) -> Result<(), MigrationError> {
+        Err(MigrationError::Io(std::io::Error::other("disk full")))
+    }
+
+    #[test]
+    fn migrate_applies_pending_and_resumes_after_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let migrations = [
+            MIGRATIONS[0],
+            Migration {
+                version: 2,
+                description: "fails",
+                apply: failing,
+            },
+        ];
+        let migrator = Migrator::with_migrations(dir.path(), &migrations);
+        assert_eq!(migrator.status().unwrap().pending.len(), 2);
+
+        assert!(matches!(
+            migrator.migrate(),
+            Err(MigrationError::Failed { version: 2, .. })
+        ));
+        // The first step stays recorded, so a retry starts at the failed one.
+        assert_eq!(migrator.on_disk_version().unwrap(), 1);
+        assert!(dir.path().join(EVOLVE_DATA_DIR).is_dir());
+
+        let migrator = Migrator::with_migrations(dir.path(), &migrations[..1]);
+        assert_eq!(migrator.migrate().unwrap(), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn migrate_refuses_newer_data_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(SCHEMA_VERSION_FILE), r#"{"version":99}"#).unwrap();
+
+        let migrator = Migrator::new(dir.path());
+        assert!(matches!(
+            migrator.migrate(),
+            Err(MigrationError::NewerOnDisk {
+                on_disk: 99,
+                supported: 1
+            })
+        ));
+        assert!(!dir.path().join(EVOLVE_DATA_DIR).exists());
+    }
+}
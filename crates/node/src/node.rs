@@ -12,8 +12,8 @@ use reth_ethereum::{
     node::{
         api::{EngineTypes, FullNodeComponents, FullNodeTypes, NodeTypes, PayloadTypes},
         builder::{
-            components::{BasicPayloadServiceBuilder, ComponentsBuilder},
-            rpc::RpcAddOns,
+            components::ComponentsBuilder,
+            rpc::{BasicEngineApiBuilder, BasicEngineValidatorBuilder, RpcAddOns},
             DebugNode, Node, NodeAdapter,
         },
         node::EthereumNetworkBuilder,
@@ -26,9 +26,16 @@ use std::sync::Arc;
 use tracing::info;
 
 use crate::{
-    attributes::EvolveEnginePayloadAttributes, executor::EvolveExecutorBuilder,
-    payload_service::EvolvePayloadBuilderBuilder, payload_types::EvBuiltPayload,
-    rpc::EvEthApiBuilder, txpool::EvolvePoolBuilder, validator::EvolveEngineValidatorBuilder,
+    attributes::EvolveEnginePayloadAttributes,
+    config::{ResolvedEvolveConfig, SharedEvolveConfig},
+    executor::EvolveExecutorBuilder,
+    load_shed::{LoadShedder, RpcLoadShedLayer},
+    payload_jobs::EvolvePayloadServiceBuilder,
+    payload_service::EvolvePayloadBuilderBuilder,
+    payload_types::{EvBuiltPayload, EvPayloadEnvelope},
+    rpc::EvEthApiBuilder,
+    txpool::EvolvePoolBuilder,
+    validator::EvolveEngineValidatorBuilder,
 };
 
 /// Evolve engine types - uses custom payload attributes that support transactions.
@@ -58,21 +65,36 @@ impl PayloadTypes for EvolveEngineTypes {
 impl EngineTypes for EvolveEngineTypes {
     type ExecutionPayloadEnvelopeV1 = ExecutionPayloadV1;
     type ExecutionPayloadEnvelopeV2 = ExecutionPayloadEnvelopeV2;
-    type ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3;
-    type ExecutionPayloadEnvelopeV4 = ExecutionPayloadEnvelopeV4;
-    type ExecutionPayloadEnvelopeV5 = ExecutionPayloadEnvelopeV5;
-    type ExecutionPayloadEnvelopeV6 = ExecutionPayloadEnvelopeV6;
+    type ExecutionPayloadEnvelopeV3 = EvPayloadEnvelope<ExecutionPayloadEnvelopeV3>;
+    type ExecutionPayloadEnvelopeV4 = EvPayloadEnvelope<ExecutionPayloadEnvelopeV4>;
+    type ExecutionPayloadEnvelopeV5 = EvPayloadEnvelope<ExecutionPayloadEnvelopeV5>;
+    type ExecutionPayloadEnvelopeV6 = EvPayloadEnvelope<ExecutionPayloadEnvelopeV6>;
 }
 
 /// Evolve node type.
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-pub struct EvolveNode {}
+pub struct EvolveNode {
+    /// Evolve configuration shared by the node's components.
+    config: SharedEvolveConfig,
+    /// Load shedder fed by the payload builder and consulted by the RPC middleware.
+    load_shedder: Arc<LoadShedder>,
+}
 
 impl EvolveNode {
-    /// Create a new evolve node with the given arguments.
-    pub const fn new() -> Self {
-        Self {}
+    /// Creates a new evolve node, resolving its configuration from the chain spec when the
+    /// first component is built.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new evolve node running with the already resolved `config`.
+    pub fn with_config(config: Arc<ResolvedEvolveConfig>) -> Self {
+        let load_shedder = Arc::new(LoadShedder::new(config.config().node.load_shedding));
+        Self {
+            config: SharedEvolveConfig::new(config),
+            load_shedder,
+        }
     }
 }
 
@@ -83,8 +105,15 @@ impl NodeTypes for EvolveNode {
     type Payload = EvolveEngineTypes;
 }
 
-/// Evolve node addons configuring RPC types with custom engine validator.
-pub type EvolveNodeAddOns<N> = RpcAddOns<N, EvEthApiBuilder, EvolveEngineValidatorBuilder>;
+/// Evolve node addons configuring RPC types with custom engine validator and RPC load shedding.
+pub type EvolveNodeAddOns<N> = RpcAddOns<
+    N,
+    EvEthApiBuilder,
+    EvolveEngineValidatorBuilder,
+    BasicEngineApiBuilder<EvolveEngineValidatorBuilder>,
+    BasicEngineValidatorBuilder<EvolveEngineValidatorBuilder>,
+    RpcLoadShedLayer,
+>;
 
 impl<N> Node<N> for EvolveNode
 where
@@ -93,7 +122,7 @@ where
     type ComponentsBuilder = ComponentsBuilder<
         N,
         EvolvePoolBuilder,
-        BasicPayloadServiceBuilder<EvolvePayloadBuilderBuilder>,
+        EvolvePayloadServiceBuilder<EvolvePayloadBuilderBuilder>,
         EthereumNetworkBuilder,
         EvolveExecutorBuilder,
         evolve_ev_reth::consensus::EvolveConsensusBuilder,
@@ -103,17 +132,19 @@ where
     fn components_builder(&self) -> Self::ComponentsBuilder {
         ComponentsBuilder::default()
             .node_types::<N>()
-            .pool(EvolvePoolBuilder::default())
-            .executor(EvolveExecutorBuilder::default())
-            .payload(BasicPayloadServiceBuilder::new(
-                EvolvePayloadBuilderBuilder::new(),
+            .pool(EvolvePoolBuilder::new(self.config.clone()))
+            .executor(EvolveExecutorBuilder::new(self.config.clone()))
+            .payload(EvolvePayloadServiceBuilder::new(
+                EvolvePayloadBuilderBuilder::new(self.config.clone(), self.load_shedder.clone()),
+                self.config.clone(),
             ))
             .network(EthereumNetworkBuilder::default())
             .consensus(evolve_ev_reth::consensus::EvolveConsensusBuilder::default())
     }
 
     fn add_ons(&self) -> Self::AddOns {
-        EvolveNodeAddOns::default()
+        RpcAddOns::<NodeAdapter<N>, EvEthApiBuilder, EvolveEngineValidatorBuilder>::default()
+            .with_rpc_middleware(RpcLoadShedLayer::new(self.load_shedder.clone()))
     }
 }
 
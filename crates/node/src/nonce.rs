@@ -0,0 +1,156 @@
+use alloy_primitives::{aliases::U192, Address, U256, U64};
+use async_trait::async_trait;
+use ev_precompiles::nonce_manager::nonce_lane_address;
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_storage_api::{AccountReader, StateProviderFactory};
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of missing nonces listed in an `evolve_getNextNonce` response.
+pub const MAX_REPORTED_GAPS: usize = 64;
+
+/// Nonce assignment returned by `evolve_getNextNonce`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcNextNonce {
+    /// Nonce the next transaction of the sender should use.
+    pub nonce: U64,
+    /// Nonce of the sender in the latest block.
+    pub latest_nonce: U64,
+    /// Number of the sender's transactions in the pool at or above `latestNonce`.
+    pub pooled: U64,
+    /// Nonces missing between `latestNonce` and the highest pooled nonce, up to
+    /// [`MAX_REPORTED_GAPS`]. Pooled transactions above a gap wait until it is filled.
+    pub gaps: Vec<U64>,
+}
+
+/// Picks the next nonce of a sender from its latest nonce and the nonces of its pooled
+/// transactions.
+///
+/// The first missing nonce is returned, so a relayer fills the gaps that hold queued
+/// transactions back before extending the sequence.
+pub fn next_nonce(latest_nonce: u64, pooled: impl IntoIterator<Item = u64>) -> RpcNextNonce {
+    let mut pooled: Vec<u64> = pooled
+        .into_iter()
+        .filter(|nonce| *nonce >= latest_nonce)
+        .collect();
+    pooled.sort_unstable();
+    pooled.dedup();
+
+    let mut expected = latest_nonce;
+    let mut first_gap = None;
+    let mut gaps = Vec::new();
+    for &nonce in &pooled {
+        if nonce > expected {
+            first_gap.get_or_insert(expected);
+            gaps.extend(
+                (expected..nonce)
+                    .take(MAX_REPORTED_GAPS - gaps.len())
+                    .map(U64::from),
+            );
+        }
+        expected = nonce + 1;
+    }
+
+    RpcNextNonce {
+        nonce: U64::from(first_gap.unwrap_or(expected)),
+        latest_nonce: U64::from(latest_nonce),
+        pooled: U64::from(pooled.len()),
+        gaps,
+    }
+}
+
+/// Nonce management RPC API for relayers.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveNonceApi {
+    /// Returns the nonce the next transaction of `address` should use in nonce lane `lane`,
+    /// `0` by default, accounting for the lane's pending and queued pool transactions.
+    #[method(name = "getNextNonce")]
+    async fn get_next_nonce(&self, address: Address, lane: Option<U256>)
+        -> RpcResult<RpcNextNonce>;
+}
+
+/// Implementation of the nonce management RPC.
+#[derive(Debug)]
+pub struct EvolveNonceApiImpl<Provider, Pool> {
+    provider: Provider,
+    pool: Pool,
+}
+
+impl<Provider, Pool> EvolveNonceApiImpl<Provider, Pool> {
+    /// Creates a new instance reading state from `provider` and pooled transactions from `pool`.
+    pub const fn new(provider: Provider, pool: Pool) -> Self {
+        Self { provider, pool }
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> EvolveNonceApiServer for EvolveNonceApiImpl<Provider, Pool>
+where
+    Provider: StateProviderFactory + Send + Sync + 'static,
+    Pool: TransactionPool + 'static,
+{
+    async fn get_next_nonce(
+        &self,
+        address: Address,
+        lane: Option<U256>,
+    ) -> RpcResult<RpcNextNonce> {
+        let lane = lane.unwrap_or_default().into_limbs();
+        if lane[3] != 0 {
+            return Err(ErrorObject::owned(
+                INVALID_PARAMS_CODE,
+                "nonce lane does not fit in 192 bits",
+                None::<()>,
+            ));
+        }
+        // A lane's sequence is the nonce of its lane account, which the pool also files the
+        // lane's transactions under.
+        let address = nonce_lane_address(address, U192::from_limbs([lane[0], lane[1], lane[2]]));
+
+        let latest_nonce = self
+            .provider
+            .latest()
+            .and_then(|state| state.basic_account(&address))
+            .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))?
+            .map_or(0, |account| account.nonce);
+        // Sponsored EvNode transactions are pooled under their signer, so their nonces are
+        // counted like any other.
+        let pooled = self
+            .pool
+            .get_transactions_by_sender(address)
+            .into_iter()
+            .map(|tx| tx.transaction.nonce());
+        Ok(next_nonce(latest_nonce, pooled))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_nonce_fills_gaps_before_extending() {
+        let contiguous = next_nonce(5, [5, 6, 7]);
+        assert_eq!(contiguous.nonce, U64::from(8));
+        assert_eq!(contiguous.pooled, U64::from(3));
+        assert!(contiguous.gaps.is_empty());
+
+        // Stale nonces below the latest are ignored, and a queued transaction at 9 leaves
+        // 7 and 8 missing.
+        let gapped = next_nonce(5, [3, 6, 5, 9, 9]);
+        assert_eq!(gapped.nonce, U64::from(7));
+        assert_eq!(gapped.pooled, U64::from(3));
+        assert_eq!(gapped.gaps, vec![U64::from(7), U64::from(8)]);
+
+        let far = next_nonce(0, [1_000_000]);
+        assert_eq!(far.nonce, U64::ZERO);
+        assert_eq!(far.gaps.len(), MAX_REPORTED_GAPS);
+
+        assert_eq!(next_nonce(2, []).nonce, U64::from(2));
+    }
+}
@@ -0,0 +1,408 @@
+use alloy_eips::eip7685::Requests;
+use alloy_primitives::{Address, Bytes, B256};
+use alloy_rlp::Encodable;
+use alloy_rpc_types::{
+    engine::{
+        CancunPayloadFields, ExecutionData, ExecutionPayload, ExecutionPayloadSidecar,
+        ExecutionPayloadV3, PraguePayloadFields,
+    },
+    trace::geth::{CallFrame, GethDebugTracingOptions, GethTrace, TraceResult},
+};
+use async_trait::async_trait;
+use ev_primitives::{CallsSummary, EvTxEnvelope};
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_ethereum::node::api::PayloadValidator;
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_primitives_traits::RecoveredBlock;
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::DebugApiServer;
+use reth_storage_api::{HashedPostStateProvider, StateProviderFactory, StateRootProvider};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::{
+    abi_registry::{AbiRegistry, DecodedFunction},
+    batch_calls::{decode_calls, RpcBatchCall},
+    rpc::EvTransactionRequest,
+    EvolveEngineValidator, EvolveEvmConfig,
+};
+
+/// Result of re-executing a payload with `evolve_debugExecutePayload`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadExecutionReport {
+    /// Block number of the payload.
+    pub block_number: u64,
+    /// Block hash as computed by ev-reth.
+    pub block_hash: B256,
+    /// Gas used according to the payload header.
+    pub expected_gas_used: u64,
+    /// Gas used by the re-execution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<u64>,
+    /// State root according to the payload header.
+    pub expected_state_root: B256,
+    /// State root computed from the re-execution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_root: Option<B256>,
+    /// Execution error, if the payload could not be executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Per-transaction traces produced by the requested tracer.
+    pub traces: Vec<TraceResult>,
+    /// Call frames of `callTracer` traces whose function is in the ABI registry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decoded_calls: Vec<DecodedTraceCall>,
+    /// Every call of the EvNode transactions in the payload, whose traces start from a single
+    /// call.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub batches: Vec<TracedBatch>,
+}
+
+/// The calls of an EvNode transaction in a traced payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracedBatch {
+    /// Index of the transaction within the payload.
+    pub transaction_index: u64,
+    /// Call count, deployments, total value and targets of the batch.
+    pub summary: CallsSummary,
+    /// The calls in batch order, decoded with the ABI registry.
+    pub calls: Vec<RpcBatchCall>,
+}
+
+/// A `callTracer` frame decoded with the ABI registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedTraceCall {
+    /// Index of the transaction within the payload.
+    pub transaction_index: u64,
+    /// Nesting depth of the frame, zero for the top-level call.
+    pub depth: u64,
+    /// Called address.
+    pub to: Option<Address>,
+    /// Decoded function.
+    pub function: DecodedFunction,
+}
+
+/// Evolve debug RPC API trait.
+///
+/// Served on the authenticated Engine API endpoint only.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveDebugApi {
+    /// Re-executes a payload on top of its parent state without persisting anything.
+    ///
+    /// Takes the same arguments as `engine_newPayloadV3`/`engine_newPayloadV4` (pass
+    /// `executionRequests` for V4 payloads), followed by the tracer options used for the
+    /// per-transaction traces.
+    #[method(name = "debugExecutePayload")]
+    async fn debug_execute_payload(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        execution_requests: Option<Requests>,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<PayloadExecutionReport>;
+}
+
+/// Implementation of the Evolve debug RPC API.
+#[derive(Debug)]
+pub struct EvolveDebugApiImpl<Provider, Debug> {
+    provider: Provider,
+    evm_config: EvolveEvmConfig,
+    validator: EvolveEngineValidator,
+    debug: Debug,
+    abi_registry: Arc<AbiRegistry>,
+}
+
+impl<Provider, Debug> EvolveDebugApiImpl<Provider, Debug> {
+    /// Creates a new instance.
+    ///
+    /// `debug` serves the tracing; it replays the block the same way `debug_traceBlock`
+    /// replays an RLP-encoded block. Call traces are decoded with `abi_registry`.
+    pub const fn new(
+        provider: Provider,
+        evm_config: EvolveEvmConfig,
+        validator: EvolveEngineValidator,
+        debug: Debug,
+        abi_registry: Arc<AbiRegistry>,
+    ) -> Self {
+        Self {
+            provider,
+            evm_config,
+            validator,
+            debug,
+            abi_registry,
+        }
+    }
+}
+
+#[async_trait]
+impl<Provider, Debug> EvolveDebugApiServer for EvolveDebugApiImpl<Provider, Debug>
+where
+    Provider: StateProviderFactory + Clone + 'static,
+    Debug: DebugApiServer<EvTransactionRequest> + Send + Sync + 'static,
+{
+    async fn debug_execute_payload(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        execution_requests: Option<Requests>,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<PayloadExecutionReport> {
+        let cancun = CancunPayloadFields::new(parent_beacon_block_root, versioned_hashes);
+        let sidecar = match execution_requests {
+            Some(requests) => {
+                ExecutionPayloadSidecar::v4(cancun, PraguePayloadFields::new(requests))
+            }
+            None => ExecutionPayloadSidecar::v3(cancun),
+        };
+        let block = self
+            .validator
+            .ensure_well_formed_payload(ExecutionData::new(ExecutionPayload::V3(payload), sidecar))
+            .map_err(|err| ErrorObject::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>))?;
+
+        let mut report = PayloadExecutionReport {
+            block_number: block.header().number,
+            block_hash: block.hash(),
+            expected_gas_used: block.header().gas_used,
+            gas_used: None,
+            expected_state_root: block.header().state_root,
+            state_root: None,
+            error: None,
+            traces: Vec::new(),
+            decoded_calls: Vec::new(),
+            batches: traced_batches(&block.body().transactions, &self.abi_registry),
+        };
+
+        let provider = self.provider.clone();
+        let evm_config = self.evm_config.clone();
+        let mut rlp = Vec::new();
+        block.clone_block().encode(&mut rlp);
+        match tokio::task::spawn_blocking(move || execute_block(&provider, &evm_config, &block))
+            .await
+            .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))?
+        {
+            Ok((gas_used, state_root)) => {
+                report.gas_used = Some(gas_used);
+                report.state_root = Some(state_root);
+            }
+            Err(err) => report.error = Some(err),
+        }
+
+        // Traces are still useful for payloads that fail to execute, up to the failing
+        // transaction, so a tracing error only replaces a missing execution error.
+        match self.debug.debug_trace_block(Bytes::from(rlp), opts).await {
+            Ok(traces) => {
+                report.decoded_calls = decode_call_traces(&traces, &self.abi_registry);
+                report.traces = traces;
+            }
+            Err(err) => {
+                report
+                    .error
+                    .get_or_insert_with(|| err.message().to_string());
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Executes `block` on top of its parent state, returning the gas used and the resulting
+/// state root. Nothing is written to the database.
+fn execute_block<Provider>(
+    provider: &Provider,
+    evm_config: &EvolveEvmConfig,
+    block: &RecoveredBlock<ev_primitives::Block>,
+) -> Result<(u64, B256), String>
+where
+    Provider: StateProviderFactory,
+{
+    let state = provider
+        .history_by_block_hash(block.header().parent_hash)
+        .map_err(|err| format!("parent state unavailable: {err}"))?;
+    let output = evm_config
+        .batch_executor(StateProviderDatabase::new(&state))
+        .execute(block)
+        .map_err(|err| err.to_string())?;
+    let state_root = state
+        .state_root(state.hashed_post_state(&output.state))
+        .map_err(|err| format!("failed to compute state root: {err}"))?;
+    Ok((output.result.gas_used, state_root))
+}
+
+/// Lists the calls of the EvNode transactions among `transactions`.
+fn traced_batches(transactions: &[EvTxEnvelope], registry: &AbiRegistry) -> Vec<TracedBatch> {
+    transactions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, tx)| match tx {
+            EvTxEnvelope::EvNode(ev) => Some(TracedBatch {
+                transaction_index: index as u64,
+                summary: ev.tx().calls_summary(),
+                calls: decode_calls(&ev.tx().calls, registry),
+            }),
+            EvTxEnvelope::Ethereum(_) => None,
+        })
+        .collect()
+}
+
+/// Decodes the frames of `callTracer` traces whose function is in `registry`, in call order.
+fn decode_call_traces(traces: &[TraceResult], registry: &AbiRegistry) -> Vec<DecodedTraceCall> {
+    fn visit(
+        frame: &CallFrame,
+        transaction_index: u64,
+        depth: u64,
+        registry: &AbiRegistry,
+        decoded: &mut Vec<DecodedTraceCall>,
+    ) {
+        if let Some(function) = registry.decode(frame.to, &frame.input) {
+            decoded.push(DecodedTraceCall {
+                transaction_index,
+                depth,
+                to: frame.to,
+                function,
+            });
+        }
+        for call in &frame.calls {
+            visit(call, transaction_index, depth + 1, registry, decoded);
+        }
+    }
+
+    let mut decoded = Vec::new();
+    if registry.is_empty() {
+        return decoded;
+    }
+    for (index, trace) in traces.iter().enumerate() {
+        if let TraceResult::Success {
+            result: GethTrace::CallTracer(frame),
+            ..
+        } = trace
+        {
+            visit(frame, index as u64, 0, registry, &mut decoded);
+        }
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_omits_missing_results() {
+        let report = PayloadExecutionReport {
+            block_number: 5,
+            block_hash: B256::repeat_byte(0x01),
+            expected_gas_used: 21_000,
+            gas_used: None,
+            expected_state_root: B256::repeat_byte(0x02),
+            state_root: None,
+            error: Some("parent state unavailable".to_string()),
+            traces: Vec::new(),
+            decoded_calls: Vec::new(),
+            batches: Vec::new(),
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["blockNumber"], 5);
+        assert_eq!(json["error"], "parent state unavailable");
+        assert!(json.get("stateRoot").is_none());
+        assert!(json.get("gasUsed").is_none());
+        assert!(json.get("decodedCalls").is_none());
+        assert!(json.get("batches").is_none());
+    }
+
+    #[test]
+    fn decodes_nested_call_frames() {
+        let token = alloy_primitives::address!("0x00000000000000000000000000000000000000aa");
+        let registry = AbiRegistry::default();
+        registry
+            .register(
+                token,
+                serde_json::from_str(
+                    r#"[{"type":"function","name":"pause","stateMutability":"nonpayable",
+                        "inputs":[],"outputs":[]}]"#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let pause = Bytes::from_static(&[0x84, 0x56, 0xcb, 0x59]);
+        let frame = CallFrame {
+            to: Some(Address::repeat_byte(0x01)),
+            input: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+            calls: vec![CallFrame {
+                to: Some(token),
+                input: pause,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let traces = vec![
+            TraceResult::Success {
+                result: GethTrace::Default(Default::default()),
+                tx_hash: None,
+            },
+            TraceResult::Success {
+                result: GethTrace::CallTracer(frame),
+                tx_hash: None,
+            },
+        ];
+
+        let decoded = decode_call_traces(&traces, &registry);
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].transaction_index, 1);
+        assert_eq!(decoded[0].depth, 1);
+        assert_eq!(decoded[0].to, Some(token));
+        assert_eq!(decoded[0].function.signature, "pause()");
+        assert_eq!(decoded[0].function.args, Some(Vec::new()));
+    }
+
+    #[test]
+    fn lists_every_batch_call() {
+        use alloy_consensus::{Signed, TxEip1559};
+        use alloy_primitives::{Signature, U256};
+        use ev_primitives::{Call, EvNodeTransaction};
+
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let recipient = Address::repeat_byte(0x0b);
+        let batch = EvNodeTransaction {
+            chain_id: 1,
+            nonce: 0,
+            nonce_key: Default::default(),
+            max_priority_fee_per_gas: 1,
+            max_fee_per_gas: 2,
+            gas_limit: 100_000,
+            calls: vec![
+                Call::transfer(recipient, U256::from(1)),
+                Call::transfer(recipient, U256::from(2)),
+            ],
+            access_list: Default::default(),
+            revert_mode: Default::default(),
+            valid_after: None,
+            valid_until: None,
+            session: None,
+            fee_payer_signature: None,
+            max_sponsor_fee: None,
+            p256_signature: None,
+        };
+        let transactions = vec![
+            EvTxEnvelope::Ethereum(Signed::new_unhashed(TxEip1559::default(), signature).into()),
+            EvTxEnvelope::EvNode(Signed::new_unhashed(batch, signature)),
+        ];
+
+        let batches = traced_batches(&transactions, &AbiRegistry::default());
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].transaction_index, 1);
+        assert_eq!(batches[0].summary.call_count, 2);
+        assert_eq!(batches[0].summary.total_value, U256::from(3));
+        assert_eq!(batches[0].calls.len(), 2);
+        assert_eq!(batches[0].calls[1].value, U256::from(2));
+    }
+}
@@ -0,0 +1,334 @@
+//! Expiry of payload jobs whose payload is never fetched.
+//!
+//! reth keeps rebuilding the payload of a job until ev-node fetches it or the builder deadline
+//! passes. An ev-node that flaps between `engine_forkchoiceUpdated` and `engine_getPayload`
+//! leaves such jobs behind, each holding a build task and its cached state. Jobs started by
+//! [`EvolvePayloadServiceBuilder`] end once they have gone unfetched for
+//! `--ev-reth.payload-job-ttl-ms`, which cancels their pending build and frees them.
+//!
+//! `ev_reth_payload_jobs_in_flight` reports the number of live jobs and
+//! `ev_reth_payload_jobs_expired_total` the number of jobs dropped unfetched.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use alloy_rpc_types_engine::PayloadId;
+use eyre::WrapErr;
+use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
+use reth_ethereum::{
+    chainspec::ChainSpec,
+    node::{
+        api::{FullNodeTypes, NodeTypes},
+        builder::{
+            components::{PayloadBuilderBuilder, PayloadServiceBuilder},
+            BuilderContext,
+        },
+    },
+    pool::TransactionPool,
+};
+use reth_payload_builder::{
+    KeepPayloadJobAlive, PayloadBuilderError, PayloadBuilderHandle, PayloadBuilderService,
+    PayloadJob, PayloadJobGenerator,
+};
+use reth_payload_primitives::PayloadKind;
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::{CanonStateNotification, CanonStateSubscriptions};
+use tokio::time::Sleep;
+use tracing::debug;
+
+use crate::{
+    config::SharedEvolveConfig,
+    error::{payload_job_error, EngineErrorStore, EvolveEngineError},
+};
+
+/// Default milliseconds a payload job may go unfetched before it is dropped.
+pub const DEFAULT_PAYLOAD_JOB_TTL_MS: u64 = 10_000;
+
+/// Payload service builder running reth's basic payload jobs with [`ExpiringPayloadJob`]s.
+///
+/// Jobs expire after the payload job TTL of the node's [`SharedEvolveConfig`].
+#[derive(Debug, Clone, Default)]
+pub struct EvolvePayloadServiceBuilder<PB> {
+    payload_builder: PB,
+    config: SharedEvolveConfig,
+}
+
+impl<PB> EvolvePayloadServiceBuilder<PB> {
+    /// Create a new service builder building payloads with the given payload builder.
+    pub const fn new(payload_builder: PB, config: SharedEvolveConfig) -> Self {
+        Self {
+            payload_builder,
+            config,
+        }
+    }
+}
+
+impl<Node, Pool, PB, EvmConfig> PayloadServiceBuilder<Node, Pool, EvmConfig>
+    for EvolvePayloadServiceBuilder<PB>
+where
+    Node: FullNodeTypes<Types: NodeTypes<ChainSpec = ChainSpec>>,
+    Pool: TransactionPool,
+    EvmConfig: Send,
+    PB: PayloadBuilderBuilder<Node, Pool, EvmConfig>,
+{
+    async fn spawn_payload_builder_service(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+        evm_config: EvmConfig,
+    ) -> eyre::Result<PayloadBuilderHandle<<Node::Types as NodeTypes>::Payload>> {
+        let config = self
+            .config
+            .get_or_resolve(&ctx.chain_spec())
+            .wrap_err("failed to load evolve config from chain spec")?;
+        let ttl = config.node.payload_job_ttl;
+        let errors = config.handles.engine_errors.clone();
+        let payload_builder = self
+            .payload_builder
+            .build_payload_builder(ctx, pool, evm_config)
+            .await?;
+
+        let conf = ctx.config().builder.clone();
+        let payload_job_config = BasicPayloadJobGeneratorConfig::default()
+            .interval(conf.interval)
+            .deadline(conf.deadline)
+            .max_payload_tasks(conf.max_payload_tasks);
+        let payload_generator = ExpiringPayloadJobGenerator::new(
+            BasicPayloadJobGenerator::with_builder(
+                ctx.provider().clone(),
+                ctx.task_executor().clone(),
+                payload_job_config,
+                payload_builder,
+            ),
+            ttl,
+            errors,
+        );
+        let (payload_service, payload_service_handle) =
+            PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
+        ctx.task_executor()
+            .spawn_critical("payload builder service", Box::pin(payload_service));
+
+        Ok(payload_service_handle)
+    }
+}
+
+/// Job generator wrapping the jobs of another generator in [`ExpiringPayloadJob`]s.
+#[derive(Debug)]
+pub struct ExpiringPayloadJobGenerator<G> {
+    inner: G,
+    ttl: Option<Duration>,
+    /// Store the errors of failed and expired jobs are recorded in.
+    errors: Arc<EngineErrorStore>,
+}
+
+impl<G> ExpiringPayloadJobGenerator<G> {
+    /// Create a generator whose jobs end once unfetched for `ttl`, or never if `None`,
+    /// recording their errors in `errors`.
+    pub const fn new(inner: G, ttl: Option<Duration>, errors: Arc<EngineErrorStore>) -> Self {
+        Self { inner, ttl, errors }
+    }
+}
+
+impl<G> PayloadJobGenerator for ExpiringPayloadJobGenerator<G>
+where
+    G: PayloadJobGenerator,
+    G::Job: Unpin,
+{
+    type Job = ExpiringPayloadJob<G::Job>;
+
+    fn new_payload_job(
+        &self,
+        attr: <Self::Job as PayloadJob>::PayloadAttributes,
+        id: PayloadId,
+    ) -> Result<Self::Job, PayloadBuilderError> {
+        let job = self
+            .inner
+            .new_payload_job(attr, id)
+            .map_err(|err| payload_job_error(&self.errors, id, err))?;
+        Ok(ExpiringPayloadJob::new(
+            job,
+            id,
+            self.ttl,
+            self.errors.clone(),
+        ))
+    }
+
+    fn on_new_state<N: NodePrimitives>(&mut self, new_state: CanonStateNotification<N>) {
+        self.inner.on_new_state(new_state);
+    }
+}
+
+/// Payload job that ends once its payload has gone unfetched for a time-to-live.
+///
+/// Ending the job drops the inner job, which cancels its pending build. Once the payload is
+/// fetched the job lives as long as the inner job does.
+#[derive(Debug)]
+pub struct ExpiringPayloadJob<J> {
+    inner: J,
+    payload_id: PayloadId,
+    /// Fires when the job expires; cleared once the payload is fetched.
+    expiry: Option<Pin<Box<Sleep>>>,
+    /// Store the expiry is recorded in.
+    errors: Arc<EngineErrorStore>,
+}
+
+impl<J> ExpiringPayloadJob<J> {
+    /// Wrap `inner`, expiring it once unfetched for `ttl`, or never if `None`, and recording
+    /// the expiry in `errors`.
+    pub fn new(
+        inner: J,
+        payload_id: PayloadId,
+        ttl: Option<Duration>,
+        errors: Arc<EngineErrorStore>,
+    ) -> Self {
+        metrics::gauge!("ev_reth_payload_jobs_in_flight").increment(1.0);
+        Self {
+            inner,
+            payload_id,
+            expiry: ttl.map(|ttl| Box::pin(tokio::time::sleep(ttl))),
+            errors,
+        }
+    }
+
+    /// Returns whether the job still expires if its payload is not fetched.
+    pub const fn is_expiring(&self) -> bool {
+        self.expiry.is_some()
+    }
+}
+
+impl<J> Drop for ExpiringPayloadJob<J> {
+    fn drop(&mut self) {
+        metrics::gauge!("ev_reth_payload_jobs_in_flight").decrement(1.0);
+    }
+}
+
+impl<J> Future for ExpiringPayloadJob<J>
+where
+    J: PayloadJob + Unpin,
+{
+    type Output = Result<(), PayloadBuilderError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if let Some(expiry) = &mut this.expiry {
+            if expiry.as_mut().poll(cx).is_ready() {
+                debug!(
+                    target: "ev-reth",
+                    payload_id = %this.payload_id,
+                    "dropping payload job that was never fetched"
+                );
+                metrics::counter!("ev_reth_payload_jobs_expired_total").increment(1);
+                this.errors
+                    .insert(this.payload_id, EvolveEngineError::PayloadExpired.data());
+                return Poll::Ready(Ok(()));
+            }
+        }
+        Pin::new(&mut this.inner).poll(cx)
+    }
+}
+
+impl<J> PayloadJob for ExpiringPayloadJob<J>
+where
+    J: PayloadJob + Unpin,
+{
+    type PayloadAttributes = J::PayloadAttributes;
+    type ResolvePayloadFuture = J::ResolvePayloadFuture;
+    type BuiltPayload = J::BuiltPayload;
+
+    fn best_payload(&self) -> Result<Self::BuiltPayload, PayloadBuilderError> {
+        self.inner.best_payload()
+    }
+
+    fn payload_attributes(&self) -> Result<Self::PayloadAttributes, PayloadBuilderError> {
+        self.inner.payload_attributes()
+    }
+
+    fn payload_timestamp(&self) -> Result<u64, PayloadBuilderError> {
+        self.inner.payload_timestamp()
+    }
+
+    fn resolve_kind(
+        &mut self,
+        kind: PayloadKind,
+    ) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
+        self.expiry = None;
+        self.inner.resolve_kind(kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{attributes::EvolveEnginePayloadAttributes, payload_types::EvBuiltPayload};
+    use futures::future::{ready, Ready};
+
+    /// Job that never finishes building and has no payload.
+    #[derive(Debug)]
+    struct PendingJob;
+
+    impl Future for PendingJob {
+        type Output = Result<(), PayloadBuilderError>;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Poll::Pending
+        }
+    }
+
+    impl PayloadJob for PendingJob {
+        type PayloadAttributes = EvolveEnginePayloadAttributes;
+        type ResolvePayloadFuture = Ready<Result<EvBuiltPayload, PayloadBuilderError>>;
+        type BuiltPayload = EvBuiltPayload;
+
+        fn best_payload(&self) -> Result<Self::BuiltPayload, PayloadBuilderError> {
+            Err(PayloadBuilderError::MissingPayload)
+        }
+
+        fn payload_attributes(&self) -> Result<Self::PayloadAttributes, PayloadBuilderError> {
+            Err(PayloadBuilderError::MissingPayload)
+        }
+
+        fn resolve_kind(
+            &mut self,
+            _kind: PayloadKind,
+        ) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
+            (
+                ready(Err(PayloadBuilderError::MissingPayload)),
+                KeepPayloadJobAlive::Yes,
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn unfetched_job_expires() {
+        let ttl = Duration::from_millis(20);
+        let errors = Arc::new(EngineErrorStore::default());
+        let job =
+            ExpiringPayloadJob::new(PendingJob, PayloadId::default(), Some(ttl), errors.clone());
+        let outcome = tokio::time::timeout(Duration::from_secs(5), job)
+            .await
+            .expect("unfetched job should expire");
+        assert!(outcome.is_ok());
+        let recorded = errors.get(&PayloadId::default()).expect("expiry recorded");
+        assert!(recorded.retryable);
+    }
+
+    #[tokio::test]
+    async fn fetched_job_outlives_ttl() {
+        let ttl = Duration::from_millis(20);
+        let errors = Arc::new(EngineErrorStore::default());
+        let mut job =
+            ExpiringPayloadJob::new(PendingJob, PayloadId::default(), Some(ttl), errors.clone());
+        assert!(job.is_expiring());
+        let _ = job.resolve_kind(PayloadKind::Earliest);
+        assert!(!job.is_expiring());
+        assert!(tokio::time::timeout(ttl * 5, job).await.is_err());
+
+        let job = ExpiringPayloadJob::new(PendingJob, PayloadId::default(), None, errors);
+        assert!(tokio::time::timeout(ttl * 5, job).await.is_err());
+    }
+}
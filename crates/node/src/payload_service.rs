@@ -1,9 +1,20 @@
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    ops::Range,
+    sync::Arc,
+    time::Instant,
+};
 
 use crate::tracing_ext::RecordDurationOnDrop;
-use alloy_primitives::{Address, U256};
-use evolve_ev_reth::EvolvePayloadAttributes;
+use alloy_consensus::{
+    transaction::{SignerRecoverable, TxHashRef},
+    Transaction,
+};
+use alloy_primitives::{Address, B256, U256};
+use evolve_ev_reth::{DecryptionKey, EvolvePayloadAttributes};
 use eyre::WrapErr;
+use futures::StreamExt;
 use reth_basic_payload_builder::{
     BuildArguments, BuildOutcome, HeaderForPayload, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
@@ -14,45 +25,64 @@ use reth_ethereum::{
         api::{payload::PayloadAttributes, FullNodeTypes, NodeTypes},
         builder::{components::PayloadBuilderBuilder, BuilderContext},
     },
-    pool::{PoolTransaction, TransactionPool},
+    pool::{PoolTransaction, TransactionPool, ValidPoolTransaction},
     primitives::Header,
 };
 use reth_payload_builder::PayloadBuilderError;
-use reth_provider::HeaderProvider;
+use reth_primitives_traits::SealedBlock;
+use reth_provider::{BlockNumReader, CanonStateSubscriptions, HeaderProvider};
 use reth_revm::cached::CachedReads;
 use tokio::runtime::Handle;
-use tracing::{info, instrument};
+use tracing::{debug, info, instrument};
 
 use alloy_eips::Decodable2718;
 
 use crate::{
-    attributes::EvolveEnginePayloadAttributes, builder::EvolvePayloadBuilder,
-    config::EvolvePayloadBuilderConfig, executor::EvolveEvmConfig, node::EvolveEngineTypes,
+    attributes::EvolveEnginePayloadAttributes,
+    builder::EvolvePayloadBuilder,
+    config::{EvolvePayloadBuilderConfig, NodeHandles, SharedEvolveConfig},
+    error::{payload_job_error, EvolveEngineError},
+    executor::EvolveEvmConfig,
+    load_shed::LoadShedder,
+    node::EvolveEngineTypes,
     payload_types::EvBuiltPayload,
+    pending_block::RpcPendingBlock,
+    speculative::{SpeculationKey, SpeculativeCache},
+    txpool::EvPooledTransaction,
 };
 
 use ev_primitives::{EvPrimitives, TransactionSigned};
-use evolve_ev_reth::config::set_current_block_gas_limit;
+use ev_revm::RecoveredSponsors;
+use evolve_ev_reth::{
+    bundle::BundlePool,
+    config::set_current_block_gas_limit,
+    derive_prev_randao,
+    encrypted::EncryptedPool,
+    maintenance::MaintenanceLane,
+    ordering::{AuditedTx, OrderingPolicy, OrderingReport, OrderingReportStore},
+    timestamp::{TimestampAdjustment, TimestampError},
+    InclusionMix, TimestampPolicy, TxInclusionReason, TxSource,
+};
 
 /// Evolve payload service builder that integrates with the evolve payload builder.
-#[derive(Debug, Clone)]
+///
+/// Operator overrides of the chainspec settings (fee sink, mint admin, ...) belong on the
+/// node's [`SharedEvolveConfig`], so the executor and pool see them too.
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
 pub struct EvolvePayloadBuilderBuilder {
-    config: EvolvePayloadBuilderConfig,
+    config: SharedEvolveConfig,
+    load_shedder: Arc<LoadShedder>,
 }
 
 impl EvolvePayloadBuilderBuilder {
-    /// Create a new builder with evolve args.
-    pub fn new() -> Self {
-        let config = EvolvePayloadBuilderConfig::new();
-        info!("Created Evolve payload builder with config: {:?}", config);
-        Self { config }
-    }
-}
-
-impl Default for EvolvePayloadBuilderBuilder {
-    fn default() -> Self {
-        Self::new()
+    /// Create a new builder reading the node's shared evolve configuration, and reporting
+    /// build times to `load_shedder`.
+    pub const fn new(config: SharedEvolveConfig, load_shedder: Arc<LoadShedder>) -> Self {
+        Self {
+            config,
+            load_shedder,
+        }
     }
 }
 
@@ -66,6 +96,11 @@ where
     pub(crate) config: EvolvePayloadBuilderConfig,
     pub(crate) pool: Pool,
     pub(crate) dev_mode: bool,
+    /// Speculatively built next block, present only in dev mode with
+    /// `--ev-reth.speculative-building`.
+    pub(crate) speculative: Option<Arc<SpeculativeCache>>,
+    /// Load shedder the duration of every build is reported to.
+    pub(crate) load_shedder: Arc<LoadShedder>,
 }
 
 impl<Node, Pool> PayloadBuilderBuilder<Node, Pool, EvolveEvmConfig> for EvolvePayloadBuilderBuilder
@@ -77,9 +112,7 @@ where
             Primitives = EvPrimitives,
         >,
     >,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
-        + Unpin
-        + 'static,
+    Pool: TransactionPool<Transaction = EvPooledTransaction> + Unpin + 'static,
 {
     type PayloadBuilder = EvolveEnginePayloadBuilder<Node::Provider, Pool>;
 
@@ -89,26 +122,19 @@ where
         pool: Pool,
         evm_config: EvolveEvmConfig,
     ) -> eyre::Result<Self::PayloadBuilder> {
-        let chain_spec = ctx.chain_spec();
-        let mut config = EvolvePayloadBuilderConfig::from_chain_spec(&chain_spec)
-            .wrap_err("failed to load evolve config from chain spec")?;
-
-        if self.config.base_fee_sink.is_some() {
-            config.base_fee_sink = self.config.base_fee_sink;
-        }
-        if self.config.base_fee_redirect_activation_height.is_some() {
-            config.base_fee_redirect_activation_height =
-                self.config.base_fee_redirect_activation_height;
-        }
-        if self.config.mint_admin.is_some() {
-            config.mint_admin = self.config.mint_admin;
-        }
-        if self.config.mint_precompile_activation_height.is_some() {
-            config.mint_precompile_activation_height =
-                self.config.mint_precompile_activation_height;
-        }
-
-        config.validate()?;
+        let config = self
+            .config
+            .get_or_resolve(&ctx.chain_spec())
+            .wrap_err("failed to load evolve config from chain spec")?
+            .config()
+            .clone();
+        info!("Created Evolve payload builder with config: {:?}", config);
+        // Bundles may only target blocks after the current tip.
+        let tip = ctx
+            .provider()
+            .best_block_number()
+            .wrap_err("failed to read the chain tip")?;
+        config.handles.bundles.advance_to(tip);
 
         let evolve_builder = Arc::new(EvolvePayloadBuilder::new(
             Arc::new(ctx.provider().clone()),
@@ -116,13 +142,366 @@ where
             config.clone(),
         ));
 
+        let speculative = (ctx.is_dev() && config.node.speculative_building).then(|| {
+            let cache = Arc::new(SpeculativeCache::default());
+            spawn_speculative_builder(
+                ctx,
+                evolve_builder.clone(),
+                pool.clone(),
+                config.handles.clone(),
+                cache.clone(),
+            );
+            info!(target: "ev-reth", "speculative next-block building enabled");
+            cache
+        });
+
         Ok(EvolveEnginePayloadBuilder {
             evolve_builder,
             config,
             pool,
             dev_mode: ctx.is_dev(),
+            speculative,
+            load_shedder: self.load_shedder,
+        })
+    }
+}
+
+/// Sponsors the txpool recovered for those of `transactions` still in `pool`, so the builder
+/// does not recover them again.
+fn pooled_sponsors<Pool>(
+    pool: &Pool,
+    transactions: &[TransactionSigned],
+) -> HashMap<B256, RecoveredSponsors>
+where
+    Pool: TransactionPool<Transaction = EvPooledTransaction>,
+{
+    transactions
+        .iter()
+        .filter_map(|tx| {
+            let sponsors = pool.get(tx.tx_hash())?.transaction.recovered_sponsors();
+            (!sponsors.is_empty()).then_some((*tx.tx_hash(), sponsors))
+        })
+        .collect()
+}
+
+/// Dev-mode candidates for `block_number`: maintenance lane transactions first, then bundles
+/// targeting the block, followed by the remaining pending pool transactions in the order the
+/// builder consumes them. The lane and bundles are read from `handles`.
+fn dev_mode_candidates<Pool>(
+    pool: &Pool,
+    handles: &NodeHandles,
+    block_number: u64,
+) -> Vec<TransactionSigned>
+where
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    let bundle_txs: Vec<TransactionSigned> = handles
+        .bundles
+        .bundles_for_block(block_number)
+        .iter()
+        .flat_map(|bundle| &bundle.transactions)
+        .filter_map(|raw| TransactionSigned::decode_2718_exact(raw.as_ref()).ok())
+        .collect();
+    let bundled: HashSet<B256> = bundle_txs.iter().map(|tx| *tx.tx_hash()).collect();
+    let (maintenance, regular) = handles.maintenance.split(
+        pool.pending_transactions()
+            .into_iter()
+            .filter(|tx| !bundled.contains(tx.hash())),
+        |tx| (*tx.hash(), tx.sender()),
+    );
+    let into_consensus = |tx: Arc<ValidPoolTransaction<Pool::Transaction>>| {
+        tx.transaction.clone_into_consensus().into_inner()
+    };
+
+    let mut candidates: Vec<TransactionSigned> =
+        maintenance.into_iter().map(into_consensus).collect();
+    candidates.extend(bundle_txs);
+    candidates.extend(regular.into_iter().map(into_consensus));
+    candidates
+}
+
+/// Decrypts the transactions in `pool` sealed for one of `keys`, in arrival order.
+///
+/// Returns the decoded transactions and the number of revealed entries; entries that fail
+/// to decrypt or decode are recorded in `decode_failures` at their arrival position.
+fn decrypt_revealed(
+    pool: &EncryptedPool,
+    keys: &[DecryptionKey],
+    candidate_indices: &mut Vec<usize>,
+    decode_failures: &mut Vec<(usize, String)>,
+) -> (Vec<TransactionSigned>, usize) {
+    let revealed = pool.revealed(keys);
+    let count = revealed.len();
+    let mut transactions = Vec::with_capacity(count);
+    for (index, (encrypted, key)) in revealed.into_iter().enumerate() {
+        let decoded = encrypted
+            .open(&key)
+            .map_err(|err| err.to_string())
+            .and_then(|raw| {
+                TransactionSigned::decode_2718_exact(raw.as_ref()).map_err(|err| err.to_string())
+            });
+        match decoded {
+            Ok(tx) => {
+                candidate_indices.push(index);
+                transactions.push(tx);
+            }
+            Err(err) => {
+                tracing::warn!(
+                    id = %encrypted.id(),
+                    key_id = encrypted.key_id,
+                    %err,
+                    "dropping encrypted transaction that failed to decrypt"
+                );
+                decode_failures.push((index, err));
+            }
+        }
+    }
+    if count > 0 {
+        info!(count, "decrypted encrypted transactions");
+    }
+    (transactions, count)
+}
+
+/// Applies the bundles in `pool` targeting `block_number` to the candidate list.
+///
+/// Bundles whose transactions are all present, contiguous and in order are kept; the
+/// transactions of any other bundle are removed. Returns the ranges of the kept bundles and
+/// the candidate index and hash of every removed transaction.
+fn resolve_bundles(
+    pool: &BundlePool,
+    block_number: u64,
+    transactions: &mut Vec<TransactionSigned>,
+    candidate_indices: &mut Vec<usize>,
+) -> (Vec<Range<usize>>, Vec<(usize, B256)>) {
+    let bundles = pool.bundles_for_block(block_number);
+    if bundles.is_empty() {
+        return Default::default();
+    }
+
+    let hashes: Vec<B256> = transactions.iter().map(|tx| *tx.tx_hash()).collect();
+    let incomplete: HashSet<usize> = bundles
+        .iter()
+        .filter(|bundle| bundle.locate(&hashes).is_none())
+        .flat_map(|bundle| {
+            hashes
+                .iter()
+                .enumerate()
+                .filter(|(_, hash)| bundle.tx_hashes.contains(hash))
+                .map(|(position, _)| position)
         })
+        .collect();
+
+    let mut removed = Vec::new();
+    let mut kept = Vec::with_capacity(transactions.len());
+    let mut kept_indices = Vec::with_capacity(candidate_indices.len());
+    for (position, (tx, index)) in transactions
+        .drain(..)
+        .zip(candidate_indices.drain(..))
+        .enumerate()
+    {
+        if incomplete.contains(&position) {
+            removed.push((index, hashes[position]));
+        } else {
+            kept.push(tx);
+            kept_indices.push(index);
+        }
     }
+    *transactions = kept;
+    *candidate_indices = kept_indices;
+
+    let hashes: Vec<B256> = transactions.iter().map(|tx| *tx.tx_hash()).collect();
+    let ranges = bundles
+        .iter()
+        .filter_map(|bundle| bundle.locate(&hashes))
+        .collect();
+    (ranges, removed)
+}
+
+/// Counts the transactions of `block` by the channel that supplied them.
+///
+/// Decrypted transactions and those flagged in `maintenance` come from system channels; all
+/// others were pulled from the txpool in dev mode and sent in the payload attributes
+/// otherwise.
+fn inclusion_mix(
+    block: &SealedBlock<ev_primitives::Block>,
+    decrypted: &HashSet<B256>,
+    maintenance: &MaintenanceLane,
+    dev_mode: bool,
+) -> InclusionMix {
+    let mut mix = InclusionMix::default();
+    for tx in &block.body().transactions {
+        let hash = tx.tx_hash();
+        let source = if decrypted.contains(hash) || maintenance.contains(hash) {
+            TxSource::System
+        } else if dev_mode {
+            TxSource::Pool
+        } else {
+            TxSource::Attributes
+        };
+        mix.record(source);
+    }
+    mix
+}
+
+/// Exports the inclusion mix of the latest block, and running totals across blocks.
+fn record_inclusion_mix(mix: &InclusionMix) {
+    for source in TxSource::ALL {
+        let count = mix.count(source);
+        metrics::counter!("ev_reth_included_transactions_total", "source" => source.as_str())
+            .increment(count);
+        metrics::gauge!("ev_reth_block_included_transactions", "source" => source.as_str())
+            .set(count as f64);
+    }
+}
+
+/// Audits the transaction order of a built block against `policy` off the build path,
+/// logging every offending pair, and records its [`OrderingReport`] in `reports` if given.
+///
+/// Arrival times are read from the txpool right away, before the block's transactions leave
+/// it. Transactions in `exempt`, and those flagged in `maintenance`, were placed by rules that
+/// take precedence over the policy and are not audited.
+fn audit_block_ordering<Pool>(
+    policy: Option<OrderingPolicy>,
+    reports: Option<Arc<OrderingReportStore>>,
+    block: Arc<SealedBlock<ev_primitives::Block>>,
+    pool: &Pool,
+    maintenance: &MaintenanceLane,
+    mut exempt: HashSet<B256>,
+) where
+    Pool: TransactionPool,
+{
+    let arrivals: Vec<Option<Instant>> = block
+        .body()
+        .transactions
+        .iter()
+        .map(|tx| {
+            let hash = tx.tx_hash();
+            if maintenance.contains(hash) {
+                exempt.insert(*hash);
+            }
+            pool.get(hash).map(|pooled| pooled.timestamp)
+        })
+        .collect();
+
+    tokio::task::spawn_blocking(move || {
+        let base_fee = block.base_fee_per_gas.unwrap_or_default();
+        let txs: Vec<AuditedTx> = block
+            .body()
+            .transactions
+            .iter()
+            .zip(arrivals)
+            .enumerate()
+            .filter_map(|(index, (tx, arrival))| {
+                Some(AuditedTx {
+                    index,
+                    hash: *tx.tx_hash(),
+                    sender: tx.recover_signer().ok()?,
+                    to: tx.to(),
+                    tip: tx.effective_tip_per_gas(base_fee).unwrap_or_default(),
+                    arrival,
+                    exempt: exempt.contains(tx.tx_hash()),
+                })
+            })
+            .collect();
+
+        if let Some(reports) = reports {
+            reports.insert(OrderingReport::new(block.number, block.hash(), &txs));
+        }
+        let Some(policy) = policy else {
+            return;
+        };
+        let audit = policy.audit(&txs);
+        metrics::counter!("ev_reth_ordering_violations_total", "policy" => policy.to_string())
+            .increment(audit.violations as u64);
+        if audit.is_compliant() {
+            debug!(
+                target: "ev-reth",
+                block_number = block.number,
+                %policy,
+                checked_pairs = audit.checked_pairs,
+                "block ordering complies with policy"
+            );
+            return;
+        }
+        tracing::warn!(
+            target: "ev-reth",
+            block_number = block.number,
+            %policy,
+            violations = audit.violations,
+            checked_pairs = audit.checked_pairs,
+            "block ordering violates policy"
+        );
+        for violation in &audit.examples {
+            tracing::warn!(
+                target: "ev-reth",
+                block_number = block.number,
+                %policy,
+                %violation,
+                "ordering violation"
+            );
+        }
+    });
+}
+
+/// Spawns a task that builds the next block from the pool every time the canonical head
+/// advances, so a matching `getPayload` can be answered from the cache.
+fn spawn_speculative_builder<Node, Pool>(
+    ctx: &BuilderContext<Node>,
+    evolve_builder: Arc<EvolvePayloadBuilder<Node::Provider>>,
+    pool: Pool,
+    handles: NodeHandles,
+    cache: Arc<SpeculativeCache>,
+) where
+    Node: FullNodeTypes<
+        Types: NodeTypes<
+            Payload = EvolveEngineTypes,
+            ChainSpec = ChainSpec,
+            Primitives = EvPrimitives,
+        >,
+    >,
+    Pool: TransactionPool<Transaction = EvPooledTransaction> + Unpin + 'static,
+{
+    let mut canon_state = ctx.provider().canonical_state_stream();
+    ctx.task_executor().spawn(async move {
+        while let Some(notification) = canon_state.next().await {
+            let tip = notification.tip();
+            let header = tip.header();
+            let Some(mut attributes) =
+                cache.predict_next(tip.hash(), header.number, header.timestamp)
+            else {
+                continue;
+            };
+            let mut transactions = dev_mode_candidates(&pool, &handles, attributes.block_number);
+            let mut candidate_indices = (0..transactions.len()).collect();
+            let (bundles, _) = resolve_bundles(
+                &handles.bundles,
+                attributes.block_number,
+                &mut transactions,
+                &mut candidate_indices,
+            );
+            let sponsors = pooled_sponsors(&pool, &transactions);
+            attributes.transactions = transactions;
+            attributes.bundles = bundles;
+            let key = SpeculationKey::from_attributes(&attributes);
+            match evolve_builder
+                .build_payload_with_sponsors(attributes, &sponsors)
+                .await
+            {
+                Ok((block, _)) => {
+                    debug!(
+                        target: "ev-reth",
+                        block_number = block.number,
+                        tx_count = block.transaction_count(),
+                        "built speculative block"
+                    );
+                    cache.store(key, block);
+                }
+                Err(err) => {
+                    debug!(target: "ev-reth", %err, "speculative block build failed");
+                }
+            }
+        }
+    });
 }
 
 impl<Client, Pool> EvolveEnginePayloadBuilder<Client, Pool>
@@ -148,6 +527,37 @@ where
     }
 }
 
+/// Applies `policy` to the requested payload timestamp.
+///
+/// Returns the timestamp to build with, and the adjustment to record in the build report when
+/// the requested timestamp does not advance past the parent's.
+fn resolve_timestamp(
+    policy: TimestampPolicy,
+    requested: u64,
+    parent_timestamp: u64,
+) -> Result<(u64, Option<TimestampAdjustment>), TimestampError> {
+    let applied = policy.resolve(requested, parent_timestamp)?;
+    let adjustment = (requested <= parent_timestamp).then(|| {
+        if applied != requested {
+            info!(
+                target: "ev-reth",
+                requested,
+                parent_timestamp,
+                applied,
+                %policy,
+                "payload timestamp does not advance past parent; adjusting"
+            );
+        }
+        TimestampAdjustment {
+            policy,
+            requested,
+            parent_timestamp,
+            applied,
+        }
+    });
+    Ok((applied, adjustment))
+}
+
 impl<Client, Pool> PayloadBuilder for EvolveEnginePayloadBuilder<Client, Pool>
 where
     Client: reth_ethereum::provider::StateProviderFactory
@@ -157,9 +567,7 @@ where
         + Send
         + Sync
         + 'static,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
-        + Unpin
-        + 'static,
+    Pool: TransactionPool<Transaction = EvPooledTransaction> + Unpin + 'static,
 {
     type Attributes = EvolveEnginePayloadAttributes;
     type BuiltPayload = EvBuiltPayload;
@@ -174,6 +582,19 @@ where
         args: BuildArguments<Self::Attributes, Self::BuiltPayload>,
     ) -> Result<BuildOutcome<Self::BuiltPayload>, PayloadBuilderError> {
         let _duration = RecordDurationOnDrop::new();
+        let _job = self
+            .config
+            .handles
+            .shutdown
+            .begin_payload_job()
+            .ok_or_else(|| {
+                payload_job_error(
+                    &self.config.handles.engine_errors,
+                    args.config.payload_id(),
+                    EvolveEngineError::ShuttingDown,
+                )
+            })?;
+        let build_started = Instant::now();
         let BuildArguments {
             cached_reads: _,
             config,
@@ -189,6 +610,18 @@ where
 
         info!("building payload");
 
+        // Speculative building can only reuse a block when the attributes are predictable,
+        // so the random values of dev-mode requests are replaced by ones derived from the
+        // parent.
+        if self.speculative.is_some() {
+            attributes.inner.prev_randao =
+                derive_prev_randao(parent_header.hash(), parent_header.mix_hash);
+            attributes.inner.suggested_fee_recipient = Address::ZERO;
+        }
+
+        // Committed to by the build attestation, so taken before any field is consumed.
+        let attributes_hash = attributes.commitment_hash(parent_header.hash());
+
         // Convert Engine API attributes to Evolve payload attributes.
         // If no gas_limit provided, default to the parent header's gas limit (genesis for first block).
         let effective_gas_limit = attributes.gas_limit.unwrap_or(parent_header.gas_limit);
@@ -199,78 +632,232 @@ where
         let fee_recipient =
             self.resolve_fee_recipient(attributes.inner.suggested_fee_recipient, block_number);
 
+        let (timestamp, timestamp_adjustment) = resolve_timestamp(
+            self.config.node.timestamp_policy,
+            attributes.timestamp(),
+            parent_header.timestamp,
+        )
+        .map_err(|err| payload_job_error(&self.config.handles.engine_errors, payload_id, err))?;
+
+        let record_report =
+            attributes.build_report.unwrap_or(false) || self.config.node.build_reports;
+        // Candidate positions of the decoded transactions, plus decode failures, so the
+        // report can refer to the transaction list ev-node actually sent.
+        let mut candidate_indices = Vec::new();
+        let mut decode_failures = Vec::new();
+
+        // Encrypted transactions whose key is revealed for this block run first, in arrival
+        // order, and take the leading candidate positions.
+        let key_ids: Vec<u64> = attributes
+            .decryption_keys
+            .iter()
+            .flatten()
+            .map(|key| key.key_id)
+            .collect();
+        let (mut transactions, encrypted_count) = match attributes.decryption_keys.take() {
+            Some(keys) => decrypt_revealed(
+                &self.config.handles.encrypted,
+                &keys,
+                &mut candidate_indices,
+                &mut decode_failures,
+            ),
+            None => (Vec::new(), 0),
+        };
+        let decrypted: HashSet<B256> = transactions.iter().map(|tx| *tx.tx_hash()).collect();
+
         // In dev mode, pull pending transactions from the txpool.
         // In production, transactions come exclusively from Engine API attributes.
-        let transactions = if self.dev_mode {
-            let pool_txs: Vec<TransactionSigned> = self
-                .pool
-                .pending_transactions()
-                .into_iter()
-                .map(|tx| tx.transaction.clone_into_consensus().into_inner())
-                .collect();
+        let plaintext: Vec<TransactionSigned> = if self.dev_mode {
+            let pool_txs = dev_mode_candidates(&self.pool, &self.config.handles, block_number);
             if !pool_txs.is_empty() {
                 info!(
                     pool_tx_count = pool_txs.len(),
                     "pulling transactions from pool (dev mode)"
                 );
             }
+            candidate_indices.extend(encrypted_count..encrypted_count + pool_txs.len());
             pool_txs
         } else {
-            // Decode transactions from raw bytes.
+            // Decode transactions from raw bytes, mapping the chain's EvNode type alias.
+            let tx_type_alias = self.config.evnode_tx_type_alias();
             attributes
                 .transactions
                 .take()
                 .unwrap_or_default()
                 .into_iter()
-                .filter_map(|tx_bytes| {
-                    match TransactionSigned::decode_2718_exact(tx_bytes.as_ref()) {
-                        Ok(tx) => Some(tx),
+                .enumerate()
+                .filter_map(|(index, tx_bytes)| {
+                    let index = encrypted_count + index;
+                    let raw = tx_type_alias.map_or(Cow::Borrowed(tx_bytes.as_ref()), |alias| {
+                        alias.canonicalize(tx_bytes.as_ref(), block_number)
+                    });
+                    match TransactionSigned::decode_2718_exact(&raw) {
+                        Ok(tx) => {
+                            candidate_indices.push(index);
+                            Some(tx)
+                        }
                         Err(err) => {
                             tracing::warn!(
                                 %err,
                                 "dropping undecodable transaction from payload attributes"
                             );
+                            decode_failures.push((index, err.to_string()));
                             None
                         }
                     }
                 })
                 .collect()
         };
+        transactions.extend(plaintext);
+
+        let (bundles, bundle_removals) = resolve_bundles(
+            &self.config.handles.bundles,
+            block_number,
+            &mut transactions,
+            &mut candidate_indices,
+        );
+        for (index, hash) in &bundle_removals {
+            tracing::warn!(
+                index,
+                %hash,
+                "dropping transaction of a bundle that is incomplete or out of order"
+            );
+        }
 
         tracing::Span::current().record("tx_count", transactions.len());
 
         let evolve_attrs = EvolvePayloadAttributes::new(
             transactions,
             Some(effective_gas_limit),
-            attributes.timestamp(),
+            timestamp,
             attributes.inner.prev_randao,
             fee_recipient,
             parent_header.hash(),
             block_number,
         )
-        .with_slot_number(attributes.slot_number());
+        .with_slot_number(attributes.slot_number())
+        .with_withdrawals(attributes.inner.withdrawals.clone().unwrap_or_default())
+        .with_bundles(bundles)
+        .with_build_report(record_report)
+        .with_max_tx_count(attributes.max_tx_count);
+
+        // Reuse the speculative block when it was built from exactly these inputs. Reports
+        // are only produced by a fresh build.
+        let speculated = self.speculative.as_ref().and_then(|cache| {
+            cache.record_attributes(&evolve_attrs, parent_header.timestamp);
+            let block = cache.take_matching(&SpeculationKey::from_attributes(&evolve_attrs));
+            block.filter(|_| !record_report)
+        });
+
+        let (sealed_block, report) = if let Some(block) = speculated {
+            info!("using speculative block");
+            (block, None)
+        } else {
+            // Build the payload using the evolve payload builder - use spawn_blocking for async work.
+            let evolve_builder = self.evolve_builder.clone();
+            let sponsors = pooled_sponsors(&self.pool, &evolve_attrs.transactions);
+            tokio::task::block_in_place(|| {
+                Handle::current()
+                    .block_on(evolve_builder.build_payload_with_sponsors(evolve_attrs, &sponsors))
+            })
+            .map_err(|err| payload_job_error(&self.config.handles.engine_errors, payload_id, err))?
+        };
 
-        // Build the payload using the evolve payload builder - use spawn_blocking for async work.
-        let evolve_builder = self.evolve_builder.clone();
-        let sealed_block = tokio::task::block_in_place(|| {
-            Handle::current().block_on(evolve_builder.build_payload(evolve_attrs))
-        })
-        .map_err(PayloadBuilderError::other)?;
+        let sources = inclusion_mix(
+            &sealed_block,
+            &decrypted,
+            &self.config.handles.maintenance,
+            self.dev_mode,
+        );
+        record_inclusion_mix(&sources);
+
+        if let Some(mut report) = report {
+            report.timestamp = timestamp_adjustment;
+            report.sources = sources;
+            for decision in &mut report.transactions {
+                decision.index = candidate_indices[decision.index];
+            }
+            for (index, err) in decode_failures {
+                report.record_excluded(index, None, TxInclusionReason::DecodeFailed, err);
+            }
+            for (index, hash) in bundle_removals {
+                report.record_excluded(
+                    index,
+                    Some(hash),
+                    TxInclusionReason::BundleReverted,
+                    "bundle incomplete or out of order",
+                );
+            }
+            report.transactions.sort_by_key(|decision| decision.index);
+            self.config.handles.build_reports.insert(payload_id, report);
+        }
 
         info!(
             tx_count = sealed_block.transaction_count(),
             gas_used = sealed_block.gas_used,
             "built block"
         );
+        self.load_shedder.record_build(build_started.elapsed());
+        let sealed_block = Arc::new(sealed_block);
+        let policy = self.config.node.ordering_audit;
+        let reports = self
+            .config
+            .node
+            .ordering_reports
+            .then(|| self.config.handles.ordering_reports.clone());
+        if policy.is_some() || reports.is_some() {
+            // Bundles and decrypted transactions keep the position they were given.
+            let mut exempt = decrypted;
+            exempt.extend(
+                self.config
+                    .handles
+                    .bundles
+                    .bundles_for_block(block_number)
+                    .into_iter()
+                    .flat_map(|bundle| bundle.tx_hashes),
+            );
+            audit_block_ordering(
+                policy,
+                reports,
+                sealed_block.clone(),
+                &self.pool,
+                &self.config.handles.maintenance,
+                exempt,
+            );
+        }
+        self.config.handles.bundles.advance_to(block_number);
+        self.config.handles.encrypted.remove_revealed(&key_ids);
+        // Lane flags outlive their transactions only until the pool drops them.
+        self.config
+            .handles
+            .maintenance
+            .retain(|hash| self.pool.contains(hash));
+        if let Some(signer) = &self.config.node.attestation_signer {
+            self.config.handles.attestations.attest(
+                signer,
+                payload_id,
+                block_number,
+                sealed_block.hash(),
+                sealed_block.transactions_root,
+                attributes_hash,
+            );
+        }
 
         // Convert to EvBuiltPayload.
         let gas_used = sealed_block.gas_used;
         let built_payload = EvBuiltPayload::new(
             payload_id,
-            Arc::new(sealed_block),
+            sealed_block,
             U256::from(gas_used), // Block gas used.
             None,                 // No blob sidecar for evolve.
         );
+        self.config
+            .handles
+            .pending_block
+            .record(RpcPendingBlock::new(
+                &built_payload,
+                attributes.max_tx_count,
+            ));
 
         Ok(BuildOutcome::Better {
             payload: built_payload,
@@ -287,14 +874,34 @@ where
         config: PayloadConfig<Self::Attributes, HeaderForPayload<Self::BuiltPayload>>,
     ) -> Result<Self::BuiltPayload, PayloadBuilderError> {
         let _duration = RecordDurationOnDrop::new();
+        let _job = self
+            .config
+            .handles
+            .shutdown
+            .begin_payload_job()
+            .ok_or_else(|| {
+                payload_job_error(
+                    &self.config.handles.engine_errors,
+                    config.payload_id(),
+                    EvolveEngineError::ShuttingDown,
+                )
+            })?;
         let PayloadConfig {
             parent_header,
-            attributes,
+            mut attributes,
             payload_id,
         } = config;
 
         info!("building empty payload");
 
+        if self.speculative.is_some() {
+            attributes.inner.prev_randao =
+                derive_prev_randao(parent_header.hash(), parent_header.mix_hash);
+            attributes.inner.suggested_fee_recipient = Address::ZERO;
+        }
+
+        let attributes_hash = attributes.commitment_hash(parent_header.hash());
+
         // Create empty evolve attributes (no transactions).
         // If no gas_limit provided, default to the parent header's gas limit (genesis for first block).
         let effective_gas_limit = attributes.gas_limit.unwrap_or(parent_header.gas_limit);
@@ -304,11 +911,17 @@ where
         let block_number = parent_header.number + 1;
         let fee_recipient =
             self.resolve_fee_recipient(attributes.inner.suggested_fee_recipient, block_number);
+        let (timestamp, _) = resolve_timestamp(
+            self.config.node.timestamp_policy,
+            attributes.timestamp(),
+            parent_header.timestamp,
+        )
+        .map_err(|err| payload_job_error(&self.config.handles.engine_errors, payload_id, err))?;
 
         let evolve_attrs = EvolvePayloadAttributes::new(
             vec![],
             Some(effective_gas_limit),
-            attributes.timestamp(),
+            timestamp,
             attributes.inner.prev_randao,
             fee_recipient,
             parent_header.hash(),
@@ -321,7 +934,18 @@ where
         let sealed_block = tokio::task::block_in_place(|| {
             Handle::current().block_on(evolve_builder.build_payload(evolve_attrs))
         })
-        .map_err(PayloadBuilderError::other)?;
+        .map_err(|err| payload_job_error(&self.config.handles.engine_errors, payload_id, err))?;
+
+        if let Some(signer) = &self.config.node.attestation_signer {
+            self.config.handles.attestations.attest(
+                signer,
+                payload_id,
+                block_number,
+                sealed_block.hash(),
+                sealed_block.transactions_root,
+                attributes_hash,
+            );
+        }
 
         let gas_used = sealed_block.gas_used;
         Ok(EvBuiltPayload::new(
@@ -351,7 +975,6 @@ mod tests {
         config::EvolvePayloadBuilderConfig, executor::EvolveEvmConfig, test_utils::SpanCollector,
         txpool::EvPooledTransaction,
     };
-    use alloy_primitives::B256;
     use alloy_rpc_types::engine::PayloadAttributes as RpcPayloadAttributes;
     use reth_basic_payload_builder::PayloadConfig;
     use reth_chainspec::ChainSpecBuilder;
@@ -413,6 +1036,8 @@ mod tests {
             config,
             pool: NoopTransactionPool::<EvPooledTransaction>::new(),
             dev_mode: false,
+            speculative: None,
+            load_shedder: Arc::default(),
         };
 
         let attrs = EvolveEnginePayloadAttributes {
@@ -426,6 +1051,9 @@ mod tests {
             },
             transactions: None,
             gas_limit: Some(30_000_000),
+            build_report: None,
+            max_tx_count: None,
+            decryption_keys: None,
         };
         let payload_id = attrs.payload_id(&genesis_hash);
 
@@ -510,6 +1138,8 @@ mod tests {
             config,
             pool: NoopTransactionPool::<EvPooledTransaction>::new(),
             dev_mode: false,
+            speculative: None,
+            load_shedder: Arc::default(),
         };
 
         let attrs = EvolveEnginePayloadAttributes {
@@ -523,6 +1153,9 @@ mod tests {
             },
             transactions: None,
             gas_limit: Some(30_000_000),
+            build_report: None,
+            max_tx_count: None,
+            decryption_keys: None,
         };
         let payload_id = attrs.payload_id(&genesis_hash);
 
@@ -595,6 +1228,8 @@ mod tests {
             config,
             pool: NoopTransactionPool::<EvPooledTransaction>::new(),
             dev_mode: false,
+            speculative: None,
+            load_shedder: Arc::default(),
         };
 
         // Include garbage bytes that cannot be decoded as valid transactions.
@@ -610,6 +1245,9 @@ mod tests {
             },
             transactions: Some(vec![invalid_tx]),
             gas_limit: Some(30_000_000),
+            build_report: None,
+            max_tx_count: None,
+            decryption_keys: None,
         };
         let payload_id = attrs.payload_id(&genesis_hash);
 
@@ -631,4 +1269,211 @@ mod tests {
             "build should succeed even with invalid raw transactions, got: {result:?}"
         );
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn try_build_records_build_report_when_requested() {
+        let genesis: alloy_genesis::Genesis =
+            serde_json::from_str(include_str!("../../tests/assets/genesis.json"))
+                .expect("valid genesis");
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(reth_chainspec::Chain::from_id(1234))
+                .genesis(genesis)
+                .cancun_activated()
+                .build(),
+        );
+
+        let provider = MockEthProvider::default();
+        let genesis_hash = B256::from_slice(
+            &hex::decode("2b8bbb1ea1e04f9c9809b4b278a8687806edc061a356c7dbc491930d8e922503")
+                .unwrap(),
+        );
+        let genesis_state_root = B256::from_slice(
+            &hex::decode("05e9954443da80d86f2104e56ffdfd98fe21988730684360104865b3dc8191b4")
+                .unwrap(),
+        );
+
+        let genesis_header = Header {
+            state_root: genesis_state_root,
+            number: 0,
+            gas_limit: 30_000_000,
+            timestamp: 1710338135,
+            base_fee_per_gas: Some(0),
+            excess_blob_gas: Some(0),
+            blob_gas_used: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        provider.add_header(genesis_hash, genesis_header.clone());
+
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(chain_spec.as_ref()).unwrap();
+        let evm_config = EvolveEvmConfig::new(chain_spec);
+        let evolve_builder = Arc::new(EvolvePayloadBuilder::new(
+            Arc::new(provider),
+            evm_config,
+            config.clone(),
+        ));
+
+        let engine_builder = EvolveEnginePayloadBuilder {
+            evolve_builder,
+            config,
+            pool: NoopTransactionPool::<EvPooledTransaction>::new(),
+            dev_mode: false,
+            speculative: None,
+            load_shedder: Arc::default(),
+        };
+
+        // Include garbage bytes that cannot be decoded as valid transactions.
+        let invalid_tx = alloy_primitives::Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]);
+        let attrs = EvolveEnginePayloadAttributes {
+            inner: RpcPayloadAttributes {
+                timestamp: 1710338136,
+                prev_randao: B256::random(),
+                suggested_fee_recipient: Address::random(),
+                withdrawals: Some(vec![]),
+                parent_beacon_block_root: Some(B256::ZERO),
+                slot_number: None,
+            },
+            transactions: Some(vec![invalid_tx]),
+            gas_limit: Some(30_000_000),
+            build_report: Some(true),
+            max_tx_count: None,
+            decryption_keys: None,
+        };
+        let payload_id = attrs.payload_id(&genesis_hash);
+
+        let sealed_parent = SealedHeader::new(genesis_header, genesis_hash);
+        let payload_config = PayloadConfig::new(Arc::new(sealed_parent), attrs, payload_id);
+        let args = BuildArguments::new(
+            CachedReads::default(),
+            None,
+            None,
+            payload_config,
+            CancelOnDrop::default(),
+            None,
+        );
+
+        engine_builder
+            .try_build(args)
+            .expect("build should succeed");
+
+        let report = engine_builder
+            .config
+            .handles
+            .build_reports
+            .get(&payload_id)
+            .expect("build report should be recorded");
+        assert_eq!(report.block_number, 1);
+        assert_eq!(report.included_count(), 0);
+        assert_eq!(report.transactions.len(), 1);
+        assert_eq!(report.transactions[0].index, 0);
+        assert_eq!(
+            report.transactions[0].reason,
+            TxInclusionReason::DecodeFailed
+        );
+        assert_eq!(report.sources, InclusionMix::default());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn try_build_records_colliding_timestamp_in_build_report() {
+        let genesis: alloy_genesis::Genesis =
+            serde_json::from_str(include_str!("../../tests/assets/genesis.json"))
+                .expect("valid genesis");
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(reth_chainspec::Chain::from_id(1234))
+                .genesis(genesis)
+                .cancun_activated()
+                .build(),
+        );
+
+        let provider = MockEthProvider::default();
+        let genesis_hash = B256::from_slice(
+            &hex::decode("2b8bbb1ea1e04f9c9809b4b278a8687806edc061a356c7dbc491930d8e922503")
+                .unwrap(),
+        );
+        let genesis_state_root = B256::from_slice(
+            &hex::decode("05e9954443da80d86f2104e56ffdfd98fe21988730684360104865b3dc8191b4")
+                .unwrap(),
+        );
+
+        let genesis_header = Header {
+            state_root: genesis_state_root,
+            number: 0,
+            gas_limit: 30_000_000,
+            timestamp: 1710338135,
+            base_fee_per_gas: Some(0),
+            excess_blob_gas: Some(0),
+            blob_gas_used: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        provider.add_header(genesis_hash, genesis_header.clone());
+
+        let config = EvolvePayloadBuilderConfig::from_chain_spec(chain_spec.as_ref()).unwrap();
+        let evm_config = EvolveEvmConfig::new(chain_spec);
+        let evolve_builder = Arc::new(EvolvePayloadBuilder::new(
+            Arc::new(provider),
+            evm_config,
+            config.clone(),
+        ));
+
+        let engine_builder = EvolveEnginePayloadBuilder {
+            evolve_builder,
+            config,
+            pool: NoopTransactionPool::<EvPooledTransaction>::new(),
+            dev_mode: false,
+            speculative: None,
+            load_shedder: Arc::default(),
+        };
+
+        // Same second as the parent, as happens with sub-second block times.
+        let attrs = EvolveEnginePayloadAttributes {
+            inner: RpcPayloadAttributes {
+                timestamp: genesis_header.timestamp,
+                prev_randao: B256::random(),
+                suggested_fee_recipient: Address::random(),
+                withdrawals: Some(vec![]),
+                parent_beacon_block_root: Some(B256::ZERO),
+                slot_number: None,
+            },
+            transactions: None,
+            gas_limit: Some(30_000_000),
+            build_report: Some(true),
+            max_tx_count: None,
+            decryption_keys: None,
+        };
+        let payload_id = attrs.payload_id(&genesis_hash);
+
+        let sealed_parent = SealedHeader::new(genesis_header, genesis_hash);
+        let payload_config = PayloadConfig::new(Arc::new(sealed_parent), attrs, payload_id);
+        let args = BuildArguments::new(
+            CachedReads::default(),
+            None,
+            None,
+            payload_config,
+            CancelOnDrop::default(),
+            None,
+        );
+
+        engine_builder
+            .try_build(args)
+            .expect("build should succeed");
+
+        let report = engine_builder
+            .config
+            .handles
+            .build_reports
+            .get(&payload_id)
+            .expect("build report should be recorded");
+        assert_eq!(
+            report.timestamp,
+            Some(TimestampAdjustment {
+                policy: evolve_ev_reth::TimestampPolicy::Grace,
+                requested: 1710338135,
+                parent_timestamp: 1710338135,
+                applied: 1710338135,
+            })
+        );
+    }
 }
@@ -1,6 +1,8 @@
-use std::sync::Arc;
+use std::{ops::Deref, sync::Arc};
 
-use alloy_eips::{eip7685::Requests, eip7928::EMPTY_BLOCK_ACCESS_LIST_HASH};
+use alloy_eips::{
+    eip2718::Encodable2718, eip7685::Requests, eip7928::EMPTY_BLOCK_ACCESS_LIST_HASH,
+};
 use alloy_primitives::{Bytes, U256};
 use alloy_rpc_types_engine::{
     BlobsBundleV1, BlobsBundleV2, ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3,
@@ -10,7 +12,8 @@ use alloy_rpc_types_engine::{
 use ev_primitives::EvPrimitives;
 use reth_payload_builder::BlobSidecars;
 use reth_payload_primitives::BuiltPayload;
-use reth_primitives_traits::SealedBlock;
+use reth_primitives_traits::{BlockBody, SealedBlock};
+use serde::{Deserialize, Serialize};
 
 /// Built payload for `EvPrimitives`.
 #[derive(Debug, Clone)]
@@ -22,6 +25,47 @@ pub struct EvBuiltPayload {
     requests: Option<Requests>,
 }
 
+/// Engine API payload envelope extended with the size of the payload's transaction list.
+///
+/// ev-node's batch packer uses the count and the encoded size to fit blocks into DA
+/// submissions without decoding the payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvPayloadEnvelope<T> {
+    /// Standard envelope for the Engine API version.
+    #[serde(flatten)]
+    pub envelope: T,
+    /// Number of transactions in the payload.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_count: u64,
+    /// Sum of the EIP-2718 encoded lengths of the payload's transactions, in bytes.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transactions_size: u64,
+}
+
+impl<T> EvPayloadEnvelope<T> {
+    fn from_payload<E>(
+        payload: EvBuiltPayload,
+        convert: impl FnOnce(EvBuiltPayload) -> Result<T, E>,
+    ) -> Result<Self, E> {
+        let transaction_count = payload.transaction_count();
+        let transactions_size = payload.transactions_size();
+        Ok(Self {
+            envelope: convert(payload)?,
+            transaction_count,
+            transactions_size,
+        })
+    }
+}
+
+impl<T> Deref for EvPayloadEnvelope<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.envelope
+    }
+}
+
 /// Errors encountered when converting an EV payload into an engine API envelope.
 #[derive(Debug, thiserror::Error)]
 pub enum EvBuiltPayloadConversionError {
@@ -65,6 +109,21 @@ impl EvBuiltPayload {
         self.fees
     }
 
+    /// Returns the number of transactions in this payload.
+    pub fn transaction_count(&self) -> u64 {
+        self.block.body().transactions().len() as u64
+    }
+
+    /// Returns the sum of the EIP-2718 encoded lengths of this payload's transactions.
+    pub fn transactions_size(&self) -> u64 {
+        self.block
+            .body()
+            .transactions()
+            .iter()
+            .map(|tx| tx.encode_2718_len() as u64)
+            .sum()
+    }
+
     /// Returns the sidecar bundle.
     pub const fn sidecars(&self) -> &BlobSidecars {
         &self.sidecars
@@ -249,3 +308,92 @@ impl TryFrom<EvBuiltPayload> for ExecutionPayloadEnvelopeV6 {
         })
     }
 }
+
+impl TryFrom<EvBuiltPayload> for EvPayloadEnvelope<ExecutionPayloadEnvelopeV3> {
+    type Error = EvBuiltPayloadConversionError;
+
+    fn try_from(value: EvBuiltPayload) -> Result<Self, Self::Error> {
+        Self::from_payload(value, EvBuiltPayload::try_into_v3)
+    }
+}
+
+impl TryFrom<EvBuiltPayload> for EvPayloadEnvelope<ExecutionPayloadEnvelopeV4> {
+    type Error = EvBuiltPayloadConversionError;
+
+    fn try_from(value: EvBuiltPayload) -> Result<Self, Self::Error> {
+        Self::from_payload(value, EvBuiltPayload::try_into_v4)
+    }
+}
+
+impl TryFrom<EvBuiltPayload> for EvPayloadEnvelope<ExecutionPayloadEnvelopeV5> {
+    type Error = EvBuiltPayloadConversionError;
+
+    fn try_from(value: EvBuiltPayload) -> Result<Self, Self::Error> {
+        Self::from_payload(value, EvBuiltPayload::try_into_v5)
+    }
+}
+
+impl TryFrom<EvBuiltPayload> for EvPayloadEnvelope<ExecutionPayloadEnvelopeV6> {
+    type Error = EvBuiltPayloadConversionError;
+
+    fn try_from(value: EvBuiltPayload) -> Result<Self, Self::Error> {
+        Self::from_payload(value, ExecutionPayloadEnvelopeV6::try_from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, Signed, TxLegacy};
+    use alloy_primitives::{Address, Signature, TxKind};
+    use ev_primitives::{BlockBody, EvTxEnvelope};
+
+    #[test]
+    fn envelope_reports_transaction_count_and_size() {
+        let tx = EvTxEnvelope::Ethereum(reth_ethereum_primitives::TransactionSigned::from(
+            Signed::new_unhashed(
+                reth_ethereum_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1234),
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::ZERO),
+                    ..Default::default()
+                }),
+                Signature::test_signature(),
+            ),
+        ));
+        let expected_size = 2 * tx.encode_2718_len() as u64;
+        let block = ev_primitives::Block {
+            header: Header {
+                base_fee_per_gas: Some(0),
+                excess_blob_gas: Some(0),
+                blob_gas_used: Some(0),
+                parent_beacon_block_root: Some(Default::default()),
+                ..Default::default()
+            },
+            body: BlockBody {
+                transactions: vec![tx.clone(), tx],
+                ommers: Vec::new(),
+                withdrawals: Some(Default::default()),
+            },
+        };
+        let payload = EvBuiltPayload::new(
+            PayloadId::new([0; 8]),
+            Arc::new(SealedBlock::seal_slow(block)),
+            U256::ZERO,
+            None,
+        );
+
+        let envelope = EvPayloadEnvelope::<ExecutionPayloadEnvelopeV3>::try_from(payload).unwrap();
+        assert_eq!(envelope.transaction_count, 2);
+        assert_eq!(envelope.transactions_size, expected_size);
+
+        // The extra fields sit next to the standard ones, so plain Engine API clients can
+        // still read the envelope.
+        let value = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(value["transactionCount"], "0x2");
+        assert!(value["executionPayload"].is_object());
+        let decoded: EvPayloadEnvelope<ExecutionPayloadEnvelopeV3> =
+            serde_json::from_value(value).unwrap();
+        assert_eq!(decoded, envelope);
+    }
+}
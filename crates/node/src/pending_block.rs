@@ -0,0 +1,198 @@
+//! `evolve_getPendingBlock` RPC.
+//!
+//! The sequencer builds each block from the transactions ev-node hands it, so the txpool says
+//! little about what the next block will hold. The payload builder records every payload it
+//! builds here, and frontends read back the latest one to show pending state that matches
+//! what the sequencer will produce. It is dropped once the chain reaches its height.
+
+use alloy_consensus::transaction::TxHashRef;
+use alloy_primitives::{Address, B256, U64};
+use alloy_rpc_types_engine::PayloadId;
+use async_trait::async_trait;
+use jsonrpsee::types::{error::INTERNAL_ERROR_CODE, ErrorObject};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_primitives_traits::BlockBody;
+use reth_storage_api::BlockNumReader;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use crate::EvBuiltPayload;
+
+/// Block being built, returned by `evolve_getPendingBlock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPendingBlock {
+    /// Payload the block was built for.
+    pub payload_id: PayloadId,
+    /// Height of the block.
+    pub number: U64,
+    /// Hash of the block as built so far.
+    pub hash: B256,
+    /// Hash of the parent block.
+    pub parent_hash: B256,
+    /// Timestamp of the block.
+    pub timestamp: U64,
+    /// Recipient of the block's fees.
+    pub fee_recipient: Address,
+    /// Base fee per gas of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U64>,
+    /// Gas limit of the block.
+    pub gas_limit: U64,
+    /// Gas used by the included transactions.
+    pub gas_used: U64,
+    /// Gas left for further transactions.
+    pub remaining_gas: U64,
+    /// Hashes of the included transactions, in block order.
+    pub transactions: Vec<B256>,
+    /// Sum of the EIP-2718 encoded lengths of the included transactions, in bytes, i.e. the
+    /// size ev-node submits to the DA layer.
+    pub transactions_size: U64,
+    /// Transactions that still fit under the payload's `maxTxCount`, if it set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining_tx_count: Option<U64>,
+}
+
+impl RpcPendingBlock {
+    /// Describes `payload`, built under a limit of `max_tx_count` transactions if set.
+    pub fn new(payload: &EvBuiltPayload, max_tx_count: Option<u64>) -> Self {
+        let block = payload.block();
+        let header = block.header();
+        let transaction_count = payload.transaction_count();
+        Self {
+            payload_id: payload.id(),
+            number: U64::from(header.number),
+            hash: block.hash(),
+            parent_hash: header.parent_hash,
+            timestamp: U64::from(header.timestamp),
+            fee_recipient: header.beneficiary,
+            base_fee_per_gas: header.base_fee_per_gas.map(U64::from),
+            gas_limit: U64::from(header.gas_limit),
+            gas_used: U64::from(header.gas_used),
+            remaining_gas: U64::from(header.gas_limit.saturating_sub(header.gas_used)),
+            transactions: block
+                .body()
+                .transactions()
+                .iter()
+                .map(|tx| *tx.tx_hash())
+                .collect(),
+            transactions_size: U64::from(payload.transactions_size()),
+            remaining_tx_count: max_tx_count
+                .map(|max_tx_count| U64::from(max_tx_count.saturating_sub(transaction_count))),
+        }
+    }
+}
+
+/// Latest payload built by this node, written by the payload builder and read by the
+/// `evolve_getPendingBlock` RPC.
+#[derive(Debug, Default)]
+pub struct PendingBlockStore {
+    latest: Mutex<Option<RpcPendingBlock>>,
+}
+
+impl PendingBlockStore {
+    /// Records `block` as the block being built, replacing any earlier one.
+    pub fn record(&self, block: RpcPendingBlock) {
+        *self.latest.lock().unwrap_or_else(|e| e.into_inner()) = Some(block);
+    }
+
+    /// Returns the block being built on top of a chain whose head is `head`, or `None` if the
+    /// latest built block is already at or below the head.
+    pub fn get(&self, head: u64) -> Option<RpcPendingBlock> {
+        let mut latest = self.latest.lock().unwrap_or_else(|e| e.into_inner());
+        if latest
+            .as_ref()
+            .is_some_and(|block| block.number.to::<u64>() <= head)
+        {
+            *latest = None;
+        }
+        latest.clone()
+    }
+}
+
+/// Pending block RPC API.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolvePendingBlockApi {
+    /// Returns the latest block built on top of the current head: its header fields, the
+    /// hashes of its transactions and the gas and transaction budget it has left, or `null`
+    /// if no such block is being built.
+    #[method(name = "getPendingBlock")]
+    async fn get_pending_block(&self) -> RpcResult<Option<RpcPendingBlock>>;
+}
+
+/// Implementation of the pending block RPC.
+#[derive(Debug)]
+pub struct EvolvePendingBlockApiImpl<Provider> {
+    provider: Provider,
+    store: Arc<PendingBlockStore>,
+}
+
+impl<Provider> EvolvePendingBlockApiImpl<Provider> {
+    /// Creates a new instance reading the pending block from `store`, and the chain head from
+    /// `provider`.
+    pub const fn new(provider: Provider, store: Arc<PendingBlockStore>) -> Self {
+        Self { provider, store }
+    }
+}
+
+#[async_trait]
+impl<Provider> EvolvePendingBlockApiServer for EvolvePendingBlockApiImpl<Provider>
+where
+    Provider: BlockNumReader + 'static,
+{
+    async fn get_pending_block(&self) -> RpcResult<Option<RpcPendingBlock>> {
+        let head = self
+            .provider
+            .best_block_number()
+            .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))?;
+        Ok(self.store.get(head))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::U256;
+    use reth_primitives_traits::SealedBlock;
+
+    fn payload(number: u64) -> EvBuiltPayload {
+        let block = ev_primitives::Block {
+            header: Header {
+                number,
+                gas_limit: 30_000_000,
+                gas_used: 21_000,
+                base_fee_per_gas: Some(7),
+                ..Default::default()
+            },
+            body: Default::default(),
+        };
+        EvBuiltPayload::new(
+            PayloadId::new([1; 8]),
+            Arc::new(SealedBlock::seal_slow(block)),
+            U256::ZERO,
+            None,
+        )
+    }
+
+    #[test]
+    fn reports_budget_and_drops_once_chain_reaches_block() {
+        let block = RpcPendingBlock::new(&payload(5), Some(10));
+        assert_eq!(block.remaining_gas, U64::from(29_979_000));
+        assert_eq!(block.remaining_tx_count, Some(U64::from(10)));
+        assert!(block.transactions.is_empty());
+
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["number"], "0x5");
+        assert_eq!(json["baseFeePerGas"], "0x7");
+        assert_eq!(json["remainingTxCount"], "0xa");
+
+        let store = PendingBlockStore::default();
+        store.record(block.clone());
+        assert_eq!(store.get(4), Some(block));
+        assert_eq!(store.get(5), None);
+        // Dropped for good, even if the head were to go back.
+        assert_eq!(store.get(4), None);
+    }
+}
@@ -0,0 +1,173 @@
+//! `eth_sendRawTransaction` for chains with an EvNode transaction type alias.
+//!
+//! The default handler only decodes EvNode transactions typed `0x76`. On chains that configure
+//! `evNodeTxTypeAlias` this one replaces it: raw transactions typed with the alias are mapped to
+//! `0x76` before decoding, and those typed `0x76` are refused once the chain requires the alias.
+
+use alloy_consensus::transaction::{SignerRecoverable, TxHashRef};
+use alloy_eips::Decodable2718;
+use alloy_primitives::{Bytes, B256};
+use async_trait::async_trait;
+use ev_primitives::{EvNodeTxTypeAlias, EvNodeTxTypeError, EvPooledTxEnvelope};
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_storage_api::BlockNumReader;
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use thiserror::Error;
+
+/// Errors of a raw transaction submission.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RawTransactionError {
+    /// The transaction uses a type byte the chain no longer accepts.
+    #[error(transparent)]
+    TxType(#[from] EvNodeTxTypeError),
+    /// The transaction could not be decoded.
+    #[error("failed to decode signed transaction: {0}")]
+    Decode(String),
+    /// The sender could not be recovered from the signature.
+    #[error("invalid transaction signature")]
+    InvalidSignature,
+    /// Error while reading the latest block number.
+    #[error("state provider error: {0}")]
+    Provider(String),
+    /// The pool rejected the transaction.
+    #[error("{0}")]
+    Pool(String),
+}
+
+impl RawTransactionError {
+    /// Returns the JSON-RPC error code of the error.
+    pub const fn code(&self) -> i32 {
+        match self {
+            Self::TxType(_) | Self::Decode(_) | Self::InvalidSignature | Self::Pool(_) => {
+                INVALID_PARAMS_CODE
+            }
+            Self::Provider(_) => INTERNAL_ERROR_CODE,
+        }
+    }
+}
+
+impl From<RawTransactionError> for ErrorObject<'static> {
+    fn from(err: RawTransactionError) -> Self {
+        ErrorObject::owned(err.code(), err.to_string(), None::<()>)
+    }
+}
+
+/// Decodes a raw transaction submitted for the block after `latest_block`, mapping the EvNode
+/// type `alias`.
+pub fn decode_raw_transaction(
+    raw: &[u8],
+    alias: &EvNodeTxTypeAlias,
+    latest_block: u64,
+) -> Result<EvPooledTxEnvelope, RawTransactionError> {
+    let raw = alias.canonicalize_submission(raw, latest_block + 1)?;
+    EvPooledTxEnvelope::decode_2718_exact(&raw)
+        .map_err(|err| RawTransactionError::Decode(err.to_string()))
+}
+
+/// Raw transaction submission RPC API.
+#[rpc(server, namespace = "eth")]
+pub trait EthRawTransactionApi {
+    /// Decodes a signed transaction, mapping the chain's EvNode type alias, submits it to the
+    /// pool and returns its hash.
+    #[method(name = "sendRawTransaction")]
+    async fn send_raw_transaction(&self, tx: Bytes) -> RpcResult<B256>;
+}
+
+/// Implementation of `eth_sendRawTransaction` with an EvNode type alias.
+#[derive(Debug)]
+pub struct EthRawTransactionApiImpl<Provider, Pool> {
+    provider: Provider,
+    pool: Pool,
+    alias: EvNodeTxTypeAlias,
+}
+
+impl<Provider, Pool> EthRawTransactionApiImpl<Provider, Pool> {
+    /// Creates a new instance submitting to `pool` transactions decoded with `alias`.
+    pub const fn new(provider: Provider, pool: Pool, alias: EvNodeTxTypeAlias) -> Self {
+        Self {
+            provider,
+            pool,
+            alias,
+        }
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> EthRawTransactionApiServer for EthRawTransactionApiImpl<Provider, Pool>
+where
+    Provider: BlockNumReader + Send + Sync + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Pooled = EvPooledTxEnvelope>>
+        + Send
+        + Sync
+        + 'static,
+{
+    async fn send_raw_transaction(&self, tx: Bytes) -> RpcResult<B256> {
+        let latest_block = self
+            .provider
+            .best_block_number()
+            .map_err(|err| RawTransactionError::Provider(err.to_string()))?;
+        let recovered = decode_raw_transaction(&tx, &self.alias, latest_block)?
+            .try_into_recovered()
+            .map_err(|_| RawTransactionError::InvalidSignature)?;
+        let tx_hash = *recovered.inner().tx_hash();
+        self.pool
+            .add_transaction(
+                TransactionOrigin::External,
+                Pool::Transaction::from_pooled(recovered),
+            )
+            .await
+            .map_err(|err| RawTransactionError::Pool(err.to_string()))?;
+        Ok(tx_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::Encodable2718;
+    use alloy_primitives::{Address, TxKind, U256};
+    use alloy_signer_local::PrivateKeySigner;
+    use ev_primitives::{EvNodeTransactionBuilder, EVNODE_TX_TYPE_ID};
+
+    #[test]
+    fn aliased_evnode_transaction_decodes_with_canonical_hash() {
+        let signer = PrivateKeySigner::random();
+        let tx = EvNodeTransactionBuilder::new(1)
+            .gas_limit(21_000)
+            .max_fee_per_gas(1_000_000_000)
+            .add_call(
+                TxKind::Call(Address::repeat_byte(0x70)),
+                U256::from(1),
+                Bytes::new(),
+            )
+            .sign_executor(&signer)
+            .unwrap();
+        let canonical = tx.encoded_2718();
+        let mut aliased = canonical.clone();
+        aliased[0] = 0x7a;
+
+        let alias = EvNodeTxTypeAlias::new(0x7a, 10)
+            .unwrap()
+            .with_canonical_refused(true);
+        let decoded = decode_raw_transaction(&aliased, &alias, 9).unwrap();
+        assert!(matches!(decoded, EvPooledTxEnvelope::EvNode(_)));
+        assert_eq!(decoded.tx_hash(), tx.tx_hash());
+        assert_eq!(decoded.recover_signer().unwrap(), signer.address());
+
+        // Before activation the alias is an unknown type; after it `0x76` is refused.
+        assert!(matches!(
+            decode_raw_transaction(&aliased, &alias, 8),
+            Err(RawTransactionError::Decode(_))
+        ));
+        assert_eq!(canonical[0], EVNODE_TX_TYPE_ID);
+        assert_eq!(
+            decode_raw_transaction(&canonical, &alias, 9).unwrap_err(),
+            RawTransactionError::TxType(EvNodeTxTypeError::CanonicalRefused { alias: 0x7a })
+        );
+    }
+}
@@ -0,0 +1,26 @@
+//! Standalone `ev-reth` subcommands that run instead of the node and print a report.
+//!
+//! `doctor`, `chaos-engine` and `audit-sponsorships` each parse their own arguments, run to
+//! completion and print what they found; the binary exits with an error when the command fails
+//! or its report is not healthy, so they can gate deployments in scripts.
+
+use clap::Parser;
+use std::fmt;
+
+/// Report printed by a [`ReportCommand`].
+pub trait CommandReport: fmt::Display {
+    /// Returns true if nothing the command checked failed.
+    fn is_healthy(&self) -> bool;
+}
+
+/// `ev-reth` subcommand that prints a [`CommandReport`].
+pub trait ReportCommand: Parser {
+    /// Name the subcommand is invoked by.
+    const NAME: &'static str;
+
+    /// Report printed once the command has run.
+    type Report: CommandReport;
+
+    /// Runs the command.
+    fn run(self) -> eyre::Result<Self::Report>;
+}
@@ -6,7 +6,7 @@ use alloy_consensus::{
 };
 use alloy_consensus_any::AnyReceiptEnvelope;
 use alloy_network::{Ethereum, ReceiptResponse, TransactionResponse, TxSigner};
-use alloy_primitives::{Address, Signature, U256};
+use alloy_primitives::{Address, Signature, B256, U256, U64};
 use alloy_rpc_types_eth::{
     Log, Transaction, TransactionInfo, TransactionReceipt, TransactionRequest,
 };
@@ -26,11 +26,15 @@ use reth_rpc_eth_api::{
     helpers::pending_block::BuildPendingEnv, FromEvmError, FullEthApiServer, RpcNodeCore,
 };
 use reth_rpc_eth_types::{receipt::build_receipt, EthApiError};
-use std::marker::PhantomData;
+use reth_storage_api::{BlockReader, StateProviderFactory};
+use std::{collections::HashMap, marker::PhantomData};
 
-use crate::EvolveEvmConfig;
-use ev_primitives::{EvPrimitives, EvTxEnvelope};
-use ev_revm::EvTxEnv;
+use crate::{
+    metering::{GasMeter, MeteredGas},
+    EvolveEvmConfig,
+};
+use ev_primitives::{Call, CallsSummary, EvPrimitives, EvTxEnvelope};
+use ev_revm::{best_effort::failed_call_index, DimensionPricing, EvTxEnv};
 
 /// Ev-specific RPC types using Ethereum responses with a custom request wrapper.
 #[derive(Clone, Debug)]
@@ -44,17 +48,36 @@ impl RpcTypes for EvRpcTypes {
 }
 
 /// RPC transaction response with optional sponsor address.
+///
+/// EvNode transactions serialize with `"type": "0x76"` and their full payload: the `calls`
+/// array, `feePayerSignature` and any optional batch fields, next to the executor signature
+/// and the resolved `feePayer`. They carry no top-level `to`, `value` or `input`; instead
+/// `callsSummary` gives the call count, deployments, total value and targets of the batch.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct EvRpcTransaction {
     #[serde(flatten)]
     inner: Transaction<EvTxEnvelope>,
     #[serde(rename = "feePayer", skip_serializing_if = "Option::is_none")]
     fee_payer: Option<Address>,
+    #[serde(
+        rename = "callsSummary",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    calls_summary: Option<CallsSummary>,
 }
 
 impl EvRpcTransaction {
-    const fn new(inner: Transaction<EvTxEnvelope>, fee_payer: Option<Address>) -> Self {
-        Self { inner, fee_payer }
+    fn new(inner: Transaction<EvTxEnvelope>, fee_payer: Option<Address>) -> Self {
+        let calls_summary = match inner.inner.inner() {
+            EvTxEnvelope::EvNode(ev) => Some(ev.tx().calls_summary()),
+            EvTxEnvelope::Ethereum(_) => None,
+        };
+        Self {
+            inner,
+            fee_payer,
+            calls_summary,
+        }
     }
 
     /// Returns the optional fee payer address.
@@ -66,6 +89,19 @@ impl EvRpcTransaction {
     pub const fn inner(&self) -> &Transaction<EvTxEnvelope> {
         &self.inner
     }
+
+    /// Returns the calls of an EvNode transaction.
+    pub fn calls(&self) -> Option<&[Call]> {
+        match self.inner.inner.inner() {
+            EvTxEnvelope::EvNode(ev) => Some(&ev.tx().calls),
+            EvTxEnvelope::Ethereum(_) => None,
+        }
+    }
+
+    /// Returns the aggregate of all calls of an EvNode transaction.
+    pub const fn calls_summary(&self) -> Option<&CallsSummary> {
+        self.calls_summary.as_ref()
+    }
 }
 
 impl ConsensusTransaction for EvRpcTransaction {
@@ -166,21 +202,66 @@ impl alloy_eips::Typed2718 for EvRpcTransaction {
     }
 }
 
-/// RPC receipt response with optional sponsor address.
+/// Compute, state growth and DA usage of a transaction, as reported in its receipt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvRpcGasDimensions {
+    /// Regular gas used by execution.
+    pub compute: U64,
+    /// Bytes added to the state.
+    pub state_growth: U64,
+    /// Encoded transaction bytes posted to the DA layer.
+    pub da: U64,
+    /// Gas under multi-dimensional pricing, once active.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priced_gas: Option<U64>,
+}
+
+impl From<MeteredGas> for EvRpcGasDimensions {
+    fn from(metered: MeteredGas) -> Self {
+        Self {
+            compute: U64::from(metered.dimensions.compute),
+            state_growth: U64::from(metered.dimensions.state_growth),
+            da: U64::from(metered.dimensions.da),
+            priced_gas: metered.priced_gas.map(U64::from),
+        }
+    }
+}
+
+/// RPC receipt response with optional sponsor address and gas dimensions.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct EvRpcReceipt {
     #[serde(flatten)]
     inner: TransactionReceipt<AnyReceiptEnvelope<Log>>,
     #[serde(rename = "feePayer", skip_serializing_if = "Option::is_none")]
     fee_payer: Option<Address>,
+    #[serde(
+        default,
+        rename = "gasDimensions",
+        skip_serializing_if = "Option::is_none"
+    )]
+    gas_dimensions: Option<EvRpcGasDimensions>,
+    #[serde(
+        default,
+        rename = "failedCalls",
+        skip_serializing_if = "Option::is_none"
+    )]
+    failed_calls: Option<Vec<U64>>,
 }
 
 impl EvRpcReceipt {
     const fn new(
         inner: TransactionReceipt<AnyReceiptEnvelope<Log>>,
         fee_payer: Option<Address>,
+        gas_dimensions: Option<EvRpcGasDimensions>,
+        failed_calls: Option<Vec<U64>>,
     ) -> Self {
-        Self { inner, fee_payer }
+        Self {
+            inner,
+            fee_payer,
+            gas_dimensions,
+            failed_calls,
+        }
     }
 
     /// Returns the optional fee payer address.
@@ -188,6 +269,16 @@ impl EvRpcReceipt {
         self.fee_payer
     }
 
+    /// Returns the gas dimensions, if the state before the transaction's block is available.
+    pub const fn gas_dimensions(&self) -> Option<EvRpcGasDimensions> {
+        self.gas_dimensions
+    }
+
+    /// Returns the indexes of the calls that failed, for best-effort EvNode batches.
+    pub fn failed_calls(&self) -> Option<&[U64]> {
+        self.failed_calls.as_deref()
+    }
+
     /// Returns the inner receipt.
     pub const fn inner(&self) -> &TransactionReceipt<AnyReceiptEnvelope<Log>> {
         &self.inner
@@ -314,20 +405,28 @@ impl<Spec, BlockEnv: alloy_evm::env::BlockEnvironment> TryIntoTxEnv<EvTxEnv, Spe
 
 /// Receipt converter for `EvPrimitives`.
 #[derive(Debug, Clone)]
-pub struct EvReceiptConverter<ChainSpec> {
+pub struct EvReceiptConverter<ChainSpec, Provider> {
     chain_spec: std::sync::Arc<ChainSpec>,
+    meter: GasMeter<Provider>,
 }
 
-impl<ChainSpec> EvReceiptConverter<ChainSpec> {
-    /// Creates a new receipt converter bound to the provided chain spec.
-    pub const fn new(chain_spec: std::sync::Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+impl<ChainSpec, Provider> EvReceiptConverter<ChainSpec, Provider> {
+    /// Creates a new receipt converter bound to the provided chain spec, reporting the gas
+    /// dimensions measured by `meter`.
+    pub const fn new(chain_spec: std::sync::Arc<ChainSpec>, meter: GasMeter<Provider>) -> Self {
+        Self { chain_spec, meter }
     }
 }
 
-impl<ChainSpec> ReceiptConverter<EvPrimitives> for EvReceiptConverter<ChainSpec>
+impl<ChainSpec, Provider> ReceiptConverter<EvPrimitives> for EvReceiptConverter<ChainSpec, Provider>
 where
     ChainSpec: EthChainSpec + 'static,
+    Provider: BlockReader<Block = ev_primitives::Block>
+        + StateProviderFactory
+        + std::fmt::Debug
+        + Send
+        + Sync
+        + 'static,
 {
     type RpcReceipt = EvRpcReceipt;
     type Error = EthApiError;
@@ -338,18 +437,47 @@ where
     ) -> Result<Vec<Self::RpcReceipt>, Self::Error> {
         let mut receipts = Vec::with_capacity(inputs.len());
 
+        // Each block is re-executed once, up to the last transaction asked for.
+        let mut counts = HashMap::<B256, usize>::new();
+        for input in &inputs {
+            let count = counts.entry(input.meta.block_hash).or_default();
+            *count = (*count).max(input.meta.index as usize + 1);
+        }
+        let metered: HashMap<B256, Vec<MeteredGas>> = counts
+            .into_iter()
+            .filter_map(|(block_hash, count)| {
+                self.meter
+                    .meter_block(block_hash, count)
+                    .ok()
+                    .map(|metered| (block_hash, metered))
+            })
+            .collect();
+
         for input in inputs {
             let blob_params = self
                 .chain_spec
                 .blob_params_at_timestamp(input.meta.timestamp);
             let fee_payer = match input.tx.inner() {
-                EvTxEnvelope::EvNode(ev) => ev
-                    .tx()
-                    .fee_payer_signature
-                    .as_ref()
-                    .and_then(|sig| ev.tx().recover_sponsor(input.tx.signer(), sig).ok()),
+                EvTxEnvelope::EvNode(ev) => ev.tx().fee_payer(input.tx.signer()),
                 EvTxEnvelope::Ethereum(_) => None,
             };
+            let gas_dimensions = metered
+                .get(&input.meta.block_hash)
+                .and_then(|metered| metered.get(input.meta.index as usize))
+                .copied()
+                .map(Into::into);
+            let failed_calls = match input.tx.inner() {
+                EvTxEnvelope::EvNode(ev) if !ev.tx().revert_mode.is_atomic() => Some(
+                    input
+                        .receipt
+                        .logs
+                        .iter()
+                        .filter_map(failed_call_index)
+                        .map(U64::from)
+                        .collect(),
+                ),
+                _ => None,
+            };
             let receipt = build_receipt(input, blob_params, |receipt, next_log_index, meta| {
                 let tx_type = u8::from(receipt.tx_type);
                 let mut log_index = next_log_index;
@@ -373,7 +501,12 @@ where
                     r#type: tx_type,
                 }
             });
-            receipts.push(EvRpcReceipt::new(receipt, fee_payer));
+            receipts.push(EvRpcReceipt::new(
+                receipt,
+                fee_payer,
+                gas_dimensions,
+                failed_calls,
+            ));
         }
 
         Ok(receipts)
@@ -384,7 +517,10 @@ where
 pub type EvRpcConvert<N> = RpcConverter<
     EvRpcTypes,
     EvolveEvmConfig,
-    EvReceiptConverter<<<N as FullNodeTypes>::Types as NodeTypes>::ChainSpec>,
+    EvReceiptConverter<
+        <<N as FullNodeTypes>::Types as NodeTypes>::ChainSpec,
+        <N as FullNodeTypes>::Provider,
+    >,
     (),
     (),
     EvSimTxConverter,
@@ -439,8 +575,18 @@ where
     type EthApi = EvEthApiFor<N>;
 
     async fn build_eth_api(self, ctx: EthApiCtx<'_, N>) -> eyre::Result<Self::EthApi> {
-        let receipt_converter =
-            EvReceiptConverter::new(FullNodeComponents::provider(ctx.components).chain_spec());
+        let provider = FullNodeComponents::provider(ctx.components);
+        let evm_config = FullNodeComponents::evm_config(ctx.components);
+        let pricing = evm_config
+            .evolve_config()
+            .and_then(|config| config.dimension_pricing_settings())
+            .map(|(activation, state_growth_gas_per_byte, da_gas_per_byte)| {
+                DimensionPricing::new(activation, state_growth_gas_per_byte, da_gas_per_byte)
+            });
+        let receipt_converter = EvReceiptConverter::new(
+            provider.chain_spec(),
+            GasMeter::new(provider.clone(), evm_config.clone(), pricing),
+        );
         let rpc_converter = RpcConverter::new(receipt_converter)
             .with_sim_tx_converter(EvSimTxConverter)
             .with_rpc_tx_converter(EvRpcTxConverter);
@@ -470,11 +616,7 @@ impl RpcTxConverter<EvTxEnvelope, RpcTransaction<EvRpcTypes>, TransactionInfo>
         tx_info: TransactionInfo,
     ) -> Result<RpcTransaction<EvRpcTypes>, Self::Err> {
         let fee_payer = match &tx {
-            EvTxEnvelope::EvNode(ev) => ev
-                .tx()
-                .fee_payer_signature
-                .as_ref()
-                .and_then(|sig| ev.tx().recover_sponsor(signer, sig).ok()),
+            EvTxEnvelope::EvNode(ev) => ev.tx().fee_payer(signer),
             EvTxEnvelope::Ethereum(_) => None,
         };
         let recovered = Recovered::new_unchecked(tx, signer);
@@ -530,3 +672,74 @@ where
             .map(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Signed;
+    use alloy_primitives::{address, aliases::U192, Bytes, TxKind};
+    use ev_primitives::{contract_fee_payer_signature, EvNodeTransaction, RevertMode};
+
+    const EXECUTOR: Address = address!("0x00000000000000000000000000000000000000e1");
+    const SPONSOR: Address = address!("0x00000000000000000000000000000000000000f1");
+
+    fn sponsored_batch() -> EvTxEnvelope {
+        let call = |to: Address, value: u64| Call {
+            to: TxKind::Call(to),
+            value: U256::from(value),
+            input: Bytes::from_static(&[0xab]),
+            gas_limit: None,
+        };
+        EvTxEnvelope::EvNode(Signed::new_unhashed(
+            EvNodeTransaction {
+                chain_id: 1,
+                nonce: 3,
+                nonce_key: U192::ZERO,
+                max_priority_fee_per_gas: 1,
+                max_fee_per_gas: 2,
+                gas_limit: 100_000,
+                calls: vec![call(SPONSOR, 1), call(EXECUTOR, 2)],
+                access_list: Default::default(),
+                revert_mode: RevertMode::Atomic,
+                valid_after: None,
+                valid_until: None,
+                session: None,
+                fee_payer_signature: Some(contract_fee_payer_signature(SPONSOR)),
+                max_sponsor_fee: None,
+                p256_signature: None,
+            },
+            Signature::new(U256::from(1), U256::from(2), false),
+        ))
+    }
+
+    #[test]
+    fn evnode_transaction_serializes_all_calls() {
+        let envelope = sponsored_batch();
+        let rpc_tx = EvRpcTxConverter
+            .convert_rpc_tx(envelope.clone(), EXECUTOR, TransactionInfo::default())
+            .expect("convert tx");
+        assert_eq!(rpc_tx.fee_payer(), Some(SPONSOR));
+        assert_eq!(rpc_tx.calls().map(<[Call]>::len), Some(2));
+
+        let json = serde_json::to_value(&rpc_tx).expect("serialize tx");
+        assert_eq!(json["type"], "0x76");
+        assert_eq!(json["from"], serde_json::json!(EXECUTOR));
+        assert_eq!(json["feePayer"], serde_json::json!(SPONSOR));
+        assert_eq!(json["calls"].as_array().map(Vec::len), Some(2));
+        assert_eq!(json["calls"][1]["to"], serde_json::json!(EXECUTOR));
+        assert_eq!(json["calls"][1]["value"], "0x2");
+        assert_eq!(json["feePayerSignature"]["s"], "0x0");
+        assert!(json.get("to").is_none() && json.get("input").is_none());
+        assert_eq!(json["callsSummary"]["callCount"], "0x2");
+        assert_eq!(json["callsSummary"]["totalValue"], "0x3");
+        assert_eq!(
+            json["callsSummary"]["targets"],
+            serde_json::json!([SPONSOR, EXECUTOR])
+        );
+
+        let decoded: EvRpcTransaction = serde_json::from_value(json).expect("deserialize tx");
+        assert_eq!(decoded.fee_payer(), Some(SPONSOR));
+        assert_eq!(decoded.calls_summary(), rpc_tx.calls_summary());
+        assert_eq!(decoded.inner().inner.inner().tx_hash(), envelope.tx_hash());
+    }
+}
@@ -0,0 +1,227 @@
+//! External screening of transactions before pool admission.
+//!
+//! When `--ev-reth.screening-url` is set, every transaction that passes the pool's own checks
+//! is POSTed as a [`ScreeningRequest`] to that URL, and admitted only if the service answers
+//! with a [`ScreeningResponse`] allowing it. Whether transactions are admitted while the
+//! service is unreachable, slow or answering garbage is up to the operator: by default the
+//! node fails open and admits them.
+
+use alloy_consensus::{transaction::TxHashRef, Transaction};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, Bytes, B256};
+use ev_primitives::{Call, EvTxEnvelope};
+use reqwest::Url;
+use reth_transaction_pool::TransactionOrigin;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+use tracing::warn;
+
+/// Default time the screening service has to answer, in milliseconds.
+pub const DEFAULT_SCREENING_TIMEOUT_MS: u64 = 500;
+
+/// Transaction submitted to the screening service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreeningRequest {
+    /// Transaction hash.
+    pub hash: B256,
+    /// Recovered sender, the executor of an EvNode batch.
+    pub from: Address,
+    /// Sponsor or contract fee payer of an EvNode transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<Address>,
+    /// Calls made by the transaction: its single call, or every call of an EvNode batch.
+    pub calls: Vec<Call>,
+    /// Where the transaction came from: `local`, `external` or `private`.
+    pub origin: String,
+    /// EIP-2718 encoding of the transaction.
+    pub raw: Bytes,
+}
+
+impl ScreeningRequest {
+    /// Builds the request for `tx` recovered from `sender`.
+    pub fn new(tx: &EvTxEnvelope, sender: Address, origin: TransactionOrigin) -> Self {
+        let (fee_payer, calls) = match tx {
+            EvTxEnvelope::EvNode(signed) => {
+                let batch = signed.tx();
+                (batch.fee_payer(sender), batch.calls.clone())
+            }
+            _ => (
+                None,
+                vec![Call {
+                    to: tx.kind(),
+                    value: tx.value(),
+                    input: tx.input().clone(),
+                    gas_limit: None,
+                }],
+            ),
+        };
+        Self {
+            hash: *tx.tx_hash(),
+            from: sender,
+            fee_payer,
+            calls,
+            origin: origin_label(origin).to_string(),
+            raw: tx.encoded_2718().into(),
+        }
+    }
+}
+
+/// Verdict of the screening service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScreeningResponse {
+    /// Whether the transaction may enter the pool.
+    pub allow: bool,
+    /// Why the transaction was rejected, returned to the submitter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Errors of a screened transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ScreeningError {
+    /// The screening service rejected the transaction.
+    #[error("transaction rejected by screening: {0}")]
+    Rejected(String),
+    /// The screening service did not answer in time or with a verdict, and the node fails
+    /// closed.
+    #[error("transaction screening unavailable: {0}")]
+    Unavailable(String),
+}
+
+/// HTTP client for an external transaction screening service.
+#[derive(Debug, Clone)]
+pub struct TransactionScreener {
+    client: reqwest::Client,
+    url: Url,
+    timeout: Duration,
+    fail_closed: bool,
+}
+
+impl TransactionScreener {
+    /// Creates a screener POSTing to `url`, which has `timeout` to answer. Transactions are
+    /// rejected while the service fails if `fail_closed` is set, and admitted otherwise.
+    pub fn new(url: Url, timeout: Duration, fail_closed: bool) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            timeout,
+            fail_closed,
+        }
+    }
+
+    /// Returns the URL of the screening service.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns true if transactions are rejected while the service fails.
+    pub const fn fail_closed(&self) -> bool {
+        self.fail_closed
+    }
+
+    /// Asks the screening service whether `request` may enter the pool.
+    pub async fn screen(&self, request: &ScreeningRequest) -> Result<(), ScreeningError> {
+        let verdict = match self.query(request).await {
+            Ok(response) if response.allow => Ok(()),
+            Ok(response) => Err(ScreeningError::Rejected(
+                response
+                    .reason
+                    .unwrap_or_else(|| "no reason given".to_string()),
+            )),
+            Err(err) if self.fail_closed => Err(ScreeningError::Unavailable(err.to_string())),
+            Err(err) => {
+                warn!(
+                    target: "ev_reth::screening",
+                    tx_hash = %request.hash,
+                    %err,
+                    "Screening service failed, admitting transaction"
+                );
+                metrics::counter!("ev_reth_txpool_screening_total", "outcome" => "failed_open")
+                    .increment(1);
+                return Ok(());
+            }
+        };
+        let outcome = match &verdict {
+            Ok(()) => "allowed",
+            Err(ScreeningError::Rejected(_)) => "rejected",
+            Err(ScreeningError::Unavailable(_)) => "failed_closed",
+        };
+        metrics::counter!("ev_reth_txpool_screening_total", "outcome" => outcome).increment(1);
+        verdict
+    }
+
+    async fn query(&self, request: &ScreeningRequest) -> reqwest::Result<ScreeningResponse> {
+        self.client
+            .post(self.url.clone())
+            .timeout(self.timeout)
+            .json(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+/// Label sent for the origin of a transaction.
+const fn origin_label(origin: TransactionOrigin) -> &'static str {
+    match origin {
+        TransactionOrigin::Local => "local",
+        TransactionOrigin::External => "external",
+        TransactionOrigin::Private => "private",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Signed, TxLegacy};
+    use alloy_primitives::{Signature, TxKind, U256};
+
+    #[test]
+    fn request_carries_the_single_call_of_ethereum_transactions() {
+        let to = Address::repeat_byte(0xbb);
+        let tx = TxLegacy {
+            to: TxKind::Call(to),
+            value: U256::from(7),
+            input: Bytes::from_static(&[0xde, 0xad]),
+            ..Default::default()
+        };
+        let signed = Signed::new_unhashed(
+            reth_ethereum_primitives::Transaction::Legacy(tx),
+            Signature::test_signature(),
+        );
+        let envelope =
+            EvTxEnvelope::Ethereum(reth_ethereum_primitives::TransactionSigned::from(signed));
+        let sender = Address::repeat_byte(0xaa);
+
+        let request = ScreeningRequest::new(&envelope, sender, TransactionOrigin::External);
+        assert_eq!(request.from, sender);
+        assert_eq!(request.fee_payer, None);
+        assert_eq!(request.calls.len(), 1);
+        assert_eq!(request.calls[0].to, TxKind::Call(to));
+        assert_eq!(request.calls[0].value, U256::from(7));
+        assert_eq!(request.raw, Bytes::from(envelope.encoded_2718()));
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["origin"], "external");
+        assert!(json.get("feePayer").is_none());
+    }
+
+    #[test]
+    fn response_reason_is_optional() {
+        let response: ScreeningResponse =
+            serde_json::from_value(serde_json::json!({ "allow": true })).unwrap();
+        assert!(response.allow);
+        assert_eq!(response.reason, None);
+
+        let response: ScreeningResponse = serde_json::from_value(
+            serde_json::json!({ "allow": false, "reason": "sanctioned sender" }),
+        )
+        .unwrap();
+        assert!(!response.allow);
+        assert_eq!(response.reason.as_deref(), Some("sanctioned sender"));
+    }
+}
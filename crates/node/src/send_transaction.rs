@@ -0,0 +1,460 @@
+//! EvNode batches signed by a node-managed executor key.
+//!
+//! `evnode_sendTransaction` takes the calls of a batch, fills in what the caller left out
+//! (gas limit, fees and nonce), signs the batch with the node's executor key and submits it to
+//! the pool. Integration tests and sequencer tooling can then send batches with a plain JSON
+//! request instead of building and signing `0x76` RLP themselves. The same signer backs the
+//! EIP-5792 `wallet_sendCalls` namespace, so wallets that speak it can batch through the node.
+//!
+//! Anyone who can reach the endpoint spends the executor account's funds, so it is only served
+//! by `ev-dev` and by nodes started with `--ev-reth.executor-key-file`, and should not be
+//! exposed publicly.
+
+use alloy_consensus::{
+    transaction::{Recovered, TxHashRef},
+    BlockHeader, Header, TxReceipt,
+};
+use alloy_eips::{eip2930::AccessList, BlockId};
+use alloy_primitives::{Address, B256, U64};
+use alloy_signer_local::PrivateKeySigner;
+use async_trait::async_trait;
+use ev_primitives::{Call, EvNodeTransactionBuilder, Receipt, RevertMode, TransactionSigned};
+use evolve_ev_reth::rpc::wallet::{
+    WalletBatchState, WalletCallsBackend, WalletCallsLog, WalletCallsReceipt,
+};
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_storage_api::{
+    AccountReader, BlockIdReader, HeaderProvider, ReceiptProvider, StateProviderFactory,
+    TransactionsProvider,
+};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::info;
+
+use crate::{
+    batch_gas::{estimate_batch_gas, BatchGasError, RpcBatchGasRequest},
+    nonce::next_nonce,
+    signer::DEFAULT_PRIORITY_FEE,
+    EvolveEvmConfig,
+};
+
+/// Unsigned EvNode batch sent with `evnode_sendTransaction`.
+///
+/// Fields left out are filled in by the node: the gas limit is estimated, the fees follow the
+/// latest base fee and the nonce is the executor's next free nonce.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSendTransactionRequest {
+    /// Calls executed in order.
+    pub calls: Vec<Call>,
+    /// EIP-2930 access list.
+    #[serde(default)]
+    pub access_list: AccessList,
+    /// How the batch handles a failing call.
+    #[serde(default)]
+    pub revert_mode: RevertMode,
+    /// Gas limit of the batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas: Option<U64>,
+    /// Maximum fee per gas, in wei.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub max_fee_per_gas: Option<u128>,
+    /// Maximum priority fee per gas, in wei.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// Nonce of the batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+}
+
+impl RpcSendTransactionRequest {
+    /// Returns the `(max_fee_per_gas, max_priority_fee_per_gas)` of the batch in a block with
+    /// `base_fee`.
+    ///
+    /// A missing priority fee defaults to [`DEFAULT_PRIORITY_FEE`], capped by the maximum fee
+    /// if one is set, and a missing maximum fee leaves room for the base fee to double.
+    fn fees(&self, base_fee: u64) -> (u128, u128) {
+        let priority_fee = self.max_priority_fee_per_gas.unwrap_or_else(|| {
+            self.max_fee_per_gas
+                .map_or(DEFAULT_PRIORITY_FEE, |max_fee| {
+                    max_fee.min(DEFAULT_PRIORITY_FEE)
+                })
+        });
+        let max_fee = self
+            .max_fee_per_gas
+            .unwrap_or_else(|| u128::from(base_fee) * 2 + priority_fee);
+        (max_fee, priority_fee)
+    }
+}
+
+/// Errors of `evnode_sendTransaction`.
+#[derive(Debug, Error)]
+pub enum SendTransactionError {
+    /// The batch has no calls.
+    #[error("batch must contain at least one call")]
+    EmptyBatch,
+    /// The gas limit of the batch could not be estimated.
+    #[error(transparent)]
+    Estimate(#[from] BatchGasError),
+    /// The gas estimation task did not complete.
+    #[error("gas estimation failed: {0}")]
+    EstimateTask(String),
+    /// Error while reading the executor account or the latest block.
+    #[error("state provider error: {0}")]
+    Provider(String),
+    /// The transaction could not be signed.
+    #[error("failed to sign transaction: {0}")]
+    Signing(String),
+    /// The pool rejected the transaction.
+    #[error("transaction rejected by the pool: {0}")]
+    Pool(String),
+}
+
+impl From<SendTransactionError> for ErrorObject<'static> {
+    fn from(err: SendTransactionError) -> Self {
+        match err {
+            SendTransactionError::Estimate(err) => err.into(),
+            SendTransactionError::EmptyBatch => {
+                Self::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>)
+            }
+            _ => Self::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>),
+        }
+    }
+}
+
+/// Signer completing and submitting batches with the node's executor key.
+#[derive(Debug)]
+pub struct ExecutorSigner<Provider, Pool> {
+    provider: Provider,
+    pool: Pool,
+    evm_config: EvolveEvmConfig,
+    signer: PrivateKeySigner,
+    chain_id: u64,
+    /// Serializes nonce assignment so concurrent requests do not reuse a nonce.
+    submission: tokio::sync::Mutex<()>,
+}
+
+impl<Provider, Pool> ExecutorSigner<Provider, Pool> {
+    /// Creates a signer sending batches signed by `signer` on `chain_id`, estimating their gas
+    /// with `evm_config`.
+    pub fn new(
+        provider: Provider,
+        pool: Pool,
+        evm_config: EvolveEvmConfig,
+        signer: PrivateKeySigner,
+        chain_id: u64,
+    ) -> Self {
+        Self {
+            provider,
+            pool,
+            evm_config,
+            signer,
+            chain_id,
+            submission: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Returns the address of the executor account.
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+}
+
+impl<Provider, Pool> ExecutorSigner<Provider, Pool>
+where
+    Provider:
+        StateProviderFactory + BlockIdReader + HeaderProvider<Header = Header> + Clone + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    /// Completes, signs and submits `request` and returns the transaction hash.
+    pub async fn send(
+        &self,
+        request: RpcSendTransactionRequest,
+    ) -> Result<B256, SendTransactionError> {
+        if request.calls.is_empty() {
+            return Err(SendTransactionError::EmptyBatch);
+        }
+        let executor = self.signer.address();
+        let gas_limit = match request.gas {
+            Some(gas) => gas.to::<u64>(),
+            None => self.estimate(&request).await?,
+        };
+
+        let _submission = self.submission.lock().await;
+        let provider_error =
+            |err: reth_provider::ProviderError| SendTransactionError::Provider(err.to_string());
+        let block_number = self.provider.best_block_number().map_err(provider_error)?;
+        let header = self
+            .provider
+            .header_by_number(block_number)
+            .map_err(provider_error)?
+            .ok_or_else(|| SendTransactionError::Provider("latest header not found".to_string()))?;
+        let (max_fee, priority_fee) = request.fees(header.base_fee_per_gas().unwrap_or_default());
+        let nonce = match request.nonce {
+            Some(nonce) => nonce.to::<u64>(),
+            None => {
+                let latest_nonce = self
+                    .provider
+                    .latest()
+                    .and_then(|state| state.basic_account(&executor))
+                    .map_err(provider_error)?
+                    .map_or(0, |account| account.nonce);
+                let pooled = self
+                    .pool
+                    .get_transactions_by_sender(executor)
+                    .into_iter()
+                    .map(|tx| tx.transaction.nonce());
+                next_nonce(latest_nonce, pooled).nonce.to::<u64>()
+            }
+        };
+
+        let calls = request.calls.len();
+        let tx = request
+            .calls
+            .into_iter()
+            .fold(
+                EvNodeTransactionBuilder::new(self.chain_id),
+                |builder, call| builder.call(call),
+            )
+            .nonce(nonce)
+            .gas_limit(gas_limit)
+            .max_fee_per_gas(max_fee)
+            .max_priority_fee_per_gas(priority_fee)
+            .access_list(request.access_list)
+            .revert_mode(request.revert_mode)
+            .sign_executor(&self.signer)
+            .map_err(|err| SendTransactionError::Signing(err.to_string()))?;
+        let tx_hash = *tx.tx_hash();
+
+        self.pool
+            .add_consensus_transaction(
+                Recovered::new_unchecked(tx, executor),
+                TransactionOrigin::Local,
+            )
+            .await
+            .map_err(|err| SendTransactionError::Pool(err.to_string()))?;
+        info!(
+            target: "ev_reth::send_transaction",
+            %executor,
+            nonce,
+            calls,
+            gas_limit,
+            %tx_hash,
+            "Sent node-signed batch"
+        );
+        Ok(tx_hash)
+    }
+
+    /// Estimates the gas limit of `request` on top of the latest block.
+    async fn estimate(
+        &self,
+        request: &RpcSendTransactionRequest,
+    ) -> Result<u64, SendTransactionError> {
+        let provider = self.provider.clone();
+        let evm_config = self.evm_config.clone();
+        let request = RpcBatchGasRequest {
+            from: self.signer.address(),
+            calls: request.calls.clone(),
+            access_list: request.access_list.clone(),
+        };
+        let estimate = tokio::task::spawn_blocking(move || {
+            estimate_batch_gas(&provider, &evm_config, BlockId::latest(), request)
+        })
+        .await
+        .map_err(|err| SendTransactionError::EstimateTask(err.to_string()))??;
+        Ok(estimate.gas.to::<u64>())
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> WalletCallsBackend for ExecutorSigner<Provider, Pool>
+where
+    Provider: StateProviderFactory
+        + BlockIdReader
+        + HeaderProvider<Header = Header>
+        + ReceiptProvider<Receipt = Receipt>
+        + TransactionsProvider<Transaction = TransactionSigned>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Send
+        + Sync
+        + 'static,
+{
+    fn account(&self) -> Address {
+        self.signer.address()
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    async fn send_calls(&self, calls: Vec<Call>) -> Result<B256, ErrorObject<'static>> {
+        let request = RpcSendTransactionRequest {
+            calls,
+            access_list: AccessList::default(),
+            revert_mode: RevertMode::Atomic,
+            gas: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            nonce: None,
+        };
+        Ok(self.send(request).await?)
+    }
+
+    fn batch_state(&self, tx_hash: B256) -> Result<Option<WalletBatchState>, ErrorObject<'static>> {
+        let provider_error = |err: reth_provider::ProviderError| {
+            ErrorObject::from(SendTransactionError::Provider(err.to_string()))
+        };
+        let Some((_, meta)) = self
+            .provider
+            .transaction_by_hash_with_meta(tx_hash)
+            .map_err(provider_error)?
+        else {
+            return Ok(self
+                .pool
+                .contains(&tx_hash)
+                .then_some(WalletBatchState::Pending));
+        };
+        let receipts = self
+            .provider
+            .receipts_by_block(meta.block_hash.into())
+            .map_err(provider_error)?
+            .unwrap_or_default();
+        let index = meta.index as usize;
+        let Some(receipt) = receipts.get(index) else {
+            return Ok(None);
+        };
+        let previous_gas = index
+            .checked_sub(1)
+            .and_then(|previous| receipts.get(previous))
+            .map_or(0, |previous| previous.cumulative_gas_used());
+        Ok(Some(WalletBatchState::Included(WalletCallsReceipt {
+            logs: receipt
+                .logs()
+                .iter()
+                .map(|log| WalletCallsLog {
+                    address: log.address,
+                    data: log.data.data.clone(),
+                    topics: log.topics().to_vec(),
+                })
+                .collect(),
+            status: U64::from(receipt.status()),
+            block_hash: meta.block_hash,
+            block_number: U64::from(meta.block_number),
+            gas_used: U64::from(receipt.cumulative_gas_used() - previous_gas),
+            transaction_hash: tx_hash,
+        })))
+    }
+}
+
+/// Node-signed transaction RPC API.
+#[rpc(server, namespace = "evnode")]
+pub trait EvNodeSendTransactionApi {
+    /// Fills in the gas limit, fees and nonce of an EvNode batch, signs it with the node's
+    /// executor key and submits it to the pool. Returns the transaction hash.
+    #[method(name = "sendTransaction")]
+    async fn send_transaction(&self, request: RpcSendTransactionRequest) -> RpcResult<B256>;
+}
+
+/// Implementation of the node-signed transaction RPC.
+#[derive(Debug)]
+pub struct EvNodeSendTransactionApiImpl<Provider, Pool> {
+    signer: Arc<ExecutorSigner<Provider, Pool>>,
+}
+
+impl<Provider, Pool> EvNodeSendTransactionApiImpl<Provider, Pool> {
+    /// Creates a new instance sending batches through `signer`.
+    pub const fn new(signer: Arc<ExecutorSigner<Provider, Pool>>) -> Self {
+        Self { signer }
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> EvNodeSendTransactionApiServer for EvNodeSendTransactionApiImpl<Provider, Pool>
+where
+    Provider: StateProviderFactory
+        + BlockIdReader
+        + HeaderProvider<Header = Header>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Send
+        + Sync
+        + 'static,
+{
+    async fn send_transaction(&self, request: RpcSendTransactionRequest) -> RpcResult<B256> {
+        Ok(self.signer.send(request).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, TxKind, U256};
+
+    #[test]
+    fn request_defaults_left_to_the_node() {
+        let request: RpcSendTransactionRequest = serde_json::from_value(serde_json::json!({
+            "calls": [{ "to": "0x7070707070707070707070707070707070707070", "value": "0x1", "input": "0x" }],
+        }))
+        .unwrap();
+        assert_eq!(
+            request.calls,
+            vec![Call {
+                to: TxKind::Call(Address::repeat_byte(0x70)),
+                value: U256::from(1),
+                input: Bytes::new(),
+                gas_limit: None,
+            }]
+        );
+        assert_eq!(request.revert_mode, RevertMode::Atomic);
+        assert_eq!(request.gas, None);
+        assert_eq!(request.nonce, None);
+        assert_eq!(
+            request.fees(7),
+            (14 + DEFAULT_PRIORITY_FEE, DEFAULT_PRIORITY_FEE)
+        );
+    }
+
+    #[test]
+    fn request_fees_respect_caller_values() {
+        let request = RpcSendTransactionRequest {
+            calls: Vec::new(),
+            access_list: AccessList::default(),
+            revert_mode: RevertMode::Atomic,
+            gas: None,
+            max_fee_per_gas: Some(500),
+            max_priority_fee_per_gas: None,
+            nonce: None,
+        };
+        // The default priority fee never exceeds the maximum fee.
+        assert_eq!(request.fees(100), (500, 500));
+
+        let request = RpcSendTransactionRequest {
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: Some(3),
+            ..request
+        };
+        assert_eq!(request.fees(100), (203, 3));
+    }
+}
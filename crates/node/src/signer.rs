@@ -0,0 +1,22 @@
+//! Keys the node signs with and defaults of the transactions it sends itself.
+//!
+//! The executor, faucet and sponsor keys sign transactions the node submits to its own pool,
+//! and the operator key signs build attestations. Each is read from a file holding a
+//! hex-encoded secp256k1 private key.
+
+use alloy_signer_local::PrivateKeySigner;
+use eyre::WrapErr;
+use std::path::Path;
+
+/// Priority fee of transactions the node sends without one set, in wei.
+pub const DEFAULT_PRIORITY_FEE: u128 = 1_000_000_000;
+
+/// Loads a signer key from a file holding it hex-encoded.
+pub fn load_signer_key(path: &Path) -> eyre::Result<PrivateKeySigner> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read key file {}", path.display()))?;
+    contents
+        .trim()
+        .parse()
+        .wrap_err_with(|| format!("invalid key in {}", path.display()))
+}
@@ -0,0 +1,341 @@
+//! `evnode_simulateBatch` RPC.
+//!
+//! Dapps preview the effects of a batch before asking a sponsor to sign it. The calls run
+//! unsigned on top of a block, optionally with state overrides, and the node reports what
+//! each call returned, logged and used, along with the balances the batch changes.
+
+use alloy_consensus::Header;
+use alloy_eips::{eip2930::AccessList, BlockId};
+use alloy_evm::overrides::apply_state_overrides;
+use alloy_primitives::{Address, Bytes, Log, U256, U64};
+use alloy_rpc_types_eth::state::StateOverride;
+use async_trait::async_trait;
+use ev_primitives::Call;
+use ev_revm::EvTxEnv;
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_evm::{ConfigureEvm, Evm};
+use reth_revm::{
+    database::StateProviderDatabase,
+    revm::{
+        context::TxEnv,
+        context_interface::{result::ExecutionResult, transaction::TransactionType},
+    },
+    Database, State,
+};
+use reth_storage_api::{
+    errors::provider::ProviderError, BlockIdReader, HeaderProvider, StateProviderFactory,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::EvolveEvmConfig;
+
+/// Most calls a batch passed to `evnode_simulateBatch` may contain.
+pub const MAX_SIMULATED_CALLS: usize = 128;
+
+/// EvNode batch simulated by `evnode_simulateBatch`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulateBatchRequest {
+    /// Executor of the batch.
+    pub from: Address,
+    /// Calls executed in order.
+    pub calls: Vec<Call>,
+    /// EIP-2930 access list.
+    #[serde(default)]
+    pub access_list: AccessList,
+}
+
+/// Outcome of one call of a simulated batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulatedCall {
+    /// Whether the call succeeded.
+    pub success: bool,
+    /// Return data of the call, or its revert data.
+    pub return_data: Bytes,
+    /// Gas the batch uses once the call is appended, including its intrinsic cost.
+    pub gas_used: U64,
+    /// Logs emitted by the call.
+    pub logs: Vec<Log>,
+    /// Halt reason if the call halted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Balance of an account before and after a simulated batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBalanceChange {
+    /// Account whose balance changed.
+    pub address: Address,
+    /// Balance before the batch, after state overrides.
+    pub before: U256,
+    /// Balance after the batch.
+    pub after: U256,
+}
+
+/// Result returned by `evnode_simulateBatch`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBatchSimulation {
+    /// Whether every call succeeded, i.e. the atomic batch would succeed.
+    pub success: bool,
+    /// Gas used by the batch, after refunds.
+    pub gas_used: U64,
+    /// Outcome of each call up to and including the first failing one.
+    pub calls: Vec<RpcSimulatedCall>,
+    /// Accounts whose balance the batch changes, ordered by address.
+    pub balance_changes: Vec<RpcBalanceChange>,
+}
+
+/// Errors returned by `evnode_simulateBatch`.
+#[derive(Debug, Error)]
+pub enum SimulateBatchError {
+    /// The batch is empty or longer than [`MAX_SIMULATED_CALLS`].
+    #[error("batch must contain between 1 and {MAX_SIMULATED_CALLS} calls")]
+    CallCount,
+    /// The requested block is unknown.
+    #[error("unknown block")]
+    UnknownBlock,
+    /// The state overrides could not be applied.
+    #[error("invalid state override: {0}")]
+    StateOverride(String),
+    /// The batch up to a call is not a valid transaction, e.g. because a call other than the
+    /// first creates a contract or the caller cannot cover the transferred value.
+    #[error("invalid batch at call {index}: {reason}")]
+    Invalid {
+        /// Position of the call that made the batch invalid.
+        index: usize,
+        /// Validation error.
+        reason: String,
+    },
+    /// The block's header or state could not be read.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<SimulateBatchError> for ErrorObject<'static> {
+    fn from(err: SimulateBatchError) -> Self {
+        match err {
+            SimulateBatchError::Provider(_) => {
+                Self::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+            }
+            _ => Self::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>),
+        }
+    }
+}
+
+/// Simulates `request` on top of the state after `block` with `overrides` applied. Nothing
+/// is written to the database.
+///
+/// Each prefix of the batch is executed to attribute return data, logs and gas to its last
+/// call, stopping at the first failing call since the batch is atomic.
+pub(crate) fn simulate_batch<Provider>(
+    provider: &Provider,
+    evm_config: &EvolveEvmConfig,
+    block: BlockId,
+    request: RpcSimulateBatchRequest,
+    overrides: Option<StateOverride>,
+) -> Result<RpcBatchSimulation, SimulateBatchError>
+where
+    Provider: StateProviderFactory + BlockIdReader + HeaderProvider<Header = Header>,
+{
+    if request.calls.is_empty() || request.calls.len() > MAX_SIMULATED_CALLS {
+        return Err(SimulateBatchError::CallCount);
+    }
+    let hash = provider
+        .block_hash_for_id(block)?
+        .ok_or(SimulateBatchError::UnknownBlock)?;
+    let header = provider
+        .header(hash)?
+        .ok_or(SimulateBatchError::UnknownBlock)?;
+    let state = provider.history_by_block_hash(hash)?;
+
+    let mut evm_env = evm_config
+        .evm_env(&header)
+        .unwrap_or_else(|err| match err {});
+    // Like `evolve_estimateBatchGas`: gas is free and the nonce is not checked, so the
+    // balance changes only show what the calls themselves move.
+    evm_env.cfg_env.disable_base_fee = true;
+    evm_env.cfg_env.disable_nonce_check = true;
+    let gas_limit = evm_env
+        .block_env
+        .gas_limit
+        .min(evm_env.cfg_env.tx_gas_limit_cap());
+    let tx = TxEnv {
+        caller: request.from,
+        gas_limit,
+        chain_id: Some(evm_env.cfg_env.chain_id),
+        access_list: request.access_list,
+        tx_type: TransactionType::Eip1559.into(),
+        ..Default::default()
+    };
+    let mut db = State::builder()
+        .with_database(StateProviderDatabase::new(&state))
+        .build();
+    if let Some(overrides) = overrides {
+        apply_state_overrides(overrides, &mut db)
+            .map_err(|err| SimulateBatchError::StateOverride(err.to_string()))?;
+    }
+    let mut evm = evm_config.evm_with_env(db, evm_env);
+
+    let mut calls = Vec::with_capacity(request.calls.len());
+    let mut gas_used = 0;
+    let mut logs = 0;
+    let mut last = None;
+    for index in 0..request.calls.len() {
+        let outcome = evm
+            .transact(EvTxEnv::with_calls(
+                tx.clone(),
+                request.calls[..=index].to_vec(),
+            ))
+            .map_err(|err| SimulateBatchError::Invalid {
+                index,
+                reason: err.to_string(),
+            })?;
+        let result = &outcome.result;
+        let prefix_gas_used = result.gas().tx_gas_used();
+        let (return_data, error) = match result {
+            ExecutionResult::Success { .. } => (result.output().cloned().unwrap_or_default(), None),
+            ExecutionResult::Revert { output, .. } => (output.clone(), None),
+            ExecutionResult::Halt { reason, .. } => (Bytes::new(), Some(format!("{reason:?}"))),
+        };
+        calls.push(RpcSimulatedCall {
+            success: result.is_success(),
+            return_data,
+            gas_used: U64::from(prefix_gas_used.saturating_sub(gas_used)),
+            logs: result.logs().get(logs..).unwrap_or_default().to_vec(),
+            error,
+        });
+        gas_used = prefix_gas_used;
+        logs = result.logs().len();
+        let success = result.is_success();
+        last = Some(outcome);
+        if !success {
+            break;
+        }
+    }
+    let Some(outcome) = last else {
+        return Err(SimulateBatchError::CallCount);
+    };
+
+    let mut balance_changes = Vec::new();
+    for (address, account) in outcome.state {
+        let before = evm
+            .db_mut()
+            .basic(address)?
+            .map(|info| info.balance)
+            .unwrap_or_default();
+        if before != account.info.balance {
+            balance_changes.push(RpcBalanceChange {
+                address,
+                before,
+                after: account.info.balance,
+            });
+        }
+    }
+    balance_changes.sort_unstable_by_key(|change| change.address);
+
+    Ok(RpcBatchSimulation {
+        success: outcome.result.is_success(),
+        gas_used: U64::from(gas_used),
+        calls,
+        balance_changes,
+    })
+}
+
+/// Batch simulation RPC API.
+#[rpc(server, namespace = "evnode")]
+pub trait EvNodeSimulateBatchApi {
+    /// Executes an unsigned EvNode batch by `from` on top of `block` (latest by default) with
+    /// optional state overrides, and returns each call's outcome and the balance changes.
+    #[method(name = "simulateBatch")]
+    async fn simulate_batch(
+        &self,
+        request: RpcSimulateBatchRequest,
+        block: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> RpcResult<RpcBatchSimulation>;
+}
+
+/// Implementation of the batch simulation RPC.
+#[derive(Debug)]
+pub struct EvNodeSimulateBatchApiImpl<Provider> {
+    provider: Provider,
+    evm_config: EvolveEvmConfig,
+}
+
+impl<Provider> EvNodeSimulateBatchApiImpl<Provider> {
+    /// Creates a new instance executing batches with `evm_config` on state read from
+    /// `provider`.
+    pub const fn new(provider: Provider, evm_config: EvolveEvmConfig) -> Self {
+        Self {
+            provider,
+            evm_config,
+        }
+    }
+}
+
+#[async_trait]
+impl<Provider> EvNodeSimulateBatchApiServer for EvNodeSimulateBatchApiImpl<Provider>
+where
+    Provider:
+        StateProviderFactory + BlockIdReader + HeaderProvider<Header = Header> + Clone + 'static,
+{
+    async fn simulate_batch(
+        &self,
+        request: RpcSimulateBatchRequest,
+        block: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> RpcResult<RpcBatchSimulation> {
+        let provider = self.provider.clone();
+        let evm_config = self.evm_config.clone();
+        let block = block.unwrap_or_default();
+        tokio::task::spawn_blocking(move || {
+            simulate_batch(&provider, &evm_config, block, request, state_overrides)
+        })
+        .await
+        .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>))?
+        .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simulation_serializes_camel_case() {
+        let simulation = RpcBatchSimulation {
+            success: false,
+            gas_used: U64::from(21_000),
+            calls: vec![RpcSimulatedCall {
+                success: false,
+                return_data: Bytes::from_static(&[0x08, 0xc3, 0x79, 0xa0]),
+                gas_used: U64::from(21_000),
+                logs: Vec::new(),
+                error: None,
+            }],
+            balance_changes: vec![RpcBalanceChange {
+                address: Address::repeat_byte(0xaa),
+                before: U256::from(2),
+                after: U256::from(1),
+            }],
+        };
+        let json = serde_json::to_value(&simulation).unwrap();
+        assert_eq!(json["gasUsed"], "0x5208");
+        assert_eq!(json["calls"][0]["returnData"], "0x08c379a0");
+        assert!(json["calls"][0].get("error").is_none());
+        assert_eq!(json["balanceChanges"][0]["before"], "0x2");
+        assert_eq!(
+            serde_json::from_value::<RpcBatchSimulation>(json).unwrap(),
+            simulation
+        );
+    }
+}
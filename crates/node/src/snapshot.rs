@@ -0,0 +1,146 @@
+use alloy_eips::{BlockId, BlockNumHash};
+use reth_storage_api::{
+    errors::provider::ProviderError, BlockIdReader, BlockNumReader, StateProvider,
+    StateProviderBox, StateProviderFactory,
+};
+use std::{fmt, sync::Arc};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Default number of state snapshots that may be open at once.
+pub const DEFAULT_MAX_STATE_SNAPSHOTS: usize = 64;
+
+/// Error pinning a state snapshot.
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    /// The requested block is not known to the node.
+    #[error("unknown block {0}")]
+    UnknownBlock(BlockId),
+    /// Every snapshot slot is in use.
+    #[error("too many concurrent state snapshots, retry later")]
+    Busy,
+    /// Reading from the database failed.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// Hands out [`StateSnapshot`]s for reads that span several lookups.
+///
+/// A snapshot holds its state provider, and with it the underlying database read transaction,
+/// open until dropped, so block commits and pruning that happen during a long read are not
+/// visible to it. Open read transactions keep the database from reusing freed pages, so the
+/// number of open snapshots is capped.
+#[derive(Debug, Clone)]
+pub struct StateSnapshots<P> {
+    provider: P,
+    permits: Arc<Semaphore>,
+}
+
+impl<P> StateSnapshots<P>
+where
+    P: BlockIdReader + StateProviderFactory,
+{
+    /// Creates a snapshot source allowing at most `max_open` snapshots at once.
+    pub fn new(provider: P, max_open: usize) -> Self {
+        Self {
+            provider,
+            permits: Arc::new(Semaphore::new(max_open)),
+        }
+    }
+
+    /// Pins the state after `block`.
+    ///
+    /// Block tags are resolved once, here, so every read through the snapshot sees the same
+    /// block even if the head moves. Fails with [`SnapshotError::Busy`] rather than waiting when
+    /// every slot is taken.
+    pub fn pin(&self, block: BlockId) -> Result<StateSnapshot, SnapshotError> {
+        let permit = self
+            .permits
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| SnapshotError::Busy)?;
+        let hash = self
+            .provider
+            .block_hash_for_id(block)?
+            .ok_or(SnapshotError::UnknownBlock(block))?;
+        let number = self
+            .provider
+            .block_number(hash)?
+            .ok_or(SnapshotError::UnknownBlock(block))?;
+        let state = self.provider.state_by_block_hash(hash)?;
+        metrics::gauge!("ev_reth_state_snapshots_open").increment(1);
+        Ok(StateSnapshot {
+            block: BlockNumHash::new(number, hash),
+            state,
+            _permit: permit,
+        })
+    }
+}
+
+/// State pinned at a single block, released on drop.
+pub struct StateSnapshot {
+    block: BlockNumHash,
+    state: StateProviderBox,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl StateSnapshot {
+    /// Returns the block the snapshot was taken at.
+    pub const fn block(&self) -> BlockNumHash {
+        self.block
+    }
+
+    /// Returns the pinned state.
+    pub fn state(&self) -> &dyn StateProvider {
+        self.state.as_ref()
+    }
+}
+
+impl fmt::Debug for StateSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateSnapshot")
+            .field("block", &self.block)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for StateSnapshot {
+    fn drop(&mut self) {
+        metrics::gauge!("ev_reth_state_snapshots_open").decrement(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_provider::test_utils::MockEthProvider;
+
+    #[test]
+    fn caps_open_snapshots() {
+        let provider = MockEthProvider::default();
+        let hash = B256::with_last_byte(1);
+        provider.add_block(hash, Default::default());
+        let snapshots = StateSnapshots::new(provider, 1);
+
+        let first = snapshots.pin(hash.into()).unwrap();
+        assert_eq!(first.block(), BlockNumHash::new(0, hash));
+        assert!(matches!(
+            snapshots.pin(hash.into()),
+            Err(SnapshotError::Busy)
+        ));
+
+        drop(first);
+        assert!(snapshots.pin(hash.into()).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_blocks() {
+        let snapshots = StateSnapshots::new(MockEthProvider::default(), 1);
+        assert!(matches!(
+            snapshots.pin(B256::with_last_byte(1).into()),
+            Err(SnapshotError::UnknownBlock(_))
+        ));
+        // The slot is released when pinning fails.
+        assert_eq!(snapshots.permits.available_permits(), 1);
+    }
+}
@@ -0,0 +1,158 @@
+use alloy_consensus::transaction::TxHashRef;
+use alloy_primitives::{Address, B256};
+use evolve_ev_reth::EvolvePayloadAttributes;
+use reth_primitives_traits::SealedBlock;
+use std::{ops::Range, sync::Mutex};
+
+/// Inputs that must match for a speculatively built block to be reused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpeculationKey {
+    /// Parent the block was built on.
+    pub parent_hash: B256,
+    /// Block timestamp.
+    pub timestamp: u64,
+    /// Fee recipient after sink fallback.
+    pub suggested_fee_recipient: Address,
+    /// `prev_randao` supplied in the attributes.
+    pub prev_randao: B256,
+    /// Effective gas limit.
+    pub gas_limit: Option<u64>,
+    /// Transaction count limit.
+    pub max_tx_count: Option<u64>,
+    /// Hashes of the candidate transactions, in order.
+    pub tx_hashes: Vec<B256>,
+    /// Candidate ranges that must be included atomically.
+    pub bundles: Vec<Range<usize>>,
+}
+
+impl SpeculationKey {
+    /// Derives the key from the attributes a block is built with.
+    pub fn from_attributes(attributes: &EvolvePayloadAttributes) -> Self {
+        Self {
+            parent_hash: attributes.parent_hash,
+            timestamp: attributes.timestamp,
+            suggested_fee_recipient: attributes.suggested_fee_recipient,
+            prev_randao: attributes.prev_randao,
+            gas_limit: attributes.gas_limit,
+            max_tx_count: attributes.max_tx_count,
+            tx_hashes: attributes
+                .transactions
+                .iter()
+                .map(|tx| *tx.tx_hash())
+                .collect(),
+            bundles: attributes.bundles.clone(),
+        }
+    }
+}
+
+/// Single-slot cache holding the speculatively built next block.
+#[derive(Debug, Default)]
+pub struct SpeculativeCache {
+    slot: Mutex<Option<(SpeculationKey, SealedBlock<ev_primitives::Block>)>>,
+    /// Attributes and block time of the last payload built on request, used to predict the
+    /// next one.
+    last_attributes: Mutex<Option<(EvolvePayloadAttributes, u64)>>,
+}
+
+impl SpeculativeCache {
+    /// Stores a speculative block, replacing any previous one.
+    pub fn store(&self, key: SpeculationKey, block: SealedBlock<ev_primitives::Block>) {
+        *self.slot.lock().unwrap_or_else(|e| e.into_inner()) = Some((key, block));
+    }
+
+    /// Takes the speculative block if it was built from exactly `key`.
+    ///
+    /// Any cached block is discarded either way, since it is only valid for one request.
+    pub fn take_matching(&self, key: &SpeculationKey) -> Option<SealedBlock<ev_primitives::Block>> {
+        let (cached_key, block) = self.slot.lock().unwrap_or_else(|e| e.into_inner()).take()?;
+        (cached_key == *key).then_some(block)
+    }
+
+    /// Records the attributes of a payload built on request along with its parent timestamp.
+    pub fn record_attributes(&self, attributes: &EvolvePayloadAttributes, parent_timestamp: u64) {
+        let mut last = attributes.clone();
+        last.transactions.clear();
+        let block_time = attributes.timestamp.saturating_sub(parent_timestamp);
+        *self
+            .last_attributes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some((last, block_time));
+    }
+
+    /// Predicts the attributes of the block following `parent`, assuming ev-node keeps the
+    /// fee recipient, randomness, gas limit and block time of the previous request.
+    pub fn predict_next(
+        &self,
+        parent_hash: B256,
+        parent_number: u64,
+        parent_timestamp: u64,
+    ) -> Option<EvolvePayloadAttributes> {
+        let (last, block_time) = self
+            .last_attributes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()?;
+        Some(
+            EvolvePayloadAttributes::new(
+                Vec::new(),
+                last.gas_limit,
+                parent_timestamp + block_time.max(1),
+                last.prev_randao,
+                last.suggested_fee_recipient,
+                parent_hash,
+                parent_number + 1,
+            )
+            .with_slot_number(last.slot_number),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes(parent_hash: B256, timestamp: u64) -> EvolvePayloadAttributes {
+        EvolvePayloadAttributes::new(
+            vec![],
+            Some(30_000_000),
+            timestamp,
+            B256::repeat_byte(9),
+            Address::repeat_byte(1),
+            parent_hash,
+            1,
+        )
+    }
+
+    #[test]
+    fn predicts_next_attributes_from_last_request() {
+        let cache = SpeculativeCache::default();
+        assert!(cache.predict_next(B256::ZERO, 1, 100).is_none());
+
+        cache.record_attributes(&attributes(B256::ZERO, 102), 100);
+        let next = cache
+            .predict_next(B256::repeat_byte(2), 1, 102)
+            .expect("prediction available");
+        assert_eq!(next.timestamp, 104);
+        assert_eq!(next.block_number, 2);
+        assert_eq!(next.parent_hash, B256::repeat_byte(2));
+        assert_eq!(next.suggested_fee_recipient, Address::repeat_byte(1));
+    }
+
+    #[test]
+    fn mismatched_speculation_is_discarded() {
+        let cache = SpeculativeCache::default();
+        let block = SealedBlock::<ev_primitives::Block>::default();
+        let key = SpeculationKey::from_attributes(&attributes(B256::ZERO, 10));
+
+        cache.store(key.clone(), block.clone());
+        let other = SpeculationKey::from_attributes(&attributes(B256::ZERO, 11));
+        assert!(cache.take_matching(&other).is_none());
+        assert!(
+            cache.take_matching(&key).is_none(),
+            "a miss must discard the speculation"
+        );
+
+        cache.store(key.clone(), block);
+        assert!(cache.take_matching(&key).is_some());
+    }
+}
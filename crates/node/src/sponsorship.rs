@@ -0,0 +1,525 @@
+//! Paymaster endpoint sponsoring EvNode transactions.
+//!
+//! `evnode_requestSponsorship` takes an unsigned EvNode transaction and its executor, asks the
+//! configured sponsorship policy whether the node pays for it, and returns the transaction with
+//! the fee-payer signature of the node's sponsor key. The executor signature does not cover
+//! the fee payer slot, so the executor signs the returned transaction and submits it as usual.
+//!
+//! The policy is a local JSON file or an HTTP endpoint the request is POSTed to. A sponsorship
+//! can be used until the transaction is included, so policies should require `validUntil` and
+//! cap the sponsor's fees.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, TxKind, U64};
+use alloy_signer_local::PrivateKeySigner;
+use async_trait::async_trait;
+use ev_primitives::{EvNodeTransaction, EvNodeTransactionBuilder};
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reqwest::Url;
+use reth_storage_api::{BlockNumReader, HeaderProvider};
+use serde::{Deserialize, Serialize};
+use std::{path::Path, sync::Arc, time::Duration};
+use thiserror::Error;
+use tracing::info;
+
+/// Default time a remote sponsorship policy has to answer, in milliseconds.
+pub const DEFAULT_SPONSORSHIP_POLICY_TIMEOUT_MS: u64 = 1_000;
+
+/// Transaction the node is asked to sponsor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSponsorshipRequest {
+    /// Executor who will sign and submit the transaction.
+    pub from: Address,
+    /// Transaction to sponsor, without a fee-payer signature.
+    pub transaction: EvNodeTransaction,
+}
+
+/// Sponsored transaction returned to the executor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSponsorshipResponse {
+    /// Sponsor paying the transaction's gas.
+    pub sponsor: Address,
+    /// Transaction with the sponsor's fee-payer signature, and the sponsor fee cap set by the
+    /// policy.
+    pub transaction: EvNodeTransaction,
+}
+
+/// Request POSTed to a remote sponsorship policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsorshipPolicyRequest {
+    /// Chain the transaction is for.
+    pub chain_id: U64,
+    /// Executor of the transaction.
+    pub from: Address,
+    /// Sponsor that would pay for it.
+    pub sponsor: Address,
+    /// Transaction to sponsor.
+    pub transaction: EvNodeTransaction,
+}
+
+/// Verdict of a sponsorship policy.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsorshipVerdict {
+    /// Whether the node sponsors the transaction.
+    pub approve: bool,
+    /// Why the sponsorship was denied, returned to the caller.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Most the sponsor pays in gas fees; lowers the transaction's `maxSponsorFee`.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::quantity::opt"
+    )]
+    pub max_sponsor_fee: Option<u128>,
+}
+
+impl SponsorshipVerdict {
+    fn denied(reason: impl Into<String>) -> Self {
+        Self {
+            approve: false,
+            reason: Some(reason.into()),
+            max_sponsor_fee: None,
+        }
+    }
+}
+
+/// Sponsorship rules read from a local policy file.
+///
+/// Every set rule must hold for a transaction to be sponsored; an empty file sponsors
+/// everything.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SponsorshipRules {
+    /// Executors whose transactions are sponsored; any executor if empty.
+    #[serde(default)]
+    pub executors: Vec<Address>,
+    /// Contracts every call must target; any call, including contract creation, if empty.
+    #[serde(default)]
+    pub targets: Vec<Address>,
+    /// Largest sponsored gas limit.
+    #[serde(default)]
+    pub max_gas_limit: Option<u64>,
+    /// Largest sponsored `maxFeePerGas`.
+    #[serde(default)]
+    pub max_fee_per_gas: Option<u128>,
+    /// Most the sponsor pays in gas fees per transaction.
+    #[serde(default)]
+    pub max_sponsor_fee: Option<u128>,
+    /// Longest time, in seconds after the latest block, a sponsorship may stay usable.
+    /// Transactions must set `validUntil` when this is set.
+    #[serde(default)]
+    pub max_validity_secs: Option<u64>,
+}
+
+impl SponsorshipRules {
+    /// Loads rules from a JSON file.
+    pub fn load(path: &Path) -> eyre::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Checks `tx` of executor `from` against the rules as of a latest block at `timestamp`.
+    pub fn evaluate(
+        &self,
+        from: Address,
+        tx: &EvNodeTransaction,
+        timestamp: u64,
+    ) -> SponsorshipVerdict {
+        if !self.executors.is_empty() && !self.executors.contains(&from) {
+            return SponsorshipVerdict::denied(format!("executor {from} is not sponsored"));
+        }
+        if !self.targets.is_empty() {
+            let unlisted = tx.calls.iter().find(|call| match call.to {
+                TxKind::Call(to) => !self.targets.contains(&to),
+                TxKind::Create => true,
+            });
+            if let Some(call) = unlisted {
+                return SponsorshipVerdict::denied(match call.to {
+                    TxKind::Call(to) => format!("calls to {to} are not sponsored"),
+                    TxKind::Create => "contract creations are not sponsored".to_string(),
+                });
+            }
+        }
+        if let Some(max) = self.max_gas_limit.filter(|max| tx.gas_limit > *max) {
+            return SponsorshipVerdict::denied(format!("gas limit above {max}"));
+        }
+        if let Some(max) = self.max_fee_per_gas.filter(|max| tx.max_fee_per_gas > *max) {
+            return SponsorshipVerdict::denied(format!("max fee per gas above {max}"));
+        }
+        if let Some(max_secs) = self.max_validity_secs {
+            let deadline = timestamp.saturating_add(max_secs);
+            match tx.valid_until {
+                None => return SponsorshipVerdict::denied("validUntil is required"),
+                Some(valid_until) if valid_until > deadline => {
+                    return SponsorshipVerdict::denied(format!("validUntil after {deadline}"));
+                }
+                Some(_) => {}
+            }
+        }
+        SponsorshipVerdict {
+            approve: true,
+            reason: None,
+            max_sponsor_fee: self.max_sponsor_fee,
+        }
+    }
+}
+
+/// Policy deciding which transactions the node sponsors.
+#[derive(Debug, Clone)]
+pub enum SponsorshipPolicy {
+    /// Rules from a local policy file.
+    Local(SponsorshipRules),
+    /// HTTP endpoint each [`SponsorshipPolicyRequest`] is POSTed to, answering with a
+    /// [`SponsorshipVerdict`].
+    Remote {
+        /// HTTP client.
+        client: reqwest::Client,
+        /// Policy endpoint.
+        url: Url,
+        /// Time the endpoint has to answer.
+        timeout: Duration,
+    },
+}
+
+impl SponsorshipPolicy {
+    /// Creates a policy POSTing requests to `url`, which has `timeout` to answer.
+    pub fn remote(url: Url, timeout: Duration) -> Self {
+        Self::Remote {
+            client: reqwest::Client::new(),
+            url,
+            timeout,
+        }
+    }
+
+    /// Returns a short description of the policy for logs.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Local(_) => "local policy file".to_string(),
+            Self::Remote { url, .. } => url.to_string(),
+        }
+    }
+
+    /// Decides whether to sponsor `request` as of a latest block at `timestamp`.
+    pub async fn evaluate(
+        &self,
+        request: &SponsorshipPolicyRequest,
+        timestamp: u64,
+    ) -> Result<SponsorshipVerdict, SponsorshipError> {
+        match self {
+            Self::Local(rules) => Ok(rules.evaluate(request.from, &request.transaction, timestamp)),
+            Self::Remote {
+                client,
+                url,
+                timeout,
+            } => query_remote(client, url, *timeout, request)
+                .await
+                .map_err(|err| SponsorshipError::PolicyUnavailable(err.to_string())),
+        }
+    }
+}
+
+async fn query_remote(
+    client: &reqwest::Client,
+    url: &Url,
+    timeout: Duration,
+    request: &SponsorshipPolicyRequest,
+) -> reqwest::Result<SponsorshipVerdict> {
+    client
+        .post(url.clone())
+        .timeout(timeout)
+        .json(request)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+}
+
+/// Errors of a sponsorship request.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SponsorshipError {
+    /// The transaction is for another chain.
+    #[error("transaction is for chain {got}, expected {expected}")]
+    WrongChain {
+        /// Chain id of the node.
+        expected: u64,
+        /// Chain id of the transaction.
+        got: u64,
+    },
+    /// The transaction has no calls.
+    #[error("transaction has no calls")]
+    EmptyBatch,
+    /// The transaction already carries a fee-payer signature.
+    #[error("transaction already has a fee payer")]
+    AlreadySponsored,
+    /// The policy refused to sponsor the transaction.
+    #[error("sponsorship denied: {0}")]
+    Denied(String),
+    /// The remote policy did not answer with a verdict.
+    #[error("sponsorship policy unavailable: {0}")]
+    PolicyUnavailable(String),
+    /// Error while reading the latest block.
+    #[error("state provider error: {0}")]
+    Provider(String),
+    /// The sponsorship could not be signed.
+    #[error("failed to sign sponsorship: {0}")]
+    Signing(String),
+}
+
+impl From<SponsorshipError> for ErrorObject<'static> {
+    fn from(err: SponsorshipError) -> Self {
+        let code = match err {
+            SponsorshipError::WrongChain { .. }
+            | SponsorshipError::EmptyBatch
+            | SponsorshipError::AlreadySponsored
+            | SponsorshipError::Denied(_) => INVALID_PARAMS_CODE,
+            SponsorshipError::PolicyUnavailable(_)
+            | SponsorshipError::Provider(_)
+            | SponsorshipError::Signing(_) => INTERNAL_ERROR_CODE,
+        };
+        ErrorObject::owned(code, err.to_string(), None::<()>)
+    }
+}
+
+/// Signs sponsorships approved by a policy with the node's sponsor key.
+#[derive(Debug)]
+pub struct Paymaster<Provider> {
+    provider: Provider,
+    policy: SponsorshipPolicy,
+    signer: PrivateKeySigner,
+    chain_id: u64,
+}
+
+impl<Provider> Paymaster<Provider> {
+    /// Creates a paymaster sponsoring transactions on `chain_id` approved by `policy`.
+    pub const fn new(
+        provider: Provider,
+        policy: SponsorshipPolicy,
+        signer: PrivateKeySigner,
+        chain_id: u64,
+    ) -> Self {
+        Self {
+            provider,
+            policy,
+            signer,
+            chain_id,
+        }
+    }
+
+    /// Returns the address of the sponsor account.
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+}
+
+impl<Provider> Paymaster<Provider>
+where
+    Provider: BlockNumReader + HeaderProvider,
+{
+    /// Asks the policy about `request` and returns the transaction signed by the sponsor.
+    pub async fn sponsor(
+        &self,
+        request: RpcSponsorshipRequest,
+    ) -> Result<RpcSponsorshipResponse, SponsorshipError> {
+        let RpcSponsorshipRequest {
+            from,
+            mut transaction,
+        } = request;
+        if transaction.chain_id != self.chain_id {
+            return Err(SponsorshipError::WrongChain {
+                expected: self.chain_id,
+                got: transaction.chain_id,
+            });
+        }
+        if transaction.calls.is_empty() {
+            return Err(SponsorshipError::EmptyBatch);
+        }
+        if transaction.fee_payer_signature.is_some() {
+            return Err(SponsorshipError::AlreadySponsored);
+        }
+
+        let provider_error =
+            |err: reth_provider::ProviderError| SponsorshipError::Provider(err.to_string());
+        let block_number = self.provider.best_block_number().map_err(provider_error)?;
+        let header = self
+            .provider
+            .header_by_number(block_number)
+            .map_err(provider_error)?
+            .ok_or_else(|| SponsorshipError::Provider("latest header not found".to_string()))?;
+        let sponsor = self.signer.address();
+        let policy_request = SponsorshipPolicyRequest {
+            chain_id: U64::from(self.chain_id),
+            from,
+            sponsor,
+            transaction,
+        };
+        let verdict = self
+            .policy
+            .evaluate(&policy_request, header.timestamp())
+            .await;
+        let outcome = match &verdict {
+            Ok(verdict) if verdict.approve => "approved",
+            Ok(_) => "denied",
+            Err(_) => "unavailable",
+        };
+        metrics::counter!("ev_reth_sponsorship_requests_total", "outcome" => outcome).increment(1);
+        let verdict = verdict?;
+        if !verdict.approve {
+            return Err(SponsorshipError::Denied(
+                verdict
+                    .reason
+                    .unwrap_or_else(|| "no reason given".to_string()),
+            ));
+        }
+
+        transaction = policy_request.transaction;
+        if let Some(cap) = verdict.max_sponsor_fee {
+            transaction.max_sponsor_fee =
+                Some(transaction.max_sponsor_fee.map_or(cap, |fee| fee.min(cap)));
+        }
+        let transaction = EvNodeTransactionBuilder::from(transaction)
+            .sign_sponsor(from, &self.signer)
+            .map_err(|err| SponsorshipError::Signing(err.to_string()))?
+            .build();
+        info!(
+            target: "ev_reth::sponsorship",
+            %sponsor,
+            executor = %from,
+            calls = transaction.calls.len(),
+            max_sponsor_fee = ?transaction.max_sponsor_fee,
+            "Signed sponsorship"
+        );
+        Ok(RpcSponsorshipResponse {
+            sponsor,
+            transaction,
+        })
+    }
+}
+
+/// Sponsorship RPC API.
+#[rpc(server, namespace = "evnode")]
+pub trait EvNodeSponsorshipApi {
+    /// Returns `request`'s transaction with the node's fee-payer signature if the sponsorship
+    /// policy approves it. The executor then signs and submits the returned transaction.
+    #[method(name = "requestSponsorship")]
+    async fn request_sponsorship(
+        &self,
+        request: RpcSponsorshipRequest,
+    ) -> RpcResult<RpcSponsorshipResponse>;
+}
+
+/// Implementation of the sponsorship RPC.
+#[derive(Debug)]
+pub struct EvNodeSponsorshipApiImpl<Provider> {
+    paymaster: Arc<Paymaster<Provider>>,
+}
+
+impl<Provider> EvNodeSponsorshipApiImpl<Provider> {
+    /// Creates a new instance signing sponsorships through `paymaster`.
+    pub const fn new(paymaster: Arc<Paymaster<Provider>>) -> Self {
+        Self { paymaster }
+    }
+}
+
+#[async_trait]
+impl<Provider> EvNodeSponsorshipApiServer for EvNodeSponsorshipApiImpl<Provider>
+where
+    Provider: BlockNumReader + HeaderProvider + Send + Sync + 'static,
+{
+    async fn request_sponsorship(
+        &self,
+        request: RpcSponsorshipRequest,
+    ) -> RpcResult<RpcSponsorshipResponse> {
+        Ok(self.paymaster.sponsor(request).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, U256};
+
+    const EXECUTOR: Address = Address::repeat_byte(0xe0);
+    const TARGET: Address = Address::repeat_byte(0x70);
+
+    fn transaction() -> EvNodeTransaction {
+        EvNodeTransactionBuilder::new(1)
+            .gas_limit(100_000)
+            .max_fee_per_gas(2_000_000_000)
+            .valid_until(1_060)
+            .add_call(TxKind::Call(TARGET), U256::ZERO, Bytes::new())
+            .build()
+    }
+
+    fn rules() -> SponsorshipRules {
+        serde_json::from_value(serde_json::json!({
+            "executors": [EXECUTOR],
+            "targets": [TARGET],
+            "maxGasLimit": 200_000,
+            "maxSponsorFee": 1_000_000_000_000_000u64,
+            "maxValiditySecs": 60,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn rules_approve_listed_calls_with_fee_cap() {
+        let verdict = rules().evaluate(EXECUTOR, &transaction(), 1_000);
+        assert!(verdict.approve);
+        assert_eq!(verdict.max_sponsor_fee, Some(1_000_000_000_000_000));
+        assert!(
+            SponsorshipRules::default()
+                .evaluate(Address::ZERO, &transaction(), 0)
+                .approve
+        );
+    }
+
+    #[test]
+    fn rules_deny_unlisted_or_unbounded_transactions() {
+        let denied = |from: Address, tx: EvNodeTransaction| {
+            let verdict = rules().evaluate(from, &tx, 1_000);
+            assert!(!verdict.approve);
+            verdict.reason.unwrap()
+        };
+        assert!(denied(Address::ZERO, transaction()).contains("executor"));
+
+        let mut create = transaction();
+        create.calls[0].to = TxKind::Create;
+        assert_eq!(
+            denied(EXECUTOR, create),
+            "contract creations are not sponsored"
+        );
+
+        let mut heavy = transaction();
+        heavy.gas_limit = 300_000;
+        assert_eq!(denied(EXECUTOR, heavy), "gas limit above 200000");
+
+        let mut open_ended = transaction();
+        open_ended.valid_until = None;
+        assert_eq!(denied(EXECUTOR, open_ended), "validUntil is required");
+
+        let mut long_lived = transaction();
+        long_lived.valid_until = Some(2_000);
+        assert_eq!(denied(EXECUTOR, long_lived), "validUntil after 1060");
+    }
+
+    #[test]
+    fn unknown_rules_are_rejected() {
+        assert!(
+            serde_json::from_value::<SponsorshipRules>(serde_json::json!({
+                "maxGas": 1,
+            }))
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,454 @@
+//! `ev-reth audit-sponsorships`: forensic scan of sponsor signatures over a block range.
+//!
+//! A sponsor signature commits to one transaction on one chain, so the same signature should
+//! never pay for two transactions. Under the `feePayer` sponsor hash scheme it does not bind
+//! the executor, and a chain that mis-validates chain IDs would accept signatures made for
+//! another; either lets a sponsorship be spent more than once. The command reads every
+//! sponsored EvNode transaction in the range from a read-only database, groups them by
+//! sponsor signature and reports reuse, along with what each sponsor paid, so operators can
+//! settle billing disputes from the chain alone. Sponsorships exported from other chains can
+//! be compared against to find signatures replayed across chains.
+
+use crate::{
+    chainspec::EvolveChainSpecParser,
+    report_command::{CommandReport, ReportCommand},
+    EvolveNode,
+};
+use alloy_consensus::{BlockHeader, Transaction as _, TxReceipt};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use clap::Parser;
+use ev_primitives::{EvTxEnvelope, Receipt};
+use eyre::{bail, WrapErr};
+use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_primitives_traits::RecoveredBlock;
+use reth_storage_api::{BlockNumReader, BlockReader, ReceiptProvider, TransactionVariant};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// A sponsored EvNode transaction found by the audit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsorshipRecord {
+    /// Chain that included the transaction.
+    pub chain_id: u64,
+    /// Chain ID the transaction is bound to.
+    pub tx_chain_id: u64,
+    /// Block that included the transaction.
+    pub block_number: u64,
+    /// Hash of the transaction.
+    pub tx_hash: B256,
+    /// Executor of the transaction.
+    pub executor: Address,
+    /// Sponsor recovered from the signature, if it recovers.
+    pub sponsor: Option<Address>,
+    /// 65-byte sponsor signature.
+    pub signature: Bytes,
+    /// Gas fees charged to the sponsor, in wei.
+    pub sponsor_fee: U256,
+}
+
+impl fmt::Display for SponsorshipRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (chain {}, block {}, executor {}",
+            self.tx_hash, self.chain_id, self.block_number, self.executor
+        )?;
+        if let Some(sponsor) = self.sponsor {
+            write!(f, ", sponsor {sponsor}")?;
+        }
+        write!(f, ", fee {} wei)", self.sponsor_fee)
+    }
+}
+
+/// Returns the sponsorship of `tx`, included on `chain_id` in `block_number` and executed by
+/// `executor`, or `None` if it is not sponsored. Contract fee payers carry a marker rather than
+/// a signature and are not audited.
+pub fn sponsorship_record(
+    chain_id: u64,
+    block_number: u64,
+    tx: &EvTxEnvelope,
+    executor: Address,
+    gas_fee: U256,
+) -> Option<SponsorshipRecord> {
+    let EvTxEnvelope::EvNode(signed) = tx else {
+        return None;
+    };
+    let ev = signed.tx();
+    let signature = ev.fee_payer_signature.as_ref()?;
+    if ev.contract_fee_payer().is_some() {
+        return None;
+    }
+    Some(SponsorshipRecord {
+        chain_id,
+        tx_chain_id: ev.chain_id,
+        block_number,
+        tx_hash: *signed.hash(),
+        executor,
+        sponsor: ev.recover_sponsor(executor, signature).ok(),
+        signature: Bytes::copy_from_slice(&signature.as_bytes()),
+        sponsor_fee: ev
+            .max_sponsor_fee
+            .map_or(gas_fee, |cap| gas_fee.min(U256::from(cap))),
+    })
+}
+
+/// Returns the sponsorships of a block included on `chain_id`, with the fees its receipts
+/// charged them.
+pub fn block_sponsorships(
+    chain_id: u64,
+    block: &RecoveredBlock<ev_primitives::Block>,
+    receipts: &[Receipt],
+) -> Vec<SponsorshipRecord> {
+    let header = block.header();
+    let base_fee = header.base_fee_per_gas();
+    let mut cumulative_gas_used = 0;
+    block
+        .transactions_recovered()
+        .zip(receipts)
+        .filter_map(|(tx, receipt)| {
+            let (tx, executor) = tx.into_parts();
+            let gas_used = receipt.cumulative_gas_used() - cumulative_gas_used;
+            cumulative_gas_used = receipt.cumulative_gas_used();
+            let gas_fee = U256::from(gas_used) * U256::from(tx.effective_gas_price(base_fee));
+            sponsorship_record(chain_id, header.number(), tx, executor, gas_fee)
+        })
+        .collect()
+}
+
+/// Suspicious use of a sponsor signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SponsorshipAnomaly {
+    /// One sponsor signature paid for several transactions on the same chain.
+    ReusedSignature(Vec<SponsorshipRecord>),
+    /// One sponsor signature paid for transactions on several chains.
+    CrossChainSignature(Vec<SponsorshipRecord>),
+    /// The transaction is bound to a chain other than the one that included it.
+    ForeignChainId(SponsorshipRecord),
+    /// The sponsor signature does not recover to an address under the executor scheme.
+    UnrecoverableSponsor(SponsorshipRecord),
+}
+
+impl fmt::Display for SponsorshipAnomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (label, records) = match self {
+            Self::ReusedSignature(records) => ("REUSED", records.as_slice()),
+            Self::CrossChainSignature(records) => ("CROSS-CHAIN", records.as_slice()),
+            Self::ForeignChainId(record) => ("FOREIGN-CHAIN", std::slice::from_ref(record)),
+            Self::UnrecoverableSponsor(record) => ("UNRECOVERABLE", std::slice::from_ref(record)),
+        };
+        write!(f, "[{label}] signature {}", records[0].signature)?;
+        if let Self::ForeignChainId(record) = self {
+            write!(f, " bound to chain {}", record.tx_chain_id)?;
+        }
+        for record in records {
+            write!(f, "\n  {record}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds anomalies among the `local` sponsorships, comparing their signatures with each other
+/// and with the sponsorships `imported` from other chains. Groups of imported sponsorships
+/// alone are not reported.
+///
+/// Anomalies of single transactions come first, in the order of `local`, followed by reused
+/// signatures ordered by signature, each listing its transactions by chain, block and hash, so
+/// the same inputs always yield the same report.
+pub fn find_anomalies(
+    local: &[SponsorshipRecord],
+    imported: &[SponsorshipRecord],
+) -> Vec<SponsorshipAnomaly> {
+    let mut anomalies = Vec::new();
+    for record in local {
+        if record.tx_chain_id != record.chain_id {
+            anomalies.push(SponsorshipAnomaly::ForeignChainId(record.clone()));
+        }
+        if record.sponsor.is_none() {
+            anomalies.push(SponsorshipAnomaly::UnrecoverableSponsor(record.clone()));
+        }
+    }
+
+    let local_signatures: BTreeSet<&Bytes> = local.iter().map(|record| &record.signature).collect();
+    let mut by_signature: BTreeMap<&Bytes, BTreeMap<(u64, u64, B256), &SponsorshipRecord>> =
+        BTreeMap::new();
+    for record in local.iter().chain(imported) {
+        if local_signatures.contains(&record.signature) {
+            by_signature.entry(&record.signature).or_default().insert(
+                (record.chain_id, record.block_number, record.tx_hash),
+                record,
+            );
+        }
+    }
+    for transactions in by_signature.into_values() {
+        if transactions.len() < 2 {
+            continue;
+        }
+        let chains: BTreeSet<u64> = transactions
+            .keys()
+            .map(|(chain_id, ..)| *chain_id)
+            .collect();
+        let records = transactions.into_values().cloned().collect();
+        anomalies.push(if chains.len() > 1 {
+            SponsorshipAnomaly::CrossChainSignature(records)
+        } else {
+            SponsorshipAnomaly::ReusedSignature(records)
+        });
+    }
+    anomalies
+}
+
+/// What a sponsor paid for over the audited range.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SponsorTotals {
+    /// Sponsored transactions.
+    pub transactions: u64,
+    /// Gas fees charged, in wei.
+    pub fees: U256,
+}
+
+/// Result of `ev-reth audit-sponsorships`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SponsorshipAuditReport {
+    /// First audited block.
+    pub from: u64,
+    /// Last audited block.
+    pub to: u64,
+    /// Sponsored transactions found in the range.
+    pub sponsorships: usize,
+    /// Totals per recovered sponsor.
+    pub sponsors: BTreeMap<Address, SponsorTotals>,
+    /// Anomalies found.
+    pub anomalies: Vec<SponsorshipAnomaly>,
+}
+
+impl SponsorshipAuditReport {
+    /// Builds the report for the `local` sponsorships found in blocks `from..=to`.
+    pub fn new(
+        from: u64,
+        to: u64,
+        local: &[SponsorshipRecord],
+        imported: &[SponsorshipRecord],
+    ) -> Self {
+        let mut sponsors = BTreeMap::<Address, SponsorTotals>::new();
+        for record in local {
+            if let Some(sponsor) = record.sponsor {
+                let totals = sponsors.entry(sponsor).or_default();
+                totals.transactions += 1;
+                totals.fees = totals.fees.saturating_add(record.sponsor_fee);
+            }
+        }
+        Self {
+            from,
+            to,
+            sponsorships: local.len(),
+            sponsors,
+            anomalies: find_anomalies(local, imported),
+        }
+    }
+}
+
+impl CommandReport for SponsorshipAuditReport {
+    /// Returns true if no anomaly was found.
+    fn is_healthy(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+}
+
+impl fmt::Display for SponsorshipAuditReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (sponsor, totals) in &self.sponsors {
+            writeln!(
+                f,
+                "sponsor {sponsor}: {} transactions, {} wei",
+                totals.transactions, totals.fees
+            )?;
+        }
+        for anomaly in &self.anomalies {
+            writeln!(f, "{anomaly}")?;
+        }
+        write!(
+            f,
+            "blocks {}..={}: {} sponsored transactions, {} sponsors, {} anomalies",
+            self.from,
+            self.to,
+            self.sponsorships,
+            self.sponsors.len(),
+            self.anomalies.len()
+        )
+    }
+}
+
+/// Writes `records` to `path`, one JSON object per line.
+pub fn export_sponsorships(path: &Path, records: &[SponsorshipRecord]) -> eyre::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    for record in records {
+        serde_json::to_writer(&mut out, record)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Reads sponsorships written by [`export_sponsorships`].
+pub fn import_sponsorships(path: &Path) -> eyre::Result<Vec<SponsorshipRecord>> {
+    let mut records = Vec::new();
+    for (index, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        records.push(
+            serde_json::from_str(&line)
+                .wrap_err_with(|| format!("{}:{}", path.display(), index + 1))?,
+        );
+    }
+    Ok(records)
+}
+
+/// `ev-reth audit-sponsorships`: reports sponsor signatures reused across transactions or
+/// chains.
+#[derive(Debug, Parser)]
+#[command(name = "ev-reth audit-sponsorships")]
+pub struct AuditSponsorshipsCommand {
+    /// Chain and data directory to audit.
+    #[command(flatten)]
+    pub env: EnvironmentArgs<EvolveChainSpecParser>,
+
+    /// First block to audit.
+    #[arg(long, default_value_t = 0)]
+    pub from: u64,
+
+    /// Last block to audit. Defaults to the head.
+    #[arg(long)]
+    pub to: Option<u64>,
+
+    /// Write the sponsorships found to this file, as JSON lines, for auditing other chains
+    /// with `--compare`.
+    #[arg(long)]
+    pub export: Option<PathBuf>,
+
+    /// Sponsorships exported from another chain to compare signatures with. Repeatable.
+    #[arg(long)]
+    pub compare: Vec<PathBuf>,
+}
+
+impl ReportCommand for AuditSponsorshipsCommand {
+    const NAME: &'static str = "audit-sponsorships";
+
+    type Report = SponsorshipAuditReport;
+
+    /// Scans the block range on a read-only view of the database.
+    fn run(self) -> eyre::Result<SponsorshipAuditReport> {
+        let chain_id = self.env.chain.chain().id();
+        let imported = self
+            .compare
+            .iter()
+            .map(|path| import_sponsorships(path))
+            .collect::<eyre::Result<Vec<_>>>()?
+            .concat();
+
+        let Environment {
+            provider_factory, ..
+        } = self.env.init::<EvolveNode>(AccessRights::RO)?;
+        let head = provider_factory.best_block_number()?;
+        let to = self.to.unwrap_or(head);
+        if self.from > to || to > head {
+            bail!("invalid block range {}..={to}, head is {head}", self.from);
+        }
+
+        let mut local = Vec::new();
+        for number in self.from..=to {
+            let Some(block) =
+                provider_factory.recovered_block(number.into(), TransactionVariant::WithHash)?
+            else {
+                bail!("block {number} not found");
+            };
+            let receipts = provider_factory
+                .receipts_by_block(number.into())?
+                .unwrap_or_default();
+            local.extend(block_sponsorships(chain_id, &block, &receipts));
+        }
+
+        if let Some(path) = &self.export {
+            export_sponsorships(path, &local)
+                .wrap_err_with(|| format!("exporting sponsorships to {}", path.display()))?;
+        }
+        Ok(SponsorshipAuditReport::new(
+            self.from, to, &local, &imported,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(chain_id: u64, block_number: u64, signature: u8) -> SponsorshipRecord {
+        SponsorshipRecord {
+            chain_id,
+            tx_chain_id: chain_id,
+            block_number,
+            tx_hash: B256::repeat_byte(block_number as u8),
+            executor: Address::repeat_byte(0xe0),
+            sponsor: Some(Address::repeat_byte(0x50)),
+            signature: Bytes::from(vec![signature; 65]),
+            sponsor_fee: U256::from(100),
+        }
+    }
+
+    #[test]
+    fn reports_reused_and_cross_chain_signatures() {
+        let reused = [record(1, 3, 0xaa), record(1, 2, 0xaa)];
+        let mut foreign = record(1, 4, 0xbb);
+        foreign.tx_chain_id = 2;
+        let mut unrecoverable = record(1, 5, 0xcc);
+        unrecoverable.sponsor = None;
+        let local = [
+            reused[0].clone(),
+            reused[1].clone(),
+            foreign.clone(),
+            unrecoverable.clone(),
+        ];
+        // Chain 2 spent the signature of block 4 too, and reuses one of its own.
+        let imported = [record(2, 9, 0xbb), record(2, 7, 0xdd), record(2, 8, 0xdd)];
+
+        let report = SponsorshipAuditReport::new(0, 5, &local, &imported);
+        assert_eq!(
+            report.anomalies,
+            vec![
+                SponsorshipAnomaly::ForeignChainId(foreign.clone()),
+                SponsorshipAnomaly::UnrecoverableSponsor(unrecoverable),
+                SponsorshipAnomaly::ReusedSignature(vec![reused[1].clone(), reused[0].clone()]),
+                SponsorshipAnomaly::CrossChainSignature(vec![foreign, record(2, 9, 0xbb)]),
+            ]
+        );
+        assert!(!report.is_healthy());
+        assert_eq!(report.sponsorships, 4);
+        assert_eq!(
+            report.sponsors[&Address::repeat_byte(0x50)],
+            SponsorTotals {
+                transactions: 3,
+                fees: U256::from(300),
+            }
+        );
+    }
+
+    #[test]
+    fn export_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sponsorships.jsonl");
+        let records = vec![record(1, 1, 0xaa), record(1, 2, 0xbb)];
+        export_sponsorships(&path, &records).unwrap();
+        assert_eq!(import_sponsorships(&path).unwrap(), records);
+
+        let report = SponsorshipAuditReport::new(0, 2, &records, &[]);
+        assert!(report.is_healthy());
+    }
+}
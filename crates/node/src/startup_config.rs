@@ -0,0 +1,258 @@
+//! Fully-resolved startup configuration recorded in the data directory.
+//!
+//! Every start writes the `evolve` chainspec extras and the Evolve CLI arguments, with all
+//! defaults applied, to [`STARTUP_CONFIG_FILE`] as canonical JSON: object keys are sorted, so
+//! two runs with the same configuration write identical files. Before overwriting the file of
+//! the previous start, the node logs every value that changed since, which lets a post-incident
+//! review tell exactly what a restart changed.
+
+use crate::{args::EvolveArgs, config::EvolvePayloadBuilderConfig};
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeMap, fmt, path::Path};
+use thiserror::Error;
+
+/// File in the Evolve data directory holding the configuration of the last start.
+pub const STARTUP_CONFIG_FILE: &str = "startup-config.json";
+
+/// Errors raised while recording the startup configuration.
+#[derive(Debug, Error)]
+pub enum StartupConfigError {
+    /// The configuration file could not be read or written.
+    #[error("startup configuration I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// The configuration could not be serialized, or the previous file is malformed.
+    #[error("invalid startup configuration: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A configuration value that differs from the previous start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// Path of the value, such as `evolve.base_fee_sink` or `commandLine[3]`.
+    pub path: String,
+    /// Value at the previous start, if it was set.
+    pub previous: Option<Value>,
+    /// Value at this start, if it is set.
+    pub current: Option<Value>,
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<Value>| {
+            value
+                .as_ref()
+                .map_or_else(|| "<unset>".to_string(), Value::to_string)
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            show(&self.previous),
+            show(&self.current)
+        )
+    }
+}
+
+/// Returns the canonical startup configuration of a node.
+///
+/// `command_line` holds the raw arguments the node was started with, so reth's own flags are
+/// recorded as given even though only the Evolve ones are resolved.
+pub fn resolved_config(
+    chain_id: u64,
+    command_line: &[String],
+    args: &EvolveArgs,
+    config: &EvolvePayloadBuilderConfig,
+) -> Result<Value, serde_json::Error> {
+    let value = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "chainId": chain_id,
+        "commandLine": command_line,
+        "args": serde_json::to_value(args)?,
+        "evolve": serde_json::to_value(config)?,
+    });
+    Ok(canonicalize(value))
+}
+
+/// Writes `config` to [`STARTUP_CONFIG_FILE`] in `dir` and returns its changes since the
+/// previous start, or `None` when no previous start was recorded.
+pub fn record_startup_config(
+    dir: &Path,
+    config: &Value,
+) -> Result<Option<Vec<ConfigChange>>, StartupConfigError> {
+    let path = dir.join(STARTUP_CONFIG_FILE);
+    let previous = match std::fs::read(&path) {
+        Ok(bytes) => Some(serde_json::from_slice::<Value>(&bytes)?),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+
+    std::fs::create_dir_all(dir)?;
+    let tmp = path.with_extension("tmp");
+    let mut bytes = serde_json::to_vec_pretty(config)?;
+    bytes.push(b'\n');
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, &path)?;
+
+    Ok(previous.map(|previous| diff(&previous, config)))
+}
+
+/// Returns the values that differ between two configurations, ordered by path.
+pub fn diff(previous: &Value, current: &Value) -> Vec<ConfigChange> {
+    let mut previous_leaves = BTreeMap::new();
+    flatten(String::new(), previous, &mut previous_leaves);
+    let mut current_leaves = BTreeMap::new();
+    flatten(String::new(), current, &mut current_leaves);
+
+    let mut paths: Vec<_> = previous_leaves
+        .keys()
+        .chain(current_leaves.keys())
+        .cloned()
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let previous = previous_leaves.remove(&path);
+            let current = current_leaves.remove(&path);
+            (previous != current).then_some(ConfigChange {
+                path,
+                previous,
+                current,
+            })
+        })
+        .collect()
+}
+
+/// Sorts the keys of every object in `value`.
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(canonicalize).collect()),
+        value => value,
+    }
+}
+
+/// Collects the scalar values of `value` keyed by their path. Empty objects and arrays are
+/// kept as values of their own, so emptying a list still shows up as a change.
+fn flatten(path: String, value: &Value, leaves: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                flatten(path, value, leaves);
+            }
+        }
+        Value::Array(values) if !values.is_empty() => {
+            for (index, value) in values.iter().enumerate() {
+                flatten(format!("{path}[{index}]"), value, leaves);
+            }
+        }
+        value => {
+            leaves.insert(path, value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_config(args: &EvolveArgs) -> Value {
+        resolved_config(
+            1234,
+            &["node".to_string(), "--ev-reth.call-cache".to_string()],
+            args,
+            &EvolvePayloadBuilderConfig::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn resolved_config_is_canonical() {
+        let config = sample_config(&EvolveArgs::default());
+        let keys: Vec<_> = config.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(
+            keys,
+            ["args", "chainId", "commandLine", "evolve", "version"]
+        );
+        assert_eq!(
+            config["args"]["callCacheTtlMs"],
+            json!(EvolveArgs::default().call_cache_ttl_ms)
+        );
+        assert_eq!(sample_config(&EvolveArgs::default()), config);
+    }
+
+    #[test]
+    fn record_reports_changes_since_previous_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = sample_config(&EvolveArgs::default());
+        assert_eq!(record_startup_config(dir.path(), &first).unwrap(), None);
+        assert_eq!(
+            record_startup_config(dir.path(), &first).unwrap(),
+            Some(Vec::new())
+        );
+
+        let args = EvolveArgs {
+            call_cache: true,
+            ..Default::default()
+        };
+        let second = sample_config(&args);
+        let changes = record_startup_config(dir.path(), &second).unwrap().unwrap();
+        assert_eq!(
+            changes,
+            vec![ConfigChange {
+                path: "args.callCache".to_string(),
+                previous: Some(json!(false)),
+                current: Some(json!(true)),
+            }]
+        );
+        assert_eq!(changes[0].to_string(), "args.callCache: false -> true");
+
+        let written: Value =
+            serde_json::from_slice(&std::fs::read(dir.path().join(STARTUP_CONFIG_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(written, second);
+    }
+
+    #[test]
+    fn diff_reports_added_and_removed_values() {
+        let previous = json!({ "a": [1, 2], "b": { "c": null } });
+        let current = json!({ "a": [1], "b": {} });
+        let changes = diff(&previous, &current);
+        assert_eq!(
+            changes,
+            vec![
+                ConfigChange {
+                    path: "a[1]".to_string(),
+                    previous: Some(json!(2)),
+                    current: None,
+                },
+                ConfigChange {
+                    path: "b".to_string(),
+                    previous: None,
+                    current: Some(json!({})),
+                },
+                ConfigChange {
+                    path: "b.c".to_string(),
+                    previous: Some(Value::Null),
+                    current: None,
+                },
+            ]
+        );
+        assert_eq!(changes[0].to_string(), "a[1]: 2 -> <unset>");
+    }
+}
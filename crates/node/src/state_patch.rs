@@ -0,0 +1,379 @@
+//! Scheduled one-time state patches for governance-approved recoveries.
+//!
+//! Patches are listed under `statePatches` in the chainspec's `evolve` extras. The block
+//! executor applies a patch before the first transaction of the block at its height, on every
+//! node alike, so recovering funds or fixing a broken contract does not need a relaunch. Each
+//! application leaves a [`StatePatchReceipt`] recording the values it replaced, served by
+//! `evolve_getStatePatchReceipt`.
+
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256, U64};
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_revm::revm::{
+    state::{Account, Bytecode, EvmState, EvmStorageSlot},
+    Database,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+/// Maximum number of patches a chainspec may schedule.
+pub const MAX_STATE_PATCHES: usize = 64;
+
+/// Maximum number of state patch receipts retained before the oldest are evicted.
+pub const DEFAULT_MAX_STATE_PATCH_RECEIPTS: usize = 256;
+
+/// One-time edit of account balances, nonces, code and storage at a block height.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct StatePatch {
+    /// Block height before whose transactions the patch is applied.
+    pub height: u64,
+    /// Why the patch exists, e.g. the governance proposal approving it.
+    pub reason: String,
+    /// Edits keyed by account.
+    pub accounts: BTreeMap<Address, AccountPatch>,
+}
+
+/// Edits to one account; unset fields keep their current value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccountPatch {
+    /// New balance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// New nonce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    /// New runtime code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// New values of storage slots; other slots are kept.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<B256, B256>,
+}
+
+impl AccountPatch {
+    /// Returns whether the patch leaves the account unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.nonce.is_none()
+            && self.code.is_none()
+            && self.storage.is_empty()
+    }
+}
+
+impl StatePatch {
+    /// Returns the hash of the patch's JSON encoding, for matching an applied patch against
+    /// the one governance approved.
+    pub fn hash(&self) -> B256 {
+        keccak256(serde_json::to_vec(self).expect("state patches serialize to JSON"))
+    }
+
+    /// Reads the patched accounts from `db` and returns the state to commit, along with the
+    /// receipt of the edits for block `block_number`.
+    pub fn apply<DB: Database>(
+        &self,
+        db: &mut DB,
+        block_number: u64,
+    ) -> Result<(EvmState, StatePatchReceipt), DB::Error> {
+        let mut state = EvmState::default();
+        let mut accounts = Vec::with_capacity(self.accounts.len());
+        for (address, patch) in &self.accounts {
+            let before = db.basic(*address)?.unwrap_or_default();
+            let mut info = before.clone();
+            let mut record = PatchedAccount {
+                address: *address,
+                balance: None,
+                nonce: None,
+                code_hash: None,
+                storage: Vec::with_capacity(patch.storage.len()),
+            };
+            if let Some(balance) = patch.balance {
+                info.balance = balance;
+                record.balance = Some(ValueChange::new(before.balance, balance));
+            }
+            if let Some(nonce) = patch.nonce {
+                info.nonce = nonce;
+                record.nonce = Some(ValueChange::new(before.nonce, nonce));
+            }
+            if let Some(code) = &patch.code {
+                info.code_hash = keccak256(code);
+                info.code = Some(Bytecode::new_raw(code.clone()));
+                record.code_hash = Some(ValueChange::new(before.code_hash, info.code_hash));
+            }
+
+            let mut account = Account::from(info);
+            account.mark_touch();
+            for (slot, value) in &patch.storage {
+                let key = U256::from_be_bytes(slot.0);
+                let original = db.storage(*address, key)?;
+                let present = U256::from_be_bytes(value.0);
+                account
+                    .storage
+                    .insert(key, EvmStorageSlot::new_changed(original, present, 0));
+                record.storage.push(PatchedSlot {
+                    slot: *slot,
+                    before: B256::from(original),
+                    after: *value,
+                });
+            }
+            state.insert(*address, account);
+            accounts.push(record);
+        }
+        let receipt = StatePatchReceipt {
+            block_number: U64::from(block_number),
+            patch_hash: self.hash(),
+            reason: self.reason.clone(),
+            accounts,
+        };
+        Ok((state, receipt))
+    }
+}
+
+/// Returns the patch scheduled at `block_number`, if any.
+pub fn patch_for_block(patches: &[StatePatch], block_number: u64) -> Option<&StatePatch> {
+    patches.iter().find(|patch| patch.height == block_number)
+}
+
+/// A value before and after a state patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueChange<T> {
+    /// Value the patch replaced.
+    pub before: T,
+    /// Value the patch wrote.
+    pub after: T,
+}
+
+impl<T> ValueChange<T> {
+    /// Creates a change from `before` to `after`.
+    pub const fn new(before: T, after: T) -> Self {
+        Self { before, after }
+    }
+}
+
+/// A storage slot written by a state patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatchedSlot {
+    /// Storage key.
+    pub slot: B256,
+    /// Value the patch replaced.
+    pub before: B256,
+    /// Value the patch wrote.
+    pub after: B256,
+}
+
+/// Edits a state patch made to one account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchedAccount {
+    /// Patched account.
+    pub address: Address,
+    /// Balance change, if the patch set the balance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<ValueChange<U256>>,
+    /// Nonce change, if the patch set the nonce.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<ValueChange<u64>>,
+    /// Code hash change, if the patch set the code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<ValueChange<B256>>,
+    /// Storage slots the patch wrote.
+    pub storage: Vec<PatchedSlot>,
+}
+
+/// Audit record of a state patch applied to a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatePatchReceipt {
+    /// Block the patch was applied to.
+    pub block_number: U64,
+    /// [`StatePatch::hash`] of the applied patch.
+    pub patch_hash: B256,
+    /// Reason given in the chainspec.
+    pub reason: String,
+    /// Edits made, in address order.
+    pub accounts: Vec<PatchedAccount>,
+}
+
+/// Bounded in-memory store of state patch receipts, newest last, written by the block executor
+/// and read by the `evolve_getStatePatchReceipt` RPC.
+#[derive(Debug)]
+pub struct StatePatchReceiptStore {
+    receipts: Mutex<VecDeque<StatePatchReceipt>>,
+    capacity: usize,
+}
+
+impl StatePatchReceiptStore {
+    /// Creates a store that retains at most `capacity` receipts.
+    pub const fn new(capacity: usize) -> Self {
+        Self {
+            receipts: Mutex::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    /// Stores a receipt, replacing any earlier receipt for the same block number, as the
+    /// block is executed again when it is built, imported or reorged, and evicting the oldest
+    /// entry once the store is full.
+    pub fn insert(&self, receipt: StatePatchReceipt) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut receipts = self.receipts.lock().unwrap_or_else(|e| e.into_inner());
+        receipts.retain(|stored| stored.block_number != receipt.block_number);
+        while receipts.len() >= self.capacity {
+            receipts.pop_front();
+        }
+        receipts.push_back(receipt);
+    }
+
+    /// Returns the receipt of the patch applied to `block_number`, if retained.
+    pub fn get(&self, block_number: u64) -> Option<StatePatchReceipt> {
+        let receipts = self.receipts.lock().unwrap_or_else(|e| e.into_inner());
+        receipts
+            .iter()
+            .rev()
+            .find(|receipt| receipt.block_number == U64::from(block_number))
+            .cloned()
+    }
+}
+
+impl Default for StatePatchReceiptStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_STATE_PATCH_RECEIPTS)
+    }
+}
+
+/// State patch audit RPC API.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveStatePatchApi {
+    /// Returns the receipt of the state patch applied to block `block_number`, or `null` if
+    /// no patch was applied there since the node started.
+    #[method(name = "getStatePatchReceipt")]
+    async fn get_state_patch_receipt(
+        &self,
+        block_number: U64,
+    ) -> RpcResult<Option<StatePatchReceipt>>;
+}
+
+/// Implementation of the state patch audit RPC.
+#[derive(Debug)]
+pub struct EvolveStatePatchApiImpl {
+    store: Arc<StatePatchReceiptStore>,
+}
+
+impl EvolveStatePatchApiImpl {
+    /// Creates a new instance serving `store`.
+    pub const fn new(store: Arc<StatePatchReceiptStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl EvolveStatePatchApiServer for EvolveStatePatchApiImpl {
+    async fn get_state_patch_receipt(
+        &self,
+        block_number: U64,
+    ) -> RpcResult<Option<StatePatchReceipt>> {
+        Ok(self.store.get(block_number.to()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use reth_revm::revm::{
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+    };
+
+    const TREASURY: Address = address!("0x00000000000000000000000000000000000000aa");
+    const VAULT: Address = address!("0x00000000000000000000000000000000000000bb");
+
+    fn patch() -> StatePatch {
+        serde_json::from_value(serde_json::json!({
+            "height": 100,
+            "reason": "proposal 7: recover locked vault funds",
+            "accounts": {
+                "0x00000000000000000000000000000000000000aa": { "balance": "0x64" },
+                "0x00000000000000000000000000000000000000bb": {
+                    "nonce": 2,
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000001":
+                            "0x0000000000000000000000000000000000000000000000000000000000000000"
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn applies_edits_and_records_replaced_values() {
+        let mut db = CacheDB::<EmptyDB>::default();
+        db.insert_account_info(
+            VAULT,
+            AccountInfo {
+                balance: U256::from(5),
+                nonce: 1,
+                ..Default::default()
+            },
+        );
+        db.insert_account_storage(VAULT, U256::from(1), U256::from(9))
+            .unwrap();
+
+        let patch = patch();
+        let (state, receipt) = patch.apply(&mut db, 100).unwrap();
+
+        let treasury = &state[&TREASURY];
+        assert!(treasury.is_touched());
+        assert_eq!(treasury.info.balance, U256::from(100));
+        let vault = &state[&VAULT];
+        assert_eq!(vault.info.balance, U256::from(5));
+        assert_eq!(vault.info.nonce, 2);
+        let slot = &vault.storage[&U256::from(1)];
+        assert_eq!(slot.original_value(), U256::from(9));
+        assert_eq!(slot.present_value(), U256::ZERO);
+
+        assert_eq!(receipt.block_number, U64::from(100));
+        assert_eq!(receipt.patch_hash, patch.hash());
+        assert_eq!(receipt.accounts.len(), 2);
+        assert_eq!(
+            receipt.accounts[0].balance,
+            Some(ValueChange::new(U256::ZERO, U256::from(100)))
+        );
+        assert_eq!(receipt.accounts[1].nonce, Some(ValueChange::new(1, 2)));
+        assert_eq!(
+            receipt.accounts[1].storage,
+            vec![PatchedSlot {
+                slot: B256::with_last_byte(1),
+                before: B256::with_last_byte(9),
+                after: B256::ZERO,
+            }]
+        );
+    }
+
+    #[test]
+    fn receipts_are_replaced_per_block() {
+        let store = StatePatchReceiptStore::new(2);
+        let receipt = |block_number: u64, reason: &str| StatePatchReceipt {
+            block_number: U64::from(block_number),
+            patch_hash: B256::ZERO,
+            reason: reason.to_string(),
+            accounts: Vec::new(),
+        };
+        store.insert(receipt(10, "first"));
+        store.insert(receipt(10, "re-executed"));
+        assert_eq!(store.get(10).unwrap().reason, "re-executed");
+
+        store.insert(receipt(20, "second"));
+        store.insert(receipt(30, "third"));
+        assert_eq!(store.get(10), None);
+        assert!(store.get(30).is_some());
+    }
+}
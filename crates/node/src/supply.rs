@@ -0,0 +1,405 @@
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{U256, U64};
+use async_trait::async_trait;
+use ev_primitives::EvPrimitives;
+use evolve_ev_reth::chain_stats::SupplyChange;
+use futures::StreamExt;
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_provider::CanonStateSubscriptions;
+use reth_storage_api::{
+    errors::provider::ProviderError, BlockIdReader, BlockNumReader, ChangeSetReader,
+    HeaderProvider, StateProviderFactory,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+/// Name of the supply index file in the Evolve data directory.
+pub const SUPPLY_INDEX_FILE: &str = "supply.idx";
+
+/// Most points a single `evolve_getSupplyHistory` call may return.
+pub const MAX_SUPPLY_HISTORY_POINTS: u64 = 10_000;
+
+/// Size of one index record: the supply after a block as a big-endian 256-bit integer.
+const RECORD_SIZE: u64 = 32;
+
+/// Errors raised while maintaining the supply index.
+#[derive(Debug, Error)]
+pub enum SupplyIndexError {
+    /// The index file could not be read or written.
+    #[error("supply index I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    /// A block's changeset or historical state could not be read.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// A block was appended out of order.
+    #[error("supply index expects block {expected}, got {got}")]
+    Gap {
+        /// Next block the index can take.
+        expected: u64,
+        /// Block that was appended.
+        got: u64,
+    },
+}
+
+/// Total native supply after each block, stored as fixed-size records indexed by block
+/// number, so any block is a single read however long the chain is.
+#[derive(Debug)]
+pub struct SupplyIndex {
+    inner: Mutex<IndexFile>,
+}
+
+#[derive(Debug)]
+struct IndexFile {
+    file: File,
+    /// Number of indexed blocks; blocks `0..len` are indexed.
+    len: u64,
+}
+
+impl SupplyIndex {
+    /// Opens the index at `path`, creating it if needed. A trailing partial record left by an
+    /// interrupted write is dropped.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SupplyIndexError> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let len = file.metadata()?.len() / RECORD_SIZE;
+        file.set_len(len * RECORD_SIZE)?;
+        Ok(Self {
+            inner: Mutex::new(IndexFile { file, len }),
+        })
+    }
+
+    /// Returns the number of the next block to index, which is also the number of indexed
+    /// blocks.
+    pub fn next_block(&self) -> u64 {
+        self.inner.lock().expect("supply index lock poisoned").len
+    }
+
+    /// Returns the supply after `block_number`, if indexed.
+    pub fn get(&self, block_number: u64) -> Result<Option<U256>, SupplyIndexError> {
+        let mut inner = self.inner.lock().expect("supply index lock poisoned");
+        if block_number >= inner.len {
+            return Ok(None);
+        }
+        let mut record = [0u8; RECORD_SIZE as usize];
+        inner
+            .file
+            .seek(SeekFrom::Start(block_number * RECORD_SIZE))?;
+        inner.file.read_exact(&mut record)?;
+        Ok(Some(U256::from_be_bytes(record)))
+    }
+
+    /// Appends the supply after `block_number`, which must be the next block to index.
+    pub fn push(&self, block_number: u64, supply: U256) -> Result<(), SupplyIndexError> {
+        let mut inner = self.inner.lock().expect("supply index lock poisoned");
+        if block_number != inner.len {
+            return Err(SupplyIndexError::Gap {
+                expected: inner.len,
+                got: block_number,
+            });
+        }
+        inner
+            .file
+            .seek(SeekFrom::Start(block_number * RECORD_SIZE))?;
+        inner.file.write_all(&supply.to_be_bytes::<32>())?;
+        inner.len += 1;
+        Ok(())
+    }
+
+    /// Drops every block from `block_number` on, e.g. when they are reorged out.
+    pub fn truncate(&self, block_number: u64) -> Result<(), SupplyIndexError> {
+        let mut inner = self.inner.lock().expect("supply index lock poisoned");
+        if block_number < inner.len {
+            inner.file.set_len(block_number * RECORD_SIZE)?;
+            inner.len = block_number;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the net native supply change of a block from its account changeset: every
+/// balance the block touched, compared before and after the block.
+///
+/// Needs the block's changeset and historical state, so it only works for blocks whose
+/// account history has not been pruned.
+pub fn block_supply_change<P>(
+    provider: &P,
+    block_number: u64,
+) -> Result<SupplyChange, ProviderError>
+where
+    P: ChangeSetReader + StateProviderFactory,
+{
+    let changes = provider.account_block_changeset(block_number)?;
+    let state = provider.history_by_block_number(block_number)?;
+    let mut change = SupplyChange::default();
+    for account in changes {
+        let before = account.info.map_or(U256::ZERO, |info| info.balance);
+        let after = state
+            .basic_account(&account.address)?
+            .map_or(U256::ZERO, |info| info.balance);
+        if after > before {
+            change.increase = change.increase.saturating_add(after - before);
+        } else {
+            change.decrease = change.decrease.saturating_add(before - after);
+        }
+    }
+    Ok(change)
+}
+
+/// Indexes every block from the index's next block up to `target`.
+pub fn sync_supply_index<P>(
+    provider: &P,
+    index: &SupplyIndex,
+    genesis_supply: U256,
+    target: u64,
+) -> Result<(), SupplyIndexError>
+where
+    P: ChangeSetReader + StateProviderFactory,
+{
+    if index.next_block() == 0 {
+        index.push(0, genesis_supply)?;
+    }
+    let mut supply = index
+        .get(index.next_block() - 1)?
+        .expect("last indexed block is readable");
+    for block_number in index.next_block()..=target {
+        let change = block_supply_change(provider, block_number)?;
+        supply = supply
+            .saturating_add(change.increase)
+            .saturating_sub(change.decrease);
+        index.push(block_number, supply)?;
+    }
+    Ok(())
+}
+
+/// Keeps `index` in step with the canonical chain, backfilling from the last indexed block
+/// on startup and truncating reorged blocks. Runs until the canonical state stream ends.
+pub async fn run_supply_index<P>(provider: P, index: Arc<SupplyIndex>, genesis_supply: U256)
+where
+    P: CanonStateSubscriptions<Primitives = EvPrimitives>
+        + ChangeSetReader
+        + StateProviderFactory
+        + BlockNumReader
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    let mut canon_state = provider.canonical_state_stream();
+    let mut failing = false;
+    let mut target = provider.best_block_number().ok();
+    info!(
+        from = index.next_block(),
+        to = ?target,
+        "backfilling native supply index"
+    );
+    loop {
+        if let Some(target) = target {
+            let (provider, index) = (provider.clone(), Arc::clone(&index));
+            let result = tokio::task::spawn_blocking(move || {
+                sync_supply_index(&provider, &index, genesis_supply, target)
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => failing = false,
+                // Pruned account history fails every block, so only the first failure of a
+                // streak is worth a warning.
+                Ok(Err(err)) if !failing => {
+                    warn!(%err, "native supply index stalled");
+                    failing = true;
+                }
+                Ok(Err(err)) => debug!(%err, "native supply index still stalled"),
+                Err(err) => warn!(%err, "native supply index task failed"),
+            }
+        }
+
+        let Some(notification) = canon_state.next().await else {
+            break;
+        };
+        if let Some(reverted) = notification.reverted() {
+            if let Err(err) = index.truncate(*reverted.range().start()) {
+                warn!(%err, "failed to truncate reorged blocks from the supply index");
+            }
+        }
+        target = Some(notification.tip().header().number());
+    }
+}
+
+/// Native supply after a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSupply {
+    /// Block number.
+    pub block_number: U64,
+    /// Block timestamp in seconds.
+    pub timestamp: U64,
+    /// Total native supply after the block, in wei.
+    pub total_supply: U256,
+}
+
+/// Evolve native supply RPC API.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveSupplyApi {
+    /// Returns the total native supply after `block` (latest by default), or `null` if the
+    /// block is not indexed yet.
+    #[method(name = "getSupply")]
+    async fn get_supply(&self, block: Option<BlockNumberOrTag>) -> RpcResult<Option<RpcSupply>>;
+
+    /// Returns the supply every `step` blocks (default 1) from `fromBlock` to `toBlock`,
+    /// stopping at the last indexed block.
+    #[method(name = "getSupplyHistory")]
+    async fn get_supply_history(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        step: Option<U64>,
+    ) -> RpcResult<Vec<RpcSupply>>;
+}
+
+/// Implementation of the native supply RPC.
+#[derive(Debug)]
+pub struct EvolveSupplyApiImpl<Provider> {
+    provider: Provider,
+    index: Arc<SupplyIndex>,
+}
+
+impl<Provider> EvolveSupplyApiImpl<Provider> {
+    /// Creates a new instance serving `index`.
+    pub const fn new(provider: Provider, index: Arc<SupplyIndex>) -> Self {
+        Self { provider, index }
+    }
+}
+
+fn internal_error(err: impl ToString) -> ErrorObject<'static> {
+    ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+impl<Provider> EvolveSupplyApiImpl<Provider>
+where
+    Provider: BlockIdReader + HeaderProvider,
+{
+    fn resolve(&self, block: BlockNumberOrTag) -> RpcResult<Option<u64>> {
+        self.provider
+            .convert_block_number(block)
+            .map_err(internal_error)
+    }
+
+    fn supply_at(&self, block_number: u64) -> RpcResult<Option<RpcSupply>> {
+        let Some(total_supply) = self.index.get(block_number).map_err(internal_error)? else {
+            return Ok(None);
+        };
+        let Some(header) = self
+            .provider
+            .header_by_number(block_number)
+            .map_err(internal_error)?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(RpcSupply {
+            block_number: U64::from(block_number),
+            timestamp: U64::from(header.timestamp()),
+            total_supply,
+        }))
+    }
+}
+
+#[async_trait]
+impl<Provider> EvolveSupplyApiServer for EvolveSupplyApiImpl<Provider>
+where
+    Provider: BlockIdReader + HeaderProvider + Send + Sync + 'static,
+{
+    async fn get_supply(&self, block: Option<BlockNumberOrTag>) -> RpcResult<Option<RpcSupply>> {
+        match self.resolve(block.unwrap_or_default())? {
+            Some(block_number) => self.supply_at(block_number),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_supply_history(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        step: Option<U64>,
+    ) -> RpcResult<Vec<RpcSupply>> {
+        let (Some(from), Some(to)) = (self.resolve(from_block)?, self.resolve(to_block)?) else {
+            return Ok(Vec::new());
+        };
+        let step = step.map_or(1, |step| step.saturating_to::<u64>());
+        if step == 0 || from > to {
+            return Err(ErrorObject::owned(
+                INVALID_PARAMS_CODE,
+                "step must be positive and fromBlock must not exceed toBlock",
+                None::<()>,
+            ));
+        }
+        if (to - from) / step >= MAX_SUPPLY_HISTORY_POINTS {
+            return Err(ErrorObject::owned(
+                INVALID_PARAMS_CODE,
+                format!("range exceeds {MAX_SUPPLY_HISTORY_POINTS} points; increase step"),
+                None::<()>,
+            ));
+        }
+
+        let mut points = Vec::new();
+        for block_number in (from..=to).step_by(step as usize) {
+            match self.supply_at(block_number)? {
+                Some(point) => points.push(point),
+                None => break,
+            }
+        }
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_appends_truncates_and_reopens() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SUPPLY_INDEX_FILE);
+
+        let index = SupplyIndex::open(&path).unwrap();
+        index.push(0, U256::from(1_000)).unwrap();
+        index.push(1, U256::from(1_050)).unwrap();
+        index.push(2, U256::from(1_020)).unwrap();
+        assert!(matches!(
+            index.push(4, U256::ZERO),
+            Err(SupplyIndexError::Gap {
+                expected: 3,
+                got: 4
+            })
+        ));
+        assert_eq!(index.get(1).unwrap(), Some(U256::from(1_050)));
+        assert_eq!(index.get(3).unwrap(), None);
+
+        index.truncate(2).unwrap();
+        assert_eq!(index.next_block(), 2);
+        drop(index);
+
+        // A torn trailing record is dropped on reopen.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[0xff; 7]).unwrap();
+        drop(file);
+        let index = SupplyIndex::open(&path).unwrap();
+        assert_eq!(index.next_block(), 2);
+        assert_eq!(index.get(1).unwrap(), Some(U256::from(1_050)));
+    }
+}
@@ -0,0 +1,449 @@
+//! Gasless ERC-20 transfers relayed from EIP-3009 authorizations.
+//!
+//! `evolve_submitTransferIntent` takes a `transferWithAuthorization` signed by a token holder
+//! and submits it to the token in an EvNode transaction sent and paid for by the node's sponsor
+//! key. The holder only signs typed data for the token and never needs the native token.
+//!
+//! The token contract checks the holder's signature, so an authorization that does not verify
+//! still costs the sponsor the gas of a reverted call. Only tokens the operator lists are
+//! relayed, authorizations outside their validity window as of the latest block are refused,
+//! and an authorization is relayed at most once while it is valid.
+
+use alloy_consensus::{
+    transaction::{Recovered, TxHashRef},
+    BlockHeader,
+};
+use alloy_primitives::{Address, Bytes, Signature, TxKind, B256, U256};
+use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::{sol, SolCall};
+use async_trait::async_trait;
+use ev_primitives::{EvNodeTransactionBuilder, TransactionSigned};
+use jsonrpsee::types::{
+    error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+    ErrorObject,
+};
+use jsonrpsee_core::RpcResult;
+use jsonrpsee_proc_macros::rpc;
+use reth_storage_api::{AccountReader, BlockNumReader, HeaderProvider, StateProviderFactory};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+use tracing::info;
+
+use crate::{nonce::next_nonce, signer::DEFAULT_PRIORITY_FEE};
+
+sol! {
+    /// EIP-3009 transfers with a signed authorization.
+    interface IEip3009 {
+        function transferWithAuthorization(
+            address from,
+            address to,
+            uint256 value,
+            uint256 validAfter,
+            uint256 validBefore,
+            bytes32 nonce,
+            uint8 v,
+            bytes32 r,
+            bytes32 s
+        ) external;
+    }
+}
+
+/// Gas limit of relayed transfers, enough for a signature check and a token transfer.
+const TRANSFER_INTENT_GAS_LIMIT: u64 = 150_000;
+
+/// An EIP-3009 transfer authorization signed by the token holder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransferAuthorization {
+    /// Token contract implementing `transferWithAuthorization`.
+    pub token: Address,
+    /// Token holder who signed the authorization.
+    pub from: Address,
+    /// Recipient of the tokens.
+    pub to: Address,
+    /// Amount of tokens.
+    pub value: U256,
+    /// Unix time after which the authorization is valid.
+    pub valid_after: U256,
+    /// Unix time before which the authorization is valid.
+    pub valid_before: U256,
+    /// Unique nonce of the authorization.
+    pub nonce: B256,
+    /// 65-byte `r || s || v` signature of the holder over the token's typed data.
+    pub signature: Bytes,
+}
+
+impl RpcTransferAuthorization {
+    /// Returns the `transferWithAuthorization` calldata for the token.
+    fn calldata(&self) -> Result<Bytes, TransferIntentError> {
+        let signature = Signature::try_from(self.signature.as_ref())
+            .map_err(|_| TransferIntentError::InvalidSignature)?;
+        let call = IEip3009::transferWithAuthorizationCall {
+            from: self.from,
+            to: self.to,
+            value: self.value,
+            validAfter: self.valid_after,
+            validBefore: self.valid_before,
+            nonce: self.nonce,
+            v: 27 + u8::from(signature.v()),
+            r: signature.r().into(),
+            s: signature.s().into(),
+        };
+        Ok(call.abi_encode().into())
+    }
+
+    /// Checks that the authorization can be used in a block following one at `timestamp`.
+    fn check_window(&self, timestamp: u64) -> Result<(), TransferIntentError> {
+        let timestamp = U256::from(timestamp);
+        if self.valid_before <= timestamp {
+            return Err(TransferIntentError::Expired {
+                valid_before: self.valid_before,
+            });
+        }
+        if self.valid_after > timestamp {
+            return Err(TransferIntentError::NotYetValid {
+                valid_after: self.valid_after,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Errors of a transfer intent.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TransferIntentError {
+    /// The token is not one the node relays transfers of.
+    #[error("token {0} is not relayed by this node")]
+    TokenNotAllowed(Address),
+    /// The authorization is not valid yet.
+    #[error("authorization is not valid until after {valid_after}")]
+    NotYetValid {
+        /// Unix time after which the authorization is valid.
+        valid_after: U256,
+    },
+    /// The authorization has expired.
+    #[error("authorization expired at {valid_before}")]
+    Expired {
+        /// Unix time before which the authorization was valid.
+        valid_before: U256,
+    },
+    /// The signature is not a 65-byte ECDSA signature.
+    #[error("invalid authorization signature")]
+    InvalidSignature,
+    /// The authorization was already relayed.
+    #[error("authorization was already submitted")]
+    AlreadySubmitted,
+    /// Error while reading the sponsor account or the latest block.
+    #[error("state provider error: {0}")]
+    Provider(String),
+    /// The transaction could not be signed.
+    #[error("failed to sign transfer transaction: {0}")]
+    Signing(String),
+    /// The pool rejected the transaction.
+    #[error("transfer transaction rejected by the pool: {0}")]
+    Pool(String),
+}
+
+impl TransferIntentError {
+    /// Returns the JSON-RPC error code of the error.
+    pub const fn code(&self) -> i32 {
+        match self {
+            Self::TokenNotAllowed(_)
+            | Self::NotYetValid { .. }
+            | Self::Expired { .. }
+            | Self::InvalidSignature
+            | Self::AlreadySubmitted => INVALID_PARAMS_CODE,
+            Self::Provider(_) | Self::Signing(_) | Self::Pool(_) => INTERNAL_ERROR_CODE,
+        }
+    }
+}
+
+impl From<TransferIntentError> for ErrorObject<'static> {
+    fn from(err: TransferIntentError) -> Self {
+        ErrorObject::owned(err.code(), err.to_string(), None::<()>)
+    }
+}
+
+/// Authorizations relayed while they are valid, keyed by token, holder and nonce.
+#[derive(Debug, Default)]
+struct SubmittedAuthorizations(HashMap<(Address, Address, B256), U256>);
+
+impl SubmittedAuthorizations {
+    /// Records `authorization`, or returns `false` if it was already recorded. Authorizations
+    /// that expired by `timestamp` are forgotten first.
+    fn insert(&mut self, authorization: &RpcTransferAuthorization, timestamp: u64) -> bool {
+        let timestamp = U256::from(timestamp);
+        self.0.retain(|_, valid_before| *valid_before > timestamp);
+        let key = (authorization.token, authorization.from, authorization.nonce);
+        if self.0.contains_key(&key) {
+            return false;
+        }
+        self.0.insert(key, authorization.valid_before);
+        true
+    }
+
+    /// Forgets an authorization that was not relayed.
+    fn remove(&mut self, authorization: &RpcTransferAuthorization) {
+        self.0
+            .remove(&(authorization.token, authorization.from, authorization.nonce));
+    }
+}
+
+/// Relayer sending EIP-3009 transfers from the sponsor key to the pool.
+#[derive(Debug)]
+pub struct TransferRelayer<Provider, Pool> {
+    provider: Provider,
+    pool: Pool,
+    signer: PrivateKeySigner,
+    chain_id: u64,
+    tokens: HashSet<Address>,
+    submitted: Mutex<SubmittedAuthorizations>,
+    /// Serializes nonce assignment so concurrent intents do not reuse a nonce.
+    submission: tokio::sync::Mutex<()>,
+}
+
+impl<Provider, Pool> TransferRelayer<Provider, Pool> {
+    /// Creates a relayer sending transfers of `tokens` signed by `signer` on `chain_id`.
+    pub fn new(
+        provider: Provider,
+        pool: Pool,
+        signer: PrivateKeySigner,
+        chain_id: u64,
+        tokens: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        Self {
+            provider,
+            pool,
+            signer,
+            chain_id,
+            tokens: tokens.into_iter().collect(),
+            submitted: Mutex::default(),
+            submission: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Returns the address of the sponsor account.
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+}
+
+impl<Provider, Pool> TransferRelayer<Provider, Pool>
+where
+    Provider: StateProviderFactory + BlockNumReader + HeaderProvider,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    /// Relays `authorization` and returns the hash of the sponsored transaction.
+    pub async fn submit(
+        &self,
+        authorization: RpcTransferAuthorization,
+    ) -> Result<B256, TransferIntentError> {
+        if !self.tokens.contains(&authorization.token) {
+            return Err(TransferIntentError::TokenNotAllowed(authorization.token));
+        }
+        let input = authorization.calldata()?;
+
+        let _submission = self.submission.lock().await;
+        let provider_error =
+            |err: reth_provider::ProviderError| TransferIntentError::Provider(err.to_string());
+        let block_number = self.provider.best_block_number().map_err(provider_error)?;
+        let header = self
+            .provider
+            .header_by_number(block_number)
+            .map_err(provider_error)?
+            .ok_or_else(|| TransferIntentError::Provider("latest header not found".to_string()))?;
+        authorization.check_window(header.timestamp())?;
+        if !self
+            .submitted
+            .lock()
+            .expect("submitted authorizations lock poisoned")
+            .insert(&authorization, header.timestamp())
+        {
+            return Err(TransferIntentError::AlreadySubmitted);
+        }
+
+        let result = self
+            .send(
+                authorization.token,
+                input,
+                header.base_fee_per_gas().unwrap_or_default(),
+            )
+            .await;
+        match result {
+            Ok(tx_hash) => {
+                info!(
+                    target: "ev_reth::transfer_intent",
+                    token = %authorization.token,
+                    from = %authorization.from,
+                    to = %authorization.to,
+                    value = %authorization.value,
+                    %tx_hash,
+                    "Relayed transfer intent"
+                );
+                Ok(tx_hash)
+            }
+            Err(err) => {
+                self.submitted
+                    .lock()
+                    .expect("submitted authorizations lock poisoned")
+                    .remove(&authorization);
+                Err(err)
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        token: Address,
+        input: Bytes,
+        base_fee: u64,
+    ) -> Result<B256, TransferIntentError> {
+        let sponsor = self.signer.address();
+        let latest_nonce = self
+            .provider
+            .latest()
+            .and_then(|state| state.basic_account(&sponsor))
+            .map_err(|err| TransferIntentError::Provider(err.to_string()))?
+            .map_or(0, |account| account.nonce);
+        let pooled = self
+            .pool
+            .get_transactions_by_sender(sponsor)
+            .into_iter()
+            .map(|tx| tx.transaction.nonce());
+        let nonce = next_nonce(latest_nonce, pooled).nonce.to::<u64>();
+
+        let tx = EvNodeTransactionBuilder::new(self.chain_id)
+            .nonce(nonce)
+            .gas_limit(TRANSFER_INTENT_GAS_LIMIT)
+            .max_fee_per_gas(u128::from(base_fee) * 2 + DEFAULT_PRIORITY_FEE)
+            .max_priority_fee_per_gas(DEFAULT_PRIORITY_FEE)
+            .add_call(TxKind::Call(token), U256::ZERO, input)
+            .sign_executor(&self.signer)
+            .map_err(|err| TransferIntentError::Signing(err.to_string()))?;
+        let tx_hash = *tx.tx_hash();
+
+        self.pool
+            .add_consensus_transaction(
+                Recovered::new_unchecked(tx, sponsor),
+                TransactionOrigin::Local,
+            )
+            .await
+            .map_err(|err| TransferIntentError::Pool(err.to_string()))?;
+        Ok(tx_hash)
+    }
+}
+
+/// Transfer intent RPC API.
+#[rpc(server, namespace = "evolve")]
+pub trait EvolveTransferIntentApi {
+    /// Relays a signed EIP-3009 transfer authorization in a transaction paid for by the
+    /// node's sponsor key and returns its hash.
+    #[method(name = "submitTransferIntent")]
+    async fn submit_transfer_intent(
+        &self,
+        authorization: RpcTransferAuthorization,
+    ) -> RpcResult<B256>;
+}
+
+/// Implementation of the transfer intent RPC.
+#[derive(Debug)]
+pub struct EvolveTransferIntentApiImpl<Provider, Pool> {
+    relayer: Arc<TransferRelayer<Provider, Pool>>,
+}
+
+impl<Provider, Pool> EvolveTransferIntentApiImpl<Provider, Pool> {
+    /// Creates a new instance relaying intents through `relayer`.
+    pub const fn new(relayer: Arc<TransferRelayer<Provider, Pool>>) -> Self {
+        Self { relayer }
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> EvolveTransferIntentApiServer for EvolveTransferIntentApiImpl<Provider, Pool>
+where
+    Provider: StateProviderFactory + BlockNumReader + HeaderProvider + Send + Sync + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Send
+        + Sync
+        + 'static,
+{
+    async fn submit_transfer_intent(
+        &self,
+        authorization: RpcTransferAuthorization,
+    ) -> RpcResult<B256> {
+        Ok(self.relayer.submit(authorization).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authorization() -> RpcTransferAuthorization {
+        let mut signature = [0x11; 65];
+        signature[64] = 28;
+        RpcTransferAuthorization {
+            token: Address::repeat_byte(0x70),
+            from: Address::repeat_byte(0xa1),
+            to: Address::repeat_byte(0xb0),
+            value: U256::from(500),
+            valid_after: U256::from(100),
+            valid_before: U256::from(200),
+            nonce: B256::repeat_byte(0x01),
+            signature: Bytes::copy_from_slice(&signature),
+        }
+    }
+
+    #[test]
+    fn authorization_encodes_transfer_with_authorization() {
+        let authorization = authorization();
+        let call =
+            IEip3009::transferWithAuthorizationCall::abi_decode(&authorization.calldata().unwrap())
+                .unwrap();
+        assert_eq!(call.from, authorization.from);
+        assert_eq!(call.value, authorization.value);
+        assert_eq!(call.validBefore, authorization.valid_before);
+        assert_eq!(call.v, 28);
+        assert_eq!(call.r, B256::repeat_byte(0x11));
+
+        let truncated = RpcTransferAuthorization {
+            signature: Bytes::from_static(&[0x11; 64]),
+            ..authorization
+        };
+        assert_eq!(
+            truncated.calldata(),
+            Err(TransferIntentError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn authorization_window_and_replay_checked() {
+        let authorization = authorization();
+        assert_eq!(
+            authorization.check_window(99),
+            Err(TransferIntentError::NotYetValid {
+                valid_after: U256::from(100)
+            })
+        );
+        assert_eq!(authorization.check_window(150), Ok(()));
+        assert_eq!(
+            authorization.check_window(200),
+            Err(TransferIntentError::Expired {
+                valid_before: U256::from(200)
+            })
+        );
+
+        let mut submitted = SubmittedAuthorizations::default();
+        assert!(submitted.insert(&authorization, 150));
+        assert!(!submitted.insert(&authorization, 160));
+        // An authorization that was not relayed, or has expired, can be submitted again.
+        submitted.remove(&authorization);
+        assert!(submitted.insert(&authorization, 160));
+        assert!(submitted.insert(&authorization, 200));
+    }
+}
@@ -1,10 +1,14 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
-use crate::tracing_ext::RecordDurationOnDrop;
+use crate::{
+    config::SharedEvolveConfig,
+    screening::{ScreeningError, ScreeningRequest, TransactionScreener},
+    tracing_ext::RecordDurationOnDrop,
+};
 use alloy_consensus::{
     constants::EIP1559_TX_TYPE_ID,
     transaction::{Recovered, TxHashRef},
-    BlobTransactionValidationError, Signed, Typed2718,
+    BlobTransactionValidationError, BlockHeader, Signed, Typed2718,
 };
 use alloy_eips::{
     eip2718::{Encodable2718, WithEncoded},
@@ -12,44 +16,179 @@ use alloy_eips::{
     eip7840::BlobParams,
     merge::EPOCH_SLOTS,
 };
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, Signature, TxKind, U256};
 use c_kzg::KzgSettings;
-use ev_primitives::{EvNodeTransaction, EvPooledTxEnvelope, EvTxEnvelope, TransactionSigned};
+use ev_precompiles::nonce_manager::nonce_lane_address;
+use ev_primitives::{
+    CrossDomainSignature, EvNodeTransaction, EvNodeTxDisabled, EvPooledTxEnvelope, EvTxEnvelope,
+    MaxBatchCalls, SponsorHashScheme, TooManyBatchCalls, TransactionSigned,
+};
+use ev_revm::RecoveredSponsors;
+use evolve_ev_reth::{config::TxpoolLimits, maintenance::MaintenanceLane};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_node_api::{FullNodeTypes, NodeTypes};
 use reth_node_builder::{
     components::{create_blob_store_with_cache, PoolBuilder, TxPoolBuilder},
     BuilderContext,
 };
+use reth_node_core::{args::TxPoolArgs, cli::config::RethTransactionPoolConfig};
 use reth_primitives_traits::NodePrimitives;
-use reth_storage_api::{AccountInfoReader, BlockNumReader, StateProviderFactory};
+use reth_revm::revm::{
+    interpreter::gas::{calculate_initial_tx_gas, ACCESS_LIST_ADDRESS, ACCESS_LIST_STORAGE_KEY},
+    primitives::hardfork::SpecId,
+};
+use reth_storage_api::{AccountInfoReader, BlockNumReader, HeaderProvider, StateProviderFactory};
 use reth_transaction_pool::{
     blobstore::DiskFileBlobStore,
     error::{InvalidPoolTransactionError, PoolTransactionError},
     CoinbaseTipOrdering, EthBlobTransactionSidecar, EthPoolTransaction, EthPooledTransaction,
-    EthTransactionValidator, PoolTransaction, TransactionOrigin, TransactionValidationOutcome,
-    TransactionValidationTaskExecutor, TransactionValidator,
+    EthTransactionValidator, PoolTransaction, TransactionOrigin, TransactionPool,
+    TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
 use tracing::{debug, info, instrument, warn};
 
+/// Error returned when a sponsored transaction would replace one paid by another sponsor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "transaction replaces one sponsored by {sponsor}; the replacement must be signed by the same \
+     sponsor or be unsponsored"
+)]
+pub struct SponsoredReplacement {
+    /// Sponsor of the pooled transaction.
+    pub sponsor: Address,
+}
+
+/// Sponsors of the transactions in the pool the validator admits into.
+///
+/// The pool replaces a transaction with any other of the same sender and nonce that bumps its
+/// fees. For sponsored transactions that would let an executor drop a sponsorship in favour of
+/// another sponsor's, so a sponsored replacement is only admitted from the sponsor of the
+/// pooled transaction. Unsponsored replacements are always admitted, as the executor then pays
+/// for itself.
+///
+/// The pool is built around its validator, so it is handed over once it exists.
+#[derive(Clone, Default)]
+pub struct PooledSponsors(Arc<OnceLock<Box<dyn SponsoredPool>>>);
+
+impl PooledSponsors {
+    /// Looks sponsors up in `pool` from now on.
+    pub fn set(&self, pool: impl SponsoredPool) {
+        let _ = self.0.set(Box::new(pool));
+    }
+
+    /// Returns the sponsor of the transaction pooled at `sender` and `nonce`, if it is
+    /// sponsored.
+    pub fn sponsor(&self, sender: Address, nonce: u64) -> Option<Address> {
+        self.0.get()?.pooled_sponsor(sender, nonce)
+    }
+
+    /// Checks that a transaction sponsored by `sponsor` may replace the one pooled at `sender`
+    /// and `nonce`.
+    pub fn check(
+        &self,
+        sender: Address,
+        nonce: u64,
+        sponsor: Address,
+    ) -> Result<(), SponsoredReplacement> {
+        match self.sponsor(sender, nonce) {
+            Some(pooled) if pooled != sponsor => Err(SponsoredReplacement { sponsor: pooled }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Debug for PooledSponsors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PooledSponsors")
+            .field("pool_set", &self.0.get().is_some())
+            .finish()
+    }
+}
+
+/// A pool of [`EvPooledTransaction`]s, looked up by sender and nonce.
+pub trait SponsoredPool: Send + Sync + 'static {
+    /// Returns the sponsor of the transaction pooled at `sender` and `nonce`, if it is
+    /// sponsored.
+    fn pooled_sponsor(&self, sender: Address, nonce: u64) -> Option<Address>;
+}
+
+impl<P> SponsoredPool for P
+where
+    P: TransactionPool<Transaction = EvPooledTransaction> + 'static,
+{
+    fn pooled_sponsor(&self, sender: Address, nonce: u64) -> Option<Address> {
+        self.get_transaction_by_sender_and_nonce(sender, nonce)?
+            .transaction
+            .sponsor()
+    }
+}
+
 /// Pool transaction wrapper for `EvTxEnvelope`.
+///
+/// An EvNode transaction in a nonce lane is pooled under the lane's account instead of its
+/// executor, so the pool orders it by the lane's sequence and it never waits on the executor's
+/// other lanes. The pool tracks no balance for lane accounts, so such a transaction reports no
+/// cost; its fee payer's balance is checked on admission and again when it is executed.
 #[derive(Debug, Clone)]
 pub struct EvPooledTransaction {
     inner: EthPooledTransaction<EvTxEnvelope>,
+    sender: Address,
+    /// Sponsor the transaction was admitted with, set when it is validated.
+    sponsorship: OnceLock<Sponsorship>,
+}
+
+/// Sponsor of an admitted transaction and the sponsors recovered from its fee-payer signature.
+#[derive(Debug, Clone, Copy)]
+struct Sponsorship {
+    sponsor: Address,
+    recovered: RecoveredSponsors,
 }
 
 impl EvPooledTransaction {
     /// Creates a new pooled transaction from a recovered envelope and encoded length.
     pub fn new(transaction: Recovered<EvTxEnvelope>, encoded_length: usize) -> Self {
+        let sender = match transaction.inner() {
+            EvTxEnvelope::EvNode(tx) => nonce_lane_address(transaction.signer(), tx.tx().nonce_key),
+            EvTxEnvelope::Ethereum(_) => transaction.signer(),
+        };
         Self {
             inner: EthPooledTransaction::new(transaction, encoded_length),
+            sender,
+            sponsorship: OnceLock::new(),
         }
     }
 
+    /// Returns whether the transaction is pooled under a nonce lane rather than its signer.
+    pub fn in_nonce_lane(&self) -> bool {
+        self.sender != self.inner.transaction.signer()
+    }
+
+    /// Returns the most the transaction can cost its signer and fee payer, including for
+    /// transactions in a nonce lane.
+    pub const fn max_cost(&self) -> &U256 {
+        &self.inner.cost
+    }
+
     /// Returns the recovered transaction.
     pub const fn transaction(&self) -> &Recovered<EvTxEnvelope> {
         self.inner.transaction()
     }
+
+    /// Returns the sponsors the txpool recovered from the fee-payer signature on admission,
+    /// for the payload builder to execute the transaction with.
+    pub fn recovered_sponsors(&self) -> RecoveredSponsors {
+        self.sponsorship
+            .get()
+            .map(|sponsorship| sponsorship.recovered)
+            .unwrap_or_default()
+    }
+
+    /// Returns the sponsor the transaction was admitted with, if it is sponsored.
+    pub fn sponsor(&self) -> Option<Address> {
+        self.sponsorship
+            .get()
+            .map(|sponsorship| sponsorship.sponsor)
+    }
 }
 
 impl PoolTransaction for EvPooledTransaction {
@@ -112,15 +251,19 @@ impl PoolTransaction for EvPooledTransaction {
     }
 
     fn sender(&self) -> Address {
-        self.inner.transaction.signer()
+        self.sender
     }
 
     fn sender_ref(&self) -> &Address {
-        self.inner.transaction.signer_ref()
+        &self.sender
     }
 
     fn cost(&self) -> &U256 {
-        &self.inner.cost
+        if self.in_nonce_lane() {
+            &U256::ZERO
+        } else {
+            &self.inner.cost
+        }
     }
 
     fn encoded_length(&self) -> usize {
@@ -297,17 +440,95 @@ pub enum EvTxPoolError {
     /// Top-level contract deployment not allowed for caller.
     #[error("contract deployment not allowed")]
     DeployNotAllowed,
+    /// The chain does not accept EvNode transactions.
+    #[error(transparent)]
+    TxTypeDisabled(#[from] EvNodeTxDisabled),
+    /// The transaction carries more calls than the chain allows.
+    #[error(transparent)]
+    TooManyCalls(#[from] TooManyBatchCalls),
+    /// The access list exceeds the chain's limits.
+    #[error(transparent)]
+    AccessListTooLarge(#[from] ev_revm::AccessListTooLarge),
+    /// The fee payer is not on the chain's sponsor allowlist.
+    #[error(transparent)]
+    SponsorNotAllowed(#[from] ev_revm::SponsorNotAllowed),
+    /// The transaction pays less than the chain's minimum gas price.
+    #[error(transparent)]
+    GasPriceBelowMinimum(#[from] ev_revm::GasPriceBelowMinimum),
+    /// The transaction tips less than the minimum priority fee while blocks run at or above
+    /// the gas target.
+    #[error("priority fee below the minimum of {0} while blocks are at or above the gas target")]
+    PriorityFeeBelowMinimum(u128),
+    /// The transaction may use more than the chain's share of the block for one transaction.
+    #[error(transparent)]
+    TxGasAboveMaximum(#[from] ev_revm::TxGasAboveMaximum),
+    /// A call carries its own gas limit before the chain accepts per-call gas limits.
+    #[error("per-call gas limits are not active")]
+    CallGasLimitInactive,
+    /// A best-effort batch before the chain accepts best-effort batches.
+    #[error("best-effort batches are not active")]
+    BestEffortInactive,
+    /// A best-effort batch deploys a contract.
+    #[error("best-effort batches may not deploy contracts")]
+    BestEffortCreate,
+    /// A transaction signed by a session key before the chain accepts session keys.
+    #[error("session keys are not active")]
+    SessionKeysInactive,
+    /// The calls of a session key transaction fall outside its authorization.
+    #[error(transparent)]
+    SessionScope(#[from] ev_primitives::SessionScopeError),
+    /// A transaction with a validity window before the chain accepts validity windows.
+    #[error("validity windows are not active")]
+    ValidityWindowsInactive,
+    /// The latest block falls outside the validity window of the transaction.
+    #[error(transparent)]
+    ValidityWindow(#[from] ev_primitives::ValidityWindowError),
+    /// A transaction in a nonce lane before the chain accepts nonce lanes.
+    #[error("nonce lanes are not active")]
+    NonceLanesInactive,
+    /// A transaction in a nonce lane deploys a contract.
+    #[error("nonce lane transactions may not deploy contracts")]
+    NonceLaneCreate,
+    /// A transaction signed with a P256 executor key before the chain accepts P256 keys.
+    #[error("P256 executor signatures are not active")]
+    P256SignaturesInactive,
+    /// The P256 executor signature does not recover to the transaction's sender.
+    #[error("invalid P256 executor signature")]
+    InvalidExecutorSignature,
+    /// The external screening service rejected the transaction or could not be reached.
+    #[error(transparent)]
+    Screening(#[from] ScreeningError),
+    /// A signature of the transaction also verifies in the other signing domain.
+    #[error(transparent)]
+    CrossDomainSignature(#[from] CrossDomainSignature),
+    /// The transaction would replace a pooled transaction paid by another sponsor.
+    #[error(transparent)]
+    SponsoredReplacement(#[from] SponsoredReplacement),
 }
 
 impl PoolTransactionError for EvTxPoolError {
     fn is_bad_transaction(&self) -> bool {
-        matches!(
-            self,
-            Self::EmptyCalls
-                | Self::InvalidCreatePosition
-                | Self::InvalidSponsorSignature
-                | Self::DeployNotAllowed
-        )
+        match self {
+            // An authorization may expire while its transaction waits for inclusion.
+            Self::SessionScope(err) => {
+                !matches!(err, ev_primitives::SessionScopeError::Expired { .. })
+            }
+            _ => matches!(
+                self,
+                Self::EmptyCalls
+                    | Self::InvalidCreatePosition
+                    | Self::BestEffortCreate
+                    | Self::NonceLaneCreate
+                    | Self::InvalidSponsorSignature
+                    | Self::InvalidExecutorSignature
+                    | Self::DeployNotAllowed
+                    | Self::TxTypeDisabled(_)
+                    | Self::TooManyCalls(_)
+                    | Self::AccessListTooLarge(_)
+                    | Self::SponsorNotAllowed(_)
+                    | Self::CrossDomainSignature(_)
+            ),
+        }
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -320,6 +541,28 @@ impl PoolTransactionError for EvTxPoolError {
 pub struct EvTransactionValidator<Client, Evm> {
     inner: Arc<EthTransactionValidator<Client, EvPooledTransaction, Evm>>,
     deploy_allowlist: Option<ev_revm::deploy::DeployAllowlistSettings>,
+    contract_size_limit: Option<ev_revm::ContractSizeLimitSettings>,
+    gas_allowance: Option<ev_revm::GasAllowanceSettings>,
+    sponsor_hash: Option<ev_revm::SponsorHashMigration>,
+    eip712_sponsor: Option<ev_revm::Eip712SponsorSettings>,
+    call_gas_limits: Option<ev_revm::CallGasLimitSettings>,
+    best_effort: Option<ev_revm::BestEffortSettings>,
+    session_keys: Option<ev_revm::SessionKeySettings>,
+    validity_windows: Option<ev_revm::ValidityWindowSettings>,
+    nonce_lanes: Option<ev_revm::NonceLaneSettings>,
+    p256_signatures: Option<ev_revm::P256SignatureSettings>,
+    max_tx_gas: Option<ev_revm::MaxTxGasSettings>,
+    max_batch_calls: Option<MaxBatchCalls>,
+    access_list: Option<ev_revm::AccessListSettings>,
+    sponsor_allowlist: Option<ev_revm::SponsorAllowlistSettings>,
+    evnode_tx_enabled: bool,
+    signing_domain_audit: bool,
+    min_gas_price: Option<ev_revm::MinGasPriceSettings>,
+    maintenance_lane: Arc<MaintenanceLane>,
+    minimum_priority_fee: Option<u128>,
+    gas_target: Option<u64>,
+    screener: Option<TransactionScreener>,
+    pooled_sponsors: PooledSponsors,
 }
 
 impl<Client, Evm> EvTransactionValidator<Client, Evm>
@@ -334,16 +577,576 @@ where
         Self {
             inner: Arc::new(inner),
             deploy_allowlist,
+            contract_size_limit: None,
+            gas_allowance: None,
+            sponsor_hash: None,
+            eip712_sponsor: None,
+            call_gas_limits: None,
+            best_effort: None,
+            session_keys: None,
+            validity_windows: None,
+            nonce_lanes: None,
+            p256_signatures: None,
+            max_tx_gas: None,
+            max_batch_calls: None,
+            access_list: None,
+            sponsor_allowlist: None,
+            evnode_tx_enabled: true,
+            signing_domain_audit: false,
+            min_gas_price: None,
+            maintenance_lane: Arc::default(),
+            minimum_priority_fee: None,
+            gas_target: None,
+            screener: None,
+            pooled_sponsors: PooledSponsors::default(),
+        }
+    }
+
+    /// Rejects deployments whose initcode exceeds twice the given contract size limit once it
+    /// activates.
+    pub const fn with_contract_size_limit(
+        mut self,
+        contract_size_limit: Option<ev_revm::ContractSizeLimitSettings>,
+    ) -> Self {
+        self.contract_size_limit = contract_size_limit;
+        self
+    }
+
+    /// Accepts `IGasAllowance` contract fee payers once the given settings activate.
+    pub const fn with_gas_allowance(
+        mut self,
+        gas_allowance: Option<ev_revm::GasAllowanceSettings>,
+    ) -> Self {
+        self.gas_allowance = gas_allowance;
+        self
+    }
+
+    /// Accepts sponsorships under the sponsor hash schemes of the given migration.
+    pub const fn with_sponsor_hash(
+        mut self,
+        sponsor_hash: Option<ev_revm::SponsorHashMigration>,
+    ) -> Self {
+        self.sponsor_hash = sponsor_hash;
+        self
+    }
+
+    /// Accepts EIP-712 sponsorships once the given settings activate.
+    pub const fn with_eip712_sponsor(
+        mut self,
+        eip712_sponsor: Option<ev_revm::Eip712SponsorSettings>,
+    ) -> Self {
+        self.eip712_sponsor = eip712_sponsor;
+        self
+    }
+
+    /// Accepts calls carrying their own gas limit once the given settings activate.
+    pub const fn with_call_gas_limits(
+        mut self,
+        call_gas_limits: Option<ev_revm::CallGasLimitSettings>,
+    ) -> Self {
+        self.call_gas_limits = call_gas_limits;
+        self
+    }
+
+    /// Accepts best-effort batches once the given settings activate.
+    pub const fn with_best_effort(
+        mut self,
+        best_effort: Option<ev_revm::BestEffortSettings>,
+    ) -> Self {
+        self.best_effort = best_effort;
+        self
+    }
+
+    /// Accepts transactions signed by session keys once the given settings activate.
+    pub const fn with_session_keys(
+        mut self,
+        session_keys: Option<ev_revm::SessionKeySettings>,
+    ) -> Self {
+        self.session_keys = session_keys;
+        self
+    }
+
+    /// Accepts transactions with a validity window once the given settings activate.
+    pub const fn with_validity_windows(
+        mut self,
+        validity_windows: Option<ev_revm::ValidityWindowSettings>,
+    ) -> Self {
+        self.validity_windows = validity_windows;
+        self
+    }
+
+    /// Accepts transactions in nonce lanes once the given settings activate.
+    pub const fn with_nonce_lanes(
+        mut self,
+        nonce_lanes: Option<ev_revm::NonceLaneSettings>,
+    ) -> Self {
+        self.nonce_lanes = nonce_lanes;
+        self
+    }
+
+    /// Accepts transactions signed with a P256 executor key once the given settings activate.
+    pub const fn with_p256_signatures(
+        mut self,
+        p256_signatures: Option<ev_revm::P256SignatureSettings>,
+    ) -> Self {
+        self.p256_signatures = p256_signatures;
+        self
+    }
+
+    /// Rejects transactions that may use more than the given share of the latest block's gas
+    /// limit.
+    pub const fn with_max_tx_gas(mut self, max_tx_gas: Option<ev_revm::MaxTxGasSettings>) -> Self {
+        self.max_tx_gas = max_tx_gas;
+        self
+    }
+
+    /// Rejects EvNode transactions carrying more calls than the given limit allows.
+    pub const fn with_max_batch_calls(mut self, max_batch_calls: Option<MaxBatchCalls>) -> Self {
+        self.max_batch_calls = max_batch_calls;
+        self
+    }
+
+    /// Rejects EvNode transactions whose access list exceeds the given limits.
+    pub const fn with_access_list(
+        mut self,
+        access_list: Option<ev_revm::AccessListSettings>,
+    ) -> Self {
+        self.access_list = access_list;
+        self
+    }
+
+    /// Rejects sponsored transactions whose fee payer is not on the given allowlist.
+    pub fn with_sponsor_allowlist(
+        mut self,
+        sponsor_allowlist: Option<ev_revm::SponsorAllowlistSettings>,
+    ) -> Self {
+        self.sponsor_allowlist = sponsor_allowlist;
+        self
+    }
+
+    /// Rejects EvNode transactions whose executor or fee payer signature also verifies in the
+    /// other signing domain, if `enabled`.
+    pub const fn with_signing_domain_audit(mut self, enabled: bool) -> Self {
+        self.signing_domain_audit = enabled;
+        self
+    }
+
+    /// Rejects EvNode transactions unless `enabled`.
+    pub const fn with_evnode_tx_enabled(mut self, enabled: bool) -> Self {
+        self.evnode_tx_enabled = enabled;
+        self
+    }
+
+    /// Rejects transactions paying less than the given minimum gas price.
+    pub fn with_min_gas_price(
+        mut self,
+        min_gas_price: Option<ev_revm::MinGasPriceSettings>,
+    ) -> Self {
+        self.min_gas_price = min_gas_price;
+        self
+    }
+
+    /// Exempts the transactions flagged in `lane` from the minimum gas price.
+    pub fn with_maintenance_lane(mut self, lane: Arc<MaintenanceLane>) -> Self {
+        self.maintenance_lane = lane;
+        self
+    }
+
+    /// Rejects external transactions tipping less than `minimum_priority_fee`, unless the
+    /// latest block used less gas than `gas_target`.
+    ///
+    /// The base validator must not enforce the minimum itself, or it would never be waived.
+    pub const fn with_gas_target(
+        mut self,
+        minimum_priority_fee: Option<u128>,
+        gas_target: Option<u64>,
+    ) -> Self {
+        self.minimum_priority_fee = minimum_priority_fee;
+        self.gas_target = gas_target;
+        self
+    }
+
+    /// Asks an external screening service about every transaction passing validation.
+    pub fn with_screener(mut self, screener: Option<TransactionScreener>) -> Self {
+        self.screener = screener;
+        self
+    }
+
+    /// Rejects sponsored replacements of transactions in `pool` from another sponsor.
+    pub fn with_pooled_sponsors(mut self, pool: PooledSponsors) -> Self {
+        self.pooled_sponsors = pool;
+        self
+    }
+
+    /// Turns a valid outcome into an invalid one if the screening service does not admit the
+    /// transaction.
+    async fn screen(
+        &self,
+        origin: TransactionOrigin,
+        outcome: TransactionValidationOutcome<EvPooledTransaction>,
+    ) -> TransactionValidationOutcome<EvPooledTransaction> {
+        let (Some(screener), TransactionValidationOutcome::Valid { transaction, .. }) =
+            (&self.screener, &outcome)
+        else {
+            return outcome;
+        };
+        let tx = transaction.transaction().transaction();
+        let request = ScreeningRequest::new(tx.inner(), tx.signer(), origin);
+        match (screener.screen(&request).await, outcome) {
+            (Err(err), TransactionValidationOutcome::Valid { transaction, .. }) => {
+                TransactionValidationOutcome::Invalid(
+                    transaction.into_transaction(),
+                    InvalidPoolTransactionError::other(EvTxPoolError::from(err)),
+                )
+            }
+            (_, outcome) => outcome,
+        }
+    }
+
+    /// Checks the effective gas price at the latest block's base fee against the minimum.
+    fn check_min_gas_price(
+        &self,
+        pooled: &EvPooledTransaction,
+    ) -> Result<(), InvalidPoolTransactionError>
+    where
+        Client: HeaderProvider,
+    {
+        let Some(settings) = &self.min_gas_price else {
+            return Ok(());
+        };
+        // Operator-injected maintenance transactions must never be priced out.
+        if self.maintenance_lane.contains(pooled.hash()) {
+            return Ok(());
+        }
+        let state_error = |err: reth_provider::ProviderError| {
+            InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+        };
+        let client = self.inner.client();
+        let block_number = client.best_block_number().map_err(state_error)?;
+        let base_fee = client
+            .header_by_number(block_number)
+            .map_err(state_error)?
+            .and_then(|header| header.base_fee_per_gas());
+        let targets: Vec<TxKind> = match pooled.transaction().inner() {
+            EvTxEnvelope::Ethereum(tx) => vec![alloy_consensus::Transaction::kind(tx)],
+            EvTxEnvelope::EvNode(signed) => signed.tx().calls.iter().map(|call| call.to).collect(),
+        };
+        settings
+            .check(
+                pooled.transaction().signer(),
+                targets,
+                pooled.effective_gas_price(base_fee),
+                block_number,
+            )
+            .map_err(|err| InvalidPoolTransactionError::other(EvTxPoolError::from(err)))
+    }
+
+    /// Checks the gas limit of a transaction against the share of the latest block's gas
+    /// limit one transaction may use.
+    fn check_max_tx_gas(
+        &self,
+        pooled: &EvPooledTransaction,
+    ) -> Result<(), InvalidPoolTransactionError>
+    where
+        Client: HeaderProvider,
+    {
+        let Some(settings) = self.max_tx_gas else {
+            return Ok(());
+        };
+        let state_error = |err: reth_provider::ProviderError| {
+            InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+        };
+        let client = self.inner.client();
+        let block_number = client.best_block_number().map_err(state_error)?;
+        let Some(header) = client.header_by_number(block_number).map_err(state_error)? else {
+            return Ok(());
+        };
+        settings
+            .check(pooled.gas_limit(), header.gas_limit(), block_number)
+            .map_err(|err| InvalidPoolTransactionError::other(EvTxPoolError::from(err)))
+    }
+
+    /// Checks the priority fee of an external transaction against the minimum, which is waived
+    /// while the latest block used less gas than the gas target.
+    fn check_priority_fee(
+        &self,
+        origin: TransactionOrigin,
+        pooled: &EvPooledTransaction,
+    ) -> Result<(), InvalidPoolTransactionError>
+    where
+        Client: HeaderProvider,
+    {
+        let (Some(minimum), Some(gas_target)) = (self.minimum_priority_fee, self.gas_target) else {
+            return Ok(());
+        };
+        if origin.is_local()
+            || pooled
+                .max_priority_fee_per_gas()
+                .is_none_or(|tip| tip >= minimum)
+        {
+            return Ok(());
         }
+        let state_error = |err: reth_provider::ProviderError| {
+            InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+        };
+        let client = self.inner.client();
+        let block_number = client.best_block_number().map_err(state_error)?;
+        let below_target = client
+            .header_by_number(block_number)
+            .map_err(state_error)?
+            .is_some_and(|header| header.gas_used() < gas_target);
+        if below_target {
+            return Ok(());
+        }
+        Err(InvalidPoolTransactionError::other(
+            EvTxPoolError::PriorityFeeBelowMinimum(minimum),
+        ))
+    }
+
+    fn gas_allowance_active(&self) -> Result<bool, InvalidPoolTransactionError> {
+        let Some(settings) = self.gas_allowance else {
+            return Ok(false);
+        };
+        let block_number = self.inner.client().best_block_number().map_err(
+            |err: reth_provider::ProviderError| {
+                InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+            },
+        )?;
+        Ok(settings.is_active(block_number))
+    }
+
+    /// Intrinsic gas the EV handler computes for `tx`: the cost of each call plus the EIP-2930
+    /// cost of the access list, under the spec the base validator checks intrinsic gas with.
+    fn evnode_intrinsic_gas(&self, tx: &EvNodeTransaction) -> u64 {
+        let forks = self.inner.fork_tracker();
+        let spec = if forks.is_prague_activated() {
+            SpecId::PRAGUE
+        } else if forks.is_shanghai_activated() {
+            SpecId::SHANGHAI
+        } else {
+            SpecId::MERGE
+        };
+        let storage_keys = tx
+            .access_list
+            .iter()
+            .map(|item| item.storage_keys.len() as u64)
+            .sum::<u64>();
+        tx.calls
+            .iter()
+            .map(|call| {
+                calculate_initial_tx_gas(spec, call.input.as_ref(), call.to.is_create(), 0, 0, 0)
+                    .initial_total_gas
+            })
+            .fold(0u64, u64::saturating_add)
+            .saturating_add((tx.access_list.len() as u64).saturating_mul(ACCESS_LIST_ADDRESS))
+            .saturating_add(storage_keys.saturating_mul(ACCESS_LIST_STORAGE_KEY))
+    }
+
+    fn call_gas_limits_active(&self) -> Result<bool, InvalidPoolTransactionError> {
+        let Some(settings) = self.call_gas_limits else {
+            return Ok(false);
+        };
+        let block_number = self.inner.client().best_block_number().map_err(
+            |err: reth_provider::ProviderError| {
+                InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+            },
+        )?;
+        Ok(settings.is_active(block_number))
+    }
+
+    fn p256_signatures_active(&self) -> Result<bool, InvalidPoolTransactionError> {
+        let Some(settings) = self.p256_signatures else {
+            return Ok(false);
+        };
+        let block_number = self.inner.client().best_block_number().map_err(
+            |err: reth_provider::ProviderError| {
+                InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+            },
+        )?;
+        Ok(settings.is_active(block_number))
+    }
+
+    /// Checks the P256 executor signature of a transaction, if it carries one.
+    ///
+    /// The signature is verified again here rather than trusting the signer the transaction
+    /// was admitted with, so that every pooled P256 transaction recovers to its sender.
+    fn check_p256_signature(
+        &self,
+        signed: &ev_primitives::EvNodeSignedTx,
+        signer: Address,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        if signed.tx().p256_signature.is_none() {
+            return Ok(());
+        }
+        if !self.p256_signatures_active()? {
+            return Err(InvalidPoolTransactionError::other(
+                EvTxPoolError::P256SignaturesInactive,
+            ));
+        }
+        if signed.tx().recover_executor(signed.signature()) != Ok(signer) {
+            return Err(InvalidPoolTransactionError::other(
+                EvTxPoolError::InvalidExecutorSignature,
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_signing_domains(
+        &self,
+        signed: &ev_primitives::EvNodeSignedTx,
+        executor: Address,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        if !self.signing_domain_audit {
+            return Ok(());
+        }
+        signed
+            .tx()
+            .check_signing_domains(executor, signed.signature())
+            .map_err(|err| {
+                warn!(
+                    target: "ev-reth::txpool",
+                    tx_hash = %signed.hash(),
+                    %err,
+                    "Rejected transaction signed across signing domains"
+                );
+                metrics::counter!("ev_reth_txpool_cross_domain_signatures_total").increment(1);
+                InvalidPoolTransactionError::other(EvTxPoolError::from(err))
+            })
+    }
+
+    fn nonce_lanes_active(&self) -> Result<bool, InvalidPoolTransactionError> {
+        let Some(settings) = self.nonce_lanes else {
+            return Ok(false);
+        };
+        let block_number = self.inner.client().best_block_number().map_err(
+            |err: reth_provider::ProviderError| {
+                InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+            },
+        )?;
+        Ok(settings.is_active(block_number))
+    }
+
+    /// Rejects a top-level deployment whose initcode exceeds the limit derived from the chain's
+    /// contract size limit at the latest block, so it is not left to fail at execution.
+    fn check_initcode_size(&self, tx: &EvTxEnvelope) -> Result<(), InvalidPoolTransactionError> {
+        let Some(settings) = self.contract_size_limit else {
+            return Ok(());
+        };
+        let initcode = match tx {
+            EvTxEnvelope::Ethereum(tx) => alloy_consensus::Transaction::is_create(tx)
+                .then(|| alloy_consensus::Transaction::input(tx)),
+            EvTxEnvelope::EvNode(signed) => signed
+                .tx()
+                .calls
+                .first()
+                .filter(|call| call.is_create())
+                .map(|call| &call.input),
+        };
+        let Some(initcode) = initcode else {
+            return Ok(());
+        };
+        let block_number = self.inner.client().best_block_number().map_err(
+            |err: reth_provider::ProviderError| {
+                InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+            },
+        )?;
+        match settings.max_initcode_size(block_number) {
+            Some(max) if initcode.len() > max => Err(
+                InvalidPoolTransactionError::ExceedsMaxInitCodeSize(initcode.len(), max),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    fn best_effort_active(&self) -> Result<bool, InvalidPoolTransactionError> {
+        let Some(settings) = self.best_effort else {
+            return Ok(false);
+        };
+        let block_number = self.inner.client().best_block_number().map_err(
+            |err: reth_provider::ProviderError| {
+                InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+            },
+        )?;
+        Ok(settings.is_active(block_number))
+    }
+
+    /// Checks a session key transaction against its authorization at the latest block.
+    ///
+    /// Expiry is checked against the latest block's timestamp, so a transaction admitted here
+    /// can still expire before it is included; the handler checks again at execution.
+    fn check_session_scope(&self, tx: &EvNodeTransaction) -> Result<(), InvalidPoolTransactionError>
+    where
+        Client: HeaderProvider,
+    {
+        let Some(session) = &tx.session else {
+            return Ok(());
+        };
+        let state_error = |err: reth_provider::ProviderError| {
+            InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+        };
+        let client = self.inner.client();
+        let block_number = client.best_block_number().map_err(state_error)?;
+        if !self
+            .session_keys
+            .is_some_and(|settings| settings.is_active(block_number))
+        {
+            return Err(InvalidPoolTransactionError::other(
+                EvTxPoolError::SessionKeysInactive,
+            ));
+        }
+        let timestamp = client
+            .header_by_number(block_number)
+            .map_err(state_error)?
+            .map_or(0, |header| header.timestamp());
+        session
+            .authorization
+            .check_scope(tx.chain_id, &tx.calls, timestamp)
+            .map_err(|err| InvalidPoolTransactionError::other(EvTxPoolError::from(err)))
+    }
+
+    /// Checks that the latest block falls within the validity window of a transaction.
+    ///
+    /// Transactions are only admitted while their window is open, and may still expire before
+    /// they are included; the handler checks again at execution.
+    fn check_validity_window(
+        &self,
+        tx: &EvNodeTransaction,
+    ) -> Result<(), InvalidPoolTransactionError>
+    where
+        Client: HeaderProvider,
+    {
+        let Some(window) = tx.validity_window() else {
+            return Ok(());
+        };
+        let state_error = |err: reth_provider::ProviderError| {
+            InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+        };
+        let client = self.inner.client();
+        let block_number = client.best_block_number().map_err(state_error)?;
+        if !self
+            .validity_windows
+            .is_some_and(|settings| settings.is_active(block_number))
+        {
+            return Err(InvalidPoolTransactionError::other(
+                EvTxPoolError::ValidityWindowsInactive,
+            ));
+        }
+        let timestamp = client
+            .header_by_number(block_number)
+            .map_err(state_error)?
+            .map_or(0, |header| header.timestamp());
+        window
+            .check(timestamp)
+            .map_err(|err| InvalidPoolTransactionError::other(EvTxPoolError::from(err)))
     }
 
     fn check_sender_overdraft(
         pooled: &EvPooledTransaction,
         sender_balance: U256,
     ) -> Result<(), InvalidPoolTransactionError> {
-        if sender_balance < *pooled.cost() {
+        if sender_balance < *pooled.max_cost() {
             return Err(InvalidPoolTransactionError::Overdraft {
-                cost: *pooled.cost(),
+                cost: *pooled.max_cost(),
                 balance: sender_balance,
             });
         }
@@ -364,6 +1167,72 @@ where
                 EvTxPoolError::InvalidCreatePosition,
             ));
         }
+        if let Some(max_batch_calls) = self.max_batch_calls {
+            let block_number = self.inner.client().best_block_number().map_err(
+                |err: reth_provider::ProviderError| {
+                    InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(
+                        err.to_string(),
+                    ))
+                },
+            )?;
+            max_batch_calls
+                .check(tx, block_number)
+                .map_err(|err| InvalidPoolTransactionError::other(EvTxPoolError::from(err)))?;
+        }
+        if let Some(settings) = self.access_list {
+            let block_number = self.inner.client().best_block_number().map_err(
+                |err: reth_provider::ProviderError| {
+                    InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(
+                        err.to_string(),
+                    ))
+                },
+            )?;
+            if settings.is_active(block_number) {
+                settings
+                    .check(tx.access_list.iter().map(|item| item.storage_keys.len()))
+                    .map_err(|err| InvalidPoolTransactionError::other(EvTxPoolError::from(err)))?;
+                // Execution charges the surcharge on top of the intrinsic gas, which must still
+                // fit the gas limit.
+                let storage_keys = tx
+                    .access_list
+                    .iter()
+                    .map(|item| item.storage_keys.len() as u64)
+                    .sum::<u64>();
+                let extra_gas = settings.extra_gas(tx.access_list.len() as u64, storage_keys);
+                if self.evnode_intrinsic_gas(tx).saturating_add(extra_gas) > tx.gas_limit {
+                    return Err(InvalidPoolTransactionError::IntrinsicGasTooLow);
+                }
+            }
+        }
+        if tx.calls.iter().any(|call| call.gas_limit.is_some()) && !self.call_gas_limits_active()? {
+            return Err(InvalidPoolTransactionError::other(
+                EvTxPoolError::CallGasLimitInactive,
+            ));
+        }
+        if !tx.revert_mode.is_atomic() {
+            if !self.best_effort_active()? {
+                return Err(InvalidPoolTransactionError::other(
+                    EvTxPoolError::BestEffortInactive,
+                ));
+            }
+            if tx.calls[0].to.is_create() {
+                return Err(InvalidPoolTransactionError::other(
+                    EvTxPoolError::BestEffortCreate,
+                ));
+            }
+        }
+        if tx.nonce_lane().is_some() {
+            if !self.nonce_lanes_active()? {
+                return Err(InvalidPoolTransactionError::other(
+                    EvTxPoolError::NonceLanesInactive,
+                ));
+            }
+            if tx.calls[0].to.is_create() {
+                return Err(InvalidPoolTransactionError::other(
+                    EvTxPoolError::NonceLaneCreate,
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -389,11 +1258,10 @@ where
         Ok(())
     }
 
-    fn validate_sponsor_balance(
+    fn account_balance(
         &self,
         state: &mut Option<Box<dyn AccountInfoReader + Send>>,
-        sponsor: Address,
-        gas_cost: U256,
+        address: Address,
     ) -> Result<U256, InvalidPoolTransactionError>
     where
         Client: StateProviderFactory,
@@ -401,18 +1269,108 @@ where
         self.ensure_state(state)?;
         let state = state.as_ref().expect("state provider is set");
         let account = state
-            .basic_account(&sponsor)
+            .basic_account(&address)
             .map_err(|err| {
                 InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
             })?
             .unwrap_or_default();
-        if account.balance < gas_cost {
+        Ok(account.balance)
+    }
+
+    fn validate_sponsor_balance(
+        &self,
+        state: &mut Option<Box<dyn AccountInfoReader + Send>>,
+        sponsor: Address,
+        gas_cost: U256,
+    ) -> Result<U256, InvalidPoolTransactionError>
+    where
+        Client: StateProviderFactory,
+    {
+        let balance = self.account_balance(state, sponsor)?;
+        if balance < gas_cost {
             return Err(InvalidPoolTransactionError::Overdraft {
                 cost: gas_cost,
-                balance: account.balance,
+                balance,
             });
         }
-        Ok(account.balance)
+        Ok(balance)
+    }
+
+    /// Recovers the sponsor of an ECDSA sponsorship under the sponsor hash scheme in force.
+    ///
+    /// During a migration's transition window the legacy scheme, and once active the EIP-712
+    /// scheme, is used only when its sponsor can cover `gas_cost` and the preferred scheme's
+    /// sponsor cannot, matching the handler's choice at execution time. Every sponsor recovered
+    /// along the way is added to `recovered`.
+    fn recover_sponsor(
+        &self,
+        state: &mut Option<Box<dyn AccountInfoReader + Send>>,
+        tx: &EvNodeTransaction,
+        executor: Address,
+        signature: &Signature,
+        gas_cost: U256,
+        recovered: &mut RecoveredSponsors,
+    ) -> Result<(Address, SponsorHashScheme), InvalidPoolTransactionError>
+    where
+        Client: StateProviderFactory,
+    {
+        let mut preferred = SponsorHashScheme::Executor;
+        let mut alternatives = [None, None];
+        if self.sponsor_hash.is_some() || self.eip712_sponsor.is_some() {
+            let block_number = self.inner.client().best_block_number().map_err(
+                |err: reth_provider::ProviderError| {
+                    InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(
+                        err.to_string(),
+                    ))
+                },
+            )?;
+            if let Some(migration) = self.sponsor_hash {
+                (preferred, alternatives[0]) = migration.schemes_at(block_number);
+            }
+            if self
+                .eip712_sponsor
+                .is_some_and(|settings| settings.is_active(block_number))
+            {
+                alternatives[1] = Some(SponsorHashScheme::Eip712);
+            }
+        }
+        let mut recover = |scheme| {
+            let sponsor = tx
+                .recover_sponsor_with(scheme, executor, signature)
+                .map_err(|_| {
+                    InvalidPoolTransactionError::other(EvTxPoolError::InvalidSponsorSignature)
+                })?;
+            recovered.insert(scheme, sponsor);
+            Ok(sponsor)
+        };
+
+        let sponsor = recover(preferred)?;
+        if alternatives.iter().any(Option::is_some)
+            && self.account_balance(state, sponsor)? < gas_cost
+        {
+            for scheme in alternatives.into_iter().flatten() {
+                let alternative = recover(scheme)?;
+                if self.account_balance(state, alternative)? >= gas_cost {
+                    return Ok((alternative, scheme));
+                }
+            }
+        }
+        Ok((sponsor, preferred))
+    }
+
+    /// Rejects `sponsor` if the sponsor allowlist is active and does not include it.
+    fn check_sponsor_allowed(&self, sponsor: Address) -> Result<(), InvalidPoolTransactionError> {
+        let Some(settings) = &self.sponsor_allowlist else {
+            return Ok(());
+        };
+        let block_number = self.inner.client().best_block_number().map_err(
+            |err: reth_provider::ProviderError| {
+                InvalidPoolTransactionError::other(EvTxPoolError::StateProvider(err.to_string()))
+            },
+        )?;
+        settings
+            .check(Some(sponsor), block_number)
+            .map_err(|err| InvalidPoolTransactionError::other(EvTxPoolError::from(err)))
     }
 
     /// Validates an `EvNode` transaction. Returns an optional override balance
@@ -430,9 +1388,17 @@ where
         state: &mut Option<Box<dyn AccountInfoReader + Send>>,
     ) -> Result<Option<U256>, InvalidPoolTransactionError>
     where
-        Client: StateProviderFactory,
+        Client: StateProviderFactory + HeaderProvider,
     {
         let _duration = RecordDurationOnDrop::new();
+        if !self.evnode_tx_enabled
+            && matches!(pooled.transaction().inner(), EvTxEnvelope::EvNode(_))
+        {
+            return Err(InvalidPoolTransactionError::other(EvTxPoolError::from(
+                EvNodeTxDisabled,
+            )));
+        }
+
         // Unified deploy allowlist check (covers both Ethereum and EvNode txs).
         if let Some(settings) = &self.deploy_allowlist {
             let is_top_level_create = match pooled.transaction().inner() {
@@ -462,34 +1428,86 @@ where
             }
         }
 
+        self.check_initcode_size(pooled.transaction().inner())?;
+
         let consensus = pooled.transaction().inner();
         let EvTxEnvelope::EvNode(tx) = consensus else {
             Self::check_sender_overdraft(pooled, sender_balance)?;
             return Ok(None);
         };
 
+        self.check_p256_signature(tx, pooled.transaction().signer())?;
+        self.check_signing_domains(tx, pooled.transaction().signer())?;
         let tx = tx.tx();
         self.validate_evnode_calls(tx)?;
+        self.check_session_scope(tx)?;
+        self.check_validity_window(tx)?;
+
+        // The base validator only saw the balance of the lane's account.
+        let sender_balance = if pooled.in_nonce_lane() {
+            self.account_balance(state, pooled.transaction().signer())?
+        } else {
+            sender_balance
+        };
 
         if let Some(signature) = tx.fee_payer_signature.as_ref() {
-            // Sponsored transaction: sponsor pays gas, executor pays call values.
+            // Sponsored transaction: sponsor pays gas up to its cap, executor pays call values
+            // and any gas above the cap.
             let executor = pooled.transaction().signer();
-            let sponsor = tx.recover_sponsor(executor, signature).map_err(|_| {
-                InvalidPoolTransactionError::other(EvTxPoolError::InvalidSponsorSignature)
-            })?;
+            let max_gas_cost =
+                U256::from(tx.max_fee_per_gas).saturating_mul(U256::from(tx.gas_limit));
+            let gas_cost = tx
+                .max_sponsor_fee
+                .map_or(max_gas_cost, |cap| max_gas_cost.min(U256::from(cap)));
+            let mut recovered = RecoveredSponsors::default();
+            let (sponsor, scheme) = match tx.contract_fee_payer() {
+                // The `approveGas` hook needs the EVM, so the pool only checks the contract's
+                // balance; a denial drops the transaction at build time.
+                Some(fee_payer) if self.gas_allowance_active()? => (fee_payer, None),
+                Some(_) => {
+                    return Err(InvalidPoolTransactionError::other(
+                        EvTxPoolError::InvalidSponsorSignature,
+                    ))
+                }
+                None => {
+                    let (sponsor, scheme) = self.recover_sponsor(
+                        state,
+                        tx,
+                        executor,
+                        signature,
+                        gas_cost,
+                        &mut recovered,
+                    )?;
+                    (sponsor, Some(scheme))
+                }
+            };
+            self.check_sponsor_allowed(sponsor)?;
+            self.pooled_sponsors
+                .check(pooled.sender(), pooled.nonce(), sponsor)
+                .map_err(|err| InvalidPoolTransactionError::other(EvTxPoolError::from(err)))?;
 
-            let gas_cost = U256::from(tx.max_fee_per_gas).saturating_mul(U256::from(tx.gas_limit));
             let sponsor_balance = self.validate_sponsor_balance(state, sponsor, gas_cost)?;
+            if let Some(scheme) = scheme {
+                metrics::counter!(
+                    "ev_reth_txpool_sponsorships_total",
+                    "scheme" => scheme.as_str()
+                )
+                .increment(1);
+            }
 
-            // Validate executor balance covers call value transfers
-            let call_value = alloy_consensus::Transaction::value(tx);
-            if !call_value.is_zero() && sender_balance < call_value {
+            // Validate executor balance covers call value transfers and the uncapped gas
+            let executor_cost =
+                alloy_consensus::Transaction::value(tx).saturating_add(max_gas_cost - gas_cost);
+            if !executor_cost.is_zero() && sender_balance < executor_cost {
                 return Err(InvalidPoolTransactionError::Overdraft {
-                    cost: call_value,
+                    cost: executor_cost,
                     balance: sender_balance,
                 });
             }
 
+            // The builder takes the sponsors with the pooled transaction instead of recovering
+            // them again.
+            let _ = pooled.sponsorship.set(Sponsorship { sponsor, recovered });
             Ok(Some(sponsor_balance))
         } else {
             Self::check_sender_overdraft(pooled, sender_balance)?;
@@ -500,7 +1518,10 @@ where
 
 impl<Client, Evm> TransactionValidator for EvTransactionValidator<Client, Evm>
 where
-    Client: ChainSpecProvider<ChainSpec: EthereumHardforks> + StateProviderFactory + BlockNumReader,
+    Client: ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + StateProviderFactory
+        + BlockNumReader
+        + HeaderProvider,
     Evm: reth_evm::ConfigureEvm + 'static,
 {
     type Transaction = EvPooledTransaction;
@@ -530,7 +1551,12 @@ where
                 transaction,
                 propagate,
                 authorities,
-            } => match self.validate_evnode(transaction.transaction(), balance, &mut state) {
+            } => match self
+                .check_min_gas_price(transaction.transaction())
+                .and_then(|()| self.check_max_tx_gas(transaction.transaction()))
+                .and_then(|()| self.check_priority_fee(origin, transaction.transaction()))
+                .and_then(|()| self.validate_evnode(transaction.transaction(), balance, &mut state))
+            {
                 Ok(override_balance) => TransactionValidationOutcome::Valid {
                     balance: override_balance.unwrap_or(balance),
                     state_nonce,
@@ -546,14 +1572,23 @@ where
             other => other,
         };
 
-        result
+        self.screen(origin, result).await
     }
 }
 
 /// Pool builder that wires the custom `EvNode` transaction validator.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EvolvePoolBuilder;
+pub struct EvolvePoolBuilder {
+    config: SharedEvolveConfig,
+}
+
+impl EvolvePoolBuilder {
+    /// Creates a pool builder validating against the node's evolve configuration.
+    pub const fn new(config: SharedEvolveConfig) -> Self {
+        Self { config }
+    }
+}
 
 impl<Types, Node, Evm> PoolBuilder<Node, Evm> for EvolvePoolBuilder
 where
@@ -572,6 +1607,7 @@ where
 
     async fn build_pool(self, ctx: &BuilderContext<Node>, evm: Evm) -> eyre::Result<Self::Pool> {
         let pool_config = ctx.pool_config();
+        let pooled_sponsors = PooledSponsors::default();
 
         let blobs_disabled = ctx.config().txpool.blobpool_max_count == 0;
 
@@ -591,6 +1627,13 @@ where
 
         let blob_store = create_blob_store_with_cache(ctx, blob_cache_size)?;
 
+        let evolve_config = self.config.get_or_resolve(&ctx.chain_spec())?;
+        // With a gas target, the minimum priority fee is waived below it, which only the EV
+        // validator knows about.
+        let minimum_priority_fee = ctx.config().txpool.minimum_priority_fee;
+        let base_minimum_priority_fee =
+            minimum_priority_fee.filter(|_| evolve_config.gas_target.is_none());
+
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone(), evm)
             .set_eip4844(!blobs_disabled)
             .kzg_settings(ctx.kzg_settings()?)
@@ -598,7 +1641,7 @@ where
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
             .set_tx_fee_cap(ctx.config().rpc.rpc_tx_fee_cap)
             .with_max_tx_gas_limit(ctx.config().txpool.max_tx_gas_limit)
-            .with_minimum_priority_fee(ctx.config().txpool.minimum_priority_fee)
+            .with_minimum_priority_fee(base_minimum_priority_fee)
             // Disable the standard caller balance check - we handle balance validation
             // in EvTransactionValidator::validate_evnode which checks:
             // - Sponsor balance for sponsored EvNode transactions
@@ -611,23 +1654,92 @@ where
             )
             .map(|inner| {
                 // Wire deploy-allowlist from chainspec extras into the pool validator.
-                let evolve_config = crate::config::EvolvePayloadBuilderConfig::from_chain_spec(
-                    ctx.chain_spec().as_ref(),
-                )
-                .unwrap_or_else(|err| {
-                    warn!(
-                        target: "reth::cli",
-                        "Failed to parse evolve config from chainspec: {err}"
-                    );
-                    Default::default()
-                });
                 let deploy_allowlist =
                     evolve_config
                         .deploy_allowlist_settings()
                         .map(|(allowlist, activation)| {
                             ev_revm::deploy::DeployAllowlistSettings::new(allowlist, activation)
                         });
+                let gas_allowance = evolve_config
+                    .gas_allowance_activation_height
+                    .map(ev_revm::GasAllowanceSettings::new);
+                let call_gas_limits = evolve_config
+                    .call_gas_limit_activation_height
+                    .map(ev_revm::CallGasLimitSettings::new);
+                let best_effort = evolve_config
+                    .best_effort_activation_height
+                    .map(ev_revm::BestEffortSettings::new);
+                let session_keys = evolve_config
+                    .session_key_activation_height
+                    .map(ev_revm::SessionKeySettings::new);
+                let validity_windows = evolve_config
+                    .validity_window_activation_height
+                    .map(ev_revm::ValidityWindowSettings::new);
+                let nonce_lanes = evolve_config
+                    .nonce_lane_activation_height
+                    .map(ev_revm::NonceLaneSettings::new);
+                let p256_signatures = evolve_config
+                    .p256_signature_activation_height
+                    .map(ev_revm::P256SignatureSettings::new);
+                let max_tx_gas =
+                    evolve_config
+                        .max_tx_gas_settings()
+                        .map(|(max_bps, activation)| {
+                            ev_revm::MaxTxGasSettings::new(max_bps, activation)
+                        });
+                let sponsor_hash = evolve_config.sponsor_hash_migration().map(
+                    |(legacy, transition, activation)| {
+                        ev_revm::SponsorHashMigration::new(legacy, transition, activation)
+                    },
+                );
+                let eip712_sponsor = evolve_config
+                    .sponsor_eip712_activation_height
+                    .map(ev_revm::Eip712SponsorSettings::new);
+                let min_gas_price = evolve_config.min_gas_price_settings().map(
+                    |(min_gas_price, exempt_senders, free_gas_targets, activation)| {
+                        ev_revm::MinGasPriceSettings::new(
+                            min_gas_price,
+                            exempt_senders,
+                            free_gas_targets,
+                            activation,
+                        )
+                    },
+                );
+                let contract_size_limit =
+                    evolve_config
+                        .contract_size_limit_settings()
+                        .map(|(limit, activation)| {
+                            ev_revm::ContractSizeLimitSettings::new(limit, activation)
+                        });
                 EvTransactionValidator::new(inner, deploy_allowlist)
+                    .with_contract_size_limit(contract_size_limit)
+                    .with_gas_allowance(gas_allowance)
+                    .with_sponsor_hash(sponsor_hash)
+                    .with_eip712_sponsor(eip712_sponsor)
+                    .with_call_gas_limits(call_gas_limits)
+                    .with_best_effort(best_effort)
+                    .with_session_keys(session_keys)
+                    .with_validity_windows(validity_windows)
+                    .with_nonce_lanes(nonce_lanes)
+                    .with_p256_signatures(p256_signatures)
+                    .with_max_tx_gas(max_tx_gas)
+                    .with_max_batch_calls(evolve_config.max_batch_calls())
+                    .with_access_list(evolve_config.access_list_settings())
+                    .with_sponsor_allowlist(evolve_config.sponsor_allowlist_settings().map(
+                        |(allowlist, activation)| {
+                            ev_revm::SponsorAllowlistSettings::new(allowlist, activation)
+                        },
+                    ))
+                    .with_evnode_tx_enabled(evolve_config.evnode_tx_enabled())
+                    .with_signing_domain_audit(evolve_config.node.signing_domain_audit)
+                    .with_min_gas_price(min_gas_price)
+                    .with_maintenance_lane(evolve_config.handles.maintenance.clone())
+                    .with_gas_target(
+                        minimum_priority_fee.filter(|_| evolve_config.gas_target.is_some()),
+                        evolve_config.gas_target,
+                    )
+                    .with_screener(evolve_config.node.screener.clone())
+                    .with_pooled_sponsors(pooled_sponsors.clone())
             });
 
         if validator.validator().inner.eip4844() {
@@ -641,6 +1753,7 @@ where
         let transaction_pool = TxPoolBuilder::new(ctx)
             .with_validator(validator)
             .build_and_spawn_maintenance_task(blob_store, pool_config)?;
+        pooled_sponsors.set(transaction_pool.clone());
 
         info!(target: "reth::cli", "Transaction pool initialized");
         debug!(target: "reth::cli", "Spawned txpool maintenance task");
@@ -649,14 +1762,20 @@ where
     }
 }
 
+/// Sub-pool eviction limits of the pool built from `args`, for `evolve_txpoolInspect`.
+pub fn txpool_limits(args: &TxPoolArgs) -> TxpoolLimits {
+    TxpoolLimits::from(&args.pool_config())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use alloy_consensus::Signed;
-    use alloy_eips::eip2930::AccessList;
-    use alloy_primitives::{Bytes, Signature, TxKind};
-    use ev_primitives::{Call, EvNodeSignedTx, EvNodeTransaction};
+    use alloy_eips::eip2930::{AccessList, AccessListItem};
+    use alloy_primitives::{aliases::U192, Bytes, Signature, TxKind, B256};
+    use ev_primitives::{Call, EvNodeSignedTx, EvNodeTransaction, RevertMode};
     use reth_provider::test_utils::MockEthProvider;
+    use std::collections::HashMap;
 
     fn sample_signature() -> Signature {
         let mut bytes = [0u8; 65];
@@ -669,6 +1788,7 @@ mod tests {
         let tx = EvNodeTransaction {
             chain_id: 1,
             nonce: 0,
+            nonce_key: U192::ZERO,
             max_priority_fee_per_gas: 1,
             max_fee_per_gas,
             gas_limit,
@@ -676,9 +1796,16 @@ mod tests {
                 to: TxKind::Call(Address::ZERO),
                 value: U256::ZERO,
                 input: Bytes::new(),
+                gas_limit: None,
             }],
             access_list: AccessList::default(),
+            revert_mode: RevertMode::Atomic,
+            valid_after: None,
+            valid_until: None,
+            session: None,
             fee_payer_signature: None, // Non-sponsored
+            max_sponsor_fee: None,
+            p256_signature: None,
         };
         Signed::new_unhashed(tx, sample_signature())
     }
@@ -691,6 +1818,7 @@ mod tests {
         let tx = EvNodeTransaction {
             chain_id: 1,
             nonce: 0,
+            nonce_key: U192::ZERO,
             max_priority_fee_per_gas: 1,
             max_fee_per_gas,
             gas_limit,
@@ -698,9 +1826,16 @@ mod tests {
                 to: TxKind::Create,
                 value: U256::ZERO,
                 input: Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]), // minimal initcode
+                gas_limit: None,
             }],
             access_list: AccessList::default(),
+            revert_mode: RevertMode::Atomic,
+            valid_after: None,
+            valid_until: None,
+            session: None,
             fee_payer_signature: None,
+            max_sponsor_fee: None,
+            p256_signature: None,
         };
         Signed::new_unhashed(tx, sample_signature())
     }
@@ -712,6 +1847,29 @@ mod tests {
         EvPooledTransaction::new(recovered, encoded_length)
     }
 
+    /// Raw 0x76 envelopes submitted over `eth_sendRawTransaction` decode into the pooled
+    /// envelope and keep their hash and encoded length in the pool.
+    #[test]
+    fn raw_evnode_transaction_decodes_into_pool_transaction() {
+        use alloy_eips::Decodable2718;
+
+        let signed = create_non_sponsored_evnode_tx(21_000, 1_000_000_000);
+        let raw = EvTxEnvelope::EvNode(signed.clone()).encoded_2718();
+        let pooled = EvPooledTxEnvelope::decode_2718_exact(&raw)
+            .expect("0x76 envelope should decode as a pooled transaction");
+        assert!(matches!(pooled, EvPooledTxEnvelope::EvNode(_)));
+
+        let signer = Address::random();
+        let tx = EvPooledTransaction::from_pooled(Recovered::new_unchecked(pooled, signer));
+        assert_eq!(tx.hash(), signed.hash());
+        assert_eq!(tx.encoded_length(), raw.len());
+        assert_eq!(tx.sender(), signer);
+        assert!(matches!(
+            tx.clone_into_consensus().inner(),
+            EvTxEnvelope::EvNode(_)
+        ));
+    }
+
     fn create_test_validator(
         deploy_allowlist: Option<ev_revm::deploy::DeployAllowlistSettings>,
     ) -> EvTransactionValidator<MockEthProvider, crate::executor::EvolveEvmConfig> {
@@ -763,6 +1921,521 @@ mod tests {
         }
     }
 
+    /// Tests that `EvNode` transactions are rejected when the type is disabled.
+    #[test]
+    fn evnode_rejected_when_tx_type_disabled() {
+        let validator = create_test_validator(None).with_evnode_tx_enabled(false);
+        let pooled = create_pooled_tx(
+            create_non_sponsored_evnode_tx(21_000, 1_000_000_000),
+            Address::random(),
+        );
+        let mut state: Option<Box<dyn AccountInfoReader + Send>> = None;
+
+        let err = validator
+            .validate_evnode(&pooled, U256::MAX, &mut state)
+            .expect_err("disabled tx type must be rejected");
+        let InvalidPoolTransactionError::Other(err) = err else {
+            panic!("expected an EV pool error, got: {err:?}");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::TxTypeDisabled(_))
+        ));
+    }
+
+    /// Tests that session key transactions need active session keys and stay within the
+    /// scope of their authorization.
+    #[test]
+    fn session_transactions_checked_against_scope() {
+        let mut tx = create_non_sponsored_evnode_tx(21_000, 1_000_000_000).strip_signature();
+        tx.session = Some(ev_primitives::SignedSessionAuthorization {
+            authorization: ev_primitives::SessionAuthorization {
+                chain_id: 1,
+                session_key: Address::repeat_byte(0x5e),
+                targets: vec![Address::ZERO],
+                max_value: U256::ZERO,
+                expiry: u64::MAX,
+            },
+            signature: sample_signature(),
+        });
+        let scope_error = |validator: &EvTransactionValidator<_, _>, tx: &EvNodeTransaction| {
+            let InvalidPoolTransactionError::Other(err) = validator
+                .check_session_scope(tx)
+                .expect_err("transaction must be rejected")
+            else {
+                panic!("expected an EV pool error");
+            };
+            err
+        };
+
+        let validator = create_test_validator(None);
+        let err = scope_error(&validator, &tx);
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::SessionKeysInactive)
+        ));
+
+        let validator = validator.with_session_keys(Some(ev_revm::SessionKeySettings::new(0)));
+        assert!(validator.check_session_scope(&tx).is_ok());
+
+        tx.calls[0].to = TxKind::Call(Address::repeat_byte(0x01));
+        let err = scope_error(&validator, &tx);
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::SessionScope(
+                ev_primitives::SessionScopeError::Target(_)
+            ))
+        ));
+        assert!(err.is_bad_transaction());
+    }
+
+    /// Tests that P256-signed transactions need active P256 signatures and must recover to
+    /// their sender.
+    #[test]
+    fn p256_signature_checked_against_sender() {
+        let key = p256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let mut tx = create_non_sponsored_evnode_tx(21_000, 1_000_000_000).strip_signature();
+        let p256 = ev_primitives::P256Signature::sign(tx.executor_signing_hash(), &key).unwrap();
+        let executor = p256.address();
+        tx.p256_signature = Some(p256);
+        let signed = Signed::new_unhashed(tx, ev_primitives::p256_executor_marker());
+        let p256_error = |validator: &EvTransactionValidator<_, _>, signer: Address| {
+            let InvalidPoolTransactionError::Other(err) = validator
+                .check_p256_signature(&signed, signer)
+                .expect_err("transaction must be rejected")
+            else {
+                panic!("expected an EV pool error");
+            };
+            err
+        };
+
+        let validator = create_test_validator(None);
+        let err = p256_error(&validator, executor);
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::P256SignaturesInactive)
+        ));
+
+        let validator =
+            validator.with_p256_signatures(Some(ev_revm::P256SignatureSettings::new(0)));
+        assert!(validator.check_p256_signature(&signed, executor).is_ok());
+
+        let err = p256_error(&validator, Address::repeat_byte(0x11));
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::InvalidExecutorSignature)
+        ));
+        assert!(err.is_bad_transaction());
+    }
+
+    /// Tests that transactions with a validity window need active validity windows and are
+    /// admitted only while their window is open.
+    #[test]
+    fn validity_window_checked_against_latest_block() {
+        let mut tx = create_non_sponsored_evnode_tx(21_000, 1_000_000_000).strip_signature();
+        tx.valid_until = Some(u64::MAX - 1);
+        let window_error = |validator: &EvTransactionValidator<_, _>, tx: &EvNodeTransaction| {
+            let InvalidPoolTransactionError::Other(err) = validator
+                .check_validity_window(tx)
+                .expect_err("transaction must be rejected")
+            else {
+                panic!("expected an EV pool error");
+            };
+            err
+        };
+
+        let validator = create_test_validator(None);
+        let err = window_error(&validator, &tx);
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::ValidityWindowsInactive)
+        ));
+
+        let validator =
+            validator.with_validity_windows(Some(ev_revm::ValidityWindowSettings::new(0)));
+        assert!(validator.check_validity_window(&tx).is_ok());
+
+        tx.valid_after = Some(u64::MAX - 1);
+        let err = window_error(&validator, &tx);
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::ValidityWindow(
+                ev_primitives::ValidityWindowError::NotYetValid { .. }
+            ))
+        ));
+        assert!(!err.is_bad_transaction());
+    }
+
+    /// Tests that EvNode transactions with more calls than the chain allows are rejected.
+    #[test]
+    fn evnode_rejected_above_max_batch_calls() {
+        let mut tx = create_non_sponsored_evnode_tx(21_000, 1_000_000_000).strip_signature();
+        tx.calls = vec![tx.calls[0].clone(); 3];
+
+        let validator = create_test_validator(None);
+        assert!(validator.validate_evnode_calls(&tx).is_ok());
+
+        let validator = validator.with_max_batch_calls(Some(MaxBatchCalls::new(2, 0)));
+        let InvalidPoolTransactionError::Other(err) = validator
+            .validate_evnode_calls(&tx)
+            .expect_err("oversized batch must be rejected")
+        else {
+            panic!("expected an EV pool error");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::TooManyCalls(TooManyBatchCalls {
+                calls: 3,
+                max_calls: 2
+            }))
+        ));
+        assert!(err.is_bad_transaction());
+
+        tx.calls.pop();
+        assert!(validator.validate_evnode_calls(&tx).is_ok());
+    }
+
+    /// Tests that EvNode transactions with an oversized access list are rejected.
+    #[test]
+    fn evnode_rejected_above_access_list_limits() {
+        let mut tx = create_non_sponsored_evnode_tx(21_000, 1_000_000_000).strip_signature();
+        tx.access_list = AccessList(vec![
+            AccessListItem {
+                address: Address::repeat_byte(0x01),
+                storage_keys: vec![B256::ZERO; 2],
+            };
+            2
+        ]);
+
+        let validator = create_test_validator(None)
+            .with_access_list(Some(ev_revm::AccessListSettings::new(2, 1, 0, 0, 0)));
+        let InvalidPoolTransactionError::Other(err) = validator
+            .validate_evnode_calls(&tx)
+            .expect_err("oversized access list must be rejected")
+        else {
+            panic!("expected an EV pool error");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::AccessListTooLarge(
+                ev_revm::AccessListTooLarge::StorageKeys { index: 0, .. }
+            ))
+        ));
+        assert!(err.is_bad_transaction());
+
+        let validator =
+            validator.with_access_list(Some(ev_revm::AccessListSettings::new(2, 2, 0, 0, 0)));
+        assert!(validator.validate_evnode_calls(&tx).is_ok());
+    }
+
+    /// Tests that the access list surcharge counts towards the intrinsic gas a transaction's
+    /// gas limit must cover.
+    #[test]
+    fn evnode_gas_limit_must_cover_access_list_surcharge() {
+        let mut tx = create_non_sponsored_evnode_tx(21_000, 1_000_000_000).strip_signature();
+        tx.access_list = AccessList(vec![
+            AccessListItem {
+                address: Address::repeat_byte(0x01),
+                storage_keys: vec![B256::ZERO; 2],
+            };
+            2
+        ]);
+        // 21000 for the call, 2 * 2400 + 4 * 1900 for the access list and 2 * 1000 + 4 * 100
+        // of surcharge.
+        let intrinsic_gas = 21_000 + 12_400;
+        let surcharged_gas = intrinsic_gas + 2_400;
+
+        let validator = create_test_validator(None)
+            .with_access_list(Some(ev_revm::AccessListSettings::new(2, 2, 1_000, 100, 0)));
+        tx.gas_limit = intrinsic_gas + 1_000;
+        assert!(matches!(
+            validator.validate_evnode_calls(&tx),
+            Err(InvalidPoolTransactionError::IntrinsicGasTooLow)
+        ));
+
+        tx.gas_limit = surcharged_gas;
+        assert!(validator.validate_evnode_calls(&tx).is_ok());
+    }
+
+    /// Tests that only allowlisted fee payers may sponsor transactions.
+    #[test]
+    fn sponsor_rejected_outside_allowlist() {
+        let approved = Address::repeat_byte(0xaa);
+        let other = Address::repeat_byte(0xbb);
+
+        let validator = create_test_validator(None);
+        assert!(validator.check_sponsor_allowed(other).is_ok());
+
+        let validator = validator.with_sponsor_allowlist(Some(
+            ev_revm::SponsorAllowlistSettings::new(vec![approved], 0),
+        ));
+        assert!(validator.check_sponsor_allowed(approved).is_ok());
+        let InvalidPoolTransactionError::Other(err) = validator
+            .check_sponsor_allowed(other)
+            .expect_err("unlisted sponsor must be rejected")
+        else {
+            panic!("expected an EV pool error");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::SponsorNotAllowed(ev_revm::SponsorNotAllowed { sponsor }))
+                if *sponsor == other
+        ));
+        assert!(err.is_bad_transaction());
+    }
+
+    /// Pool holding the sponsors of transactions by sender and nonce.
+    #[derive(Debug, Default)]
+    struct TestSponsoredPool(HashMap<(Address, u64), Address>);
+
+    impl SponsoredPool for TestSponsoredPool {
+        fn pooled_sponsor(&self, sender: Address, nonce: u64) -> Option<Address> {
+            self.0.get(&(sender, nonce)).copied()
+        }
+    }
+
+    /// A sponsored transaction may only replace a pooled one from the same sponsor or an
+    /// unsponsored one.
+    #[test]
+    fn sponsored_replacement_requires_pooled_sponsor() {
+        let sender = Address::repeat_byte(0x01);
+        let sponsor = Address::repeat_byte(0xaa);
+        let other = Address::repeat_byte(0xbb);
+
+        // Before the pool is handed over nothing is bound.
+        let pooled_sponsors = PooledSponsors::default();
+        assert!(pooled_sponsors.check(sender, 0, other).is_ok());
+
+        pooled_sponsors.set(TestSponsoredPool(HashMap::from([((sender, 0), sponsor)])));
+        assert_eq!(
+            pooled_sponsors.check(sender, 0, other),
+            Err(SponsoredReplacement { sponsor })
+        );
+        assert!(pooled_sponsors.check(sender, 0, sponsor).is_ok());
+        assert!(pooled_sponsors.check(sender, 1, other).is_ok());
+    }
+
+    /// The validator rejects a transaction replacing a pooled one paid by another sponsor, and
+    /// records the sponsor of the transactions it admits.
+    #[test]
+    fn validator_rejects_replacement_from_another_sponsor() {
+        let signer = Address::random();
+        let pooled_sponsor = Address::repeat_byte(0xaa);
+        let fee_payer = Address::repeat_byte(0x66);
+        let (mut tx, signature, _) =
+            create_non_sponsored_evnode_tx(21_000, 1_000_000_000).into_parts();
+        tx.fee_payer_signature = Some(ev_primitives::contract_fee_payer_signature(fee_payer));
+        tx.max_sponsor_fee = Some(0);
+        let replacement = create_pooled_tx(Signed::new_unhashed(tx, signature), signer);
+        let sender_balance = *replacement.max_cost();
+        let mut state: Option<Box<dyn AccountInfoReader + Send>> = None;
+
+        let pooled_sponsors = PooledSponsors::default();
+        pooled_sponsors.set(TestSponsoredPool(HashMap::from([(
+            (replacement.sender(), replacement.nonce()),
+            pooled_sponsor,
+        )])));
+        let validator = create_test_validator(None)
+            .with_gas_allowance(Some(ev_revm::GasAllowanceSettings::new(0)))
+            .with_pooled_sponsors(pooled_sponsors);
+        let InvalidPoolTransactionError::Other(err) = validator
+            .validate_evnode(&replacement, sender_balance, &mut state)
+            .expect_err("replacement from another sponsor must be rejected")
+        else {
+            panic!("expected an EV pool error");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::SponsoredReplacement(SponsoredReplacement { sponsor }))
+                if *sponsor == pooled_sponsor
+        ));
+        assert_eq!(replacement.sponsor(), None);
+
+        // Without a conflicting pooled transaction it is admitted under its own sponsor.
+        let validator = create_test_validator(None)
+            .with_gas_allowance(Some(ev_revm::GasAllowanceSettings::new(0)));
+        validator
+            .validate_evnode(&replacement, sender_balance, &mut state)
+            .expect("transaction without a pooled conflict should be accepted");
+        assert_eq!(replacement.sponsor(), Some(fee_payer));
+    }
+
+    /// Tests that the signing domain audit rejects a fee payer slot copying the executor
+    /// signature, and only runs when enabled.
+    #[test]
+    fn signing_domain_audit_rejects_reused_signature() {
+        let mut tx = create_non_sponsored_evnode_tx(21_000, 1_000_000_000).strip_signature();
+        tx.fee_payer_signature = Some(sample_signature());
+        let signed = Signed::new_unhashed(tx, sample_signature());
+        let executor = Address::random();
+
+        let validator = create_test_validator(None);
+        assert!(validator.check_signing_domains(&signed, executor).is_ok());
+
+        let validator = validator.with_signing_domain_audit(true);
+        let InvalidPoolTransactionError::Other(err) = validator
+            .check_signing_domains(&signed, executor)
+            .expect_err("reused signature must be rejected")
+        else {
+            panic!("expected an EV pool error");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::CrossDomainSignature(
+                CrossDomainSignature::ReusedSignature
+            ))
+        ));
+        assert!(err.is_bad_transaction());
+    }
+
+    /// Tests that transactions in a nonce lane are pooled under the lane's account at no cost
+    /// to it, and are only admitted once nonce lanes are active.
+    #[test]
+    fn nonce_lane_transactions_pooled_under_lane_account() {
+        let signer = Address::random();
+        let mut tx = create_non_sponsored_evnode_tx(21_000, 1_000_000_000).strip_signature();
+        tx.nonce_key = U192::from(3);
+        let pooled = create_pooled_tx(Signed::new_unhashed(tx.clone(), sample_signature()), signer);
+        assert!(pooled.in_nonce_lane());
+        assert_eq!(pooled.sender(), nonce_lane_address(signer, U192::from(3)));
+        assert_eq!(pooled.transaction().signer(), signer);
+        assert_eq!(*pooled.cost(), U256::ZERO);
+        assert_eq!(*pooled.max_cost(), U256::from(21_000u64 * 1_000_000_000));
+
+        let lane_error = |validator: &EvTransactionValidator<_, _>, tx: &EvNodeTransaction| {
+            let InvalidPoolTransactionError::Other(err) = validator
+                .validate_evnode_calls(tx)
+                .expect_err("transaction must be rejected")
+            else {
+                panic!("expected an EV pool error");
+            };
+            err
+        };
+
+        let validator = create_test_validator(None);
+        let err = lane_error(&validator, &tx);
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::NonceLanesInactive)
+        ));
+
+        let validator = validator.with_nonce_lanes(Some(ev_revm::NonceLaneSettings::new(0)));
+        assert!(validator.validate_evnode_calls(&tx).is_ok());
+
+        tx.calls[0].to = TxKind::Create;
+        let err = lane_error(&validator, &tx);
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::NonceLaneCreate)
+        ));
+        assert!(err.is_bad_transaction());
+    }
+
+    /// Tests that zero-priced transactions are rejected unless their sender or every call
+    /// target is exempt.
+    #[test]
+    fn min_gas_price_rejects_zero_priced_transactions() {
+        let exempt_sender = Address::repeat_byte(0x01);
+        let validator = create_test_validator(None).with_min_gas_price(Some(
+            ev_revm::MinGasPriceSettings::new(1_000_000_000, vec![exempt_sender], Vec::new(), 0),
+        ));
+
+        let free_priced = create_non_sponsored_evnode_tx(21_000, 0);
+        let err = validator
+            .check_min_gas_price(&create_pooled_tx(free_priced.clone(), Address::random()))
+            .expect_err("zero-priced transaction must be rejected");
+        let InvalidPoolTransactionError::Other(err) = err else {
+            panic!("expected an EV pool error, got: {err:?}");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::GasPriceBelowMinimum(_))
+        ));
+        assert!(validator
+            .check_min_gas_price(&create_pooled_tx(free_priced, exempt_sender))
+            .is_ok());
+
+        // The test transaction calls the zero address.
+        let validator = validator.with_min_gas_price(Some(ev_revm::MinGasPriceSettings::new(
+            1_000_000_000,
+            Vec::new(),
+            vec![Address::ZERO],
+            0,
+        )));
+        assert!(validator
+            .check_min_gas_price(&create_pooled_tx(
+                create_non_sponsored_evnode_tx(21_000, 0),
+                Address::random(),
+            ))
+            .is_ok());
+    }
+
+    /// Tests that transactions may use at most the configured share of the latest block.
+    #[test]
+    fn max_tx_gas_caps_share_of_latest_block() {
+        let validator = create_test_validator(None)
+            .with_max_tx_gas(Some(ev_revm::MaxTxGasSettings::new(5_000, 0)));
+        let block_gas_limit = validator
+            .inner
+            .client()
+            .header_by_number(0)
+            .unwrap()
+            .expect("genesis header")
+            .gas_limit();
+        let pooled = |gas_limit| {
+            create_pooled_tx(
+                create_non_sponsored_evnode_tx(gas_limit, 1_000_000_000),
+                Address::random(),
+            )
+        };
+
+        assert!(validator
+            .check_max_tx_gas(&pooled(block_gas_limit / 2))
+            .is_ok());
+        let err = validator
+            .check_max_tx_gas(&pooled(block_gas_limit / 2 + 1))
+            .expect_err("transaction above half of the block must be rejected");
+        let InvalidPoolTransactionError::Other(err) = err else {
+            panic!("expected an EV pool error, got: {err:?}");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::TxGasAboveMaximum(_))
+        ));
+        assert!(!err.is_bad_transaction());
+    }
+
+    /// Tests that the minimum priority fee is only enforced on external transactions while the
+    /// latest block is at or above the gas target.
+    #[test]
+    fn minimum_priority_fee_waived_below_gas_target() {
+        // The test transaction tips 1 wei, and the genesis block uses no gas.
+        let pooled = create_pooled_tx(
+            create_non_sponsored_evnode_tx(21_000, 1_000_000_000),
+            Address::random(),
+        );
+
+        let validator = create_test_validator(None).with_gas_target(Some(5), Some(0));
+        let err = validator
+            .check_priority_fee(TransactionOrigin::External, &pooled)
+            .expect_err("tip below the minimum must be rejected at the gas target");
+        let InvalidPoolTransactionError::Other(err) = err else {
+            panic!("expected an EV pool error, got: {err:?}");
+        };
+        assert!(matches!(
+            err.as_any().downcast_ref::<EvTxPoolError>(),
+            Some(EvTxPoolError::PriorityFeeBelowMinimum(5))
+        ));
+        assert!(validator
+            .check_priority_fee(TransactionOrigin::Local, &pooled)
+            .is_ok());
+
+        let validator = validator.with_gas_target(Some(5), Some(1));
+        assert!(validator
+            .check_priority_fee(TransactionOrigin::External, &pooled)
+            .is_ok());
+    }
+
     /// Tests that non-sponsored `EvNode` transactions with sufficient balance are accepted.
     #[test]
     fn non_sponsored_evnode_accepts_sufficient_balance() {
@@ -857,6 +2530,7 @@ mod tests {
         let tx = EvNodeTransaction {
             chain_id: 1,
             nonce: 0,
+            nonce_key: U192::ZERO,
             max_priority_fee_per_gas: 1,
             max_fee_per_gas,
             gas_limit,
@@ -864,9 +2538,16 @@ mod tests {
                 to: TxKind::Call(Address::ZERO),
                 value: call_value,
                 input: Bytes::new(),
+                gas_limit: None,
             }],
             access_list: AccessList::default(),
+            revert_mode: RevertMode::Atomic,
+            valid_after: None,
+            valid_until: None,
+            session: None,
             fee_payer_signature: None,
+            max_sponsor_fee: None,
+            p256_signature: None,
         };
         let signed_tx = Signed::new_unhashed(tx, sample_signature());
 
@@ -892,6 +2573,67 @@ mod tests {
         }
     }
 
+    /// Tests that contract fee payer markers are only accepted once the gas allowance activates.
+    #[test]
+    fn contract_fee_payer_requires_gas_allowance() {
+        let gas_limit = 21_000u64;
+        let max_fee_per_gas = 1_000_000_000u128;
+        let fee_payer = Address::from([0x66u8; 20]);
+        let mut signed_tx = create_non_sponsored_evnode_tx(gas_limit, max_fee_per_gas);
+        let (mut tx, signature, _) = signed_tx.into_parts();
+        tx.fee_payer_signature = Some(ev_primitives::contract_fee_payer_signature(fee_payer));
+        signed_tx = Signed::new_unhashed(tx, signature);
+
+        let pooled = create_pooled_tx(signed_tx, Address::random());
+        let mut state: Option<Box<dyn AccountInfoReader + Send>> = None;
+
+        let validator = create_test_validator(None);
+        let err = validator
+            .validate_evnode(&pooled, U256::ZERO, &mut state)
+            .expect_err("marker must be rejected without gas allowance");
+        assert!(matches!(err, InvalidPoolTransactionError::Other(_)));
+
+        // Once active, the contract pays like any sponsor and must cover the max gas cost.
+        let validator = create_test_validator(None)
+            .with_gas_allowance(Some(ev_revm::GasAllowanceSettings::new(0)));
+        let err = validator
+            .validate_evnode(&pooled, U256::ZERO, &mut state)
+            .expect_err("unfunded contract fee payer must be rejected");
+        assert!(matches!(err, InvalidPoolTransactionError::Overdraft { .. }));
+    }
+
+    /// Tests that gas above the sponsor's `max_sponsor_fee` is checked against the executor.
+    #[test]
+    fn capped_sponsor_requires_executor_to_cover_excess() {
+        let gas_limit = 21_000u64;
+        let max_fee_per_gas = 1_000_000_000u128;
+        let max_gas_cost = U256::from(gas_limit) * U256::from(max_fee_per_gas);
+        let fee_payer = Address::from([0x66u8; 20]);
+        let mut signed_tx = create_non_sponsored_evnode_tx(gas_limit, max_fee_per_gas);
+        let (mut tx, signature, _) = signed_tx.into_parts();
+        tx.fee_payer_signature = Some(ev_primitives::contract_fee_payer_signature(fee_payer));
+        // The unfunded contract pays nothing, so the executor owes the whole max gas cost.
+        tx.max_sponsor_fee = Some(0);
+        signed_tx = Signed::new_unhashed(tx, signature);
+
+        let pooled = create_pooled_tx(signed_tx, Address::random());
+        let mut state: Option<Box<dyn AccountInfoReader + Send>> = None;
+        let validator = create_test_validator(None)
+            .with_gas_allowance(Some(ev_revm::GasAllowanceSettings::new(0)));
+
+        let err = validator
+            .validate_evnode(&pooled, max_gas_cost - U256::from(1), &mut state)
+            .expect_err("executor must cover gas above the sponsor cap");
+        assert!(matches!(
+            err,
+            InvalidPoolTransactionError::Overdraft { cost, .. } if cost == max_gas_cost
+        ));
+
+        validator
+            .validate_evnode(&pooled, max_gas_cost, &mut state)
+            .expect("executor covering the excess should be accepted");
+    }
+
     /// Tests pool-level deploy allowlist rejection for `EvNode` CREATE when caller not allowlisted.
     #[test]
     fn evnode_create_rejected_when_not_allowlisted() {
@@ -917,6 +2659,38 @@ mod tests {
         }
     }
 
+    /// A leading CREATE whose initcode exceeds twice the configured contract size limit is
+    /// rejected before admission.
+    #[test]
+    fn evnode_create_rejects_oversized_initcode() {
+        let gas_limit = 200_000u64;
+        let max_fee_per_gas = 1_000_000_000u128;
+        let signer = Address::random();
+        let pooled = create_pooled_tx(
+            create_non_sponsored_evnode_create_tx(gas_limit, max_fee_per_gas),
+            signer,
+        );
+        let sender_balance = *pooled.cost() + U256::from(1);
+        let mut state: Option<Box<dyn AccountInfoReader + Send>> = None;
+
+        // The 5-byte initcode is over the 4-byte bound derived from a 2-byte limit.
+        let validator = create_test_validator(None)
+            .with_contract_size_limit(Some(ev_revm::ContractSizeLimitSettings::new(2, 0)));
+        let err = validator
+            .validate_evnode(&pooled, sender_balance, &mut state)
+            .expect_err("oversized initcode should be rejected");
+        assert!(matches!(
+            err,
+            InvalidPoolTransactionError::ExceedsMaxInitCodeSize(5, 4)
+        ));
+
+        let validator = create_test_validator(None)
+            .with_contract_size_limit(Some(ev_revm::ContractSizeLimitSettings::new(2, 1)));
+        assert!(validator
+            .validate_evnode(&pooled, sender_balance, &mut state)
+            .is_ok());
+    }
+
     #[test]
     fn validate_evnode_span_has_expected_fields() {
         use crate::test_utils::SpanCollector;
@@ -1,12 +1,13 @@
 #![allow(missing_docs, rustdoc::missing_crate_level_docs)]
 
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use crate::tracing_ext::RecordDurationOnDrop;
-use alloy_consensus::Header;
+use alloy_consensus::{BlockHeader, Header};
 use alloy_eips::Decodable2718;
+use alloy_primitives::B256;
 use alloy_rpc_types::engine::ExecutionData;
-use ev_primitives::{Block as EvBlock, BlockBody as EvBlockBody, EvTxEnvelope};
+use ev_primitives::{Block as EvBlock, BlockBody as EvBlockBody, EvNodeTxTypeAlias, EvTxEnvelope};
 use reth_ethereum::{
     chainspec::ChainSpec,
     node::{
@@ -21,14 +22,168 @@ use reth_ethereum::{
 };
 use reth_ethereum_payload_builder::EthereumExecutionPayloadValidator;
 use reth_primitives_traits::{Block as _, RecoveredBlock, SealedBlock};
-use tracing::{debug, info, instrument, Span};
+use reth_provider::{HeaderProvider, ProviderResult};
+use thiserror::Error;
+use tracing::{debug, info, instrument, warn, Span};
 
-use crate::{attributes::EvolveEnginePayloadAttributes, node::EvolveEngineTypes};
+use crate::{
+    attributes::EvolveEnginePayloadAttributes, config::ResolvedEvolveConfig,
+    executor::EvolveEvmConfig, node::EvolveEngineTypes,
+};
+use evolve_ev_reth::TimestampPolicy;
+
+/// Basis points representing a 100% gas limit change.
+const MAX_GAS_LIMIT_CHANGE_BPS: u16 = 10_000;
+
+/// A payload gas limit outside the configured bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum GasLimitBoundsError {
+    /// The gas limit is below the configured minimum.
+    #[error("gas limit {gas_limit} is below the minimum of {min}")]
+    BelowMinimum { gas_limit: u64, min: u64 },
+    /// The gas limit is above the configured maximum.
+    #[error("gas limit {gas_limit} is above the maximum of {max}")]
+    AboveMaximum { gas_limit: u64, max: u64 },
+    /// The gas limit moved further from the parent's than the permitted per-block delta.
+    #[error(
+        "gas limit {gas_limit} changes parent gas limit {parent_gas_limit} by more than \
+         {max_change_bps} bps"
+    )]
+    ChangeTooLarge {
+        gas_limit: u64,
+        parent_gas_limit: u64,
+        max_change_bps: u16,
+    },
+}
+
+/// A sponsored EvNode transaction in a payload whose fee-payer signature does not recover to
+/// a sponsor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("transaction {index} ({hash}) carries an invalid fee payer signature")]
+pub struct InvalidFeePayerSignature {
+    /// Position of the transaction in the block.
+    pub index: usize,
+    /// Hash of the transaction.
+    pub hash: B256,
+}
+
+/// Checks that every sponsored EvNode transaction of `block` has a fee-payer signature that
+/// recovers to a sponsor for its executor.
+///
+/// Execution rejects such transactions too, but checking here turns a block from a sequencer
+/// that bypassed the txpool into an `INVALID` payload before anything is executed, naming the
+/// offending transaction. Contract fee payer markers carry no signature and are left to the
+/// executor, which checks them against the gas allowance activation.
+pub fn check_fee_payer_signatures(
+    block: &RecoveredBlock<EvBlock>,
+) -> Result<(), InvalidFeePayerSignature> {
+    for (index, (executor, tx)) in block.transactions_with_sender().enumerate() {
+        let EvTxEnvelope::EvNode(ev) = tx else {
+            continue;
+        };
+        let Some(signature) = ev.tx().fee_payer_signature.as_ref() else {
+            continue;
+        };
+        if ev.tx().contract_fee_payer().is_none()
+            && ev.tx().recover_sponsor(*executor, signature).is_err()
+        {
+            return Err(InvalidFeePayerSignature {
+                index,
+                hash: *ev.hash(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Chainspec-configured bounds on the gas limit of incoming payloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasLimitBounds {
+    min: u64,
+    max: u64,
+    max_change_bps: Option<u16>,
+    activation_height: u64,
+}
+
+impl GasLimitBounds {
+    /// Creates bounds enforced from `activation_height`. `max_change_bps` limits how far a
+    /// block's gas limit may move from its parent's, in basis points of the parent's.
+    pub const fn new(
+        min: u64,
+        max: u64,
+        max_change_bps: Option<u16>,
+        activation_height: u64,
+    ) -> Self {
+        Self {
+            min,
+            max,
+            max_change_bps,
+            activation_height,
+        }
+    }
+
+    /// Checks the gas limit of a block, with the parent's gas limit if it is known.
+    pub fn check(
+        &self,
+        block_number: u64,
+        gas_limit: u64,
+        parent_gas_limit: Option<u64>,
+    ) -> Result<(), GasLimitBoundsError> {
+        if block_number < self.activation_height {
+            return Ok(());
+        }
+        if gas_limit < self.min {
+            return Err(GasLimitBoundsError::BelowMinimum {
+                gas_limit,
+                min: self.min,
+            });
+        }
+        if gas_limit > self.max {
+            return Err(GasLimitBoundsError::AboveMaximum {
+                gas_limit,
+                max: self.max,
+            });
+        }
+        if let (Some(max_change_bps), Some(parent_gas_limit)) =
+            (self.max_change_bps, parent_gas_limit)
+        {
+            let allowed = u128::from(parent_gas_limit) * u128::from(max_change_bps)
+                / u128::from(MAX_GAS_LIMIT_CHANGE_BPS);
+            if u128::from(gas_limit.abs_diff(parent_gas_limit)) > allowed {
+                return Err(GasLimitBoundsError::ChangeTooLarge {
+                    gas_limit,
+                    parent_gas_limit,
+                    max_change_bps,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Looks up parent gas limits for the per-block delta check.
+pub trait ParentGasLimits: std::fmt::Debug + Send + Sync + 'static {
+    /// Returns the gas limit of the block with `hash`, if the block is known.
+    fn gas_limit(&self, hash: B256) -> ProviderResult<Option<u64>>;
+}
+
+impl<P> ParentGasLimits for P
+where
+    P: HeaderProvider + std::fmt::Debug + Send + Sync + 'static,
+{
+    fn gas_limit(&self, hash: B256) -> ProviderResult<Option<u64>> {
+        Ok(self.header(hash)?.map(|header| header.gas_limit()))
+    }
+}
 
 /// Evolve engine validator that handles custom payload validation.
 #[derive(Debug, Clone)]
 pub struct EvolveEngineValidator {
     inner: EthereumExecutionPayloadValidator<ChainSpec>,
+    gas_limit_bounds: Option<GasLimitBounds>,
+    parent_gas_limits: Option<Arc<dyn ParentGasLimits>>,
+    evnode_tx_type_alias: Option<EvNodeTxTypeAlias>,
+    timestamp_policy: TimestampPolicy,
 }
 
 impl EvolveEngineValidator {
@@ -36,7 +191,69 @@ impl EvolveEngineValidator {
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
         Self {
             inner: EthereumExecutionPayloadValidator::new(chain_spec),
+            gas_limit_bounds: None,
+            parent_gas_limits: None,
+            evnode_tx_type_alias: None,
+            timestamp_policy: TimestampPolicy::Grace,
+        }
+    }
+
+    /// Instantiates a validator enforcing the payload rules of `config`, resolving parent gas
+    /// limits through `parents`.
+    ///
+    /// The engine API and the payload debug API both build their validator here, so they
+    /// accept the same payloads.
+    pub fn from_config(
+        chain_spec: Arc<ChainSpec>,
+        config: &ResolvedEvolveConfig,
+        parents: Arc<dyn ParentGasLimits>,
+    ) -> Self {
+        let mut validator = Self::new(chain_spec);
+        if let Some((min, max, max_change_bps, activation)) = config.gas_limit_bounds_settings() {
+            info!(
+                min,
+                max,
+                ?max_change_bps,
+                activation_height = activation,
+                "Payload gas limit bounds enabled"
+            );
+            validator = validator
+                .with_gas_limit_bounds(GasLimitBounds::new(min, max, max_change_bps, activation))
+                .with_parent_gas_limits(parents);
         }
+        if let Some(alias) = config.evnode_tx_type_alias() {
+            info!(
+                alias = alias.alias(),
+                activation_height = alias.activation_height(),
+                "EvNode transaction type alias enabled"
+            );
+            validator = validator.with_evnode_tx_type_alias(alias);
+        }
+        validator.with_timestamp_policy(config.node.timestamp_policy)
+    }
+
+    /// Rejects payloads whose gas limit falls outside `bounds`.
+    pub const fn with_gas_limit_bounds(mut self, bounds: GasLimitBounds) -> Self {
+        self.gas_limit_bounds = Some(bounds);
+        self
+    }
+
+    /// Resolves parent gas limits through `parents`, enabling the per-block delta check.
+    pub fn with_parent_gas_limits(mut self, parents: Arc<dyn ParentGasLimits>) -> Self {
+        self.parent_gas_limits = Some(parents);
+        self
+    }
+
+    /// Decodes payload transactions typed `alias` as EvNode transactions once it is active.
+    pub const fn with_evnode_tx_type_alias(mut self, alias: EvNodeTxTypeAlias) -> Self {
+        self.evnode_tx_type_alias = Some(alias);
+        self
+    }
+
+    /// Refuses payload attributes whose timestamp `policy` would refuse at build time.
+    pub const fn with_timestamp_policy(mut self, policy: TimestampPolicy) -> Self {
+        self.timestamp_policy = policy;
+        self
     }
 
     /// Returns the chain spec used by the validator.
@@ -44,6 +261,32 @@ impl EvolveEngineValidator {
     fn chain_spec(&self) -> &ChainSpec {
         self.inner.chain_spec().as_ref()
     }
+
+    /// Rejects a payload whose gas limit is out of bounds or moves too far from its parent's.
+    ///
+    /// The delta check is skipped when the parent is unknown; such a payload cannot be
+    /// executed yet anyway.
+    fn check_gas_limit(&self, payload: &ExecutionData) -> Result<(), NewPayloadError> {
+        let Some(bounds) = self.gas_limit_bounds else {
+            return Ok(());
+        };
+        let parent_gas_limit = match &self.parent_gas_limits {
+            Some(parents) => parents
+                .gas_limit(payload.payload.parent_hash())
+                .map_err(NewPayloadError::other)?,
+            None => None,
+        };
+        bounds
+            .check(
+                payload.payload.block_number(),
+                payload.payload.gas_limit(),
+                parent_gas_limit,
+            )
+            .map_err(|err| {
+                warn!(error = %err, "rejecting payload with out-of-bounds gas limit");
+                NewPayloadError::other(err)
+            })
+    }
 }
 
 impl PayloadValidator<EvolveEngineTypes> for EvolveEngineValidator {
@@ -69,6 +312,7 @@ impl PayloadValidator<EvolveEngineTypes> for EvolveEngineValidator {
         payload: ExecutionData,
     ) -> Result<RecoveredBlock<Self::Block>, NewPayloadError> {
         let _duration = RecordDurationOnDrop::new();
+        self.check_gas_limit(&payload)?;
         // Use inner validator but with custom evolve handling.
         match self.inner.ensure_well_formed_payload(payload.clone()) {
             Ok(sealed_block) => {
@@ -98,11 +342,16 @@ impl PayloadValidator<EvolveEngineTypes> for EvolveEngineValidator {
                 if should_bypass {
                     info!(error = ?err, "bypassing validation error for ev-reth");
                     // For evolve, we trust the payload builder - parse the block with EvNode support.
-                    let ev_block = parse_evolve_payload(payload)?;
+                    let ev_block = parse_evolve_payload(payload, self.evnode_tx_type_alias)?;
                     Span::current().record("block_hash", tracing::field::display(ev_block.hash()));
-                    ev_block
+                    let block = ev_block
                         .try_recover()
-                        .map_err(|e| NewPayloadError::Other(e.into()))
+                        .map_err(|e| NewPayloadError::Other(e.into()))?;
+                    check_fee_payer_signatures(&block).map_err(|err| {
+                        warn!(error = %err, "rejecting payload with an invalid sponsor signature");
+                        NewPayloadError::other(err)
+                    })?;
+                    Ok(block)
                 } else {
                     // For other errors, re-throw them.
                     Err(NewPayloadError::Eth(err))
@@ -113,11 +362,16 @@ impl PayloadValidator<EvolveEngineTypes> for EvolveEngineValidator {
 
     fn validate_payload_attributes_against_header(
         &self,
-        _attr: &EvolveEnginePayloadAttributes,
-        _header: &<Self::Block as reth_primitives_traits::Block>::Header,
+        attr: &EvolveEnginePayloadAttributes,
+        header: &<Self::Block as reth_primitives_traits::Block>::Header,
     ) -> Result<(), InvalidPayloadAttributesError> {
-        // Skip default timestamp validation for evolve.
-        Ok(())
+        // Evolve allows timestamps equal to the parent's, so the default check does not apply.
+        // Refuse up front what the timestamp policy would refuse at build time, rather than
+        // hand out a payload id that never yields a payload.
+        self.timestamp_policy
+            .resolve(attr.inner.timestamp, header.timestamp)
+            .map(|_| ())
+            .map_err(|_| InvalidPayloadAttributesError::InvalidTimestamp)
     }
 }
 
@@ -130,23 +384,29 @@ fn convert_sealed_block(
 }
 
 /// Parses an execution payload containing `EvNode` transactions.
-#[instrument(skip(payload), fields(
+#[instrument(skip(payload, tx_type_alias), fields(
     tx_count = payload.payload.transactions().len(),
     block_number = payload.payload.block_number(),
     duration_ms = tracing::field::Empty,
 ))]
 fn parse_evolve_payload(
     payload: ExecutionData,
+    tx_type_alias: Option<EvNodeTxTypeAlias>,
 ) -> Result<SealedBlock<ev_primitives::Block>, NewPayloadError> {
     let _duration = RecordDurationOnDrop::new();
     let ExecutionData { payload, sidecar } = payload;
 
-    // Parse transactions using EvTxEnvelope which supports both Ethereum and EvNode types.
+    // Parse transactions using EvTxEnvelope which supports both Ethereum and EvNode types,
+    // mapping the chain's EvNode type alias first.
+    let block_number = payload.block_number();
     let transactions: Vec<EvTxEnvelope> = payload
         .transactions()
         .iter()
         .map(|tx| {
-            EvTxEnvelope::decode_2718(&mut tx.as_ref())
+            let raw = tx_type_alias.map_or(Cow::Borrowed(tx.as_ref()), |alias| {
+                alias.canonicalize(tx.as_ref(), block_number)
+            });
+            EvTxEnvelope::decode_2718(&mut raw.as_ref())
                 .map_err(|e| NewPayloadError::Other(Box::new(e)))
         })
         .collect::<Result<Vec<_>, _>>()?;
@@ -240,12 +500,22 @@ where
             ChainSpec = ChainSpec,
             Primitives = ev_primitives::EvPrimitives,
         >,
+        Evm = EvolveEvmConfig,
     >,
 {
     type Validator = EvolveEngineValidator;
 
     async fn build(self, ctx: &AddOnsContext<'_, N>) -> eyre::Result<Self::Validator> {
-        Ok(EvolveEngineValidator::new(ctx.config.chain.clone()))
+        let config = ctx
+            .node
+            .evm_config()
+            .evolve_config()
+            .ok_or_else(|| eyre::eyre!("EVM config was built without the evolve config"))?;
+        Ok(EvolveEngineValidator::from_config(
+            ctx.config.chain.clone(),
+            config,
+            Arc::new(ctx.node.provider().clone()),
+        ))
     }
 }
 
@@ -372,6 +642,208 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gas_limit_bounds_reject_out_of_range_and_large_changes() {
+        let bounds = GasLimitBounds::new(10_000_000, 100_000_000, Some(1_000), 5);
+
+        // Not enforced before activation.
+        assert!(bounds.check(4, 1, Some(30_000_000)).is_ok());
+        assert_eq!(
+            bounds.check(5, 9_999_999, None),
+            Err(GasLimitBoundsError::BelowMinimum {
+                gas_limit: 9_999_999,
+                min: 10_000_000
+            })
+        );
+        assert!(matches!(
+            bounds.check(5, 100_000_001, None),
+            Err(GasLimitBoundsError::AboveMaximum { .. })
+        ));
+
+        // A 10% change is the most allowed either way.
+        assert!(bounds.check(5, 33_000_000, Some(30_000_000)).is_ok());
+        assert!(bounds.check(5, 27_000_000, Some(30_000_000)).is_ok());
+        assert!(matches!(
+            bounds.check(5, 15_000_000, Some(30_000_000)),
+            Err(GasLimitBoundsError::ChangeTooLarge { .. })
+        ));
+        // Without a known parent only the absolute bounds apply.
+        assert!(bounds.check(5, 15_000_000, None).is_ok());
+    }
+
+    #[test]
+    fn ensure_well_formed_payload_rejects_out_of_bounds_gas_limit() {
+        use alloy_primitives::{Address, Bloom, Bytes, B256, U256};
+        use alloy_rpc_types::engine::{
+            ExecutionData, ExecutionPayload, ExecutionPayloadSidecar, ExecutionPayloadV1,
+        };
+        use reth_chainspec::ChainSpecBuilder;
+
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(reth_chainspec::Chain::from_id(1234))
+                .genesis(
+                    serde_json::from_str(include_str!("../../tests/assets/genesis.json"))
+                        .expect("valid genesis"),
+                )
+                .build(),
+        );
+        let config = ResolvedEvolveConfig::new(crate::config::EvolvePayloadBuilderConfig {
+            min_block_gas_limit: Some(60_000_000),
+            ..Default::default()
+        })
+        .expect("valid config");
+        let validator = EvolveEngineValidator::from_config(
+            chain_spec,
+            &config,
+            Arc::new(reth_provider::test_utils::MockEthProvider::default()),
+        );
+
+        let payload = ExecutionPayload::V1(ExecutionPayloadV1 {
+            parent_hash: B256::ZERO,
+            fee_recipient: Address::ZERO,
+            state_root: B256::ZERO,
+            receipts_root: B256::ZERO,
+            logs_bloom: Bloom::ZERO,
+            prev_randao: B256::ZERO,
+            block_number: 1,
+            gas_limit: 30_000_000,
+            gas_used: 0,
+            timestamp: 1710338136,
+            extra_data: Bytes::default(),
+            base_fee_per_gas: U256::ZERO,
+            block_hash: B256::ZERO,
+            transactions: vec![],
+        });
+        let execution_data = ExecutionData::new(payload, ExecutionPayloadSidecar::default());
+
+        let err = PayloadValidator::ensure_well_formed_payload(&validator, execution_data)
+            .expect_err("gas limit below the minimum must be rejected");
+        assert!(err.to_string().contains("below the minimum"), "{err}");
+    }
+
+    /// A sequencer that skips the txpool cannot get a sponsored transaction with a forged
+    /// fee-payer signature past `new_payload`.
+    #[test]
+    fn ensure_well_formed_payload_rejects_invalid_fee_payer_signature() {
+        use alloy_consensus::transaction::TxHashRef;
+        use alloy_eips::eip2718::Encodable2718;
+        use alloy_primitives::{Address, Bloom, Bytes, Signature, B256, U256};
+        use alloy_rpc_types::engine::{
+            ExecutionData, ExecutionPayload, ExecutionPayloadSidecar, ExecutionPayloadV1,
+        };
+        use alloy_signer::SignerSync;
+        use alloy_signer_local::PrivateKeySigner;
+        use ev_primitives::{Call, EvNodeTransactionBuilder};
+        use reth_chainspec::ChainSpecBuilder;
+
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(reth_chainspec::Chain::from_id(1234))
+                .genesis(
+                    serde_json::from_str(include_str!("../../tests/assets/genesis.json"))
+                        .expect("valid genesis"),
+                )
+                .build(),
+        );
+        let validator = EvolveEngineValidator::new(chain_spec);
+        let executor = PrivateKeySigner::random();
+        let sponsor = PrivateKeySigner::random();
+        let builder = EvNodeTransactionBuilder::new(1234)
+            .gas_limit(100_000)
+            .max_fee_per_gas(2)
+            .call(Call::transfer(Address::repeat_byte(0x0b), U256::ZERO));
+
+        let payload = |tx: EvTxEnvelope| {
+            ExecutionData::new(
+                ExecutionPayload::V1(ExecutionPayloadV1 {
+                    parent_hash: B256::ZERO,
+                    fee_recipient: Address::ZERO,
+                    state_root: B256::ZERO,
+                    receipts_root: B256::ZERO,
+                    logs_bloom: Bloom::ZERO,
+                    prev_randao: B256::ZERO,
+                    block_number: 1,
+                    gas_limit: 30_000_000,
+                    gas_used: 0,
+                    timestamp: 1710338136,
+                    extra_data: Bytes::default(),
+                    base_fee_per_gas: U256::ZERO,
+                    block_hash: B256::ZERO,
+                    transactions: vec![tx.encoded_2718().into()],
+                }),
+                ExecutionPayloadSidecar::default(),
+            )
+        };
+
+        let sponsored = builder
+            .clone()
+            .sign_sponsor(executor.address(), &sponsor)
+            .unwrap()
+            .sign_executor(&executor)
+            .unwrap();
+        let block = PayloadValidator::ensure_well_formed_payload(&validator, payload(sponsored))
+            .expect("validly sponsored payload");
+        assert_eq!(block.senders(), [executor.address()]);
+
+        // `r` is not a valid scalar, so the signature recovers to no sponsor at all.
+        let mut forged = builder.build();
+        forged.fee_payer_signature = Some(Signature::new(U256::MAX, U256::from(1), false));
+        let signature = executor
+            .sign_hash_sync(&forged.executor_signing_hash())
+            .unwrap();
+        let forged = EvTxEnvelope::EvNode(alloy_consensus::Signed::new_unhashed(forged, signature));
+        let hash = *forged.tx_hash();
+
+        let err = PayloadValidator::ensure_well_formed_payload(&validator, payload(forged))
+            .expect_err("forged sponsor signature must be rejected");
+        assert!(
+            err.to_string().contains(&format!(
+                "transaction 0 ({hash}) carries an invalid fee payer signature"
+            )),
+            "{err}"
+        );
+    }
+
+    /// Attributes may repeat the head's timestamp but not go back before it.
+    #[test]
+    fn payload_attributes_timestamp_must_not_regress() {
+        use reth_chainspec::ChainSpecBuilder;
+
+        let validator = EvolveEngineValidator::new(Arc::new(ChainSpecBuilder::mainnet().build()));
+        let header = Header {
+            timestamp: 100,
+            ..Default::default()
+        };
+        let attributes = |timestamp: u64| -> EvolveEnginePayloadAttributes {
+            serde_json::from_value(serde_json::json!({
+                "timestamp": format!("{timestamp:#x}"),
+                "prevRandao": B256::ZERO,
+                "suggestedFeeRecipient": alloy_primitives::Address::ZERO,
+            }))
+            .expect("valid attributes")
+        };
+
+        for timestamp in [100, 101] {
+            assert!(
+                PayloadValidator::validate_payload_attributes_against_header(
+                    &validator,
+                    &attributes(timestamp),
+                    &header
+                )
+                .is_ok()
+            );
+        }
+        assert!(matches!(
+            PayloadValidator::validate_payload_attributes_against_header(
+                &validator,
+                &attributes(99),
+                &header
+            ),
+            Err(InvalidPayloadAttributesError::InvalidTimestamp)
+        ));
+    }
+
     /// Verifies that `is_unknown_tx_type_error` correctly identifies decode errors
     /// with the expected message.
     #[test]
@@ -446,7 +918,7 @@ mod tests {
         let execution_data = ExecutionData::new(payload, sidecar);
 
         // we only care that the span was created with the right fields.
-        let _ = parse_evolve_payload(execution_data);
+        let _ = parse_evolve_payload(execution_data, None);
 
         let span = collector
             .find_span("parse_evolve_payload")
@@ -11,7 +11,7 @@ use alloy_primitives::{Address, Bytes, ChainId, Signature, TxKind, B256, U256};
 use ev_primitives::{EvTxEnvelope, TransactionSigned};
 use ev_revm::{
     BaseFeeRedirect, BaseFeeRedirectSettings, ContractSizeLimitSettings, DeployAllowlistSettings,
-    EvTxEvmFactory, MintPrecompileSettings,
+    EvHandlerSettings, EvTxEvmFactory, MintPrecompileSettings,
 };
 use eyre::Result;
 use reth_chainspec::{ChainSpec, ChainSpecBuilder};
@@ -66,24 +66,36 @@ fn create_test_chain_spec_with_extras(
     mint_admin: Option<Address>,
     deploy_allowlist: Option<Vec<Address>>,
 ) -> Arc<ChainSpec> {
+    if base_fee_sink.is_none() && mint_admin.is_none() && deploy_allowlist.is_none() {
+        return build_test_chain_spec(None);
+    }
+
+    let mut extras = serde_json::Map::new();
+    if let Some(sink) = base_fee_sink {
+        extras.insert("baseFeeSink".to_string(), json!(sink));
+    }
+    if let Some(admin) = mint_admin {
+        extras.insert("mintAdmin".to_string(), json!(admin));
+    }
+    if let Some(allowlist) = deploy_allowlist {
+        extras.insert("deployAllowlist".to_string(), json!(allowlist));
+    }
+    build_test_chain_spec(Some(serde_json::Value::Object(extras)))
+}
+
+/// Creates a reusable chain specification with the given `evolve` chainspec extras.
+pub fn create_test_chain_spec_with_evolve_extras(extras: serde_json::Value) -> Arc<ChainSpec> {
+    build_test_chain_spec(Some(extras))
+}
+
+fn build_test_chain_spec(extras: Option<serde_json::Value>) -> Arc<ChainSpec> {
     let mut genesis: Genesis =
         serde_json::from_str(include_str!("../assets/genesis.json")).expect("valid genesis");
-
-    if base_fee_sink.is_some() || mint_admin.is_some() || deploy_allowlist.is_some() {
-        let mut extras = serde_json::Map::new();
-        if let Some(sink) = base_fee_sink {
-            extras.insert("baseFeeSink".to_string(), json!(sink));
-        }
-        if let Some(admin) = mint_admin {
-            extras.insert("mintAdmin".to_string(), json!(admin));
-        }
-        if let Some(allowlist) = deploy_allowlist {
-            extras.insert("deployAllowlist".to_string(), json!(allowlist));
-        }
+    if let Some(extras) = extras {
         genesis
             .config
             .extra_fields
-            .insert("evolve".to_string(), serde_json::Value::Object(extras));
+            .insert("evolve".to_string(), extras);
     }
 
     Arc::new(
@@ -158,7 +170,8 @@ impl EvolveTestFixture {
         let base_fee_redirect = config
             .base_fee_redirect_settings()
             .map(|(sink, activation)| {
-                BaseFeeRedirectSettings::new(BaseFeeRedirect::new(sink), activation)
+                let redirect = BaseFeeRedirect::new(sink).with_burn_bps(config.base_fee_burn_bps());
+                BaseFeeRedirectSettings::new(redirect, activation)
             });
         let mint_precompile = config
             .mint_precompile_settings()
@@ -169,12 +182,12 @@ impl EvolveTestFixture {
         let deploy_allowlist = config
             .deploy_allowlist_settings()
             .map(|(allowlist, activation)| DeployAllowlistSettings::new(allowlist, activation));
-        let evm_factory = EvTxEvmFactory::new(
-            base_fee_redirect,
-            mint_precompile,
-            deploy_allowlist,
-            contract_size_limit,
-        );
+        let evm_factory =
+            EvTxEvmFactory::new(base_fee_redirect, mint_precompile, contract_size_limit)
+                .with_handler_settings(EvHandlerSettings {
+                    deploy_allowlist,
+                    ..Default::default()
+                });
         let wrapped_evm = EvolveEvmConfig::new_with_evm_factory(test_chainspec, evm_factory);
 
         let builder = EvolvePayloadBuilder::new(Arc::new(provider.clone()), wrapped_evm, config);
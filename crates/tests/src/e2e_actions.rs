@@ -0,0 +1,364 @@
+//! Reusable testsuite actions for EvNode (0x76) transactions.
+//!
+//! The submit actions sign a batch, include it in a block through
+//! [`build_block_with_transactions`] and record it in a [`SubmittedTx`] handle, which the
+//! assert actions then check the receipt and balances against.
+
+use std::sync::{Arc, Mutex};
+
+use alloy_eips::{eip2718::Encodable2718, eip2930::AccessList, BlockNumberOrTag};
+use alloy_network::ReceiptResponse;
+use alloy_primitives::{aliases::U192, Address, Bytes, B256, U256, U64};
+use alloy_rpc_types::{
+    eth::{Block, Header, Receipt, Transaction, TransactionRequest},
+    BlockId,
+};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use eyre::{eyre, Result};
+use futures::future::BoxFuture;
+use reth_e2e_test_utils::testsuite::{actions::Action, Environment};
+use reth_rpc_api::clients::EthApiClient;
+
+use crate::e2e_tests::build_block_with_transactions;
+use ev_node::{
+    rpc::{EvRpcReceipt, EvRpcTransaction, EvTransactionRequest},
+    EvolveEngineTypes,
+};
+use ev_primitives::{Call, EvNodeTransaction, EvTxEnvelope, RevertMode};
+
+/// Gas limit of submitted batches.
+const GAS_LIMIT: u64 = 500_000;
+
+/// Handle to the EvNode transaction of a submit action, shared with the assert actions.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SubmittedTx(Arc<Mutex<Option<SubmittedTxInfo>>>);
+
+/// A submitted EvNode transaction and the balances before its block.
+#[derive(Debug, Clone)]
+struct SubmittedTxInfo {
+    hash: B256,
+    executor: Address,
+    sponsor: Option<Address>,
+    call_values: Vec<U256>,
+    executor_balance: U256,
+    sponsor_balance: U256,
+}
+
+impl SubmittedTx {
+    fn info(&self) -> Result<SubmittedTxInfo> {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .ok_or_else(|| eyre!("no EvNode transaction submitted yet"))
+    }
+
+    fn set(&self, info: SubmittedTxInfo) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(info);
+    }
+}
+
+/// Submits an unsponsored EvNode batch in a new block.
+#[derive(Debug)]
+pub(crate) struct SubmitBatchTx {
+    executor: PrivateKeySigner,
+    sponsor: Option<PrivateKeySigner>,
+    calls: Vec<Call>,
+    revert_mode: RevertMode,
+    submitted: SubmittedTx,
+}
+
+impl SubmitBatchTx {
+    /// Creates an action submitting `calls` signed by `executor`, recorded in `submitted`.
+    pub(crate) fn new(
+        executor: PrivateKeySigner,
+        calls: Vec<Call>,
+        submitted: SubmittedTx,
+    ) -> Self {
+        Self {
+            executor,
+            sponsor: None,
+            calls,
+            revert_mode: RevertMode::Atomic,
+            submitted,
+        }
+    }
+
+    /// Sets the revert mode of the batch.
+    pub(crate) const fn with_revert_mode(mut self, revert_mode: RevertMode) -> Self {
+        self.revert_mode = revert_mode;
+        self
+    }
+}
+
+impl Action<EvolveEngineTypes> for SubmitBatchTx {
+    fn execute<'a>(
+        &'a mut self,
+        env: &'a mut Environment<EvolveEngineTypes>,
+    ) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let info = submit(env, self).await?;
+            self.submitted.set(info);
+            Ok(())
+        })
+    }
+}
+
+/// Submits an EvNode batch whose gas is paid by a sponsor in a new block.
+#[derive(Debug)]
+pub(crate) struct SubmitSponsoredTx(SubmitBatchTx);
+
+impl SubmitSponsoredTx {
+    /// Creates an action submitting `calls` signed by `executor` and sponsored by `sponsor`,
+    /// recorded in `submitted`.
+    pub(crate) fn new(
+        executor: PrivateKeySigner,
+        sponsor: PrivateKeySigner,
+        calls: Vec<Call>,
+        submitted: SubmittedTx,
+    ) -> Self {
+        let mut inner = SubmitBatchTx::new(executor, calls, submitted);
+        inner.sponsor = Some(sponsor);
+        Self(inner)
+    }
+
+    /// Sets the revert mode of the batch.
+    pub(crate) const fn with_revert_mode(self, revert_mode: RevertMode) -> Self {
+        Self(self.0.with_revert_mode(revert_mode))
+    }
+}
+
+impl Action<EvolveEngineTypes> for SubmitSponsoredTx {
+    fn execute<'a>(
+        &'a mut self,
+        env: &'a mut Environment<EvolveEngineTypes>,
+    ) -> BoxFuture<'a, Result<()>> {
+        self.0.execute(env)
+    }
+}
+
+/// Asserts that the sponsor of the submitted transaction paid its gas and the executor only
+/// the value of its successful calls.
+#[derive(Debug)]
+pub(crate) struct AssertSponsorCharged {
+    submitted: SubmittedTx,
+}
+
+impl AssertSponsorCharged {
+    /// Creates an action checking the transaction recorded in `submitted`.
+    pub(crate) const fn new(submitted: SubmittedTx) -> Self {
+        Self { submitted }
+    }
+}
+
+impl Action<EvolveEngineTypes> for AssertSponsorCharged {
+    fn execute<'a>(
+        &'a mut self,
+        env: &'a mut Environment<EvolveEngineTypes>,
+    ) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let info = self.submitted.info()?;
+            let sponsor = info
+                .sponsor
+                .ok_or_else(|| eyre!("submitted transaction is not sponsored"))?;
+            let receipt = receipt(env, info.hash).await?;
+            assert_eq!(
+                receipt.fee_payer(),
+                Some(sponsor),
+                "receipt should expose the sponsor as fee payer"
+            );
+
+            let gas_cost = U256::from(receipt.inner().gas_used)
+                .saturating_mul(U256::from(receipt.inner().effective_gas_price));
+            let sponsor_spent = info
+                .sponsor_balance
+                .saturating_sub(balance(env, sponsor).await?);
+            assert_eq!(sponsor_spent, gas_cost, "sponsor should pay the gas cost");
+
+            let executor_spent = info
+                .executor_balance
+                .saturating_sub(balance(env, info.executor).await?);
+            assert_eq!(
+                executor_spent,
+                transferred_value(&info, &receipt),
+                "executor should only pay the value of its successful calls"
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Asserts the status and failed calls reported by the receipt of the submitted transaction.
+#[derive(Debug)]
+pub(crate) struct AssertPerCallReceipts {
+    submitted: SubmittedTx,
+    success: bool,
+    failed_calls: Option<Vec<u64>>,
+}
+
+impl AssertPerCallReceipts {
+    /// Expects an atomic batch that succeeded or reverted as a whole.
+    pub(crate) const fn atomic(submitted: SubmittedTx, success: bool) -> Self {
+        Self {
+            submitted,
+            success,
+            failed_calls: None,
+        }
+    }
+
+    /// Expects a best-effort batch in which exactly the calls at `failed_calls` failed.
+    pub(crate) const fn best_effort(submitted: SubmittedTx, failed_calls: Vec<u64>) -> Self {
+        Self {
+            submitted,
+            success: true,
+            failed_calls: Some(failed_calls),
+        }
+    }
+}
+
+impl Action<EvolveEngineTypes> for AssertPerCallReceipts {
+    fn execute<'a>(
+        &'a mut self,
+        env: &'a mut Environment<EvolveEngineTypes>,
+    ) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let info = self.submitted.info()?;
+            let receipt = receipt(env, info.hash).await?;
+            assert_eq!(
+                receipt.status(),
+                self.success,
+                "unexpected status of the EvNode transaction"
+            );
+            let failed_calls = receipt
+                .failed_calls()
+                .map(|calls| calls.iter().map(|index| index.to::<u64>()).collect());
+            assert_eq!(
+                failed_calls, self.failed_calls,
+                "unexpected failed calls of the EvNode transaction"
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Signs the batch of `action`, includes it in a new block and returns what was submitted.
+async fn submit(
+    env: &mut Environment<EvolveEngineTypes>,
+    action: &SubmitBatchTx,
+) -> Result<SubmittedTxInfo> {
+    let parent_block = env.node_clients[0]
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await?
+        .ok_or_else(|| eyre!("parent block should exist"))?;
+    let mut parent_hash = parent_block.header.hash;
+    let mut parent_timestamp = parent_block.header.inner.timestamp;
+    let mut parent_number = parent_block.header.inner.number;
+    let block_gas_limit = parent_block.header.inner.gas_limit;
+
+    let rpc = &env.node_clients[0].rpc;
+    let chain_id =
+        EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header, Bytes>::chain_id(
+            rpc,
+        )
+        .await?
+        .ok_or_else(|| eyre!("node should report its chain id"))?;
+    let executor = action.executor.address();
+    let nonce = EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header, Bytes>::transaction_count(
+        rpc,
+        executor,
+        Some(BlockId::latest()),
+    )
+    .await?;
+    let sponsor = action.sponsor.as_ref().map(|sponsor| sponsor.address());
+    let executor_balance = balance(env, executor).await?;
+    let sponsor_balance = match sponsor {
+        Some(sponsor) => balance(env, sponsor).await?,
+        None => U256::ZERO,
+    };
+
+    let ev_tx = EvNodeTransaction {
+        chain_id: chain_id.to(),
+        nonce: nonce.to(),
+        nonce_key: U192::ZERO,
+        max_priority_fee_per_gas: 1_000_000_000,
+        max_fee_per_gas: 2_000_000_000,
+        gas_limit: GAS_LIMIT,
+        calls: action.calls.clone(),
+        access_list: AccessList::default(),
+        revert_mode: action.revert_mode,
+        valid_after: None,
+        valid_until: None,
+        session: None,
+        fee_payer_signature: None,
+        max_sponsor_fee: None,
+        p256_signature: None,
+    };
+    let executor_sig = action.executor.sign_hash_sync(&ev_tx.signature_hash())?;
+    let mut signed = ev_tx.into_signed(executor_sig);
+    if let Some(sponsor) = &action.sponsor {
+        let sponsor_hash = signed.tx().sponsor_signing_hash(executor);
+        signed.tx_mut().fee_payer_signature = Some(sponsor.sign_hash_sync(&sponsor_hash)?);
+    }
+    let envelope = EvTxEnvelope::EvNode(signed);
+    let raw_tx: Bytes = envelope.encoded_2718().into();
+    let hash = *envelope.tx_hash();
+
+    build_block_with_transactions(
+        env,
+        &mut parent_hash,
+        &mut parent_number,
+        &mut parent_timestamp,
+        Some(block_gas_limit),
+        vec![raw_tx],
+        Address::ZERO,
+    )
+    .await?;
+
+    Ok(SubmittedTxInfo {
+        hash,
+        executor,
+        sponsor,
+        call_values: action.calls.iter().map(|call| call.value).collect(),
+        executor_balance,
+        sponsor_balance,
+    })
+}
+
+/// Returns the value moved by the calls of `info` that took effect according to `receipt`.
+fn transferred_value(info: &SubmittedTxInfo, receipt: &EvRpcReceipt) -> U256 {
+    if !receipt.status() {
+        return U256::ZERO;
+    }
+    let failed = receipt.failed_calls().unwrap_or_default();
+    info.call_values
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !failed.contains(&U64::from(*index)))
+        .fold(U256::ZERO, |total, (_, value)| total.saturating_add(*value))
+}
+
+async fn balance(env: &Environment<EvolveEngineTypes>, address: Address) -> Result<U256> {
+    Ok(
+        EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header, Bytes>::balance(
+            &env.node_clients[0].rpc,
+            address,
+            Some(BlockId::latest()),
+        )
+        .await?,
+    )
+}
+
+async fn receipt(env: &Environment<EvolveEngineTypes>, hash: B256) -> Result<EvRpcReceipt> {
+    type EvRpcBlock = Block<EvRpcTransaction, Header>;
+    EthApiClient::<
+        EvTransactionRequest,
+        EvRpcTransaction,
+        EvRpcBlock,
+        EvRpcReceipt,
+        Header,
+        Bytes,
+    >::transaction_receipt(&env.node_clients[0].rpc, hash)
+    .await?
+    .ok_or_else(|| eyre!("receipt of EvNode transaction {hash} should be available"))
+}
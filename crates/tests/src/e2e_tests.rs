@@ -1,7 +1,7 @@
 use alloy_consensus::{transaction::TxHashRef, SignableTransaction, TxEnvelope, TxReceipt};
 use alloy_eips::{eip2718::Encodable2718, eip2930::AccessList, BlockNumberOrTag};
 use alloy_network::{eip2718::Decodable2718, ReceiptResponse};
-use alloy_primitives::{address, Address, Bytes, Signature, TxKind, B256, U256};
+use alloy_primitives::{address, aliases::U192, Address, Bytes, Signature, TxKind, B256, U256};
 use alloy_rpc_types::{
     eth::{
         Block, BlockTransactions, Header, Receipt, Transaction, TransactionInput,
@@ -32,7 +32,7 @@ use crate::common::{
 };
 use ev_node::rpc::{EvRpcReceipt, EvRpcTransaction, EvTransactionRequest};
 use ev_precompiles::mint::MINT_PRECOMPILE_ADDR;
-use ev_primitives::{Call, EvNodeTransaction, EvTxEnvelope};
+use ev_primitives::{Call, EvNodeTransaction, EvTxEnvelope, RevertMode};
 
 sol! {
     /// Interface for the native token precompile used in e2e tests.
@@ -116,7 +116,7 @@ pub(crate) async fn build_block_with_transactions(
     gas_limit: Option<u64>,
     transactions: Vec<Bytes>,
     suggested_fee_recipient: Address,
-) -> Result<alloy_rpc_types_engine::ExecutionPayloadEnvelopeV3> {
+) -> Result<EvPayloadEnvelope<alloy_rpc_types_engine::ExecutionPayloadEnvelopeV3>> {
     let payload_attributes = EvolveEnginePayloadAttributes {
         inner: PayloadAttributes {
             timestamp: *parent_timestamp + 12,
@@ -128,6 +128,9 @@ pub(crate) async fn build_block_with_transactions(
         },
         transactions: Some(transactions),
         gas_limit,
+        build_report: None,
+        max_tx_count: None,
+        decryption_keys: None,
     };
 
     let fork_choice = ForkchoiceState {
@@ -190,7 +193,8 @@ pub(crate) async fn build_block_with_transactions(
     Ok(payload_envelope)
 }
 use ev_node::{
-    EvolveEnginePayloadAttributes, EvolveEngineTypes, EvolveNode, EvolvePayloadBuilderConfig,
+    EvPayloadEnvelope, EvolveEnginePayloadAttributes, EvolveEngineTypes, EvolveNode,
+    EvolvePayloadBuilderConfig,
 };
 
 /// Tests that a single ev-reth node can successfully produce blocks.
@@ -473,17 +477,25 @@ async fn test_e2e_sponsored_evnode_transaction() -> Result<()> {
         to: TxKind::Call(recipient),
         value: transfer_value,
         input: Bytes::default(),
+        gas_limit: None,
     };
 
     let ev_tx = EvNodeTransaction {
         chain_id,
         nonce: executor_nonce,
+        nonce_key: U192::ZERO,
         max_priority_fee_per_gas: 1_000_000_000,
         max_fee_per_gas: 2_000_000_000,
         gas_limit: 100_000,
         calls: vec![call],
         access_list: AccessList::default(),
+        revert_mode: RevertMode::Atomic,
+        valid_after: None,
+        valid_until: None,
+        session: None,
         fee_payer_signature: None,
+        max_sponsor_fee: None,
+        p256_signature: None,
     };
 
     let executor_sig = executor
@@ -653,17 +665,25 @@ async fn test_e2e_invalid_sponsor_signature_skipped() -> Result<()> {
         to: TxKind::Call(Address::random()),
         value: U256::ZERO,
         input: Bytes::default(),
+        gas_limit: None,
     };
 
     let ev_tx = EvNodeTransaction {
         chain_id,
         nonce: executor_nonce,
+        nonce_key: U192::ZERO,
         max_priority_fee_per_gas: 1_000_000_000,
         max_fee_per_gas: 2_000_000_000,
         gas_limit: 100_000,
         calls: vec![call],
         access_list: AccessList::default(),
+        revert_mode: RevertMode::Atomic,
+        valid_after: None,
+        valid_until: None,
+        session: None,
         fee_payer_signature: None,
+        max_sponsor_fee: None,
+        p256_signature: None,
     };
 
     let executor_sig = executor
@@ -777,12 +797,19 @@ async fn test_e2e_empty_calls_skipped() -> Result<()> {
     let ev_tx = EvNodeTransaction {
         chain_id,
         nonce: executor_nonce,
+        nonce_key: U192::ZERO,
         max_priority_fee_per_gas: 1_000_000_000,
         max_fee_per_gas: 2_000_000_000,
         gas_limit: 100_000,
         calls: Vec::new(),
         access_list: AccessList::default(),
+        revert_mode: RevertMode::Atomic,
+        valid_after: None,
+        valid_until: None,
+        session: None,
         fee_payer_signature: None,
+        max_sponsor_fee: None,
+        p256_signature: None,
     };
 
     let executor_sig = executor
@@ -901,6 +928,7 @@ async fn test_e2e_sponsor_insufficient_max_fee_skipped() -> Result<()> {
         to: TxKind::Call(Address::random()),
         value: U256::ZERO,
         input: Bytes::default(),
+        gas_limit: None,
     };
 
     let tx_gas_limit = 100_000u64;
@@ -920,12 +948,19 @@ async fn test_e2e_sponsor_insufficient_max_fee_skipped() -> Result<()> {
     let ev_tx = EvNodeTransaction {
         chain_id,
         nonce: executor_nonce,
+        nonce_key: U192::ZERO,
         max_priority_fee_per_gas,
         max_fee_per_gas,
         gas_limit: tx_gas_limit,
         calls: vec![call],
         access_list: AccessList::default(),
+        revert_mode: RevertMode::Atomic,
+        valid_after: None,
+        valid_until: None,
+        session: None,
         fee_payer_signature: None,
+        max_sponsor_fee: None,
+        p256_signature: None,
     };
 
     let executor_sig = executor
@@ -1098,23 +1133,32 @@ async fn test_e2e_nonce_bumped_on_create_batch_failure() -> Result<()> {
             to: TxKind::Create,
             value: U256::ZERO,
             input: Bytes::new(),
+            gas_limit: None,
         },
         Call {
             to: TxKind::Call(revert_address),
             value: U256::ZERO,
             input: revert_calldata,
+            gas_limit: None,
         },
     ];
 
     let ev_tx = EvNodeTransaction {
         chain_id,
         nonce: executor_nonce,
+        nonce_key: U192::ZERO,
         max_priority_fee_per_gas: 1_000_000_000,
         max_fee_per_gas: 2_000_000_000,
         gas_limit: 300_000,
         calls,
         access_list: AccessList::default(),
+        revert_mode: RevertMode::Atomic,
+        valid_after: None,
+        valid_until: None,
+        session: None,
         fee_payer_signature: None,
+        max_sponsor_fee: None,
+        p256_signature: None,
     };
 
     let executor_sig = executor
@@ -2091,3 +2135,132 @@ async fn test_e2e_dev_mode_txpool_fallback() -> Result<()> {
 
     Ok(())
 }
+
+/// Tests that a sponsored `EvNode` transaction submitted over `eth_sendRawTransaction` is
+/// validated into the txpool and included from there.
+///
+/// # Success Criteria
+/// - `eth_sendRawTransaction` accepts the raw 0x76 envelope and returns its hash
+/// - The dev-mode payload builder includes it from the txpool
+/// - The receipt succeeds and reports the sponsor as fee payer
+#[tokio::test(flavor = "multi_thread")]
+async fn test_e2e_evnode_transaction_via_send_raw_transaction() -> Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let chain_spec = create_test_chain_spec();
+    let chain_id = chain_spec.chain().id();
+
+    let mut setup = Setup::<EvolveEngineTypes>::default()
+        .with_chain_spec(chain_spec)
+        .with_network(NetworkSetup::single_node())
+        .with_dev_mode(true)
+        .with_tree_config(e2e_test_tree_config());
+
+    let mut env = Environment::<EvolveEngineTypes>::default();
+    setup.apply::<EvolveNode>(&mut env).await?;
+
+    let parent_block = env.node_clients[0]
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await?
+        .expect("parent block should exist");
+    let mut parent_hash = parent_block.header.hash;
+    let mut parent_timestamp = parent_block.header.inner.timestamp;
+    let mut parent_number = parent_block.header.inner.number;
+
+    let mut wallets = Wallet::new(2).with_chain_id(chain_id).wallet_gen();
+    let executor = wallets.remove(0);
+    let sponsor = wallets.remove(0);
+
+    let ev_tx = EvNodeTransaction {
+        chain_id,
+        nonce: 0,
+        nonce_key: U192::ZERO,
+        max_priority_fee_per_gas: 1_000_000_000,
+        max_fee_per_gas: 2_000_000_000,
+        gas_limit: 100_000,
+        calls: vec![Call {
+            to: TxKind::Call(Address::random()),
+            value: U256::from(1_000u64),
+            input: Bytes::default(),
+            gas_limit: None,
+        }],
+        access_list: AccessList::default(),
+        revert_mode: RevertMode::Atomic,
+        valid_after: None,
+        valid_until: None,
+        session: None,
+        fee_payer_signature: None,
+        max_sponsor_fee: None,
+        p256_signature: None,
+    };
+    let executor_sig = executor
+        .sign_hash_sync(&ev_tx.signature_hash())
+        .expect("executor signature");
+    let mut signed = ev_tx.into_signed(executor_sig);
+    let sponsor_hash = signed.tx().sponsor_signing_hash(executor.address());
+    signed.tx_mut().fee_payer_signature = Some(
+        sponsor
+            .sign_hash_sync(&sponsor_hash)
+            .expect("sponsor signature"),
+    );
+
+    let envelope = EvTxEnvelope::EvNode(signed);
+    let tx_hash = *envelope.tx_hash();
+    let submitted =
+        EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header, Bytes>::send_raw_transaction(
+            &env.node_clients[0].rpc,
+            envelope.encoded_2718().into(),
+        )
+        .await?;
+    assert_eq!(
+        submitted, tx_hash,
+        "RPC should return the EvNode transaction hash"
+    );
+
+    // Empty attributes: in dev mode the builder pulls the transaction from the txpool.
+    let payload_envelope = build_block_with_transactions(
+        &mut env,
+        &mut parent_hash,
+        &mut parent_number,
+        &mut parent_timestamp,
+        None,
+        vec![],
+        Address::random(),
+    )
+    .await?;
+    let block_txs = &payload_envelope
+        .execution_payload
+        .payload_inner
+        .payload_inner
+        .transactions;
+    assert!(
+        block_txs
+            .iter()
+            .any(|raw| EvTxEnvelope::decode_2718_exact(raw.as_ref())
+                .is_ok_and(|tx| *tx.tx_hash() == tx_hash)),
+        "EvNode transaction from the txpool should be included"
+    );
+
+    type EvRpcBlock = Block<EvRpcTransaction, Header>;
+    let receipt = EthApiClient::<
+        EvTransactionRequest,
+        EvRpcTransaction,
+        EvRpcBlock,
+        EvRpcReceipt,
+        Header,
+        Bytes,
+    >::transaction_receipt(&env.node_clients[0].rpc, tx_hash)
+    .await?
+    .expect("EvNode transaction receipt available");
+    assert!(
+        receipt.inner().status(),
+        "EvNode transaction should succeed"
+    );
+    assert_eq!(
+        receipt.fee_payer(),
+        Some(sponsor.address()),
+        "receipt should expose sponsor fee payer"
+    );
+
+    Ok(())
+}
@@ -5,12 +5,24 @@
 
 pub mod common;
 
+#[cfg(test)]
+pub(crate) mod e2e_actions;
 #[cfg(test)]
 pub(crate) mod e2e_tests;
 #[cfg(test)]
+mod test_chaos_engine;
+#[cfg(test)]
 mod test_deploy_allowlist;
 #[cfg(test)]
+mod test_engine_fixtures;
+#[cfg(test)]
+mod test_evnode_actions;
+#[cfg(test)]
 mod test_evolve_engine_api;
+#[cfg(test)]
+mod test_signing_domains;
+#[cfg(test)]
+mod test_system_fee;
 
 // Re-export common test utilities
 pub use common::*;
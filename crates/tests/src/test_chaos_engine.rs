@@ -0,0 +1,64 @@
+//! Adversarial Engine API sequences replayed against a fresh node.
+//!
+//! Runs every [`EngineScenario`] in order through the node's authenticated Engine API client,
+//! the same sequences `ev-reth chaos-engine` sends to a live node.
+
+use crate::common::{create_test_chain_spec, e2e_test_tree_config};
+
+use async_trait::async_trait;
+use ev_node::{
+    chaos_engine::{run_scenario, EngineScenario, EngineTransport},
+    EvolveEngineTypes, EvolveNode,
+};
+use eyre::Result;
+use jsonrpsee_core::{
+    client::{ClientT, Error as ClientError},
+    params::ArrayParams,
+};
+use reth_e2e_test_utils::testsuite::{
+    setup::{NetworkSetup, Setup},
+    Environment,
+};
+use serde_json::Value;
+
+/// Engine API transport over a jsonrpsee client.
+struct ClientTransport<C>(C);
+
+#[async_trait]
+impl<C: ClientT + Send + Sync> EngineTransport for ClientTransport<C> {
+    async fn call(&self, method: &str, params: Vec<Value>) -> Result<Result<Value, Value>> {
+        let mut array = ArrayParams::new();
+        for param in params {
+            array.insert(param)?;
+        }
+        match self.0.request::<Value, _>(method, array).await {
+            Ok(result) => Ok(Ok(result)),
+            Err(ClientError::Call(err)) => Ok(Err(serde_json::to_value(&err)?)),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Every adversarial sequence gets the expected answers and leaves the node usable.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_engine_api_survives_adversarial_sequences() -> Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let mut setup = Setup::<EvolveEngineTypes>::default()
+        .with_chain_spec(create_test_chain_spec())
+        .with_network(NetworkSetup::single_node())
+        .with_dev_mode(false)
+        .with_tree_config(e2e_test_tree_config());
+    let mut env = Environment::<EvolveEngineTypes>::default();
+    setup.apply::<EvolveNode>(&mut env).await?;
+
+    let transport = ClientTransport(env.node_clients[0].engine.http_client());
+    for scenario in EngineScenario::ALL {
+        let outcome = run_scenario(&transport, scenario).await?;
+        assert_eq!(outcome.failure, None, "{scenario}");
+        assert!(outcome.steps > 0, "{scenario} did not run");
+    }
+
+    drop(setup);
+    Ok(())
+}
@@ -0,0 +1,270 @@
+//! Engine API conformance tests replaying recorded ev-node exchanges.
+//!
+//! Each fixture under `assets/engine-fixtures/` (plus any in the directory named by
+//! `EV_NODE_ENGINE_FIXTURES`, e.g. fixtures exported by ev-node's CI) is a list of JSON-RPC
+//! requests as ev-node sends them, each paired with the response ev-node expects. Every
+//! fixture is replayed in order against a fresh node started from `assets/genesis.json`, and
+//! each response must match the recorded one exactly: same fields, same encodings, no extras.
+//!
+//! Values that depend on the build rather than the protocol (hashes, payload ids) can be
+//! written as `"${name}"`. The first occurrence in a response binds the name to the actual
+//! value, later occurrences must match it, and occurrences in requests are replaced by it.
+//! `${genesisHash}` is bound before the first request.
+
+use crate::common::{create_test_chain_spec, e2e_test_tree_config};
+
+use alloy_rpc_types::BlockNumberOrTag;
+use ev_node::{EvolveEngineTypes, EvolveNode};
+use eyre::{bail, ensure, eyre, Result, WrapErr};
+use jsonrpsee_core::{
+    client::{ClientT, Error as ClientError},
+    params::ArrayParams,
+};
+use reth_e2e_test_utils::testsuite::{
+    setup::{NetworkSetup, Setup},
+    Environment,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// Directory of additional fixtures to replay, such as ones fetched from ev-node.
+const FIXTURES_DIR_ENV: &str = "EV_NODE_ENGINE_FIXTURES";
+
+#[derive(Debug, Deserialize)]
+struct EngineFixture {
+    description: String,
+    exchanges: Vec<Exchange>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Exchange {
+    request: Request,
+    response: Response,
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    method: String,
+    #[serde(default)]
+    params: Vec<Value>,
+}
+
+/// A JSON-RPC response; `jsonrpc` and `id` are ignored.
+#[derive(Debug, Deserialize)]
+struct Response {
+    #[serde(flatten)]
+    outcome: Outcome,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Outcome {
+    Result(Value),
+    Error(Value),
+}
+
+fn fixture_paths() -> Result<Vec<PathBuf>> {
+    let mut dirs = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/engine-fixtures")];
+    if let Some(dir) = std::env::var_os(FIXTURES_DIR_ENV) {
+        dirs.push(dir.into());
+    }
+
+    let mut paths = Vec::new();
+    for dir in dirs {
+        let mut found = Vec::new();
+        for entry in std::fs::read_dir(&dir).wrap_err_with(|| format!("reading {dir:?}"))? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                found.push(path);
+            }
+        }
+        found.sort();
+        paths.extend(found);
+    }
+    Ok(paths)
+}
+
+/// Returns the binding name of a `"${name}"` placeholder.
+fn placeholder(value: &Value) -> Option<&str> {
+    value.as_str()?.strip_prefix("${")?.strip_suffix('}')
+}
+
+/// Replaces bound placeholders in a request parameter.
+fn substitute(value: &Value, bindings: &HashMap<String, Value>) -> Result<Value> {
+    if let Some(name) = placeholder(value) {
+        return bindings
+            .get(name)
+            .cloned()
+            .ok_or_else(|| eyre!("request uses unbound placeholder ${{{name}}}"));
+    }
+    Ok(match value {
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| substitute(item, bindings))
+                .collect::<Result<_>>()?,
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, field)| Ok((key.clone(), substitute(field, bindings)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+/// Checks `actual` against the recorded `expected` value, binding new placeholders.
+fn match_value(
+    path: &str,
+    expected: &Value,
+    actual: &Value,
+    bindings: &mut HashMap<String, Value>,
+) -> Result<()> {
+    if let Some(name) = placeholder(expected) {
+        match bindings.get(name) {
+            Some(bound) => ensure!(
+                bound == actual,
+                "{path}: expected ${{{name}}} = {bound}, got {actual}"
+            ),
+            None => {
+                bindings.insert(name.to_string(), actual.clone());
+            }
+        }
+        return Ok(());
+    }
+
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for key in actual.keys() {
+                ensure!(
+                    expected.contains_key(key),
+                    "{path}: unexpected field `{key}`"
+                );
+            }
+            for (key, expected) in expected {
+                let actual = actual
+                    .get(key)
+                    .ok_or_else(|| eyre!("{path}: missing field `{key}`"))?;
+                match_value(&format!("{path}.{key}"), expected, actual, bindings)?;
+            }
+            Ok(())
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            ensure!(
+                expected.len() == actual.len(),
+                "{path}: expected {} items, got {}",
+                expected.len(),
+                actual.len()
+            );
+            for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                match_value(&format!("{path}[{index}]"), expected, actual, bindings)?;
+            }
+            Ok(())
+        }
+        _ => {
+            ensure!(
+                expected == actual,
+                "{path}: expected {expected}, got {actual}"
+            );
+            Ok(())
+        }
+    }
+}
+
+async fn replay(fixture: &EngineFixture) -> Result<()> {
+    let mut setup = Setup::<EvolveEngineTypes>::default()
+        .with_chain_spec(create_test_chain_spec())
+        .with_network(NetworkSetup::single_node())
+        .with_dev_mode(false)
+        .with_tree_config(e2e_test_tree_config());
+    let mut env = Environment::<EvolveEngineTypes>::default();
+    setup.apply::<EvolveNode>(&mut env).await?;
+
+    let genesis = env.node_clients[0]
+        .get_block_by_number(BlockNumberOrTag::Number(0))
+        .await?
+        .ok_or_else(|| eyre!("genesis block missing"))?;
+    let mut bindings = HashMap::from([(
+        "genesisHash".to_string(),
+        serde_json::to_value(genesis.header.hash)?,
+    )]);
+
+    let client = env.node_clients[0].engine.http_client();
+    for (index, exchange) in fixture.exchanges.iter().enumerate() {
+        let method = &exchange.request.method;
+        let mut params = ArrayParams::new();
+        for param in &exchange.request.params {
+            params.insert(substitute(param, &bindings)?)?;
+        }
+
+        let path = format!("exchange {index} ({method})");
+        match (
+            &exchange.response.outcome,
+            client.request::<Value, _>(method, params).await,
+        ) {
+            (Outcome::Result(expected), Ok(actual)) => {
+                match_value(&format!("{path}.result"), expected, &actual, &mut bindings)?
+            }
+            (Outcome::Error(expected), Err(ClientError::Call(err))) => match_value(
+                &format!("{path}.error"),
+                expected,
+                &serde_json::to_value(&err)?,
+                &mut bindings,
+            )?,
+            (Outcome::Result(_), Err(err)) => bail!("{path}: expected a result, got {err}"),
+            (Outcome::Error(_), Ok(actual)) => bail!("{path}: expected an error, got {actual}"),
+            (Outcome::Error(_), Err(err)) => return Err(err).wrap_err(path),
+        }
+    }
+
+    drop(setup);
+    Ok(())
+}
+
+#[test]
+fn placeholders_bind_then_must_match() {
+    let mut bindings = HashMap::new();
+    let expected = serde_json::json!({ "a": "${hash}", "b": ["${hash}"], "c": "0x1" });
+
+    match_value(
+        "root",
+        &expected,
+        &serde_json::json!({ "a": "0xab", "b": ["0xab"], "c": "0x1" }),
+        &mut bindings,
+    )
+    .unwrap();
+    assert_eq!(bindings["hash"], "0xab");
+    assert_eq!(
+        substitute(&serde_json::json!(["${hash}"]), &bindings).unwrap(),
+        serde_json::json!(["0xab"])
+    );
+
+    let mut bindings = HashMap::new();
+    let mismatched = serde_json::json!({ "a": "0xab", "b": ["0xcd"], "c": "0x1" });
+    assert!(match_value("root", &expected, &mismatched, &mut bindings).is_err());
+
+    let extra_field = serde_json::json!({ "a": "0xab", "b": ["0xab"], "c": "0x1", "d": 1 });
+    assert!(match_value("root", &expected, &extra_field, &mut HashMap::new()).is_err());
+}
+
+/// Replays every Engine API fixture against a fresh node.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_engine_api_matches_ev_node_fixtures() -> Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let paths = fixture_paths()?;
+    ensure!(!paths.is_empty(), "no engine fixtures found");
+    for path in paths {
+        let fixture: EngineFixture = serde_json::from_slice(&std::fs::read(&path)?)
+            .wrap_err_with(|| format!("parsing {}", path.display()))?;
+        replay(&fixture)
+            .await
+            .wrap_err_with(|| format!("{}: {}", path.display(), fixture.description))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use alloy_primitives::{Address, Bytes, TxKind, U256};
+use alloy_sol_types::{sol, SolCall};
+use eyre::Result;
+use reth_e2e_test_utils::{
+    testsuite::{
+        setup::{NetworkSetup, Setup},
+        TestBuilder,
+    },
+    wallet::Wallet,
+};
+use serde_json::json;
+
+use crate::{
+    common::{create_test_chain_spec_with_evolve_extras, e2e_test_tree_config, TEST_CHAIN_ID},
+    e2e_actions::{
+        AssertPerCallReceipts, AssertSponsorCharged, SubmitBatchTx, SubmitSponsoredTx, SubmittedTx,
+    },
+};
+use ev_node::{EvolveEngineTypes, EvolveNode};
+use ev_precompiles::mint::MINT_PRECOMPILE_ADDR;
+use ev_primitives::{Call, RevertMode};
+
+sol! {
+    interface MintPrecompile {
+        function mint(address to, uint256 amount);
+    }
+}
+
+fn transfer(to: Address, value: u64) -> Call {
+    Call {
+        to: TxKind::Call(to),
+        value: U256::from(value),
+        input: Bytes::new(),
+        gas_limit: None,
+    }
+}
+
+/// A mint by an account that is neither the mint admin nor allowlisted, which halts.
+fn unauthorized_mint(to: Address) -> Call {
+    Call {
+        to: TxKind::Call(MINT_PRECOMPILE_ADDR),
+        value: U256::ZERO,
+        input: MintPrecompile::mintCall {
+            to,
+            amount: U256::from(1),
+        }
+        .abi_encode()
+        .into(),
+        gas_limit: None,
+    }
+}
+
+fn setup() -> Setup<EvolveEngineTypes> {
+    let chain_spec = create_test_chain_spec_with_evolve_extras(json!({
+        "mintAdmin": Address::repeat_byte(0xad),
+        "bestEffortActivationHeight": 0,
+    }));
+    Setup::<EvolveEngineTypes>::default()
+        .with_chain_spec(chain_spec)
+        .with_network(NetworkSetup::single_node())
+        .with_dev_mode(false)
+        .with_tree_config(e2e_test_tree_config())
+}
+
+/// Tests that a sponsored batch of transfers succeeds with the sponsor paying its gas.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_e2e_sponsored_batch_charges_sponsor() -> Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let mut wallets = Wallet::new(2).with_chain_id(TEST_CHAIN_ID).wallet_gen();
+    let executor = wallets.remove(0);
+    let sponsor = wallets.remove(0);
+    let calls = vec![
+        transfer(Address::repeat_byte(0x01), 1_000),
+        transfer(Address::repeat_byte(0x02), 2_000),
+    ];
+
+    let submitted = SubmittedTx::default();
+    TestBuilder::new()
+        .with_setup(setup())
+        .with_action(SubmitSponsoredTx::new(
+            executor,
+            sponsor,
+            calls,
+            submitted.clone(),
+        ))
+        .with_action(AssertPerCallReceipts::atomic(submitted.clone(), true))
+        .with_action(AssertSponsorCharged::new(submitted))
+        .run::<EvolveNode>()
+        .await
+}
+
+/// Tests that a failing call of a sponsored best-effort batch is reported in the receipt and
+/// that the sponsor still pays for the whole batch.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_e2e_sponsored_best_effort_batch_reports_failed_call() -> Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let mut wallets = Wallet::new(2).with_chain_id(TEST_CHAIN_ID).wallet_gen();
+    let executor = wallets.remove(0);
+    let sponsor = wallets.remove(0);
+    let recipient = Address::repeat_byte(0x01);
+    let calls = vec![
+        transfer(recipient, 1_000),
+        transfer(Address::repeat_byte(0x02), 2_000),
+        unauthorized_mint(recipient),
+    ];
+
+    let submitted = SubmittedTx::default();
+    TestBuilder::new()
+        .with_setup(setup())
+        .with_action(
+            SubmitSponsoredTx::new(executor, sponsor, calls, submitted.clone())
+                .with_revert_mode(RevertMode::BestEffort),
+        )
+        .with_action(AssertPerCallReceipts::best_effort(
+            submitted.clone(),
+            vec![2],
+        ))
+        .with_action(AssertSponsorCharged::new(submitted))
+        .run::<EvolveNode>()
+        .await
+}
+
+/// Tests that a failing call reverts an atomic batch as a whole.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_e2e_atomic_batch_reverts_on_failed_call() -> Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let executor = Wallet::new(1)
+        .with_chain_id(TEST_CHAIN_ID)
+        .wallet_gen()
+        .remove(0);
+    let recipient = Address::repeat_byte(0x01);
+    let calls = vec![transfer(recipient, 1_000), unauthorized_mint(recipient)];
+
+    let submitted = SubmittedTx::default();
+    TestBuilder::new()
+        .with_setup(setup())
+        .with_action(SubmitBatchTx::new(executor, calls, submitted.clone()))
+        .with_action(AssertPerCallReceipts::atomic(submitted, false))
+        .run::<EvolveNode>()
+        .await
+}
@@ -318,6 +318,9 @@ async fn test_e2e_engine_api_gas_limit_handling() -> Result<()> {
         },
         transactions: Some(invalid_batch),
         gas_limit: Some(0),
+        build_report: None,
+        max_tx_count: None,
+        decryption_keys: None,
     };
 
     let engine_client = env.node_clients[0].engine.http_client();
@@ -0,0 +1,185 @@
+//! Property tests for the separation of the EvNode executor (`0x76`) and sponsor (`0x78`)
+//! signing domains.
+//!
+//! Transactions are derived from seeds, so every run checks the same cases and a failing seed
+//! can be replayed.
+
+use alloy_primitives::{keccak256, Address, Bytes, Signature, TxKind, B256, U256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use ev_primitives::{
+    CrossDomainSignature, EvNodeSignedTx, EvNodeTransactionBuilder, EvTxEnvelope,
+    SponsorHashScheme, EVNODE_SPONSOR_DOMAIN, EVNODE_TX_TYPE_ID,
+};
+
+const CASES: u64 = 64;
+
+const SCHEMES: [SponsorHashScheme; 3] = [
+    SponsorHashScheme::Executor,
+    SponsorHashScheme::FeePayer,
+    SponsorHashScheme::Eip712,
+];
+
+/// Deterministic pseudo-random words for a test case.
+struct Seed(B256);
+
+impl Seed {
+    fn new(case: u64, label: &str) -> Self {
+        Self(keccak256([label.as_bytes(), &case.to_be_bytes()].concat()))
+    }
+
+    fn next(&mut self) -> B256 {
+        self.0 = keccak256(self.0);
+        self.0
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_be_bytes(self.next()[..8].try_into().unwrap())
+    }
+
+    fn signer(&mut self) -> PrivateKeySigner {
+        PrivateKeySigner::from_bytes(&self.next()).expect("valid key")
+    }
+}
+
+/// Builds an unsponsored transaction with fields taken from `seed`.
+fn random_transaction(seed: &mut Seed, executor: &PrivateKeySigner) -> EvNodeSignedTx {
+    let calls = 1 + seed.u64() % 3;
+    let mut builder = EvNodeTransactionBuilder::new(1 + seed.u64() % 100_000)
+        .nonce(seed.u64() % 1_000)
+        .gas_limit(21_000 + seed.u64() % 1_000_000)
+        .max_fee_per_gas(u128::from(seed.u64()))
+        .max_priority_fee_per_gas(u128::from(seed.u64() % 1_000_000_000));
+    for _ in 0..calls {
+        let to = Address::from_word(seed.next());
+        let input = Bytes::from(seed.next()[..(seed.u64() % 33) as usize].to_vec());
+        builder = builder.add_call(TxKind::Call(to), U256::from(seed.u64() % 1_000), input);
+    }
+    if seed.u64() % 2 == 0 {
+        builder = builder.max_sponsor_fee(u128::from(seed.u64()));
+    }
+    let EvTxEnvelope::EvNode(signed) = builder.sign_executor(executor).expect("executor signs")
+    else {
+        unreachable!()
+    };
+    signed
+}
+
+/// Sponsors `signed` under `scheme` with `sponsor`.
+fn sponsor(
+    signed: &mut EvNodeSignedTx,
+    executor: Address,
+    sponsor: &PrivateKeySigner,
+    scheme: SponsorHashScheme,
+) {
+    let hash = signed.tx().sponsor_signing_hash_with(scheme, executor);
+    let signature = sponsor.sign_hash_sync(&hash).expect("sponsor signs");
+    signed.tx_mut().fee_payer_signature = Some(signature);
+}
+
+fn recovers_to(signature: &Signature, hash: B256, signer: Address) -> bool {
+    signature.recover_address_from_prehash(&hash) == Ok(signer)
+}
+
+/// The domain prefixes differ from each other and from the EIP-191 prefix of EIP-712 hashes.
+#[test]
+fn test_domain_prefixes_are_distinct() {
+    assert_ne!(EVNODE_TX_TYPE_ID, EVNODE_SPONSOR_DOMAIN);
+    assert_ne!(EVNODE_TX_TYPE_ID, 0x19);
+    assert_ne!(EVNODE_SPONSOR_DOMAIN, 0x19);
+}
+
+/// No sponsor signing hash equals the executor signing hash of the same transaction, whoever
+/// the executor is.
+#[test]
+fn test_signing_hashes_never_collide() {
+    for case in 0..CASES {
+        let mut seed = Seed::new(case, "hashes");
+        let executor = seed.signer();
+        let tx = random_transaction(&mut seed, &executor);
+        let executor_hash = tx.tx().executor_signing_hash();
+        for candidate in [
+            executor.address(),
+            Address::ZERO,
+            Address::from_word(seed.next()),
+        ] {
+            for scheme in SCHEMES {
+                assert_ne!(
+                    tx.tx().sponsor_signing_hash_with(scheme, candidate),
+                    executor_hash,
+                    "case {case}, scheme {}",
+                    scheme.as_str()
+                );
+            }
+        }
+    }
+}
+
+/// Honest signatures only verify in their own domain, including when one key signs both as
+/// executor and as sponsor.
+#[test]
+fn test_signatures_do_not_cross_domains() {
+    for case in 0..CASES {
+        let mut seed = Seed::new(case, "signatures");
+        let executor = seed.signer();
+        let fee_payer = if case % 4 == 0 {
+            executor.clone()
+        } else {
+            seed.signer()
+        };
+        let scheme = SCHEMES[(case % 3) as usize];
+
+        let mut signed = random_transaction(&mut seed, &executor);
+        let executor_address = executor.address();
+        assert_eq!(
+            signed
+                .tx()
+                .check_signing_domains(executor_address, signed.signature()),
+            Ok(())
+        );
+
+        sponsor(&mut signed, executor_address, &fee_payer, scheme);
+        let tx = signed.tx();
+        let fee_payer_signature = tx.fee_payer_signature.expect("sponsored");
+        assert_eq!(
+            tx.check_signing_domains(executor_address, signed.signature()),
+            Ok(()),
+            "case {case}, scheme {}",
+            scheme.as_str()
+        );
+
+        let executor_hash = tx.executor_signing_hash();
+        assert!(!recovers_to(
+            &fee_payer_signature,
+            executor_hash,
+            fee_payer.address()
+        ));
+        for scheme in SCHEMES {
+            let sponsor_hash = tx.sponsor_signing_hash_with(scheme, executor_address);
+            assert!(!recovers_to(
+                signed.signature(),
+                sponsor_hash,
+                executor_address
+            ));
+        }
+    }
+}
+
+/// Copying the executor signature into the fee payer slot is caught.
+#[test]
+fn test_reused_executor_signature_is_rejected() {
+    for case in 0..CASES / 8 {
+        let mut seed = Seed::new(case, "reuse");
+        let executor = seed.signer();
+        let mut signed = random_transaction(&mut seed, &executor);
+        let executor_signature = *signed.signature();
+        signed.tx_mut().fee_payer_signature = Some(executor_signature);
+
+        assert_eq!(
+            signed
+                .tx()
+                .check_signing_domains(executor.address(), signed.signature()),
+            Err(CrossDomainSignature::ReusedSignature)
+        );
+    }
+}
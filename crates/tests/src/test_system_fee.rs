@@ -0,0 +1,183 @@
+use alloy_eips::{eip2718::Encodable2718, BlockNumberOrTag};
+use alloy_primitives::{Address, Bytes, TxKind, U256};
+use alloy_rpc_types::{
+    eth::{Block, Header, Receipt, Transaction, TransactionRequest},
+    BlockId,
+};
+use eyre::Result;
+use reth_e2e_test_utils::{
+    testsuite::{
+        setup::{NetworkSetup, Setup},
+        Environment,
+    },
+    transaction::TransactionTestContext,
+    wallet::Wallet,
+};
+use reth_rpc_api::clients::EthApiClient;
+use serde_json::json;
+
+use crate::{
+    common::{create_test_chain_spec_with_evolve_extras, e2e_test_tree_config, TEST_CHAIN_ID},
+    e2e_tests::build_block_with_transactions,
+};
+use ev_node::{EvolveEngineTypes, EvolveNode};
+
+/// Balances observed around a single system transaction.
+struct SystemTxOutcome {
+    /// Fee the transaction would pay under regular rules (`gas_used * effective_gas_price`).
+    fee: U256,
+    /// Priority fee the beneficiary would earn under regular rules.
+    tip: U256,
+    sender_spent: U256,
+    fee_account_spent: U256,
+    beneficiary_earned: U256,
+}
+
+async fn balance(env: &Environment<EvolveEngineTypes>, address: Address) -> Result<U256> {
+    Ok(
+        EthApiClient::<TransactionRequest, Transaction, Block, Receipt, Header, Bytes>::balance(
+            &env.node_clients[0].rpc,
+            address,
+            Some(BlockId::latest()),
+        )
+        .await?,
+    )
+}
+
+/// Sends one zero-value transfer from a system sender on a chain configured with `policy`
+/// and reports who paid for it.
+async fn run_system_tx(policy: &str) -> Result<SystemTxOutcome> {
+    reth_tracing::init_test_tracing();
+
+    let mut wallets = Wallet::new(2).with_chain_id(TEST_CHAIN_ID).wallet_gen();
+    let system_sender = wallets.remove(0);
+    let fee_account = wallets.remove(0).address();
+    let beneficiary = Address::repeat_byte(0xbe);
+
+    let mut extras = json!({
+        "systemTxSenders": [system_sender.address()],
+        "systemTxFeePolicy": policy,
+    });
+    if policy == "charged" {
+        extras["systemFeeAccount"] = json!(fee_account);
+    }
+    let chain_spec = create_test_chain_spec_with_evolve_extras(extras);
+    let chain_id = chain_spec.chain().id();
+
+    let mut setup = Setup::<EvolveEngineTypes>::default()
+        .with_chain_spec(chain_spec)
+        .with_network(NetworkSetup::single_node())
+        .with_dev_mode(false)
+        .with_tree_config(e2e_test_tree_config());
+
+    let mut env = Environment::<EvolveEngineTypes>::default();
+    setup.apply::<EvolveNode>(&mut env).await?;
+
+    let parent_block = env.node_clients[0]
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await?
+        .expect("parent block should exist");
+    let mut parent_hash = parent_block.header.hash;
+    let mut parent_timestamp = parent_block.header.inner.timestamp;
+    let mut parent_number = parent_block.header.inner.number;
+    let gas_limit = parent_block.header.inner.gas_limit;
+
+    let sender_before = balance(&env, system_sender.address()).await?;
+    let fee_account_before = balance(&env, fee_account).await?;
+    let beneficiary_before = balance(&env, beneficiary).await?;
+
+    let tx = TransactionRequest {
+        nonce: Some(0),
+        gas: Some(21_000),
+        max_fee_per_gas: Some(20_000_000_000),
+        max_priority_fee_per_gas: Some(2_000_000_000),
+        chain_id: Some(chain_id),
+        value: Some(U256::ZERO),
+        to: Some(TxKind::Call(Address::repeat_byte(0x42))),
+        ..Default::default()
+    };
+    let envelope = TransactionTestContext::sign_tx(system_sender.clone(), tx).await;
+    let tx_hash = *envelope.tx_hash();
+    let raw: Bytes = envelope.encoded_2718().into();
+
+    build_block_with_transactions(
+        &mut env,
+        &mut parent_hash,
+        &mut parent_number,
+        &mut parent_timestamp,
+        Some(gas_limit),
+        vec![raw],
+        beneficiary,
+    )
+    .await?;
+
+    let receipt = EthApiClient::<
+        TransactionRequest,
+        Transaction,
+        Block,
+        Receipt,
+        Header,
+        Bytes,
+    >::transaction_receipt(&env.node_clients[0].rpc, tx_hash)
+    .await?
+    .expect("system transaction should be included");
+    let block = env.node_clients[0]
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await?
+        .expect("latest block should exist");
+    let base_fee = block.header.inner.base_fee_per_gas.unwrap_or_default() as u128;
+    let gas_used = U256::from(receipt.gas_used);
+    let fee = gas_used * U256::from(receipt.effective_gas_price);
+    let tip = gas_used * U256::from(receipt.effective_gas_price - base_fee);
+
+    let outcome = SystemTxOutcome {
+        fee,
+        tip,
+        sender_spent: sender_before - balance(&env, system_sender.address()).await?,
+        fee_account_spent: fee_account_before - balance(&env, fee_account).await?,
+        beneficiary_earned: balance(&env, beneficiary).await? - beneficiary_before,
+    };
+
+    drop(setup);
+
+    Ok(outcome)
+}
+
+/// Tests that the `waived` policy lets system senders transact without paying gas and
+/// without crediting the beneficiary.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_e2e_system_fee_waived() -> Result<()> {
+    let outcome = run_system_tx("waived").await?;
+
+    assert!(!outcome.fee.is_zero(), "test transaction should use gas");
+    assert_eq!(outcome.sender_spent, U256::ZERO);
+    assert_eq!(outcome.fee_account_spent, U256::ZERO);
+    assert_eq!(outcome.beneficiary_earned, U256::ZERO);
+
+    Ok(())
+}
+
+/// Tests that the `charged` policy bills the configured system account instead of the
+/// sender and pays the beneficiary as usual.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_e2e_system_fee_charged_to_system_account() -> Result<()> {
+    let outcome = run_system_tx("charged").await?;
+
+    assert_eq!(outcome.sender_spent, U256::ZERO);
+    assert_eq!(outcome.fee_account_spent, outcome.fee);
+    assert_eq!(outcome.beneficiary_earned, outcome.tip);
+
+    Ok(())
+}
+
+/// Tests that the `burned` policy charges the sender but pays nothing to the beneficiary.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_e2e_system_fee_burned() -> Result<()> {
+    let outcome = run_system_tx("burned").await?;
+
+    assert_eq!(outcome.sender_spent, outcome.fee);
+    assert_eq!(outcome.fee_account_spent, U256::ZERO);
+    assert_eq!(outcome.beneficiary_earned, U256::ZERO);
+
+    Ok(())
+}